@@ -0,0 +1,106 @@
+//! Spend-rate limiting (`budget.max_usd_per_hour`).
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Window over which spend is measured.
+const WINDOW: Duration = Duration::from_secs(3600);
+
+/// Rolling record of iteration costs over the last hour.
+#[derive(Debug, Default)]
+pub struct SpendTracker {
+    /// `(when, usd)` per result event, oldest first.
+    entries: VecDeque<(Instant, f64)>,
+}
+
+impl SpendTracker {
+    /// Record a result event's cost.
+    pub fn record(&mut self, usd: f64) {
+        self.record_at(Instant::now(), usd);
+    }
+
+    fn record_at(&mut self, at: Instant, usd: f64) {
+        if usd > 0.0 {
+            self.entries.push_back((at, usd));
+        }
+    }
+
+    fn prune(&mut self, now: Instant) {
+        while let Some(&(at, _)) = self.entries.front() {
+            if now.saturating_duration_since(at) < WINDOW {
+                break;
+            }
+            self.entries.pop_front();
+        }
+    }
+
+    /// Total spend in the hour before `now`.
+    pub fn spent_last_hour(&mut self, now: Instant) -> f64 {
+        self.prune(now);
+        self.entries.iter().map(|&(_, usd)| usd).sum()
+    }
+
+    /// How long to wait before the next iteration so the rolling hourly spend
+    /// is back within `max_per_hour`, or `None` if it already is.
+    pub fn throttle_delay(&mut self, max_per_hour: f64, now: Instant) -> Option<Duration> {
+        let mut spent = self.spent_last_hour(now);
+        if spent <= max_per_hour {
+            return None;
+        }
+        // Wait for the oldest entries to age out until the rest fit the cap
+        for &(at, usd) in &self.entries {
+            spent -= usd;
+            if spent <= max_per_hour {
+                return Some((at + WINDOW).saturating_duration_since(now));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minutes(m: u64) -> Duration {
+        Duration::from_secs(m * 60)
+    }
+
+    #[test]
+    fn spend_outside_the_window_is_forgotten() {
+        let start = Instant::now();
+        let mut t = SpendTracker::default();
+        t.record_at(start, 2.0);
+        t.record_at(start + minutes(30), 1.0);
+        assert_eq!(t.spent_last_hour(start + minutes(59)), 3.0);
+        assert_eq!(t.spent_last_hour(start + minutes(61)), 1.0);
+    }
+
+    #[test]
+    fn no_delay_within_cap() {
+        let start = Instant::now();
+        let mut t = SpendTracker::default();
+        t.record_at(start, 4.0);
+        assert_eq!(t.throttle_delay(5.0, start + minutes(1)), None);
+    }
+
+    #[test]
+    fn delay_lasts_until_enough_spend_ages_out() {
+        let start = Instant::now();
+        let mut t = SpendTracker::default();
+        t.record_at(start, 3.0);
+        t.record_at(start + minutes(10), 3.0);
+        t.record_at(start + minutes(20), 3.0);
+        // $9 in the last hour against a $5 cap: the first two entries must expire
+        let now = start + minutes(30);
+        assert_eq!(t.throttle_delay(5.0, now), Some(minutes(40)));
+        assert_eq!(t.throttle_delay(5.0, start + minutes(70)), None);
+    }
+
+    #[test]
+    fn zero_cost_results_are_ignored() {
+        let mut t = SpendTracker::default();
+        t.record(0.0);
+        assert_eq!(t.spent_last_hour(Instant::now()), 0.0);
+    }
+}
@@ -0,0 +1,187 @@
+//! Time source for run-loop timing.
+//!
+//! Timers, throttles and quiet hours read the time through a [`Clock`] so
+//! tests (and replays) can drive them with a [`ManualClock`] instead of
+//! sleeping.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+pub const MINUTES_PER_DAY: u16 = 24 * 60;
+const SECONDS_PER_DAY: u64 = MINUTES_PER_DAY as u64 * 60;
+
+/// Where the current time comes from.
+pub trait Clock: Send + Sync {
+    /// Monotonic now, for timers and deadlines.
+    fn now(&self) -> Instant;
+
+    /// Minutes since local midnight, for daily windows.
+    fn minute_of_day(&self) -> u16;
+
+    /// Time since `earlier`, zero if `earlier` is in the future.
+    fn elapsed(&self, earlier: Instant) -> Duration {
+        self.now().saturating_duration_since(earlier)
+    }
+}
+
+/// The real wall clock.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn minute_of_day(&self) -> u16 {
+        local_minute_of_day()
+    }
+}
+
+/// A clock that only moves when told to.
+#[derive(Debug)]
+pub struct ManualClock {
+    start: Instant,
+    /// Offset from `start`, and seconds since midnight.
+    state: Mutex<(Duration, u64)>,
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ManualClock {
+    /// Start at the real current instant and midnight.
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            state: Mutex::new((Duration::ZERO, 0)),
+        }
+    }
+
+    /// Move time forward by `by`, carrying the time of day along.
+    pub fn advance(&self, by: Duration) {
+        let mut state = self.state.lock().unwrap_or_else(|p| p.into_inner());
+        state.0 += by;
+        state.1 = (state.1 + by.as_secs()) % SECONDS_PER_DAY;
+    }
+
+    /// Jump the time of day to `minute` since midnight (monotonic time is unchanged).
+    pub fn set_minute_of_day(&self, minute: u16) {
+        self.state.lock().unwrap_or_else(|p| p.into_inner()).1 =
+            u64::from(minute % MINUTES_PER_DAY) * 60;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.start + self.state.lock().unwrap_or_else(|p| p.into_inner()).0
+    }
+
+    fn minute_of_day(&self) -> u16 {
+        (self.state.lock().unwrap_or_else(|p| p.into_inner()).1 / 60) as u16
+    }
+}
+
+/// Minutes since local midnight.
+#[cfg(unix)]
+fn local_minute_of_day() -> u16 {
+    // SAFETY: `time` with a null pointer only returns the current time, and
+    // `localtime_r` writes into the zeroed `tm` we own.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    let ok = unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        !libc::localtime_r(&now, &mut tm).is_null()
+    };
+    if !ok {
+        return utc_minute_of_day();
+    }
+    (tm.tm_hour * 60 + tm.tm_min) as u16
+}
+
+#[cfg(not(unix))]
+fn local_minute_of_day() -> u16 {
+    utc_minute_of_day()
+}
+
+fn utc_minute_of_day() -> u16 {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    ((secs / 60) % MINUTES_PER_DAY as u64) as u16
+}
+
+/// Formats a duration as M:SS (under 1 hour) or H:MM:SS (1+ hours).
+pub fn format_elapsed(duration: Duration) -> String {
+    let total_secs = duration.as_secs();
+    let hours = total_secs / 3600;
+    let minutes = (total_secs % 3600) / 60;
+    let seconds = total_secs % 60;
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manual_clock_moves_only_when_advanced() {
+        let clock = ManualClock::new();
+        let t0 = clock.now();
+        assert_eq!(clock.now(), t0);
+
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(clock.elapsed(t0), Duration::from_secs(90));
+        assert_eq!(clock.minute_of_day(), 1);
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(clock.minute_of_day(), 2);
+        assert_eq!(
+            clock.elapsed(clock.now() + Duration::from_secs(1)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn manual_clock_time_of_day_wraps() {
+        let clock = ManualClock::new();
+        clock.set_minute_of_day(23 * 60 + 30);
+        clock.advance(Duration::from_secs(45 * 60));
+        assert_eq!(clock.minute_of_day(), 15);
+    }
+
+    #[test]
+    fn test_format_elapsed_zero() {
+        assert_eq!(format_elapsed(Duration::from_secs(0)), "0:00");
+    }
+
+    #[test]
+    fn test_format_elapsed_seconds_only() {
+        assert_eq!(format_elapsed(Duration::from_secs(5)), "0:05");
+        assert_eq!(format_elapsed(Duration::from_secs(45)), "0:45");
+        assert_eq!(format_elapsed(Duration::from_secs(59)), "0:59");
+    }
+
+    #[test]
+    fn test_format_elapsed_minutes_and_seconds() {
+        assert_eq!(format_elapsed(Duration::from_secs(60)), "1:00");
+        assert_eq!(format_elapsed(Duration::from_secs(65)), "1:05");
+        assert_eq!(format_elapsed(Duration::from_secs(125)), "2:05");
+        assert_eq!(format_elapsed(Duration::from_secs(3599)), "59:59");
+    }
+
+    #[test]
+    fn test_format_elapsed_hours() {
+        assert_eq!(format_elapsed(Duration::from_secs(3600)), "1:00:00");
+        assert_eq!(format_elapsed(Duration::from_secs(3661)), "1:01:01");
+        assert_eq!(format_elapsed(Duration::from_secs(7325)), "2:02:05");
+        assert_eq!(format_elapsed(Duration::from_secs(36000)), "10:00:00");
+    }
+}
@@ -0,0 +1,1676 @@
+//! Configuration types: the global `config.toml`, per-project overrides
+//! (`PartialConfig`), and how the two merge.
+//!
+//! Finding, reading and saving the files is the binary's job; this module
+//! only knows their shape.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::schedule::QuietHours;
+
+/// Claude CLI configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClaudeConfig {
+    pub path: String,
+    /// Legacy field - ignored on load, not serialized.
+    /// CLI args are hardcoded in main.rs as Ralph depends on specific args for streaming.
+    #[serde(skip_serializing, default)]
+    #[allow(dead_code)]
+    pub args: Option<String>,
+    /// Run Claude with its output on a pseudo-terminal instead of pipes, so
+    /// tools that check for a TTY behave as they do interactively. Escape
+    /// sequences are stripped before lines are parsed. Default: false.
+    pub pty: bool,
+}
+
+impl Default for ClaudeConfig {
+    fn default() -> Self {
+        Self {
+            path: "~/.claude/local/claude".to_string(),
+            args: None,
+            pty: false,
+        }
+    }
+}
+
+/// Logging configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    pub level: String,
+    /// Minutes to log at `debug` after the run errors or Claude's output
+    /// fails to parse repeatedly, before going back to `level`. `0` disables.
+    /// Default: 10.
+    pub debug_on_error_minutes: u64,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            debug_on_error_minutes: 10,
+        }
+    }
+}
+
+/// Behavior configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BehaviorConfig {
+    /// Number of iterations to run:
+    /// - Negative (-1): Infinite mode, continues until user stops or all beads complete
+    /// - Zero (0): Stopped mode, pressing 's' has no effect
+    /// - Positive (N): Runs exactly N iterations then stops
+    pub iterations: i32,
+    /// Whether to acquire a wake lock to prevent display and system idle sleep.
+    /// When true, the system won't sleep while claude is running. Default: true.
+    pub keep_awake: bool,
+    /// Path to the `bd` CLI binary. Default: "bd".
+    pub bd_path: String,
+    /// How often to send agent heartbeats (seconds). Default: 30.
+    pub heartbeat_interval: u64,
+    /// How long before an agent is considered stale (seconds). Default: 180.
+    pub stale_threshold: u64,
+    /// Number of concurrent Claude Code workers to spawn on S press. Default: 1.
+    pub workers: u32,
+    /// Continue the previous iteration's Claude conversation (`--resume`)
+    /// instead of starting a fresh one. The first iteration of a run always
+    /// starts fresh. Default: false.
+    pub resume_conversation: bool,
+    /// Copy existing files before Claude's Write/Edit calls change them, for
+    /// restoring with `b` in the workers stream. Default: false.
+    pub backup_files: bool,
+    /// Ask before starting a run while the project's git tree has uncommitted
+    /// changes (stash, continue or cancel). When false, only warn. Default: true.
+    pub confirm_dirty_start: bool,
+    /// Seconds a stopped run gets to write its final result and exit after
+    /// the interrupt (SIGINT) before it is killed. Default: 5.
+    pub stop_grace_secs: u64,
+    /// Seconds a single iteration may run before it is killed; 0 for no
+    /// limit. Default: 0.
+    pub iteration_timeout_secs: u64,
+    /// After an iteration times out, carry on with the next one instead of
+    /// entering the error state. Default: false.
+    pub continue_after_timeout: bool,
+    /// Hold auto-continue when an iteration ends with a question for the
+    /// user, until it is answered or skipped. Default: true.
+    pub pause_on_question: bool,
+}
+
+impl Default for BehaviorConfig {
+    fn default() -> Self {
+        Self {
+            iterations: -1,   // Infinite mode by default
+            keep_awake: true, // Prevent system sleep by default
+            bd_path: "bd".to_string(),
+            heartbeat_interval: 30,
+            stale_threshold: 180,
+            workers: 1,
+            resume_conversation: false,
+            backup_files: false,
+            confirm_dirty_start: true,
+            stop_grace_secs: 5,
+            iteration_timeout_secs: 0,
+            continue_after_timeout: false,
+            pause_on_question: true,
+        }
+    }
+}
+
+/// Output display configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// Lines of each tool result shown in the output stream. Default: 3.
+    /// Adjustable at runtime with `+`/`-` in the workers view.
+    pub result_preview_lines: usize,
+    /// Max display width of file paths and patterns in tool calls. Default: 60.
+    pub tool_input_width: usize,
+    /// Max display width of Bash commands in tool calls. Default: 50.
+    pub bash_command_width: usize,
+    /// Regex → style rules coloring matches in tool result previews
+    /// (e.g. `'warning:' = "yellow"`). Defaults flag rustc errors, warnings and panics.
+    pub highlights: BTreeMap<String, String>,
+    /// Minutes without keyboard input before the screen dims or blanks
+    /// (the run keeps going). `0` disables. Default: 0.
+    pub idle_minutes: u64,
+    /// What happens after `idle_minutes`: `"dim"` or `"blank"`. Default: dim.
+    pub idle_style: IdleStyle,
+    /// Failed tool results in one iteration at which the iteration is flagged
+    /// as a likely environment problem. `0` disables. Default: 5.
+    pub tool_error_threshold: u32,
+    /// Digit grouping separator in token counts and costs. Default: `","`.
+    pub thousands_separator: String,
+    /// Decimal separator in costs and averages. Default: `"."`.
+    pub decimal_separator: String,
+    /// Currency symbol shown with costs (always USD amounts). Default: `"$"`.
+    pub currency_symbol: String,
+    /// Whether the currency symbol goes `"before"` or `"after"` the amount. Default: before.
+    pub currency_position: CurrencyPosition,
+    /// Colors the terminal can show: `"auto"` (detect from `COLORTERM`/`TERM`),
+    /// `"truecolor"`, `"256"` or `"16"`. Default: auto.
+    pub color_depth: ColorDepth,
+    /// Color scheme: `"default"`, `"colorblind"` or `"high-contrast"`. Default: default.
+    pub theme: Theme,
+    /// Iteration divider and run banners (`[display.banners]`).
+    pub banners: BannersConfig,
+}
+
+/// Lines marking iterations and run state changes in the output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BannersConfig {
+    /// How banners are framed: `"heavy"` (`══ DONE ══`), `"plain"`
+    /// (`== DONE ==`) or `"bare"` (`DONE`). Default: heavy.
+    pub style: BannerStyle,
+    /// Line between iterations. `""` omits it. Default: 40 `─`.
+    pub divider: String,
+    /// Banner when the next iteration starts on its own. `""` omits it.
+    /// Default: `"AUTO-CONTINUING"`.
+    pub auto_continue: String,
+    /// Banner when no work is left. Default: the work source's message.
+    pub complete: Option<String>,
+}
+
+impl Default for BannersConfig {
+    fn default() -> Self {
+        Self {
+            style: BannerStyle::default(),
+            divider: "\u{2500}".repeat(40),
+            auto_continue: "AUTO-CONTINUING".to_string(),
+            complete: None,
+        }
+    }
+}
+
+impl BannersConfig {
+    /// `text` framed in the configured style.
+    pub fn banner(&self, text: &str) -> String {
+        match self.style {
+            BannerStyle::Heavy => {
+                let rule = "\u{2550}".repeat(18);
+                format!("{rule} {text} {rule}")
+            }
+            BannerStyle::Plain => format!("== {text} =="),
+            BannerStyle::Bare => text.to_string(),
+        }
+    }
+}
+
+/// Frame around banner text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BannerStyle {
+    /// Box-drawing rules on both sides.
+    #[default]
+    Heavy,
+    /// ASCII `==` on both sides, for pasting into issues and chat.
+    Plain,
+    /// The text alone.
+    Bare,
+}
+
+/// Idle screen treatment, for avoiding OLED burn-in on long runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdleStyle {
+    /// Keep the layout but render everything dimmed.
+    #[default]
+    Dim,
+    /// Clear the screen except for a small status line that drifts around.
+    Blank,
+}
+
+/// Side of the amount the currency symbol is written on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CurrencyPosition {
+    /// `$1.50`
+    #[default]
+    Before,
+    /// `1,50 €` (separated by a space)
+    After,
+}
+
+/// Terminal color capability; colors beyond it are mapped to the nearest it has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorDepth {
+    /// Detect at startup.
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    /// 24-bit RGB.
+    #[serde(rename = "truecolor")]
+    TrueColor,
+    /// The xterm 256-color palette.
+    #[serde(rename = "256")]
+    Ansi256,
+    /// The 16 ANSI colors.
+    #[serde(rename = "16")]
+    Ansi16,
+}
+
+/// Color scheme applied to everything drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    #[default]
+    Default,
+    /// Okabe-Ito colors that stay apart with deuteranopia and protanopia.
+    Colorblind,
+    /// Bright colors, with dim text raised to full brightness.
+    HighContrast,
+}
+
+impl ColorDepth {
+    /// This depth, or `detected` when set to auto.
+    pub fn or_detected(self, detected: ColorDepth) -> ColorDepth {
+        match self {
+            ColorDepth::Auto => detected,
+            depth => depth,
+        }
+    }
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            result_preview_lines: 3,
+            tool_input_width: 60,
+            bash_command_width: 50,
+            highlights: default_highlights(),
+            idle_minutes: 0,
+            idle_style: IdleStyle::default(),
+            tool_error_threshold: 5,
+            thousands_separator: ",".to_string(),
+            decimal_separator: ".".to_string(),
+            currency_symbol: "$".to_string(),
+            currency_position: CurrencyPosition::default(),
+            color_depth: ColorDepth::default(),
+            theme: Theme::default(),
+            banners: BannersConfig::default(),
+        }
+    }
+}
+
+fn default_highlights() -> BTreeMap<String, String> {
+    [
+        (r"error(\[E\d+\])?:", "red"),
+        (r"warning:", "yellow"),
+        (r"panicked at", "red bold"),
+    ]
+    .into_iter()
+    .map(|(pattern, style)| (pattern.to_string(), style.to_string()))
+    .collect()
+}
+
+/// Time-of-day scheduling.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScheduleConfig {
+    /// Daily local-time window (`"23:00-07:00"`) in which finished iterations
+    /// don't auto-continue; the loop resumes when it ends. Default: none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<String>,
+}
+
+impl ScheduleConfig {
+    /// Parsed quiet hours, if configured.
+    pub fn quiet_hours(&self) -> Result<Option<QuietHours>, String> {
+        self.quiet_hours.as_deref().map(str::parse).transpose()
+    }
+
+    /// Whether `minute` (since local midnight) is inside valid quiet hours.
+    pub fn in_quiet_hours_at(&self, minute: u16) -> bool {
+        matches!(self.quiet_hours(), Ok(Some(q)) if q.contains(minute))
+    }
+}
+
+/// Spend limits.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BudgetConfig {
+    /// Rolling hourly spend (USD, from Claude's result events) above which
+    /// auto-continue waits before starting the next iteration. Default: none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_usd_per_hour: Option<f64>,
+}
+
+/// Claude CLI control protocol (`control_request` / `control_response`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ControlConfig {
+    /// Drive Claude over stream-json stdin so ralph can answer permission
+    /// prompts and interrupt a run instead of killing it. Default: false.
+    pub enabled: bool,
+    /// Tool names whose permission prompts are approved automatically (`"*"`
+    /// approves everything); other prompts are denied. Default: none.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub auto_approve: Vec<String>,
+}
+
+/// Proxy settings for the Claude CLI and any HTTP ralph does itself.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Proxy URL for HTTPS traffic (e.g. `http://proxy.corp:3128`). Default: none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub https_proxy: Option<String>,
+    /// Comma-separated hosts that bypass the proxy. Default: none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<String>,
+}
+
+impl NetworkConfig {
+    /// Environment variables to set on child processes. Both spellings are
+    /// set because tools disagree on which one they read.
+    pub fn child_env(&self) -> Vec<(&'static str, String)> {
+        let mut env = Vec::new();
+        if let Some(proxy) = &self.https_proxy {
+            env.push(("HTTPS_PROXY", proxy.clone()));
+            env.push(("https_proxy", proxy.clone()));
+        }
+        if let Some(no_proxy) = &self.no_proxy {
+            env.push(("NO_PROXY", no_proxy.clone()));
+            env.push(("no_proxy", no_proxy.clone()));
+        }
+        env
+    }
+}
+
+/// Metrics export without any network code.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    /// Prometheus textfile-collector file rewritten after every iteration
+    /// (e.g. `/var/lib/node_exporter/textfile_collector/ralph.prom`). `~` is
+    /// expanded. Default: none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub textfile: Option<String>,
+}
+
+/// Issue tracker integration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrackerConfig {
+    /// When a run ends in error, comment on the issue linked to the spec being
+    /// worked (the bead's `external_ref`) with the failure, the last tool
+    /// errors and the transcript path. Posted with the `gh` CLI. Default: false.
+    pub comment_on_failure: bool,
+}
+
+/// Encryption at rest.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncryptionConfig {
+    /// age recipients (`age1…` or `ssh-…` public keys, or paths to recipients
+    /// files) that transcripts, reports and exports are encrypted to with the
+    /// `age` CLI. Default: none (written unencrypted).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub age_recipients: Vec<String>,
+}
+
+/// Shell commands run around each iteration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    /// Run with `sh -c` in the worker's directory before Claude starts; a
+    /// non-zero exit aborts the iteration. Default: none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_iteration: Option<String>,
+    /// Run the same way after each iteration ends, before the next one
+    /// starts. A failure is reported but the run carries on. Default: none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_iteration: Option<String>,
+    /// Seconds either command may run before it is killed and counted as
+    /// failed. Default: 1800.
+    pub timeout_secs: u64,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            before_iteration: None,
+            after_iteration: None,
+            timeout_secs: 1800,
+        }
+    }
+}
+
+/// Terminal input handling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeysConfig {
+    /// Capture mouse events. Turn off to select and copy text with the mouse
+    /// as in any other terminal program. Default: true.
+    pub mouse_capture: bool,
+}
+
+impl Default for KeysConfig {
+    fn default() -> Self {
+        Self {
+            mouse_capture: true,
+        }
+    }
+}
+
+/// Disk safety limits checked before each auto-continued iteration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiskConfig {
+    /// Free space (MB) on the project's filesystem below which auto-continue
+    /// pauses. `0` disables. Default: 1024.
+    pub min_free_mb: u64,
+    /// Growth (MB) of the project directory since the run started above
+    /// which auto-continue pauses. `0` disables. Default: 0.
+    pub max_growth_mb: u64,
+}
+
+impl Default for DiskConfig {
+    fn default() -> Self {
+        Self {
+            min_free_mb: 1024,
+            max_growth_mb: 0,
+        }
+    }
+}
+
+/// Upper bound for `display.result_preview_lines` (also caps retained result text).
+pub const MAX_RESULT_PREVIEW_LINES: usize = 100;
+
+/// Narrowest allowed tool-call truncation width.
+const MIN_TOOL_INPUT_WIDTH: usize = 10;
+
+/// Main application configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Config {
+    #[serde(default)]
+    pub claude: ClaudeConfig,
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    #[serde(default)]
+    pub behavior: BehaviorConfig,
+    #[serde(default)]
+    pub display: DisplayConfig,
+    #[serde(default)]
+    pub schedule: ScheduleConfig,
+    #[serde(default)]
+    pub budget: BudgetConfig,
+    #[serde(default)]
+    pub control: ControlConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub disk: DiskConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub tracker: TrackerConfig,
+    #[serde(default)]
+    pub encryption: EncryptionConfig,
+    #[serde(default)]
+    pub hooks: HooksConfig,
+    #[serde(default)]
+    pub keys: KeysConfig,
+}
+
+impl Config {
+    /// Expand `~` to home directory in a path string
+    pub fn expand_tilde(path: &str) -> PathBuf {
+        if let Some(stripped) = path.strip_prefix("~/")
+            && let Some(home) = dirs::home_dir()
+        {
+            return home.join(stripped);
+        }
+        PathBuf::from(path)
+    }
+
+    /// Get the expanded Claude CLI path
+    pub fn claude_path(&self) -> PathBuf {
+        Self::expand_tilde(&self.claude.path)
+    }
+}
+
+/// Partial Claude CLI configuration for project overrides.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialClaudeConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pty: Option<bool>,
+}
+
+/// Partial logging configuration for project overrides.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialLoggingConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debug_on_error_minutes: Option<u64>,
+}
+
+/// Partial behavior configuration for project overrides.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialBehaviorConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iterations: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep_awake: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bd_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heartbeat_interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale_threshold: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workers: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resume_conversation: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup_files: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirm_dirty_start: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_grace_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iteration_timeout_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continue_after_timeout: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pause_on_question: Option<bool>,
+}
+
+/// Partial display configuration for project overrides.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialDisplayConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result_preview_lines: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_input_width: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bash_command_width: Option<usize>,
+    /// Replaces the default rules entirely when set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlights: Option<BTreeMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idle_minutes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idle_style: Option<IdleStyle>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_error_threshold: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thousands_separator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimal_separator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency_symbol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency_position: Option<CurrencyPosition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color_depth: Option<ColorDepth>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<Theme>,
+    #[serde(skip_serializing_if = "is_partial_banners_empty")]
+    pub banners: PartialBannersConfig,
+}
+
+/// Partial banner configuration for project overrides.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialBannersConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<BannerStyle>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub divider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_continue: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub complete: Option<String>,
+}
+
+/// Partial budget configuration for project overrides.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialBudgetConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_usd_per_hour: Option<f64>,
+}
+
+/// Partial control protocol configuration for project overrides.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialControlConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_approve: Option<Vec<String>>,
+}
+
+/// Partial network configuration for project overrides.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialNetworkConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub https_proxy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<String>,
+}
+
+/// Partial disk limits for project overrides.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialDiskConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_free_mb: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_growth_mb: Option<u64>,
+}
+
+/// Partial metrics configuration for project overrides.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialMetricsConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub textfile: Option<String>,
+}
+
+/// Partial tracker configuration for project overrides.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialTrackerConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment_on_failure: Option<bool>,
+}
+
+/// Partial encryption configuration for project overrides.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialEncryptionConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_recipients: Option<Vec<String>>,
+}
+
+/// Partial hooks configuration for project overrides.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialHooksConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_iteration: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_iteration: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Partial keys configuration for project overrides.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialKeysConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mouse_capture: Option<bool>,
+}
+
+/// Partial schedule configuration for project overrides.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialScheduleConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<String>,
+}
+
+/// Project-specific configuration where every field is optional.
+/// Fields that are `None` inherit from compiled-in defaults.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PartialConfig {
+    /// Another config file to inherit from. `~` is expanded and relative
+    /// paths resolve against the directory of the file that names it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    #[serde(skip_serializing_if = "is_partial_claude_empty")]
+    pub claude: PartialClaudeConfig,
+    #[serde(skip_serializing_if = "is_partial_logging_empty")]
+    pub logging: PartialLoggingConfig,
+    #[serde(skip_serializing_if = "is_partial_behavior_empty")]
+    pub behavior: PartialBehaviorConfig,
+    #[serde(skip_serializing_if = "is_partial_display_empty")]
+    pub display: PartialDisplayConfig,
+    #[serde(skip_serializing_if = "is_partial_schedule_empty")]
+    pub schedule: PartialScheduleConfig,
+    #[serde(skip_serializing_if = "is_partial_budget_empty")]
+    pub budget: PartialBudgetConfig,
+    #[serde(skip_serializing_if = "is_partial_control_empty")]
+    pub control: PartialControlConfig,
+    #[serde(skip_serializing_if = "is_partial_network_empty")]
+    pub network: PartialNetworkConfig,
+    #[serde(skip_serializing_if = "is_partial_disk_empty")]
+    pub disk: PartialDiskConfig,
+    #[serde(skip_serializing_if = "is_partial_metrics_empty")]
+    pub metrics: PartialMetricsConfig,
+    #[serde(skip_serializing_if = "is_partial_tracker_empty")]
+    pub tracker: PartialTrackerConfig,
+    #[serde(skip_serializing_if = "is_partial_encryption_empty")]
+    pub encryption: PartialEncryptionConfig,
+    #[serde(skip_serializing_if = "is_partial_hooks_empty")]
+    pub hooks: PartialHooksConfig,
+    #[serde(skip_serializing_if = "is_partial_keys_empty")]
+    pub keys: PartialKeysConfig,
+}
+
+fn is_partial_claude_empty(c: &PartialClaudeConfig) -> bool {
+    c.path.is_none() && c.pty.is_none()
+}
+
+fn is_partial_logging_empty(l: &PartialLoggingConfig) -> bool {
+    l.level.is_none() && l.debug_on_error_minutes.is_none()
+}
+
+fn is_partial_behavior_empty(b: &PartialBehaviorConfig) -> bool {
+    b.iterations.is_none()
+        && b.keep_awake.is_none()
+        && b.bd_path.is_none()
+        && b.heartbeat_interval.is_none()
+        && b.stale_threshold.is_none()
+        && b.workers.is_none()
+        && b.resume_conversation.is_none()
+        && b.backup_files.is_none()
+        && b.confirm_dirty_start.is_none()
+        && b.stop_grace_secs.is_none()
+        && b.iteration_timeout_secs.is_none()
+        && b.continue_after_timeout.is_none()
+        && b.pause_on_question.is_none()
+}
+
+fn is_partial_display_empty(d: &PartialDisplayConfig) -> bool {
+    d.result_preview_lines.is_none()
+        && d.tool_input_width.is_none()
+        && d.bash_command_width.is_none()
+        && d.highlights.is_none()
+        && d.idle_minutes.is_none()
+        && d.idle_style.is_none()
+        && d.tool_error_threshold.is_none()
+        && d.thousands_separator.is_none()
+        && d.decimal_separator.is_none()
+        && d.currency_symbol.is_none()
+        && d.currency_position.is_none()
+        && d.color_depth.is_none()
+        && d.theme.is_none()
+        && is_partial_banners_empty(&d.banners)
+}
+
+fn is_partial_banners_empty(b: &PartialBannersConfig) -> bool {
+    b.style.is_none() && b.divider.is_none() && b.auto_continue.is_none() && b.complete.is_none()
+}
+
+fn is_partial_schedule_empty(s: &PartialScheduleConfig) -> bool {
+    s.quiet_hours.is_none()
+}
+
+fn is_partial_budget_empty(b: &PartialBudgetConfig) -> bool {
+    b.max_usd_per_hour.is_none()
+}
+
+fn is_partial_control_empty(c: &PartialControlConfig) -> bool {
+    c.enabled.is_none() && c.auto_approve.is_none()
+}
+
+fn is_partial_network_empty(n: &PartialNetworkConfig) -> bool {
+    n.https_proxy.is_none() && n.no_proxy.is_none()
+}
+
+fn is_partial_disk_empty(d: &PartialDiskConfig) -> bool {
+    d.min_free_mb.is_none() && d.max_growth_mb.is_none()
+}
+
+fn is_partial_metrics_empty(m: &PartialMetricsConfig) -> bool {
+    m.textfile.is_none()
+}
+
+fn is_partial_tracker_empty(t: &PartialTrackerConfig) -> bool {
+    t.comment_on_failure.is_none()
+}
+
+fn is_partial_encryption_empty(e: &PartialEncryptionConfig) -> bool {
+    e.age_recipients.is_none()
+}
+
+fn is_partial_hooks_empty(h: &PartialHooksConfig) -> bool {
+    h.before_iteration.is_none() && h.after_iteration.is_none() && h.timeout_secs.is_none()
+}
+
+fn is_partial_keys_empty(k: &PartialKeysConfig) -> bool {
+    k.mouse_capture.is_none()
+}
+
+/// Merge a base config with a project-level partial config.
+/// Project values override base values where present.
+pub fn merge_config(global: &Config, project: &PartialConfig) -> Config {
+    Config {
+        claude: ClaudeConfig {
+            path: project
+                .claude
+                .path
+                .clone()
+                .unwrap_or_else(|| global.claude.path.clone()),
+            args: None,
+            pty: project.claude.pty.unwrap_or(global.claude.pty),
+        },
+        logging: LoggingConfig {
+            level: project
+                .logging
+                .level
+                .clone()
+                .unwrap_or_else(|| global.logging.level.clone()),
+            debug_on_error_minutes: project
+                .logging
+                .debug_on_error_minutes
+                .unwrap_or(global.logging.debug_on_error_minutes),
+        },
+        behavior: BehaviorConfig {
+            iterations: project
+                .behavior
+                .iterations
+                .unwrap_or(global.behavior.iterations),
+            keep_awake: project
+                .behavior
+                .keep_awake
+                .unwrap_or(global.behavior.keep_awake),
+            bd_path: project
+                .behavior
+                .bd_path
+                .clone()
+                .unwrap_or_else(|| global.behavior.bd_path.clone()),
+            heartbeat_interval: project
+                .behavior
+                .heartbeat_interval
+                .unwrap_or(global.behavior.heartbeat_interval),
+            stale_threshold: project
+                .behavior
+                .stale_threshold
+                .unwrap_or(global.behavior.stale_threshold),
+            workers: project
+                .behavior
+                .workers
+                .unwrap_or(global.behavior.workers)
+                .max(1),
+            resume_conversation: project
+                .behavior
+                .resume_conversation
+                .unwrap_or(global.behavior.resume_conversation),
+            backup_files: project
+                .behavior
+                .backup_files
+                .unwrap_or(global.behavior.backup_files),
+            confirm_dirty_start: project
+                .behavior
+                .confirm_dirty_start
+                .unwrap_or(global.behavior.confirm_dirty_start),
+            stop_grace_secs: project
+                .behavior
+                .stop_grace_secs
+                .unwrap_or(global.behavior.stop_grace_secs),
+            iteration_timeout_secs: project
+                .behavior
+                .iteration_timeout_secs
+                .unwrap_or(global.behavior.iteration_timeout_secs),
+            continue_after_timeout: project
+                .behavior
+                .continue_after_timeout
+                .unwrap_or(global.behavior.continue_after_timeout),
+            pause_on_question: project
+                .behavior
+                .pause_on_question
+                .unwrap_or(global.behavior.pause_on_question),
+        },
+        display: DisplayConfig {
+            result_preview_lines: project
+                .display
+                .result_preview_lines
+                .unwrap_or(global.display.result_preview_lines)
+                .min(MAX_RESULT_PREVIEW_LINES),
+            tool_input_width: project
+                .display
+                .tool_input_width
+                .unwrap_or(global.display.tool_input_width)
+                .max(MIN_TOOL_INPUT_WIDTH),
+            bash_command_width: project
+                .display
+                .bash_command_width
+                .unwrap_or(global.display.bash_command_width)
+                .max(MIN_TOOL_INPUT_WIDTH),
+            highlights: project
+                .display
+                .highlights
+                .clone()
+                .unwrap_or_else(|| global.display.highlights.clone()),
+            idle_minutes: project
+                .display
+                .idle_minutes
+                .unwrap_or(global.display.idle_minutes),
+            idle_style: project
+                .display
+                .idle_style
+                .unwrap_or(global.display.idle_style),
+            tool_error_threshold: project
+                .display
+                .tool_error_threshold
+                .unwrap_or(global.display.tool_error_threshold),
+            thousands_separator: project
+                .display
+                .thousands_separator
+                .clone()
+                .unwrap_or_else(|| global.display.thousands_separator.clone()),
+            decimal_separator: project
+                .display
+                .decimal_separator
+                .clone()
+                .unwrap_or_else(|| global.display.decimal_separator.clone()),
+            currency_symbol: project
+                .display
+                .currency_symbol
+                .clone()
+                .unwrap_or_else(|| global.display.currency_symbol.clone()),
+            currency_position: project
+                .display
+                .currency_position
+                .unwrap_or(global.display.currency_position),
+            color_depth: project
+                .display
+                .color_depth
+                .unwrap_or(global.display.color_depth),
+            theme: project.display.theme.unwrap_or(global.display.theme),
+            banners: BannersConfig {
+                style: project
+                    .display
+                    .banners
+                    .style
+                    .unwrap_or(global.display.banners.style),
+                divider: project
+                    .display
+                    .banners
+                    .divider
+                    .clone()
+                    .unwrap_or_else(|| global.display.banners.divider.clone()),
+                auto_continue: project
+                    .display
+                    .banners
+                    .auto_continue
+                    .clone()
+                    .unwrap_or_else(|| global.display.banners.auto_continue.clone()),
+                complete: project
+                    .display
+                    .banners
+                    .complete
+                    .clone()
+                    .or_else(|| global.display.banners.complete.clone()),
+            },
+        },
+        schedule: ScheduleConfig {
+            quiet_hours: project
+                .schedule
+                .quiet_hours
+                .clone()
+                .or_else(|| global.schedule.quiet_hours.clone()),
+        },
+        budget: BudgetConfig {
+            max_usd_per_hour: project
+                .budget
+                .max_usd_per_hour
+                .or(global.budget.max_usd_per_hour)
+                .filter(|max| *max > 0.0),
+        },
+        control: ControlConfig {
+            enabled: project.control.enabled.unwrap_or(global.control.enabled),
+            auto_approve: project
+                .control
+                .auto_approve
+                .clone()
+                .unwrap_or_else(|| global.control.auto_approve.clone()),
+        },
+        network: NetworkConfig {
+            https_proxy: project
+                .network
+                .https_proxy
+                .clone()
+                .or_else(|| global.network.https_proxy.clone()),
+            no_proxy: project
+                .network
+                .no_proxy
+                .clone()
+                .or_else(|| global.network.no_proxy.clone()),
+        },
+        disk: DiskConfig {
+            min_free_mb: project.disk.min_free_mb.unwrap_or(global.disk.min_free_mb),
+            max_growth_mb: project
+                .disk
+                .max_growth_mb
+                .unwrap_or(global.disk.max_growth_mb),
+        },
+        metrics: MetricsConfig {
+            textfile: project
+                .metrics
+                .textfile
+                .clone()
+                .or_else(|| global.metrics.textfile.clone()),
+        },
+        tracker: TrackerConfig {
+            comment_on_failure: project
+                .tracker
+                .comment_on_failure
+                .unwrap_or(global.tracker.comment_on_failure),
+        },
+        encryption: EncryptionConfig {
+            age_recipients: project
+                .encryption
+                .age_recipients
+                .clone()
+                .unwrap_or_else(|| global.encryption.age_recipients.clone()),
+        },
+        // An empty command in the project config turns off a global hook
+        hooks: HooksConfig {
+            before_iteration: project
+                .hooks
+                .before_iteration
+                .clone()
+                .or_else(|| global.hooks.before_iteration.clone())
+                .filter(|command| !command.trim().is_empty()),
+            after_iteration: project
+                .hooks
+                .after_iteration
+                .clone()
+                .or_else(|| global.hooks.after_iteration.clone())
+                .filter(|command| !command.trim().is_empty()),
+            timeout_secs: project
+                .hooks
+                .timeout_secs
+                .unwrap_or(global.hooks.timeout_secs),
+        },
+        keys: KeysConfig {
+            mouse_capture: project
+                .keys
+                .mouse_capture
+                .unwrap_or(global.keys.mouse_capture),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = Config::default();
+        assert_eq!(config.claude.path, "~/.claude/local/claude");
+        assert!(config.claude.args.is_none());
+        assert_eq!(config.logging.level, "info");
+    }
+
+    #[test]
+    fn network_overrides_and_child_env() {
+        let partial: PartialConfig = toml::from_str(
+            r#"
+            [network]
+            https_proxy = "http://proxy.corp:3128"
+            "#,
+        )
+        .unwrap();
+        let merged = merge_config(&Config::default(), &partial);
+        assert_eq!(
+            merged.network.https_proxy.as_deref(),
+            Some("http://proxy.corp:3128")
+        );
+        assert!(merged.network.no_proxy.is_none());
+        assert_eq!(
+            merged.network.child_env(),
+            vec![
+                ("HTTPS_PROXY", "http://proxy.corp:3128".to_string()),
+                ("https_proxy", "http://proxy.corp:3128".to_string()),
+            ]
+        );
+        assert!(Config::default().network.child_env().is_empty());
+    }
+
+    #[test]
+    fn test_expand_tilde() {
+        let expanded = Config::expand_tilde("~/.config/test");
+        assert!(!expanded.to_string_lossy().starts_with('~'));
+
+        let no_tilde = Config::expand_tilde("/absolute/path");
+        assert_eq!(no_tilde, PathBuf::from("/absolute/path"));
+
+        let relative = Config::expand_tilde("./relative/path");
+        assert_eq!(relative, PathBuf::from("./relative/path"));
+    }
+
+    #[test]
+    fn test_config_deserialization() {
+        let toml_str = r#"
+[claude]
+path = "/custom/claude"
+
+[logging]
+level = "debug"
+"#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.claude.path, "/custom/claude");
+        assert!(config.claude.args.is_none());
+        assert_eq!(config.logging.level, "debug");
+    }
+
+    #[test]
+    fn test_config_partial_deserialization() {
+        // Only claude section specified, others should use defaults
+        let toml_str = r#"
+[claude]
+path = "/custom/claude"
+"#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.claude.path, "/custom/claude");
+        // args should be None since not specified (legacy field)
+        assert!(config.claude.args.is_none());
+        // logging should be defaults
+        assert_eq!(config.logging.level, "info");
+    }
+
+    #[test]
+    fn test_unknown_keys_ignored() {
+        let toml_str = r#"
+[claude]
+path = "/custom/claude"
+unknown_key = "should be ignored"
+
+[unknown_section]
+foo = "bar"
+"#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.claude.path, "/custom/claude");
+    }
+
+    #[test]
+    fn test_legacy_claude_args_ignored() {
+        // Existing config files may have claude.args - ensure they still load
+        let toml_str = r#"
+[claude]
+path = "/custom/claude"
+args = "--output-format=stream-json --verbose"
+"#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.claude.path, "/custom/claude");
+        // args is parsed but ignored (legacy field)
+        assert_eq!(
+            config.claude.args,
+            Some("--output-format=stream-json --verbose".to_string())
+        );
+    }
+
+    #[test]
+    fn test_iterations_default() {
+        let config = Config::default();
+        assert_eq!(config.behavior.iterations, -1); // Infinite by default
+    }
+
+    #[test]
+    fn test_iterations_explicit() {
+        let toml_str = r#"
+[behavior]
+iterations = 5
+"#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.behavior.iterations, 5);
+    }
+
+    #[test]
+    fn test_keep_awake_default() {
+        let config = Config::default();
+        assert!(config.behavior.keep_awake); // Default: true
+    }
+
+    #[test]
+    fn test_keep_awake_explicit() {
+        let toml_str = r#"
+[behavior]
+keep_awake = false
+"#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert!(!config.behavior.keep_awake);
+    }
+
+    #[test]
+    fn test_partial_config_empty() {
+        let toml_str = "";
+        let partial: PartialConfig = toml::from_str(toml_str).unwrap();
+        assert!(partial.claude.path.is_none());
+        assert!(partial.logging.level.is_none());
+        assert!(partial.behavior.iterations.is_none());
+        assert!(partial.behavior.keep_awake.is_none());
+    }
+
+    #[test]
+    fn test_partial_config_some_fields() {
+        let toml_str = r#"
+[behavior]
+iterations = 3
+"#;
+
+        let partial: PartialConfig = toml::from_str(toml_str).unwrap();
+        assert!(partial.claude.path.is_none());
+        assert_eq!(partial.behavior.iterations, Some(3));
+        assert!(partial.behavior.keep_awake.is_none());
+    }
+
+    #[test]
+    fn test_partial_config_unknown_keys_ignored() {
+        let toml_str = r#"
+[unknown_section]
+foo = "bar"
+"#;
+
+        let _partial: PartialConfig = toml::from_str(toml_str).unwrap();
+    }
+
+    #[test]
+    fn test_partial_config_comment_only() {
+        let toml_str = "# Project-specific Ralph config — edit with config modal (c)\n";
+        let partial: PartialConfig = toml::from_str(toml_str).unwrap();
+        assert!(partial.claude.path.is_none());
+    }
+
+    #[test]
+    fn test_merge_config_no_overrides() {
+        let global = Config::default();
+        let partial = PartialConfig::default();
+        let merged = merge_config(&global, &partial);
+
+        assert_eq!(merged.claude.path, global.claude.path);
+        assert_eq!(merged.logging.level, global.logging.level);
+        assert_eq!(merged.behavior.iterations, global.behavior.iterations);
+        assert_eq!(merged.behavior.keep_awake, global.behavior.keep_awake);
+    }
+
+    #[test]
+    fn test_merge_config_all_overrides() {
+        let global = Config::default();
+        let partial = PartialConfig {
+            extends: None,
+            claude: PartialClaudeConfig {
+                path: Some("/custom/claude".to_string()),
+                pty: None,
+            },
+            logging: PartialLoggingConfig {
+                level: Some("debug".to_string()),
+                debug_on_error_minutes: Some(0),
+            },
+            behavior: PartialBehaviorConfig {
+                iterations: Some(5),
+                keep_awake: Some(false),
+                bd_path: None,
+                heartbeat_interval: None,
+                stale_threshold: None,
+                workers: None,
+                resume_conversation: None,
+                backup_files: None,
+                confirm_dirty_start: None,
+                stop_grace_secs: None,
+                iteration_timeout_secs: None,
+                continue_after_timeout: None,
+                pause_on_question: None,
+            },
+            display: PartialDisplayConfig::default(),
+            schedule: PartialScheduleConfig::default(),
+            budget: PartialBudgetConfig::default(),
+            control: PartialControlConfig::default(),
+            network: PartialNetworkConfig::default(),
+            disk: PartialDiskConfig::default(),
+            metrics: PartialMetricsConfig::default(),
+            tracker: PartialTrackerConfig::default(),
+            encryption: PartialEncryptionConfig::default(),
+            hooks: PartialHooksConfig::default(),
+            keys: PartialKeysConfig::default(),
+        };
+        let merged = merge_config(&global, &partial);
+
+        assert_eq!(merged.claude.path, "/custom/claude");
+        assert_eq!(merged.logging.level, "debug");
+        assert_eq!(merged.logging.debug_on_error_minutes, 0);
+        assert_eq!(merged.behavior.iterations, 5);
+        assert!(!merged.behavior.keep_awake);
+    }
+
+    #[test]
+    fn test_merge_config_partial_overrides() {
+        let global = Config::default();
+        let partial: PartialConfig = toml::from_str(
+            r#"
+[behavior]
+iterations = 3
+"#,
+        )
+        .unwrap();
+        let merged = merge_config(&global, &partial);
+
+        // Overridden fields
+        assert_eq!(merged.behavior.iterations, 3);
+
+        // Inherited fields
+        assert_eq!(merged.claude.path, global.claude.path);
+        assert_eq!(merged.logging.level, global.logging.level);
+        assert_eq!(merged.behavior.keep_awake, global.behavior.keep_awake);
+    }
+
+    #[test]
+    fn test_partial_config_serialize_empty() {
+        let partial = PartialConfig::default();
+        let toml_str = toml::to_string_pretty(&partial).unwrap();
+        // Empty partial config should serialize to empty string (all sections skipped)
+        assert_eq!(toml_str.trim(), "");
+    }
+
+    #[test]
+    fn test_partial_config_serialize_some_fields() {
+        let partial = PartialConfig {
+            behavior: PartialBehaviorConfig {
+                iterations: Some(3),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let toml_str = toml::to_string_pretty(&partial).unwrap();
+        // Should contain only the set fields
+        assert!(toml_str.contains("iterations = 3"));
+        // Should not contain unset fields
+        assert!(!toml_str.contains("claude"));
+        assert!(!toml_str.contains("keep_awake"));
+    }
+
+    #[test]
+    fn test_partial_config_serialize_roundtrip() {
+        let partial = PartialConfig {
+            extends: None,
+            claude: PartialClaudeConfig {
+                path: Some("/custom/claude".to_string()),
+                pty: None,
+            },
+            logging: PartialLoggingConfig::default(),
+            behavior: PartialBehaviorConfig {
+                iterations: Some(5),
+                keep_awake: None,
+                bd_path: None,
+                heartbeat_interval: None,
+                stale_threshold: None,
+                workers: None,
+                resume_conversation: None,
+                backup_files: None,
+                confirm_dirty_start: None,
+                stop_grace_secs: None,
+                iteration_timeout_secs: None,
+                continue_after_timeout: None,
+                pause_on_question: None,
+            },
+            display: PartialDisplayConfig::default(),
+            schedule: PartialScheduleConfig::default(),
+            budget: PartialBudgetConfig::default(),
+            control: PartialControlConfig::default(),
+            network: PartialNetworkConfig::default(),
+            disk: PartialDiskConfig::default(),
+            metrics: PartialMetricsConfig::default(),
+            tracker: PartialTrackerConfig::default(),
+            encryption: PartialEncryptionConfig::default(),
+            hooks: PartialHooksConfig::default(),
+            keys: PartialKeysConfig::default(),
+        };
+        let toml_str = toml::to_string_pretty(&partial).unwrap();
+        let deserialized: PartialConfig = toml::from_str(&toml_str).unwrap();
+
+        assert_eq!(deserialized.claude.path, Some("/custom/claude".to_string()));
+        assert!(deserialized.logging.level.is_none());
+        assert_eq!(deserialized.behavior.iterations, Some(5));
+        assert!(deserialized.behavior.keep_awake.is_none());
+    }
+
+    #[test]
+    fn workers_default_is_one() {
+        let config = BehaviorConfig::default();
+        assert_eq!(config.workers, 1);
+    }
+
+    #[test]
+    fn workers_parsed_from_toml() {
+        let toml_str = r#"
+[behavior]
+workers = 4
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.behavior.workers, 4);
+    }
+
+    #[test]
+    fn workers_explicit_values() {
+        for n in [1, 2, 4, 8] {
+            let toml_str = format!("[behavior]\nworkers = {}", n);
+            let config: Config = toml::from_str(&toml_str).unwrap();
+            assert_eq!(config.behavior.workers, n);
+        }
+    }
+
+    #[test]
+    fn workers_zero_clamped_to_one_on_merge() {
+        let base = Config::default();
+        let partial = PartialConfig {
+            behavior: PartialBehaviorConfig {
+                workers: Some(0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let merged = merge_config(&base, &partial);
+        assert_eq!(merged.behavior.workers, 1);
+    }
+
+    #[test]
+    fn workers_missing_defaults_to_one() {
+        let toml_str = r#"
+[behavior]
+iterations = -1
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.behavior.workers, 1);
+    }
+
+    #[test]
+    fn workers_merge_project_overrides_global() {
+        let global = Config::default();
+        let partial = PartialConfig {
+            behavior: PartialBehaviorConfig {
+                workers: Some(3),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let merged = merge_config(&global, &partial);
+        assert_eq!(merged.behavior.workers, 3);
+    }
+
+    #[test]
+    fn workers_merge_project_none_inherits_global() {
+        let mut global = Config::default();
+        global.behavior.workers = 2;
+        let partial = PartialConfig::default();
+        let merged = merge_config(&global, &partial);
+        assert_eq!(merged.behavior.workers, 2);
+    }
+
+    #[test]
+    fn display_section_parses_and_merges() {
+        let partial: PartialConfig = toml::from_str(
+            r#"
+[display]
+result_preview_lines = 12
+"#,
+        )
+        .unwrap();
+        let merged = merge_config(&Config::default(), &partial);
+        assert_eq!(merged.display.result_preview_lines, 12);
+        assert_eq!(merged.display.tool_input_width, 60);
+        assert_eq!(merged.display.bash_command_width, 50);
+        assert_eq!(merged.display.highlights, default_highlights());
+    }
+
+    #[test]
+    fn display_highlights_replace_defaults() {
+        let partial: PartialConfig = toml::from_str(
+            r#"
+[display.highlights]
+'FAILED' = "red bold"
+"#,
+        )
+        .unwrap();
+        let merged = merge_config(&Config::default(), &partial);
+        assert_eq!(merged.display.highlights.len(), 1);
+        assert_eq!(merged.display.highlights["FAILED"], "red bold");
+    }
+
+    #[test]
+    fn display_tool_error_threshold_overrides_default() {
+        let partial: PartialConfig =
+            toml::from_str("[display]\ntool_error_threshold = 0\n").unwrap();
+        assert_eq!(Config::default().display.tool_error_threshold, 5);
+        let merged = merge_config(&Config::default(), &partial);
+        assert_eq!(merged.display.tool_error_threshold, 0);
+    }
+
+    #[test]
+    fn disk_limits_override_defaults() {
+        let partial: PartialConfig = toml::from_str("[disk]\nmax_growth_mb = 2048\n").unwrap();
+        let merged = merge_config(&Config::default(), &partial);
+        assert_eq!(merged.disk.min_free_mb, 1024);
+        assert_eq!(merged.disk.max_growth_mb, 2048);
+    }
+
+    #[test]
+    fn keys_mouse_capture_inherits_unless_overridden() {
+        let mut global = Config::default();
+        assert!(global.keys.mouse_capture);
+        global.keys.mouse_capture = false;
+        let merged = merge_config(&global, &PartialConfig::default());
+        assert!(!merged.keys.mouse_capture);
+
+        let partial: PartialConfig = toml::from_str("[keys]\nmouse_capture = true\n").unwrap();
+        assert!(merge_config(&global, &partial).keys.mouse_capture);
+        assert!(is_partial_keys_empty(&PartialConfig::default().keys));
+    }
+
+    #[test]
+    fn display_idle_settings_parse() {
+        let partial: PartialConfig = toml::from_str(
+            r#"
+[display]
+idle_minutes = 20
+idle_style = "blank"
+"#,
+        )
+        .unwrap();
+        let merged = merge_config(&Config::default(), &partial);
+        assert_eq!(merged.display.idle_minutes, 20);
+        assert_eq!(merged.display.idle_style, IdleStyle::Blank);
+        assert_eq!(Config::default().display.idle_style, IdleStyle::Dim);
+    }
+
+    #[test]
+    fn display_number_format_settings_parse() {
+        let partial: PartialConfig = toml::from_str(
+            r#"
+[display]
+thousands_separator = "."
+decimal_separator = ","
+currency_symbol = "€"
+currency_position = "after"
+color_depth = "16"
+theme = "high-contrast"
+"#,
+        )
+        .unwrap();
+        let merged = merge_config(&Config::default(), &partial);
+        assert_eq!(merged.display.thousands_separator, ".");
+        assert_eq!(merged.display.decimal_separator, ",");
+        assert_eq!(merged.display.currency_symbol, "€");
+        assert_eq!(merged.display.currency_position, CurrencyPosition::After);
+        assert_eq!(merged.display.color_depth, ColorDepth::Ansi16);
+        assert_eq!(merged.display.theme, Theme::HighContrast);
+        assert_eq!(merged.display.banners, BannersConfig::default());
+        assert_eq!(
+            Config::default().display.currency_position,
+            CurrencyPosition::Before
+        );
+    }
+
+    #[test]
+    fn display_banners_merge_per_field() {
+        let partial: PartialConfig = toml::from_str(
+            r#"
+[display.banners]
+style = "plain"
+divider = ""
+"#,
+        )
+        .unwrap();
+        let merged = merge_config(&Config::default(), &partial);
+        let banners = &merged.display.banners;
+        assert_eq!(banners.style, BannerStyle::Plain);
+        assert_eq!(banners.divider, "");
+        assert_eq!(banners.auto_continue, "AUTO-CONTINUING");
+        assert_eq!(banners.banner("DONE"), "== DONE ==");
+        assert_eq!(
+            BannersConfig::default().banner("DONE"),
+            "══════════════════ DONE ══════════════════"
+        );
+        assert!(
+            toml::to_string(&PartialConfig::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn display_merge_clamps_out_of_range_values() {
+        let partial = PartialConfig {
+            display: PartialDisplayConfig {
+                result_preview_lines: Some(10_000),
+                tool_input_width: Some(0),
+                bash_command_width: Some(2),
+                highlights: None,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let merged = merge_config(&Config::default(), &partial);
+        assert_eq!(
+            merged.display.result_preview_lines,
+            MAX_RESULT_PREVIEW_LINES
+        );
+        assert_eq!(merged.display.tool_input_width, MIN_TOOL_INPUT_WIDTH);
+        assert_eq!(merged.display.bash_command_width, MIN_TOOL_INPUT_WIDTH);
+    }
+
+    #[test]
+    fn extends_is_written_back_before_tables() {
+        let partial: PartialConfig =
+            toml::from_str("extends = \"~/base.toml\"\n[behavior]\nworkers = 2\n").unwrap();
+        let out = toml::to_string_pretty(&partial).unwrap();
+        assert!(out.starts_with("extends = \"~/base.toml\""));
+    }
+}
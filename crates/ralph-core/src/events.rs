@@ -28,6 +28,62 @@ pub enum ClaudeEvent {
     // Heartbeat
     #[serde(rename = "ping")]
     Ping,
+
+    // Control protocol (stream-json input only)
+    #[serde(rename = "control_request")]
+    ControlRequest(ControlRequestEvent),
+    #[serde(rename = "control_response")]
+    ControlResponse(ControlResponseEvent),
+
+    // Written to transcripts by ralph itself, never by Claude
+    #[serde(rename = "ralph_prompt")]
+    PromptSnapshot(PromptSnapshotEvent),
+}
+
+/// The exact prompt an iteration was started with.
+#[derive(Debug, Deserialize)]
+pub struct PromptSnapshotEvent {
+    pub iteration: u32,
+    pub prompt: String,
+}
+
+/// A request from Claude CLI that needs an answer on stdin.
+#[derive(Debug, Deserialize)]
+pub struct ControlRequestEvent {
+    pub request_id: String,
+    pub request: ControlRequest,
+}
+
+/// Control request payloads, discriminated by "subtype".
+#[derive(Debug, Deserialize)]
+#[serde(tag = "subtype")]
+pub enum ControlRequest {
+    /// Permission prompt for a tool call not covered by the allow rules.
+    #[serde(rename = "can_use_tool")]
+    CanUseTool {
+        tool_name: String,
+        #[serde(default)]
+        input: serde_json::Value,
+    },
+    /// Any subtype ralph doesn't handle.
+    #[serde(other)]
+    Unsupported,
+}
+
+/// Claude CLI's answer to a request ralph sent (e.g. an interrupt).
+#[derive(Debug, Deserialize)]
+pub struct ControlResponseEvent {
+    pub response: ControlResponseBody,
+}
+
+/// Body of a control response.
+#[derive(Debug, Deserialize)]
+pub struct ControlResponseBody {
+    /// `success` or `error`.
+    pub subtype: String,
+    pub request_id: String,
+    #[serde(default)]
+    pub error: Option<String>,
 }
 
 /// User event containing tool results from Claude.
@@ -90,6 +146,30 @@ pub enum StreamInnerEvent {
 pub struct SystemEvent {
     #[serde(default)]
     pub subtype: Option<String>,
+    /// Claude CLI's own session ID (on `init`), usable with `--resume`.
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Model the session actually runs on (on `init`).
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Names of the tools available to Claude (on `init`).
+    #[serde(default)]
+    pub tools: Vec<String>,
+    #[serde(default, rename = "permissionMode")]
+    pub permission_mode: Option<String>,
+    #[serde(default)]
+    pub mcp_servers: Vec<McpServerStatus>,
+}
+
+/// An MCP server listed in the `init` event.
+#[derive(Debug, Deserialize)]
+pub struct McpServerStatus {
+    pub name: String,
+    /// e.g. `connected` or `failed`.
+    #[serde(default)]
+    pub status: Option<String>,
 }
 
 /// Assistant turn marker from Claude CLI.
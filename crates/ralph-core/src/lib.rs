@@ -0,0 +1,21 @@
+//! Terminal-independent core of ralph.
+//!
+//! The `ralph` binary is the TUI on top of this crate. Anything here must
+//! work without a terminal, so it can back a headless mode, integration
+//! tests, or another frontend.
+//!
+//! - [`clock`]: the time source, swappable for a manual clock in tests.
+//! - [`config`]: configuration types and how project overrides merge.
+//! - [`events`]: typed Claude CLI stream-json events.
+//! - [`output`]: a run's output as it streams in, before it is drawn.
+//! - [`run_loop`]: what follows a run's exit, and whether to auto-continue.
+//! - [`schedule`]: quiet hours, when auto-continue is held.
+//! - [`budget`]: rolling spend tracking for the hourly budget.
+
+pub mod budget;
+pub mod clock;
+pub mod config;
+pub mod events;
+pub mod output;
+pub mod run_loop;
+pub mod schedule;
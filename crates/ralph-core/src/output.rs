@@ -0,0 +1,47 @@
+//! The model of a run's output as it streams in: what the runner sends for
+//! each line, content blocks being assembled from deltas, and the tool calls
+//! seen so far. How any of it is drawn is up to the frontend.
+
+/// Message types for output processing.
+pub enum OutputMessage {
+    Line(String),
+}
+
+/// Tracks accumulated state for a content block being streamed.
+#[derive(Debug, Default)]
+pub struct ContentBlockState {
+    /// For text blocks: accumulated text content.
+    pub text: String,
+    /// For tool_use blocks: the tool name.
+    pub tool_name: Option<String>,
+    /// For tool_use blocks: the tool use ID (for correlating with results).
+    pub tool_use_id: Option<String>,
+    /// For tool_use blocks: accumulated JSON input string.
+    pub input_json: String,
+    /// Whether we've shown the assistant header for this text block.
+    pub header_shown: bool,
+}
+
+/// Status of a tool call in the panel display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolCallStatus {
+    /// Tool call sent, waiting for result.
+    Pending,
+    /// Tool call completed successfully.
+    Success,
+    /// Tool call returned an error.
+    Error,
+}
+
+/// A tool call entry for the panel display.
+#[derive(Debug, Clone)]
+pub struct ToolCallEntry {
+    /// The tool name (e.g., "Read", "Bash").
+    pub tool_name: String,
+    /// Summary of the key argument (e.g., "git status", "/path/to/file.rs").
+    pub summary: String,
+    /// Current status.
+    pub status: ToolCallStatus,
+    /// Tool use ID for correlating with results.
+    pub tool_use_id: Option<String>,
+}
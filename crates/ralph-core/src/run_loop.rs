@@ -0,0 +1,289 @@
+//! Run-loop policy: what a worker does once a run ends and once the work
+//! source has been asked whether anything is left.
+//!
+//! The binary carries these decisions out (stopping workers, offering
+//! `claude login`, starting the next iteration); they are kept here as pure
+//! functions so they can be tested, or reused, without a running loop.
+
+use std::time::Duration;
+
+use crate::clock::format_elapsed;
+use crate::config::BannersConfig;
+
+/// Result of checking if there's remaining work.
+#[derive(Debug, PartialEq)]
+pub enum WorkRemaining {
+    /// There are work items with active status.
+    Yes,
+    /// All work items are done or blocked.
+    No,
+    /// All ready beads are for humans only (human label).
+    HumanOnly(usize),
+    /// Error reading the work source.
+    ReadError(String),
+}
+
+/// How a worker's run ended, as far as deciding what comes next goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunExit<'a> {
+    /// Process exit code; `None` when killed by a signal.
+    pub exit_code: Option<i32>,
+    /// The run was interrupted with `S`.
+    pub stopped: bool,
+    /// Claude reported that it isn't logged in.
+    pub auth_failed: bool,
+    /// The iteration count allows another iteration.
+    pub iterations_left: bool,
+    /// Error from the run's final result message, if any.
+    pub result_error: Option<&'a str>,
+    /// The run was killed for exceeding this `behavior.iteration_timeout_secs`.
+    pub timed_out: Option<Duration>,
+    /// `behavior.continue_after_timeout`.
+    pub continue_after_timeout: bool,
+}
+
+/// What to do after a worker's run ends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExitAction {
+    /// Stop this worker quietly (interrupted, killed, or out of iterations).
+    Stop,
+    /// Stop every worker and offer `claude login`; every iteration would
+    /// fail the same way.
+    StopForLogin,
+    /// Check in the background whether work remains before continuing.
+    CheckRemaining,
+    /// Stop every worker, show `message` and enter the error state.
+    Fail { message: String },
+}
+
+/// Decide what follows a run's exit. An interrupt wins over everything (the
+/// exit code is whatever Claude chose on interrupt), then a login failure,
+/// then a timeout.
+pub fn decide_exit(exit: RunExit) -> ExitAction {
+    if let Some(limit) = exit
+        .timed_out
+        .filter(|_| !exit.stopped && !exit.auth_failed)
+    {
+        return if exit.continue_after_timeout && exit.iterations_left {
+            ExitAction::CheckRemaining
+        } else {
+            ExitAction::Fail {
+                message: format!(
+                    "[Error: iteration timed out after {}]",
+                    format_elapsed(limit)
+                ),
+            }
+        };
+    }
+    match exit.exit_code {
+        _ if exit.stopped => ExitAction::Stop,
+        _ if exit.auth_failed => ExitAction::StopForLogin,
+        Some(0) if exit.iterations_left => ExitAction::CheckRemaining,
+        Some(0) | None => ExitAction::Stop,
+        Some(code) => ExitAction::Fail {
+            message: match exit.result_error {
+                Some(msg) => format!("[Error: {msg}]"),
+                None => format!("[Error: process exited with code {code}]"),
+            },
+        },
+    }
+}
+
+/// What to do once the background check for remaining work reports back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemainingAction {
+    /// Start the next iteration.
+    Continue,
+    /// Nothing left to do; end the run with `banner`.
+    Complete { banner: String },
+    /// Only beads for humans are ready; end the run with `banner` and fire
+    /// the blocked hook.
+    HumanOnly { count: usize, banner: String },
+    /// The work source couldn't be read; enter the error state.
+    Fail { message: String },
+}
+
+/// Decide whether a worker auto-continues, given what the work source reported.
+pub fn decide_remaining(
+    result: WorkRemaining,
+    complete_msg: &str,
+    banners: &BannersConfig,
+) -> RemainingAction {
+    match result {
+        WorkRemaining::Yes => RemainingAction::Continue,
+        WorkRemaining::No => RemainingAction::Complete {
+            banner: banners.banner(banners.complete.as_deref().unwrap_or(complete_msg)),
+        },
+        WorkRemaining::HumanOnly(count) => RemainingAction::HumanOnly {
+            count,
+            banner: banners.banner(&format!(
+                "no work for Ralph — {} {} available for humans",
+                count,
+                if count == 1 { "bead" } else { "beads" }
+            )),
+        },
+        WorkRemaining::ReadError(e) => RemainingAction::Fail {
+            message: format!("[Error reading work source: {e}]"),
+        },
+    }
+}
+
+/// The per-iteration time limit, `None` when `secs` is 0.
+pub fn iteration_timeout(secs: u64) -> Option<Duration> {
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BannerStyle;
+
+    fn exit(exit_code: Option<i32>) -> RunExit<'static> {
+        RunExit {
+            exit_code,
+            stopped: false,
+            auth_failed: false,
+            iterations_left: true,
+            result_error: None,
+            timed_out: None,
+            continue_after_timeout: false,
+        }
+    }
+
+    #[test]
+    fn timeout_fails_unless_configured_to_continue() {
+        let timed_out = RunExit {
+            timed_out: Some(Duration::from_secs(600)),
+            ..exit(None)
+        };
+        assert_eq!(
+            decide_exit(timed_out),
+            ExitAction::Fail {
+                message: "[Error: iteration timed out after 10:00]".into()
+            }
+        );
+        let continuing = RunExit {
+            continue_after_timeout: true,
+            ..timed_out
+        };
+        assert_eq!(decide_exit(continuing), ExitAction::CheckRemaining);
+        let last = RunExit {
+            iterations_left: false,
+            ..continuing
+        };
+        assert!(matches!(decide_exit(last), ExitAction::Fail { .. }));
+        let stopped = RunExit {
+            stopped: true,
+            ..continuing
+        };
+        assert_eq!(decide_exit(stopped), ExitAction::Stop);
+    }
+
+    #[test]
+    fn interrupt_stops_whatever_the_exit() {
+        for code in [Some(0), Some(1), Some(130), None] {
+            let stopped = RunExit {
+                stopped: true,
+                auth_failed: true,
+                ..exit(code)
+            };
+            assert_eq!(decide_exit(stopped), ExitAction::Stop, "{code:?}");
+        }
+    }
+
+    #[test]
+    fn login_failure_wins_over_exit_code() {
+        for code in [Some(0), Some(1), None] {
+            let auth = RunExit {
+                auth_failed: true,
+                ..exit(code)
+            };
+            assert_eq!(decide_exit(auth), ExitAction::StopForLogin, "{code:?}");
+        }
+    }
+
+    #[test]
+    fn clean_exit_checks_remaining_only_with_iterations_left() {
+        assert_eq!(decide_exit(exit(Some(0))), ExitAction::CheckRemaining);
+        let last = RunExit {
+            iterations_left: false,
+            ..exit(Some(0))
+        };
+        assert_eq!(decide_exit(last), ExitAction::Stop);
+    }
+
+    #[test]
+    fn killed_by_signal_stops() {
+        assert_eq!(decide_exit(exit(None)), ExitAction::Stop);
+        let last = RunExit {
+            iterations_left: false,
+            ..exit(None)
+        };
+        assert_eq!(decide_exit(last), ExitAction::Stop);
+    }
+
+    #[test]
+    fn nonzero_exit_fails_with_result_error_or_code() {
+        assert_eq!(
+            decide_exit(exit(Some(2))),
+            ExitAction::Fail {
+                message: "[Error: process exited with code 2]".into()
+            }
+        );
+        let with_error = RunExit {
+            result_error: Some("overloaded"),
+            iterations_left: false,
+            ..exit(Some(1))
+        };
+        assert_eq!(
+            decide_exit(with_error),
+            ExitAction::Fail {
+                message: "[Error: overloaded]".into()
+            }
+        );
+    }
+
+    #[test]
+    fn remaining_work_decisions() {
+        assert_eq!(
+            decide_remaining(WorkRemaining::Yes, "DONE", &BannersConfig::default()),
+            RemainingAction::Continue
+        );
+        assert_eq!(
+            decide_remaining(WorkRemaining::No, "DONE", &BannersConfig::default()),
+            RemainingAction::Complete {
+                banner: "══════════════════ DONE ══════════════════".into()
+            }
+        );
+        assert!(matches!(
+            decide_remaining(WorkRemaining::HumanOnly(1), "DONE", &BannersConfig::default()),
+            RemainingAction::HumanOnly { count: 1, banner } if banner.contains("1 bead available")
+        ));
+        assert!(matches!(
+            decide_remaining(WorkRemaining::HumanOnly(3), "DONE", &BannersConfig::default()),
+            RemainingAction::HumanOnly { count: 3, banner } if banner.contains("3 beads available")
+        ));
+        assert_eq!(
+            decide_remaining(
+                WorkRemaining::ReadError("locked".into()),
+                "DONE",
+                &BannersConfig::default()
+            ),
+            RemainingAction::Fail {
+                message: "[Error reading work source: locked]".into()
+            }
+        );
+
+        let banners = BannersConfig {
+            style: BannerStyle::Bare,
+            complete: Some("Backlog empty".into()),
+            ..BannersConfig::default()
+        };
+        assert_eq!(
+            decide_remaining(WorkRemaining::No, "DONE", &banners),
+            RemainingAction::Complete {
+                banner: "Backlog empty".into()
+            }
+        );
+    }
+}
@@ -0,0 +1,109 @@
+//! Quiet hours (`schedule.quiet_hours`) — a daily window with no auto-continue.
+
+use std::str::FromStr;
+
+/// Daily window as minutes since local midnight. Wraps past midnight when
+/// `start > end` (e.g. `23:00-07:00`); empty when `start == end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    start: u16,
+    end: u16,
+}
+
+impl QuietHours {
+    /// Whether `minute` (since midnight) falls inside the window.
+    pub fn contains(&self, minute: u16) -> bool {
+        if self.start <= self.end {
+            (self.start..self.end).contains(&minute)
+        } else {
+            minute >= self.start || minute < self.end
+        }
+    }
+}
+
+impl FromStr for QuietHours {
+    type Err = String;
+
+    /// Parse `HH:MM-HH:MM`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("'{s}' must look like \"23:00-07:00\""))?;
+        Ok(Self {
+            start: parse_time(start.trim())?,
+            end: parse_time(end.trim())?,
+        })
+    }
+}
+
+/// Parse `HH:MM` (24-hour) into minutes since midnight.
+fn parse_time(s: &str) -> Result<u16, String> {
+    let invalid = || format!("invalid time '{s}' (expected HH:MM, 00:00-23:59)");
+    let (h, m) = s.split_once(':').ok_or_else(invalid)?;
+    let h: u16 = h.parse().map_err(|_| invalid())?;
+    let m: u16 = m.parse().map_err(|_| invalid())?;
+    if h >= 24 || m >= 60 {
+        return Err(invalid());
+    }
+    Ok(h * 60 + m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MINUTES_PER_DAY;
+
+    fn hours(s: &str) -> QuietHours {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_window() {
+        assert_eq!(
+            hours("23:00-07:30"),
+            QuietHours {
+                start: 23 * 60,
+                end: 7 * 60 + 30
+            }
+        );
+        assert_eq!(hours(" 9:05 - 17:00 ").start, 9 * 60 + 5);
+    }
+
+    #[test]
+    fn rejects_malformed_windows() {
+        for bad in [
+            "",
+            "23:00",
+            "24:00-07:00",
+            "23:60-07:00",
+            "ab:cd-07:00",
+            "23-07",
+        ] {
+            assert!(bad.parse::<QuietHours>().is_err(), "{bad} should fail");
+        }
+    }
+
+    #[test]
+    fn window_wrapping_midnight() {
+        let q = hours("23:00-07:00");
+        assert!(q.contains(23 * 60));
+        assert!(q.contains(0));
+        assert!(q.contains(6 * 60 + 59));
+        assert!(!q.contains(7 * 60));
+        assert!(!q.contains(12 * 60));
+    }
+
+    #[test]
+    fn window_within_a_day() {
+        let q = hours("12:00-13:00");
+        assert!(q.contains(12 * 60 + 30));
+        assert!(!q.contains(13 * 60));
+        assert!(!q.contains(11 * 60 + 59));
+    }
+
+    #[test]
+    fn equal_start_and_end_is_empty() {
+        let q = hours("08:00-08:00");
+        assert!((0..MINUTES_PER_DAY).all(|m| !q.contains(m)));
+    }
+}
@@ -5,6 +5,8 @@ use std::process::Command;
 
 use tracing::{info, warn};
 
+use crate::bd::Bd;
+
 /// Result of selecting and claiming work — either a standalone bead or a child of an epic.
 pub struct Claim {
     pub bead_id: String,
@@ -67,7 +69,7 @@ pub fn find_standalone_beads<'a>(beads: &[&'a serde_json::Value]) -> Vec<&'a ser
         .filter(|b| {
             b.get("parent")
                 .and_then(|p| p.as_str())
-                .map_or(true, |s| s.is_empty())
+                .is_none_or(|s| s.is_empty())
         })
         .copied()
         .collect()
@@ -143,9 +145,9 @@ If the changes are unclear or problematic, escalate via `bd human <bead>` with a
 // --- I/O functions for work selection ---
 
 /// Select and claim the best available work — either a standalone bead or an epic's first child.
-pub fn select_and_claim_work(bd_path: &str, agent_bead_id: &str) -> Option<Claim> {
+pub fn select_and_claim_work(bd: &Bd, agent_bead_id: &str) -> Option<Claim> {
     let output = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["ready", "--json"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -196,7 +198,7 @@ pub fn select_and_claim_work(bd_path: &str, agent_bead_id: &str) -> Option<Claim
                 if children.is_empty() {
                     continue;
                 }
-                let priority = get_bead_priority(bd_path, epic_id);
+                let priority = get_bead_priority(bd, epic_id);
                 candidates.push(ScoredCandidate {
                     kind: CandidateKind::Epic {
                         epic_id: epic_id.clone(),
@@ -219,22 +221,20 @@ pub fn select_and_claim_work(bd_path: &str, agent_bead_id: &str) -> Option<Claim
         CandidateKind::Standalone {
             bead_id,
             bead_title,
-        } => claim_standalone_bead(bd_path, agent_bead_id, bead_id, bead_title),
-        CandidateKind::Epic { epic_id } => {
-            claim_epic_and_first_child(bd_path, agent_bead_id, epic_id)
-        }
+        } => claim_standalone_bead(bd, agent_bead_id, bead_id, bead_title),
+        CandidateKind::Epic { epic_id } => claim_epic_and_first_child(bd, agent_bead_id, epic_id),
     }
 }
 
 /// Atomically claim a standalone bead, record the hook, and assess its specification.
 fn claim_standalone_bead(
-    bd_path: &str,
+    bd: &Bd,
     agent_bead_id: &str,
     bead_id: String,
     bead_title: String,
 ) -> Option<Claim> {
     let claim_result = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["update", &bead_id, "--claim"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::null())
@@ -246,7 +246,7 @@ fn claim_standalone_bead(
         Ok(o) if o.status.success() => {
             let hook_arg = format!("hook={}", bead_id);
             let _ = crate::bd_lock::with_lock(|| {
-                Command::new(bd_path)
+                bd.command()
                     .args(["set-state", agent_bead_id, &hook_arg])
                     .stdin(std::process::Stdio::null())
                     .stdout(std::process::Stdio::null())
@@ -254,7 +254,7 @@ fn claim_standalone_bead(
                     .output()
             });
 
-            if !super::lifecycle::assess_bead_specification(bd_path, &bead_id, agent_bead_id) {
+            if !super::lifecycle::assess_bead_specification(bd, &bead_id, agent_bead_id) {
                 info!(bead_id = %bead_id, "standalone_rejected_assessment");
                 return None;
             }
@@ -279,13 +279,9 @@ fn claim_standalone_bead(
 }
 
 /// Claim an epic, record it on agent state, and claim its first ready child.
-fn claim_epic_and_first_child(
-    bd_path: &str,
-    agent_bead_id: &str,
-    epic_id: String,
-) -> Option<Claim> {
+fn claim_epic_and_first_child(bd: &Bd, agent_bead_id: &str, epic_id: String) -> Option<Claim> {
     let claim_result = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["update", &epic_id, "--claim"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::null())
@@ -310,7 +306,7 @@ fn claim_epic_and_first_child(
 
     let epic_arg = format!("epic={}", epic_id);
     let _ = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["set-state", agent_bead_id, &epic_arg])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::null())
@@ -318,7 +314,7 @@ fn claim_epic_and_first_child(
             .output()
     });
 
-    match claim_next_child(bd_path, agent_bead_id, &epic_id) {
+    match claim_next_child(bd, agent_bead_id, &epic_id) {
         Some((child_id, child_title)) => Some(Claim {
             bead_id: child_id,
             bead_title: child_title,
@@ -327,7 +323,7 @@ fn claim_epic_and_first_child(
         None => {
             warn!(epic_id = %epic_id, "epic_claimed_but_no_children_ready");
             let _ = crate::bd_lock::with_lock(|| {
-                Command::new(bd_path)
+                bd.command()
                     .args(["update", &epic_id, "--status=open", "--assignee="])
                     .stdin(std::process::Stdio::null())
                     .stdout(std::process::Stdio::null())
@@ -340,13 +336,9 @@ fn claim_epic_and_first_child(
 }
 
 /// Claim the next ready child bead within an epic.
-pub fn claim_next_child(
-    bd_path: &str,
-    agent_bead_id: &str,
-    epic_id: &str,
-) -> Option<(String, String)> {
+pub fn claim_next_child(bd: &Bd, agent_bead_id: &str, epic_id: &str) -> Option<(String, String)> {
     let output = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["ready", "--parent", epic_id, "--json"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -374,7 +366,7 @@ pub fn claim_next_child(
 
         // Atomic claim
         let claim = crate::bd_lock::with_lock(|| {
-            Command::new(bd_path)
+            bd.command()
                 .args(["update", id, "--claim"])
                 .stdin(std::process::Stdio::null())
                 .stdout(std::process::Stdio::null())
@@ -387,7 +379,7 @@ pub fn claim_next_child(
                 // Record hook on agent bead
                 let hook_arg = format!("hook={}", id);
                 let _ = crate::bd_lock::with_lock(|| {
-                    Command::new(bd_path)
+                    bd.command()
                         .args(["set-state", agent_bead_id, &hook_arg])
                         .stdin(std::process::Stdio::null())
                         .stdout(std::process::Stdio::null())
@@ -396,7 +388,7 @@ pub fn claim_next_child(
                 });
 
                 // Assess specification
-                if !super::lifecycle::assess_bead_specification(bd_path, id, agent_bead_id) {
+                if !super::lifecycle::assess_bead_specification(bd, id, agent_bead_id) {
                     info!(bead_id = %id, "child_rejected_trying_next");
                     continue;
                 }
@@ -419,9 +411,9 @@ pub fn claim_next_child(
 }
 
 /// Complete an epic: close it if eligible.
-pub fn complete_epic(bd_path: &str, epic_id: &str) -> bool {
+pub fn complete_epic(bd: &Bd, epic_id: &str) -> bool {
     let result = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["epic", "close-eligible"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -447,9 +439,9 @@ pub fn complete_epic(bd_path: &str, epic_id: &str) -> bool {
 }
 
 /// Get a bead's priority via bd show. Returns 2 (medium) if not found.
-fn get_bead_priority(bd_path: &str, bead_id: &str) -> i64 {
+fn get_bead_priority(bd: &Bd, bead_id: &str) -> i64 {
     let output = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["show", bead_id, "--json"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -501,9 +493,9 @@ pub fn check_worktree_dirty(worktree_path: &std::path::Path) -> Option<(String,
 }
 
 /// Get the epic ID from an agent bead's state labels.
-pub fn get_epic_from_state(bd_path: &str, agent_id: &str) -> Option<String> {
+pub fn get_epic_from_state(bd: &Bd, agent_id: &str) -> Option<String> {
     let output = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["show", agent_id, "--json"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -567,7 +559,7 @@ mod tests {
 
     #[test]
     fn group_beads_by_parent_groups_correctly() {
-        let items = vec![
+        let items = [
             json!({"id": "b1", "parent": "epic-1"}),
             json!({"id": "b2", "parent": "epic-1"}),
             json!({"id": "b3", "parent": "epic-2"}),
@@ -584,7 +576,7 @@ mod tests {
 
     #[test]
     fn find_standalone_beads_returns_beads_without_parent() {
-        let items = vec![
+        let items = [
             json!({"id": "b1", "parent": "epic-1"}),
             json!({"id": "b2"}),
             json!({"id": "b3", "parent": ""}),
@@ -1,12 +1,13 @@
 //! Agent registration, heartbeat, bead specification checks, and cleanup.
 
-use std::process::Command;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use tracing::{info, warn};
 
+use crate::bd::Bd;
+
 /// Result of agent registration.
 pub struct AgentSetup {
     pub agent_bead_id: String,
@@ -14,10 +15,10 @@ pub struct AgentSetup {
 
 /// Register an ephemeral agent bead and create a git worktree.
 /// Returns None if any step fails (logs warnings).
-pub fn register(bd_path: &str, session_id: &str) -> Option<AgentSetup> {
+pub fn register(bd: &Bd, session_id: &str) -> Option<AgentSetup> {
     // Create ephemeral agent bead with rig:ralph label
     let output = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args([
                 "create",
                 "--type=task",
@@ -53,7 +54,7 @@ pub fn register(bd_path: &str, session_id: &str) -> Option<AgentSetup> {
 
     // Set agent to in_progress
     let _ = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["update", &agent_bead_id, "--status=in_progress"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::null())
@@ -68,11 +69,7 @@ pub fn register(bd_path: &str, session_id: &str) -> Option<AgentSetup> {
 
 /// Start a background heartbeat thread that updates the agent bead periodically.
 /// Returns a stop flag that can be set to true to stop the heartbeat.
-pub fn start_heartbeat(
-    bd_path: String,
-    agent_bead_id: String,
-    interval_secs: u64,
-) -> Arc<AtomicBool> {
+pub fn start_heartbeat(bd: Bd, agent_bead_id: String, interval_secs: u64) -> Arc<AtomicBool> {
     let stop = Arc::new(AtomicBool::new(false));
     let stop_clone = Arc::clone(&stop);
 
@@ -85,7 +82,7 @@ pub fn start_heartbeat(
             }
             let now = chrono_now_iso();
             let result = crate::bd_lock::with_lock(|| {
-                Command::new(&bd_path)
+                bd.command()
                     .args([
                         "update",
                         &agent_bead_id,
@@ -158,9 +155,9 @@ pub fn check_bead_specification(bead: &serde_json::Value) -> Option<String> {
 /// Assess a claimed bead's specification quality.
 /// If under-specified, flags for human review, resets to open, releases the hook, and returns false.
 /// Returns true if the bead is ready for implementation.
-pub(crate) fn assess_bead_specification(bd_path: &str, bead_id: &str, agent_bead_id: &str) -> bool {
+pub(crate) fn assess_bead_specification(bd: &Bd, bead_id: &str, agent_bead_id: &str) -> bool {
     let output = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["show", bead_id, "--json"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -190,7 +187,7 @@ pub(crate) fn assess_bead_specification(bd_path: &str, bead_id: &str, agent_bead
     if let Some(reason) = check_bead_specification(&bead) {
         let notes = format!("Flagged by Ralph: {}", reason);
         let _ = crate::bd_lock::with_lock(|| {
-            Command::new(bd_path)
+            bd.command()
                 .args(["update", bead_id, "--notes", &notes])
                 .stdin(std::process::Stdio::null())
                 .stdout(std::process::Stdio::null())
@@ -199,7 +196,7 @@ pub(crate) fn assess_bead_specification(bd_path: &str, bead_id: &str, agent_bead
         });
 
         let _ = crate::bd_lock::with_lock(|| {
-            Command::new(bd_path)
+            bd.command()
                 .args(["human", bead_id])
                 .stdin(std::process::Stdio::null())
                 .stdout(std::process::Stdio::null())
@@ -207,8 +204,8 @@ pub(crate) fn assess_bead_specification(bd_path: &str, bead_id: &str, agent_bead
                 .output()
         });
 
-        reset_bead_to_open(bd_path, bead_id);
-        release_hook(bd_path, agent_bead_id);
+        reset_bead_to_open(bd, bead_id);
+        release_hook(bd, agent_bead_id);
 
         info!(bead_id = %bead_id, reason = %reason, "bead_under_specified");
         return false;
@@ -218,9 +215,9 @@ pub(crate) fn assess_bead_specification(bd_path: &str, bead_id: &str, agent_bead
 }
 
 /// Release the hook on this agent (clear the hook state dimension).
-pub fn release_hook(bd_path: &str, agent_bead_id: &str) {
+pub fn release_hook(bd: &Bd, agent_bead_id: &str) {
     let result = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args([
                 "set-state",
                 agent_bead_id,
@@ -249,17 +246,19 @@ pub fn release_hook(bd_path: &str, agent_bead_id: &str) {
 
 /// Release the currently hooked bead: clear the hook and reset the bead to open.
 /// Used during both stop (between iterations) and quit (full teardown).
-pub fn release_bead(bd_path: &str, agent_bead_id: &str, bead_id: &str) {
-    release_hook(bd_path, agent_bead_id);
-    reset_bead_to_open(bd_path, bead_id);
+/// Returns the bead's title if Claude already closed it.
+pub fn release_bead(bd: &Bd, agent_bead_id: &str, bead_id: &str) -> Option<String> {
+    release_hook(bd, agent_bead_id);
+    reset_bead_to_open(bd, bead_id)
 }
 
 /// Reset a bead's status to open so other agents can pick it up.
-/// Skips the reset if the bead was already closed (e.g. by Claude during the iteration).
-fn reset_bead_to_open(bd_path: &str, bead_id: &str) {
+/// Skips the reset if the bead was already closed (e.g. by Claude during the
+/// iteration) and returns its title (empty if it has none).
+fn reset_bead_to_open(bd: &Bd, bead_id: &str) -> Option<String> {
     // Check current status — don't reopen beads that Claude already closed
     let show = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["show", bead_id, "--json"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -275,21 +274,18 @@ fn reset_bead_to_open(bd_path: &str, bead_id: &str) {
     }) {
         let stdout = String::from_utf8_lossy(&o.stdout);
         if let Ok(val) = serde_json::from_str::<serde_json::Value>(&stdout) {
-            let status = val
-                .as_array()
-                .and_then(|arr| arr.first())
-                .unwrap_or(&val)
-                .get("status")
-                .and_then(|s| s.as_str());
+            let bead = val.as_array().and_then(|arr| arr.first()).unwrap_or(&val);
+            let status = bead.get("status").and_then(|s| s.as_str());
             if matches!(status, Some("closed")) {
                 info!(bead_id = %bead_id, "bead_already_closed_skipping_reset");
-                return;
+                let title = bead.get("title").and_then(|t| t.as_str()).unwrap_or("");
+                return Some(title.to_string());
             }
         }
     }
 
     let result = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["update", bead_id, "--status=open", "--assignee="])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::null())
@@ -309,21 +305,22 @@ fn reset_bead_to_open(bd_path: &str, bead_id: &str) {
             warn!(error = %e, "bead_reset_failed");
         }
     }
+    None
 }
 
 /// Clean up agent resources: release hook, close agent bead, merge + remove worktree.
-pub fn cleanup(bd_path: &str, agent_bead_id: &str, worktree_name: &str) {
+pub fn cleanup(bd: &Bd, agent_bead_id: &str, worktree_name: &str) {
     info!(agent_bead_id = %agent_bead_id, "agent_cleanup_start");
 
     // Release any hooked bead
-    release_hook(bd_path, agent_bead_id);
+    release_hook(bd, agent_bead_id);
 
     // Close the agent bead
-    cleanup_agent_bead(bd_path, agent_bead_id);
+    cleanup_agent_bead(bd, agent_bead_id);
 
     // Try to merge worktree branch to main before removal
-    if super::worktree::merge_worktree_to_main(worktree_name) {
-        super::worktree::remove_merged_worktree(bd_path, worktree_name);
+    if super::worktree::merge_worktree_to_main(bd.dir(), worktree_name) {
+        super::worktree::remove_merged_worktree(bd, worktree_name);
     } else {
         // Merge failed — leave worktree intact so user can resolve
         warn!(worktree_name = %worktree_name, "session_end_merge_failed_worktree_preserved");
@@ -331,9 +328,9 @@ pub fn cleanup(bd_path: &str, agent_bead_id: &str, worktree_name: &str) {
 }
 
 /// Close an agent bead (used during cleanup or when worktree creation fails).
-pub(crate) fn cleanup_agent_bead(bd_path: &str, agent_bead_id: &str) {
+pub(crate) fn cleanup_agent_bead(bd: &Bd, agent_bead_id: &str) {
     let result = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["close", agent_bead_id, "--reason=ralph session ended"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::null())
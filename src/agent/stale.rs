@@ -1,9 +1,9 @@
 //! Stale agent detection and recovery.
 
-use std::process::Command;
-
 use tracing::{info, warn};
 
+use crate::bd::Bd;
+
 /// A stale agent detected during recovery.
 #[derive(Clone)]
 pub struct StaleAgent {
@@ -31,7 +31,7 @@ pub enum ResumeResult {
 /// updated within `stale_threshold_secs`. For each, checks if they have a hook
 /// (a claimed bead). Returns only those with active hooks.
 pub fn find_stale_agents(
-    bd_path: &str,
+    bd: &Bd,
     stale_threshold_secs: u64,
     exclude_agent_id: Option<&str>,
 ) -> Vec<StaleAgent> {
@@ -47,7 +47,7 @@ pub fn find_stale_agents(
 
     // Find in_progress agent beads updated before cutoff
     let output = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args([
                 "list",
                 "--json",
@@ -100,14 +100,14 @@ pub fn find_stale_agents(
         }
 
         // Get labels via bd show to find the hook state
-        let hook_bead_id = match get_hook_from_labels(bd_path, agent_id) {
+        let hook_bead_id = match get_hook_from_labels(bd, agent_id) {
             Some(id) => id,
             None => {
                 // No hook — agent finished work but session ended without cleanup.
                 // Close the agent bead and remove its worktree.
-                super::lifecycle::cleanup_agent_bead(bd_path, agent_id);
+                super::lifecycle::cleanup_agent_bead(bd, agent_id);
                 let _ = crate::bd_lock::with_lock(|| {
-                    Command::new(bd_path)
+                    bd.command()
                         .args(["worktree", "remove", "--force", agent_id])
                         .stdin(std::process::Stdio::null())
                         .stdout(std::process::Stdio::null())
@@ -120,10 +120,10 @@ pub fn find_stale_agents(
         };
 
         // Get the hooked bead's title
-        let hooked_title = get_bead_title(bd_path, &hook_bead_id);
+        let hooked_title = get_bead_title(bd, &hook_bead_id);
 
         // Worktree name: epic ID if the agent had an epic, otherwise agent bead ID
-        let epic_id = super::epic::get_epic_from_state(bd_path, agent_id);
+        let epic_id = super::epic::get_epic_from_state(bd, agent_id);
         let has_epic = epic_id.is_some();
         let worktree_name = epic_id.unwrap_or_else(|| agent_id.to_string());
 
@@ -145,20 +145,20 @@ pub fn find_stale_agents(
 
 /// Resume a stale bead: claim it on our agent, mark old agent dead.
 /// If the bead was already retried once (has retry:1 label), escalate to human instead.
-pub fn resume_stale_bead(bd_path: &str, new_agent_id: &str, stale: &StaleAgent) -> ResumeResult {
+pub fn resume_stale_bead(bd: &Bd, new_agent_id: &str, stale: &StaleAgent) -> ResumeResult {
     // Check if this bead was already retried once
-    if has_label(bd_path, &stale.hooked_bead_id, "retry:1") {
-        escalate_to_human(bd_path, stale);
+    if has_label(bd, &stale.hooked_bead_id, "retry:1") {
+        escalate_to_human(bd, stale);
         return ResumeResult::EscalatedToHuman;
     }
 
     // Clear hook on stale agent
-    super::lifecycle::release_hook(bd_path, &stale.agent_bead_id);
+    super::lifecycle::release_hook(bd, &stale.agent_bead_id);
 
     // Set hook on our agent for the stale bead
     let hook_arg = format!("hook={}", stale.hooked_bead_id);
     let result = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["set-state", new_agent_id, &hook_arg])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::null())
@@ -180,13 +180,13 @@ pub fn resume_stale_bead(bd_path: &str, new_agent_id: &str, stale: &StaleAgent)
     };
 
     // Close stale agent bead
-    super::lifecycle::cleanup_agent_bead(bd_path, &stale.agent_bead_id);
+    super::lifecycle::cleanup_agent_bead(bd, &stale.agent_bead_id);
 
     // Only remove worktree for non-epic agents. Epic worktrees are reused
     // by the next worker who picks up the same epic.
     if !stale.has_epic {
         let _ = crate::bd_lock::with_lock(|| {
-            Command::new(bd_path)
+            bd.command()
                 .args(["worktree", "remove", &stale.worktree_name])
                 .stdin(std::process::Stdio::null())
                 .stdout(std::process::Stdio::null())
@@ -198,7 +198,7 @@ pub fn resume_stale_bead(bd_path: &str, new_agent_id: &str, stale: &StaleAgent)
     if hooked {
         // Mark retry:1 so next stale detection escalates to human
         let _ = crate::bd_lock::with_lock(|| {
-            Command::new(bd_path)
+            bd.command()
                 .args([
                     "set-state",
                     &stale.hooked_bead_id,
@@ -224,17 +224,17 @@ pub fn resume_stale_bead(bd_path: &str, new_agent_id: &str, stale: &StaleAgent)
 }
 
 /// Release a stale bead: clear hook, reset bead to open, clean up agent.
-pub fn release_stale_bead(bd_path: &str, stale: &StaleAgent) {
+pub fn release_stale_bead(bd: &Bd, stale: &StaleAgent) {
     // Clear hook and reset bead to open
-    super::lifecycle::release_bead(bd_path, &stale.agent_bead_id, &stale.hooked_bead_id);
+    super::lifecycle::release_bead(bd, &stale.agent_bead_id, &stale.hooked_bead_id);
 
     // Close stale agent bead
-    super::lifecycle::cleanup_agent_bead(bd_path, &stale.agent_bead_id);
+    super::lifecycle::cleanup_agent_bead(bd, &stale.agent_bead_id);
 
     // Only remove worktree for non-epic agents
     if !stale.has_epic {
         let _ = crate::bd_lock::with_lock(|| {
-            Command::new(bd_path)
+            bd.command()
                 .args(["worktree", "remove", &stale.worktree_name])
                 .stdin(std::process::Stdio::null())
                 .stdout(std::process::Stdio::null())
@@ -251,9 +251,9 @@ pub fn release_stale_bead(bd_path: &str, stale: &StaleAgent) {
 }
 
 /// Check if a bead has a specific label (e.g. "retry:1", "human").
-fn has_label(bd_path: &str, bead_id: &str, target: &str) -> bool {
+fn has_label(bd: &Bd, bead_id: &str, target: &str) -> bool {
     let output = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["show", bead_id, "--json"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -284,17 +284,17 @@ fn has_label(bd_path: &str, bead_id: &str, target: &str) -> bool {
 }
 
 /// Escalate a stuck bead to human review: release it, flag it, add a comment.
-fn escalate_to_human(bd_path: &str, stale: &StaleAgent) {
+fn escalate_to_human(bd: &Bd, stale: &StaleAgent) {
     // Release the hook and reset bead to open
-    super::lifecycle::release_bead(bd_path, &stale.agent_bead_id, &stale.hooked_bead_id);
+    super::lifecycle::release_bead(bd, &stale.agent_bead_id, &stale.hooked_bead_id);
 
     // Close stale agent bead
-    super::lifecycle::cleanup_agent_bead(bd_path, &stale.agent_bead_id);
+    super::lifecycle::cleanup_agent_bead(bd, &stale.agent_bead_id);
 
     // Only remove worktree for non-epic agents
     if !stale.has_epic {
         let _ = crate::bd_lock::with_lock(|| {
-            Command::new(bd_path)
+            bd.command()
                 .args(["worktree", "remove", &stale.worktree_name])
                 .stdin(std::process::Stdio::null())
                 .stdout(std::process::Stdio::null())
@@ -305,7 +305,7 @@ fn escalate_to_human(bd_path: &str, stale: &StaleAgent) {
 
     // Flag for human review
     let _ = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["label", "add", &stale.hooked_bead_id, "human"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::null())
@@ -320,7 +320,7 @@ fn escalate_to_human(bd_path: &str, stale: &StaleAgent) {
         stale.agent_bead_id,
     );
     let _ = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["comments", "add", &stale.hooked_bead_id, &comment])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::null())
@@ -337,9 +337,9 @@ fn escalate_to_human(bd_path: &str, stale: &StaleAgent) {
 
 /// Get the hook value from an agent bead's labels.
 /// Returns None if no hook is set or hook is "none".
-fn get_hook_from_labels(bd_path: &str, agent_id: &str) -> Option<String> {
+fn get_hook_from_labels(bd: &Bd, agent_id: &str) -> Option<String> {
     let output = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["show", agent_id, "--json"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -369,9 +369,9 @@ fn get_hook_from_labels(bd_path: &str, agent_id: &str) -> Option<String> {
 }
 
 /// Get a bead's title via bd show.
-fn get_bead_title(bd_path: &str, bead_id: &str) -> String {
+fn get_bead_title(bd: &Bd, bead_id: &str) -> String {
     let output = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["show", bead_id, "--json"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -1,13 +1,15 @@
 //! Git worktree creation, merging, and cleanup.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use tracing::{info, warn};
 
+use crate::bd::Bd;
+
 /// Get list of files that differ between main and a worktree branch.
-fn get_changed_files(worktree_name: &str) -> Vec<String> {
-    let repo_root = repo_root();
+fn get_changed_files(project_dir: &Path, worktree_name: &str) -> Vec<String> {
+    let repo_root = repo_root(project_dir);
     let diff_spec = format!("main...{}", worktree_name);
     let output = Command::new("git")
         .args(["diff", "--name-only", &diff_spec])
@@ -29,9 +31,9 @@ fn get_changed_files(worktree_name: &str) -> Vec<String> {
 
 /// Search for an existing open merge-conflict bead for this branch.
 /// Returns the bead ID if found.
-pub fn find_merge_conflict_bead(bd_path: &str, worktree_name: &str) -> Option<String> {
+pub fn find_merge_conflict_bead(bd: &Bd, worktree_name: &str) -> Option<String> {
     let output = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["list", "--json", "--status=open", "--limit=0"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -60,8 +62,8 @@ pub fn find_merge_conflict_bead(bd_path: &str, worktree_name: &str) -> Option<St
 
 /// File a P0 merge-conflict bead for Claude to resolve next iteration.
 /// Returns the new bead ID on success.
-pub fn file_merge_conflict_bead(bd_path: &str, worktree_name: &str) -> Option<String> {
-    let files = get_changed_files(worktree_name);
+pub fn file_merge_conflict_bead(bd: &Bd, worktree_name: &str) -> Option<String> {
+    let files = get_changed_files(bd.dir(), worktree_name);
     let files_display = if files.is_empty() {
         "Could not determine changed files".to_string()
     } else {
@@ -78,7 +80,7 @@ pub fn file_merge_conflict_bead(bd_path: &str, worktree_name: &str) -> Option<St
     );
 
     let output = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args([
                 "create",
                 &format!("--title={}", title),
@@ -110,13 +112,13 @@ pub fn file_merge_conflict_bead(bd_path: &str, worktree_name: &str) -> Option<St
 /// Closes the existing merge-conflict bead and files a new human-labeled bead.
 /// Returns the new human bead ID on success.
 pub fn escalate_merge_conflict(
-    bd_path: &str,
+    bd: &Bd,
     worktree_name: &str,
     existing_bead_id: &str,
 ) -> Option<String> {
     // Close the existing merge-conflict bead
     let _ = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args([
                 "close",
                 existing_bead_id,
@@ -128,7 +130,7 @@ pub fn escalate_merge_conflict(
             .output()
     });
 
-    let files = get_changed_files(worktree_name);
+    let files = get_changed_files(bd.dir(), worktree_name);
     let files_display = if files.is_empty() {
         "Could not determine changed files".to_string()
     } else {
@@ -146,7 +148,7 @@ pub fn escalate_merge_conflict(
     );
 
     let output = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args([
                 "create",
                 &format!("--title={}", title),
@@ -180,10 +182,11 @@ pub fn escalate_merge_conflict(
     Some(bead_id)
 }
 
-/// Attempt to merge the worktree branch into main from the repo root.
-/// Returns true if the merge succeeded, false if it failed (and aborts the merge).
-pub fn merge_worktree_to_main(worktree_name: &str) -> bool {
-    let repo_root = repo_root();
+/// Attempt to merge the worktree branch into main from the root of the repo
+/// in `project_dir`. Returns true if the merge succeeded, false if it failed
+/// (and aborts the merge).
+pub fn merge_worktree_to_main(project_dir: &Path, worktree_name: &str) -> bool {
+    let repo_root = repo_root(project_dir);
     info!(worktree_name = %worktree_name, "merge_worktree_start");
 
     let result = Command::new("git")
@@ -220,12 +223,12 @@ pub fn merge_worktree_to_main(worktree_name: &str) -> bool {
 }
 
 /// Remove worktree, revert .gitignore, and delete the merged branch.
-pub fn remove_merged_worktree(bd_path: &str, worktree_name: &str) {
-    let repo_root = repo_root();
+pub fn remove_merged_worktree(bd: &Bd, worktree_name: &str) {
+    let repo_root = repo_root(bd.dir());
 
     // Remove the worktree directory (--force handles untracked files like target/)
     let remove_result = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["worktree", "remove", "--force", worktree_name])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::null())
@@ -278,12 +281,13 @@ pub fn remove_merged_worktree(bd_path: &str, worktree_name: &str) {
 /// If a worktree directory already exists at `<repo-root>/<worktree_name>`,
 /// reuses it (all previous commits are preserved).
 /// Returns the worktree name and path, or None on failure.
-pub fn create_or_reuse_worktree(bd_path: &str, worktree_name: &str) -> Option<(String, PathBuf)> {
-    let worktree_path = resolve_worktree_path(worktree_name);
+pub fn create_or_reuse_worktree(bd: &Bd, worktree_name: &str) -> Option<(String, PathBuf)> {
+    let repo_root = repo_root(bd.dir());
+    let worktree_path = repo_root.join(worktree_name);
 
     // Check if worktree already exists — reuse it
     if worktree_path.exists() {
-        symlink_settings_local(&worktree_path);
+        symlink_settings_local(&repo_root, &worktree_path);
         ensure_beads_redirect(&worktree_path);
         info!(
             worktree_name = %worktree_name,
@@ -295,7 +299,7 @@ pub fn create_or_reuse_worktree(bd_path: &str, worktree_name: &str) -> Option<(S
 
     // Create new worktree
     let wt_output = crate::bd_lock::with_lock(|| {
-        Command::new(bd_path)
+        bd.command()
             .args(["worktree", "create", worktree_name])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -317,7 +321,7 @@ pub fn create_or_reuse_worktree(bd_path: &str, worktree_name: &str) -> Option<(S
         return None;
     }
 
-    symlink_settings_local(&worktree_path);
+    symlink_settings_local(&repo_root, &worktree_path);
     ensure_beads_redirect(&worktree_path);
 
     info!(
@@ -359,10 +363,11 @@ fn ensure_beads_redirect(worktree_path: &std::path::Path) {
     }
 }
 
-/// Get the repo root path.
-fn repo_root() -> PathBuf {
+/// The root of the repo in `project_dir` (the directory itself outside one).
+fn repo_root(project_dir: &Path) -> PathBuf {
     Command::new("git")
         .args(["rev-parse", "--show-toplevel"])
+        .current_dir(project_dir)
         .output()
         .ok()
         .and_then(|o| {
@@ -373,33 +378,11 @@ fn repo_root() -> PathBuf {
             }
         })
         .map(|s| PathBuf::from(s.trim()))
-        .unwrap_or_else(|| std::env::current_dir().unwrap_or_default())
-}
-
-/// Resolve the worktree path by asking git.
-fn resolve_worktree_path(worktree_name: &str) -> PathBuf {
-    repo_root().join(worktree_name)
+        .unwrap_or_else(|| project_dir.to_path_buf())
 }
 
 /// Symlink .claude/settings.local.json from the main repo into a worktree.
-fn symlink_settings_local(worktree_path: &std::path::Path) {
-    let main_root = Command::new("git")
-        .args(["rev-parse", "--show-toplevel"])
-        .output()
-        .ok()
-        .and_then(|o| {
-            if o.status.success() {
-                String::from_utf8(o.stdout).ok()
-            } else {
-                None
-            }
-        })
-        .map(|s| PathBuf::from(s.trim()));
-
-    let Some(main_root) = main_root else {
-        return;
-    };
-
+fn symlink_settings_local(main_root: &Path, worktree_path: &Path) {
     let source = main_root.join(".claude").join("settings.local.json");
     if !source.exists() {
         return;
@@ -0,0 +1,115 @@
+//! Noticing when the agent ends an iteration by asking the user something.
+//!
+//! Agents sometimes stop to ask for clarification ("Should I keep the old
+//! API or remove it?") and the loop would otherwise barrel on with a fresh
+//! iteration that never sees an answer. The self-report's `question` field
+//! is taken as is; without one, the end of the final message is checked
+//! with simple heuristics. With `behavior.pause_on_question`, a question
+//! holds auto-continue until the user answers it (the answer goes to the
+//! next iteration through the instruction queue) or skips it.
+
+use crate::self_report::SelfReport;
+
+/// Longest question kept, in characters.
+const MAX_LEN: usize = 500;
+
+/// Phrases that mark a question as meant for the user rather than rhetorical.
+const CUES: [&str; 14] = [
+    "you",
+    "should i",
+    "shall i",
+    "can i",
+    "may i",
+    "do i",
+    "would it",
+    "is it ok",
+    "is that ok",
+    "which",
+    "prefer",
+    "let me know",
+    "please confirm",
+    "please advise",
+];
+
+/// The question the agent ended `final_text` with, if any. A question in the
+/// self-report wins over the heuristics.
+pub fn detect(final_text: &str, report: Option<&SelfReport>) -> Option<String> {
+    if let Some(question) = report
+        .and_then(|r| r.question.as_deref())
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+    {
+        return Some(truncate(question));
+    }
+    let line = last_prose_line(final_text)?;
+    let lower = line.to_lowercase();
+    let asks = line.ends_with('?')
+        || lower.starts_with("let me know")
+        || lower.starts_with("please confirm");
+    (asks && CUES.iter().any(|cue| lower.contains(cue))).then(|| truncate(line))
+}
+
+/// The last non-empty line outside fenced code blocks, without list,
+/// quote or emphasis markup.
+fn last_prose_line(text: &str) -> Option<&str> {
+    let mut in_fence = false;
+    let mut last = None;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if !in_fence && !trimmed.is_empty() {
+            last = Some(trimmed);
+        }
+    }
+    let line = last?.trim_start_matches(['-', '*', '>', ' ']);
+    Some(line.trim_matches(['*', '_', ' ']))
+}
+
+fn truncate(text: &str) -> String {
+    match text.char_indices().nth(MAX_LEN) {
+        Some((end, _)) => format!("{}\u{2026}", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trailing_question_for_the_user_is_detected() {
+        let text =
+            "I added the parser.\n\n**Should I also remove the old API, or keep it for now?**\n";
+        assert_eq!(
+            detect(text, None).as_deref(),
+            Some("Should I also remove the old API, or keep it for now?")
+        );
+        assert!(detect("Let me know which fixture to use.", None).is_some());
+    }
+
+    #[test]
+    fn statements_rhetorical_questions_and_code_are_not_questions() {
+        assert_eq!(detect("All tests pass. Closing bd-12.", None), None);
+        assert_eq!(detect("Why did it fail? A missing fixture.", None), None);
+        assert_eq!(detect("What went wrong?", None), None);
+        let text = "Done.\n```ralph-report\n{\"confidence\": 0.9}\n```\n";
+        assert_eq!(detect(text, None), None);
+        let text = "Fixed.\n```rust\n// do you need this?\n```";
+        assert_eq!(detect(text, None), None);
+    }
+
+    #[test]
+    fn self_report_question_wins() {
+        let report = SelfReport {
+            question: Some(" Keep the v1 endpoint? ".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            detect("Done.", Some(&report)).as_deref(),
+            Some("Keep the v1 endpoint?")
+        );
+    }
+}
@@ -4,4 +4,4 @@ mod output;
 mod polling;
 mod state;
 
-pub use state::{App, AppStatus, PendingDep};
+pub use state::{App, AppStatus, BashRun, PendingDep, ToolCallLine, config_base_mtimes};
@@ -1,7 +1,13 @@
-use ratatui::text::Line;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 
-use super::state::App;
+use super::state::{App, BlockContent, ResultBlock, ToolCallLine};
+use crate::config::DisplayConfig;
+use crate::config::MAX_RESULT_PREVIEW_LINES;
+use crate::output::indent_line;
+use crate::output_scripts::LineAction;
+use crate::ui::{Highlights, ToolResultPreview};
 
 impl App {
     pub fn visual_line_count(&mut self) -> u16 {
@@ -11,6 +17,7 @@ impl App {
         if let Some(cached) = self.cached_visual_line_count {
             return cached;
         }
+        let started = std::time::Instant::now();
         // Include both completed lines and the current partial line
         let w = self.selected_worker;
         let mut content: Vec<Line> = self.workers[w].output_lines.to_vec();
@@ -22,6 +29,7 @@ impl App {
             .wrap(Wrap { trim: false });
         let count = paragraph.line_count(self.main_pane_width) as u16;
         self.cached_visual_line_count = Some(count);
+        self.debug_stats.visual_line_count = Some(started.elapsed());
         count
     }
 
@@ -54,7 +62,14 @@ impl App {
 
     /// Adds a styled line to the output.
     pub fn add_line(&mut self, line: Line<'static>) {
-        let w = self.selected_worker;
+        self.add_worker_line(self.selected_worker, line);
+    }
+
+    /// Adds a styled line to worker `w`'s output.
+    pub fn add_worker_line(&mut self, w: usize, line: Line<'static>) {
+        let Some(line) = self.script_line(w, line) else {
+            return;
+        };
         self.workers[w].output_lines.push(line);
         self.cached_visual_line_count = None;
         if self.is_auto_following {
@@ -62,6 +77,231 @@ impl App {
         }
     }
 
+    /// Run `line` through the project's output scripts; `None` hides it. A
+    /// rewritten line keeps the style of its first span.
+    fn script_line(&mut self, w: usize, line: Line<'static>) -> Option<Line<'static>> {
+        if self.output_scripts.is_empty() {
+            return Some(line);
+        }
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        let action = self.output_scripts.apply(&text);
+        let error_style = Style::default().fg(Color::Yellow);
+        for error in self.output_scripts.take_errors() {
+            self.workers[w].output_lines.push(Line::styled(
+                format!("[Output script: {error}]"),
+                error_style,
+            ));
+        }
+        if let Some(hint) = self.output_scripts.take_hint() {
+            self.set_hint(hint);
+        }
+        match action {
+            LineAction::Keep => Some(line),
+            LineAction::Hide => None,
+            LineAction::Replace(text) => {
+                let style = line.spans.first().map(|s| s.style).unwrap_or_default();
+                Some(Line::styled(text, style).style(line.style))
+            }
+        }
+    }
+
+    /// Show `line` as the live preview of an in-progress tool call, replacing
+    /// the previous preview.
+    pub fn set_tool_preview(&mut self, line: Line<'static>) {
+        let w = self.selected_worker;
+        match self.workers[w].tool_preview_line {
+            Some(i) if i < self.workers[w].output_lines.len() => {
+                self.workers[w].output_lines[i] = line;
+                self.cached_visual_line_count = None;
+            }
+            _ => {
+                self.workers[w].tool_preview_line = Some(self.workers[w].output_lines.len());
+                self.add_line(line);
+            }
+        }
+    }
+
+    /// Remove the live tool call preview, if any.
+    pub fn clear_tool_preview(&mut self) {
+        let w = self.selected_worker;
+        let worker = &mut self.workers[w];
+        let Some(i) = worker.tool_preview_line.take() else {
+            return;
+        };
+        if i < worker.output_lines.len() {
+            worker.output_lines.remove(i);
+            for block in worker.result_blocks.iter_mut().filter(|b| b.start > i) {
+                block.start -= 1;
+            }
+            for section in worker.sections.iter_mut().filter(|s| s.start > i) {
+                section.start -= 1;
+            }
+            self.cached_visual_line_count = None;
+        }
+    }
+
+    /// Adds a tool call (if known) and its result, remembering where they
+    /// landed so they can be re-rendered later.
+    ///
+    /// A call identical to the one immediately above it (nothing else output
+    /// in between) is folded into that entry with a `×N` repeat badge.
+    pub fn add_tool_result(&mut self, call: Option<ToolCallLine>, preview: ToolResultPreview) {
+        let w = self.selected_worker;
+        let output_len = self.workers[w].output_lines.len();
+        let repeats_last = match (&call, self.workers[w].result_blocks.last()) {
+            (Some(call), Some(last)) => {
+                last.start + last.len == output_len
+                    && matches!(&last.content, BlockContent::Tool { call: Some(c), .. } if c.key == call.key)
+            }
+            _ => false,
+        };
+
+        if repeats_last {
+            let block = self.workers[w]
+                .result_blocks
+                .last_mut()
+                .expect("checked above");
+            if let BlockContent::Tool { results, .. } = &mut block.content {
+                results.push(preview);
+            }
+            let start = block.start;
+            self.workers[w].output_lines.truncate(start);
+        } else {
+            self.workers[w].result_blocks.push(ResultBlock {
+                start: output_len,
+                len: 0,
+                content: BlockContent::Tool {
+                    call,
+                    results: vec![preview],
+                },
+            });
+        }
+        self.render_last_block();
+    }
+
+    /// Adds lines shown only in verbose output (usage summaries, stderr),
+    /// remembering where they landed so compact mode can hide them.
+    pub fn add_verbose_lines(&mut self, lines: Vec<Line<'static>>) {
+        let w = self.selected_worker;
+        let start = self.workers[w].output_lines.len();
+        self.workers[w].result_blocks.push(ResultBlock {
+            start,
+            len: 0,
+            content: BlockContent::Verbose(lines),
+        });
+        self.render_last_block();
+    }
+
+    /// Render the selected worker's newest block onto the end of its output.
+    fn render_last_block(&mut self) {
+        let w = self.selected_worker;
+        let block = self.workers[w].result_blocks.last().expect("just pushed");
+        let lines = render_block(
+            block,
+            &RenderOptions {
+                preview_lines: self.config.display.result_preview_lines,
+                expanded: self.expand_repeated_tools,
+                show_diffs: self.show_edit_diffs,
+                compact: self.compact_output,
+                highlights: &self.highlights,
+            },
+        );
+        let len = lines.len();
+        self.cached_visual_line_count = None;
+        for line in lines {
+            self.add_line(line);
+        }
+        if let Some(block) = self.workers[w].result_blocks.last_mut() {
+            block.len = len;
+        }
+    }
+
+    /// Change how many lines of each tool result are shown (session only) and
+    /// re-render every result already in the output. Returns the new value.
+    pub fn set_result_preview_lines(&mut self, lines: usize) -> usize {
+        let lines = lines.min(MAX_RESULT_PREVIEW_LINES);
+        if lines != self.config.display.result_preview_lines {
+            self.config.display.result_preview_lines = lines;
+            self.rerender_result_blocks();
+        }
+        lines
+    }
+
+    /// Apply reloaded `[display]` settings, recompiling highlight rules and
+    /// re-rendering existing results if anything visible changed.
+    pub fn apply_display_config(&mut self, display: DisplayConfig) {
+        let changed = display.result_preview_lines != self.config.display.result_preview_lines
+            || display.highlights != self.config.display.highlights;
+        if display.highlights != self.config.display.highlights {
+            self.highlights = Highlights::compile(&display.highlights);
+        }
+        self.config.display = display;
+        if changed {
+            self.rerender_result_blocks();
+        }
+    }
+
+    /// Show every result of repeated tool calls, or only the latest. Returns the new state.
+    pub fn toggle_repeated_tools_expanded(&mut self) -> bool {
+        self.expand_repeated_tools = !self.expand_repeated_tools;
+        self.rerender_result_blocks();
+        self.expand_repeated_tools
+    }
+
+    /// Switch between compact output (tool calls and assistant text only) and
+    /// verbose output. Returns whether compact output is now on.
+    pub fn toggle_compact_output(&mut self) -> bool {
+        self.compact_output = !self.compact_output;
+        self.rerender_result_blocks();
+        self.compact_output
+    }
+
+    /// Show or hide the diff under Edit calls. Returns the new state.
+    pub fn toggle_edit_diffs(&mut self) -> bool {
+        self.show_edit_diffs = !self.show_edit_diffs;
+        self.rerender_result_blocks();
+        self.show_edit_diffs
+    }
+
+    /// Re-render every tracked tool block in place with the current display settings.
+    fn rerender_result_blocks(&mut self) {
+        let options = RenderOptions {
+            preview_lines: self.config.display.result_preview_lines,
+            expanded: self.expand_repeated_tools,
+            show_diffs: self.show_edit_diffs,
+            compact: self.compact_output,
+            highlights: &self.highlights,
+        };
+        for worker in &mut self.workers {
+            let old = std::mem::take(&mut worker.output_lines);
+            let mut rebuilt = Vec::with_capacity(old.len());
+            let mut cursor = 0;
+            // Sections start between blocks: shift each by the change so far
+            let mut sections = worker.sections.iter_mut().peekable();
+            for block in &mut worker.result_blocks {
+                while let Some(section) = sections.next_if(|s| s.start <= block.start) {
+                    section.start = section.start + rebuilt.len() - cursor;
+                }
+                rebuilt.extend_from_slice(&old[cursor..block.start]);
+                cursor = block.start + block.len;
+                let rendered = render_block(block, &options);
+                block.start = rebuilt.len();
+                block.len = rendered.len();
+                rebuilt.extend(rendered);
+            }
+            for section in sections {
+                section.start = section.start + rebuilt.len() - cursor;
+            }
+            rebuilt.extend_from_slice(&old[cursor..]);
+            // The preview follows every block, so it moves by the total change
+            worker.tool_preview_line = worker
+                .tool_preview_line
+                .and_then(|i| (i + rebuilt.len()).checked_sub(old.len()));
+            worker.output_lines = rebuilt;
+        }
+        self.cached_visual_line_count = None;
+    }
+
     /// Adds a plain text line to the output (convenience method).
     pub fn add_text_line(&mut self, text: String) {
         self.add_line(Line::raw(text));
@@ -103,3 +343,322 @@ impl App {
         self.in_indented_text = false;
     }
 }
+
+/// How blocks are rendered; mirrors the App's display toggles.
+struct RenderOptions<'a> {
+    preview_lines: usize,
+    expanded: bool,
+    show_diffs: bool,
+    compact: bool,
+    highlights: &'a Highlights,
+}
+
+/// Render a block. Tool blocks show the call (with a `×N` badge when
+/// repeated) followed by its latest result, or every result when `expanded`;
+/// compact output keeps only the call and drops verbose-only lines.
+fn render_block(block: &ResultBlock, options: &RenderOptions) -> Vec<Line<'static>> {
+    let RenderOptions {
+        preview_lines,
+        expanded,
+        show_diffs,
+        compact,
+        highlights,
+    } = *options;
+    let (call, results) = match &block.content {
+        BlockContent::Verbose(_) if compact => return Vec::new(),
+        BlockContent::Verbose(lines) => return lines.clone(),
+        BlockContent::Tool { call, results } => (call, results),
+    };
+    let Some(call) = call else {
+        if compact {
+            return Vec::new();
+        }
+        return results
+            .iter()
+            .flat_map(|r| r.styled(preview_lines, highlights))
+            .collect();
+    };
+
+    let runs = results.len();
+    let mut call_line = call.line.clone();
+    if runs > 1 {
+        call_line.spans.push(Span::styled(
+            format!(" \u{d7}{runs}"),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    let mut lines = vec![call_line];
+    if compact {
+        return lines;
+    }
+    if show_diffs {
+        lines.extend(call.diff.iter().cloned().map(indent_line));
+    }
+
+    let dim = Style::default().fg(Color::DarkGray);
+    if expanded && runs > 1 {
+        for (i, result) in results.iter().enumerate() {
+            lines.push(Line::from(Span::styled(
+                format!("  run {}/{runs}", i + 1),
+                dim,
+            )));
+            lines.extend(
+                result
+                    .styled(preview_lines, highlights)
+                    .into_iter()
+                    .map(indent_line),
+            );
+        }
+    } else {
+        let latest = results.last().expect("tool blocks always hold a result");
+        lines.extend(
+            latest
+                .styled(preview_lines, highlights)
+                .into_iter()
+                .map(indent_line),
+        );
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LoadedConfig;
+
+    fn app() -> App {
+        App::new("test".into(), None, LoadedConfig::default_for_test(), None)
+    }
+
+    fn call(tool: &str, input: &str) -> ToolCallLine {
+        ToolCallLine {
+            line: Line::raw(format!("{tool}({input})")),
+            key: format!("{tool}\0{input}"),
+            diff: Vec::new(),
+        }
+    }
+
+    fn texts(app: &App) -> Vec<String> {
+        app.workers[0]
+            .output_lines
+            .iter()
+            .map(|l| l.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn set_result_preview_lines_rerenders_existing_results() {
+        let mut app = app();
+        app.add_text_line("before".into());
+        app.add_tool_result(
+            Some(call("Bash", "ls")),
+            ToolResultPreview::new("1\n2\n3\n4\n5", false),
+        );
+        app.add_text_line("after".into());
+        assert_eq!(texts(&app).len(), 8); // before + call + summary + 3 + "more" + after
+
+        app.set_result_preview_lines(5);
+        let lines = texts(&app);
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[0], "before");
+        assert_eq!(lines[7], "    5");
+        assert_eq!(lines[8], "after");
+
+        app.set_result_preview_lines(0);
+        let lines = texts(&app);
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[3], "    (5 more lines)");
+        assert_eq!(lines[4], "after");
+    }
+
+    #[test]
+    fn set_result_preview_lines_tracks_multiple_blocks() {
+        let mut app = app();
+        app.add_tool_result(None, ToolResultPreview::new("a\nb", false));
+        app.add_tool_result(None, ToolResultPreview::new("c\nd", true));
+
+        app.set_result_preview_lines(1);
+        app.set_result_preview_lines(2);
+        let lines = texts(&app);
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[4], "  c");
+        assert_eq!(lines[5], "  d");
+        assert_eq!(app.workers[0].result_blocks[1].start, 3);
+    }
+
+    #[test]
+    fn set_result_preview_lines_clamps_to_max() {
+        let mut app = app();
+        assert_eq!(
+            app.set_result_preview_lines(usize::MAX),
+            MAX_RESULT_PREVIEW_LINES
+        );
+    }
+
+    #[test]
+    fn tool_preview_is_replaced_in_place_and_removed() {
+        let mut app = app();
+        app.add_tool_result(None, ToolResultPreview::new("a", false));
+        app.set_tool_preview(Line::raw("Write(/tm"));
+        app.set_tool_preview(Line::raw("Write(/tmp/x"));
+        assert_eq!(texts(&app).last().unwrap(), "Write(/tmp/x");
+        assert_eq!(texts(&app).len(), 3);
+
+        // Lines added after the preview keep their block positions when it goes
+        app.add_tool_result(None, ToolResultPreview::new("b", false));
+        app.clear_tool_preview();
+        let lines = texts(&app);
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|l| !l.starts_with("Write")));
+        assert_eq!(app.workers[0].result_blocks[1].start, 2);
+        assert!(app.workers[0].tool_preview_line.is_none());
+    }
+
+    #[test]
+    fn tool_preview_follows_rerendered_results() {
+        let mut app = app();
+        app.add_tool_result(None, ToolResultPreview::new("1\n2\n3\n4", false));
+        app.set_tool_preview(Line::raw("Bash(ls"));
+        app.set_result_preview_lines(0);
+        app.set_tool_preview(Line::raw("Bash(ls -la"));
+        assert_eq!(texts(&app).last().unwrap(), "Bash(ls -la");
+        app.clear_tool_preview();
+        assert_eq!(texts(&app).len(), 2);
+    }
+
+    #[test]
+    fn iteration_sections_follow_rerendered_results() {
+        let mut app = app();
+        app.add_tool_result(None, ToolResultPreview::new("1\n2\n3\n4", false));
+        app.begin_iteration_section(0);
+        app.add_text_line("second".into());
+        app.add_tool_result(None, ToolResultPreview::new("5\n6\n7\n8", false));
+        app.begin_iteration_section(0);
+        app.add_text_line("third".into());
+        app.set_result_preview_lines(0);
+        app.set_result_preview_lines(4);
+        let starts: Vec<String> = app.workers[0]
+            .sections
+            .iter()
+            .map(|s| texts(&app)[s.start].clone())
+            .collect();
+        assert_eq!(starts, vec!["second", "third"]);
+        assert!(app.workers[0].sections[0].collapsed);
+    }
+
+    #[test]
+    fn edit_diffs_render_under_the_call_and_toggle() {
+        let mut app = app();
+        let mut edit = call("Edit", "a.rs");
+        edit.diff = vec![Line::raw("- old"), Line::raw("+ new")];
+        app.add_tool_result(Some(edit), ToolResultPreview::new("ok", false));
+        app.add_text_line("after".into());
+        assert_eq!(texts(&app)[..3], ["Edit(a.rs)", "  - old", "  + new"]);
+
+        assert!(!app.toggle_edit_diffs());
+        let lines = texts(&app);
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], "  \u{2705} (1 lines, 2 chars)");
+        assert_eq!(lines[3], "after");
+    }
+
+    #[test]
+    fn compact_output_hides_results_and_verbose_lines() {
+        let mut app = app();
+        let mut edit = call("Edit", "a.rs");
+        edit.diff = vec![Line::raw("- old")];
+        app.add_tool_result(Some(edit), ToolResultPreview::new("ok", false));
+        app.add_text_line("  assistant text".into());
+        app.add_verbose_lines(vec![Line::raw("Cost: $0.05")]);
+        app.add_tool_result(None, ToolResultPreview::new("orphan", false));
+        assert_eq!(texts(&app).len(), 8);
+
+        assert!(app.toggle_compact_output());
+        assert_eq!(texts(&app), ["Edit(a.rs)", "  assistant text"]);
+
+        app.add_verbose_lines(vec![Line::raw("[stderr] oops")]);
+        app.add_tool_result(
+            Some(call("Read", "b.rs")),
+            ToolResultPreview::new("x", false),
+        );
+        assert_eq!(texts(&app).len(), 3);
+
+        assert!(!app.toggle_compact_output());
+        let lines = texts(&app);
+        assert_eq!(lines.len(), 12);
+        assert_eq!(lines[5], "Cost: $0.05");
+        assert_eq!(lines[8], "[stderr] oops");
+        assert_eq!(lines[9], "Read(b.rs)");
+    }
+
+    #[test]
+    fn consecutive_identical_calls_collapse_with_badge() {
+        let mut app = app();
+        for out in ["fail 1", "fail 2", "fail 3"] {
+            app.add_tool_result(
+                Some(call("Bash", "cargo test")),
+                ToolResultPreview::new(out, true),
+            );
+        }
+
+        let lines = texts(&app);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "Bash(cargo test) \u{d7}3");
+        assert_eq!(lines[2], "    fail 3");
+        assert_eq!(app.workers[0].result_blocks.len(), 1);
+    }
+
+    #[test]
+    fn different_or_interrupted_calls_do_not_collapse() {
+        let mut app = app();
+        app.add_tool_result(Some(call("Bash", "ls")), ToolResultPreview::new("a", false));
+        app.add_tool_result(
+            Some(call("Bash", "pwd")),
+            ToolResultPreview::new("b", false),
+        );
+        app.add_text_line("  thinking".into());
+        app.add_tool_result(
+            Some(call("Bash", "pwd")),
+            ToolResultPreview::new("c", false),
+        );
+
+        assert_eq!(app.workers[0].result_blocks.len(), 3);
+        assert!(texts(&app).iter().all(|l| !l.contains('\u{d7}')));
+    }
+
+    #[test]
+    fn expanding_repeats_shows_every_result() {
+        let mut app = app();
+        app.add_tool_result(
+            Some(call("Bash", "make")),
+            ToolResultPreview::new("one", true),
+        );
+        app.add_tool_result(
+            Some(call("Bash", "make")),
+            ToolResultPreview::new("two", false),
+        );
+        app.add_text_line("after".into());
+
+        assert!(app.toggle_repeated_tools_expanded());
+        let lines = texts(&app);
+        assert_eq!(
+            lines,
+            vec![
+                "Bash(make) \u{d7}2",
+                "  run 1/2",
+                "  \u{274c} (1 lines, 3 chars)",
+                "    one",
+                "  run 2/2",
+                "  \u{2705} (1 lines, 3 chars)",
+                "    two",
+                "after",
+            ]
+        );
+
+        assert!(!app.toggle_repeated_tools_expanded());
+        assert_eq!(texts(&app).len(), 4);
+        assert_eq!(texts(&app)[3], "after");
+    }
+}
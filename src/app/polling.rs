@@ -1,15 +1,16 @@
 use std::sync::Arc;
 use std::sync::mpsc::{self, TryRecvError};
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 use tracing::{debug, info, warn};
 
-use crate::config::{get_project_config_path, reload_config};
+use crate::bd::Bd;
+use crate::config::{get_project_config_path, reload_config, resolve_prompt_path};
 use crate::logging;
 use crate::startup::get_file_mtime;
 use crate::work_source::BeadsWorkSource;
 
-use super::state::{App, AppStatus};
+use super::state::{App, AppStatus, config_base_mtimes};
 
 /// How often to probe the beads DB for mutations.
 const BOARD_MUTATION_POLL_INTERVAL: Duration = Duration::from_secs(60);
@@ -20,9 +21,9 @@ const BOARD_MUTATION_POLL_INTERVAL: Duration = Duration::from_secs(60);
 /// Signal = bytes(`bd count --json`) ++ bytes(`bd list --all --sort updated -n 1 --json --flat`).
 /// Total wall time ~0.9s on a warm cache, <1KB payload. Returns `None` if
 /// either bd call fails — caller should skip the tick and retry next interval.
-fn compute_board_signature(bd_path: &str) -> Option<Vec<u8>> {
+fn compute_board_signature(bd: &Bd) -> Option<Vec<u8>> {
     let count_out = crate::bd_lock::with_lock(|| {
-        std::process::Command::new(bd_path)
+        bd.command()
             .args(["count", "--json"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -35,7 +36,7 @@ fn compute_board_signature(bd_path: &str) -> Option<Vec<u8>> {
     }
 
     let newest_out = crate::bd_lock::with_lock(|| {
-        std::process::Command::new(bd_path)
+        bd.command()
             .args([
                 "list", "--all", "--sort", "updated", "-n", "1", "--json", "--flat",
             ])
@@ -79,11 +80,11 @@ impl App {
         }
 
         // Throttle: poll every 2 seconds
-        if self.last_bead_poll.elapsed() < Duration::from_secs(2) {
+        if self.clock.elapsed(self.last_bead_poll) < Duration::from_secs(2) {
             return;
         }
 
-        self.last_bead_poll = Instant::now();
+        self.last_bead_poll = self.clock.now();
 
         // Kick off background detect_current
         let (tx, rx) = mpsc::channel();
@@ -94,19 +95,41 @@ impl App {
         self.bead_poll_rx = Some(rx);
     }
 
+    /// Flag edits to the prompt file made since the latest iteration started.
+    fn poll_prompt_file(&mut self) {
+        let mtime = resolve_prompt_path(&self.project_dir).and_then(|p| get_file_mtime(&p));
+        if mtime != self.prompt_mtime && !self.prompt_changed {
+            info!("prompt_file_changed");
+            self.prompt_changed = true;
+            self.dirty = true;
+        }
+    }
+
+    /// Note that worker `w` started with `prompt`: snapshot it into the
+    /// transcript and clear the prompt-changed indicator.
+    pub fn mark_prompt_used(&mut self, w: usize, prompt: &str) {
+        self.prompt_mtime = resolve_prompt_path(&self.project_dir).and_then(|p| get_file_mtime(&p));
+        self.prompt_changed = false;
+        let iteration = self.workers[w].current_iteration;
+        if let Some(transcript) = &mut self.transcript {
+            transcript.record_prompt(w, iteration, prompt);
+        }
+    }
+
     pub fn poll_config(&mut self) {
         // Throttle: poll every 2 seconds
-        if self.last_config_poll.elapsed() < Duration::from_secs(2) {
+        if self.clock.elapsed(self.last_config_poll) < Duration::from_secs(2) {
             return;
         }
 
-        self.last_config_poll = Instant::now();
+        self.last_config_poll = self.clock.now();
+        self.poll_prompt_file();
 
         // Check project config mtime (also detect new project config appearing)
         let project_path = self
             .project_config_path
             .clone()
-            .or_else(get_project_config_path);
+            .or_else(|| get_project_config_path(&self.project_dir));
         let project_mtime = project_path.as_ref().and_then(|p| get_file_mtime(p));
         let project_changed = match (project_mtime, self.project_config_mtime) {
             (Some(current), Some(prev)) => current != prev,
@@ -115,21 +138,40 @@ impl App {
             (None, None) => false,
         };
 
-        if !project_changed {
-            return;
-        }
+        // Then the files it extends (may also appear or disappear)
+        let changed_base = self
+            .config_base_mtimes
+            .iter()
+            .find(|(path, prev)| get_file_mtime(path) != *prev)
+            .map(|(path, _)| path.clone());
+
+        let trigger = match (project_changed, changed_base) {
+            (true, _) => project_path.clone(),
+            (false, Some(base)) => Some(base),
+            (false, None) => return,
+        };
 
         self.dirty = true;
 
         self.project_config_mtime = project_mtime;
         // Update project path (may have appeared or disappeared)
         self.project_config_path = project_path;
+        // The extends chain itself may have changed
+        self.config_base_mtimes = config_base_mtimes(self.project_config_path.as_deref());
 
         let reloaded = reload_config(self.project_config_path.as_ref());
+        let trigger = trigger.as_deref().and_then(|p| p.file_name()).map_or_else(
+            || "config".to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        );
+        info!(trigger, "config_reload_triggered");
 
         // Check if log level changed and update if we have a reload handle
         let new_log_level = &reloaded.config.logging.level;
-        if new_log_level != &self.current_log_level
+        if self.log_bump.until.is_some() {
+            // Applied when the temporary debug level ends
+            self.current_log_level = new_log_level.clone();
+        } else if new_log_level != &self.current_log_level
             && let Some(ref handle) = self.log_level_handle
         {
             match logging::update_log_level(handle, new_log_level) {
@@ -150,7 +192,10 @@ impl App {
         // Reconstruct work source if bd_path changed
         let new_bd_path = &reloaded.config.behavior.bd_path;
         if new_bd_path != &self.config.behavior.bd_path {
-            self.work_source = Arc::new(BeadsWorkSource::new(new_bd_path.clone()));
+            self.work_source = Arc::new(BeadsWorkSource::new(Bd::new(
+                new_bd_path.clone(),
+                self.project_dir.clone(),
+            )));
             self.clear_pending_work_ops();
         }
 
@@ -169,11 +214,24 @@ impl App {
             }
         }
 
+        self.apply_display_config(reloaded.config.display.clone());
+        if reloaded.config.behavior.keep_awake != self.config.behavior.keep_awake {
+            self.wake_lock
+                .set_enabled(reloaded.config.behavior.keep_awake);
+        }
+        if reloaded.config.behavior.resume_conversation != self.config.behavior.resume_conversation
+        {
+            self.resume_conversation = reloaded.config.behavior.resume_conversation;
+        }
         self.config = reloaded.config;
-        self.project_config_error = reloaded.project_error;
+        self.project_config_error = reloaded.project_error.map(|e| e.to_string());
 
         if self.project_config_error.is_none() {
-            self.config_reloaded_at = Some(Instant::now());
+            self.config_reloaded_at = Some(self.clock.now());
+            match reloaded.clamped.first() {
+                Some(adjustment) => self.set_hint(format!("Config reloaded; {adjustment}")),
+                None => self.set_hint(format!("Config reloaded ({trigger} changed)")),
+            }
         }
     }
 
@@ -271,7 +329,7 @@ impl App {
         let debounce = std::time::Duration::from_millis(150);
 
         if let Some(moved_at) = state.preview_cursor_moved
-            && moved_at.elapsed() >= debounce
+            && self.clock.elapsed(moved_at) >= debounce
             && let Some(pending_id) = state.preview_pending_id.take()
         {
             state.preview_cursor_moved = None;
@@ -302,11 +360,11 @@ impl App {
                 ));
             }
 
-            let bd_path = self.config.behavior.bd_path.clone();
+            let bd = self.bd();
             let (tx, rx) = std::sync::mpsc::channel();
             std::thread::spawn(move || {
                 let output = crate::bd_lock::with_lock(|| {
-                    std::process::Command::new(&bd_path)
+                    bd.command()
                         .args(["show", &pending_id, "--json"])
                         .stdin(std::process::Stdio::null())
                         .stdout(std::process::Stdio::piped())
@@ -343,7 +401,7 @@ impl App {
         if self.kanban_items_rx.is_some() {
             return;
         }
-        let bd_path = self.config.behavior.bd_path.clone();
+        let bd = self.bd();
         let column_defs = self.kanban_board_state.column_defs.clone();
         self.kanban_board_state.begin_refresh();
         self.dirty = true;
@@ -352,7 +410,7 @@ impl App {
         self.last_board_signature = None;
         let (tx, rx) = mpsc::channel();
         std::thread::spawn(move || {
-            crate::modals::stream_board_data(&bd_path, &column_defs, tx);
+            crate::modals::stream_board_data(&bd, &column_defs, tx);
         });
         self.kanban_items_rx = Some(rx);
 
@@ -369,7 +427,7 @@ impl App {
     /// If a refresh is already in flight, the old receiver is replaced; the
     /// old stream thread will notice on its next send and abort.
     pub fn mutate_and_refresh_kanban(&mut self, args: Vec<String>) {
-        let bd_path = self.config.behavior.bd_path.clone();
+        let bd = self.bd();
         let column_defs = self.kanban_board_state.column_defs.clone();
         self.kanban_board_state.begin_refresh();
         self.dirty = true;
@@ -380,14 +438,14 @@ impl App {
         let (tx, rx) = mpsc::channel();
         std::thread::spawn(move || {
             let _ = crate::bd_lock::with_lock(|| {
-                std::process::Command::new(&bd_path)
+                bd.command()
                     .args(&args)
                     .stdin(std::process::Stdio::null())
                     .stdout(std::process::Stdio::null())
                     .stderr(std::process::Stdio::null())
                     .status()
             });
-            crate::modals::stream_board_data(&bd_path, &column_defs, tx);
+            crate::modals::stream_board_data(&bd, &column_defs, tx);
         });
         self.kanban_items_rx = Some(rx);
         self.refresh_preview_detail();
@@ -405,12 +463,12 @@ impl App {
         if detail.is_loading {
             return;
         }
-        let bd_path = self.config.behavior.bd_path.clone();
+        let bd = self.bd();
         let bead_id = detail.id.clone();
         let (tx, rx) = mpsc::channel();
         std::thread::spawn(move || {
             let output = crate::bd_lock::with_lock(|| {
-                std::process::Command::new(&bd_path)
+                bd.command()
                     .args(["show", &bead_id, "--json"])
                     .stdin(std::process::Stdio::null())
                     .stdout(std::process::Stdio::piped())
@@ -477,16 +535,16 @@ impl App {
 
         let ready_to_poll = self
             .last_board_signature_check_at
-            .is_none_or(|t| t.elapsed() >= BOARD_MUTATION_POLL_INTERVAL);
+            .is_none_or(|t| self.clock.elapsed(t) >= BOARD_MUTATION_POLL_INTERVAL);
         if !ready_to_poll {
             return;
         }
 
-        self.last_board_signature_check_at = Some(Instant::now());
-        let bd_path = self.config.behavior.bd_path.clone();
+        self.last_board_signature_check_at = Some(self.clock.now());
+        let bd = self.bd();
         let (tx, rx) = mpsc::channel();
         std::thread::spawn(move || {
-            let _ = tx.send(compute_board_signature(&bd_path));
+            let _ = tx.send(compute_board_signature(&bd));
         });
         self.board_signature_rx = Some(rx);
     }
@@ -522,15 +580,53 @@ impl App {
         self.show_bead_picker = true;
         self.bead_picker_state = Some(crate::modals::BeadPickerState::new_loading());
         self.bead_picker_result = None;
-        let bd_path = self.config.behavior.bd_path.clone();
+        let bd = self.bd();
         let (tx, rx) = mpsc::channel();
         std::thread::spawn(move || {
-            let result = crate::modals::fetch_bead_picker_data(&bd_path);
+            let result = crate::modals::fetch_bead_picker_data(&bd);
             let _ = tx.send(result);
         });
         self.bead_picker_rx = Some(rx);
     }
 
+    /// Poll for background bead search data.
+    pub fn poll_bead_search(&mut self) {
+        let rx = match self.bead_search_rx.take() {
+            Some(rx) => rx,
+            None => return,
+        };
+
+        match rx.try_recv() {
+            Ok(result) => {
+                self.dirty = true;
+                if let Some(ref mut state) = self.bead_search_state {
+                    state.populate(result);
+                }
+            }
+            Err(TryRecvError::Empty) => {
+                self.bead_search_rx = Some(rx); // still running
+            }
+            Err(TryRecvError::Disconnected) => {
+                self.dirty = true;
+                if let Some(ref mut state) = self.bead_search_state {
+                    state.populate(Err("Background fetch failed".to_string()));
+                }
+            }
+        }
+    }
+
+    /// Open the bead search modal and start loading every bead's text.
+    pub fn open_bead_search(&mut self) {
+        self.bead_search_state = Some(crate::modals::BeadSearchState::new_loading());
+        let bd = self.bd();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = crate::modals::fetch_bead_search_data(&bd);
+            let _ = tx.send(result);
+        });
+        self.bead_search_rx = Some(rx);
+    }
+
     /// If a bead was picked and a dependency is pending, run `bd dep add`.
     pub fn poll_pending_dep(&mut self) {
         let picked_id = match self.bead_picker_result.take() {
@@ -560,6 +656,43 @@ impl App {
         self.workers[w].pending_work_check = None;
     }
 
+    /// Check the checkboxes of the spec worker `w` just iterated on;
+    /// `reported_done` is the agent's own claim that the spec is complete.
+    pub fn check_spec_checklist(&mut self, w: usize, reported_done: bool) {
+        if let Some(bead_id) = self.workers[w].hooked_bead_id.clone() {
+            self.spec_check_rx = Some(crate::spec_checklist::spawn_check(
+                self.bd(),
+                bead_id,
+                w,
+                reported_done,
+            ));
+        }
+    }
+
+    /// Flag a spec that looks done but is still open, and offer to mark it Done.
+    pub fn poll_spec_checklist(&mut self) {
+        let Some(rx) = self.spec_check_rx.take() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(Some(spec)) => {
+                info!(bead_id = %spec.bead_id, status = %spec.status, reported_done = spec.reported_done, "spec_checklist_complete_but_open");
+                self.add_worker_line(spec.worker, crate::ui::format_unclosed_spec_styled(&spec));
+                self.mark_done_prompt = Some(spec);
+                self.dirty = true;
+            }
+            Ok(None) | Err(TryRecvError::Disconnected) => {}
+            Err(TryRecvError::Empty) => self.spec_check_rx = Some(rx),
+        }
+    }
+
+    /// Surface hook script failures as hints.
+    pub fn poll_hook_failures(&mut self) {
+        for failure in self.hooks.failures() {
+            self.set_hint(failure);
+        }
+    }
+
     /// Poll for background doctor check results. Displays only failures.
     pub fn poll_doctor(&mut self) {
         let rx = match self.doctor_rx.take() {
@@ -573,6 +706,9 @@ impl App {
                     if !check.passed {
                         self.dirty = true;
                         self.add_text_line(format!("\u{2717} {}", check.message));
+                        if let Some(summary) = &mut self.project_summary {
+                            summary.warnings.push(check.message.clone());
+                        }
                     }
                 }
             }
@@ -592,22 +728,20 @@ impl App {
             return true;
         };
 
-        if crate::agent::merge_worktree_to_main(wt_name) {
-            let bd_path = self.config.behavior.bd_path.clone();
+        if crate::agent::merge_worktree_to_main(&self.project_dir, wt_name) {
+            let bd = self.bd();
             let wt_name = wt_name.clone();
-            crate::agent::remove_merged_worktree(&bd_path, &wt_name);
+            crate::agent::remove_merged_worktree(&bd, &wt_name);
             self.workers[w].worktree_name = None;
             self.workers[w].worktree_path = None;
             true
         } else {
-            let bd_path = self.config.behavior.bd_path.clone();
+            let bd = self.bd();
             let wt_name = wt_name.clone();
 
-            if let Some(existing_bead_id) =
-                crate::agent::find_merge_conflict_bead(&bd_path, &wt_name)
-            {
+            if let Some(existing_bead_id) = crate::agent::find_merge_conflict_bead(&bd, &wt_name) {
                 // Tier 2: Claude already tried — escalate to human
-                crate::agent::escalate_merge_conflict(&bd_path, &wt_name, &existing_bead_id);
+                crate::agent::escalate_merge_conflict(&bd, &wt_name, &existing_bead_id);
                 self.add_text_line(
                     "[Merge conflict persists after Claude attempt — filed human bead, stopping]"
                         .into(),
@@ -615,8 +749,7 @@ impl App {
                 self.workers[w].reset_iteration_state();
                 self.status = AppStatus::Stopped;
                 false
-            } else if let Some(bead_id) = crate::agent::file_merge_conflict_bead(&bd_path, &wt_name)
-            {
+            } else if let Some(bead_id) = crate::agent::file_merge_conflict_bead(&bd, &wt_name) {
                 // Tier 1: First conflict — file bead, Claude resolves next iteration
                 self.add_text_line(format!(
                     "[Merge conflict — filed {}, Claude will resolve next iteration]",
@@ -649,7 +782,7 @@ impl App {
                 &self.workers[w].agent_bead_id,
                 &self.workers[w].worktree_name,
             ) {
-                crate::agent::cleanup(&self.config.behavior.bd_path, agent_id, wt_name);
+                crate::agent::cleanup(&self.bd(), agent_id, wt_name);
             }
 
             self.workers[w].agent_bead_id = None;
@@ -660,11 +793,30 @@ impl App {
         }
     }
 
-    /// Returns true if any worker has a running process or pending output.
+    /// Returns true if any worker has a running process, pending output or
+    /// a running `[hooks]` command.
     pub fn any_worker_active(&self) -> bool {
-        self.workers
-            .iter()
-            .any(|w| w.child_process.is_some() || w.output_receiver.is_some())
+        self.workers.iter().any(|w| {
+            w.child_process.is_some() || w.output_receiver.is_some() || w.iteration_hook.is_some()
+        })
+    }
+
+    /// Returns true while a run is under way, between iterations included:
+    /// besides an active worker, a pending check for more work, a queued
+    /// auto-continue or a hold (quiet hours, budget, disk, question) keeps
+    /// the run going even though the status reads Stopped.
+    pub fn run_in_progress(&self) -> bool {
+        self.status == AppStatus::Starting
+            || self.start_workers_rx.is_some()
+            || self.any_worker_active()
+            || self
+                .workers
+                .iter()
+                .any(|w| w.pending_work_check.is_some() || w.auto_continue_pending)
+            || self.quiet_hours_paused
+            || self.question_paused
+            || self.budget_throttled_until.is_some()
+            || self.disk_paused.is_some()
     }
 
     /// Update app status based on aggregate worker state.
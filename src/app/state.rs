@@ -1,26 +1,49 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Child;
 use std::sync::mpsc::Receiver;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant, SystemTime};
 
+use ralph_core::budget::SpendTracker;
+use ralph_core::clock::{Clock, SystemClock};
+use ralph_core::output::{ContentBlockState, OutputMessage};
 use ratatui::style::Color;
 use ratatui::text::Line;
 use ratatui::widgets::BorderType;
 use rusqlite::Connection;
 use tracing::info;
 
-use crate::config::{Config, LoadedConfig};
+use crate::bd::Bd;
+use crate::claude_cli::Capabilities;
+use crate::config::{Config, LoadedConfig, extends_chain, resolve_prompt_path};
+use crate::control::{self, ControlChannel};
+use crate::debug_overlay::DebugStats;
+use crate::disk_guard::DiskGuard;
 use crate::doctor;
+use crate::file_backup::FileBackups;
+use crate::file_touches::FileTouches;
+use crate::hooks::{HookEvent, Hooks};
+use crate::instruction_queue::InstructionQueue;
+use crate::issue_comment::FailureComments;
+use crate::iteration_sections::IterationSection;
+use crate::log_bump::LogBump;
 use crate::logging::ReloadHandle;
+use crate::metrics_textfile::MetricsSample;
 use crate::modals::{
     ConfigModalState, HelpContext, InitModalState, KanbanBoardState, ToolAllowModalState,
 };
-use crate::output::OutputMessage;
+use crate::output_scripts::OutputScripts;
+use crate::project_summary::ProjectSummary;
+use crate::run_status::RunStatus;
+use crate::self_report::SelfReport;
+use crate::session_lock::{LockInfo, SessionLock};
+use crate::spec_checklist::UnclosedSpec;
 use crate::startup::get_file_mtime;
-use crate::tool_panel::{ContentBlockState, ToolPanel};
-use crate::wake_lock::WakeLock;
+use crate::tool_panel::ToolPanel;
+use crate::transcript::TranscriptWriter;
+use crate::ui::{Highlights, ToolResultPreview};
+use crate::wake_lock::{WakeLockState, WakeLockStatus};
 use crate::work_source::{BeadsWorkSource, WorkRemaining};
 
 /// Application status states.
@@ -49,6 +72,16 @@ impl AppStatus {
             AppStatus::Error => Color::Red,
         }
     }
+
+    /// Short status word for the tab strip.
+    pub fn tab_label(&self) -> &'static str {
+        match self {
+            AppStatus::Stopped => "idle",
+            AppStatus::Starting => "starting",
+            AppStatus::Running => "running",
+            AppStatus::Error => "error",
+        }
+    }
 }
 
 /// Per-worker state extracted from App.
@@ -59,6 +92,8 @@ pub struct Worker {
     pub id: usize,
     /// Handle to the running Claude CLI subprocess.
     pub child_process: Option<Child>,
+    /// Claude's stdin when `control.enabled`; dropped after the result to let it exit.
+    pub control: Option<ControlChannel>,
     /// Channel receiving stdout/stderr from child process.
     pub output_receiver: Option<Receiver<OutputMessage>>,
     /// Git worktree name for this worker.
@@ -69,6 +104,8 @@ pub struct Worker {
     pub agent_bead_id: Option<String>,
     /// Currently hooked bead ID (the bead this worker is working on).
     pub hooked_bead_id: Option<String>,
+    /// One-line summary of the last finished iteration (`y` copies it).
+    pub last_summary: Option<String>,
     /// Handle to signal the heartbeat thread to stop.
     pub heartbeat_stop: Option<Arc<std::sync::atomic::AtomicBool>>,
     /// When the current run started (for elapsed time display).
@@ -94,6 +131,130 @@ pub struct Worker {
     pub claimed_epic_id: Option<String>,
     /// Human-readable error from the last result event (e.g. rate limit message).
     pub last_result_error: Option<String>,
+    /// Authentication failure reported by the current run's stderr or result.
+    pub auth_error: Option<String>,
+    /// Claude CLI session ID of the current (or last) iteration, from its init event.
+    pub claude_session_id: Option<String>,
+    /// Whether the current iteration continues the previous Claude conversation.
+    pub continued_conversation: bool,
+    /// The next launch starts a new Claude conversation, as a new run does.
+    pub start_fresh: bool,
+    /// Exact prompt text piped into Claude for the current iteration.
+    pub prompt_context: Option<String>,
+    /// Index in `output_lines` of the live line for a tool call whose input is
+    /// streaming or whose result hasn't arrived yet.
+    pub tool_preview_line: Option<usize>,
+    /// Tool results and verbose-only lines rendered into `output_lines`, in
+    /// order, so they can be re-rendered when the preview length or output
+    /// mode changes.
+    pub result_blocks: Vec<ResultBlock>,
+    /// What the Claude CLI running the current iteration outputs.
+    pub output_caps: Capabilities,
+    /// Where each iteration's output begins in `output_lines`, oldest first.
+    pub sections: Vec<IterationSection>,
+    /// Bash commands run so far in the current iteration.
+    pub iteration_bash: Vec<BashRun>,
+    /// Files created, modified or deleted so far in the current iteration.
+    pub iteration_files: FileTouches,
+    /// Tool results so far in the current iteration, and how many were errors.
+    pub iteration_tool_results: u32,
+    pub iteration_tool_errors: u32,
+    /// The last few failed tool results as `Tool: first line`, newest last
+    /// (for the failure comment on a linked issue).
+    pub recent_tool_errors: Vec<String>,
+    /// Recently finished iterations, oldest first (capped at `MAX_ITERATION_HISTORY`).
+    pub iteration_history: Vec<IterationRecord>,
+    /// Set while a stopped run finishes up after an interrupt; the child is
+    /// killed if it is still running at this time.
+    pub stop_deadline: Option<Instant>,
+    /// When the child was paused with `P` (SIGSTOP), while it is.
+    pub paused_at: Option<Instant>,
+    /// Set when the child was killed for running past the iteration timeout
+    /// (the limit it hit), until its exit is handled.
+    pub timed_out: Option<Duration>,
+    /// The question the last iteration ended with, until it is answered or
+    /// skipped (`behavior.pause_on_question`).
+    pub question: Option<String>,
+    /// A `[hooks]` command running before or after an iteration.
+    pub iteration_hook: Option<crate::iteration_hook::IterationHook>,
+}
+
+/// How many finished iterations each worker remembers.
+const MAX_ITERATION_HISTORY: usize = 20;
+
+/// A Bash command the agent ran and whether its tool result was an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BashRun {
+    pub command: String,
+    pub failed: bool,
+}
+
+/// Summary of a finished iteration.
+#[derive(Debug, Clone)]
+pub struct IterationRecord {
+    pub iteration: u32,
+    /// The result event was an error or any Bash command failed.
+    pub failed: bool,
+    pub commands: Vec<BashRun>,
+    /// `--tag` labels the run was started with.
+    pub tags: Vec<String>,
+    /// Claude CLI session ID, for resuming the conversation.
+    pub claude_session_id: Option<String>,
+    /// The iteration continued the previous conversation rather than starting fresh.
+    pub continued: bool,
+    /// Tool results received, and how many of them were errors.
+    pub tool_results: u32,
+    pub tool_errors: u32,
+    /// The agent's `ralph-report` block, when its final message had one.
+    pub report: Option<SelfReport>,
+}
+
+/// Location of a re-renderable entry within a worker's `output_lines`.
+#[derive(Debug, Clone)]
+pub struct ResultBlock {
+    /// Index of the block's first line.
+    pub start: usize,
+    /// Number of lines the block currently occupies.
+    pub len: usize,
+    pub content: BlockContent,
+}
+
+/// What a [`ResultBlock`] renders.
+#[derive(Debug, Clone)]
+pub enum BlockContent {
+    /// A tool call and its result(s). Consecutive identical calls collapse
+    /// into one block holding every result.
+    Tool {
+        /// The tool call the results are indented under (`None` for a standalone result).
+        call: Option<ToolCallLine>,
+        /// One result per run of the call, oldest first (never empty).
+        results: Vec<ToolResultPreview>,
+    },
+    /// Lines shown only in verbose output (usage summaries, stderr).
+    Verbose(Vec<Line<'static>>),
+}
+
+/// A rendered tool call plus the identity used to detect repeats.
+#[derive(Debug, Clone)]
+pub struct ToolCallLine {
+    pub line: Line<'static>,
+    /// Tool name and raw input; equal keys mean an identical call.
+    pub key: String,
+    /// Diff shown under Edit calls (empty for other tools).
+    pub diff: Vec<Line<'static>>,
+}
+
+/// The files `project_config` extends, with their current mtimes.
+pub fn config_base_mtimes(project_config: Option<&Path>) -> Vec<(PathBuf, Option<SystemTime>)> {
+    project_config
+        .map(extends_chain)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|path| {
+            let mtime = get_file_mtime(&path);
+            (path, mtime)
+        })
+        .collect()
 }
 
 impl Worker {
@@ -102,11 +263,13 @@ impl Worker {
         Self {
             id,
             child_process: None,
+            control: None,
             output_receiver: None,
             worktree_name: None,
             worktree_path: None,
             agent_bead_id: None,
             hooked_bead_id: None,
+            last_summary: None,
             heartbeat_stop: None,
             run_start_time: None,
             current_iteration: 0,
@@ -118,18 +281,133 @@ impl Worker {
             pending_work_check: None,
             claimed_epic_id: None,
             last_result_error: None,
+            auth_error: None,
+            claude_session_id: None,
+            continued_conversation: false,
+            start_fresh: false,
+            prompt_context: None,
+            tool_preview_line: None,
+            result_blocks: Vec::new(),
+            output_caps: Capabilities::default(),
+            sections: Vec::new(),
+            iteration_bash: Vec::new(),
+            iteration_files: FileTouches::default(),
+            iteration_tool_results: 0,
+            iteration_tool_errors: 0,
+            recent_tool_errors: Vec::new(),
+            iteration_history: Vec::new(),
+            stop_deadline: None,
+            paused_at: None,
+            timed_out: None,
+            question: None,
+            iteration_hook: None,
         }
     }
 
-    /// Terminate the child process if running.
+    /// Terminate the child process, and any `[hooks]` command, if running.
     pub fn kill_child(&mut self) {
+        self.control = None;
+        self.stop_deadline = None;
+        self.paused_at = None;
         if let Some(mut child) = self.child_process.take() {
             let pid = child.id();
-            let _ = child.kill();
-            let _ = child.wait();
+            crate::control::kill_process(&mut child);
             info!(pid, "process_killed");
         }
         self.output_receiver = None;
+        // Dropping a hook kills it
+        self.iteration_hook = None;
+    }
+
+    /// Ask the running child to stop, leaving its output connected so the
+    /// final result event (with cost and usage) is still recorded. Uses the
+    /// control protocol when open, SIGINT otherwise, and gives it `grace` to
+    /// exit. Returns false if there is no child or the interrupt couldn't be
+    /// sent.
+    pub fn interrupt_child(&mut self, now: Instant, grace: Duration) -> bool {
+        // A frozen process can't act on the interrupt
+        self.resume(now);
+        let Some(child) = &self.child_process else {
+            return false;
+        };
+        let pid = child.id();
+        let sent = match &mut self.control {
+            Some(channel) => channel.interrupt().is_ok(),
+            None => control::interrupt_process(child),
+        };
+        if sent {
+            info!(pid, "process_interrupted");
+            self.stop_deadline = Some(now + grace);
+        }
+        sent
+    }
+
+    /// Freeze the child with SIGSTOP. Returns false if there is no child, it
+    /// is already paused, or the signal couldn't be sent.
+    pub fn pause(&mut self, now: Instant) -> bool {
+        if self.paused_at.is_some() {
+            return false;
+        }
+        let Some(child) = &self.child_process else {
+            return false;
+        };
+        if !control::pause_process(child) {
+            return false;
+        }
+        info!(pid = child.id(), "process_paused");
+        self.paused_at = Some(now);
+        true
+    }
+
+    /// Continue a paused child, moving the run's start time forward by the
+    /// pause so the elapsed timer skips it. Returns false if it wasn't paused.
+    pub fn resume(&mut self, now: Instant) -> bool {
+        let Some(paused_at) = self.paused_at.take() else {
+            return false;
+        };
+        if let Some(child) = &self.child_process {
+            control::resume_process(child);
+            info!(pid = child.id(), "process_resumed");
+        }
+        if let Some(start) = &mut self.run_start_time {
+            *start += now.saturating_duration_since(paused_at);
+        }
+        true
+    }
+
+    /// Whether the child process is still running.
+    pub fn child_running(&mut self) -> bool {
+        self.child_process
+            .as_mut()
+            .is_some_and(|c| matches!(c.try_wait(), Ok(None)))
+    }
+
+    /// Close out the current iteration into `iteration_history`.
+    pub fn finish_iteration(&mut self, result_error: bool, tags: &[String]) {
+        let commands = std::mem::take(&mut self.iteration_bash);
+        let failed = result_error || commands.iter().any(|c| c.failed);
+        self.iteration_history.push(IterationRecord {
+            iteration: self.current_iteration,
+            failed,
+            commands,
+            tags: tags.to_vec(),
+            claude_session_id: self.claude_session_id.clone(),
+            continued: self.continued_conversation,
+            tool_results: std::mem::take(&mut self.iteration_tool_results),
+            tool_errors: std::mem::take(&mut self.iteration_tool_errors),
+            report: None,
+        });
+        if self.iteration_history.len() > MAX_ITERATION_HISTORY {
+            self.iteration_history.remove(0);
+        }
+    }
+
+    /// Most recent failed iteration that ran at least one Bash command.
+    pub fn last_failed_iteration(&self) -> Option<&IterationRecord> {
+        self.iteration_history
+            .iter()
+            .rev()
+            .find(|r| r.failed && !r.commands.is_empty())
     }
 
     /// Reset iteration state when stopping (error, manual stop, or run complete).
@@ -170,18 +448,27 @@ pub struct App {
     pub loop_count: u64,
     /// Directory where logs are written.
     pub log_directory: Option<PathBuf>,
+    /// The project this tab works on: where bd, Claude and the hooks run, and
+    /// what its config, prompt and session lock are keyed by.
+    pub project_dir: PathBuf,
     /// Loaded configuration.
     pub config: Config,
     /// Path to the per-project configuration file, if it existed at startup.
     pub project_config_path: Option<PathBuf>,
     /// Last known mtime of the project config file for change detection.
     pub project_config_mtime: Option<SystemTime>,
+    /// Files the project config `extends`, with their last known mtimes.
+    pub config_base_mtimes: Vec<(PathBuf, Option<SystemTime>)>,
     /// Last time we polled for config changes.
     pub last_config_poll: Instant,
     /// When config was last successfully reloaded (for "Reloaded" indicator fade).
     pub config_reloaded_at: Option<Instant>,
     /// Error message if per-project config reload failed.
     pub project_config_error: Option<String>,
+    /// Prompt file mtime when the latest iteration's prompt was assembled.
+    pub prompt_mtime: Option<SystemTime>,
+    /// The prompt file was edited after the latest iteration started.
+    pub prompt_changed: bool,
     /// Name of the currently active bead (from bd list).
     pub current_bead: Option<String>,
     /// Last time we polled for the current bead.
@@ -202,16 +489,33 @@ pub struct App {
     pub help_context: Option<HelpContext>,
     /// Whether the quit confirmation modal is visible.
     pub show_quit_modal: bool,
+    /// Compiled `[display.highlights]` rules for tool result previews.
+    pub highlights: Highlights,
+    /// Whether repeated tool calls show every result instead of only the latest.
+    pub expand_repeated_tools: bool,
+    /// Whether Edit calls show their diff under the summary line.
+    pub show_edit_diffs: bool,
+    /// Whether the output shows only tool calls and assistant text, hiding
+    /// result previews, diffs, usage summaries and stderr.
+    pub compact_output: bool,
+    /// Whether the workers view soft-wraps long lines (kept across openings).
+    pub stream_wrap: bool,
     /// Transient hint message displayed in the status bar (auto-clears after timeout).
     pub hint: Option<(String, Instant)>,
     /// Cumulative token count (input + output) across all exchanges in the session.
     pub cumulative_tokens: u64,
     /// Exchange counter within the current session (incremented on each Result event).
     pub exchange_count: u32,
+    /// Iterations this session that ended in an error or a failed command.
+    pub failed_iterations: u32,
     /// Name of the last tool used (for categorizing exchanges).
     pub last_tool_used: Option<String>,
-    /// Wake lock to prevent system idle sleep while running.
-    pub wake_lock: Option<WakeLock>,
+    /// Wake lock to prevent system idle sleep while running (toggled with `K`).
+    pub wake_lock: WakeLockState,
+    /// Time source for timers, throttles and quiet hours.
+    pub clock: Arc<dyn Clock>,
+    /// Continue the previous iteration's conversation on auto-continue (toggled with `C`).
+    pub resume_conversation: bool,
     /// Tool call tracking and panel display state.
     pub tool_panel: ToolPanel,
     /// Whether we're currently in an indented text block (for flush).
@@ -222,12 +526,76 @@ pub struct App {
     pub bead_poll_rx: Option<Receiver<Option<String>>>,
     /// When the app entered Error state (for auto-clearing the pulsing flash).
     pub error_at: Option<Instant>,
+    /// Temporary debug logging after an error (`[logging] debug_on_error_minutes`).
+    pub log_bump: LogBump,
+    /// Failure comments on linked issues (`[tracker] comment_on_failure`).
+    pub failure_comments: FailureComments,
     /// Receiver for background doctor checks (run once on TUI open).
     pub doctor_rx: Option<Receiver<Vec<doctor::CheckResult>>>,
-    /// SQLite connection for tool call recording (None if DB open failed at startup).
+    /// SQLite connection for tool call and run stats recording (None if DB open failed at startup).
     pub tool_history_db: Option<Connection>,
     /// Sequence counter for tool calls within this session.
     pub tool_call_sequence: u32,
+    /// Raw worker output transcript for `ralph attach` (None if it couldn't be opened).
+    pub transcript: Option<TranscriptWriter>,
+    /// Session ID being observed when running as `ralph attach` (read-only).
+    pub attached_session: Option<String>,
+    /// Project session lock, held while a run is active.
+    pub session_lock: Option<SessionLock>,
+    /// Last status written for `ralph status` (None when not publishing).
+    pub published_status: Option<RunStatus>,
+    /// Last `[metrics] textfile` path and the values written to it.
+    pub metrics_sample: Option<(PathBuf, MetricsSample)>,
+    /// Holder of the project lock when a start was refused (shows the conflict modal).
+    pub lock_conflict: Option<LockInfo>,
+    /// Uncommitted changes found when starting a run, while asking what to do with them.
+    pub dirty_start_prompt: Option<Vec<String>>,
+    /// Whether the "Reduced output" warning for an older Claude CLI was shown.
+    pub output_limitation_shown: bool,
+    /// Whether the F12 debug overlay is shown.
+    pub show_debug_overlay: bool,
+    /// Timings and counters for the debug overlay, collected whether it's shown or not.
+    pub debug_stats: DebugStats,
+    /// Session to attach to read-only after the TUI exits.
+    pub attach_request: Option<String>,
+    /// Auth failure that stopped the run (shows the not-authenticated modal).
+    pub auth_failure: Option<String>,
+    /// File to open in `$EDITOR` once the event loop can suspend the terminal.
+    pub editor_request: Option<PathBuf>,
+    /// Pending end-of-iteration check of the hooked spec's checkboxes.
+    pub spec_check_rx: Option<Receiver<Option<UnclosedSpec>>>,
+    /// Spec whose criteria are all checked but is still open (shows the mark-done prompt).
+    pub mark_done_prompt: Option<UnclosedSpec>,
+    /// A finished iteration's auto-continue is being held for quiet hours.
+    pub quiet_hours_paused: bool,
+    /// A finished iteration's auto-continue is held for Claude's question.
+    pub question_paused: bool,
+    /// Reply box for Claude's question; `None` when closed.
+    pub question_reply_state: Option<crate::modals::QuestionReplyState>,
+    /// Project directory prompt for a new tab; `None` when closed.
+    pub new_tab_state: Option<crate::modals::NewTabState>,
+    /// Recent spend, for `budget.max_usd_per_hour`.
+    pub spend: SpendTracker,
+    /// User hook scripts in `~/.config/ralph/hooks`.
+    pub hooks: Hooks,
+    /// The project's Lua output scripts.
+    pub output_scripts: OutputScripts,
+    /// `--tag` labels for this run, stamped on every iteration record.
+    pub run_tags: Vec<String>,
+    /// Total reported cost of this session's iterations.
+    pub session_cost_usd: f64,
+    /// Startup summary shown in the preview pane until a run starts or a board key is pressed.
+    pub project_summary: Option<ProjectSummary>,
+    /// Per-spec averages behind the "est. remaining" line; `None` until a spec has completed.
+    pub estimate_history: Option<crate::estimate::History>,
+    /// Auto-continue is held until this time to keep within the hourly budget.
+    pub budget_throttled_until: Option<Instant>,
+    /// Project directory size across the run, for `disk.max_growth_mb`.
+    pub disk_guard: DiskGuard,
+    /// Why auto-continue is held for `[disk]` limits, while it is.
+    pub disk_paused: Option<String>,
+    /// When to re-measure the project directory while held for its growth.
+    pub disk_recheck_at: Option<Instant>,
     /// Whether the tool allow modal is visible.
     pub show_tool_allow_modal: bool,
     /// State for the tool allow modal (when open).
@@ -250,6 +618,9 @@ pub struct App {
     pub board_signature_rx: Option<Receiver<Option<Vec<u8>>>>,
     /// When the last signature probe was kicked off (for throttling).
     pub last_board_signature_check_at: Option<Instant>,
+    /// Shortcut page the command bar last drew, while its shortcuts overflow
+    /// into several pages.
+    pub command_bar_page: Option<usize>,
     /// Cached visual line count (invalidated on content or width changes).
     pub cached_visual_line_count: Option<u16>,
     /// Error from parsing board_columns.toml (None = valid).
@@ -258,10 +629,29 @@ pub struct App {
     pub show_bead_picker: bool,
     /// State for the bead picker modal (when open).
     pub bead_picker_state: Option<crate::modals::BeadPickerState>,
+    /// `--read-only`: browsing only; starting, init, config saves, edits and
+    /// board mutations are refused.
+    pub read_only: bool,
+    /// Re-run modal state (open when Some).
+    pub rerun_state: Option<crate::modals::RerunState>,
+    /// Files copied before Claude changed them (`behavior.backup_files`).
+    pub file_backups: FileBackups,
+    /// Restore-backup modal state (open when Some).
+    pub restore_state: Option<crate::modals::RestoreState>,
+    /// Diagnostics modal (recent log events); `None` when closed.
+    pub diagnostics_state: Option<crate::modals::DiagnosticsState>,
+    /// Notes typed for upcoming iterations' prompts.
+    pub instruction_queue: InstructionQueue,
+    /// Instruction queue modal; `None` when closed.
+    pub instructions_state: Option<crate::modals::InstructionsState>,
     /// Result from the bead picker — callers `.take()` this after the picker closes.
     pub bead_picker_result: Option<String>,
     /// Receiver for background bead picker data.
     pub bead_picker_rx: Option<Receiver<Result<Vec<crate::modals::BeadPickerItem>, String>>>,
+    /// Bead search modal; `None` when closed.
+    pub bead_search_state: Option<crate::modals::BeadSearchState>,
+    /// Receiver for background bead search data.
+    pub bead_search_rx: Option<Receiver<Result<Vec<crate::modals::BeadSearchItem>, String>>>,
     /// Pending dependency: bead ID + direction, waiting for bead picker result.
     pub pending_dep: Option<PendingDep>,
     /// Whether the workers stream modal is visible.
@@ -274,6 +664,8 @@ pub struct App {
     pub selected_worker: usize,
     /// Receiver for background worker startup results.
     pub start_workers_rx: Option<Receiver<Vec<crate::work_start::WorkerStartResult>>>,
+    /// Queued instructions handed to the workers now starting.
+    pub starting_instructions: usize,
 }
 
 /// Tracks a pending dependency addition while the bead picker is open.
@@ -293,11 +685,18 @@ impl App {
         log_level_handle: Option<Arc<Mutex<ReloadHandle>>>,
     ) -> Self {
         let current_log_level = loaded_config.config.logging.level.clone();
+        let highlights = Highlights::compile(&loaded_config.config.display.highlights);
+        let keep_awake = loaded_config.config.behavior.keep_awake;
+        let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+        let project_dir = loaded_config.project_dir.clone();
+        let file_backups = FileBackups::new(&project_dir, &session_id);
+        let resume_conversation = loaded_config.config.behavior.resume_conversation;
         let worker_count = loaded_config.config.behavior.workers.max(1) as usize;
-        let work_source = Arc::new(BeadsWorkSource::new(
+        let work_source = Arc::new(BeadsWorkSource::new(Bd::new(
             loaded_config.config.behavior.bd_path.clone(),
-        ));
-        let board_columns = crate::modals::load_board_config()
+            project_dir.clone(),
+        )));
+        let board_columns = crate::modals::load_board_config(&project_dir)
             .map(|c| c.columns)
             .unwrap_or_default();
         let kanban_board_state = KanbanBoardState::new_loading(board_columns);
@@ -312,19 +711,23 @@ impl App {
             session_id,
             loop_count: 0,
             log_directory,
+            project_dir: project_dir.clone(),
             config: loaded_config.config,
             project_config_path: loaded_config.project_config_path.clone(),
             project_config_mtime: loaded_config
                 .project_config_path
                 .as_ref()
                 .and_then(|p| get_file_mtime(p)),
+            config_base_mtimes: config_base_mtimes(loaded_config.project_config_path.as_deref()),
             // Initialize to "long ago" so we poll immediately on start
-            last_config_poll: Instant::now() - Duration::from_secs(10),
+            last_config_poll: clock.now() - Duration::from_secs(10),
             config_reloaded_at: None,
             project_config_error: None,
+            prompt_mtime: resolve_prompt_path(&project_dir).and_then(|p| get_file_mtime(&p)),
+            prompt_changed: false,
             current_bead: None,
             // Initialize to "long ago" so we poll immediately on start
-            last_bead_poll: Instant::now() - Duration::from_secs(10),
+            last_bead_poll: clock.now() - Duration::from_secs(10),
             log_level_handle,
             current_log_level,
             dirty: true,
@@ -333,43 +736,110 @@ impl App {
             init_modal_state: None,
             help_context: None,
             show_quit_modal: false,
+            highlights,
+            expand_repeated_tools: false,
+            show_edit_diffs: true,
+            compact_output: false,
+            stream_wrap: true,
             hint: None,
             cumulative_tokens: 0,
             exchange_count: 0,
+            failed_iterations: 0,
             last_tool_used: None,
-            wake_lock: None,
+            wake_lock: WakeLockState::new(keep_awake),
+            clock,
+            resume_conversation,
             tool_panel: ToolPanel::new(),
             in_indented_text: false,
             work_source,
             bead_poll_rx: None,
             error_at: None,
+            log_bump: LogBump::default(),
+            failure_comments: FailureComments::default(),
             doctor_rx: None,
             tool_history_db: None,
             tool_call_sequence: 0,
+            transcript: None,
+            attached_session: None,
+            session_lock: None,
+            published_status: None,
+            metrics_sample: None,
+            lock_conflict: None,
+            dirty_start_prompt: None,
+            output_limitation_shown: false,
+            show_debug_overlay: false,
+            debug_stats: DebugStats::default(),
+            attach_request: None,
+            auth_failure: None,
+            editor_request: None,
+            spec_check_rx: None,
+            mark_done_prompt: None,
+            quiet_hours_paused: false,
+            question_paused: false,
+            question_reply_state: None,
+            new_tab_state: None,
+            spend: SpendTracker::default(),
+            hooks: Hooks::new(),
+            output_scripts: OutputScripts::load(&project_dir),
+            run_tags: Vec::new(),
+            session_cost_usd: 0.0,
+            project_summary: None,
+            estimate_history: None,
+            budget_throttled_until: None,
+            disk_guard: DiskGuard::new(project_dir.clone()),
+            disk_paused: None,
+            disk_recheck_at: None,
             show_tool_allow_modal: false,
             tool_allow_modal_state: None,
-            repo_path: crate::db::detect_repo_path(),
+            repo_path: crate::db::detect_repo_path(&project_dir),
             kanban_board_state,
             kanban_items_rx: None,
             bead_detail_rx: None,
             last_board_signature: None,
             board_signature_rx: None,
             last_board_signature_check_at: None,
+            command_bar_page: None,
             cached_visual_line_count: None,
             board_config_error: None,
             show_bead_picker: false,
             bead_picker_state: None,
+            read_only: false,
+            rerun_state: None,
+            file_backups,
+            restore_state: None,
+            diagnostics_state: None,
+            instruction_queue: InstructionQueue::default(),
+            instructions_state: None,
             bead_picker_result: None,
             bead_picker_rx: None,
+            bead_search_state: None,
+            bead_search_rx: None,
             pending_dep: None,
             show_workers_stream: false,
             workers_stream_state: None,
             workers: (0..worker_count).map(Worker::new).collect(),
             selected_worker: 0,
             start_workers_rx: None,
+            starting_instructions: 0,
         }
     }
 
+    /// bd as this tab runs it, in its project directory.
+    pub fn bd(&self) -> Bd {
+        Bd::new(
+            self.config.behavior.bd_path.clone(),
+            self.project_dir.clone(),
+        )
+    }
+
+    /// Where worker `w` works: its worktree, or the project directory.
+    pub fn worker_dir(&self, w: usize) -> PathBuf {
+        self.workers[w]
+            .worktree_path
+            .clone()
+            .unwrap_or_else(|| self.project_dir.clone())
+    }
+
     /// Returns a reference to the currently selected worker.
     #[allow(dead_code)]
     pub fn worker(&self) -> &Worker {
@@ -385,7 +855,7 @@ impl App {
     /// Validate board column TOML and store any error.
     /// Call after construction to set the initial hint if invalid.
     pub fn validate_board_config(&mut self) {
-        if let Err(e) = crate::modals::load_board_config() {
+        if let Err(e) = crate::modals::load_board_config(&self.project_dir) {
             let msg = format!("Board TOML invalid: {e}");
             self.board_config_error = Some(msg.clone());
             self.set_hint(msg);
@@ -395,7 +865,7 @@ impl App {
     /// Auto-revert from Error to Stopped after a timeout.
     pub fn check_error_timeout(&mut self) {
         if let Some(at) = self.error_at
-            && at.elapsed() >= Duration::from_secs(5)
+            && self.clock.elapsed(at) >= Duration::from_secs(5)
         {
             self.status = AppStatus::Stopped;
             self.error_at = None;
@@ -403,15 +873,58 @@ impl App {
         }
     }
 
+    /// In read-only mode, refuse `action` with a hint and return `true`.
+    pub fn deny_in_read_only(&mut self, action: &str) -> bool {
+        if self.read_only {
+            self.set_hint(format!("Read-only mode: {action} is disabled"));
+        }
+        self.read_only
+    }
+
     /// Set a transient hint message in the status bar.
     pub fn set_hint(&mut self, message: impl Into<String>) {
-        self.hint = Some((message.into(), Instant::now()));
+        self.hint = Some((message.into(), self.clock.now()));
+    }
+
+    /// Flip keep-awake mid-session, acquiring or releasing the lock immediately.
+    pub fn toggle_wake_lock(&mut self) {
+        let running = self.any_worker_active();
+        let message = match self.wake_lock.toggle(running) {
+            WakeLockStatus::Held => "Keep awake: on (lock held)".to_string(),
+            WakeLockStatus::NotHeld if self.wake_lock.enabled() => {
+                "Keep awake: on (from next start)".to_string()
+            }
+            WakeLockStatus::NotHeld => "Keep awake: off".to_string(),
+            WakeLockStatus::Unsupported => "Keep awake: unsupported on this platform".to_string(),
+            WakeLockStatus::Error(e) => format!("Keep awake: {}", e),
+        };
+        self.set_hint(message);
+    }
+
+    /// Switch between fresh and continued conversations for later iterations.
+    pub fn toggle_conversation_mode(&mut self) {
+        self.resume_conversation = !self.resume_conversation;
+        info!(
+            resume = self.resume_conversation,
+            "conversation_mode_toggled"
+        );
+        let mode = if self.resume_conversation {
+            "continued"
+        } else {
+            "fresh"
+        };
+        let message = if self.any_worker_active() {
+            format!("Conversation: {mode} (from next iteration)")
+        } else {
+            format!("Conversation: {mode}")
+        };
+        self.set_hint(message);
     }
 
     /// Auto-clear hint after timeout.
     pub fn check_hint_timeout(&mut self) {
-        if let Some((_, at)) = &self.hint
-            && at.elapsed() >= Duration::from_secs(3)
+        if let Some((_, at)) = self.hint
+            && self.clock.elapsed(at) >= Duration::from_secs(3)
         {
             self.hint = None;
             self.dirty = true;
@@ -424,13 +937,24 @@ impl App {
             return;
         }
         info!("manual_stop");
+        let grace = Duration::from_secs(self.config.behavior.stop_grace_secs);
         for w in 0..self.workers.len() {
-            self.workers[w].kill_child();
+            // A second stop while a run is still finishing up kills it
+            if self.workers[w].stop_deadline.is_some()
+                || !self.workers[w].interrupt_child(self.clock.now(), grace)
+            {
+                self.workers[w].kill_child();
+                self.workers[w].reset_iteration_state();
+            }
             self.workers[w].run_start_time = None;
             self.release_worker_hooked_bead(w);
         }
-        self.status = AppStatus::Stopped;
         self.current_bead = None;
+        // Interrupted workers stay active until their process exits
+        self.update_derived_status();
+        if !self.any_worker_active() {
+            self.wake_lock.release();
+        }
     }
 
     /// Release the currently hooked bead for the selected worker.
@@ -443,8 +967,59 @@ impl App {
     /// Release the hooked bead for a specific worker by index.
     pub fn release_worker_hooked_bead(&mut self, w: usize) {
         let bead_id = self.workers[w].hooked_bead_id.take();
-        if let (Some(agent_id), Some(bead_id)) = (&self.workers[w].agent_bead_id, bead_id) {
-            crate::agent::release_bead(&self.config.behavior.bd_path, agent_id, &bead_id);
+        if let (Some(agent_id), Some(bead_id)) = (&self.workers[w].agent_bead_id, bead_id)
+            && let Some(title) = crate::agent::release_bead(&self.bd(), agent_id, &bead_id)
+        {
+            self.announce_spec_done(w, &bead_id, &title);
+        }
+    }
+
+    /// Mark a spec Claude closed as complete in the stats file and celebrate
+    /// it in worker `w`'s output.
+    pub fn announce_spec_done(&mut self, w: usize, bead_id: &str, title: &str) {
+        info!(bead_id, "spec_done");
+        let stats = self
+            .tool_history_db
+            .as_ref()
+            .map(|conn| crate::stats::complete_spec(conn, &self.repo_path, bead_id, title))
+            .unwrap_or_default();
+        self.fire_hook(
+            HookEvent::SpecDone,
+            serde_json::json!({
+                "bead_id": bead_id,
+                "title": title,
+                "iterations": stats.iterations,
+                "tokens": stats.tokens,
+                "cost_usd": stats.cost_usd,
+                "duration_ms": stats.duration_ms,
+            }),
+        );
+        self.add_worker_line(
+            w,
+            crate::ui::format_spec_done_styled(
+                bead_id,
+                title,
+                &stats,
+                &crate::number_format::NumberFormat::from(&self.config.display),
+            ),
+        );
+    }
+
+    /// Run the user's hook for `event`, stamping the payload with this session's ID.
+    pub fn fire_hook(&self, event: HookEvent, mut payload: serde_json::Value) {
+        payload["session_id"] = serde_json::Value::from(self.session_id.as_str());
+        self.hooks.fire(event, payload);
+    }
+
+    /// Drop this tab's handle on the project session lock once its run has
+    /// fully ended, between-iteration waits included. The lock itself goes
+    /// once no tab holds it.
+    pub fn release_session_lock_if_idle(&mut self) {
+        if self.status == AppStatus::Stopped
+            && !self.run_in_progress()
+            && self.session_lock.is_some()
+        {
+            self.session_lock = None;
         }
     }
 
@@ -474,6 +1049,7 @@ impl App {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ralph_core::clock::ManualClock;
 
     /// Helper to build a minimal App with N workers (no side effects).
     fn app_with_workers(n: u32) -> App {
@@ -483,6 +1059,86 @@ mod tests {
         App::new("test".into(), None, loaded, None)
     }
 
+    #[test]
+    fn finish_iteration_records_bash_commands_and_failure() {
+        let mut app = app_with_workers(1);
+        let worker = &mut app.workers[0];
+        worker.current_iteration = 2;
+        worker.iteration_bash.push(BashRun {
+            command: "cargo test".into(),
+            failed: true,
+        });
+        worker.claude_session_id = Some("abc-123".into());
+        worker.iteration_tool_results = 4;
+        worker.iteration_tool_errors = 1;
+        worker.finish_iteration(false, &["prompt-v3".to_string()]);
+        worker.current_iteration = 3;
+        worker.claude_session_id = None;
+        worker.continued_conversation = true;
+        worker.finish_iteration(false, &[]);
+
+        assert!(worker.iteration_bash.is_empty());
+        assert_eq!(worker.iteration_history.len(), 2);
+        let failed = worker.last_failed_iteration().unwrap();
+        assert_eq!(failed.iteration, 2);
+        assert_eq!(failed.commands[0].command, "cargo test");
+        assert_eq!(failed.tags, ["prompt-v3"]);
+        assert_eq!(failed.claude_session_id.as_deref(), Some("abc-123"));
+        assert!(!failed.continued);
+        assert_eq!((failed.tool_results, failed.tool_errors), (4, 1));
+        assert_eq!(worker.iteration_tool_results, 0);
+        assert_eq!(worker.iteration_history[1].tool_errors, 0);
+        assert!(!worker.iteration_history[1].failed);
+        assert!(worker.iteration_history[1].continued);
+    }
+
+    #[test]
+    fn iteration_history_is_capped() {
+        let mut app = app_with_workers(1);
+        let worker = &mut app.workers[0];
+        for i in 0..(MAX_ITERATION_HISTORY as u32 + 5) {
+            worker.current_iteration = i;
+            worker.finish_iteration(true, &[]);
+        }
+        assert_eq!(worker.iteration_history.len(), MAX_ITERATION_HISTORY);
+        assert_eq!(worker.iteration_history[0].iteration, 5);
+        // Failed iterations without Bash commands have nothing to re-run
+        assert!(worker.last_failed_iteration().is_none());
+    }
+
+    #[test]
+    fn toggle_conversation_mode_flips_and_hints() {
+        let mut app = app_with_workers(1);
+        assert!(!app.resume_conversation);
+        app.toggle_conversation_mode();
+        assert!(app.resume_conversation);
+        assert_eq!(
+            app.hint.as_ref().map(|(m, _)| m.as_str()),
+            Some("Conversation: continued")
+        );
+        app.toggle_conversation_mode();
+        assert!(!app.resume_conversation);
+    }
+
+    #[test]
+    fn auth_failure_stops_run_without_error_state() {
+        let mut app = app_with_workers(1);
+        app.status = AppStatus::Running;
+        app.workers[0].total_iterations = -1;
+        app.workers[0].auth_error = Some("Invalid API key · Please run /login".into());
+
+        app.handle_channel_disconnected(0, Some(1));
+
+        assert_eq!(app.status, AppStatus::Stopped);
+        assert_eq!(
+            app.auth_failure.as_deref(),
+            Some("Invalid API key · Please run /login")
+        );
+        assert!(app.workers[0].auth_error.is_none());
+        assert!(app.workers[0].pending_work_check.is_none());
+        assert_eq!(app.workers[0].total_iterations, 0);
+    }
+
     #[test]
     fn worker_count_matches_config() {
         for n in [1, 2, 4] {
@@ -518,7 +1174,7 @@ mod tests {
     fn status_derivation_one_running() {
         let mut app = app_with_workers(3);
         // Simulate one worker having a receiver (active)
-        let (_tx, rx) = std::sync::mpsc::channel::<crate::output::OutputMessage>();
+        let (_tx, rx) = std::sync::mpsc::channel::<ralph_core::output::OutputMessage>();
         app.workers[1].output_receiver = Some(rx);
         app.status = AppStatus::Stopped;
         app.update_derived_status();
@@ -533,16 +1189,215 @@ mod tests {
         assert_eq!(app.status, AppStatus::Error);
     }
 
+    #[test]
+    fn quiet_hours_hold_pending_auto_continue() {
+        let mut app = app_with_workers(2);
+        assert!(!app.set_quiet_hours_hold(true));
+        assert!(!app.quiet_hours_paused);
+
+        app.workers[1].auto_continue_pending = true;
+        assert!(app.set_quiet_hours_hold(true));
+        assert!(app.quiet_hours_paused);
+        assert!(!app.set_quiet_hours_hold(false));
+        assert!(!app.quiet_hours_paused);
+        assert!(app.workers[1].auto_continue_pending);
+    }
+
+    #[test]
+    fn cancel_held_iterations_drops_pending_runs() {
+        let mut app = app_with_workers(1);
+        assert!(!app.cancel_held_iterations());
+
+        app.workers[0].auto_continue_pending = true;
+        app.workers[0].total_iterations = 5;
+        app.set_quiet_hours_hold(true);
+        assert!(app.cancel_held_iterations());
+        assert!(!app.quiet_hours_paused);
+        assert!(!app.workers[0].auto_continue_pending);
+        assert_eq!(app.workers[0].total_iterations, 0);
+    }
+
+    #[test]
+    fn question_holds_only_the_worker_that_asked() {
+        let mut app = app_with_workers(2);
+        app.workers[0].auto_continue_pending = true;
+        assert!(!app.hold_for_question());
+
+        app.workers[1].auto_continue_pending = true;
+        app.workers[1].question = Some("Which fixture?".into());
+        assert!(app.hold_for_question());
+        assert!(app.question_paused);
+        assert_eq!(app.question_reply_state.as_ref().unwrap().worker, 1);
+
+        app.workers[1].total_iterations = 5;
+        assert!(app.cancel_held_iterations());
+        assert!(!app.question_paused);
+        assert!(app.question_reply_state.is_none());
+        assert!(app.workers[1].question.is_none());
+        assert!(!app.hold_for_question());
+    }
+
+    #[test]
+    fn disk_hold_pauses_until_space_is_freed() {
+        let mut app = app_with_workers(1);
+        app.config.disk.min_free_mb = u64::MAX;
+        assert!(!app.hold_for_disk_space());
+
+        app.workers[0].auto_continue_pending = true;
+        assert!(app.hold_for_disk_space());
+        assert!(
+            app.disk_paused
+                .as_deref()
+                .is_some_and(|r| r.starts_with("LOW DISK SPACE"))
+        );
+        app.config.disk.min_free_mb = 0;
+        assert!(!app.hold_for_disk_space());
+        assert!(app.disk_paused.is_none());
+
+        app.config.disk.min_free_mb = u64::MAX;
+        assert!(app.hold_for_disk_space());
+        assert!(app.cancel_held_iterations());
+        assert!(app.disk_paused.is_none());
+        assert!(!app.workers[0].auto_continue_pending);
+    }
+
+    #[test]
+    fn budget_throttle_holds_until_spend_ages_out() {
+        let mut app = app_with_workers(1);
+        app.config.budget.max_usd_per_hour = Some(1.0);
+        app.workers[0].auto_continue_pending = true;
+        assert!(!app.hold_for_budget());
+
+        app.spend.record(2.5);
+        assert!(app.hold_for_budget());
+        assert!(app.budget_throttled_until.is_some());
+
+        assert!(app.cancel_held_iterations());
+        assert!(app.budget_throttled_until.is_none());
+        assert!(!app.hold_for_budget());
+    }
+
     #[test]
     fn any_worker_active_false_when_all_idle() {
         let app = app_with_workers(3);
         assert!(!app.any_worker_active());
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn stop_interrupts_then_kills_after_grace() {
+        let mut app = app_with_workers(1);
+        let mut cmd = std::process::Command::new("sleep");
+        cmd.arg("60");
+        control::own_process_group(&mut cmd);
+        let (_tx, rx) = std::sync::mpsc::channel::<ralph_core::output::OutputMessage>();
+        app.workers[0].child_process = Some(cmd.spawn().unwrap());
+        app.workers[0].output_receiver = Some(rx);
+        app.status = AppStatus::Running;
+
+        app.stop_command();
+        assert!(app.workers[0].stop_deadline.is_some());
+        assert!(app.workers[0].output_receiver.is_some());
+        assert_eq!(app.status, AppStatus::Running);
+
+        app.workers[0].stop_deadline = Some(Instant::now());
+        app.poll_stop_deadlines();
+        assert!(app.workers[0].child_process.is_none());
+        assert!(app.workers[0].stop_deadline.is_none());
+        assert_eq!(app.status, AppStatus::Stopped);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stop_grace_comes_from_config() {
+        let mut app = app_with_workers(1);
+        app.config.behavior.stop_grace_secs = 30;
+        let mut cmd = std::process::Command::new("sleep");
+        cmd.arg("60");
+        control::own_process_group(&mut cmd);
+        app.workers[0].child_process = Some(cmd.spawn().unwrap());
+        app.status = AppStatus::Running;
+
+        let before = app.clock.now();
+        app.stop_command();
+        let deadline = app.workers[0].stop_deadline.unwrap();
+        assert!(deadline >= before + Duration::from_secs(30));
+        app.workers[0].kill_child();
+        assert!(!app.workers[0].child_running());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn pause_freezes_the_timer_until_resumed() {
+        let mut app = app_with_workers(1);
+        let mut cmd = std::process::Command::new("sleep");
+        cmd.arg("60");
+        control::own_process_group(&mut cmd);
+        app.workers[0].child_process = Some(cmd.spawn().unwrap());
+        app.status = AppStatus::Running;
+        let start = Instant::now();
+        app.workers[0].run_start_time = Some(start);
+
+        app.toggle_pause();
+        let paused_at = app.workers[0].paused_at.unwrap();
+        assert!(crate::ui::status_text(&app).starts_with("PAUSED "));
+
+        app.workers[0].resume(paused_at + Duration::from_secs(90));
+        assert!(app.workers[0].paused_at.is_none());
+        assert_eq!(
+            app.workers[0].run_start_time,
+            Some(start + Duration::from_secs(90))
+        );
+        assert!(app.workers[0].child_running());
+        app.workers[0].kill_child();
+    }
+
+    #[test]
+    fn second_stop_kills_an_interrupted_run() {
+        let mut app = app_with_workers(1);
+        app.workers[0].child_process = Some(
+            std::process::Command::new("sleep")
+                .arg("60")
+                .spawn()
+                .unwrap(),
+        );
+        app.workers[0].stop_deadline = Some(Instant::now() + Duration::from_secs(5));
+        app.status = AppStatus::Running;
+
+        app.stop_command();
+        assert!(app.workers[0].child_process.is_none());
+        assert!(app.workers[0].stop_deadline.is_none());
+        assert_eq!(app.status, AppStatus::Stopped);
+    }
+
+    #[test]
+    fn poll_config_reloads_when_an_extended_file_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("config.toml");
+        let base = dir.path().join("base.toml");
+        std::fs::write(&project, "extends = \"base.toml\"\n").unwrap();
+        std::fs::write(&base, "[display]\nresult_preview_lines = 2\n").unwrap();
+        let mut loaded = LoadedConfig::default_for_test();
+        loaded.project_config_path = Some(project);
+        let mut app = App::new("test".into(), None, loaded, None);
+        assert_eq!(app.config_base_mtimes.len(), 1);
+
+        std::fs::write(&base, "[display]\nresult_preview_lines = 7\n").unwrap();
+        // Stand in for a newer mtime; the rewrite may land in the same tick
+        app.config_base_mtimes[0].1 = None;
+        app.last_config_poll = Instant::now() - Duration::from_secs(10);
+        app.poll_config();
+
+        assert_eq!(app.config.display.result_preview_lines, 7);
+        let (hint, _) = app.hint.as_ref().unwrap();
+        assert_eq!(hint, "Config reloaded (base.toml changed)");
+        assert!(app.config_base_mtimes[0].1.is_some());
+    }
+
     #[test]
     fn any_worker_active_true_with_receiver() {
         let mut app = app_with_workers(3);
-        let (_tx, rx) = std::sync::mpsc::channel::<crate::output::OutputMessage>();
+        let (_tx, rx) = std::sync::mpsc::channel::<ralph_core::output::OutputMessage>();
         app.workers[2].output_receiver = Some(rx);
         assert!(app.any_worker_active());
     }
@@ -598,4 +1453,91 @@ mod tests {
         app.reshape_workers_to(0);
         assert_eq!(app.workers.len(), 1);
     }
+
+    fn app_with_manual_clock() -> (App, Arc<ManualClock>) {
+        let mut app = app_with_workers(1);
+        let clock = Arc::new(ManualClock::new());
+        app.clock = clock.clone();
+        (app, clock)
+    }
+
+    #[test]
+    fn hint_clears_after_three_seconds() {
+        let (mut app, clock) = app_with_manual_clock();
+        app.set_hint("hello");
+        clock.advance(Duration::from_millis(2999));
+        app.check_hint_timeout();
+        assert!(app.hint.is_some());
+
+        clock.advance(Duration::from_millis(1));
+        app.check_hint_timeout();
+        assert!(app.hint.is_none());
+    }
+
+    #[test]
+    fn error_reverts_to_stopped_after_five_seconds() {
+        let (mut app, clock) = app_with_manual_clock();
+        app.status = AppStatus::Error;
+        app.error_at = Some(clock.now());
+        clock.advance(Duration::from_secs(4));
+        app.check_error_timeout();
+        assert_eq!(app.status, AppStatus::Error);
+
+        clock.advance(Duration::from_secs(1));
+        app.check_error_timeout();
+        assert_eq!(app.status, AppStatus::Stopped);
+    }
+
+    #[test]
+    fn iteration_past_the_timeout_is_killed_into_the_error_state() {
+        let (mut app, clock) = app_with_manual_clock();
+        app.config.behavior.iteration_timeout_secs = 60;
+        app.workers[0].child_process = Some(
+            std::process::Command::new("sleep")
+                .arg("60")
+                .spawn()
+                .unwrap(),
+        );
+        app.workers[0].run_start_time = Some(clock.now());
+        app.status = AppStatus::Running;
+
+        clock.advance(Duration::from_secs(45));
+        assert!(crate::ui::status_text(&app).ends_with("(0:15 left)"));
+        app.poll_iteration_timeouts();
+        assert!(app.workers[0].child_process.is_some());
+
+        clock.advance(Duration::from_secs(15));
+        app.poll_iteration_timeouts();
+        assert!(app.workers[0].child_process.is_none());
+        assert!(app.workers[0].timed_out.is_none());
+        assert_eq!(app.status, AppStatus::Error);
+    }
+
+    #[test]
+    fn quiet_hours_hold_follows_the_clock() {
+        let (mut app, clock) = app_with_manual_clock();
+        app.config.schedule.quiet_hours = Some("23:00-07:00".into());
+        app.workers[0].auto_continue_pending = true;
+
+        clock.set_minute_of_day(22 * 60 + 59);
+        assert!(!app.hold_for_quiet_hours());
+        clock.advance(Duration::from_secs(60));
+        assert!(app.hold_for_quiet_hours());
+        clock.set_minute_of_day(7 * 60);
+        assert!(!app.hold_for_quiet_hours());
+    }
+
+    #[test]
+    fn read_only_denies_actions_with_a_hint() {
+        let mut app = app_with_workers(1);
+        assert!(!app.deny_in_read_only("Init"));
+        assert!(app.hint.is_none());
+
+        app.read_only = true;
+        assert!(app.deny_in_read_only("Init"));
+        assert_eq!(
+            app.hint.as_ref().map(|(h, _)| h.as_str()),
+            Some("Read-only mode: Init is disabled")
+        );
+    }
 }
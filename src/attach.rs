@@ -0,0 +1,128 @@
+//! `ralph attach` — read-only observer for a session running elsewhere.
+//!
+//! Tails the session's transcript and feeds each line through the normal output
+//! pipeline into a throwaway `App`, rendered with the workers stream view. No
+//! workers are spawned, nothing is claimed, and no bd mutations are made.
+
+use std::io;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use crossterm::event::{DisableMouseCapture, Event, KeyCode, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use ratatui::{DefaultTerminal, Terminal};
+
+use crate::app::App;
+use crate::config;
+use crate::modals::{WorkersStreamState, draw_workers_stream, handle_workers_stream_input};
+use crate::output;
+use crate::suspend;
+use crate::transcript;
+use crate::ui::{apply_theme, detect_color_depth, fit_frame_colors};
+
+/// Upper bound on workers an observed session can have (matches the config limit).
+const MAX_OBSERVED_WORKERS: usize = 8;
+
+/// Entry point for `ralph attach <session_id>`.
+pub fn run(session_id: &str) -> Result<()> {
+    let path = transcript::transcript_path(session_id)
+        .ok_or_else(|| anyhow!("Failed to determine log directory"))?;
+    if !path.exists() && crate::encryption::encrypted_path(&path).exists() {
+        return Err(anyhow!(
+            "The transcript for session {session_id} is encrypted and can't be attached to\n\
+             Decrypt a copy with `age -d -i <identity> {}`",
+            crate::encryption::encrypted_path(&path).display()
+        ));
+    }
+    if !path.exists() {
+        let recent = transcript::list_attachable_sessions();
+        let hint = if recent.is_empty() {
+            "No session transcripts found.".to_string()
+        } else {
+            let ids: Vec<&str> = recent.iter().take(5).map(String::as_str).collect();
+            format!("Recent sessions: {}", ids.join(", "))
+        };
+        return Err(anyhow!(
+            "No transcript for session {session_id} at {}\n{hint}",
+            path.display()
+        ));
+    }
+    let tail = transcript::tail(path)?;
+
+    let mut app = App::new(
+        format!("attach-{session_id}"),
+        None,
+        config::load_config(),
+        None,
+    );
+    app.attached_session = Some(session_id.to_string());
+    app.show_workers_stream = true;
+    app.workers_stream_state = Some(WorkersStreamState::new(0));
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    suspend::set_mouse_capture(app.config.keys.mouse_capture)?;
+    let mut terminal = Terminal::new(ratatui::backend::CrosstermBackend::new(stdout))?;
+
+    let result = observe(&mut app, &mut terminal, tail.lines());
+
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+
+    result
+}
+
+/// Drain transcript lines into the app and redraw until the user quits.
+fn observe(
+    app: &mut App,
+    terminal: &mut DefaultTerminal,
+    rx: &std::sync::mpsc::Receiver<(usize, String)>,
+) -> Result<()> {
+    let detected_colors = detect_color_depth();
+    loop {
+        for (w, line) in rx.try_iter() {
+            if w >= MAX_OBSERVED_WORKERS {
+                continue;
+            }
+            if w >= app.workers.len() {
+                app.reshape_workers_to(w + 1);
+            }
+            app.selected_worker = w;
+            output::process_line(app, &line);
+            app.dirty = true;
+        }
+        app.selected_worker = 0;
+        app.check_hint_timeout();
+
+        if app.dirty {
+            let color_depth = app.config.display.color_depth.or_detected(detected_colors);
+            terminal.draw(|f| {
+                draw_workers_stream(f, app);
+                apply_theme(f, app.config.display.theme);
+                fit_frame_colors(f, color_depth);
+            })?;
+            app.dirty = false;
+        }
+
+        if crossterm::event::poll(Duration::from_millis(100))? {
+            let Event::Key(key) = crossterm::event::read()? else {
+                app.dirty = true;
+                continue;
+            };
+            app.dirty = true;
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(());
+                }
+                // Help isn't rendered in observer mode
+                KeyCode::Char('?') => {}
+                _ => handle_workers_stream_input(app, key.code, key.modifiers),
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! The `bd` CLI as a tab runs it.
+//!
+//! bd finds its database by walking up from the directory it runs in, so a
+//! tab opened on another project has to run bd there rather than in ralph's
+//! own working directory.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The configured bd binary and the project directory it runs in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bd {
+    path: String,
+    dir: PathBuf,
+}
+
+impl Bd {
+    pub fn new(path: impl Into<String>, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            dir: dir.into(),
+        }
+    }
+
+    /// bd run in the current directory, for the CLI subcommands.
+    pub fn in_cwd(path: impl Into<String>) -> Self {
+        Self::new(path, ".")
+    }
+
+    /// The configured binary, as shown in error messages.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The project directory bd runs in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// A `Command` for bd in the project directory. Hold the
+    /// [`crate::bd_lock`] mutex from spawn until it exits.
+    pub fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.path);
+        cmd.current_dir(&self.dir);
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_runs_in_the_project_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let bd = Bd::new("pwd", dir.path());
+        let output = bd.command().output().unwrap();
+        let cwd = dir.path().canonicalize().unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim(),
+            cwd.display().to_string()
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Why a blocked bead is blocked, pulled out of its own text.
+//!
+//! The prompt has agents record a blocker as a `BLOCKED: …` note; people
+//! tend to write a `## Blocked` or `## Blockers` section instead. Either is
+//! reduced to one line so the board can show it without opening the bead.
+
+/// Labels that introduce a reason on the same line (lowercase).
+const LABELS: [&str; 3] = ["blocked:", "blockers:", "blocker:"];
+
+/// Headings that introduce a reason section (lowercase, without `#` or `:`).
+const HEADINGS: [&str; 4] = ["blocked", "blockers", "blocker", "blocked by"];
+
+/// The first reason found in `texts`, checked in order (e.g. notes, then
+/// description, then design). Within a text the last `BLOCKED:` line wins,
+/// being the newest, then the first Blocked/Blockers section.
+pub fn extract(texts: &[&str]) -> Option<String> {
+    texts.iter().find_map(|text| from_text(text))
+}
+
+fn from_text(text: &str) -> Option<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    for (i, line) in lines.iter().enumerate().rev() {
+        if let Some(rest) = label_rest(line) {
+            // A label on its own line is followed by the reason
+            let reason = if rest.is_empty() {
+                section_text(&lines[i + 1..])
+            } else {
+                Some(collapse(rest))
+            };
+            if reason.is_some() {
+                return reason;
+            }
+        }
+    }
+    let start = lines.iter().position(|l| is_reason_heading(l))?;
+    section_text(&lines[start + 1..])
+}
+
+/// Text after a leading `BLOCKED:`-style label (bullets and bold allowed).
+fn label_rest(line: &str) -> Option<&str> {
+    let line = strip_bullet(line.trim()).trim_start_matches(['*', '_']);
+    LABELS.iter().find_map(|label| {
+        let head = line.get(..label.len())?;
+        head.eq_ignore_ascii_case(label)
+            .then(|| line[label.len()..].trim_start_matches(['*', '_']).trim())
+    })
+}
+
+fn is_reason_heading(line: &str) -> bool {
+    line.starts_with('#') && {
+        let title = line.trim_start_matches('#').trim().trim_end_matches(':');
+        HEADINGS.contains(&title.to_lowercase().as_str())
+    }
+}
+
+/// Non-empty lines up to the next heading, joined with `; `.
+fn section_text(lines: &[&str]) -> Option<String> {
+    let items: Vec<String> = lines
+        .iter()
+        .take_while(|l| !l.starts_with('#'))
+        .map(|l| collapse(strip_bullet(l.trim())))
+        .filter(|l| !l.is_empty())
+        .collect();
+    (!items.is_empty()).then(|| items.join("; "))
+}
+
+fn strip_bullet(line: &str) -> &str {
+    ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|b| line.strip_prefix(b))
+        .unwrap_or(line)
+}
+
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newest_blocked_note_wins() {
+        let notes =
+            "BLOCKED: cargo can't reach crates.io\n\nTried again.\nBLOCKED:   staging DB is down";
+        assert_eq!(extract(&[notes]).as_deref(), Some("staging DB is down"));
+    }
+
+    #[test]
+    fn labels_allow_bullets_bold_and_any_case() {
+        assert_eq!(
+            extract(&["- **Blocker:** needs an API key"]).as_deref(),
+            Some("needs an API key")
+        );
+        assert_eq!(
+            extract(&["blocked:\n  waiting on design\n"]).as_deref(),
+            Some("waiting on design")
+        );
+    }
+
+    #[test]
+    fn falls_back_to_a_blockers_section() {
+        let description = "Add export.\n\n## Blockers\n- bd-12 schema\n- legal review\n\n## Acceptance\n- [ ] works";
+        assert_eq!(
+            extract(&["", description]).as_deref(),
+            Some("bd-12 schema; legal review")
+        );
+    }
+
+    #[test]
+    fn nothing_without_a_label_or_section() {
+        assert_eq!(
+            extract(&["Blocked on nothing in particular", "## Approach\n- x"]),
+            None
+        );
+        assert_eq!(extract(&["## Blocked\n\n## Next"]), None);
+    }
+}
@@ -0,0 +1,202 @@
+//! What the installed Claude CLI can output, probed once per binary from
+//! `claude --help`.
+//!
+//! Ralph is built around `--output-format=stream-json` with
+//! `--include-partial-messages`. Older CLIs reject those flags and the run
+//! fails with an opaque broken stream, so the args follow what the binary
+//! supports: plain `json` gives only the final message and usage, plain text
+//! only the final message.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{LazyLock, Mutex};
+
+use tracing::info;
+
+use crate::app::App;
+
+/// Probe results by binary path.
+static PROBED: LazyLock<Mutex<HashMap<PathBuf, Capabilities>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// `--output-format` value, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// One JSON event per line as the iteration runs.
+    #[default]
+    StreamJson,
+    /// A single result object when the iteration ends.
+    Json,
+    /// The final message as plain text.
+    Text,
+}
+
+/// Output options the Claude CLI supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    pub format: OutputFormat,
+    /// Text and tool input stream as they're generated (`--include-partial-messages`).
+    pub partial_messages: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            format: OutputFormat::StreamJson,
+            partial_messages: true,
+        }
+    }
+}
+
+impl Capabilities {
+    /// Read the options from `claude --help` output.
+    pub fn from_help(help: &str) -> Self {
+        let lines: Vec<&str> = help.lines().collect();
+        let format = match lines.iter().position(|l| l.contains("--output-format")) {
+            None => OutputFormat::Text,
+            Some(i) => {
+                // Choices can wrap onto the lines below, up to the next option
+                let mut described = lines[i].to_string();
+                for line in lines[i + 1..]
+                    .iter()
+                    .take_while(|l| !l.trim_start().starts_with('-'))
+                {
+                    described.push_str(line);
+                }
+                if described.contains("stream-json") {
+                    OutputFormat::StreamJson
+                } else if described.contains("json") {
+                    OutputFormat::Json
+                } else {
+                    OutputFormat::Text
+                }
+            }
+        };
+        Self {
+            format,
+            partial_messages: format == OutputFormat::StreamJson
+                && help.contains("--include-partial-messages"),
+        }
+    }
+
+    /// Arguments selecting the output, ahead of `--resume` and control args.
+    pub fn args(&self) -> String {
+        match self.format {
+            OutputFormat::StreamJson if self.partial_messages => {
+                "--output-format=stream-json --verbose --print --include-partial-messages"
+            }
+            OutputFormat::StreamJson => "--output-format=stream-json --verbose --print",
+            OutputFormat::Json => "--output-format=json --print",
+            OutputFormat::Text => "--print",
+        }
+        .to_string()
+    }
+
+    /// Whether assistant text and tool calls show up as Claude works. Without
+    /// it, only the final message is shown.
+    pub fn streams_text(&self) -> bool {
+        self.format == OutputFormat::StreamJson && self.partial_messages
+    }
+
+    /// What's missing compared to a current CLI, or `None` if nothing is.
+    pub fn limitation(&self) -> Option<&'static str> {
+        match self.format {
+            OutputFormat::StreamJson if self.partial_messages => None,
+            OutputFormat::StreamJson => Some(
+                "this Claude CLI has no --include-partial-messages; only tool results and the final message are shown",
+            ),
+            OutputFormat::Json => Some(
+                "this Claude CLI has no stream-json output; only the final message and usage are shown",
+            ),
+            OutputFormat::Text => Some(
+                "this Claude CLI has no JSON output; only the final message is shown, without cost or usage",
+            ),
+        }
+    }
+}
+
+/// Capabilities of the CLI at `claude_path`, running `--help` the first time.
+/// A binary that can't be probed is assumed current, so the run fails the
+/// usual way.
+pub fn probe(claude_path: &Path) -> Capabilities {
+    let mut probed = PROBED.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(caps) = probed.get(claude_path) {
+        return *caps;
+    }
+    let output = crate::bd_lock::non_bd_command(claude_path)
+        .arg("--help")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output();
+    let caps = match output {
+        Ok(output) if output.status.success() => {
+            Capabilities::from_help(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => Capabilities::default(),
+    };
+    info!(path = %claude_path.display(), ?caps, "claude_cli_probed");
+    probed.insert(claude_path.to_path_buf(), caps);
+    caps
+}
+
+impl App {
+    /// Record how worker `w`'s iteration reports output, warning the first
+    /// time a run has to make do with less.
+    pub fn set_output_capabilities(&mut self, w: usize, caps: Capabilities) {
+        self.workers[w].output_caps = caps;
+        if let Some(limitation) = caps.limitation()
+            && !self.output_limitation_shown
+        {
+            self.output_limitation_shown = true;
+            self.add_text_line(format!("⚠ Reduced output: {limitation}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURRENT: &str = "Options:\n  -p, --print  Print response and exit\n  \
+        --output-format <format>  Output format (only works with --print): \"text\" (default),\n                            \
+        \"json\" (single result), or \"stream-json\" (realtime streaming) (choices: \"text\",\n                            \
+        \"json\", \"stream-json\")\n  --include-partial-messages  Include partial message chunks\n  \
+        --verbose  Override verbose mode\n";
+
+    #[test]
+    fn current_cli_streams_everything() {
+        let caps = Capabilities::from_help(CURRENT);
+        assert_eq!(caps, Capabilities::default());
+        assert!(caps.streams_text());
+        assert_eq!(caps.limitation(), None);
+        assert!(caps.args().contains("--include-partial-messages"));
+    }
+
+    #[test]
+    fn older_clis_fall_back() {
+        let no_partial = CURRENT.replace("--include-partial-messages", "--other");
+        let caps = Capabilities::from_help(&no_partial);
+        assert_eq!(caps.format, OutputFormat::StreamJson);
+        assert_eq!(caps.args(), "--output-format=stream-json --verbose --print");
+        assert!(!caps.streams_text());
+
+        let json_only = "  --output-format <format>  \"text\" or \"json\"\n  --verbose\n";
+        let caps = Capabilities::from_help(json_only);
+        assert_eq!(caps.format, OutputFormat::Json);
+        assert!(!caps.partial_messages);
+        assert_eq!(caps.args(), "--output-format=json --print");
+
+        let caps = Capabilities::from_help("  -p, --print  Print response and exit\n");
+        assert_eq!(caps.format, OutputFormat::Text);
+        assert_eq!(caps.args(), "--print");
+        assert!(caps.limitation().is_some());
+    }
+
+    #[test]
+    fn choices_are_read_only_up_to_the_next_option() {
+        let help =
+            "  --output-format <format>  \"text\"\n  --input-format <format>  \"stream-json\"\n";
+        assert_eq!(Capabilities::from_help(help).format, OutputFormat::Text);
+    }
+}
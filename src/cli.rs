@@ -11,13 +11,19 @@ use crate::config;
 use crate::doctor;
 use crate::logging;
 use crate::modals::InitModalState;
+use crate::run_status::StatusFormat;
+use crate::templates::ProjectPreset;
 use crate::work_source;
 
 /// CLI subcommands.
 #[derive(Debug, Parser)]
 pub enum Commands {
     /// Initialize project with Ralph scaffolding
-    Init,
+    Init {
+        /// Tailor PROMPT.md for a project type: generic, rust, web, library, docs
+        #[arg(long, value_parser = ProjectPreset::from_name)]
+        preset: Option<ProjectPreset>,
+    },
     /// Check environment health and report pass/fail for each check
     Doctor,
     /// List implementable beads
@@ -35,11 +41,86 @@ pub enum Commands {
         #[arg(long)]
         path: bool,
     },
+    /// Report cost, tokens, iterations and time spent per spec
+    Specs {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check spec text
+    #[command(subcommand)]
+    Spec(SpecCommands),
+    /// Summarize cost, iterations and specs completed over recent days
+    Stats {
+        /// How many days back to look
+        #[arg(long, default_value_t = 7)]
+        days: u32,
+        /// Include every project, not just this one
+        #[arg(long)]
+        all: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Bundle this project's config, history and reports into a tarball
+    Export {
+        /// Archive to write (defaults to ./ralph-state-<repo>.tar.gz)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Also include session transcripts
+        #[arg(long)]
+        transcripts: bool,
+    },
+    /// Restore project state from a `ralph export` tarball
+    Import {
+        /// Archive written by `ralph export`
+        archive: PathBuf,
+        /// Overwrite existing project config files
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print a one-line status of this project's running session (nothing if idle)
+    Status {
+        /// Output style: plain, tmux (with color codes) or json
+        #[arg(long, default_value = "plain", value_parser = StatusFormat::from_name)]
+        format: StatusFormat,
+    },
+    /// Bundle a session's logs, config and output into an archive for bug reports
+    Report {
+        /// Session to report on (defaults to the most recent)
+        #[arg(long)]
+        id: Option<String>,
+    },
+    /// Watch a running session's output read-only
+    Attach {
+        /// Session ID to observe (see `ralph logs` or the transcripts directory)
+        session_id: String,
+    },
+    /// Run the loop in the background with no TUI (follow it with `attach` and `status`)
+    Daemon {
+        /// Ask the running daemon to stop its run
+        #[arg(long, conflicts_with = "foreground")]
+        stop: bool,
+        /// Run in this process instead of starting a background one
+        #[arg(long)]
+        foreground: bool,
+    },
     /// Manage and inspect tool permissions and history
     #[command(subcommand)]
     Tool(ToolCommands),
 }
 
+/// Subcommands under `ralph spec`.
+#[derive(Debug, Parser)]
+pub enum SpecCommands {
+    /// Flag missing acceptance criteria or error cases, oversized slices and
+    /// unrecorded dependencies
+    Lint {
+        /// Markdown file holding the spec description
+        file: PathBuf,
+    },
+}
+
 /// Subcommands under `ralph tool`.
 #[derive(Debug, Parser)]
 pub enum ToolCommands {
@@ -63,6 +144,9 @@ pub enum ToolCommands {
         /// Filter by repo (basename match if no slash, substring match if has slash)
         #[arg(long)]
         repo: Option<String>,
+        /// Filter to sessions started with this --tag
+        #[arg(long)]
+        tag: Option<String>,
         /// Show tool calls from all repos (overrides default current-repo filter)
         #[arg(long)]
         all: bool,
@@ -102,12 +186,33 @@ pub enum ToolCommands {
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+    /// Label this run (repeatable, e.g. --tag prompt-v3 --tag model:opus)
+    #[arg(long = "tag", value_name = "TAG", value_parser = parse_tag)]
+    pub tags: Vec<String>,
+    /// Browse specs, transcripts and history without starting, initializing or saving anything
+    #[arg(long)]
+    pub read_only: bool,
+    /// Keep all config, data, logs and project state under DIR (for tests and demos)
+    #[arg(long, value_name = "DIR", hide = true)]
+    pub sandbox: Option<PathBuf>,
+}
+
+/// Validate a `--tag` value: non-empty, no whitespace.
+pub fn parse_tag(s: &str) -> Result<String, String> {
+    let tag = s.trim();
+    if tag.is_empty() {
+        return Err("tag must not be empty".to_string());
+    }
+    if tag.chars().any(char::is_whitespace) {
+        return Err(format!("tag '{tag}' must not contain whitespace"));
+    }
+    Ok(tag.to_string())
 }
 
 /// Run the init subcommand: create project scaffolding files.
-pub fn run_init() -> Result<()> {
-    let loaded_config = config::load_config();
-    let state = InitModalState::new(&loaded_config.config);
+pub fn run_init(preset: Option<ProjectPreset>) -> Result<()> {
+    let mut state = InitModalState::with_preset(preset.unwrap_or_default(), &config::startup_dir());
+    state.select_all();
 
     if state.all_up_to_date() {
         println!("All skill files are up to date.");
@@ -329,15 +434,16 @@ pub fn run_doctor() -> Result<()> {
     let mut checks: Vec<doctor::CheckResult> = vec![
         doctor::check_config(&loaded_config),
         doctor::check_claude(cfg),
-        doctor::check_prompt(cfg),
+        doctor::check_prompt(&loaded_config),
+        doctor::check_network(cfg),
     ];
 
     checks.push(doctor::check_bd(cfg));
     checks.push(doctor::check_bd_prime_hook());
     checks.push(doctor::check_bd_retry_hook());
-    checks.push(doctor::check_scaffolding_drift(cfg));
-    checks.push(doctor::check_board_toml());
-    checks.push(doctor::check_work_items(cfg));
+    checks.push(doctor::check_scaffolding_drift(&loaded_config));
+    checks.push(doctor::check_board_toml(&loaded_config));
+    checks.push(doctor::check_work_items(&loaded_config));
 
     let mut all_passed = true;
     for check in &checks {
@@ -1,220 +1,70 @@
 use directories::ProjectDirs;
-use serde::{Deserialize, Serialize};
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
 use tracing::{debug, info, warn};
 
-/// Claude CLI configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
-pub struct ClaudeConfig {
-    pub path: String,
-    /// Legacy field - ignored on load, not serialized.
-    /// CLI args are hardcoded in main.rs as Ralph depends on specific args for streaming.
-    #[serde(skip_serializing, default)]
-    #[allow(dead_code)]
-    pub args: Option<String>,
-}
-
-impl Default for ClaudeConfig {
-    fn default() -> Self {
-        Self {
-            path: "~/.claude/local/claude".to_string(),
-            args: None,
-        }
-    }
-}
-
-/// Logging configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
-pub struct LoggingConfig {
-    pub level: String,
-}
-
-impl Default for LoggingConfig {
-    fn default() -> Self {
-        Self {
-            level: "info".to_string(),
-        }
-    }
-}
+pub use ralph_core::config::*;
 
-/// Behavior configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
-pub struct BehaviorConfig {
-    /// Number of iterations to run:
-    /// - Negative (-1): Infinite mode, continues until user stops or all beads complete
-    /// - Zero (0): Stopped mode, pressing 's' has no effect
-    /// - Positive (N): Runs exactly N iterations then stops
-    pub iterations: i32,
-    /// Whether to acquire a wake lock to prevent display and system idle sleep.
-    /// When true, the system won't sleep while claude is running. Default: true.
-    pub keep_awake: bool,
-    /// Path to the `bd` CLI binary. Default: "bd".
-    pub bd_path: String,
-    /// How often to send agent heartbeats (seconds). Default: 30.
-    pub heartbeat_interval: u64,
-    /// How long before an agent is considered stale (seconds). Default: 180.
-    pub stale_threshold: u64,
-    /// Number of concurrent Claude Code workers to spawn on S press. Default: 1.
-    pub workers: u32,
+/// Filesystem operation that failed, for error messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileOp {
+    Read,
+    Write,
+    CreateDir,
+    Chmod,
 }
 
-impl Default for BehaviorConfig {
-    fn default() -> Self {
-        Self {
-            iterations: -1,   // Infinite mode by default
-            keep_awake: true, // Prevent system sleep by default
-            bd_path: "bd".to_string(),
-            heartbeat_interval: 30,
-            stale_threshold: 180,
-            workers: 1,
-        }
+impl std::fmt::Display for FileOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            FileOp::Read => "read",
+            FileOp::Write => "write",
+            FileOp::CreateDir => "create",
+            FileOp::Chmod => "chmod",
+        })
     }
 }
 
-/// Main application configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub struct Config {
-    #[serde(default)]
-    pub claude: ClaudeConfig,
-    #[serde(default)]
-    pub logging: LoggingConfig,
-    #[serde(default)]
-    pub behavior: BehaviorConfig,
+/// Why loading, saving or seeding a per-project file failed.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Could not determine config directory")]
+    NoConfigDir,
+    #[error("Failed to {op} {}: {source}", path.display())]
+    Io {
+        op: FileOp,
+        path: PathBuf,
+        source: io::Error,
+    },
+    #[error("Invalid project config: {source}")]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("Failed to serialize config: {0}")]
+    Serialize(#[from] toml::ser::Error),
+    #[error("Config extends cycle at {}", path.display())]
+    ExtendsCycle { path: PathBuf },
+    #[error("Config extends chain is longer than {MAX_EXTENDS_DEPTH} files")]
+    ExtendsTooDeep,
+    /// A file further up the `extends` chain failed to load.
+    #[error("{} (extended): {source}", path.display())]
+    Extended {
+        path: PathBuf,
+        source: Box<ConfigError>,
+    },
 }
 
-impl Config {
-    /// Expand `~` to home directory in a path string
-    pub fn expand_tilde(path: &str) -> PathBuf {
-        if let Some(stripped) = path.strip_prefix("~/")
-            && let Some(home) = dirs::home_dir()
-        {
-            return home.join(stripped);
+impl ConfigError {
+    fn io(op: FileOp, path: &Path, source: io::Error) -> Self {
+        ConfigError::Io {
+            op,
+            path: path.to_path_buf(),
+            source,
         }
-        PathBuf::from(path)
-    }
-
-    /// Get the expanded Claude CLI path
-    pub fn claude_path(&self) -> PathBuf {
-        Self::expand_tilde(&self.claude.path)
-    }
-}
-
-/// Partial Claude CLI configuration for project overrides.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(default)]
-pub struct PartialClaudeConfig {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub path: Option<String>,
-}
-
-/// Partial logging configuration for project overrides.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(default)]
-pub struct PartialLoggingConfig {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub level: Option<String>,
-}
-
-/// Partial behavior configuration for project overrides.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(default)]
-pub struct PartialBehaviorConfig {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub iterations: Option<i32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub keep_awake: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub bd_path: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub heartbeat_interval: Option<u64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub stale_threshold: Option<u64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub workers: Option<u32>,
-}
-
-/// Project-specific configuration where every field is optional.
-/// Fields that are `None` inherit from compiled-in defaults.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
-#[serde(default)]
-pub struct PartialConfig {
-    #[serde(skip_serializing_if = "is_partial_claude_empty")]
-    pub claude: PartialClaudeConfig,
-    #[serde(skip_serializing_if = "is_partial_logging_empty")]
-    pub logging: PartialLoggingConfig,
-    #[serde(skip_serializing_if = "is_partial_behavior_empty")]
-    pub behavior: PartialBehaviorConfig,
-}
-
-fn is_partial_claude_empty(c: &PartialClaudeConfig) -> bool {
-    c.path.is_none()
-}
-
-fn is_partial_logging_empty(l: &PartialLoggingConfig) -> bool {
-    l.level.is_none()
-}
-
-fn is_partial_behavior_empty(b: &PartialBehaviorConfig) -> bool {
-    b.iterations.is_none()
-        && b.keep_awake.is_none()
-        && b.bd_path.is_none()
-        && b.heartbeat_interval.is_none()
-        && b.stale_threshold.is_none()
-        && b.workers.is_none()
-}
-
-/// Merge a base config with a project-level partial config.
-/// Project values override base values where present.
-pub fn merge_config(global: &Config, project: &PartialConfig) -> Config {
-    Config {
-        claude: ClaudeConfig {
-            path: project
-                .claude
-                .path
-                .clone()
-                .unwrap_or_else(|| global.claude.path.clone()),
-            args: None,
-        },
-        logging: LoggingConfig {
-            level: project
-                .logging
-                .level
-                .clone()
-                .unwrap_or_else(|| global.logging.level.clone()),
-        },
-        behavior: BehaviorConfig {
-            iterations: project
-                .behavior
-                .iterations
-                .unwrap_or(global.behavior.iterations),
-            keep_awake: project
-                .behavior
-                .keep_awake
-                .unwrap_or(global.behavior.keep_awake),
-            bd_path: project
-                .behavior
-                .bd_path
-                .clone()
-                .unwrap_or_else(|| global.behavior.bd_path.clone()),
-            heartbeat_interval: project
-                .behavior
-                .heartbeat_interval
-                .unwrap_or(global.behavior.heartbeat_interval),
-            stale_threshold: project
-                .behavior
-                .stale_threshold
-                .unwrap_or(global.behavior.stale_threshold),
-            workers: project
-                .behavior
-                .workers
-                .unwrap_or(global.behavior.workers)
-                .max(1),
-        },
     }
 }
 
@@ -222,7 +72,11 @@ pub fn merge_config(global: &Config, project: &PartialConfig) -> Config {
 #[derive(Debug, Clone)]
 pub struct LoadedConfig {
     pub config: Config,
+    /// The project the config was resolved for.
+    pub project_dir: PathBuf,
     pub project_config_path: Option<PathBuf>,
+    /// Out-of-range values that were pulled back into range, described.
+    pub clamped: Vec<String>,
 }
 
 impl LoadedConfig {
@@ -231,13 +85,18 @@ impl LoadedConfig {
     pub fn default_for_test() -> Self {
         Self {
             config: Config::default(),
+            project_dir: startup_dir(),
             project_config_path: None,
+            clamped: Vec::new(),
         }
     }
 }
 
 /// Get the platform-appropriate config directory
 fn get_config_dir() -> Option<PathBuf> {
+    if let Some(sandbox) = crate::sandbox::get() {
+        return Some(sandbox.config_dir());
+    }
     ProjectDirs::from("com", "cmoel", "ralph").map(|dirs| dirs.config_dir().to_path_buf())
 }
 
@@ -247,27 +106,36 @@ fn project_key_from_path(path: &std::path::Path) -> String {
     path.to_string_lossy().replace(['/', '\\'], "-")
 }
 
+/// The directory ralph was started in: the project of the first tab and of
+/// the CLI subcommands. Relative (and so keyed to no project) if the working
+/// directory can't be read.
+pub fn startup_dir() -> PathBuf {
+    std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
+}
+
 /// Compute the per-project config path (deterministic, may not exist yet).
-/// Returns `<config-dir>/projects/<key>/config.toml` based on the current working directory.
-pub fn compute_project_config_path() -> Option<PathBuf> {
+/// Returns `<config-dir>/projects/<key>/config.toml`, keyed by the absolute
+/// project directory.
+pub fn compute_project_config_path(project_dir: &Path) -> Option<PathBuf> {
+    if !project_dir.is_absolute() {
+        return None;
+    }
     let config_dir = get_config_dir()?;
-    let cwd = std::env::current_dir().ok()?;
-    let key = project_key_from_path(&cwd);
+    let key = project_key_from_path(project_dir);
     Some(config_dir.join("projects").join(key).join("config.toml"))
 }
 
 /// Get the per-project config path if the file exists.
-pub fn get_project_config_path() -> Option<PathBuf> {
-    let path = compute_project_config_path()?;
+pub fn get_project_config_path(project_dir: &Path) -> Option<PathBuf> {
+    let path = compute_project_config_path(project_dir)?;
     if path.exists() { Some(path) } else { None }
 }
 
 /// Resolve the per-project PROMPT.md path if the file exists.
 /// Returns the path to `<per-project-config-dir>/PROMPT.md` when present, or None
 /// (meaning the compiled-in default should be used).
-pub fn resolve_prompt_path() -> Option<PathBuf> {
-    let config_path = compute_project_config_path()?;
-    let prompt_path = config_path.with_file_name("PROMPT.md");
+pub fn resolve_prompt_path(project_dir: &Path) -> Option<PathBuf> {
+    let prompt_path = compute_prompt_path(project_dir)?;
     if prompt_path.exists() {
         Some(prompt_path)
     } else {
@@ -275,11 +143,32 @@ pub fn resolve_prompt_path() -> Option<PathBuf> {
     }
 }
 
+/// Compute the per-project PROMPT.md path (deterministic, may not exist yet).
+pub fn compute_prompt_path(project_dir: &Path) -> Option<PathBuf> {
+    Some(compute_project_config_path(project_dir)?.with_file_name("PROMPT.md"))
+}
+
+/// Return the per-project PROMPT.md path for editing, first seeding it with the
+/// compiled-in prompt so an edit starts from what Claude currently receives.
+pub fn ensure_project_prompt(project_dir: &Path) -> Result<PathBuf, ConfigError> {
+    let path = compute_prompt_path(project_dir).ok_or(ConfigError::NoConfigDir)?;
+    if !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| ConfigError::io(FileOp::CreateDir, parent, e))?;
+        }
+        fs::write(&path, crate::templates::PROMPT_MD)
+            .map_err(|e| ConfigError::io(FileOp::Write, &path, e))?;
+        info!(path = %path.display(), "project_prompt_seeded");
+    }
+    Ok(path)
+}
+
 /// Resolve the per-project board_columns.toml path if the file exists.
 /// Returns the path to `<per-project-config-dir>/board_columns.toml` when present,
 /// or None (meaning the compiled-in default should be used).
-pub fn resolve_board_columns_path() -> Option<PathBuf> {
-    let config_path = compute_project_config_path()?;
+pub fn resolve_board_columns_path(project_dir: &Path) -> Option<PathBuf> {
+    let config_path = compute_project_config_path(project_dir)?;
     let board_path = config_path.with_file_name("board_columns.toml");
     if board_path.exists() {
         Some(board_path)
@@ -289,30 +178,114 @@ pub fn resolve_board_columns_path() -> Option<PathBuf> {
 }
 
 /// Load a per-project config from the given path.
-/// Returns Ok(PartialConfig) on success, Err(String) on parse/read failure.
-pub fn load_project_config(path: &PathBuf) -> Result<PartialConfig, String> {
+pub fn load_project_config(path: &PathBuf) -> Result<PartialConfig, ConfigError> {
     let contents = fs::read_to_string(path).map_err(|e| {
         warn!(path = ?path, error = %e, "project_config_read_failed");
-        format!("Failed to read project config: {}", e)
+        ConfigError::io(FileOp::Read, path, e)
     })?;
 
-    toml::from_str::<PartialConfig>(&contents).map_err(|e| {
-        warn!(path = ?path, error = %e, "project_config_parse_failed");
-        format!("Invalid project config: {}", e)
+    toml::from_str::<PartialConfig>(&contents).map_err(|source| {
+        warn!(path = ?path, error = %source, "project_config_parse_failed");
+        ConfigError::Parse {
+            path: path.clone(),
+            source,
+        }
     })
 }
 
+/// Most files an `extends` chain may span.
+const MAX_EXTENDS_DEPTH: usize = 8;
+
+/// Resolve an `extends` value against the file it appears in.
+fn resolve_extends(value: &str, from: &Path) -> PathBuf {
+    let path = Config::expand_tilde(value);
+    match from.parent() {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path,
+    }
+}
+
+/// Load a project config and every file it `extends`, base first.
+pub fn load_config_layers(path: &Path) -> Result<Vec<PartialConfig>, ConfigError> {
+    let mut layers = Vec::new();
+    let mut seen = Vec::new();
+    let mut next = Some(path.to_path_buf());
+    while let Some(path) = next.take() {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if seen.contains(&canonical) {
+            return Err(ConfigError::ExtendsCycle { path });
+        }
+        if seen.len() == MAX_EXTENDS_DEPTH {
+            return Err(ConfigError::ExtendsTooDeep);
+        }
+        seen.push(canonical);
+        let partial = load_project_config(&path).map_err(|e| {
+            if layers.is_empty() {
+                e
+            } else {
+                ConfigError::Extended {
+                    path: path.clone(),
+                    source: Box::new(e),
+                }
+            }
+        })?;
+        next = partial
+            .extends
+            .as_deref()
+            .map(|e| resolve_extends(e, &path));
+        layers.push(partial);
+    }
+    layers.reverse();
+    Ok(layers)
+}
+
+/// Files a project config `extends`, nearest first. Follows the chain as far
+/// as it can be read, including a named file that doesn't exist yet, so
+/// callers can watch every file that affects the merged config.
+pub fn extends_chain(path: &Path) -> Vec<PathBuf> {
+    let mut chain: Vec<PathBuf> = Vec::new();
+    let mut current = path.to_path_buf();
+    while chain.len() < MAX_EXTENDS_DEPTH {
+        let Some(next) = fs::read_to_string(&current)
+            .ok()
+            .and_then(|contents| toml::from_str::<PartialConfig>(&contents).ok())
+            .and_then(|partial| partial.extends)
+            .map(|e| resolve_extends(&e, &current))
+        else {
+            break;
+        };
+        if next == path || chain.contains(&next) {
+            break;
+        }
+        chain.push(next.clone());
+        current = next;
+    }
+    chain
+}
+
+/// Apply config layers (base first) on top of `config`.
+fn merge_layers(config: Config, layers: &[PartialConfig]) -> Config {
+    layers
+        .iter()
+        .fold(config, |config, layer| merge_config(&config, layer))
+}
+
 /// Load configuration from compiled-in defaults, per-project overrides, and env vars.
 pub fn load_config() -> LoadedConfig {
+    load_config_for(&startup_dir())
+}
+
+/// [`load_config`] for the project in `project_dir`.
+pub fn load_config_for(project_dir: &Path) -> LoadedConfig {
     let mut config = Config::default();
 
     // Check for per-project config file
-    let project_config_path = get_project_config_path();
+    let project_config_path = get_project_config_path(project_dir);
     if let Some(ref project_path) = project_config_path {
-        match load_project_config(project_path) {
-            Ok(partial) => {
-                config = merge_config(&config, &partial);
-                info!(path = ?project_path, "project_config_loaded");
+        match load_config_layers(project_path) {
+            Ok(layers) => {
+                config = merge_layers(config, &layers);
+                info!(path = ?project_path, layers = layers.len(), "project_config_loaded");
             }
             Err(e) => {
                 warn!(path = ?project_path, error = %e, "project_config_error");
@@ -320,18 +293,32 @@ pub fn load_config() -> LoadedConfig {
         }
     }
 
-    let config = apply_env_overrides(config);
+    let mut config = apply_env_overrides(config);
+    let clamped = clamp_numbers(&mut config);
 
     LoadedConfig {
         config,
+        project_dir: project_dir.to_path_buf(),
         project_config_path,
+        clamped,
     }
 }
 
+/// [`crate::validators::clamp_numbers`], logging each adjustment.
+fn clamp_numbers(config: &mut Config) -> Vec<String> {
+    let clamped = crate::validators::clamp_numbers(config);
+    for adjustment in &clamped {
+        warn!(adjustment = %adjustment, "config_value_clamped");
+    }
+    clamped
+}
+
 /// Result of reloading configuration.
 pub struct ReloadedConfig {
     pub config: Config,
-    pub project_error: Option<String>,
+    pub project_error: Option<ConfigError>,
+    /// Out-of-range values that were pulled back into range, described.
+    pub clamped: Vec<String>,
 }
 
 /// Reload configuration from compiled-in defaults and optional project config.
@@ -342,9 +329,9 @@ pub fn reload_config(project_config_path: Option<&PathBuf>) -> ReloadedConfig {
     // Merge with project config if present
     let project_error = if let Some(project_path) = project_config_path {
         if project_path.exists() {
-            match load_project_config(project_path) {
-                Ok(partial) => {
-                    config = merge_config(&config, &partial);
+            match load_config_layers(project_path) {
+                Ok(layers) => {
+                    config = merge_layers(config, &layers);
                     None
                 }
                 Err(e) => Some(e),
@@ -357,22 +344,27 @@ pub fn reload_config(project_config_path: Option<&PathBuf>) -> ReloadedConfig {
         None
     };
 
-    let config = apply_env_overrides(config);
+    let mut config = apply_env_overrides(config);
+    let clamped = clamp_numbers(&mut config);
     info!("config_reloaded");
 
     ReloadedConfig {
         config,
         project_error,
+        clamped,
     }
 }
 
 /// Save a partial config to the given file path (per-project config).
 /// Creates parent directories if needed. Prepends a comment header.
 /// Only writes fields that are Some.
-pub fn save_partial_config(partial: &PartialConfig, config_path: &PathBuf) -> Result<(), String> {
+pub fn save_partial_config(
+    partial: &PartialConfig,
+    config_path: &PathBuf,
+) -> Result<(), ConfigError> {
     let toml_content = toml::to_string_pretty(partial).map_err(|e| {
         warn!(error = %e, "partial_config_save_serialize_failed");
-        format!("Failed to serialize config: {}", e)
+        ConfigError::from(e)
     })?;
 
     // Prepend comment header, then TOML content (skip if all fields are None)
@@ -389,13 +381,13 @@ pub fn save_partial_config(partial: &PartialConfig, config_path: &PathBuf) -> Re
     if let Some(parent) = config_path.parent() {
         fs::create_dir_all(parent).map_err(|e| {
             warn!(path = ?parent, error = %e, "project_config_dir_create_failed");
-            format!("Failed to create project config directory: {}", e)
+            ConfigError::io(FileOp::CreateDir, parent, e)
         })?;
     }
 
     fs::write(config_path, &content).map_err(|e| {
         warn!(path = ?config_path, error = %e, "partial_config_save_write_failed");
-        format!("Failed to write config: {}", e)
+        ConfigError::io(FileOp::Write, config_path, e)
     })?;
 
     info!(path = ?config_path, "partial_config_saved");
@@ -426,275 +418,6 @@ fn apply_env_overrides(mut config: Config) -> Config {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_default_config() {
-        let config = Config::default();
-        assert_eq!(config.claude.path, "~/.claude/local/claude");
-        assert!(config.claude.args.is_none());
-        assert_eq!(config.logging.level, "info");
-    }
-
-    #[test]
-    fn test_expand_tilde() {
-        let expanded = Config::expand_tilde("~/.config/test");
-        assert!(!expanded.to_string_lossy().starts_with('~'));
-
-        let no_tilde = Config::expand_tilde("/absolute/path");
-        assert_eq!(no_tilde, PathBuf::from("/absolute/path"));
-
-        let relative = Config::expand_tilde("./relative/path");
-        assert_eq!(relative, PathBuf::from("./relative/path"));
-    }
-
-    #[test]
-    fn test_config_deserialization() {
-        let toml_str = r#"
-[claude]
-path = "/custom/claude"
-
-[logging]
-level = "debug"
-"#;
-
-        let config: Config = toml::from_str(toml_str).unwrap();
-        assert_eq!(config.claude.path, "/custom/claude");
-        assert!(config.claude.args.is_none());
-        assert_eq!(config.logging.level, "debug");
-    }
-
-    #[test]
-    fn test_config_partial_deserialization() {
-        // Only claude section specified, others should use defaults
-        let toml_str = r#"
-[claude]
-path = "/custom/claude"
-"#;
-
-        let config: Config = toml::from_str(toml_str).unwrap();
-        assert_eq!(config.claude.path, "/custom/claude");
-        // args should be None since not specified (legacy field)
-        assert!(config.claude.args.is_none());
-        // logging should be defaults
-        assert_eq!(config.logging.level, "info");
-    }
-
-    #[test]
-    fn test_unknown_keys_ignored() {
-        let toml_str = r#"
-[claude]
-path = "/custom/claude"
-unknown_key = "should be ignored"
-
-[unknown_section]
-foo = "bar"
-"#;
-
-        let config: Config = toml::from_str(toml_str).unwrap();
-        assert_eq!(config.claude.path, "/custom/claude");
-    }
-
-    #[test]
-    fn test_legacy_claude_args_ignored() {
-        // Existing config files may have claude.args - ensure they still load
-        let toml_str = r#"
-[claude]
-path = "/custom/claude"
-args = "--output-format=stream-json --verbose"
-"#;
-
-        let config: Config = toml::from_str(toml_str).unwrap();
-        assert_eq!(config.claude.path, "/custom/claude");
-        // args is parsed but ignored (legacy field)
-        assert_eq!(
-            config.claude.args,
-            Some("--output-format=stream-json --verbose".to_string())
-        );
-    }
-
-    #[test]
-    fn test_iterations_default() {
-        let config = Config::default();
-        assert_eq!(config.behavior.iterations, -1); // Infinite by default
-    }
-
-    #[test]
-    fn test_iterations_explicit() {
-        let toml_str = r#"
-[behavior]
-iterations = 5
-"#;
-
-        let config: Config = toml::from_str(toml_str).unwrap();
-        assert_eq!(config.behavior.iterations, 5);
-    }
-
-    #[test]
-    fn test_keep_awake_default() {
-        let config = Config::default();
-        assert!(config.behavior.keep_awake); // Default: true
-    }
-
-    #[test]
-    fn test_keep_awake_explicit() {
-        let toml_str = r#"
-[behavior]
-keep_awake = false
-"#;
-
-        let config: Config = toml::from_str(toml_str).unwrap();
-        assert!(!config.behavior.keep_awake);
-    }
-
-    #[test]
-    fn test_partial_config_empty() {
-        let toml_str = "";
-        let partial: PartialConfig = toml::from_str(toml_str).unwrap();
-        assert!(partial.claude.path.is_none());
-        assert!(partial.logging.level.is_none());
-        assert!(partial.behavior.iterations.is_none());
-        assert!(partial.behavior.keep_awake.is_none());
-    }
-
-    #[test]
-    fn test_partial_config_some_fields() {
-        let toml_str = r#"
-[behavior]
-iterations = 3
-"#;
-
-        let partial: PartialConfig = toml::from_str(toml_str).unwrap();
-        assert!(partial.claude.path.is_none());
-        assert_eq!(partial.behavior.iterations, Some(3));
-        assert!(partial.behavior.keep_awake.is_none());
-    }
-
-    #[test]
-    fn test_partial_config_unknown_keys_ignored() {
-        let toml_str = r#"
-[unknown_section]
-foo = "bar"
-"#;
-
-        let _partial: PartialConfig = toml::from_str(toml_str).unwrap();
-    }
-
-    #[test]
-    fn test_partial_config_comment_only() {
-        let toml_str = "# Project-specific Ralph config — edit with config modal (c)\n";
-        let partial: PartialConfig = toml::from_str(toml_str).unwrap();
-        assert!(partial.claude.path.is_none());
-    }
-
-    #[test]
-    fn test_merge_config_no_overrides() {
-        let global = Config::default();
-        let partial = PartialConfig::default();
-        let merged = merge_config(&global, &partial);
-
-        assert_eq!(merged.claude.path, global.claude.path);
-        assert_eq!(merged.logging.level, global.logging.level);
-        assert_eq!(merged.behavior.iterations, global.behavior.iterations);
-        assert_eq!(merged.behavior.keep_awake, global.behavior.keep_awake);
-    }
-
-    #[test]
-    fn test_merge_config_all_overrides() {
-        let global = Config::default();
-        let partial = PartialConfig {
-            claude: PartialClaudeConfig {
-                path: Some("/custom/claude".to_string()),
-            },
-            logging: PartialLoggingConfig {
-                level: Some("debug".to_string()),
-            },
-            behavior: PartialBehaviorConfig {
-                iterations: Some(5),
-                keep_awake: Some(false),
-                bd_path: None,
-                heartbeat_interval: None,
-                stale_threshold: None,
-                workers: None,
-            },
-        };
-        let merged = merge_config(&global, &partial);
-
-        assert_eq!(merged.claude.path, "/custom/claude");
-        assert_eq!(merged.logging.level, "debug");
-        assert_eq!(merged.behavior.iterations, 5);
-        assert!(!merged.behavior.keep_awake);
-    }
-
-    #[test]
-    fn test_merge_config_partial_overrides() {
-        let global = Config::default();
-        let partial: PartialConfig = toml::from_str(
-            r#"
-[behavior]
-iterations = 3
-"#,
-        )
-        .unwrap();
-        let merged = merge_config(&global, &partial);
-
-        // Overridden fields
-        assert_eq!(merged.behavior.iterations, 3);
-
-        // Inherited fields
-        assert_eq!(merged.claude.path, global.claude.path);
-        assert_eq!(merged.logging.level, global.logging.level);
-        assert_eq!(merged.behavior.keep_awake, global.behavior.keep_awake);
-    }
-
-    #[test]
-    fn test_partial_config_serialize_empty() {
-        let partial = PartialConfig::default();
-        let toml_str = toml::to_string_pretty(&partial).unwrap();
-        // Empty partial config should serialize to empty string (all sections skipped)
-        assert_eq!(toml_str.trim(), "");
-    }
-
-    #[test]
-    fn test_partial_config_serialize_some_fields() {
-        let partial = PartialConfig {
-            behavior: PartialBehaviorConfig {
-                iterations: Some(3),
-                ..Default::default()
-            },
-            ..Default::default()
-        };
-        let toml_str = toml::to_string_pretty(&partial).unwrap();
-        // Should contain only the set fields
-        assert!(toml_str.contains("iterations = 3"));
-        // Should not contain unset fields
-        assert!(!toml_str.contains("claude"));
-        assert!(!toml_str.contains("keep_awake"));
-    }
-
-    #[test]
-    fn test_partial_config_serialize_roundtrip() {
-        let partial = PartialConfig {
-            claude: PartialClaudeConfig {
-                path: Some("/custom/claude".to_string()),
-            },
-            logging: PartialLoggingConfig { level: None },
-            behavior: PartialBehaviorConfig {
-                iterations: Some(5),
-                keep_awake: None,
-                bd_path: None,
-                heartbeat_interval: None,
-                stale_threshold: None,
-                workers: None,
-            },
-        };
-        let toml_str = toml::to_string_pretty(&partial).unwrap();
-        let deserialized: PartialConfig = toml::from_str(&toml_str).unwrap();
-
-        assert_eq!(deserialized.claude.path, Some("/custom/claude".to_string()));
-        assert!(deserialized.logging.level.is_none());
-        assert_eq!(deserialized.behavior.iterations, Some(5));
-        assert!(deserialized.behavior.keep_awake.is_none());
-    }
-
     #[test]
     fn test_project_key_from_path() {
         let key = project_key_from_path(std::path::Path::new("/Users/me/code/foo"));
@@ -707,7 +430,7 @@ iterations = 3
     #[test]
     fn test_compute_project_config_path_structure() {
         // Verify the returned path has the expected structure
-        if let Some(path) = compute_project_config_path() {
+        if let Some(path) = compute_project_config_path(&startup_dir()) {
             let path_str = path.to_string_lossy();
             assert!(path_str.contains("projects"));
             assert!(path_str.ends_with("config.toml"));
@@ -716,74 +439,75 @@ iterations = 3
     }
 
     #[test]
-    fn workers_default_is_one() {
-        let config = BehaviorConfig::default();
-        assert_eq!(config.workers, 1);
-    }
-
-    #[test]
-    fn workers_parsed_from_toml() {
-        let toml_str = r#"
-[behavior]
-workers = 4
-"#;
-        let config: Config = toml::from_str(toml_str).unwrap();
-        assert_eq!(config.behavior.workers, 4);
+    fn config_is_loaded_for_the_given_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let loaded = load_config_for(dir.path());
+        assert_eq!(loaded.project_dir, dir.path());
+        // Each project has its own config file
+        assert_ne!(
+            compute_project_config_path(dir.path()),
+            compute_project_config_path(&dir.path().join("other"))
+        );
+        assert_eq!(compute_project_config_path(Path::new("relative")), None);
     }
 
     #[test]
-    fn workers_explicit_values() {
-        for n in [1, 2, 4, 8] {
-            let toml_str = format!("[behavior]\nworkers = {}", n);
-            let config: Config = toml::from_str(&toml_str).unwrap();
-            assert_eq!(config.behavior.workers, n);
-        }
-    }
+    fn extends_chain_merges_base_first() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("profiles")).unwrap();
+        fs::write(
+            dir.path().join("profiles/strict.toml"),
+            "[behavior]\nworkers = 3\niterations = 5\n[logging]\nlevel = \"warn\"\n",
+        )
+        .unwrap();
+        let project = dir.path().join("config.toml");
+        fs::write(
+            &project,
+            "extends = \"profiles/strict.toml\"\n[behavior]\niterations = 2\n",
+        )
+        .unwrap();
 
-    #[test]
-    fn workers_zero_clamped_to_one_on_merge() {
-        let base = Config::default();
-        let partial = PartialConfig {
-            behavior: PartialBehaviorConfig {
-                workers: Some(0),
-                ..Default::default()
-            },
-            ..Default::default()
-        };
-        let merged = merge_config(&base, &partial);
-        assert_eq!(merged.behavior.workers, 1);
+        let layers = load_config_layers(&project).unwrap();
+        assert_eq!(layers.len(), 2);
+        let config = merge_layers(Config::default(), &layers);
+        assert_eq!(config.behavior.workers, 3);
+        assert_eq!(config.behavior.iterations, 2);
+        assert_eq!(config.logging.level, "warn");
     }
 
     #[test]
-    fn workers_missing_defaults_to_one() {
-        let toml_str = r#"
-[behavior]
-iterations = -1
-"#;
-        let config: Config = toml::from_str(toml_str).unwrap();
-        assert_eq!(config.behavior.workers, 1);
+    fn extends_reports_cycles_and_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.toml");
+        fs::write(&a, "extends = \"b.toml\"\n").unwrap();
+        fs::write(dir.path().join("b.toml"), "extends = \"a.toml\"\n").unwrap();
+        let err = load_config_layers(&a).unwrap_err();
+        assert!(matches!(err, ConfigError::ExtendsCycle { .. }));
+        assert!(err.to_string().contains("cycle"));
+
+        fs::write(&a, "extends = \"missing.toml\"\n").unwrap();
+        let err = load_config_layers(&a).unwrap_err();
+        assert!(matches!(
+            &err,
+            ConfigError::Extended { source, .. }
+                if matches!(**source, ConfigError::Io { op: FileOp::Read, .. })
+        ));
+        assert!(err.to_string().contains("missing.toml"));
     }
 
     #[test]
-    fn workers_merge_project_overrides_global() {
-        let global = Config::default();
-        let partial = PartialConfig {
-            behavior: PartialBehaviorConfig {
-                workers: Some(3),
-                ..Default::default()
-            },
-            ..Default::default()
-        };
-        let merged = merge_config(&global, &partial);
-        assert_eq!(merged.behavior.workers, 3);
-    }
+    fn extends_chain_lists_bases_including_missing_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("config.toml");
+        let base = dir.path().join("base.toml");
+        fs::write(&project, "extends = \"base.toml\"\n").unwrap();
+        fs::write(&base, "extends = \"missing.toml\"\n").unwrap();
+        assert_eq!(
+            extends_chain(&project),
+            vec![base.clone(), dir.path().join("missing.toml")]
+        );
 
-    #[test]
-    fn workers_merge_project_none_inherits_global() {
-        let mut global = Config::default();
-        global.behavior.workers = 2;
-        let partial = PartialConfig::default();
-        let merged = merge_config(&global, &partial);
-        assert_eq!(merged.behavior.workers, 2);
+        fs::write(&base, "extends = \"config.toml\"\n").unwrap();
+        assert_eq!(extends_chain(&project), vec![base]);
     }
 }
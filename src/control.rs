@@ -0,0 +1,341 @@
+//! Claude CLI control protocol over stream-json stdin (`control.enabled`).
+//!
+//! With `--input-format=stream-json` ralph owns Claude's stdin: it sends the
+//! prompt as a user message, answers `control_request` permission prompts
+//! (`--permission-prompt-tool=stdio`), and sends an `interrupt` request on
+//! stop. Claude keeps reading stdin after its result, so the channel is
+//! closed once the iteration's result event arrives to let the process exit.
+//!
+//! Without the control protocol, a stop sends SIGINT to the run's process
+//! group instead (see [`own_process_group`]).
+//!
+//! Process groups are Unix-only. On Windows a kill reaches Claude alone, and
+//! the tools it started keep running; there is no Job Object support.
+
+use std::io::{self, Write};
+use std::process::{Child, ChildStdin, Command};
+
+use serde_json::{Value, json};
+use tracing::{debug, warn};
+
+/// Extra Claude CLI arguments when the control protocol is enabled.
+pub const CONTROL_ARGS: &str = "--input-format=stream-json --permission-prompt-tool=stdio";
+
+/// How ralph answers a permission prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PermissionDecision {
+    Allow,
+    Deny(String),
+}
+
+/// Approve `tool_name` if it (or `"*"`) is listed in `control.auto_approve`.
+pub fn decide(tool_name: &str, auto_approve: &[String]) -> PermissionDecision {
+    if auto_approve.iter().any(|t| t == "*" || t == tool_name) {
+        PermissionDecision::Allow
+    } else {
+        PermissionDecision::Deny(format!(
+            "{tool_name} is not allowed; add it to control.auto_approve or allow it in Claude settings"
+        ))
+    }
+}
+
+/// The prompt as a stream-json user message.
+fn user_message(prompt: &str) -> Value {
+    json!({
+        "type": "user",
+        "message": { "role": "user", "content": prompt },
+    })
+}
+
+/// Answer to a `can_use_tool` request. Approvals echo the original input.
+fn permission_response(request_id: &str, decision: &PermissionDecision, input: &Value) -> Value {
+    let response = match decision {
+        PermissionDecision::Allow => json!({ "behavior": "allow", "updatedInput": input }),
+        PermissionDecision::Deny(message) => json!({ "behavior": "deny", "message": message }),
+    };
+    json!({
+        "type": "control_response",
+        "response": { "subtype": "success", "request_id": request_id, "response": response },
+    })
+}
+
+/// Reply to a request subtype ralph doesn't handle, so Claude doesn't wait on it.
+fn unsupported_response(request_id: &str) -> Value {
+    json!({
+        "type": "control_response",
+        "response": {
+            "subtype": "error",
+            "request_id": request_id,
+            "error": "unsupported by ralph",
+        },
+    })
+}
+
+fn interrupt_request(request_id: &str) -> Value {
+    json!({
+        "type": "control_request",
+        "request_id": request_id,
+        "request": { "subtype": "interrupt" },
+    })
+}
+
+/// Take `child`'s stdin and send it `prompt`. Logs and returns `None` if that
+/// fails; Claude then sees EOF and exits without doing any work.
+pub fn open(child: &mut Child, prompt: &str) -> Option<ControlChannel> {
+    let Some(stdin) = child.stdin.take() else {
+        warn!("control_stdin_missing");
+        return None;
+    };
+    let mut channel = ControlChannel::new(stdin);
+    match channel.send_prompt(prompt) {
+        Ok(()) => Some(channel),
+        Err(e) => {
+            warn!(error = %e, "control_prompt_send_failed");
+            None
+        }
+    }
+}
+
+/// Spawn `cmd` in its own process group so [`interrupt_process`] and
+/// [`kill_process`] reach every process Claude starts, not just Claude itself.
+pub fn own_process_group(cmd: &mut Command) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    #[cfg(not(unix))]
+    let _ = cmd;
+}
+
+/// Send SIGINT to `child`'s process group. Returns false where unsupported
+/// or if the signal couldn't be delivered.
+pub fn interrupt_process(child: &Child) -> bool {
+    #[cfg(unix)]
+    {
+        signal_process_group(child, libc::SIGINT)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = child;
+        false
+    }
+}
+
+/// Kill `child`'s whole process group (SIGKILL), so tool subprocesses don't
+/// outlive it, then reap `child`. Processes that moved to a group of their
+/// own escape. On Windows, or if the signal can't be sent, only `child` is
+/// killed and its subprocesses are left running.
+pub fn kill_process(child: &mut Child) {
+    #[cfg(unix)]
+    let killed = signal_process_group(child, libc::SIGKILL);
+    #[cfg(not(unix))]
+    let killed = false;
+    if !killed {
+        let _ = child.kill();
+    }
+    let _ = child.wait();
+}
+
+/// Freeze `child`'s process group (SIGSTOP). Returns false where unsupported
+/// or if the signal couldn't be delivered.
+pub fn pause_process(child: &Child) -> bool {
+    #[cfg(unix)]
+    {
+        signal_process_group(child, libc::SIGSTOP)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = child;
+        false
+    }
+}
+
+/// Let a paused process group carry on (SIGCONT).
+pub fn resume_process(child: &Child) -> bool {
+    #[cfg(unix)]
+    {
+        signal_process_group(child, libc::SIGCONT)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = child;
+        false
+    }
+}
+
+#[cfg(unix)]
+fn signal_process_group(child: &Child, signal: libc::c_int) -> bool {
+    let Ok(pgid) = libc::pid_t::try_from(child.id()) else {
+        return false;
+    };
+    // SAFETY: kill(2) has no memory-safety preconditions.
+    unsafe { libc::kill(-pgid, signal) == 0 }
+}
+
+/// Writer for one Claude process's stdin.
+#[derive(Debug)]
+pub struct ControlChannel {
+    stdin: ChildStdin,
+    sent_requests: u32,
+}
+
+impl ControlChannel {
+    pub fn new(stdin: ChildStdin) -> Self {
+        Self {
+            stdin,
+            sent_requests: 0,
+        }
+    }
+
+    fn send(&mut self, message: &Value) -> io::Result<()> {
+        debug!(%message, "control_send");
+        writeln!(self.stdin, "{message}")?;
+        self.stdin.flush()
+    }
+
+    /// Send the iteration's prompt.
+    pub fn send_prompt(&mut self, prompt: &str) -> io::Result<()> {
+        self.send(&user_message(prompt))
+    }
+
+    /// Answer a `can_use_tool` request.
+    pub fn answer_permission(
+        &mut self,
+        request_id: &str,
+        decision: &PermissionDecision,
+        input: &Value,
+    ) {
+        if let Err(e) = self.send(&permission_response(request_id, decision, input)) {
+            warn!(request_id, error = %e, "control_permission_reply_failed");
+        }
+    }
+
+    /// Decline a request subtype ralph doesn't handle.
+    pub fn reject_unsupported(&mut self, request_id: &str) {
+        if let Err(e) = self.send(&unsupported_response(request_id)) {
+            warn!(request_id, error = %e, "control_reject_failed");
+        }
+    }
+
+    /// Ask Claude to stop the current turn. Returns the request id.
+    pub fn interrupt(&mut self) -> io::Result<String> {
+        self.sent_requests += 1;
+        let request_id = format!("ralph-{}", self.sent_requests);
+        self.send(&interrupt_request(&request_id))?;
+        Ok(request_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ralph_core::events::{ClaudeEvent, ControlRequest};
+
+    /// Whether `pid` is gone or only a zombie waiting to be reaped.
+    #[cfg(target_os = "linux")]
+    fn is_dead(pid: &str) -> bool {
+        std::fs::read_to_string(format!("/proc/{pid}/stat")).map_or(true, |stat| {
+            stat.rsplit(')')
+                .next()
+                .is_some_and(|s| s.trim_start().starts_with('Z'))
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn kill_process_takes_the_whole_group() {
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg("sleep 30 & echo $!; wait")
+            .stdout(Stdio::piped());
+        own_process_group(&mut cmd);
+        let mut child = cmd.spawn().unwrap();
+        let mut grandchild = String::new();
+        BufReader::new(child.stdout.take().unwrap())
+            .read_line(&mut grandchild)
+            .unwrap();
+        let grandchild = grandchild.trim().to_string();
+        assert!(!is_dead(&grandchild));
+
+        kill_process(&mut child);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while !is_dead(&grandchild) && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        assert!(is_dead(&grandchild));
+    }
+
+    #[test]
+    fn parses_can_use_tool_request() {
+        let line = r#"{"type":"control_request","request_id":"req-1","request":{"subtype":"can_use_tool","tool_name":"Bash","input":{"command":"ls"}}}"#;
+        let Ok(ClaudeEvent::ControlRequest(event)) = serde_json::from_str(line) else {
+            panic!("expected control_request");
+        };
+        assert_eq!(event.request_id, "req-1");
+        let ControlRequest::CanUseTool { tool_name, input } = event.request else {
+            panic!("expected can_use_tool");
+        };
+        assert_eq!(tool_name, "Bash");
+        assert_eq!(input["command"], "ls");
+    }
+
+    #[test]
+    fn unknown_request_subtypes_still_parse() {
+        let line = r#"{"type":"control_request","request_id":"req-2","request":{"subtype":"hook_callback","callback_id":"x"}}"#;
+        let Ok(ClaudeEvent::ControlRequest(event)) = serde_json::from_str(line) else {
+            panic!("expected control_request");
+        };
+        assert!(matches!(event.request, ControlRequest::Unsupported));
+    }
+
+    #[test]
+    fn parses_control_response() {
+        let line = r#"{"type":"control_response","response":{"subtype":"error","request_id":"ralph-1","error":"no active turn"}}"#;
+        let Ok(ClaudeEvent::ControlResponse(event)) = serde_json::from_str(line) else {
+            panic!("expected control_response");
+        };
+        assert_eq!(event.response.request_id, "ralph-1");
+        assert_eq!(event.response.error.as_deref(), Some("no active turn"));
+    }
+
+    #[test]
+    fn auto_approve_matches_tool_names_or_wildcard() {
+        let list = vec!["Edit".to_string()];
+        assert_eq!(decide("Edit", &list), PermissionDecision::Allow);
+        assert!(matches!(decide("Bash", &list), PermissionDecision::Deny(_)));
+        assert_eq!(
+            decide("Bash", &["*".to_string()]),
+            PermissionDecision::Allow
+        );
+        assert!(matches!(decide("Bash", &[]), PermissionDecision::Deny(_)));
+    }
+
+    #[test]
+    fn permission_responses_echo_request_id_and_input() {
+        let input = json!({"command": "ls"});
+        let allow = permission_response("req-1", &PermissionDecision::Allow, &input);
+        assert_eq!(allow["type"], "control_response");
+        assert_eq!(allow["response"]["request_id"], "req-1");
+        assert_eq!(allow["response"]["response"]["behavior"], "allow");
+        assert_eq!(allow["response"]["response"]["updatedInput"], input);
+
+        let deny = permission_response("req-2", &PermissionDecision::Deny("no".into()), &input);
+        assert_eq!(deny["response"]["response"]["behavior"], "deny");
+        assert_eq!(deny["response"]["response"]["message"], "no");
+    }
+
+    #[test]
+    fn prompt_and_interrupt_messages() {
+        let msg = user_message("do the thing");
+        assert_eq!(msg["type"], "user");
+        assert_eq!(msg["message"]["content"], "do the thing");
+
+        let req = interrupt_request("ralph-1");
+        assert_eq!(req["type"], "control_request");
+        assert_eq!(req["request"]["subtype"], "interrupt");
+    }
+}
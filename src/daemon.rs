@@ -0,0 +1,302 @@
+//! `ralph daemon`: the loop as a long-lived background process with no TUI.
+//!
+//! `ralph daemon` starts `ralph daemon --foreground` in its own process group
+//! with its output in `daemon.log` and its pid in `daemon.pid`, both next to
+//! the project config. The daemon starts a run straight away (uncommitted
+//! changes are warned about, never prompted for) and exits when the run ends.
+//! It is followed like any other session: `ralph attach` for the output,
+//! `ralph status` for the state. `ralph daemon --stop` asks it to stop the way
+//! `S` does in the TUI.
+
+use std::fs::OpenOptions;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use tracing::{info, warn};
+
+use crate::app::App;
+use crate::config::{self, compute_project_config_path};
+use crate::event_loop::tick;
+use crate::logging;
+use crate::session_lock;
+use crate::shutdown_summary::{self, ExitReason, ShutdownSummary};
+use crate::startup::{init_app, shutdown_app};
+
+const PID_FILE_NAME: &str = "daemon.pid";
+const LOG_FILE_NAME: &str = "daemon.log";
+/// How long `ralph daemon` waits for the daemon to take the session lock.
+const START_TIMEOUT: Duration = Duration::from_secs(10);
+/// Pause between ticks; matches the TUI's poll while a run is active.
+const TICK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// File named `name` next to the project config.
+fn project_file(name: &str) -> Result<PathBuf> {
+    compute_project_config_path(&config::startup_dir())
+        .map(|p| p.with_file_name(name))
+        .context("Failed to determine the project config directory")
+}
+
+/// Run the daemon subcommand.
+pub fn run(stop: bool, foreground: bool, tags: Vec<String>) -> Result<()> {
+    if stop {
+        request_stop()
+    } else if foreground {
+        run_foreground(tags)
+    } else {
+        spawn_background(&tags)
+    }
+}
+
+/// The running daemon's pid, if there is one. A `daemon.pid` left behind by
+/// a daemon that died only counts if that pid still holds the session lock,
+/// so a recycled pid is never signalled.
+fn running_pid() -> Result<Option<u32>> {
+    let path = project_file(PID_FILE_NAME)?;
+    let lock = session_lock::lock_path(&config::startup_dir()).and_then(|p| session_lock::read(&p));
+    let pid = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|s| s.trim().parse::<u32>().ok())
+        .filter(|pid| {
+            lock.as_ref()
+                .is_some_and(|lock| lock.pid == *pid && lock.on_this_host())
+        })
+        .filter(|pid| session_lock::is_pid_alive(*pid));
+    Ok(pid)
+}
+
+/// Start `ralph daemon --foreground` in the background and report its session.
+fn spawn_background(tags: &[String]) -> Result<()> {
+    if let Some(pid) = running_pid()? {
+        bail!("A daemon is already running for this project (pid {pid})");
+    }
+    let log_path = project_file(LOG_FILE_NAME)?;
+    if let Some(dir) = log_path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .with_context(|| format!("Failed to open {}", log_path.display()))?;
+
+    let exe = std::env::current_exe().context("Failed to find the ralph executable")?;
+    let mut cmd = crate::bd_lock::non_bd_command(exe);
+    cmd.args(tags.iter().flat_map(|t| ["--tag", t.as_str()]));
+    if let Some(sandbox) = crate::sandbox::get() {
+        cmd.arg("--sandbox").arg(sandbox.root());
+    }
+    cmd.args(["daemon", "--foreground"])
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+    crate::control::own_process_group(&mut cmd);
+    let mut child = cmd.spawn().context("Failed to start daemon")?;
+
+    // Wait for the lock so the session id can be printed
+    let lock_path = session_lock::lock_path(&config::startup_dir())
+        .context("Failed to find the session lock")?;
+    let started = Instant::now();
+    while started.elapsed() < START_TIMEOUT {
+        if let Some(status) = child.try_wait()? {
+            bail!(
+                "Daemon exited with {status} before starting; see {}",
+                log_path.display()
+            );
+        }
+        if let Some(lock) = session_lock::read(&lock_path)
+            && lock.pid == child.id()
+        {
+            println!("Daemon started (pid {}).", child.id());
+            println!("  Watch it:  ralph attach {}", lock.session_id);
+            println!("  Check it:  ralph status");
+            println!("  Stop it:   ralph daemon --stop");
+            println!("  Log:       {}", log_path.display());
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    println!(
+        "Daemon started (pid {}) but hasn't begun a run yet; see {}",
+        child.id(),
+        log_path.display()
+    );
+    Ok(())
+}
+
+/// Ask the running daemon to stop its run.
+fn request_stop() -> Result<()> {
+    let Some(pid) = running_pid()? else {
+        bail!("No daemon is running for this project");
+    };
+    send_stop(pid)?;
+    println!("Asked the daemon (pid {pid}) to stop; it exits once the run has stopped");
+    Ok(())
+}
+
+#[cfg(unix)]
+fn send_stop(pid: u32) -> Result<()> {
+    // SAFETY: kill(2) has no memory-safety preconditions.
+    if unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) } != 0 {
+        bail!(
+            "Failed to signal pid {pid}: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn send_stop(_pid: u32) -> Result<()> {
+    bail!("ralph daemon --stop is only supported on Unix")
+}
+
+#[cfg(unix)]
+fn handle_signals() {
+    // SAFETY: ignoring a signal has no memory-safety preconditions.
+    unsafe {
+        libc::signal(libc::SIGHUP, libc::SIG_IGN);
+    }
+    shutdown_summary::catch_signals(&[libc::SIGTERM, libc::SIGINT]);
+}
+
+#[cfg(not(unix))]
+fn handle_signals() {}
+
+/// Run the loop in this process until it ends.
+fn run_foreground(tags: Vec<String>) -> Result<()> {
+    let session_id = logging::new_session_id();
+    let loaded_config = config::load_config();
+    let (log_directory, _guard, reload_handle) =
+        match logging::init(session_id.clone(), &loaded_config.config.logging.level) {
+            Ok(ctx) => (
+                Some(ctx.log_directory),
+                Some(ctx._guard),
+                Some(ctx.reload_handle),
+            ),
+            Err(e) => {
+                eprintln!("Warning: Failed to initialize logging: {e}");
+                (None, None, None)
+            }
+        };
+    handle_signals();
+    shutdown_summary::install_panic_hook();
+
+    let pid_path = project_file(PID_FILE_NAME)?;
+    std::fs::write(&pid_path, std::process::id().to_string())
+        .with_context(|| format!("Failed to write {}", pid_path.display()))?;
+    info!(session_id = %session_id, "daemon_start");
+
+    let mut app = init_app(
+        session_id.clone(),
+        log_directory,
+        loaded_config,
+        reload_handle,
+        tags,
+        false,
+    );
+    app.begin_starting_workers();
+    if let Some(changes) = app.dirty_start_prompt.take() {
+        // Nobody is there to answer the prompt
+        app.warn_dirty_tree(changes.len());
+        app.start_workers();
+    }
+    let result = if let Some(holder) = app.lock_conflict.take() {
+        Err(anyhow::anyhow!(
+            "Another session holds this project: {}",
+            holder.describe()
+        ))
+    } else {
+        eprintln!("Session {session_id} started");
+        supervise(&mut app)
+    };
+
+    let reason = match (&result, shutdown_summary::caught_signal()) {
+        (Err(e), _) => ExitReason::Error(format!("{e:#}")),
+        (Ok(()), Some(signal)) => ExitReason::Signal(signal),
+        (Ok(()), None) => ExitReason::RunEnded,
+    };
+    let summaries = ShutdownSummary::capture_by_project(std::slice::from_ref(&app), &reason);
+    crate::project_summary::save_last_session(&app);
+    shutdown_app(&mut app);
+    for (project_dir, summary) in &summaries {
+        shutdown_summary::write(project_dir, summary);
+    }
+    let _ = std::fs::remove_file(&pid_path);
+    match &result {
+        Ok(()) => info!("daemon_end"),
+        Err(e) => warn!(error = %e, "daemon_failed"),
+    }
+    result
+}
+
+/// Tick until the run ends, stopping it when asked.
+fn supervise(app: &mut App) -> Result<()> {
+    let mut stopping = false;
+    loop {
+        tick(app)?;
+        shutdown_summary::record(std::slice::from_ref(app));
+        let stop_requested = shutdown_summary::caught_signal().is_some();
+        if stop_requested && !stopping {
+            info!("daemon_stop_requested");
+            app.stop_run();
+            stopping = true;
+        }
+        if run_ended(app) {
+            eprintln!("Run ended ({})", app.status.tab_label());
+            return Ok(());
+        }
+        std::thread::sleep(TICK_INTERVAL);
+    }
+}
+
+/// True once nothing is left of the run: no process or hook, no pending
+/// check for more work, no queued iteration and no hold. The status alone
+/// reads Stopped between iterations.
+fn run_ended(app: &App) -> bool {
+    !app.run_in_progress()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppStatus;
+    use crate::config::LoadedConfig;
+
+    #[test]
+    fn run_continues_across_an_iteration_boundary() {
+        let mut app = App::new("test".into(), None, LoadedConfig::default_for_test(), None);
+        app.status = AppStatus::Running;
+        app.workers[0].total_iterations = 3;
+        app.workers[0].current_iteration = 1;
+
+        // The first iteration exits cleanly with iterations left
+        app.handle_channel_disconnected(0, Some(0));
+        assert_eq!(app.status, AppStatus::Stopped);
+        assert!(app.workers[0].pending_work_check.is_some());
+        assert!(!run_ended(&app));
+
+        // Work remains: the next iteration is queued, then held
+        app.workers[0].pending_work_check = None;
+        app.workers[0].auto_continue_pending = true;
+        assert!(!run_ended(&app));
+        app.workers[0].auto_continue_pending = false;
+        app.quiet_hours_paused = true;
+        assert!(!run_ended(&app));
+
+        app.quiet_hours_paused = false;
+        assert!(run_ended(&app));
+    }
+
+    #[test]
+    fn stop_run_drops_a_pending_iteration() {
+        let mut app = App::new("test".into(), None, LoadedConfig::default_for_test(), None);
+        app.workers[0].total_iterations = 3;
+        app.workers[0].auto_continue_pending = true;
+        app.budget_throttled_until = Some(Instant::now() + Duration::from_secs(60));
+        assert!(!run_ended(&app));
+        app.stop_run();
+        assert!(run_ended(&app));
+    }
+}
@@ -1,19 +1,22 @@
-//! SQLite database foundation for tool history and future persistent storage.
+//! SQLite database foundation for tool history and run statistics.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use rusqlite::Connection;
 use tracing::warn;
 
 #[cfg(test)]
-const CURRENT_SCHEMA_VERSION: i32 = 3;
+const CURRENT_SCHEMA_VERSION: i32 = 5;
 
 /// Returns the platform-appropriate database directory.
 ///
 /// - macOS: ~/Library/Application Support/ralph/
 /// - Linux: $XDG_DATA_HOME/ralph/ (defaults to ~/.local/share/ralph/)
 fn db_dir() -> Result<PathBuf> {
+    if let Some(sandbox) = crate::sandbox::get() {
+        return Ok(sandbox.data_dir());
+    }
     let base = dirs::data_dir().context("Failed to determine data directory")?;
     Ok(base.join("ralph"))
 }
@@ -78,6 +81,12 @@ fn migrate(conn: &Connection) -> Result<()> {
     if current < 3 {
         migrate_v3(conn)?;
     }
+    if current < 4 {
+        migrate_v4(conn)?;
+    }
+    if current < 5 {
+        migrate_v5(conn)?;
+    }
 
     Ok(())
 }
@@ -131,17 +140,89 @@ fn migrate_v3(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
-/// Detects the git repository root, falling back to the current working directory.
-pub fn detect_repo_path() -> String {
+fn migrate_v4(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS session_tags (
+            session_id TEXT NOT NULL,
+            tag TEXT NOT NULL,
+            PRIMARY KEY (session_id, tag)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_session_tags_tag
+            ON session_tags(tag);
+
+        INSERT INTO schema_version (version) VALUES (4);",
+    )?;
+    Ok(())
+}
+
+/// Run statistics (see `stats`): sessions, per-iteration usage, and the specs
+/// iterations are attributed to. Times are Unix seconds.
+fn migrate_v5(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS sessions (
+            session_id TEXT PRIMARY KEY,
+            repo_path TEXT NOT NULL,
+            started_at INTEGER NOT NULL,
+            ended_at INTEGER,
+            iterations INTEGER NOT NULL DEFAULT 0,
+            cost_usd REAL NOT NULL DEFAULT 0
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_sessions_repo_path
+            ON sessions(repo_path, ended_at);
+
+        CREATE TABLE IF NOT EXISTS iterations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id TEXT NOT NULL,
+            repo_path TEXT NOT NULL,
+            worker INTEGER NOT NULL,
+            iteration INTEGER NOT NULL,
+            bead_id TEXT,
+            tokens INTEGER NOT NULL,
+            cost_usd REAL NOT NULL,
+            duration_ms INTEGER NOT NULL,
+            failed INTEGER NOT NULL DEFAULT 0,
+            tool_results INTEGER NOT NULL DEFAULT 0,
+            tool_errors INTEGER NOT NULL DEFAULT 0,
+            ended_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_iterations_repo_ended
+            ON iterations(repo_path, ended_at);
+        CREATE INDEX IF NOT EXISTS idx_iterations_bead
+            ON iterations(repo_path, bead_id);
+
+        CREATE TABLE IF NOT EXISTS specs (
+            repo_path TEXT NOT NULL,
+            bead_id TEXT NOT NULL,
+            title TEXT,
+            completed_at INTEGER,
+            imported_iterations INTEGER NOT NULL DEFAULT 0,
+            imported_tokens INTEGER NOT NULL DEFAULT 0,
+            imported_cost_usd REAL NOT NULL DEFAULT 0,
+            imported_duration_ms INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (repo_path, bead_id)
+        );
+
+        INSERT INTO schema_version (version) VALUES (5);",
+    )?;
+    Ok(())
+}
+
+/// Detects the git repository root of `project_dir`, falling back to the
+/// directory itself.
+pub fn detect_repo_path(project_dir: &Path) -> String {
     std::process::Command::new("git")
         .args(["rev-parse", "--show-toplevel"])
+        .current_dir(project_dir)
         .output()
         .ok()
         .filter(|o| o.status.success())
         .and_then(|o| String::from_utf8(o.stdout).ok())
         .map(|s| s.trim().to_string())
         .or_else(|| {
-            std::env::current_dir()
+            std::path::absolute(project_dir)
                 .ok()
                 .map(|p| p.to_string_lossy().to_string())
         })
@@ -173,6 +254,21 @@ pub fn insert_tool_call(
     }
 }
 
+/// Records the `--tag` labels a session was started with.
+/// Returns true on success, or logs a warning and returns false on failure.
+pub fn insert_session_tags(conn: &Connection, session_id: &str, tags: &[String]) -> bool {
+    for tag in tags {
+        if let Err(e) = conn.execute(
+            "INSERT OR IGNORE INTO session_tags (session_id, tag) VALUES (?1, ?2)",
+            rusqlite::params![session_id, tag],
+        ) {
+            warn!(error = %e, tag, "Failed to record session tag");
+            return false;
+        }
+    }
+    true
+}
+
 /// Updates a tool call record with its result at User event time.
 /// Returns true on success, or logs a warning and returns false on failure.
 pub fn update_tool_result(
@@ -273,6 +369,19 @@ mod tests {
         assert_eq!(count, 1);
     }
 
+    #[test]
+    fn session_tags_are_deduplicated() {
+        let conn = open_memory().unwrap();
+        let tags = vec!["prompt-v3".to_string(), "prompt-v3".to_string()];
+        assert!(insert_session_tags(&conn, "sess-1", &tags));
+        assert!(insert_session_tags(&conn, "sess-1", &tags));
+
+        let count: i32 = conn
+            .query_row("SELECT COUNT(*) FROM session_tags", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
     #[test]
     fn migrate_is_idempotent() {
         let conn = open_memory().unwrap();
@@ -493,7 +602,7 @@ mod tests {
 
     #[test]
     fn detect_repo_path_returns_something() {
-        let path = detect_repo_path();
+        let path = detect_repo_path(&crate::config::startup_dir());
         assert!(!path.is_empty());
     }
 
@@ -0,0 +1,224 @@
+//! Developer overlay (`F12`) showing live internals: frame rate, draw and poll
+//! timings, how many output messages each worker's channel had queued, the
+//! cost of the last visual line count, and memory held by output lines. For
+//! performance work and for diagnosing reported lag.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::App;
+
+/// Slowest polls listed in the overlay.
+const MAX_POLLS_LISTED: usize = 5;
+
+/// Timings and counters collected while the app runs.
+#[derive(Debug, Default)]
+pub struct DebugStats {
+    /// When each frame in the last second was drawn.
+    frames: VecDeque<Instant>,
+    pub last_draw: Duration,
+    pub last_tick: Duration,
+    /// Time per poll in the last tick, in call order.
+    polls: Vec<(&'static str, Duration)>,
+    /// Messages drained from each worker's output channel on its last poll.
+    pub queue_depth: Vec<usize>,
+    /// How long the last uncached visual line count took.
+    pub visual_line_count: Option<Duration>,
+}
+
+impl DebugStats {
+    /// Note a frame drawn at `now` that took `took`.
+    pub fn record_frame(&mut self, now: Instant, took: Duration) {
+        self.last_draw = took;
+        self.frames.push_back(now);
+        while self
+            .frames
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > Duration::from_secs(1))
+        {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Frames drawn in the second before `now`.
+    pub fn fps(&self, now: Instant) -> usize {
+        self.frames
+            .iter()
+            .filter(|t| now.duration_since(**t) <= Duration::from_secs(1))
+            .count()
+    }
+
+    /// Start timing a new tick.
+    pub fn begin_tick(&mut self) {
+        self.polls.clear();
+    }
+
+    pub fn record_poll(&mut self, name: &'static str, took: Duration) {
+        self.polls.push((name, took));
+    }
+
+    /// Finish the tick, which took `took` in total.
+    pub fn end_tick(&mut self, took: Duration) {
+        self.last_tick = took;
+    }
+
+    /// Polls of the last tick, slowest first.
+    pub fn slowest_polls(&self) -> Vec<(&'static str, Duration)> {
+        let mut polls = self.polls.clone();
+        polls.sort_by_key(|p| std::cmp::Reverse(p.1));
+        polls.truncate(MAX_POLLS_LISTED);
+        polls
+    }
+
+    pub fn set_queue_depth(&mut self, worker: usize, depth: usize) {
+        if self.queue_depth.len() <= worker {
+            self.queue_depth.resize(worker + 1, 0);
+        }
+        self.queue_depth[worker] = depth;
+    }
+}
+
+/// Approximate heap and inline bytes held by `lines`.
+pub fn output_bytes(lines: &[Line]) -> usize {
+    lines
+        .iter()
+        .map(|line| {
+            std::mem::size_of::<Line>()
+                + line
+                    .spans
+                    .iter()
+                    .map(|span| std::mem::size_of::<Span>() + span.content.len())
+                    .sum::<usize>()
+        })
+        .sum()
+}
+
+/// `812 B`, `4.2 KB` or `3.1 MB`.
+fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        0..1024 => format!("{bytes} B"),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Draw the overlay in the top-right corner.
+pub fn draw_debug_overlay(f: &mut Frame, app: &App) {
+    let stats = &app.debug_stats;
+    let label = Style::default().fg(Color::DarkGray);
+    let row = |name: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!(" {name:<14}"), label),
+            Span::raw(value),
+        ])
+    };
+
+    let lines: usize = app.workers.iter().map(|w| w.output_lines.len()).sum();
+    let bytes: usize = app
+        .workers
+        .iter()
+        .map(|w| output_bytes(&w.output_lines))
+        .sum();
+    let queues = stats
+        .queue_depth
+        .iter()
+        .enumerate()
+        .map(|(w, depth)| format!("w{w}:{depth}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut content = vec![
+        row("fps", stats.fps(Instant::now()).to_string()),
+        row("draw", millis(stats.last_draw)),
+        row("tick", millis(stats.last_tick)),
+        row(
+            "queue depth",
+            if queues.is_empty() {
+                "-".into()
+            } else {
+                queues
+            },
+        ),
+        row(
+            "line count",
+            stats.visual_line_count.map_or("-".into(), millis),
+        ),
+        row(
+            "output",
+            format!("{} in {lines} lines", format_bytes(bytes)),
+        ),
+        Line::styled(" slowest polls", label),
+    ];
+    for (name, took) in stats.slowest_polls() {
+        content.push(row(&format!("  {name}"), millis(took)));
+    }
+
+    let width: u16 = 40;
+    let height = content.len() as u16 + 2;
+    let screen = f.area();
+    let area = Rect {
+        x: screen.x + screen.width.saturating_sub(width),
+        y: screen.y,
+        width: width.min(screen.width),
+        height: height.min(screen.height),
+    };
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(content).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Debug (F12) ")
+                .style(Style::default().fg(Color::White)),
+        ),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fps_counts_the_last_second() {
+        let mut stats = DebugStats::default();
+        let start = Instant::now();
+        for i in 0..30 {
+            stats.record_frame(start + Duration::from_millis(i * 100), Duration::ZERO);
+        }
+        let now = start + Duration::from_millis(2900);
+        assert_eq!(stats.fps(now), 11);
+        assert!(stats.frames.len() <= 11);
+    }
+
+    #[test]
+    fn slowest_polls_sorted_and_capped() {
+        let mut stats = DebugStats::default();
+        stats.begin_tick();
+        for (i, name) in ["a", "b", "c", "d", "e", "f"].into_iter().enumerate() {
+            stats.record_poll(name, Duration::from_millis(i as u64));
+        }
+        let names: Vec<_> = stats.slowest_polls().iter().map(|p| p.0).collect();
+        assert_eq!(names, vec!["f", "e", "d", "c", "b"]);
+
+        stats.set_queue_depth(2, 7);
+        assert_eq!(stats.queue_depth, vec![0, 0, 7]);
+    }
+
+    #[test]
+    fn output_bytes_counts_span_text() {
+        let empty = output_bytes(&[Line::from(Span::raw(""))]);
+        assert_eq!(output_bytes(&[Line::from(Span::raw("hello"))]), empty + 5);
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MB");
+    }
+}
@@ -0,0 +1,110 @@
+//! Detaching from the terminal (`Ctrl+d`).
+//!
+//! `q` stops the run before quitting. Detaching closes the TUI instead and
+//! keeps this process supervising the run without a terminal: the terminal's
+//! signals are ignored and stdio points at `/dev/null`, so closing the
+//! terminal or the laptop lid, or a dropped ssh connection, no longer ends
+//! hours of work. The process then moves to the background and the shell
+//! gets its prompt back. Claude already runs in its own process group (see
+//! [`crate::control::own_process_group`]), out of reach of the terminal's
+//! signals. The process exits once every tab's run has ended; `ralph attach`
+//! follows it meanwhile, and `kill` (SIGTERM) stops it.
+
+use std::io;
+
+/// What to tell the user as the TUI closes.
+pub fn detached_message(session_ids: &[&str], pid: u32) -> String {
+    let mut message = format!("Detached. The run keeps going in the background (pid {pid}).\n");
+    for id in session_ids {
+        message.push_str(&format!("  Watch it:  ralph attach {id}\n"));
+    }
+    message.push_str("  Check it:  ralph status\n");
+    message.push_str(&format!("  Stop it:   kill {pid}\n"));
+    message.push_str("The terminal can be closed.\n");
+    message
+}
+
+/// Signals from the terminal that must not reach a detached process.
+#[cfg(unix)]
+const TERMINAL_SIGNALS: [libc::c_int; 6] = [
+    libc::SIGHUP,
+    libc::SIGINT,
+    libc::SIGQUIT,
+    libc::SIGTSTP,
+    libc::SIGTTIN,
+    libc::SIGTTOU,
+];
+
+/// Ignore the terminal's signals, point stdin, stdout and stderr at
+/// `/dev/null`, and move to the background.
+#[cfg(unix)]
+pub fn release_terminal() -> io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let null = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/null")?;
+    // SAFETY: signal(2) and dup2(2) have no memory-safety preconditions; `null`
+    // stays open for both calls.
+    unsafe {
+        for signal in TERMINAL_SIGNALS {
+            libc::signal(signal, libc::SIG_IGN);
+        }
+        for fd in [libc::STDIN_FILENO, libc::STDOUT_FILENO, libc::STDERR_FILENO] {
+            if libc::dup2(null.as_raw_fd(), fd) < 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+    }
+    background()
+}
+
+/// Give the shell its prompt back. Forking would leave the threads reading
+/// Claude's output behind in the parent, so instead the process stops itself,
+/// which makes a job-control shell take the terminal back, and a helper
+/// started just before continues it in the background, as `Ctrl+Z` and `bg`
+/// would.
+#[cfg(unix)]
+fn background() -> io::Result<()> {
+    use std::process::{Command, Stdio};
+
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(format!("sleep 1; kill -CONT {}", std::process::id()))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    // Out of this job, so the shell doesn't stop it along with us
+    crate::control::own_process_group(&mut cmd);
+    let mut helper = cmd.spawn()?;
+    std::thread::spawn(move || helper.wait());
+    // SAFETY: raise(3) has no memory-safety preconditions.
+    if unsafe { libc::raise(libc::SIGSTOP) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn release_terminal() -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "detaching is only supported on Unix",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_names_every_session() {
+        let message = detached_message(&["s1", "s2"], 42);
+        assert!(message.contains("(pid 42)"));
+        assert!(message.contains("ralph attach s1\n"));
+        assert!(message.contains("ralph attach s2\n"));
+        assert!(message.contains("ralph status"));
+        assert!(message.contains("kill 42"));
+    }
+}
@@ -0,0 +1,96 @@
+//! Uncommitted changes in the project's git tree when a run starts.
+//!
+//! The agent commits whatever is in the tree, so half-finished edits end up
+//! tangled into its commits. Starting a run checks first and, with
+//! `behavior.confirm_dirty_start`, asks whether to stash them, continue anyway
+//! or cancel.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use thiserror::Error;
+
+/// Changes under here are bd's own bookkeeping, not the user's work.
+const IGNORED_PREFIX: &str = ".beads/";
+
+/// Paths with uncommitted changes, from `git status --porcelain` output.
+pub fn parse_porcelain(status: &str) -> Vec<String> {
+    status
+        .lines()
+        .filter_map(|line| line.get(3..))
+        // Renames read `old -> new`; the new path is the one in the tree
+        .map(|path| path.rsplit(" -> ").next().unwrap_or(path))
+        .map(|path| path.trim_matches('"').to_string())
+        .filter(|path| !path.starts_with(IGNORED_PREFIX))
+        .collect()
+}
+
+/// Uncommitted changes in the git tree of `project_dir`. Empty outside a
+/// repository or if git fails.
+pub fn uncommitted_changes(project_dir: &Path) -> Vec<String> {
+    let Ok(output) = Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(project_dir)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+    else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    parse_porcelain(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Why stashing failed.
+#[derive(Debug, Error)]
+pub enum StashError {
+    #[error("Failed to run git: {0}")]
+    Spawn(#[from] std::io::Error),
+    /// git refused; holds what it wrote to stderr.
+    #[error("{0}")]
+    Git(String),
+}
+
+/// Stash the uncommitted changes in `project_dir`, untracked files included
+/// (`git stash pop` brings them back).
+pub fn stash(project_dir: &Path) -> Result<(), StashError> {
+    let output = Command::new("git")
+        .args([
+            "stash",
+            "push",
+            "--include-untracked",
+            "--message",
+            "ralph: uncommitted changes before a run",
+            "--",
+            ".",
+            ":(exclude).beads",
+        ])
+        .current_dir(project_dir)
+        .stdin(Stdio::null())
+        .output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(StashError::Git(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_porcelain_skips_bd_files() {
+        let status = " M src/app.rs\n?? notes.txt\nR  old.rs -> new.rs\n M .beads/issues.jsonl\n\
+                      ?? \"with space.txt\"\n";
+        assert_eq!(
+            parse_porcelain(status),
+            vec!["src/app.rs", "notes.txt", "new.rs", "with space.txt"]
+        );
+        assert!(parse_porcelain("").is_empty());
+    }
+}
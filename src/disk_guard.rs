@@ -0,0 +1,207 @@
+//! Disk safety checks between iterations.
+//!
+//! Before an auto-continued iteration starts, ralph checks the free space on
+//! the project's filesystem and how much the project directory has grown
+//! since the run started. Past either `[disk]` limit the loop pauses with the
+//! reason, so an agent writing build artifacts in a loop can't fill the disk.
+//! Directory sizes are measured on a background thread.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, TryRecvError, channel};
+use std::thread;
+
+use tracing::debug;
+
+use crate::config::DiskConfig;
+
+const MB: u64 = 1024 * 1024;
+
+/// Free space available to unprivileged users on the filesystem holding `path`.
+#[cfg(unix)]
+pub fn free_bytes(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    // SAFETY: `c_path` is a valid NUL-terminated string and `stat` is a zeroed
+    // struct we own for `statvfs` to fill in.
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    #[allow(clippy::unnecessary_cast)] // field widths differ between platforms
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+pub fn free_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Total size of the files under `path`, skipping `.git` and not following symlinks.
+pub fn tree_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|e| e.file_name() != ".git")
+        .map(|e| match e.metadata() {
+            Ok(meta) if meta.is_dir() => tree_size(&e.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Why the loop should pause, if `free` or `growth` (bytes) is past a limit.
+pub fn exceeded(config: &DiskConfig, free: Option<u64>, growth: Option<u64>) -> Option<String> {
+    if config.min_free_mb > 0
+        && let Some(free) = free
+        && free < config.min_free_mb.saturating_mul(MB)
+    {
+        return Some(format!(
+            "LOW DISK SPACE — {} MB free (min {} MB)",
+            free / MB,
+            config.min_free_mb
+        ));
+    }
+    if config.max_growth_mb > 0
+        && let Some(growth) = growth
+        && growth > config.max_growth_mb.saturating_mul(MB)
+    {
+        return Some(format!(
+            "PROJECT GREW {} MB THIS RUN (max {} MB)",
+            growth / MB,
+            config.max_growth_mb
+        ));
+    }
+    None
+}
+
+/// Tracks the project directory's size across a run.
+pub struct DiskGuard {
+    root: PathBuf,
+    baseline: Option<u64>,
+    latest: Option<u64>,
+    pending: Option<Receiver<u64>>,
+}
+
+impl DiskGuard {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            baseline: None,
+            latest: None,
+            pending: None,
+        }
+    }
+
+    /// Directory the limits apply to.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Forget the previous run's sizes and measure a new baseline.
+    pub fn start_run(&mut self) {
+        self.baseline = None;
+        self.latest = None;
+        self.pending = None;
+        self.measure();
+    }
+
+    /// Measure the directory size in the background, unless already measuring.
+    pub fn measure(&mut self) {
+        if self.pending.is_some() {
+            return;
+        }
+        let (tx, rx) = channel();
+        let root = self.root.clone();
+        thread::spawn(move || {
+            let _ = tx.send(tree_size(&root));
+        });
+        self.pending = Some(rx);
+    }
+
+    /// Whether a measurement is still running. Picks up a finished one.
+    pub fn measuring(&mut self) -> bool {
+        let Some(rx) = &self.pending else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(size) => {
+                debug!(size, "disk_tree_measured");
+                self.record(size);
+                self.pending = None;
+                false
+            }
+            Err(TryRecvError::Empty) => true,
+            Err(TryRecvError::Disconnected) => {
+                self.pending = None;
+                false
+            }
+        }
+    }
+
+    fn record(&mut self, size: u64) {
+        self.baseline.get_or_insert(size);
+        self.latest = Some(size);
+    }
+
+    /// Growth since the run's baseline, once both have been measured.
+    pub fn growth(&self) -> Option<u64> {
+        Some(self.latest?.saturating_sub(self.baseline?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(min_free_mb: u64, max_growth_mb: u64) -> DiskConfig {
+        DiskConfig {
+            min_free_mb,
+            max_growth_mb,
+        }
+    }
+
+    #[test]
+    fn exceeded_reports_low_space_then_growth() {
+        let config = limits(100, 50);
+        assert_eq!(exceeded(&config, Some(200 * MB), Some(10 * MB)), None);
+        assert_eq!(
+            exceeded(&config, Some(99 * MB), Some(60 * MB)).as_deref(),
+            Some("LOW DISK SPACE — 99 MB free (min 100 MB)")
+        );
+        assert_eq!(
+            exceeded(&config, Some(200 * MB), Some(60 * MB)).as_deref(),
+            Some("PROJECT GREW 60 MB THIS RUN (max 50 MB)")
+        );
+    }
+
+    #[test]
+    fn zero_limits_disable_checks() {
+        assert_eq!(exceeded(&limits(0, 0), Some(0), Some(u64::MAX)), None);
+        assert_eq!(exceeded(&limits(100, 50), None, None), None);
+    }
+
+    #[test]
+    fn tree_size_skips_git_and_tracks_growth() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+        std::fs::write(dir.path().join(".git/objects"), vec![0; 4096]).unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), vec![0; 100]).unwrap();
+        assert_eq!(tree_size(dir.path()), 100);
+
+        let mut guard = DiskGuard::new(dir.path().to_path_buf());
+        guard.record(tree_size(dir.path()));
+        std::fs::write(dir.path().join("big.bin"), vec![0; 1000]).unwrap();
+        guard.record(tree_size(dir.path()));
+        assert_eq!(guard.growth(), Some(1000));
+    }
+
+    #[test]
+    fn free_bytes_reads_current_filesystem() {
+        assert!(free_bytes(Path::new(".")).is_some());
+        assert!(free_bytes(Path::new("/definitely/not/here")).is_none());
+    }
+}
@@ -0,0 +1,202 @@
+//! Encryption at rest for transcripts, reports and exports
+//! (`[encryption] age_recipients`).
+//!
+//! Transcripts hold everything Claude read and wrote, proprietary code and
+//! secrets included, in the shared log directory. With recipients set, they
+//! are piped through the `age` CLI as they're written, and archives are
+//! streamed from `tar` straight into `age`, so no plaintext copy of either is
+//! written. Files staged for an archive live in a private temp directory that
+//! is removed however the build ends. Decrypting is left to
+//! `age -d -i <identity>`.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::config::Config;
+
+/// Extension added to encrypted files.
+pub const EXTENSION: &str = ".age";
+
+/// `age` arguments for `recipients`: public keys (`age1…`, `ssh-…`) are
+/// passed with `-r`, anything else is read as a recipients file with `-R`.
+pub fn recipient_args(recipients: &[String]) -> Vec<String> {
+    recipients
+        .iter()
+        .flat_map(|r| {
+            if r.starts_with("age1") || r.starts_with("ssh-") {
+                ["-r".to_string(), r.clone()]
+            } else {
+                [
+                    "-R".to_string(),
+                    Config::expand_tilde(r).display().to_string(),
+                ]
+            }
+        })
+        .collect()
+}
+
+/// `path` with the encrypted file extension.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Start `age` writing what it reads on stdin, encrypted, to `output`.
+pub fn spawn_writer(recipients: &[String], output: &Path) -> io::Result<Child> {
+    Command::new("age")
+        .args(recipient_args(recipients))
+        .arg("-o")
+        .arg(output)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+}
+
+/// Copy `from` to `to`, encrypting it on the way when `recipients` is set
+/// (unless it already is). Returns the path written.
+pub fn copy_file(recipients: &[String], from: &Path, to: &Path) -> Result<PathBuf> {
+    let already_encrypted = from.to_str().is_some_and(|name| name.ends_with(EXTENSION));
+    if recipients.is_empty() || already_encrypted {
+        std::fs::copy(from, to).with_context(|| format!("Failed to copy {}", from.display()))?;
+        return Ok(to.to_path_buf());
+    }
+    let mut source =
+        std::fs::File::open(from).with_context(|| format!("Failed to read {}", from.display()))?;
+    let encrypted = encrypted_path(to);
+    let mut age =
+        spawn_writer(recipients, &encrypted).context("Failed to run age (is it installed?)")?;
+    let copied = match age.stdin.as_mut() {
+        Some(stdin) => io::copy(&mut source, stdin).map(drop),
+        None => Ok(()),
+    };
+    drop(age.stdin.take());
+    let result = age
+        .wait()
+        .map_err(Into::into)
+        .and_then(|status| exit_ok(status).map_err(|e| anyhow!("age {e}")))
+        .and(copied.context("Failed to pipe into age"));
+    finish(&encrypted, result)
+}
+
+/// Run `cmd` and write its stdout to `output`, piped through `age` when
+/// `recipients` is set so it only ever reaches the disk encrypted. Returns
+/// the path written (`output` with the encrypted extension when encrypting).
+/// On failure nothing is left at that path.
+pub fn write_command_output(
+    recipients: &[String],
+    mut cmd: Command,
+    output: &Path,
+) -> Result<PathBuf> {
+    if recipients.is_empty() {
+        let file = std::fs::File::create(output)
+            .with_context(|| format!("Failed to create {}", output.display()))?;
+        let status = cmd.stdout(file).status();
+        return finish(output, status.map_err(Into::into).and_then(exit_ok));
+    }
+    let encrypted = encrypted_path(output);
+    let mut age =
+        spawn_writer(recipients, &encrypted).context("Failed to run age (is it installed?)")?;
+    let result = pipe_into(&mut cmd, &mut age);
+    // Closing age's stdin lets it finish the file
+    drop(age.stdin.take());
+    let age_status = age
+        .wait()
+        .map_err(Into::into)
+        .and_then(|status| exit_ok(status).map_err(|e| anyhow!("age {e}")));
+    finish(&encrypted, result.and(age_status))
+}
+
+/// Run `cmd` with its stdout copied into `age`'s stdin until it exits.
+fn pipe_into(cmd: &mut Command, age: &mut Child) -> Result<()> {
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .spawn()
+        .context("Failed to start archiver")?;
+    let copied = match (child.stdout.as_mut(), age.stdin.as_mut()) {
+        (Some(from), Some(to)) => io::copy(from, to).map(drop),
+        _ => Ok(()),
+    };
+    let status = child.wait()?;
+    copied.context("Failed to pipe into age")?;
+    exit_ok(status)
+}
+
+fn exit_ok(status: std::process::ExitStatus) -> Result<()> {
+    if status.success() {
+        Ok(())
+    } else {
+        bail!("exited with {status}")
+    }
+}
+
+/// `path` on success; on failure, remove whatever was written there.
+fn finish(path: &Path, result: Result<()>) -> Result<PathBuf> {
+    match result {
+        Ok(()) => Ok(path.to_path_buf()),
+        Err(e) => {
+            let _ = std::fs::remove_file(path);
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_and_files_get_their_flags() {
+        let args = recipient_args(&[
+            "age1qyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqs3290gq".into(),
+            "ssh-ed25519 AAAAC3Nza user@host".into(),
+            "/etc/ralph/recipients.txt".into(),
+        ]);
+        assert_eq!(args[0], "-r");
+        assert!(args[1].starts_with("age1"));
+        assert_eq!(args[2], "-r");
+        assert_eq!(args[4..], ["-R", "/etc/ralph/recipients.txt"]);
+        assert!(recipient_args(&[]).is_empty());
+    }
+
+    fn sh(script: &str) -> Command {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(script);
+        cmd
+    }
+
+    #[test]
+    fn without_recipients_output_is_written_as_is() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.tar.gz");
+        assert_eq!(
+            write_command_output(&[], sh("printf archive"), &path).unwrap(),
+            path
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "archive");
+        assert_eq!(encrypted_path(&path), dir.path().join("report.tar.gz.age"));
+    }
+
+    #[test]
+    fn failed_command_leaves_nothing_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.tar.gz");
+        assert!(write_command_output(&[], sh("printf partial; exit 2"), &path).is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn encrypted_files_are_copied_without_age() {
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("s1.log.age");
+        std::fs::write(&from, "sealed").unwrap();
+        let to = dir.path().join("copy.log.age");
+        let written = copy_file(&["age1xyz".to_string()], &from, &to).unwrap();
+        assert_eq!(written, to);
+        assert_eq!(std::fs::read_to_string(&to).unwrap(), "sealed");
+    }
+}
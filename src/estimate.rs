@@ -0,0 +1,216 @@
+//! "est. remaining" line in the command panel while a run is active.
+//!
+//! Specs left are the cards in the board's Ready and In Progress columns.
+//! Each is expected to take as many iterations as completed specs have
+//! averaged in this repo, less what an in-progress spec has already used, at
+//! the historical cost and duration per iteration. Time is divided across
+//! workers. The history is re-read from the stats database at the start of a
+//! run and after every iteration, so the estimate tracks the run.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::app::App;
+use crate::number_format::NumberFormat;
+use crate::spec_stats::SpecStats;
+
+/// Board columns whose cards count as work left.
+const READY_COLUMN: &str = "Ready";
+const IN_PROGRESS_COLUMN: &str = "In Progress";
+
+/// Averages over this repo's completed specs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct History {
+    pub iterations_per_spec: f64,
+    pub cost_per_iteration: f64,
+    /// Zero when no duration was recorded (specs imported from older versions).
+    pub ms_per_iteration: f64,
+    /// Iterations already attributed to each spec, completed or not.
+    pub spent: BTreeMap<String, u32>,
+}
+
+impl History {
+    /// Averages from per-spec totals, or `None` before any spec has completed.
+    pub fn from_totals(totals: &BTreeMap<String, SpecStats>) -> Option<Self> {
+        let done: Vec<&SpecStats> = totals
+            .values()
+            .filter(|s| s.completed_at.is_some() && s.iterations > 0)
+            .collect();
+        if done.is_empty() {
+            return None;
+        }
+        let iterations: f64 = done.iter().map(|s| f64::from(s.iterations)).sum();
+        Some(Self {
+            iterations_per_spec: iterations / done.len() as f64,
+            cost_per_iteration: done.iter().map(|s| s.cost_usd).sum::<f64>() / iterations,
+            ms_per_iteration: done.iter().map(|s| s.duration_ms as f64).sum::<f64>() / iterations,
+            spent: totals
+                .iter()
+                .map(|(id, s)| (id.clone(), s.iterations))
+                .collect(),
+        })
+    }
+}
+
+/// Expected work left.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimate {
+    pub iterations: u32,
+    pub cost_usd: f64,
+    /// `None` without duration history.
+    pub duration: Option<Duration>,
+}
+
+/// Estimate `ready` untouched specs plus the `in_progress` ones, run by `workers`.
+pub fn estimate(
+    history: &History,
+    ready: usize,
+    in_progress: &[&str],
+    workers: usize,
+) -> Option<Estimate> {
+    if ready == 0 && in_progress.is_empty() {
+        return None;
+    }
+    let fresh = ready as f64 * history.iterations_per_spec;
+    // A spec that has run past the average still needs at least one more go
+    let started: f64 = in_progress
+        .iter()
+        .map(|id| {
+            let spent = history.spent.get(*id).copied().unwrap_or(0);
+            (history.iterations_per_spec - f64::from(spent)).max(1.0)
+        })
+        .sum();
+    let iterations = (fresh + started).round().max(1.0) as u32;
+    let duration = (history.ms_per_iteration > 0.0).then(|| {
+        let ms = f64::from(iterations) * history.ms_per_iteration / workers.max(1) as f64;
+        Duration::from_millis(ms as u64)
+    });
+    Some(Estimate {
+        iterations,
+        cost_usd: f64::from(iterations) * history.cost_per_iteration,
+        duration,
+    })
+}
+
+/// `est. remaining: ~6 iters / ~$3.40 / ~2h`
+pub fn format(estimate: &Estimate, numbers: &NumberFormat) -> String {
+    let noun = if estimate.iterations == 1 {
+        "iter"
+    } else {
+        "iters"
+    };
+    let mut parts = vec![
+        format!("~{} {noun}", estimate.iterations),
+        format!("~{}", numbers.cost(estimate.cost_usd)),
+    ];
+    parts.extend(estimate.duration.map(|d| format!("~{}", rough_duration(d))));
+    format!("est. remaining: {}", parts.join(" / "))
+}
+
+/// Minutes under an hour and a half, whole hours beyond.
+fn rough_duration(duration: Duration) -> String {
+    let minutes = duration.as_secs().div_ceil(60).max(1);
+    if minutes < 90 {
+        format!("{minutes}m")
+    } else {
+        format!("{}h", (minutes + 30) / 60)
+    }
+}
+
+impl App {
+    /// Re-read per-spec history from the stats database.
+    pub fn refresh_estimate_history(&mut self) {
+        self.estimate_history = self
+            .tool_history_db
+            .as_ref()
+            .and_then(|conn| crate::stats::spec_totals(conn, &self.repo_path).ok())
+            .and_then(|totals| History::from_totals(&totals));
+    }
+
+    /// Work left on the board, given this repo's history.
+    pub fn remaining_estimate(&self) -> Option<Estimate> {
+        let history = self.estimate_history.as_ref()?;
+        let board = &self.kanban_board_state;
+        let column = |name: &str| {
+            board
+                .column_defs
+                .iter()
+                .position(|def| def.name.eq_ignore_ascii_case(name))
+                .and_then(|i| board.columns.get(i))
+                .into_iter()
+                .flatten()
+                .filter(|card| !card.is_error && !card.is_epic)
+        };
+        let ready = column(READY_COLUMN).count();
+        let in_progress: Vec<&str> = column(IN_PROGRESS_COLUMN)
+            .map(|card| card.id.as_str())
+            .collect();
+        estimate(history, ready, &in_progress, self.workers.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(iterations: u32, cost_usd: f64, duration_ms: u64, done: bool) -> SpecStats {
+        SpecStats {
+            iterations,
+            cost_usd,
+            duration_ms,
+            completed_at: done.then_some(1),
+            ..Default::default()
+        }
+    }
+
+    fn history() -> History {
+        let totals = BTreeMap::from([
+            ("bd-1".to_string(), spec(2, 1.0, 600_000, true)),
+            ("bd-2".to_string(), spec(4, 1.0, 1_200_000, true)),
+            ("bd-3".to_string(), spec(1, 0.5, 300_000, false)),
+        ]);
+        History::from_totals(&totals).unwrap()
+    }
+
+    #[test]
+    fn averages_completed_specs_only() {
+        let history = history();
+        assert_eq!(history.iterations_per_spec, 3.0);
+        assert!((history.cost_per_iteration - 2.0 / 6.0).abs() < 1e-9);
+        assert_eq!(history.ms_per_iteration, 300_000.0);
+        assert_eq!(history.spent["bd-3"], 1);
+        assert_eq!(History::from_totals(&BTreeMap::new()), None);
+    }
+
+    #[test]
+    fn counts_what_in_progress_specs_have_used() {
+        let history = history();
+        assert_eq!(estimate(&history, 0, &[], 1), None);
+
+        // Two fresh specs at 3 each, bd-3 has 2 to go, an unknown one a full 3
+        let est = estimate(&history, 2, &["bd-3", "bd-9"], 1).unwrap();
+        assert_eq!(est.iterations, 11);
+        assert_eq!(est.duration, Some(Duration::from_secs(11 * 300)));
+        let numbers = NumberFormat::default();
+        assert_eq!(
+            format(&est, &numbers),
+            "est. remaining: ~11 iters / ~$3.67 / ~55m"
+        );
+
+        // Two workers halve the time, not the cost
+        let est = estimate(&history, 6, &[], 2).unwrap();
+        assert_eq!(est.iterations, 18);
+        assert_eq!(
+            format(&est, &numbers),
+            "est. remaining: ~18 iters / ~$6.00 / ~45m"
+        );
+    }
+
+    #[test]
+    fn rough_duration_rounds_to_hours_past_ninety_minutes() {
+        assert_eq!(rough_duration(Duration::from_secs(20)), "1m");
+        assert_eq!(rough_duration(Duration::from_secs(89 * 60)), "89m");
+        assert_eq!(rough_duration(Duration::from_secs(100 * 60)), "2h");
+        assert_eq!(rough_duration(Duration::from_secs(150 * 60)), "3h");
+    }
+}
@@ -1,243 +1,680 @@
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::Result;
-use crossterm::event::{Event, KeyCode};
+use crossterm::event::{DisableMouseCapture, Event, KeyCode, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{LeaveAlternateScreen, disable_raw_mode};
 use ratatui::DefaultTerminal;
+use tracing::info;
 
 use crate::app::{App, AppStatus};
-use crate::config::{compute_project_config_path, load_project_config};
+use crate::config::{
+    IdleStyle, compute_project_config_path, ensure_project_prompt, load_project_config,
+};
+use crate::detach;
 use crate::execution;
+use crate::login;
 use crate::modals::{
-    ConfigModalState, InitModalState, WorkersStreamState, handle_bead_picker_input,
-    handle_config_modal_input, handle_init_modal_input, handle_kanban_input,
-    handle_tool_allow_modal_input, handle_workers_stream_input,
+    ConfigModalState, DiagnosticsState, InitModalState, InstructionsState, NewTabState,
+    WorkersStreamState, handle_bead_picker_input, handle_bead_search_input,
+    handle_config_modal_input, handle_diagnostics_input, handle_dirty_start_input,
+    handle_init_modal_input, handle_instructions_input, handle_kanban_input,
+    handle_mark_done_input, handle_new_tab_input, handle_question_reply_input, handle_rerun_input,
+    handle_restore_input, handle_tool_allow_modal_input, handle_workers_stream_input,
 };
 use crate::output;
-use crate::startup::{ensure_worktree, merge_and_refresh_worktree};
-use crate::ui::draw_ui;
+use crate::shaping;
+use crate::shutdown_summary::{self, ExitReason};
+use crate::startup::{ensure_worktree, merge_and_refresh_worktree, shutdown_app};
+use crate::suspend;
+use crate::tabs::Tabs;
+use crate::ui::{
+    IdleTracker, apply_theme, detect_color_depth, dim_frame, draw_blank_screen, draw_tab_strip,
+    draw_ui, fit_frame_colors, idle_timeout, shortcut_page_at, status_text,
+};
 
-pub(crate) fn run_event_loop(app: &mut App, terminal: &mut DefaultTerminal) -> Result<()> {
-    loop {
-        // Poll for output from child process
-        output::poll_output(app);
+/// What the loop should do after the active tab handled an event.
+enum LoopControl {
+    Continue,
+    Quit,
+    /// Open a tab on this project directory.
+    NewTab(PathBuf),
+    CloseTab,
+    SwitchTab(usize),
+    /// Suspend the TUI for an interactive `claude /shape` session.
+    ShapeSpec,
+    /// Suspend the TUI and open the active tab's `editor_request` in `$EDITOR`.
+    OpenEditor,
+    /// Suspend the TUI for an interactive `claude login`.
+    Login,
+    /// Close the TUI and keep supervising the run without a terminal.
+    Detach,
+}
 
-        // Poll for background worker startup completion
-        app.poll_worker_start();
+/// Run the main loop across all tabs.
+///
+/// `new_tab` builds a fully-initialised `App` for the given tab number and
+/// project directory.
+pub(crate) fn run_event_loop(
+    tabs: &mut Tabs,
+    terminal: &mut DefaultTerminal,
+    mut new_tab: impl FnMut(u32, &Path) -> App,
+) -> Result<ExitReason> {
+    let mut idle = IdleTracker::new();
+    let detected_colors = detect_color_depth();
+    loop {
+        // Every tab keeps its background work moving, focused or not
+        for app in &mut tabs.apps {
+            tick(app)?;
+        }
+        shutdown_summary::record(&tabs.apps);
+        if let Some(signal) = shutdown_summary::caught_signal() {
+            info!(signal, "signal_shutdown");
+            return Ok(ExitReason::Signal(signal));
+        }
 
-        // Handle auto-continue for all workers
-        for w_idx in 0..app.workers.len() {
-            if app.workers[w_idx].auto_continue_pending {
-                app.dirty = true;
-                app.workers[w_idx].auto_continue_pending = false;
-                app.selected_worker = w_idx;
+        // Mouse capture follows the focused tab's project config
+        suspend::set_mouse_capture(tabs.apps[tabs.active].config.keys.mouse_capture)?;
+        let display = &tabs.apps[tabs.active].config.display;
+        let idle_style = display.idle_style;
+        let color_depth = display.color_depth.or_detected(detected_colors);
+        let theme = display.theme;
+        let idle_changed = idle.check(idle_timeout(display.idle_minutes));
 
-                if !merge_and_refresh_worktree(app) {
-                    continue;
+        // Draw UI only when state changed
+        if idle_changed || tabs.apps.iter().any(|a| a.dirty) {
+            let labels: Vec<_> = tabs
+                .apps
+                .iter()
+                .map(|a| (a.status.tab_label(), a.status.status_color()))
+                .collect();
+            let active = tabs.active;
+            let app = &mut tabs.apps[active];
+            let draw_started = Instant::now();
+            terminal.draw(|f| {
+                if idle.is_idle() && idle_style == IdleStyle::Blank {
+                    draw_blank_screen(f, &idle, &status_text(app));
+                    return;
                 }
-
-                app.workers[w_idx].increment_iteration();
-                execution::claim_before_start(app);
-                if !ensure_worktree(app) {
-                    continue;
+                draw_ui(f, app);
+                if labels.len() > 1 {
+                    draw_tab_strip(f, &labels, active);
                 }
-                execution::start_command(app)?;
-                app.update_derived_status();
+                if idle.is_idle() {
+                    dim_frame(f);
+                }
+                apply_theme(f, theme);
+                fit_frame_colors(f, color_depth);
+            })?;
+            app.debug_stats
+                .record_frame(Instant::now(), draw_started.elapsed());
+            for app in &mut tabs.apps {
+                app.dirty = false;
             }
         }
-        app.selected_worker = 0;
-
-        // Poll for background work source operations
-        app.poll_work_check();
-        app.poll_kanban_items();
-        app.poll_bead_detail();
-        app.poll_preview_fetch();
-        app.poll_board_mutations();
-        app.poll_bead_picker();
-        app.poll_pending_dep();
 
-        // Poll for current bead (throttled to every 2 seconds)
-        app.poll_bead();
+        // State-dependent poll timeout: fast when running/starting, slow when idle
+        let poll_timeout = if tabs.any_active() {
+            Duration::from_millis(50)
+        } else {
+            Duration::from_millis(250)
+        };
+        if crossterm::event::poll(poll_timeout)? {
+            let event = crossterm::event::read()?;
+            // The first key after the screen went idle only wakes it
+            if matches!(event, Event::Key(_)) && idle.wake() {
+                tabs.active_mut().dirty = true;
+                continue;
+            }
+            let tab_count = tabs.apps.len();
+            match handle_event(tabs.active_mut(), event, tab_count) {
+                LoopControl::Continue => {}
+                LoopControl::Quit => return Ok(ExitReason::Quit),
+                LoopControl::NewTab(dir) => match tabs.next_tab_number() {
+                    Ok(n) => tabs.open(new_tab(n, &dir)),
+                    Err(e) => tabs.active_mut().set_hint(e.hint()),
+                },
+                LoopControl::CloseTab => match tabs.close_active() {
+                    Ok(mut closed) => shutdown_app(&mut closed),
+                    Err(e) => tabs.active_mut().set_hint(e.hint()),
+                },
+                LoopControl::SwitchTab(index) => {
+                    tabs.switch_to(index);
+                }
+                LoopControl::Detach => {
+                    if !tabs.any_active() {
+                        tabs.active_mut()
+                            .set_hint("Nothing is running \u{2014} q quits");
+                        continue;
+                    }
+                    return run_detached(tabs);
+                }
+                LoopControl::ShapeSpec => {
+                    let app = tabs.active_mut();
+                    let claude_path = app.config.claude_path();
+                    match shaping::run_shaping_session(terminal, &claude_path, &app.config.network)
+                    {
+                        Ok(()) => {
+                            // Show beads created during the interview
+                            app.trigger_kanban_refresh();
+                            app.set_hint("Shaping session ended \u{2014} board refreshed");
+                        }
+                        Err(e) => app.set_hint(format!("Could not start claude: {e}")),
+                    }
+                    idle.wake();
+                    app.dirty = true;
+                }
+                LoopControl::OpenEditor => {
+                    let app = tabs.active_mut();
+                    if let Some(path) = app.editor_request.take() {
+                        match suspend::open_in_editor(terminal, &path) {
+                            Ok(()) => app.set_hint(format!("Edited {}", path.display())),
+                            Err(e) => app.set_hint(format!("Editor failed: {e}")),
+                        }
+                    }
+                    idle.wake();
+                    app.dirty = true;
+                }
+                LoopControl::Login => {
+                    let app = tabs.active_mut();
+                    let claude_path = app.config.claude_path();
+                    match login::run_login_session(terminal, &claude_path, &app.config.network) {
+                        Ok(true) => app.set_hint("Logged in \u{2014} press S to start"),
+                        Ok(false) => app.set_hint("claude login did not complete"),
+                        Err(e) => app.set_hint(format!("Could not start claude: {e}")),
+                    }
+                    idle.wake();
+                    app.dirty = true;
+                }
+            }
+        }
+    }
+}
 
-        // Poll for config file changes (throttled to every 2 seconds)
-        app.poll_config();
+/// Hand back the terminal and keep ticking every tab until all runs end.
+fn run_detached(tabs: &mut Tabs) -> Result<ExitReason> {
+    disable_raw_mode()?;
+    execute!(std::io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+    let session_ids: Vec<&str> = tabs.apps.iter().map(|a| a.session_id.as_str()).collect();
+    print!(
+        "{}",
+        detach::detached_message(&session_ids, std::process::id())
+    );
+    detach::release_terminal()?;
+    info!("detached");
 
-        // Poll for background doctor check results
-        app.poll_doctor();
+    while tabs.any_active() {
+        for app in &mut tabs.apps {
+            tick(app)?;
+        }
+        shutdown_summary::record(&tabs.apps);
+        if let Some(signal) = shutdown_summary::caught_signal() {
+            info!(signal, "signal_shutdown");
+            return Ok(ExitReason::Signal(signal));
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    info!("detached_run_ended");
+    Ok(ExitReason::RunEnded)
+}
 
-        // Auto-clear error flash after timeout
-        app.check_error_timeout();
+/// Advance one tab's background state: child output, startup, auto-continue, polls.
+pub(crate) fn tick(app: &mut App) -> Result<()> {
+    let started = Instant::now();
+    app.debug_stats.begin_tick();
 
-        // Auto-clear hint after timeout
-        app.check_hint_timeout();
+    // Poll for output from child process
+    timed(app, "output", output::poll_output);
 
-        // Draw UI only when state changed
-        if app.dirty {
-            terminal.draw(|f| draw_ui(f, app))?;
-            app.dirty = false;
-        }
+    // Poll for background worker startup completion
+    timed(app, "worker_start", App::poll_worker_start);
 
-        // State-dependent poll timeout: fast when running/starting, slow when idle
-        let poll_timeout = match app.status {
-            AppStatus::Running | AppStatus::Starting => Duration::from_millis(50),
-            _ => Duration::from_millis(250),
-        };
-        if crossterm::event::poll(poll_timeout)? {
-            let event = crossterm::event::read()?;
+    // Handle auto-continue for all workers (held during quiet hours, while
+    // over the hourly budget, or past the disk limits)
+    let quiet = app.hold_for_quiet_hours();
+    let throttled = app.hold_for_budget();
+    let disk = app.hold_for_disk_space();
+    let held = quiet || throttled || disk;
+    app.hold_for_question();
+    for w_idx in 0..app.workers.len() {
+        if app.workers[w_idx].auto_continue_pending
+            && app.workers[w_idx].question.is_none()
+            && app.workers[w_idx].iteration_hook.is_none()
+            && !held
+        {
             app.dirty = true;
+            app.workers[w_idx].auto_continue_pending = false;
+            app.selected_worker = w_idx;
 
-            // Clear hint on any keypress
-            if matches!(event, Event::Key(_)) {
-                app.hint = None;
+            if !merge_and_refresh_worktree(app) {
+                continue;
             }
 
-            // Handle popup dismissal first
-            if app.show_already_running_popup {
-                if let Event::Key(key) = event
-                    && (key.code == KeyCode::Enter || key.code == KeyCode::Esc)
-                {
-                    app.show_already_running_popup = false;
-                }
+            app.workers[w_idx].increment_iteration();
+            execution::claim_before_start(app);
+            if !ensure_worktree(app) {
                 continue;
             }
+            execution::start_command(app)?;
+            app.update_derived_status();
+        }
+    }
+    app.selected_worker = 0;
+
+    // Poll for background work source operations
+    timed(app, "work_check", App::poll_work_check);
+    timed(app, "stop_deadlines", App::poll_stop_deadlines);
+    timed(app, "iteration_timeouts", App::poll_iteration_timeouts);
+    timed(app, "iteration_hooks", App::poll_iteration_hooks);
+    timed(app, "kanban_items", App::poll_kanban_items);
+    timed(app, "bead_detail", App::poll_bead_detail);
+    timed(app, "preview_fetch", App::poll_preview_fetch);
+    timed(app, "board_mutations", App::poll_board_mutations);
+    timed(app, "bead_picker", App::poll_bead_picker);
+    timed(app, "bead_search", App::poll_bead_search);
+    timed(app, "hook_failures", App::poll_hook_failures);
+    timed(app, "pending_dep", App::poll_pending_dep);
+    timed(app, "spec_checklist", App::poll_spec_checklist);
+
+    // Poll for current bead (throttled to every 2 seconds)
+    timed(app, "bead", App::poll_bead);
+
+    // Poll for config file changes (throttled to every 2 seconds)
+    timed(app, "config", App::poll_config);
+
+    // Poll for background doctor check results
+    timed(app, "doctor", App::poll_doctor);
+
+    // Stream output from a locally re-run command
+    if let Some(state) = &mut app.rerun_state
+        && state.poll()
+    {
+        app.dirty = true;
+    }
+
+    // Raise the log level on entering the error state (before it auto-clears)
+    app.check_log_bump();
+
+    // Comment on the linked issue when a run fails
+    app.check_failure_comments();
+
+    // Auto-clear error flash after timeout
+    app.check_error_timeout();
+
+    // Auto-clear hint after timeout
+    app.check_hint_timeout();
+    // Overflowing shortcuts cycle through their pages
+    if app
+        .command_bar_page
+        .is_some_and(|page| page != shortcut_page_at(SystemTime::now()))
+    {
+        app.dirty = true;
+    }
+
+    // Free the project lock for other instances once this tab's run ends
+    app.release_session_lock_if_idle();
+
+    // Keep `ralph status` current (removed once the lock is released)
+    app.publish_run_status();
+    app.publish_metrics();
+
+    app.debug_stats.end_tick(started.elapsed());
+    // Keep the overlay's numbers live
+    if app.show_debug_overlay {
+        app.dirty = true;
+    }
+    Ok(())
+}
+
+/// Run one poll, recording how long it took for the debug overlay.
+fn timed(app: &mut App, name: &'static str, poll: impl FnOnce(&mut App)) {
+    let started = Instant::now();
+    poll(app);
+    app.debug_stats.record_poll(name, started.elapsed());
+}
+
+/// Route one terminal event to the focused tab's modal-precedence chain.
+fn handle_event(app: &mut App, event: Event, tab_count: usize) -> LoopControl {
+    app.dirty = true;
+
+    // Clear hint on any keypress
+    if matches!(event, Event::Key(_)) {
+        app.hint = None;
+    }
+
+    // The debug overlay toggles over everything else
+    if let Event::Key(key) = event
+        && key.code == KeyCode::F(12)
+    {
+        app.show_debug_overlay = !app.show_debug_overlay;
+        return LoopControl::Continue;
+    }
 
-            // Handle bead picker input
-            if app.show_bead_picker {
-                if let Event::Key(key) = event {
-                    handle_bead_picker_input(app, key.code);
+    // Handle popup dismissal first
+    if app.show_already_running_popup {
+        if let Event::Key(key) = event
+            && (key.code == KeyCode::Enter || key.code == KeyCode::Esc)
+        {
+            app.show_already_running_popup = false;
+        }
+        return LoopControl::Continue;
+    }
+
+    // Handle lock conflict modal input
+    if app.lock_conflict.is_some() {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Char('a') => {
+                    app.attach_request = app.lock_conflict.take().map(|info| info.session_id);
+                    return LoopControl::Quit;
                 }
-                continue;
+                KeyCode::Esc | KeyCode::Char('n') => {
+                    app.lock_conflict = None;
+                }
+                _ => {}
             }
+        }
+        return LoopControl::Continue;
+    }
+
+    // Handle dirty tree prompt input
+    if app.dirty_start_prompt.is_some() {
+        if let Event::Key(key) = event {
+            handle_dirty_start_input(app, key.code);
+        }
+        return LoopControl::Continue;
+    }
 
-            // Handle config modal input
-            if app.show_config_modal {
-                if let Event::Key(key) = event {
-                    handle_config_modal_input(app, key.code, key.modifiers);
+    // Handle not-authenticated modal input
+    if app.auth_failure.is_some() {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Char('l') => {
+                    app.auth_failure = None;
+                    return LoopControl::Login;
                 }
-                continue;
+                KeyCode::Esc | KeyCode::Char('n') => {
+                    app.auth_failure = None;
+                }
+                _ => {}
             }
+        }
+        return LoopControl::Continue;
+    }
 
-            // Handle init modal input
-            if app.show_init_modal {
-                if let Event::Key(key) = event {
-                    handle_init_modal_input(app, key.code);
+    // Handle bead picker input
+    if app.show_bead_picker {
+        if let Event::Key(key) = event {
+            handle_bead_picker_input(app, key.code);
+        }
+        return LoopControl::Continue;
+    }
+
+    // Handle bead search input
+    if app.bead_search_state.is_some() {
+        if let Event::Key(key) = event {
+            handle_bead_search_input(app, key.code);
+        }
+        return LoopControl::Continue;
+    }
+
+    // Handle config modal input
+    if app.show_config_modal {
+        if let Event::Key(key) = event {
+            handle_config_modal_input(app, key.code, key.modifiers);
+        }
+        if app.editor_request.is_some() {
+            return LoopControl::OpenEditor;
+        }
+        return LoopControl::Continue;
+    }
+
+    // Handle init modal input
+    if app.show_init_modal {
+        if let Event::Key(key) = event {
+            handle_init_modal_input(app, key.code);
+        }
+        return LoopControl::Continue;
+    }
+
+    // Handle quit confirmation modal input
+    if app.show_quit_modal {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    return LoopControl::Quit;
                 }
-                continue;
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    app.show_quit_modal = false;
+                }
+                _ => {}
             }
+        }
+        return LoopControl::Continue;
+    }
 
-            // Handle quit confirmation modal input
-            if app.show_quit_modal {
-                if let Event::Key(key) = event {
-                    match key.code {
-                        KeyCode::Char('y') | KeyCode::Char('Y') => {
-                            return Ok(());
-                        }
-                        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-                            app.show_quit_modal = false;
+    // Handle mark-done prompt input
+    if app.mark_done_prompt.is_some() {
+        if let Event::Key(key) = event {
+            handle_mark_done_input(app, key.code);
+        }
+        return LoopControl::Continue;
+    }
+
+    // Handle help modal input — overlay on top of everything
+    if app.help_context.is_some() {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Char('?') | KeyCode::Esc => {
+                    app.help_context = None;
+                }
+                KeyCode::Char('S') if app.deny_in_read_only("Starting the loop") => {}
+                KeyCode::Char('S') => match app.status {
+                    AppStatus::Stopped | AppStatus::Error => {
+                        app.help_context = None;
+                        if !app.cancel_held_iterations() {
+                            app.begin_starting_workers();
                         }
-                        _ => {}
+                    }
+                    AppStatus::Running => {
+                        app.help_context = None;
+                        app.stop_command();
+                    }
+                    AppStatus::Starting => {}
+                },
+                KeyCode::Char('q') => {
+                    app.help_context = None;
+                    if app.status == AppStatus::Running {
+                        app.set_hint("press s to stop the loop");
+                    } else {
+                        app.show_quit_modal = true;
                     }
                 }
-                continue;
+                _ => {}
             }
+        }
+        return LoopControl::Continue;
+    }
 
-            // Handle help modal input — overlay on top of everything
-            if app.help_context.is_some() {
-                if let Event::Key(key) = event {
-                    match key.code {
-                        KeyCode::Char('?') | KeyCode::Esc => {
-                            app.help_context = None;
-                        }
-                        KeyCode::Char('S') => match app.status {
-                            AppStatus::Stopped | AppStatus::Error => {
-                                app.help_context = None;
-                                app.begin_starting_workers();
-                            }
-                            AppStatus::Running => {
-                                app.help_context = None;
-                                app.stop_command();
-                            }
-                            AppStatus::Starting => {}
-                        },
-                        KeyCode::Char('q') => {
-                            app.help_context = None;
-                            if app.status == AppStatus::Running {
-                                app.set_hint("press s to stop the loop");
-                            } else {
-                                app.show_quit_modal = true;
-                            }
-                        }
-                        _ => {}
+    // Handle tool allow modal input
+    if app.show_tool_allow_modal {
+        if let Event::Key(key) = event {
+            handle_tool_allow_modal_input(app, key.code, key.modifiers);
+        }
+        return LoopControl::Continue;
+    }
+
+    // Handle re-run modal input (opened from the workers stream)
+    if app.rerun_state.is_some() {
+        if let Event::Key(key) = event {
+            handle_rerun_input(app, key.code);
+        }
+        return LoopControl::Continue;
+    }
+
+    // Handle restore modal input (opened from the workers stream)
+    if app.restore_state.is_some() {
+        if let Event::Key(key) = event {
+            handle_restore_input(app, key.code);
+        }
+        return LoopControl::Continue;
+    }
+
+    // Handle diagnostics modal input
+    if app.diagnostics_state.is_some() {
+        if let Event::Key(key) = event {
+            handle_diagnostics_input(app, key.code);
+        }
+        return LoopControl::Continue;
+    }
+
+    // Handle the reply box for Claude's question
+    if app.question_reply_state.is_some() {
+        if let Event::Key(key) = event {
+            handle_question_reply_input(app, key.code, key.modifiers);
+        }
+        return LoopControl::Continue;
+    }
+
+    // Handle the new tab prompt
+    if app.new_tab_state.is_some() {
+        if let Event::Key(key) = event
+            && let Some(dir) = handle_new_tab_input(app, key.code)
+        {
+            return LoopControl::NewTab(dir);
+        }
+        return LoopControl::Continue;
+    }
+
+    // Handle instruction queue modal input
+    if app.instructions_state.is_some() {
+        if let Event::Key(key) = event {
+            handle_instructions_input(app, key.code, key.modifiers);
+        }
+        return LoopControl::Continue;
+    }
+
+    // Handle workers stream modal input
+    if app.show_workers_stream {
+        if let Event::Key(key) = event {
+            handle_workers_stream_input(app, key.code, key.modifiers);
+        }
+        return LoopControl::Continue;
+    }
+
+    // App-level keys handled before board, then fall through to board input
+    if let Event::Key(key) = event {
+        let board_overlay = app.kanban_board_state.has_overlay();
+        match key.code {
+            KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.new_tab_state = Some(NewTabState::new(&app.project_dir));
+                return LoopControl::Continue;
+            }
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return LoopControl::CloseTab;
+            }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return LoopControl::Detach;
+            }
+            KeyCode::Char(c @ '1'..='9') if tab_count > 1 && !board_overlay => {
+                return LoopControl::SwitchTab(c as usize - '1' as usize);
+            }
+            KeyCode::Char('q') => {
+                if app.status == AppStatus::Running {
+                    app.set_hint("press s to stop the loop");
+                } else {
+                    app.show_quit_modal = true;
+                }
+            }
+            KeyCode::Char('S') if app.deny_in_read_only("Starting the loop") => {}
+            KeyCode::Char('S') => match app.status {
+                AppStatus::Stopped | AppStatus::Error => {
+                    if !app.cancel_held_iterations() {
+                        app.begin_starting_workers();
                     }
                 }
-                continue;
+                AppStatus::Running => {
+                    app.stop_command();
+                }
+                AppStatus::Starting => {}
+            },
+            KeyCode::Char('c') => {
+                app.show_config_modal = true;
+                let project_path = app
+                    .project_config_path
+                    .clone()
+                    .or_else(|| compute_project_config_path(&app.project_dir));
+                let partial = project_path
+                    .as_ref()
+                    .filter(|p| p.exists())
+                    .and_then(|p| load_project_config(p).ok())
+                    .unwrap_or_default();
+                app.config_modal_state = Some(ConfigModalState::from_config(
+                    &partial,
+                    &app.config,
+                    project_path,
+                ));
             }
-
-            // Handle tool allow modal input
-            if app.show_tool_allow_modal {
-                if let Event::Key(key) = event {
-                    handle_tool_allow_modal_input(app, key.code, key.modifiers);
+            KeyCode::Char('i') if app.deny_in_read_only("Init") => {}
+            KeyCode::Char('i') => {
+                app.show_init_modal = true;
+                let mut state = InitModalState::new(&app.project_dir);
+                // Already-scaffolded project: offer to reinstall just what drifted
+                if state.is_partially_initialized() {
+                    state.prepare_repair();
                 }
-                continue;
+                app.init_modal_state = Some(state);
             }
-
-            // Handle workers stream modal input
-            if app.show_workers_stream {
-                if let Event::Key(key) = event {
-                    handle_workers_stream_input(app, key.code, key.modifiers);
+            KeyCode::Char('N' | 'e')
+                if !board_overlay && app.deny_in_read_only("Editing specs and prompts") => {}
+            KeyCode::Char('N') if !board_overlay => {
+                if shaping::shape_skill_installed() {
+                    return LoopControl::ShapeSpec;
                 }
-                continue;
+                app.set_hint("Shape skill not installed \u{2014} press `i` to run init.");
             }
-
-            // App-level keys handled before board, then fall through to board input
-            if let Event::Key(key) = event {
-                match key.code {
-                    KeyCode::Char('q') => {
-                        if app.status == AppStatus::Running {
-                            app.set_hint("press s to stop the loop");
-                        } else {
-                            app.show_quit_modal = true;
-                        }
-                    }
-                    KeyCode::Char('S') => match app.status {
-                        AppStatus::Stopped | AppStatus::Error => {
-                            app.begin_starting_workers();
-                        }
-                        AppStatus::Running => {
-                            app.stop_command();
-                        }
-                        AppStatus::Starting => {}
-                    },
-                    KeyCode::Char('c') => {
-                        app.show_config_modal = true;
-                        let project_path = app
-                            .project_config_path
-                            .clone()
-                            .or_else(compute_project_config_path);
-                        let partial = project_path
-                            .as_ref()
-                            .filter(|p| p.exists())
-                            .and_then(|p| load_project_config(p).ok())
-                            .unwrap_or_default();
-                        app.config_modal_state = Some(ConfigModalState::from_config(
-                            &partial,
-                            &app.config,
-                            project_path,
-                        ));
-                    }
-                    KeyCode::Char('i') => {
-                        app.show_init_modal = true;
-                        app.init_modal_state = Some(InitModalState::new(&app.config));
-                    }
-                    KeyCode::Char('w') if !app.workers.is_empty() => {
-                        app.show_workers_stream = true;
-                        app.workers_stream_state =
-                            Some(WorkersStreamState::new(app.selected_worker));
-                    }
-                    _ => {
-                        handle_kanban_input(app, key.code, key.modifiers);
-                    }
+            KeyCode::Char('e') if !board_overlay => match ensure_project_prompt(&app.project_dir) {
+                Ok(path) => {
+                    app.editor_request = Some(path);
+                    return LoopControl::OpenEditor;
                 }
+                Err(e) => app.set_hint(e.to_string()),
+            },
+            KeyCode::Char('L') => {
+                app.diagnostics_state = Some(DiagnosticsState::new());
+            }
+            KeyCode::Char('K') => {
+                app.toggle_wake_lock();
+            }
+            KeyCode::Char('P') => {
+                app.toggle_pause();
+            }
+            KeyCode::Char('I') if app.deny_in_read_only("Queueing instructions") => {}
+            KeyCode::Char('I') => {
+                app.instructions_state = Some(InstructionsState::default());
+            }
+            KeyCode::Char('C') => {
+                app.toggle_conversation_mode();
+            }
+            KeyCode::Char('a') if app.question_paused => {
+                app.open_question_reply();
+            }
+            KeyCode::Char('R') => match crate::report::build(&app.session_id, &app.config) {
+                Ok(path) => app.set_hint(format!("Report saved: {}", path.display())),
+                Err(e) => app.set_hint(format!("Report failed: {e}")),
+            },
+            KeyCode::Char('w') if !app.workers.is_empty() => {
+                app.show_workers_stream = true;
+                let mut state = WorkersStreamState::new(app.selected_worker);
+                state.wrap = app.stream_wrap;
+                app.workers_stream_state = Some(state);
+            }
+            _ => {
+                handle_kanban_input(app, key.code, key.modifiers);
             }
         }
     }
+
+    LoopControl::Continue
 }
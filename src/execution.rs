@@ -1,60 +1,214 @@
-use std::io::{BufRead, BufReader};
-use std::process::{Command, Stdio};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
 use std::sync::mpsc;
 use std::thread;
 
 use anyhow::Result;
+use ralph_core::output::OutputMessage;
 use tracing::{debug, info};
 
 use crate::agent;
 use crate::app::{App, AppStatus};
-use crate::output;
+use crate::bd::Bd;
+use crate::claude_cli::{self, Capabilities, OutputFormat};
+use crate::control;
+use crate::iteration_hook::HookPhase;
+use crate::pty;
+use crate::spec_prompt::{self, SpecPrompt};
 use crate::templates;
-use crate::wake_lock;
 
-/// Assemble the prompt content and build the shell command string for Claude CLI.
+/// The Claude CLI invocation plus the exact prompt text written to its stdin.
+pub struct AssembledPrompt {
+    /// The Claude CLI binary.
+    pub program: PathBuf,
+    /// Its arguments, passed as-is (no shell in between).
+    pub args: Vec<String>,
+    /// Concatenated prompt content as Claude receives it on stdin.
+    pub context: String,
+    /// Whether Claude's output goes to a PTY (`claude.pty`).
+    pub pty: bool,
+    /// Whether `context` must be sent over the control protocol (Claude then
+    /// reads stream-json stdin instead of the plain prompt).
+    pub control: bool,
+    /// What the Claude CLI can output, which picked the command's args.
+    pub caps: Capabilities,
+    /// Output line about the claimed spec's own prompt, when it names one.
+    pub prompt_note: Option<String>,
+}
+
+/// Assemble the prompt content and the Claude CLI invocation.
 ///
-/// Resolves PROMPT.md from the per-project config dir, falling back to the compiled-in
-/// default. Appends the beads workflow content and optional dirty context, writes temp
-/// files, and returns the full shell command to pipe into Claude.
+/// Uses the claimed spec's own prompt when its front matter names one (see
+/// [`crate::spec_prompt`]), otherwise PROMPT.md from the config dir of `bd`'s
+/// project, falling back to the compiled-in default. Appends the beads workflow content, optional dirty context and any
+/// queued operator `instructions`, and returns the arguments to run Claude
+/// with along with the prompt text to write to its stdin (also the "Prompt
+/// context" preview). With
+/// `resume_session`, Claude continues that conversation instead of starting fresh.
 pub fn assemble_prompt(
     config: &crate::config::Config,
+    bd: &Bd,
     claimed_bead_id: Option<&str>,
     dirty_context: Option<String>,
-) -> Result<String> {
+    instructions: &[String],
+    resume_session: Option<&str>,
+) -> Result<AssembledPrompt> {
     let claude_path = config.claude_path();
-    const CLAUDE_ARGS: &str =
-        "--output-format=stream-json --verbose --print --include-partial-messages";
+    let caps = claude_cli::probe(&claude_path);
+    let mut args: Vec<String> = caps.args().split_whitespace().map(String::from).collect();
+    // Session IDs are UUIDs; anything else is stale or corrupt history
+    if let Some(id) = resume_session.filter(|id| is_session_id(id)) {
+        args.push("--resume".to_string());
+        args.push(id.to_string());
+    }
 
-    // Resolve prompt: per-project config dir PROMPT.md → compiled-in default
-    let prompt_path = if let Some(path) = crate::config::resolve_prompt_path() {
-        path
+    // Resolve prompt: the spec's own prompt → per-project config dir PROMPT.md
+    // → compiled-in default
+    let spec_prompt = claimed_bead_id.and_then(|id| spec_prompt::for_bead(bd, id));
+    let prompt_note = match &spec_prompt {
+        Some(SpecPrompt::Found(path)) => Some(format!("[Spec prompt: {}]", path.display())),
+        Some(SpecPrompt::Missing(path)) => Some(format!(
+            "⚠ Spec prompt {} not found — using PROMPT.md",
+            path.display()
+        )),
+        None => None,
+    };
+    let prompt_content = if let Some(SpecPrompt::Found(path)) = spec_prompt {
+        std::fs::read_to_string(path)?
+    } else if let Some(path) = crate::config::resolve_prompt_path(bd.dir()) {
+        std::fs::read_to_string(path)?
     } else {
-        let path = std::env::temp_dir().join("ralph-prompt.md");
-        std::fs::write(&path, templates::PROMPT_MD)?;
-        path
+        templates::PROMPT_MD.to_string()
     };
 
-    let workflow_path = {
+    let workflow_content = {
         let mut content = templates::beads_workflow(claimed_bead_id);
         if let Some(dirty) = dirty_context {
             content.push('\n');
             content.push_str(&dirty);
         }
-        let path = std::env::temp_dir().join("ralph-beads.md");
-        std::fs::write(&path, &content)?;
-        path
+        if let Some(section) = crate::instruction_queue::prompt_section(instructions) {
+            content.push('\n');
+            content.push_str(&section);
+        }
+        content
     };
 
-    let command = format!(
-        "cat {} {} | {} {}",
-        prompt_path.display(),
-        workflow_path.display(),
-        claude_path.display(),
-        CLAUDE_ARGS
-    );
+    // The control protocol is stream-json in both directions
+    let control = config.control.enabled && caps.format == OutputFormat::StreamJson;
+    if control {
+        args.extend(control::CONTROL_ARGS.split_whitespace().map(String::from));
+    }
+
+    // Written to stdin verbatim, so this is byte-for-byte what Claude sees.
+    let context = format!("{prompt_content}{workflow_content}");
+
+    Ok(AssembledPrompt {
+        program: claude_path,
+        args,
+        pty: config.claude.pty,
+        context,
+        control,
+        caps,
+        prompt_note,
+    })
+}
+
+impl AssembledPrompt {
+    /// Start Claude in its own process group with `cwd` (the worktree) as
+    /// its working directory, and read its output lines on background
+    /// threads. stdout and stderr are pipes, or one PTY with `claude.pty`
+    /// (see [`crate::pty`]). Outside control mode the prompt is written to
+    /// stdin from a background thread, which then closes it so Claude
+    /// starts; in control mode stdin is left for [`control::open`].
+    pub fn spawn(
+        &self,
+        env: Vec<(&'static str, String)>,
+        cwd: Option<&Path>,
+    ) -> io::Result<(Child, mpsc::Receiver<OutputMessage>)> {
+        let pty = if self.pty { Some(pty::open()?) } else { None };
+        let (mut child, master) = crate::bd_lock::with_lock(|| {
+            let mut cmd = Command::new(&self.program);
+            cmd.args(&self.args).stdin(Stdio::piped()).envs(env);
+            let master = match pty {
+                Some(pty) => {
+                    cmd.stdout(pty.slave.try_clone()?).stderr(pty.slave);
+                    Some(pty.master)
+                }
+                None => {
+                    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+                    None
+                }
+            };
+            control::own_process_group(&mut cmd);
+            if let Some(cwd) = cwd {
+                cmd.current_dir(cwd);
+            }
+            // `cmd` holds our copy of the slave; it must be closed for the
+            // master to see EOF, so it goes out of scope here
+            cmd.spawn().map(|child| (child, master))
+        })?;
+        if !self.control
+            && let Some(mut stdin) = child.stdin.take()
+        {
+            let prompt = self.context.clone();
+            thread::spawn(move || {
+                // Claude exiting early closes the pipe; its exit status says why
+                if let Err(e) = stdin.write_all(prompt.as_bytes()) {
+                    debug!(error = %e, "prompt_write_failed");
+                }
+            });
+        }
+
+        let (tx, rx) = mpsc::channel();
+        if let Some(master) = master {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let mut reader = BufReader::new(master);
+                let mut buf = Vec::new();
+                // Reading the master fails with EIO once the child is gone
+                while reader.read_until(b'\n', &mut buf).is_ok_and(|n| n > 0) {
+                    let line = String::from_utf8_lossy(&buf);
+                    let line = pty::clean_line(line.strip_suffix('\n').unwrap_or(&line));
+                    if tx.send(OutputMessage::Line(line)).is_err() {
+                        break;
+                    }
+                    buf.clear();
+                }
+            });
+        }
+        if let Some(stdout) = child.stdout.take() {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let reader = BufReader::new(stdout);
+                for line in reader.lines().map_while(Result::ok) {
+                    if tx.send(OutputMessage::Line(line)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        if let Some(stderr) = child.stderr.take() {
+            thread::spawn(move || {
+                let reader = BufReader::new(stderr);
+                for line in reader.lines().map_while(Result::ok) {
+                    if tx
+                        .send(OutputMessage::Line(format!("[stderr] {}", line)))
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            });
+        }
+        Ok((child, rx))
+    }
+}
 
-    Ok(command)
+/// Whether `id` looks like a Claude CLI session ID (hex digits and dashes).
+fn is_session_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
 }
 
 /// Claim the next available bead before starting claude.
@@ -74,15 +228,12 @@ pub fn claim_before_start(app: &mut App) {
     };
 
     // Auto-reclaim stale beads (priority over new claims)
-    let bd_path = app.config.behavior.bd_path.clone();
-    let stale_agents = agent::find_stale_agents(
-        &bd_path,
-        app.config.behavior.stale_threshold,
-        Some(&agent_id),
-    );
+    let bd = app.bd();
+    let stale_agents =
+        agent::find_stale_agents(&bd, app.config.behavior.stale_threshold, Some(&agent_id));
     if !stale_agents.is_empty() {
         let first = &stale_agents[0];
-        match agent::resume_stale_bead(&bd_path, &agent_id, first) {
+        match agent::resume_stale_bead(&bd, &agent_id, first) {
             agent::ResumeResult::Resumed => {
                 app.add_text_line(format!(
                     "[Auto-reclaimed: {} \"{}\"]",
@@ -91,7 +242,7 @@ pub fn claim_before_start(app: &mut App) {
                 app.workers[w].hooked_bead_id = Some(first.hooked_bead_id.clone());
                 // Release remaining stale agents back to open
                 for stale in stale_agents.iter().skip(1) {
-                    agent::release_stale_bead(&bd_path, stale);
+                    agent::release_stale_bead(&bd, stale);
                     app.add_text_line(format!(
                         "[Released stale: {} \"{}\"]",
                         stale.hooked_bead_id, stale.hooked_bead_title
@@ -106,7 +257,7 @@ pub fn claim_before_start(app: &mut App) {
                 ));
                 // Release remaining stale agents back to open
                 for stale in stale_agents.iter().skip(1) {
-                    agent::release_stale_bead(&bd_path, stale);
+                    agent::release_stale_bead(&bd, stale);
                     app.add_text_line(format!(
                         "[Released stale: {} \"{}\"]",
                         stale.hooked_bead_id, stale.hooked_bead_title
@@ -123,7 +274,7 @@ pub fn claim_before_start(app: &mut App) {
     // If we have an active epic, claim the next child within it
     if let Some(epic_id) = &app.workers[w].claimed_epic_id {
         let epic_id = epic_id.clone();
-        match agent::claim_next_child(&bd_path, &agent_id, &epic_id) {
+        match agent::claim_next_child(&bd, &agent_id, &epic_id) {
             Some((child_id, child_title)) => {
                 app.add_text_line(format!(
                     "[Claimed child: {} {} (epic: {})]",
@@ -145,7 +296,7 @@ pub fn claim_before_start(app: &mut App) {
     }
 
     // Select a new work item (standalone bead or epic's first child)
-    match agent::select_and_claim_work(&bd_path, &agent_id) {
+    match agent::select_and_claim_work(&bd, &agent_id) {
         Some(claim) => {
             match &claim.epic_id {
                 Some(epic_id) => app.add_text_line(format!(
@@ -181,13 +332,32 @@ pub fn start_command(app: &mut App) -> Result<()> {
     let w = app.selected_worker;
 
     // Add divider if not first run
-    if !app.workers[w].output_lines.is_empty() {
-        app.add_text_line("─".repeat(40));
+    let divider = &app.config.display.banners.divider;
+    if !app.workers[w].output_lines.is_empty() && !divider.is_empty() {
+        app.add_text_line(divider.clone());
     }
+    app.begin_iteration_section(w);
 
     // Reset streaming state for new command
     app.workers[w].content_blocks.clear();
     app.workers[w].current_line.clear();
+    app.workers[w].tool_preview_line = None;
+    app.workers[w].iteration_files = Default::default();
+    app.workers[w].claude_session_id = None;
+    app.workers[w].question = None;
+
+    // Claude starts once the before_iteration hook passes (see poll_iteration_hooks)
+    if let Some(command) = app.config.hooks.before_iteration.clone() {
+        app.start_iteration_hook(w, HookPhase::Before, &command);
+        return Ok(());
+    }
+    launch_claude(app)
+}
+
+/// Assemble the prompt and spawn Claude for the selected worker, once its
+/// iteration has been set up by [`start_command`].
+pub fn launch_claude(app: &mut App) -> Result<()> {
+    let w = app.selected_worker;
 
     // Check for dirty worktree (uncommitted changes from previous session)
     let dirty_context = app.workers[w]
@@ -196,85 +366,65 @@ pub fn start_command(app: &mut App) -> Result<()> {
         .and_then(agent::check_worktree_dirty)
         .map(|(status, diff)| agent::build_dirty_worktree_context(&status, &diff));
 
-    let command = assemble_prompt(
+    // Auto-continue: pick up the previous iteration's conversation in continued mode
+    let fresh = std::mem::take(&mut app.workers[w].start_fresh);
+    let resume_session = app.workers[w]
+        .iteration_history
+        .last()
+        .and_then(|r| r.claude_session_id.clone())
+        .filter(|_| app.resume_conversation && !fresh);
+    app.workers[w].continued_conversation = resume_session.is_some();
+    if let Some(id) = &resume_session {
+        app.add_text_line(format!("[Resuming Claude session {id}]"));
+    }
+    let instructions = app.instruction_queue.pending();
+    let assembled = assemble_prompt(
         &app.config,
+        &app.bd(),
         app.workers[w].hooked_bead_id.as_deref(),
         dirty_context,
+        &instructions,
+        resume_session.as_deref(),
     )?;
-    let mut cmd = Command::new("sh");
-    cmd.arg("-c")
-        .arg(&command)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
-
-    // Run claude in the worktree directory
-    if let Some(ref wt_path) = app.workers[w].worktree_path {
-        cmd.current_dir(wt_path);
+    if let Some(note) = &assembled.prompt_note {
+        app.add_text_line(note.clone());
     }
-
-    let child = cmd.spawn();
+    app.add_line(crate::ui::format_prompt_context_styled(
+        &assembled.context,
+        false,
+        &crate::number_format::NumberFormat::from(&app.config.display),
+    ));
+    // Run claude in the worktree directory
+    let child = assembled.spawn(app.config.network.child_env(), Some(&app.worker_dir(w)));
 
     match child {
-        Ok(mut child) => {
+        Ok((mut child, rx)) => {
             // Log command_spawned with PID
             debug!(pid = child.id(), "command_spawned");
 
             // Attempt to acquire wake lock (prevents system idle sleep)
-            let wake_lock = if app.config.behavior.keep_awake {
-                match wake_lock::acquire() {
-                    Some(lock) => Some(lock),
-                    None => {
-                        // Wake lock failed - display warning in output panel
-                        app.add_text_line(
-                            "⚠ Warning: Could not acquire wake lock - system may sleep during execution"
-                                .to_string(),
-                        );
-                        None
-                    }
-                }
-            } else {
-                None
-            };
-            app.wake_lock = wake_lock;
-
-            let (tx, rx) = mpsc::channel();
-
-            // Read stdout in a background thread
-            if let Some(stdout) = child.stdout.take() {
-                let tx_stdout = tx.clone();
-                thread::spawn(move || {
-                    let reader = BufReader::new(stdout);
-                    for line in reader.lines().map_while(Result::ok) {
-                        if tx_stdout.send(output::OutputMessage::Line(line)).is_err() {
-                            break;
-                        }
-                    }
-                });
+            if !app.wake_lock.acquire_if_enabled() {
+                // Wake lock failed - display warning in output panel
+                app.add_text_line(
+                    "⚠ Warning: Could not acquire wake lock - system may sleep during execution"
+                        .to_string(),
+                );
             }
 
-            // Read stderr in a background thread
-            if let Some(stderr) = child.stderr.take() {
-                let tx_stderr = tx.clone();
-                thread::spawn(move || {
-                    let reader = BufReader::new(stderr);
-                    for line in reader.lines().map_while(Result::ok) {
-                        if tx_stderr
-                            .send(output::OutputMessage::Line(format!("[stderr] {}", line)))
-                            .is_err()
-                        {
-                            break;
-                        }
-                    }
-                });
+            if assembled.control {
+                app.workers[w].control = control::open(&mut child, &assembled.context);
             }
-
+            app.mark_prompt_used(w, &assembled.context);
+            app.set_output_capabilities(w, assembled.caps);
+            app.deliver_instructions(instructions.len());
+            app.workers[w].prompt_context = Some(assembled.context);
             app.workers[w].child_process = Some(child);
             app.workers[w].output_receiver = Some(rx);
-            app.workers[w].run_start_time = Some(std::time::Instant::now());
+            app.workers[w].run_start_time = Some(app.clock.now());
         }
         Err(e) => {
             app.status = AppStatus::Error;
-            app.error_at = Some(std::time::Instant::now());
+            app.error_at = Some(app.clock.now());
             app.add_text_line(format!("Error starting command: {}", e));
         }
     }
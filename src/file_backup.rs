@@ -0,0 +1,173 @@
+//! Copies of files taken before Claude's Write/Edit calls change them.
+//!
+//! With `behavior.backup_files` on, the first Write/Edit/MultiEdit/NotebookEdit
+//! touching an existing file in an iteration copies it to
+//! `<project config dir>/backups/<session_id>/` first, so files git can't
+//! recover (untracked configs, `.env` files) can be restored with `b` in the
+//! workers stream. The copy is taken as soon as the tool call is seen, which
+//! is before Claude runs it when `[control]` is enabled and a best effort
+//! otherwise.
+
+use std::path::{Path, PathBuf};
+
+use tracing::{info, warn};
+
+use crate::config::compute_project_config_path;
+
+/// A file copied before a tool call changed it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    /// The file that was changed.
+    pub original: PathBuf,
+    /// Where its previous contents were saved.
+    pub copy: PathBuf,
+    pub worker: usize,
+    pub iteration: u32,
+}
+
+/// This session's backups, oldest first.
+#[derive(Debug)]
+pub struct FileBackups {
+    dir: Option<PathBuf>,
+    entries: Vec<Backup>,
+}
+
+impl FileBackups {
+    /// Backups for `session_id` in the config directory of the project in
+    /// `project_dir`.
+    pub fn new(project_dir: &Path, session_id: &str) -> Self {
+        let dir = compute_project_config_path(project_dir)
+            .and_then(|p| p.parent().map(Path::to_path_buf))
+            .map(|d| d.join("backups").join(session_id));
+        Self::with_dir(dir)
+    }
+
+    fn with_dir(dir: Option<PathBuf>) -> Self {
+        Self {
+            dir,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn entries(&self) -> &[Backup] {
+        &self.entries
+    }
+
+    /// Copy the file a `tool_name` call with `input` is about to change,
+    /// unless it doesn't exist yet or was already copied this iteration.
+    /// Relative paths resolve against `root`.
+    pub fn snapshot_for_tool(
+        &mut self,
+        tool_name: &str,
+        input: &serde_json::Value,
+        root: &Path,
+        worker: usize,
+        iteration: u32,
+    ) {
+        let field = match tool_name {
+            "Write" | "Edit" | "MultiEdit" => "file_path",
+            "NotebookEdit" => "notebook_path",
+            _ => return,
+        };
+        let Some(path) = input.get(field).and_then(|v| v.as_str()) else {
+            return;
+        };
+        self.snapshot(&root.join(path), worker, iteration);
+    }
+
+    fn snapshot(&mut self, original: &Path, worker: usize, iteration: u32) {
+        let Some(dir) = &self.dir else {
+            return;
+        };
+        if !original.is_file()
+            || self
+                .entries
+                .iter()
+                .any(|b| b.original == original && b.worker == worker && b.iteration == iteration)
+        {
+            return;
+        }
+        let name = original
+            .file_name()
+            .map_or_else(|| "file".into(), |n| n.to_string_lossy());
+        let copy = dir.join(format!("{:04}-{name}", self.entries.len() + 1));
+        let copied = std::fs::create_dir_all(dir).and_then(|()| std::fs::copy(original, &copy));
+        match copied {
+            Ok(_) => {
+                info!(original = %original.display(), copy = %copy.display(), "file_backed_up");
+                self.entries.push(Backup {
+                    original: original.to_path_buf(),
+                    copy,
+                    worker,
+                    iteration,
+                });
+            }
+            Err(e) => {
+                warn!(original = %original.display(), error = %e, "file_backup_failed");
+            }
+        }
+    }
+}
+
+/// Copy `backup` back over its original file.
+pub fn restore(backup: &Backup) -> std::io::Result<()> {
+    if let Some(parent) = backup.original.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::copy(&backup.copy, &backup.original)?;
+    info!(original = %backup.original.display(), "file_restored");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn first_edit_per_iteration_is_backed_up_and_restorable() {
+        let project = tempfile::tempdir().unwrap();
+        let store = tempfile::tempdir().unwrap();
+        std::fs::write(project.path().join(".env"), "SECRET=1").unwrap();
+        let mut backups = FileBackups::with_dir(Some(store.path().to_path_buf()));
+
+        let edit = json!({"file_path": ".env", "old_string": "1", "new_string": "2"});
+        backups.snapshot_for_tool("Edit", &edit, project.path(), 0, 1);
+        std::fs::write(project.path().join(".env"), "SECRET=2").unwrap();
+        backups.snapshot_for_tool("Edit", &edit, project.path(), 0, 1);
+        assert_eq!(backups.entries().len(), 1);
+
+        backups.snapshot_for_tool("Write", &json!({"file_path": ".env"}), project.path(), 0, 2);
+        assert_eq!(backups.entries().len(), 2);
+
+        restore(&backups.entries()[0]).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(project.path().join(".env")).unwrap(),
+            "SECRET=1"
+        );
+    }
+
+    #[test]
+    fn new_files_and_other_tools_are_skipped() {
+        let project = tempfile::tempdir().unwrap();
+        let store = tempfile::tempdir().unwrap();
+        std::fs::write(project.path().join("notes.txt"), "x").unwrap();
+        let mut backups = FileBackups::with_dir(Some(store.path().to_path_buf()));
+
+        backups.snapshot_for_tool(
+            "Write",
+            &json!({"file_path": "new.rs"}),
+            project.path(),
+            0,
+            1,
+        );
+        backups.snapshot_for_tool(
+            "Read",
+            &json!({"file_path": "notes.txt"}),
+            project.path(),
+            0,
+            1,
+        );
+        assert!(backups.entries().is_empty());
+    }
+}
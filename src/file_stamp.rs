@@ -0,0 +1,72 @@
+//! Detect another writer changing a file between ralph reading and rewriting it.
+//!
+//! ralph rewrites a few files Claude may also edit mid-run (`.claude/settings*.json`).
+//! Callers take a stamp (mtime + content hash) before reading, and check it
+//! again just before writing. If it moved, they re-read and re-apply their
+//! change instead of overwriting the agent's edit, giving up after
+//! [`MAX_ATTEMPTS`].
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Read-modify-write attempts before reporting a conflict.
+pub const MAX_ATTEMPTS: u32 = 3;
+
+/// A file's modification time and content hash; both `None` if it doesn't exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStamp {
+    mtime: Option<SystemTime>,
+    hash: Option<u64>,
+}
+
+impl FileStamp {
+    /// Stamp the file at `path` as it is now.
+    pub fn of(path: &Path) -> io::Result<Self> {
+        let contents = match std::fs::read(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Ok(Self {
+                    mtime: None,
+                    hash: None,
+                });
+            }
+            Err(e) => return Err(e),
+        };
+        let mut hasher = DefaultHasher::new();
+        contents.hash(&mut hasher);
+        Ok(Self {
+            mtime: std::fs::metadata(path).and_then(|m| m.modified()).ok(),
+            hash: Some(hasher.finish()),
+        })
+    }
+
+    /// Whether the file at `path` is unchanged since this stamp was taken.
+    pub fn still_matches(&self, path: &Path) -> io::Result<bool> {
+        Ok(Self::of(path)? == *self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stamp_tracks_content_and_existence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+
+        let missing = FileStamp::of(&path).unwrap();
+        assert!(missing.still_matches(&path).unwrap());
+
+        std::fs::write(&path, "{}").unwrap();
+        assert!(!missing.still_matches(&path).unwrap());
+
+        let stamp = FileStamp::of(&path).unwrap();
+        assert!(stamp.still_matches(&path).unwrap());
+        std::fs::write(&path, "{\"a\": 1}").unwrap();
+        assert!(!stamp.still_matches(&path).unwrap());
+    }
+}
@@ -0,0 +1,196 @@
+//! Files an iteration created, modified or deleted, derived from its
+//! successful tool calls and summarized before the usage summary.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// What an iteration did to one file, net of all its tool calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChange {
+    Created,
+    Modified,
+    Deleted,
+}
+
+/// Net file changes for the current iteration, keyed by path.
+#[derive(Debug, Default)]
+pub struct FileTouches(BTreeMap<String, FileChange>);
+
+impl FileTouches {
+    /// Record a successful tool call. `result` is the tool result text, which
+    /// tells a Write that created its file apart from one that overwrote it.
+    pub fn record_tool(&mut self, tool_name: &str, input_json: &str, result: &str) {
+        let Ok(input) = serde_json::from_str::<serde_json::Value>(input_json) else {
+            return;
+        };
+        let field = |name: &str| input.get(name).and_then(|v| v.as_str());
+        match tool_name {
+            "Write" => {
+                if let Some(path) = field("file_path") {
+                    let change = if result.starts_with("File created") {
+                        FileChange::Created
+                    } else {
+                        FileChange::Modified
+                    };
+                    self.record(path, change);
+                }
+            }
+            "Edit" | "MultiEdit" => {
+                if let Some(path) = field("file_path") {
+                    self.record(path, FileChange::Modified);
+                }
+            }
+            "NotebookEdit" => {
+                if let Some(path) = field("notebook_path") {
+                    self.record(path, FileChange::Modified);
+                }
+            }
+            "Bash" => {
+                for path in field("command").map(removed_paths).unwrap_or_default() {
+                    self.record(&path, FileChange::Deleted);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Fold `change` into what's already known about `path`.
+    fn record(&mut self, path: &str, change: FileChange) {
+        use FileChange::*;
+        let net = match (self.0.get(path), change) {
+            (None, change) => Some(change),
+            (Some(Created), Modified) => Some(Created),
+            (Some(Created), Deleted) => None,
+            (Some(Deleted), Created | Modified) => Some(Modified),
+            (Some(_), change) => Some(change),
+        };
+        match net {
+            Some(net) => self.0.insert(path.to_string(), net),
+            None => self.0.remove(path),
+        };
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// A count line followed by one line per file (`+` created, `~` modified,
+    /// `-` deleted), with paths shown relative to `root` where possible.
+    pub fn summary_lines(&self, root: Option<&Path>) -> Vec<String> {
+        let count = |kind| self.0.values().filter(|c| **c == kind).count();
+        let counts = [
+            (FileChange::Created, "created"),
+            (FileChange::Modified, "modified"),
+            (FileChange::Deleted, "deleted"),
+        ]
+        .into_iter()
+        .filter_map(|(kind, label)| match count(kind) {
+            0 => None,
+            n => Some(format!("{n} {label}")),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+        let mut lines = vec![format!("Files: {counts}")];
+        for (path, change) in &self.0 {
+            let sign = match change {
+                FileChange::Created => '+',
+                FileChange::Modified => '~',
+                FileChange::Deleted => '-',
+            };
+            lines.push(format!("  {sign} {}", relative(path, root)));
+        }
+        lines
+    }
+
+    /// Changed paths relative to `root` where possible, created and modified
+    /// files ahead of deleted ones.
+    pub fn changed_paths(&self, root: Option<&Path>) -> Vec<String> {
+        let (deleted, kept): (Vec<_>, Vec<_>) = self
+            .0
+            .iter()
+            .partition(|(_, change)| **change == FileChange::Deleted);
+        kept.into_iter()
+            .chain(deleted)
+            .map(|(path, _)| relative(path, root))
+            .collect()
+    }
+}
+
+fn relative(path: &str, root: Option<&Path>) -> String {
+    root.and_then(|root| Path::new(path).strip_prefix(root).ok())
+        .map_or_else(|| path.to_string(), |p| p.display().to_string())
+}
+
+/// Paths removed by `rm` / `git rm` in a shell command. Only plain words are
+/// considered; globs and variables can't be resolved without the shell.
+fn removed_paths(command: &str) -> Vec<String> {
+    command
+        .split(['\n', ';', '|', '&'])
+        .flat_map(|segment| {
+            let words: Vec<&str> = segment.split_whitespace().collect();
+            let args = match words.as_slice() {
+                ["rm", args @ ..] | ["git", "rm", args @ ..] => args,
+                _ => &[],
+            };
+            args.iter()
+                .filter(|a| !a.starts_with('-') && !a.contains(['*', '?', '$']))
+                .map(|a| a.trim_matches(['\'', '"']).to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &str) -> String {
+        serde_json::json!({"file_path": path, "content": "x"}).to_string()
+    }
+
+    #[test]
+    fn nets_out_changes_per_file() {
+        let mut touches = FileTouches::default();
+        touches.record_tool(
+            "Write",
+            &write("/r/new.rs"),
+            "File created successfully at: /r/new.rs",
+        );
+        touches.record_tool("Edit", &write("/r/new.rs"), "ok");
+        touches.record_tool(
+            "Write",
+            &write("/r/old.rs"),
+            "The file /r/old.rs has been updated",
+        );
+        touches.record_tool("Write", &write("/r/tmp.txt"), "File created successfully");
+        touches.record_tool(
+            "Bash",
+            r#"{"command": "rm -f /r/tmp.txt /r/gone.rs && cargo build"}"#,
+            "",
+        );
+
+        assert_eq!(
+            touches.summary_lines(Some(Path::new("/r"))),
+            vec![
+                "Files: 1 created, 1 modified, 1 deleted",
+                "  - gone.rs",
+                "  + new.rs",
+                "  ~ old.rs",
+            ]
+        );
+        assert_eq!(
+            touches.changed_paths(Some(Path::new("/r"))),
+            vec!["new.rs", "old.rs", "gone.rs"]
+        );
+    }
+
+    #[test]
+    fn removed_paths_skips_flags_and_globs() {
+        assert_eq!(
+            removed_paths("git rm -r src/old; rm *.log 'x.txt'\nls"),
+            vec!["src/old", "x.txt"]
+        );
+        assert!(removed_paths("cargo rm foo").is_empty());
+    }
+}
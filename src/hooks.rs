@@ -0,0 +1,260 @@
+//! User hook scripts, run on lifecycle events.
+//!
+//! An executable in `~/.config/ralph/hooks/` named after an event (e.g.
+//! `on-spec-done`) is run whenever that event fires, with a JSON payload on
+//! stdin. Hooks run in the background so a slow script never stalls the UI;
+//! failures (non-zero exit, timeout) come back as messages for the caller to
+//! surface as hints.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+use tracing::{debug, warn};
+
+/// How long a hook may run before it is killed and reported as failed.
+const HOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Why a hook run failed.
+#[derive(Debug, Error)]
+enum HookError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("timed out after {}s", .0.as_secs())]
+    TimedOut(Duration),
+    #[error("{0}")]
+    Exited(ExitStatus),
+    /// Exited unsuccessfully, with the last line it wrote to stderr.
+    #[error("{status}: {detail}")]
+    ExitedWith { status: ExitStatus, detail: String },
+}
+
+/// Lifecycle events a hook can be attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    /// An iteration's result event arrived.
+    IterationEnd,
+    /// A claimed bead was found closed.
+    SpecDone,
+    /// The only ready beads left are for humans.
+    Blocked,
+    /// Claude issued a tool call.
+    ToolCall,
+}
+
+impl HookEvent {
+    /// Executable name for this event, also sent as the payload's `event` field.
+    pub fn name(self) -> &'static str {
+        match self {
+            HookEvent::IterationEnd => "on-iteration-end",
+            HookEvent::SpecDone => "on-spec-done",
+            HookEvent::Blocked => "on-blocked",
+            HookEvent::ToolCall => "on-tool-call",
+        }
+    }
+}
+
+/// Default hooks directory: `~/.config/ralph/hooks`.
+pub fn hooks_dir() -> Option<PathBuf> {
+    if let Some(sandbox) = crate::sandbox::get() {
+        return Some(sandbox.config_dir().join("hooks"));
+    }
+    dirs::home_dir().map(|home| home.join(".config").join("ralph").join("hooks"))
+}
+
+/// Runs hook scripts and collects their failures.
+pub struct Hooks {
+    dir: Option<PathBuf>,
+    tx: Sender<String>,
+    rx: Receiver<String>,
+}
+
+impl Hooks {
+    pub fn new() -> Self {
+        Self::with_dir(hooks_dir())
+    }
+
+    fn with_dir(dir: Option<PathBuf>) -> Self {
+        let (tx, rx) = channel();
+        Self { dir, tx, rx }
+    }
+
+    /// Run the hook for `event`, if one is installed, with `payload` on stdin.
+    ///
+    /// `payload` should be a JSON object; its `event` field is set here.
+    pub fn fire(&self, event: HookEvent, mut payload: serde_json::Value) {
+        let Some(path) = self
+            .dir
+            .as_ref()
+            .map(|dir| dir.join(event.name()))
+            .filter(|path| path.is_file())
+        else {
+            return;
+        };
+        payload["event"] = serde_json::Value::from(event.name());
+        let input = payload.to_string().into_bytes();
+        let tx = self.tx.clone();
+        debug!(hook = event.name(), "hook_fired");
+        thread::spawn(move || {
+            if let Err(e) = run_hook(&path, input) {
+                warn!(hook = event.name(), error = %e, "hook_failed");
+                let _ = tx.send(format!("Hook {} failed: {e}", event.name()));
+            }
+        });
+    }
+
+    /// Failure messages from hooks that finished since the last call.
+    pub fn failures(&self) -> Vec<String> {
+        self.rx.try_iter().collect()
+    }
+}
+
+/// Run one hook to completion, feeding it `input` on stdin.
+fn run_hook(path: &Path, input: Vec<u8>) -> Result<(), HookError> {
+    let mut child = Command::new("sh")
+        .args(["-c", "exec \"$0\""])
+        .arg(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Write from a separate thread: a hook that never reads stdin must not
+    // wedge us on a full pipe (a broken pipe when it exits early is fine).
+    if let Some(mut stdin) = child.stdin.take() {
+        thread::spawn(move || {
+            let _ = stdin.write_all(&input);
+        });
+    }
+    // Drain stderr as it arrives too: a chatty hook would otherwise fill the
+    // pipe and block until the timeout.
+    let stderr = child
+        .stderr
+        .take()
+        .map(|pipe| thread::spawn(|| last_line(pipe)));
+
+    let deadline = Instant::now() + HOOK_TIMEOUT;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(HookError::TimedOut(HOOK_TIMEOUT));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(e.into()),
+        }
+    };
+    if status.success() {
+        return Ok(());
+    }
+
+    match stderr.and_then(|reader| reader.join().ok()).flatten() {
+        Some(detail) => Err(HookError::ExitedWith { status, detail }),
+        None => Err(HookError::Exited(status)),
+    }
+}
+
+/// The last non-blank line of `stream`, read to the end.
+fn last_line(stream: impl Read) -> Option<String> {
+    let mut last = None;
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else { break };
+        if !line.trim().is_empty() {
+            last = Some(line.trim().to_string());
+        }
+    }
+    last
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn install(dir: &Path, event: HookEvent, script: &str) {
+        let path = dir.join(event.name());
+        std::fs::write(&path, script).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn hook_receives_payload_on_stdin() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("payload.json");
+        install(
+            dir.path(),
+            HookEvent::SpecDone,
+            &format!("#!/bin/sh\ncat > '{}'\n", out.display()),
+        );
+
+        run_hook(
+            &dir.path().join("on-spec-done"),
+            br#"{"bead_id":"bd-1"}"#.to_vec(),
+        )
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            r#"{"bead_id":"bd-1"}"#
+        );
+    }
+
+    #[test]
+    fn failing_hook_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        install(
+            dir.path(),
+            HookEvent::Blocked,
+            "#!/bin/sh\necho 'webhook down' >&2\nexit 3\n",
+        );
+        let hooks = Hooks::with_dir(Some(dir.path().to_path_buf()));
+
+        hooks.fire(HookEvent::Blocked, serde_json::json!({}));
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let failures = loop {
+            let failures = hooks.failures();
+            if !failures.is_empty() || Instant::now() >= deadline {
+                break failures;
+            }
+            thread::sleep(Duration::from_millis(20));
+        };
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].starts_with("Hook on-blocked failed:"));
+        assert!(failures[0].ends_with("webhook down"));
+    }
+
+    #[test]
+    fn chatty_hook_does_not_block() {
+        let dir = tempfile::tempdir().unwrap();
+        install(
+            dir.path(),
+            HookEvent::Blocked,
+            "#!/bin/sh
+seq 1 100000 >&2
+echo 'last words' >&2
+exit 1
+",
+        );
+        let started = Instant::now();
+        let err = run_hook(&dir.path().join("on-blocked"), Vec::new()).unwrap_err();
+        assert!(
+            matches!(&err, HookError::ExitedWith { detail, .. } if detail == "last words"),
+            "{err}"
+        );
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn missing_hook_is_a_no_op() {
+        let dir = tempfile::tempdir().unwrap();
+        let hooks = Hooks::with_dir(Some(dir.path().to_path_buf()));
+        hooks.fire(HookEvent::ToolCall, serde_json::json!({}));
+        thread::sleep(Duration::from_millis(50));
+        assert!(hooks.failures().is_empty());
+    }
+}
@@ -0,0 +1,143 @@
+//! One-off instructions typed while a run is in progress (`I`).
+//!
+//! Notes like "skip bd-12, do bd-15 first" or "the staging DB is down" wait in
+//! the queue until the next iteration starts, are appended to its prompt, and
+//! are then marked delivered. Steering without stopping the loop.
+
+use tracing::info;
+
+use crate::app::App;
+
+/// An instruction and the loop it was sent with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedInstruction {
+    pub text: String,
+    /// Loop number of the iteration whose prompt carried it.
+    pub delivered_in: Option<u64>,
+}
+
+/// Instructions for upcoming iterations, oldest first.
+#[derive(Debug, Default)]
+pub struct InstructionQueue {
+    items: Vec<QueuedInstruction>,
+}
+
+impl InstructionQueue {
+    /// Queue `text` for the next iteration. Blank text is ignored.
+    pub fn push(&mut self, text: &str) -> bool {
+        let text = text.trim();
+        if text.is_empty() {
+            return false;
+        }
+        self.items.push(QueuedInstruction {
+            text: text.to_string(),
+            delivered_in: None,
+        });
+        true
+    }
+
+    pub fn items(&self) -> &[QueuedInstruction] {
+        &self.items
+    }
+
+    /// Texts still waiting for an iteration, oldest first.
+    pub fn pending(&self) -> Vec<String> {
+        self.items
+            .iter()
+            .filter(|i| i.delivered_in.is_none())
+            .map(|i| i.text.clone())
+            .collect()
+    }
+
+    /// Drop the newest instruction that hasn't been delivered yet.
+    pub fn remove_last_pending(&mut self) -> Option<String> {
+        let index = self.items.iter().rposition(|i| i.delivered_in.is_none())?;
+        Some(self.items.remove(index).text)
+    }
+
+    /// Mark the oldest `count` pending instructions as sent with loop `loop_number`.
+    /// Anything queued after the prompt was assembled stays pending.
+    pub fn mark_delivered(&mut self, count: usize, loop_number: u64) {
+        for item in self
+            .items
+            .iter_mut()
+            .filter(|i| i.delivered_in.is_none())
+            .take(count)
+        {
+            item.delivered_in = Some(loop_number);
+        }
+    }
+}
+
+impl App {
+    /// Record that the oldest `count` queued instructions went out with the
+    /// iteration that just started.
+    pub fn deliver_instructions(&mut self, count: usize) {
+        if count == 0 {
+            return;
+        }
+        self.instruction_queue
+            .mark_delivered(count, self.loop_count);
+        info!(
+            count,
+            loop_number = self.loop_count,
+            "instructions_delivered"
+        );
+        let noun = if count == 1 {
+            "instruction"
+        } else {
+            "instructions"
+        };
+        self.add_text_line(format!("[Delivered {count} queued {noun}]"));
+    }
+}
+
+/// Prompt section carrying `instructions`, or `None` when there are none.
+pub fn prompt_section(instructions: &[String]) -> Option<String> {
+    if instructions.is_empty() {
+        return None;
+    }
+    let mut section = "## Instructions From the Operator\n\n\
+                       The person running Ralph queued these notes for this iteration. \
+                       They take precedence over conflicting guidance above.\n\n"
+        .to_string();
+    for text in instructions {
+        section.push_str(&format!("- {text}\n"));
+    }
+    Some(section)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delivers_only_what_was_pending_at_assembly() {
+        let mut queue = InstructionQueue::default();
+        assert!(queue.push("  skip bd-12  "));
+        assert!(!queue.push("   "));
+        let sent = queue.pending();
+        assert_eq!(sent, vec!["skip bd-12"]);
+
+        // Typed while the next iteration was starting
+        queue.push("staging DB is down");
+        queue.mark_delivered(sent.len(), 4);
+        assert_eq!(queue.items()[0].delivered_in, Some(4));
+        assert_eq!(queue.pending(), vec!["staging DB is down"]);
+
+        assert_eq!(
+            queue.remove_last_pending().as_deref(),
+            Some("staging DB is down")
+        );
+        assert_eq!(queue.remove_last_pending(), None);
+        assert_eq!(queue.items().len(), 1);
+    }
+
+    #[test]
+    fn prompt_section_lists_instructions() {
+        assert_eq!(prompt_section(&[]), None);
+        let section = prompt_section(&["skip bd-12".into(), "prioritize bd-15".into()]).unwrap();
+        assert!(section.starts_with("## Instructions From the Operator"));
+        assert!(section.ends_with("- skip bd-12\n- prioritize bd-15\n"));
+    }
+}
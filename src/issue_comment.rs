@@ -0,0 +1,360 @@
+//! Comment on a spec's linked issue when a run ends in error
+//! (`[tracker] comment_on_failure`).
+//!
+//! A spec is linked through its bead's `external_ref` (`bd update <id>
+//! --external-ref gh-123`): `gh-123`, `#123`, `123` or a GitHub issue URL. The
+//! comment carries the tail of the worker's output, the last failed tool
+//! results and the transcript path, and is posted with the `gh` CLI using the
+//! account `gh auth login` set up. Overnight failures then show up on the
+//! ticket instead of waiting in a terminal.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use ratatui::text::Line;
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::app::{App, AppStatus};
+use crate::bd::Bd;
+use crate::ui::truncate_to_width;
+
+/// Output lines quoted as the failure summary.
+const SUMMARY_LINES: usize = 15;
+/// Failed tool results kept per worker.
+const MAX_TOOL_ERRORS: usize = 5;
+/// Display width each failed tool result is cut to.
+const TOOL_ERROR_WIDTH: usize = 200;
+
+/// Why looking up the linked issue or commenting on it failed.
+#[derive(Debug, Error)]
+enum IssueCommentError {
+    #[error("{0}")]
+    Bd(std::io::Error),
+    #[error("{0}")]
+    BdJson(#[from] serde_json::Error),
+    #[error("could not run gh: {0}")]
+    Gh(std::io::Error),
+    /// bd or gh exited unsuccessfully; holds what it wrote to stderr.
+    #[error("{0}")]
+    Failed(String),
+}
+
+impl IssueCommentError {
+    fn from_stderr(stderr: &[u8]) -> Self {
+        Self::Failed(String::from_utf8_lossy(stderr).trim().to_string())
+    }
+}
+
+/// Tracks entering the error state and the comments being posted.
+#[derive(Debug, Default)]
+pub struct FailureComments {
+    /// Whether the app was in the error state at the last check.
+    was_error: bool,
+    /// Outcome lines from the thread posting the comments.
+    rx: Option<Receiver<String>>,
+}
+
+/// An issue an `external_ref` points at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueRef {
+    /// `owner/repo`; `None` means the repository `gh` finds in the project.
+    pub repo: Option<String>,
+    pub number: u64,
+}
+
+impl IssueRef {
+    /// `owner/repo#12` or `#12`.
+    pub fn label(&self) -> String {
+        format!("{}#{}", self.repo.as_deref().unwrap_or(""), self.number)
+    }
+}
+
+/// The GitHub issue `external_ref` names, if it names one.
+pub fn parse_external_ref(external_ref: &str) -> Option<IssueRef> {
+    let external_ref = external_ref.trim();
+    if let Some(rest) = external_ref
+        .strip_prefix("https://github.com/")
+        .or_else(|| external_ref.strip_prefix("http://github.com/"))
+    {
+        let parts: Vec<&str> = rest.trim_end_matches('/').split('/').collect();
+        return match parts.as_slice() {
+            [owner, repo, "issues", number] => Some(IssueRef {
+                repo: Some(format!("{owner}/{repo}")),
+                number: number.parse().ok()?,
+            }),
+            _ => None,
+        };
+    }
+    let number = external_ref
+        .strip_prefix("gh-")
+        .or_else(|| external_ref.strip_prefix('#'))
+        .unwrap_or(external_ref);
+    Some(IssueRef {
+        repo: None,
+        number: number.parse().ok()?,
+    })
+}
+
+/// Markdown body of the comment for a failed run on `bead_id`.
+pub fn failure_comment(
+    bead_id: &str,
+    summary: &[String],
+    tool_errors: &[String],
+    transcript: Option<&Path>,
+) -> String {
+    let mut body = format!("**Ralph run failed** while working `{bead_id}`.\n\n");
+    if !summary.is_empty() {
+        body.push_str("Last output:\n\n```text\n");
+        for line in summary {
+            body.push_str(line);
+            body.push('\n');
+        }
+        body.push_str("```\n\n");
+    }
+    if !tool_errors.is_empty() {
+        body.push_str("Last tool errors:\n\n");
+        for error in tool_errors {
+            body.push_str(&format!("- `{}`\n", error.replace('`', "'")));
+        }
+        body.push('\n');
+    }
+    if let Some(path) = transcript {
+        body.push_str(&format!("Transcript: `{}`\n", path.display()));
+    }
+    body
+}
+
+/// Last `n` non-blank lines of `lines` as plain text.
+fn tail_text(lines: &[Line], n: usize) -> Vec<String> {
+    let mut tail: Vec<String> = lines
+        .iter()
+        .rev()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .filter(|text| !text.trim().is_empty())
+        .take(n)
+        .collect();
+    tail.reverse();
+    tail
+}
+
+/// The bead's `external_ref`, read with `bd show`.
+fn external_ref(bd: &Bd, bead_id: &str) -> Result<Option<String>, IssueCommentError> {
+    let output = crate::bd_lock::with_lock(|| {
+        bd.command()
+            .args(["show", bead_id, "--json"])
+            .stdin(Stdio::null())
+            .output()
+    })
+    .map_err(IssueCommentError::Bd)?;
+    if !output.status.success() {
+        return Err(IssueCommentError::from_stderr(&output.stderr));
+    }
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let bead = value.as_array().and_then(|a| a.first()).unwrap_or(&value);
+    Ok(bead
+        .get("external_ref")
+        .and_then(|r| r.as_str())
+        .filter(|r| !r.is_empty())
+        .map(str::to_string))
+}
+
+/// Post `body` on `issue` with `gh issue comment`.
+fn post(issue: &IssueRef, body: &str) -> Result<(), IssueCommentError> {
+    let number = issue.number.to_string();
+    let mut args = vec!["issue", "comment", number.as_str(), "--body", body];
+    if let Some(repo) = &issue.repo {
+        args.extend(["--repo", repo.as_str()]);
+    }
+    let output = Command::new("gh")
+        .args(&args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(IssueCommentError::Gh)?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(IssueCommentError::from_stderr(&output.stderr))
+    }
+}
+
+/// Comment on `bead_id`'s linked issue. Returns the line for the output, or
+/// `None` when the bead isn't linked to a GitHub issue.
+fn comment_on_linked_issue(
+    bd: &Bd,
+    bead_id: &str,
+    build_body: impl FnOnce() -> String,
+) -> Option<String> {
+    let external_ref = match external_ref(bd, bead_id) {
+        Ok(Some(external_ref)) => external_ref,
+        Ok(None) => return None,
+        Err(e) => {
+            warn!(bead_id, error = %e, "failure_comment_lookup_failed");
+            return Some(format!("[Could not read {bead_id}'s linked issue: {e}]"));
+        }
+    };
+    let Some(issue) = parse_external_ref(&external_ref) else {
+        info!(bead_id, external_ref, "failure_comment_unsupported_ref");
+        return None;
+    };
+    Some(match post(&issue, &build_body()) {
+        Ok(()) => {
+            info!(bead_id, issue = issue.label(), "failure_comment_posted");
+            format!("[Posted the failure on issue {}]", issue.label())
+        }
+        Err(e) => {
+            warn!(bead_id, issue = issue.label(), error = %e, "failure_comment_failed");
+            format!("[Could not comment on issue {}: {e}]", issue.label())
+        }
+    })
+}
+
+impl App {
+    /// Note a failed tool result on worker `w` for the failure comment.
+    pub fn record_tool_error(&mut self, w: usize, tool_name: &str, content: &str) {
+        let first_line = content.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+        let errors = &mut self.workers[w].recent_tool_errors;
+        errors.push(format!(
+            "{tool_name}: {}",
+            truncate_to_width(first_line.trim(), TOOL_ERROR_WIDTH, "\u{2026}")
+        ));
+        if errors.len() > MAX_TOOL_ERRORS {
+            errors.remove(0);
+        }
+    }
+
+    /// On entering the error state, comment on the issues linked to the
+    /// specs being worked; then show how posting went.
+    pub fn check_failure_comments(&mut self) {
+        let is_error = self.status == AppStatus::Error;
+        let entered = is_error && !self.failure_comments.was_error;
+        self.failure_comments.was_error = is_error;
+        if entered && self.config.tracker.comment_on_failure {
+            self.post_failure_comments();
+        }
+
+        let Some(rx) = &self.failure_comments.rx else {
+            return;
+        };
+        let mut lines = Vec::new();
+        let done = loop {
+            match rx.try_recv() {
+                Ok(line) => lines.push(line),
+                Err(TryRecvError::Empty) => break false,
+                Err(TryRecvError::Disconnected) => break true,
+            }
+        };
+        if done {
+            self.failure_comments.rx = None;
+        }
+        for line in lines {
+            self.add_text_line(line);
+        }
+    }
+
+    fn post_failure_comments(&mut self) {
+        let transcript = self.transcript.as_ref().map(|t| t.path().to_path_buf());
+        let failed: Vec<(String, Vec<String>, Vec<String>)> = self
+            .workers
+            .iter()
+            .filter_map(|worker| {
+                let bead_id = worker.hooked_bead_id.clone()?;
+                Some((
+                    bead_id,
+                    tail_text(&worker.output_lines, SUMMARY_LINES),
+                    worker.recent_tool_errors.clone(),
+                ))
+            })
+            .collect();
+        if failed.is_empty() {
+            return;
+        }
+        let bd = self.bd();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for (bead_id, summary, tool_errors) in failed {
+                let line = comment_on_linked_issue(&bd, &bead_id, || {
+                    failure_comment(&bead_id, &summary, &tool_errors, transcript.as_deref())
+                });
+                if let Some(line) = line {
+                    let _ = tx.send(line);
+                }
+            }
+        });
+        self.failure_comments.rx = Some(rx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LoadedConfig;
+
+    #[test]
+    fn parse_external_ref_forms() {
+        let local = |number| Some(IssueRef { repo: None, number });
+        assert_eq!(parse_external_ref("gh-123"), local(123));
+        assert_eq!(parse_external_ref("#7"), local(7));
+        assert_eq!(parse_external_ref(" 42 "), local(42));
+        assert_eq!(
+            parse_external_ref("https://github.com/cmoel/ralph/issues/9/"),
+            Some(IssueRef {
+                repo: Some("cmoel/ralph".into()),
+                number: 9
+            })
+        );
+        assert_eq!(
+            parse_external_ref("https://github.com/cmoel/ralph/pull/9"),
+            None
+        );
+        assert_eq!(parse_external_ref("jira-PROJ-12"), None);
+        assert_eq!(parse_external_ref(""), None);
+    }
+
+    #[test]
+    fn failure_comment_lists_output_errors_and_transcript() {
+        let body = failure_comment(
+            "bd-12",
+            &["[Error: overloaded]".into()],
+            &["Bash: `cargo` not found".into()],
+            Some(Path::new("/logs/transcripts/s1.log")),
+        );
+        assert!(body.starts_with("**Ralph run failed** while working `bd-12`."));
+        assert!(body.contains("```text\n[Error: overloaded]\n```"));
+        assert!(body.contains("- `Bash: 'cargo' not found`"));
+        assert!(body.ends_with("Transcript: `/logs/transcripts/s1.log`\n"));
+
+        let bare = failure_comment("bd-12", &[], &[], None);
+        assert!(!bare.contains("Last"));
+    }
+
+    #[test]
+    fn tool_errors_and_output_tail_are_capped() {
+        let mut app = App::new("test".into(), None, LoadedConfig::default_for_test(), None);
+        for i in 0..7 {
+            app.record_tool_error(0, "Bash", &format!("\nfailure {i}\nmore"));
+        }
+        let errors = &app.workers[0].recent_tool_errors;
+        assert_eq!(errors.len(), MAX_TOOL_ERRORS);
+        assert_eq!(errors.last().unwrap(), "Bash: failure 6");
+
+        let lines: Vec<Line> = ["a", "", "b", "c"].into_iter().map(Line::raw).collect();
+        assert_eq!(tail_text(&lines, 2), vec!["b", "c"]);
+    }
+
+    #[test]
+    fn entering_error_posts_nothing_when_disabled() {
+        let mut app = App::new("test".into(), None, LoadedConfig::default_for_test(), None);
+        app.workers[0].hooked_bead_id = Some("bd-1".into());
+        app.status = AppStatus::Error;
+        app.check_failure_comments();
+        assert!(app.failure_comments.was_error);
+        assert!(app.failure_comments.rx.is_none());
+    }
+}
@@ -0,0 +1,259 @@
+//! `[hooks]` commands run around each iteration.
+//!
+//! `before_iteration` (e.g. `git pull`) runs before Claude starts and a
+//! non-zero exit aborts the iteration; `after_iteration` (e.g. `cargo test`)
+//! runs once an iteration ends and holds auto-continue until it is done.
+//! Unlike the event scripts in [`crate::hooks`], these are part of the loop:
+//! they run with `sh -c` in the worker's directory, the loop waits for them,
+//! and their output goes to the main panel as it arrives. One that runs past
+//! `hooks.timeout_secs` is killed and counts as failed.
+
+use std::path::Path;
+use std::process::{Child, ExitStatus};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::shell_command::{self, OutputLine};
+
+/// How long to keep reading output after the command exits, in case
+/// something it started in the background still holds the pipes open.
+const DRAIN_GRACE: Duration = Duration::from_secs(1);
+
+/// When a hook runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookPhase {
+    Before,
+    After,
+}
+
+impl HookPhase {
+    /// Config key, used to label the hook's output.
+    pub fn name(self) -> &'static str {
+        match self {
+            HookPhase::Before => "before_iteration",
+            HookPhase::After => "after_iteration",
+        }
+    }
+}
+
+/// Why a hook command failed.
+#[derive(Debug, Error)]
+pub enum IterationHookError {
+    #[error("{0}")]
+    Spawn(std::io::Error),
+    #[error("timed out after {}s", .0.as_secs())]
+    TimedOut(Duration),
+    #[error("{0}")]
+    Wait(std::io::Error),
+    #[error("exit code {0}")]
+    ExitCode(i32),
+    #[error("killed by a signal")]
+    Signal,
+}
+
+/// A running hook command.
+#[derive(Debug)]
+pub struct IterationHook {
+    pub phase: HookPhase,
+    child: Option<Child>,
+    receiver: Receiver<OutputLine>,
+    /// When the command is killed for running too long.
+    deadline: Instant,
+    timeout: Duration,
+    /// Exit status and when it was seen, while the output drains.
+    exited: Option<(ExitStatus, Instant)>,
+    output_closed: bool,
+}
+
+impl IterationHook {
+    /// Start `command` in `cwd` (the current directory if `None`), allowing
+    /// it `timeout` to finish.
+    pub fn start(
+        phase: HookPhase,
+        command: &str,
+        cwd: Option<&Path>,
+        timeout: Duration,
+    ) -> std::io::Result<Self> {
+        // Stopping the run must also stop what the shell started
+        let (child, receiver) = shell_command::spawn(command, cwd)?;
+        info!(
+            hook = phase.name(),
+            pid = child.id(),
+            "iteration_hook_started"
+        );
+        Ok(Self {
+            phase,
+            child: Some(child),
+            receiver,
+            deadline: Instant::now() + timeout,
+            timeout,
+            exited: None,
+            output_closed: false,
+        })
+    }
+
+    /// Move output that arrived since the last call into `lines`. Returns
+    /// the outcome once the command has exited and its output is read.
+    pub fn poll(&mut self, lines: &mut Vec<String>) -> Option<Result<(), IterationHookError>> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(line) => lines.push(line.text),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.output_closed = true;
+                    break;
+                }
+            }
+        }
+        if self.exited.is_none()
+            && let Some(child) = &mut self.child
+        {
+            match child.try_wait() {
+                Ok(Some(status)) => {
+                    self.child = None;
+                    self.exited = Some((status, Instant::now()));
+                }
+                Ok(None) if Instant::now() >= self.deadline => {
+                    warn!(hook = self.phase.name(), "iteration_hook_timed_out");
+                    self.kill();
+                    return Some(Err(IterationHookError::TimedOut(self.timeout)));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    warn!(hook = self.phase.name(), error = %e, "iteration_hook_wait_failed");
+                    self.kill();
+                    return Some(Err(IterationHookError::Wait(e)));
+                }
+            }
+        }
+        let (status, at) = self.exited?;
+        if !self.output_closed && at.elapsed() < DRAIN_GRACE {
+            return None;
+        }
+        info!(hook = self.phase.name(), code = ?status.code(), "iteration_hook_exited");
+        Some(match status.code() {
+            Some(0) => Ok(()),
+            Some(code) => Err(IterationHookError::ExitCode(code)),
+            None => Err(IterationHookError::Signal),
+        })
+    }
+
+    /// Kill the command if it is still running.
+    pub fn kill(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            crate::control::kill_process(&mut child);
+            info!(
+                hook = self.phase.name(),
+                pid = child.id(),
+                "iteration_hook_killed"
+            );
+        }
+    }
+}
+
+impl Drop for IterationHook {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+/// The line shown before a hook's output.
+pub fn header(phase: HookPhase, command: &str) -> String {
+    format!("[{}: {command}]", phase.name())
+}
+
+/// A line of hook output as shown in the main panel.
+pub fn output_line(line: &str) -> String {
+    format!("  {line}")
+}
+
+/// The line shown when a hook fails.
+pub fn failure_line(phase: HookPhase, error: &IterationHookError) -> String {
+    match phase {
+        HookPhase::Before => format!(
+            "[{} failed ({error}) \u{2014} iteration aborted]",
+            phase.name()
+        ),
+        HookPhase::After => format!("[{} failed ({error})]", phase.name()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Poll `command` until it finishes, collecting its output into `lines`.
+    fn run_to_completion(
+        phase: HookPhase,
+        command: &str,
+        cwd: Option<&Path>,
+        timeout: Duration,
+        lines: &mut Vec<String>,
+    ) -> Result<(), IterationHookError> {
+        let mut hook = IterationHook::start(phase, command, cwd, timeout).unwrap();
+        loop {
+            if let Some(outcome) = hook.poll(lines) {
+                return outcome;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn output_is_collected_and_exit_code_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut lines = Vec::new();
+        let outcome = run_to_completion(
+            HookPhase::Before,
+            "pwd; echo oops >&2; exit 3",
+            Some(dir.path()),
+            TIMEOUT,
+            &mut lines,
+        );
+        assert!(
+            matches!(outcome, Err(IterationHookError::ExitCode(3))),
+            "{outcome:?}"
+        );
+        let cwd = dir.path().canonicalize().unwrap();
+        assert!(lines.contains(&cwd.display().to_string()), "{lines:?}");
+        assert!(lines.contains(&"oops".to_string()));
+
+        let mut lines = Vec::new();
+        assert!(run_to_completion(HookPhase::After, "true", None, TIMEOUT, &mut lines).is_ok());
+        assert!(lines.is_empty());
+    }
+
+    #[test]
+    fn dropping_a_running_hook_kills_it() {
+        let mut hook = IterationHook::start(HookPhase::After, "sleep 30", None, TIMEOUT).unwrap();
+        let mut lines = Vec::new();
+        assert!(hook.poll(&mut lines).is_none());
+        let started = Instant::now();
+        drop(hook);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn hook_past_its_timeout_is_killed_and_fails() {
+        let started = Instant::now();
+        let mut lines = Vec::new();
+        let outcome = run_to_completion(
+            HookPhase::After,
+            "echo started; sleep 30",
+            None,
+            Duration::from_millis(200),
+            &mut lines,
+        );
+        assert!(
+            matches!(outcome, Err(IterationHookError::TimedOut(_))),
+            "{outcome:?}"
+        );
+        assert_eq!(lines, ["started"]);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
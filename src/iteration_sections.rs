@@ -0,0 +1,285 @@
+//! Collapsible per-iteration sections in the workers stream.
+//!
+//! Each iteration's output sits under a header such as
+//! `▾ Iteration 7 — Parse nested lists — $0.42 — 4m12s`. A section only
+//! records where its iteration begins in the worker's `output_lines`; folding
+//! happens when drawing, so the indices held by result blocks and the tool
+//! preview stay valid. Starting an iteration folds the ones before it.
+
+use std::time::Duration;
+
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::app::App;
+use crate::number_format::NumberFormat;
+
+/// One iteration's span of a worker's output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IterationSection {
+    /// Index in `output_lines` of the iteration's first line. The section runs
+    /// up to the next section's start.
+    pub start: usize,
+    pub iteration: u32,
+    /// Title of the spec being worked on, or its bead ID.
+    pub spec: Option<String>,
+    pub cost_usd: Option<f64>,
+    pub duration: Option<Duration>,
+    /// Whether the iteration's result event has arrived.
+    pub finished: bool,
+    pub failed: bool,
+    pub collapsed: bool,
+}
+
+/// A row of the folded view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Row {
+    /// The header of the section at this index.
+    Header(usize),
+    /// An output line, and the section it belongs to (`None` before the first).
+    Line {
+        index: usize,
+        section: Option<usize>,
+    },
+}
+
+impl Row {
+    /// The section this row belongs to.
+    pub fn section(self) -> Option<usize> {
+        match self {
+            Row::Header(section) => Some(section),
+            Row::Line { section, .. } => section,
+        }
+    }
+}
+
+/// Rows shown for `total` output lines split into `sections`: anything before
+/// the first section, then each section's header and, unless collapsed, its lines.
+pub fn rows(sections: &[IterationSection], total: usize) -> Vec<Row> {
+    let first = sections.first().map_or(total, |s| s.start.min(total));
+    let mut rows: Vec<Row> = (0..first)
+        .map(|index| Row::Line {
+            index,
+            section: None,
+        })
+        .collect();
+    for (i, section) in sections.iter().enumerate() {
+        rows.push(Row::Header(i));
+        if section.collapsed {
+            continue;
+        }
+        let end = sections
+            .get(i + 1)
+            .map_or(total, |next| next.start.min(total));
+        rows.extend((section.start.min(end)..end).map(|index| Row::Line {
+            index,
+            section: Some(i),
+        }));
+    }
+    rows
+}
+
+/// The folded view of `lines`.
+pub fn fold(
+    lines: &[Line<'static>],
+    sections: &[IterationSection],
+    numbers: &NumberFormat,
+) -> Vec<Line<'static>> {
+    rows(sections, lines.len())
+        .into_iter()
+        .map(|row| match row {
+            Row::Header(i) => {
+                let end = sections.get(i + 1).map_or(lines.len(), |next| next.start);
+                let hidden = end.saturating_sub(sections[i].start);
+                header_line(&sections[i], hidden, numbers)
+            }
+            Row::Line { index, .. } => lines[index].clone(),
+        })
+        .collect()
+}
+
+/// `▸ Iteration 7 — Parse nested lists — $0.42 — 4m12s · 312 lines`
+pub fn header_line(
+    section: &IterationSection,
+    lines: usize,
+    numbers: &NumberFormat,
+) -> Line<'static> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let marker = if section.collapsed {
+        "\u{25b8}"
+    } else {
+        "\u{25be}"
+    };
+    let mut spans = vec![Span::styled(
+        format!("{marker} Iteration {}", section.iteration),
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )];
+    let mut detail: Vec<String> = section.spec.iter().cloned().collect();
+    if section.finished {
+        detail.extend(section.cost_usd.map(|cost| numbers.cost(cost)));
+        detail.extend(section.duration.map(short_duration));
+    } else {
+        detail.push("running".into());
+    }
+    for part in detail {
+        spans.push(Span::raw(format!(" \u{2014} {part}")));
+    }
+    if section.failed {
+        spans.push(Span::styled(
+            " \u{2014} failed",
+            Style::default().fg(Color::Red),
+        ));
+    }
+    if section.collapsed {
+        let noun = if lines == 1 { "line" } else { "lines" };
+        spans.push(Span::styled(format!(" \u{b7} {lines} {noun}"), dim));
+    }
+    Line::from(spans)
+}
+
+/// `42s`, `4m12s` or `1h05m`.
+fn short_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// Expand every section if any is collapsed, otherwise collapse all but the
+/// latest. Returns whether everything is now expanded.
+pub fn toggle_all(sections: &mut [IterationSection]) -> bool {
+    let expand = sections.iter().any(|s| s.collapsed);
+    let last = sections.len().saturating_sub(1);
+    for (i, section) in sections.iter_mut().enumerate() {
+        section.collapsed = !expand && i < last;
+    }
+    expand
+}
+
+impl App {
+    /// Open a section for the iteration worker `w` is starting, folding the
+    /// earlier ones.
+    pub fn begin_iteration_section(&mut self, w: usize) {
+        let worker = &self.workers[w];
+        let spec = worker.hooked_bead_id.as_deref().map(|id| {
+            self.kanban_board_state
+                .find_card(id)
+                .map_or_else(|| id.to_string(), |card| card.title.clone())
+        });
+        let section = IterationSection {
+            start: worker.output_lines.len(),
+            iteration: worker.current_iteration,
+            spec,
+            cost_usd: None,
+            duration: None,
+            finished: false,
+            failed: false,
+            collapsed: false,
+        };
+        let worker = &mut self.workers[w];
+        for earlier in &mut worker.sections {
+            earlier.collapsed = true;
+        }
+        worker.sections.push(section);
+    }
+
+    /// Fill in the header of worker `w`'s current section from its result.
+    pub fn finish_iteration_section(
+        &mut self,
+        w: usize,
+        cost_usd: Option<f64>,
+        duration_ms: Option<u64>,
+        failed: bool,
+    ) {
+        let Some(section) = self.workers[w].sections.last_mut() else {
+            return;
+        };
+        section.cost_usd = cost_usd;
+        section.duration = duration_ms.map(Duration::from_millis);
+        section.finished = true;
+        section.failed = failed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(start: usize, iteration: u32, collapsed: bool) -> IterationSection {
+        IterationSection {
+            start,
+            iteration,
+            spec: None,
+            cost_usd: None,
+            duration: None,
+            finished: false,
+            failed: false,
+            collapsed,
+        }
+    }
+
+    fn line(index: usize, section: Option<usize>) -> Row {
+        Row::Line { index, section }
+    }
+
+    #[test]
+    fn collapsed_sections_show_only_their_header() {
+        let sections = [section(1, 1, true), section(3, 2, false)];
+        assert_eq!(
+            rows(&sections, 5),
+            vec![
+                line(0, None),
+                Row::Header(0),
+                Row::Header(1),
+                line(3, Some(1)),
+                line(4, Some(1)),
+            ]
+        );
+        assert_eq!(rows(&[], 2), vec![line(0, None), line(1, None)]);
+        // A section opened at the end of the output has no lines yet
+        assert_eq!(rows(&[section(2, 1, false)], 2)[2], Row::Header(0));
+    }
+
+    #[test]
+    fn header_shows_spec_cost_and_duration() {
+        let numbers = NumberFormat::default();
+        let text = |s: &IterationSection, lines| header_line(s, lines, &numbers).to_string();
+
+        let mut running = section(0, 7, false);
+        running.spec = Some("Parse nested lists".into());
+        assert_eq!(
+            text(&running, 3),
+            "\u{25be} Iteration 7 \u{2014} Parse nested lists \u{2014} running"
+        );
+
+        let mut done = running.clone();
+        done.finished = true;
+        done.cost_usd = Some(0.42);
+        done.duration = Some(Duration::from_secs(252));
+        done.collapsed = true;
+        assert_eq!(
+            text(&done, 312),
+            "\u{25b8} Iteration 7 \u{2014} Parse nested lists \u{2014} $0.42 \u{2014} 4m12s \u{b7} 312 lines"
+        );
+    }
+
+    #[test]
+    fn short_duration_picks_units() {
+        assert_eq!(short_duration(Duration::from_secs(42)), "42s");
+        assert_eq!(short_duration(Duration::from_secs(252)), "4m12s");
+        assert_eq!(short_duration(Duration::from_secs(3900)), "1h05m");
+    }
+
+    #[test]
+    fn toggle_all_expands_then_folds_all_but_latest() {
+        let mut sections = [section(0, 1, true), section(2, 2, false)];
+        assert!(toggle_all(&mut sections));
+        assert!(sections.iter().all(|s| !s.collapsed));
+        assert!(!toggle_all(&mut sections));
+        assert!(sections[0].collapsed && !sections[1].collapsed);
+    }
+}
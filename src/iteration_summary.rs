@@ -0,0 +1,154 @@
+//! One-line summary of each finished iteration, for reuse in commit messages
+//! and standup notes.
+//!
+//! The line names the bead, the main files changed and how the iteration went,
+//! e.g. `bd-12: src/app.rs, src/ui.rs (+2 more) — ok, $0.42`. The latest one
+//! is kept in `last-iteration.txt` next to the project config, and `y` in the
+//! workers view copies the selected worker's to the clipboard.
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::config::compute_project_config_path;
+use crate::number_format::NumberFormat;
+
+const LAST_ITERATION_FILE_NAME: &str = "last-iteration.txt";
+
+/// Files named before the rest are counted as `(+N more)`.
+const MAX_FILES_SHOWN: usize = 3;
+
+/// What an iteration did, as far as its summary line goes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IterationOutcome<'a> {
+    pub bead: Option<&'a str>,
+    pub iteration: u32,
+    /// Changed paths, most relevant first.
+    pub files: &'a [String],
+    pub failed: bool,
+    pub tool_errors: u32,
+    pub cost_usd: Option<f64>,
+}
+
+/// Render `outcome` as a single line.
+pub fn summary_line(outcome: &IterationOutcome, numbers: &NumberFormat) -> String {
+    let subject = outcome.bead.map_or_else(
+        || format!("iteration {}", outcome.iteration),
+        str::to_string,
+    );
+    let files = match outcome.files.len() {
+        0 => "no file changes".to_string(),
+        n => {
+            let shown = outcome.files[..n.min(MAX_FILES_SHOWN)].join(", ");
+            match n.saturating_sub(MAX_FILES_SHOWN) {
+                0 => shown,
+                more => format!("{shown} (+{more} more)"),
+            }
+        }
+    };
+    let mut result = vec![if outcome.failed { "failed" } else { "ok" }.to_string()];
+    match outcome.tool_errors {
+        0 => {}
+        1 => result.push("1 tool error".to_string()),
+        n => result.push(format!("{n} tool errors")),
+    }
+    if let Some(cost) = outcome.cost_usd {
+        result.push(numbers.cost(cost));
+    }
+    format!("{subject}: {files} \u{2014} {}", result.join(", "))
+}
+
+/// `last-iteration.txt` for the project in `project_dir`.
+fn last_iteration_path(project_dir: &Path) -> Option<PathBuf> {
+    compute_project_config_path(project_dir).map(|p| p.with_file_name(LAST_ITERATION_FILE_NAME))
+}
+
+/// Overwrite the project's `last-iteration.txt` with `line`.
+pub fn write_last(project_dir: &Path, line: &str) -> io::Result<()> {
+    let Some(path) = last_iteration_path(project_dir) else {
+        return Ok(());
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, format!("{line}\n"))
+}
+
+/// Put `text` on the system clipboard through the terminal (OSC 52), which
+/// also works over SSH and inside tmux with `set-clipboard on`.
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(osc52(text).as_bytes())?;
+    stdout.flush()
+}
+
+fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(files: &[String]) -> IterationOutcome<'_> {
+        IterationOutcome {
+            bead: Some("bd-12"),
+            iteration: 3,
+            files,
+            failed: false,
+            tool_errors: 0,
+            cost_usd: Some(0.42),
+        }
+    }
+
+    #[test]
+    fn names_bead_files_and_result() {
+        let numbers = NumberFormat::default();
+        let files: Vec<String> = ["src/a.rs", "src/b.rs", "c.md", "d.md", "e.md"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(
+            summary_line(&outcome(&files), &numbers),
+            "bd-12: src/a.rs, src/b.rs, c.md (+2 more) \u{2014} ok, $0.42"
+        );
+        let failed = IterationOutcome {
+            bead: None,
+            failed: true,
+            tool_errors: 2,
+            cost_usd: None,
+            ..outcome(&[])
+        };
+        assert_eq!(
+            summary_line(&failed, &numbers),
+            "iteration 3: no file changes \u{2014} failed, 2 tool errors"
+        );
+    }
+
+    #[test]
+    fn base64_pads_like_the_standard_alphabet() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64("bd-12 \u{2014}".as_bytes()), "YmQtMTIg4oCU");
+        assert_eq!(osc52("hi"), "\x1b]52;c;aGk=\x07");
+    }
+}
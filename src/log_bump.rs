@@ -0,0 +1,183 @@
+//! Temporary debug logging after something goes wrong
+//! (`[logging] debug_on_error_minutes`).
+//!
+//! When a run enters the error state, or Claude's output produces a burst of
+//! lines that don't parse, the log filter is raised to `debug` for a few
+//! minutes and then put back. Intermittent failures leave detail in the log
+//! without running at debug all the time.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
+
+use crate::app::{App, AppStatus};
+use crate::logging;
+
+/// Unparseable output lines within [`PARSE_FAILURE_WINDOW`] that count as a burst.
+const PARSE_FAILURE_BURST: usize = 5;
+const PARSE_FAILURE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Level used while raised.
+const BUMPED_LEVEL: &str = "debug";
+
+/// State of a temporary raise of the log level.
+#[derive(Debug, Default)]
+pub struct LogBump {
+    /// When the configured level comes back; `None` while not raised.
+    pub until: Option<Instant>,
+    /// Whether the app was in the error state at the last check.
+    was_error: bool,
+    /// Recent output lines that failed to parse.
+    parse_failures: VecDeque<Instant>,
+}
+
+impl LogBump {
+    /// Record an unparseable line; true when it completes a burst.
+    fn note_parse_failure(&mut self, now: Instant) -> bool {
+        while self
+            .parse_failures
+            .front()
+            .is_some_and(|&at| now.duration_since(at) > PARSE_FAILURE_WINDOW)
+        {
+            self.parse_failures.pop_front();
+        }
+        self.parse_failures.push_back(now);
+        if self.parse_failures.len() < PARSE_FAILURE_BURST {
+            return false;
+        }
+        self.parse_failures.clear();
+        true
+    }
+}
+
+impl App {
+    /// Note a line of Claude's output that didn't parse.
+    pub fn note_parse_failure(&mut self) {
+        let now = self.clock.now();
+        if self.log_bump.note_parse_failure(now) {
+            self.bump_log_level("parse_failures");
+        }
+    }
+
+    /// Raise the level when the app enters the error state, and restore it
+    /// once the raise has run its course.
+    pub fn check_log_bump(&mut self) {
+        let is_error = self.status == AppStatus::Error;
+        if is_error && !self.log_bump.was_error {
+            self.bump_log_level("error_state");
+        }
+        self.log_bump.was_error = is_error;
+
+        if self
+            .log_bump
+            .until
+            .is_some_and(|until| self.clock.now() >= until)
+        {
+            self.restore_log_level();
+        }
+    }
+
+    /// Log at debug for the next `debug_on_error_minutes`, extending a raise
+    /// already in progress.
+    fn bump_log_level(&mut self, reason: &str) {
+        let minutes = self.config.logging.debug_on_error_minutes;
+        if minutes == 0
+            || logging::env_override_active()
+            || matches!(self.current_log_level.as_str(), "trace" | "debug")
+        {
+            return;
+        }
+        let Some(handle) = &self.log_level_handle else {
+            return;
+        };
+        if self.log_bump.until.is_none()
+            && let Err(e) = logging::update_log_level(handle, BUMPED_LEVEL)
+        {
+            warn!(error = %e, "log_level_bump_failed");
+            return;
+        }
+        self.log_bump.until = Some(self.clock.now() + Duration::from_secs(minutes * 60));
+        info!(reason, minutes, "log_level_bumped");
+    }
+
+    /// Go back to the configured level.
+    fn restore_log_level(&mut self) {
+        self.log_bump.until = None;
+        let Some(handle) = &self.log_level_handle else {
+            return;
+        };
+        match logging::update_log_level(handle, &self.current_log_level) {
+            Ok(()) => info!(level = %self.current_log_level, "log_level_restored"),
+            Err(e) => warn!(error = %e, "log_level_restore_failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use ralph_core::clock::ManualClock;
+    use tracing_subscriber::{EnvFilter, Registry, reload};
+
+    use super::*;
+    use crate::config::LoadedConfig;
+
+    #[test]
+    fn parse_failures_burst_within_window() {
+        let mut bump = LogBump::default();
+        let start = Instant::now();
+        for i in 0..4 {
+            assert!(!bump.note_parse_failure(start + Duration::from_secs(i * 30)));
+        }
+        // Spread over 90 seconds, so the window never held five; now start over
+        for i in 200..204 {
+            assert!(!bump.note_parse_failure(start + Duration::from_secs(i)));
+        }
+        assert!(bump.note_parse_failure(start + Duration::from_secs(204)));
+        assert!(bump.parse_failures.is_empty());
+    }
+
+    #[test]
+    fn error_state_raises_level_until_timeout() {
+        let (layer, handle) = reload::Layer::<_, Registry>::new(EnvFilter::new("info"));
+        let mut app = App::new("test".into(), None, LoadedConfig::default_for_test(), None);
+        let clock = Arc::new(ManualClock::new());
+        app.clock = clock.clone();
+        app.current_log_level = "info".into();
+        app.log_level_handle = Some(Arc::new(Mutex::new(handle.clone())));
+        let level = || handle.with_current(|f| f.to_string()).unwrap();
+
+        app.status = AppStatus::Error;
+        app.check_log_bump();
+        assert_eq!(level(), "debug");
+        assert!(app.log_bump.until.is_some());
+
+        clock.advance(Duration::from_secs(10 * 60));
+        app.status = AppStatus::Stopped;
+        app.check_log_bump();
+        assert_eq!(level(), "info");
+        assert!(app.log_bump.until.is_none());
+        drop(layer);
+    }
+
+    #[test]
+    fn disabled_or_verbose_levels_are_left_alone() {
+        let mut app = App::new("test".into(), None, LoadedConfig::default_for_test(), None);
+        let (_layer, handle) = reload::Layer::<_, Registry>::new(EnvFilter::new("trace"));
+        app.log_level_handle = Some(Arc::new(Mutex::new(handle)));
+        app.current_log_level = "trace".into();
+        app.status = AppStatus::Error;
+        app.check_log_bump();
+        assert!(app.log_bump.until.is_none());
+
+        app.current_log_level = "info".into();
+        app.config.logging.debug_on_error_minutes = 0;
+        app.status = AppStatus::Stopped;
+        app.check_log_bump();
+        app.status = AppStatus::Error;
+        app.check_log_bump();
+        assert!(app.log_bump.until.is_none());
+    }
+}
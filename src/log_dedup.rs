@@ -0,0 +1,169 @@
+//! Collapsing floods of identical warnings.
+//!
+//! A misbehaving stream can log the same warning hundreds of times (e.g.
+//! `Unknown event type, skipping event_type=thinking`). The first one is
+//! logged; identical ones within [`WINDOW`] never reach the log file and are
+//! counted on the first one's log ring entry instead. When the window closes,
+//! a single `warning_repeated` line records how many were dropped.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tracing::{Event, Level, Subscriber, warn};
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::log_ring::{self, LogRing};
+
+/// How long an identical warning stays suppressed after it was logged.
+pub const WINDOW: Duration = Duration::from_secs(60);
+
+/// Target of the `warning_repeated` summaries, which are never suppressed.
+pub const SUMMARY_TARGET: &str = module_path!();
+
+/// A warning's target and message (with fields).
+type Key = (String, String);
+
+/// One warning's current window.
+struct Run {
+    started: Instant,
+    dropped: u32,
+}
+
+/// Which warnings were logged recently and how often they repeated since.
+#[derive(Default)]
+struct Repeats {
+    runs: HashMap<Key, Run>,
+}
+
+impl Repeats {
+    /// Whether `key` should be logged; counts it as dropped otherwise.
+    /// Call [`Repeats::expire`] first so no closed window is still open.
+    fn observe(&mut self, key: &Key, now: Instant) -> bool {
+        if let Some(run) = self.runs.get_mut(key) {
+            run.dropped += 1;
+            return false;
+        }
+        self.runs.insert(
+            key.clone(),
+            Run {
+                started: now,
+                dropped: 0,
+            },
+        );
+        true
+    }
+
+    /// Close the windows older than [`WINDOW`], returning those that dropped
+    /// anything along with the count.
+    fn expire(&mut self, now: Instant) -> Vec<(Key, u32)> {
+        let mut closed = Vec::new();
+        self.runs.retain(|key, run| {
+            let open = now.duration_since(run.started) < WINDOW;
+            if !open && run.dropped > 0 {
+                closed.push((key.clone(), run.dropped));
+            }
+            open
+        });
+        closed
+    }
+}
+
+/// Tracing layer that disables repeats of a recent WARN or ERROR event for
+/// every layer, counting them in `ring`.
+pub struct DuplicateWarnings {
+    ring: LogRing,
+    repeats: Mutex<Repeats>,
+}
+
+impl DuplicateWarnings {
+    pub fn new(ring: LogRing) -> Self {
+        Self {
+            ring,
+            repeats: Mutex::new(Repeats::default()),
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for DuplicateWarnings {
+    fn event_enabled(&self, event: &Event<'_>, _ctx: Context<'_, S>) -> bool {
+        let meta = event.metadata();
+        if meta.target() == SUMMARY_TARGET {
+            return true;
+        }
+        let now = Instant::now();
+        let warning = *meta.level() <= Level::WARN;
+        let key = warning.then(|| (meta.target().to_string(), log_ring::event_message(event)));
+        let (closed, log) = {
+            let Ok(mut repeats) = self.repeats.lock() else {
+                return true;
+            };
+            if repeats.runs.is_empty() && key.is_none() {
+                return true;
+            }
+            let closed = repeats.expire(now);
+            let log = key.as_ref().is_none_or(|key| repeats.observe(key, now));
+            (closed, log)
+        };
+        // Logged with the lock released: the summaries pass through here too
+        for ((target, message), dropped) in closed {
+            warn!(dropped, source = %target, warning = %message, "warning_repeated");
+        }
+        if let Some((target, message)) = key.filter(|_| !log) {
+            self.ring.bump(*meta.level(), &target, &message);
+        }
+        log
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::prelude::*;
+
+    fn key(message: &str) -> Key {
+        ("ralph::output".to_string(), message.to_string())
+    }
+
+    #[test]
+    fn repeats_within_the_window_are_dropped_and_counted() {
+        let mut repeats = Repeats::default();
+        let start = Instant::now();
+        assert!(repeats.observe(&key("a"), start));
+        assert!(!repeats.observe(&key("a"), start + Duration::from_secs(1)));
+        assert!(!repeats.observe(&key("a"), start + Duration::from_secs(2)));
+        assert!(repeats.observe(&key("b"), start + Duration::from_secs(2)));
+
+        assert!(repeats.expire(start + Duration::from_secs(59)).is_empty());
+        let closed = repeats.expire(start + WINDOW + Duration::from_secs(2));
+        assert_eq!(closed, vec![(key("a"), 2)]);
+        assert!(repeats.runs.is_empty());
+
+        // A new window starts with the next occurrence
+        assert!(repeats.observe(&key("a"), start + WINDOW * 2));
+    }
+
+    #[test]
+    fn layer_drops_duplicate_warnings_before_other_layers() {
+        let ring = LogRing::new(10);
+        let subscriber = tracing_subscriber::registry()
+            .with(DuplicateWarnings::new(ring.clone()))
+            .with(ring.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            for _ in 0..5 {
+                tracing::warn!(event_type = "thinking", "Unknown event type, skipping");
+                tracing::info!("tick");
+            }
+        });
+        let entries = ring.snapshot();
+        let warnings: Vec<_> = entries.iter().filter(|e| e.is_warning()).collect();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].count, 5);
+        assert_eq!(
+            warnings[0].message_with_count(),
+            "Unknown event type, skipping event_type=thinking (\u{d7}5)"
+        );
+        // Info events are never collapsed
+        assert_eq!(entries.len(), 6);
+    }
+}
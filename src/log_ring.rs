@@ -0,0 +1,256 @@
+//! In-memory ring of the most recent tracing events.
+//!
+//! Installed as a layer next to the file logger so the Diagnostics modal (and
+//! error exits) can show recent warnings without locating the log files.
+//! Warnings dropped as duplicates by [`crate::log_dedup`] are counted on the
+//! entry they repeat rather than stored again.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Events kept by the global ring.
+pub const LOG_RING_CAPACITY: usize = 500;
+
+static GLOBAL: LazyLock<LogRing> = LazyLock::new(|| LogRing::new(LOG_RING_CAPACITY));
+
+/// The process-wide ring fed by the logging subscriber.
+pub fn global() -> &'static LogRing {
+    &GLOBAL
+}
+
+/// One captured tracing event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub level: Level,
+    pub target: String,
+    /// Event message followed by its fields as `key=value`.
+    pub message: String,
+    /// Times the event was logged, counting dropped duplicates.
+    pub count: u32,
+}
+
+impl LogEntry {
+    /// `HH:MM:SS` (UTC) for compact display.
+    pub fn time_of_day(&self) -> String {
+        let secs = self.timestamp % 86400;
+        format!(
+            "{:02}:{:02}:{:02}",
+            secs / 3600,
+            (secs % 3600) / 60,
+            secs % 60
+        )
+    }
+
+    /// WARN or ERROR.
+    pub fn is_warning(&self) -> bool {
+        self.level <= Level::WARN
+    }
+
+    /// The message, with `(×N)` when the event repeated.
+    pub fn message_with_count(&self) -> String {
+        if self.count > 1 {
+            format!("{} (\u{d7}{})", self.message, self.count)
+        } else {
+            self.message.clone()
+        }
+    }
+}
+
+/// Bounded buffer of recent events; also the tracing layer that fills it.
+#[derive(Debug, Clone)]
+pub struct LogRing {
+    entries: Arc<Mutex<VecDeque<LogEntry>>>,
+    capacity: usize,
+}
+
+impl LogRing {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    fn push(&self, entry: LogEntry) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Count a dropped duplicate on the latest matching entry, or record it
+    /// afresh if that entry has already left the ring.
+    pub fn bump(&self, level: Level, target: &str, message: &str) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        if let Some(entry) = entries
+            .iter_mut()
+            .rev()
+            .find(|e| e.level == level && e.target == target && e.message == message)
+        {
+            entry.count += 1;
+            return;
+        }
+        drop(entries);
+        self.push(LogEntry {
+            timestamp: unix_now(),
+            level,
+            target: target.to_string(),
+            message: message.to_string(),
+            count: 1,
+        });
+    }
+
+    /// Snapshot of the buffered events, oldest first.
+    pub fn snapshot(&self) -> Vec<LogEntry> {
+        self.entries
+            .lock()
+            .map(|e| e.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogRing {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        // Duplicate counts are already on the entries they summarize
+        if event.metadata().target() == crate::log_dedup::SUMMARY_TARGET {
+            return;
+        }
+        self.push(LogEntry {
+            timestamp: unix_now(),
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_string(),
+            message: event_message(event),
+            count: 1,
+        });
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// An event's message followed by its fields as `key=value`.
+pub fn event_message(event: &Event<'_>) -> String {
+    let mut visitor = MessageVisitor::default();
+    event.record(&mut visitor);
+    visitor.finish()
+}
+
+/// Collects the `message` field and the remaining fields as `key=value`.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        if self.message.is_empty() {
+            self.fields.trim_start().to_string()
+        } else {
+            self.message + &self.fields
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::prelude::*;
+
+    fn capture(ring: &LogRing, f: impl FnOnce()) {
+        let subscriber = tracing_subscriber::registry().with(ring.clone());
+        tracing::subscriber::with_default(subscriber, f);
+    }
+
+    #[test]
+    fn records_message_and_fields() {
+        let ring = LogRing::new(10);
+        capture(&ring, || {
+            tracing::warn!(path = "a.toml", code = 3, "config_parse_failed");
+        });
+        let entries = ring.snapshot();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].level, Level::WARN);
+        assert_eq!(entries[0].message, "config_parse_failed path=a.toml code=3");
+        assert!(entries[0].is_warning());
+    }
+
+    #[test]
+    fn drops_oldest_beyond_capacity() {
+        let ring = LogRing::new(3);
+        capture(&ring, || {
+            for i in 0..5 {
+                tracing::info!(i, "tick");
+            }
+        });
+        let entries = ring.snapshot();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].message, "tick i=2");
+        assert!(!entries[0].is_warning());
+    }
+
+    #[test]
+    fn time_of_day_formats_utc() {
+        let entry = LogEntry {
+            timestamp: 86400 + 3600 * 13 + 60 * 5 + 9,
+            level: Level::INFO,
+            target: String::new(),
+            message: String::new(),
+            count: 1,
+        };
+        assert_eq!(entry.time_of_day(), "13:05:09");
+    }
+
+    #[test]
+    fn bump_counts_on_the_matching_entry() {
+        let ring = LogRing::new(10);
+        capture(&ring, || {
+            tracing::warn!("disk_low");
+            tracing::info!("tick");
+        });
+        ring.bump(Level::WARN, "ralph::log_ring::tests", "disk_low");
+        ring.bump(Level::WARN, "ralph::log_ring::tests", "disk_low");
+        ring.bump(Level::WARN, "ralph::log_ring::tests", "gone");
+        let entries = ring.snapshot();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].message_with_count(), "disk_low (\u{d7}3)");
+        assert_eq!(entries[1].message_with_count(), "tick");
+        assert_eq!(entries[2].message, "gone");
+        assert_eq!(entries[2].count, 1);
+    }
+}
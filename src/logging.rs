@@ -14,6 +14,8 @@ use tracing_subscriber::fmt::format::FmtSpan;
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::reload;
 
+use crate::{log_dedup, log_ring};
+
 /// Handle for dynamically changing the log level at runtime.
 pub type ReloadHandle = reload::Handle<EnvFilter, tracing_subscriber::Registry>;
 
@@ -54,7 +56,9 @@ pub fn new_session_id() -> String {
 
 /// Returns the platform-appropriate log directory path without initializing logging.
 pub fn log_directory() -> Option<PathBuf> {
-    if cfg!(target_os = "macos") {
+    if let Some(sandbox) = crate::sandbox::get() {
+        Some(sandbox.log_dir())
+    } else if cfg!(target_os = "macos") {
         dirs_home_log_dir()
     } else {
         ProjectDirs::from("com", "cmoel", "ralph").and_then(|p| p.state_dir().map(PathBuf::from))
@@ -71,21 +75,11 @@ pub fn log_directory() -> Option<PathBuf> {
 /// The returned `WorkerGuard` must be held for the application lifetime.
 /// The `reload_handle` can be used to dynamically change the log level.
 pub fn init(session_id: String, log_level: &str) -> Result<LoggingContext, LoggingError> {
-    // Get platform-appropriate log directory
-    let project_dirs = ProjectDirs::from("com", "cmoel", "ralph").ok_or_else(|| LoggingError {
-        message: "Failed to determine platform directories".to_string(),
-    })?;
-
-    // Use cache_dir as base, but we want state/logs directory
+    // Get platform-appropriate log directory (or the sandbox's)
     // macOS: ~/Library/Logs/ralph/
     // Linux: ~/.local/state/ralph/
     // Windows: %LocalAppData%\ralph\
-    let log_dir = if cfg!(target_os = "macos") {
-        dirs_home_log_dir()
-    } else {
-        project_dirs.state_dir().map(PathBuf::from)
-    }
-    .ok_or_else(|| LoggingError {
+    let log_dir = log_directory().ok_or_else(|| LoggingError {
         message: "Failed to determine log directory".to_string(),
     })?;
 
@@ -124,10 +118,15 @@ pub fn init(session_id: String, log_level: &str) -> Result<LoggingContext, Loggi
         .with_span_events(FmtSpan::NONE)
         .with_target(true);
 
-    // Build and set the subscriber
+    // Build and set the subscriber; duplicate warnings are dropped before the
+    // file and the ring, which keeps recent events for the Diagnostics modal
     tracing_subscriber::registry()
         .with(filter_layer)
+        .with(log_dedup::DuplicateWarnings::new(
+            log_ring::global().clone(),
+        ))
         .with(fmt_layer)
+        .with(log_ring::global().clone())
         .init();
 
     // Log session start
@@ -140,12 +139,17 @@ pub fn init(session_id: String, log_level: &str) -> Result<LoggingContext, Loggi
     })
 }
 
+/// Whether `RALPH_LOG` fixes the log level, overriding config and runtime changes.
+pub fn env_override_active() -> bool {
+    std::env::var("RALPH_LOG").is_ok()
+}
+
 /// Updates the log level filter at runtime.
 ///
 /// Returns `Ok(())` if the level was successfully updated, or an error message if the level is invalid.
 pub fn update_log_level(handle: &Arc<Mutex<ReloadHandle>>, new_level: &str) -> Result<(), String> {
     // Check for RALPH_LOG env override - if set, don't allow config to override it
-    if std::env::var("RALPH_LOG").is_ok() {
+    if env_override_active() {
         return Ok(()); // Silently ignore, env var takes precedence
     }
 
@@ -169,6 +173,9 @@ fn dirs_home_log_dir() -> Option<PathBuf> {
     dirs::home_dir().map(|home| home.join("Library").join("Logs").join("ralph"))
 }
 
+/// How many days log files and transcripts are kept.
+pub const RETENTION_DAYS: u64 = 7;
+
 /// Cleans up log files older than the retention period.
 ///
 /// Scans the log directory for `ralph.*` files and deletes those older than 7 days.
@@ -177,7 +184,6 @@ pub fn cleanup_old_logs(log_dir: &PathBuf) {
     use std::time::{Duration, SystemTime};
     use tracing::{debug, warn};
 
-    const RETENTION_DAYS: u64 = 7;
     let retention_duration = Duration::from_secs(RETENTION_DAYS * 24 * 60 * 60);
 
     let entries = match fs::read_dir(log_dir) {
@@ -0,0 +1,78 @@
+//! Claude CLI authentication failures — recognized in a worker's stderr or
+//! result, and fixed by handing the terminal to `claude login` and back.
+
+use std::io;
+use std::path::Path;
+use std::process::{Command, ExitStatus};
+
+use anyhow::Result;
+use ratatui::DefaultTerminal;
+use tracing::{info, warn};
+
+use crate::config::NetworkConfig;
+use crate::suspend;
+
+/// Lowercased fragments of the messages Claude prints when it has no usable
+/// credentials.
+const AUTH_ERROR_MARKERS: &[&str] = &[
+    "invalid api key",
+    "please run /login",
+    "not logged in",
+    "authentication_error",
+    "oauth token has expired",
+    "oauth token revoked",
+    "could not resolve authentication",
+    "missing api key",
+];
+
+/// True when `text` reports missing or rejected Claude credentials.
+pub fn is_auth_error(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    AUTH_ERROR_MARKERS.iter().any(|m| lower.contains(m))
+}
+
+/// Suspend the TUI, run `claude login`, then restore it. Returns whether the
+/// login command exited successfully.
+pub fn run_login_session(
+    terminal: &mut DefaultTerminal,
+    claude_path: &Path,
+    network: &NetworkConfig,
+) -> Result<bool> {
+    info!(claude = %claude_path.display(), "login_session_start");
+    match suspend::suspended(terminal, || run_claude(claude_path, network))? {
+        Ok(status) => {
+            info!(code = ?status.code(), "login_session_end");
+            Ok(status.success())
+        }
+        Err(e) => {
+            warn!(error = %e, "login_session_failed");
+            Err(e.into())
+        }
+    }
+}
+
+/// Spawned through `sh` with the path passed as `$0` so it needs no quoting.
+fn run_claude(claude_path: &Path, network: &NetworkConfig) -> io::Result<ExitStatus> {
+    Command::new("sh")
+        .arg("-c")
+        .arg("exec \"$0\" login")
+        .arg(claude_path)
+        .envs(network.child_env())
+        .status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_auth_failures() {
+        assert!(is_auth_error("Invalid API key · Please run /login"));
+        assert!(is_auth_error(
+            r#"[stderr] API Error: 401 {"type":"error","error":{"type":"authentication_error"}}"#
+        ));
+        assert!(is_auth_error("OAuth token has expired."));
+        assert!(!is_auth_error("API Error: 529 Overloaded"));
+        assert!(!is_auth_error("Claude AI usage limit reached"));
+    }
+}
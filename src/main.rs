@@ -1,25 +1,73 @@
 //! Ralph - TUI wrapper for claude CLI that displays formatted streaming output.
 
 mod agent;
+mod agent_question;
 mod app;
+mod attach;
+mod bd;
 mod bd_lock;
+mod blocked_reason;
+mod claude_cli;
 mod cli;
 mod config;
+mod control;
+mod daemon;
 mod db;
+mod debug_overlay;
+mod detach;
+mod dirty_tree;
+mod disk_guard;
 mod doctor;
+mod encryption;
+mod estimate;
 mod event_loop;
-mod events;
 mod execution;
+mod file_backup;
+mod file_stamp;
+mod file_touches;
+mod hooks;
+mod instruction_queue;
+mod issue_comment;
+mod iteration_hook;
+mod iteration_sections;
+mod iteration_summary;
+mod log_bump;
+mod log_dedup;
+mod log_ring;
 mod logging;
+mod login;
+mod metrics_textfile;
 mod modals;
+mod number_format;
 mod output;
+mod output_scripts;
+mod project_state;
+mod project_summary;
+mod pty;
+mod report;
+mod run_status;
+mod sandbox;
+mod self_report;
+mod session_lock;
+mod shaping;
+mod shell_command;
+mod shutdown_summary;
+mod spec_checklist;
+mod spec_lint;
+mod spec_prompt;
+mod spec_stats;
 mod startup;
+mod stats;
+mod suspend;
+mod tabs;
 mod templates;
 mod tool_history;
 #[allow(dead_code)]
 mod tool_panel;
 mod tool_settings;
+mod transcript;
 mod ui;
+mod ui_state;
 mod validators;
 mod wake_lock;
 mod work_control;
@@ -28,9 +76,9 @@ mod work_start;
 
 use std::io;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
-use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::event::DisableMouseCapture;
 use crossterm::execute;
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
@@ -38,20 +86,40 @@ use crossterm::terminal::{
 use ratatui::Terminal;
 use tracing::{debug, info};
 
-use crate::cli::{Cli, Commands, ToolCommands};
+use crate::cli::{Cli, Commands, SpecCommands, ToolCommands};
 
 fn main() -> Result<()> {
     use std::time::Instant;
 
     // Parse CLI args (handles --version, --help, subcommands)
     let cli = Cli::parse();
+    if let Some(dir) = &cli.sandbox {
+        sandbox::enable(dir)
+            .with_context(|| format!("Failed to create sandbox {}", dir.display()))?;
+    }
 
     // Handle subcommands that don't need the TUI
     match cli.command {
-        Some(Commands::Init) => return cli::run_init(),
+        Some(Commands::Init { preset }) => return cli::run_init(preset),
         Some(Commands::Doctor) => return cli::run_doctor(),
         Some(Commands::Ready { verbose }) => return cli::run_ready(verbose),
         Some(Commands::Logs { id, path }) => return cli::run_logs(id, path),
+        Some(Commands::Specs { json }) => return spec_stats::run(json),
+        Some(Commands::Spec(SpecCommands::Lint { file })) => return spec_lint::run(&file),
+        Some(Commands::Stats { days, all, json }) => return stats::run(days, all, json),
+        Some(Commands::Export {
+            output,
+            transcripts,
+        }) => return project_state::run_export(output, transcripts),
+        Some(Commands::Import { archive, force }) => {
+            return project_state::run_import(&archive, force);
+        }
+        Some(Commands::Report { id }) => return report::run(id),
+        Some(Commands::Status { format }) => return run_status::run(format),
+        Some(Commands::Attach { session_id }) => return attach::run(&session_id),
+        Some(Commands::Daemon { stop, foreground }) => {
+            return daemon::run(stop, foreground, cli.tags);
+        }
         Some(Commands::Tool(tool_cmd)) => {
             return match tool_cmd {
                 ToolCommands::History {
@@ -61,6 +129,7 @@ fn main() -> Result<()> {
                     until,
                     rejected,
                     repo,
+                    tag,
                     all,
                     json,
                     db_path,
@@ -73,6 +142,7 @@ fn main() -> Result<()> {
                     json,
                     show_db_path: db_path,
                     repo,
+                    tag,
                     all,
                 }),
                 ToolCommands::Allow { pattern, project } => {
@@ -101,6 +171,7 @@ fn main() -> Result<()> {
             Ok(ctx) => {
                 // Clean up old log files after logging is initialized
                 logging::cleanup_old_logs(&ctx.log_directory);
+                transcript::prune(&session_id);
                 (
                     Some(ctx.log_directory),
                     Some(ctx._guard),
@@ -119,11 +190,20 @@ fn main() -> Result<()> {
         log_level = %loaded_config.config.logging.level,
         "config_loaded"
     );
+    if !cli.tags.is_empty() {
+        info!(session_id = %session_id, tags = %cli.tags.join(","), "run_tags");
+    }
+
+    // Whatever ends the session, the next launch can say how it ended
+    shutdown_summary::install_panic_hook();
+    #[cfg(unix)]
+    shutdown_summary::catch_signals(&[libc::SIGTERM, libc::SIGHUP, libc::SIGINT]);
 
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen)?;
+    suspend::set_mouse_capture(loaded_config.config.keys.mouse_capture)?;
     let terminal = Terminal::new(ratatui::backend::CrosstermBackend::new(stdout))?;
 
     let result = startup::run_app(
@@ -132,6 +212,8 @@ fn main() -> Result<()> {
         log_directory,
         loaded_config,
         reload_handle,
+        cli.tags,
+        cli.read_only,
     );
 
     // Restore terminal
@@ -146,12 +228,36 @@ fn main() -> Result<()> {
         "session_end"
     );
 
-    result
+    // On an error exit, surface recent warnings since the TUI is gone
+    if result.is_err() {
+        let warnings: Vec<_> = log_ring::global()
+            .snapshot()
+            .into_iter()
+            .filter(|e| e.is_warning())
+            .collect();
+        for entry in &warnings[warnings.len().saturating_sub(20)..] {
+            eprintln!(
+                "{} {} {}: {}",
+                entry.time_of_day(),
+                entry.level,
+                entry.target,
+                entry.message_with_count()
+            );
+        }
+    }
+
+    // The user chose to observe the session holding the project lock
+    match result? {
+        Some(attach_to) => attach::run(&attach_to),
+        None => Ok(()),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bd::Bd;
+    use crate::run_status::StatusFormat;
     use clap::Parser;
 
     #[test]
@@ -175,6 +281,13 @@ mod tests {
         assert_eq!(err.kind(), clap::error::ErrorKind::DisplayHelp);
     }
 
+    #[test]
+    fn cli_sandbox_takes_a_directory() {
+        let cli = Cli::try_parse_from(["ralph", "--sandbox", "/tmp/demo", "status"]).unwrap();
+        assert_eq!(cli.sandbox, Some(std::path::PathBuf::from("/tmp/demo")));
+        assert!(Cli::try_parse_from(["ralph", "--sandbox"]).is_err());
+    }
+
     #[test]
     fn cli_unknown_arg_fails() {
         let result = Cli::try_parse_from(["ralph", "--bogus"]);
@@ -184,7 +297,19 @@ mod tests {
     #[test]
     fn cli_init_subcommand_parses() {
         let cli = Cli::try_parse_from(["ralph", "init"]).unwrap();
-        assert!(matches!(cli.command, Some(Commands::Init)));
+        assert!(matches!(cli.command, Some(Commands::Init { preset: None })));
+    }
+
+    #[test]
+    fn cli_init_preset_parses() {
+        let cli = Cli::try_parse_from(["ralph", "init", "--preset", "rust"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Init {
+                preset: Some(crate::templates::ProjectPreset::RustCrate)
+            })
+        ));
+        assert!(Cli::try_parse_from(["ralph", "init", "--preset", "cobol"]).is_err());
     }
 
     #[test]
@@ -204,6 +329,30 @@ mod tests {
         assert!(Cli::try_parse_from(["ralph", "reinit"]).is_err());
     }
 
+    #[test]
+    fn cli_run_tags_parse() {
+        let cli =
+            Cli::try_parse_from(["ralph", "--tag", "prompt-v3", "--tag", "model:opus"]).unwrap();
+        assert_eq!(cli.tags, ["prompt-v3", "model:opus"]);
+        assert!(Cli::try_parse_from(["ralph", "--tag", "two words"]).is_err());
+        assert!(Cli::try_parse_from(["ralph", "--tag", " "]).is_err());
+    }
+
+    #[test]
+    fn cli_daemon_subcommand_parses() {
+        let cli =
+            Cli::try_parse_from(["ralph", "--tag", "night", "daemon", "--foreground"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Daemon {
+                stop: false,
+                foreground: true
+            })
+        ));
+        assert_eq!(cli.tags, ["night"]);
+        assert!(Cli::try_parse_from(["ralph", "daemon", "--stop", "--foreground"]).is_err());
+    }
+
     #[test]
     fn cli_doctor_subcommand_parses() {
         let cli = Cli::try_parse_from(["ralph", "doctor"]).unwrap();
@@ -326,15 +475,214 @@ mod tests {
     }
 
     #[test]
-    fn assemble_prompt_includes_prompt_and_workflow_file() {
+    fn assemble_prompt_sends_prompt_and_workflow_on_stdin() {
+        let config = crate::config::Config::default();
+        let assembled =
+            execution::assemble_prompt(&config, &Bd::in_cwd("bd"), None, None, &[], None).unwrap();
+
+        // Claude gets the prompt and beads workflow on stdin, not as arguments
+        assert_eq!(assembled.program, config.claude_path());
+        assert!(
+            assembled
+                .context
+                .ends_with(&templates::beads_workflow(None))
+        );
+        assert!(
+            assembled
+                .args
+                .contains(&"--output-format=stream-json".to_string())
+        );
+        assert!(assembled.args.contains(&"--print".to_string()));
+    }
+
+    /// Every output line of a spawned command, stdout and stderr.
+    #[cfg(unix)]
+    fn spawned_output(assembled: &execution::AssembledPrompt) -> Vec<String> {
+        let (mut child, output) = assembled.spawn(Vec::new(), None).unwrap();
+        assert!(child.wait().unwrap().success());
+        output
+            .iter()
+            .map(|ralph_core::output::OutputMessage::Line(line)| line)
+            .collect()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn spawned_claude_reads_the_prompt_from_stdin() {
+        let config = crate::config::Config::default();
+        let mut assembled =
+            execution::assemble_prompt(&config, &Bd::in_cwd("bd"), None, None, &[], None).unwrap();
+        assembled.program = "cat".into();
+        assembled.args.clear();
+        assert_eq!(
+            spawned_output(&assembled).join("\n"),
+            assembled.context.trim_end_matches('\n')
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn pty_output_is_a_terminal_without_escape_sequences() {
+        let mut config = crate::config::Config::default();
+        config.claude.pty = true;
+        let mut assembled =
+            execution::assemble_prompt(&config, &Bd::in_cwd("bd"), None, None, &[], None).unwrap();
+        assembled.program = "sh".into();
+        assembled.args = vec![
+            "-c".into(),
+            "test -t 1 && printf '\\033[32mok\\033[0m\\n' && echo warn >&2".into(),
+        ];
+        assert_eq!(spawned_output(&assembled), ["ok", "warn"]);
+    }
+
+    #[test]
+    fn assemble_prompt_resumes_only_valid_session_ids() {
+        let config = crate::config::Config::default();
+        let id = "3f2a9c1e-8b4d-4e6f-a0b1-c2d3e4f5a6b7";
+        let assembled =
+            execution::assemble_prompt(&config, &Bd::in_cwd("bd"), None, None, &[], Some(id))
+                .unwrap();
+        assert!(assembled.args.windows(2).any(|a| a == ["--resume", id]));
+
+        let assembled = execution::assemble_prompt(
+            &config,
+            &Bd::in_cwd("bd"),
+            None,
+            None,
+            &[],
+            Some("x; rm -rf /"),
+        )
+        .unwrap();
+        assert!(!assembled.args.contains(&"--resume".to_string()));
+    }
+
+    #[test]
+    fn assemble_prompt_context_includes_workflow_and_dirty_context() {
+        let config = crate::config::Config::default();
+        let assembled = execution::assemble_prompt(
+            &config,
+            &Bd::in_cwd("bd"),
+            None,
+            Some("DIRTY-MARKER".to_string()),
+            &[],
+            None,
+        )
+        .unwrap();
+
+        assert!(assembled.context.contains(&templates::beads_workflow(None)));
+        assert!(assembled.context.ends_with("DIRTY-MARKER"));
+    }
+
+    #[test]
+    fn assemble_prompt_context_ends_with_queued_instructions() {
         let config = crate::config::Config::default();
-        let command = execution::assemble_prompt(&config, None, None).unwrap();
+        let instructions = vec!["staging DB is down".to_string()];
+        let assembled =
+            execution::assemble_prompt(&config, &Bd::in_cwd("bd"), None, None, &instructions, None)
+                .unwrap();
+
+        assert!(
+            assembled
+                .context
+                .contains("## Instructions From the Operator")
+        );
+        assert!(assembled.context.ends_with("- staging DB is down\n"));
+    }
+
+    #[test]
+    fn cli_attach_requires_session_id() {
+        let cli = Cli::try_parse_from(["ralph", "attach", "abc123"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Attach { ref session_id }) if session_id == "abc123"
+        ));
+        assert!(Cli::try_parse_from(["ralph", "attach"]).is_err());
+    }
+
+    #[test]
+    fn cli_spec_lint_requires_file() {
+        let cli = Cli::try_parse_from(["ralph", "spec", "lint", "spec.md"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Spec(SpecCommands::Lint { ref file })) if file.as_os_str() == "spec.md"
+        ));
+        assert!(Cli::try_parse_from(["ralph", "spec", "lint"]).is_err());
+    }
+
+    #[test]
+    fn cli_specs_json_flag_parses() {
+        let cli = Cli::try_parse_from(["ralph", "specs"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Specs { json: false })));
+        let cli = Cli::try_parse_from(["ralph", "specs", "--json"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Specs { json: true })));
+    }
+
+    #[test]
+    fn cli_stats_flags_parse() {
+        let cli = Cli::try_parse_from(["ralph", "stats"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Stats {
+                days: 7,
+                all: false,
+                json: false
+            })
+        ));
+        let cli =
+            Cli::try_parse_from(["ralph", "stats", "--days", "30", "--all", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Stats {
+                days: 30,
+                all: true,
+                json: true
+            })
+        ));
+    }
+
+    #[test]
+    fn cli_export_and_import_parse() {
+        let cli = Cli::try_parse_from(["ralph", "export", "-o", "state.tar.gz"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Export { output: Some(ref p), transcripts: false })
+                if p.as_os_str() == "state.tar.gz"
+        ));
+        let cli = Cli::try_parse_from(["ralph", "import", "state.tar.gz", "--force"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Import { ref archive, force: true }) if archive.as_os_str() == "state.tar.gz"
+        ));
+        assert!(Cli::try_parse_from(["ralph", "import"]).is_err());
+    }
 
-        // Should pipe prompt and beads workflow content through Claude CLI
-        assert!(command.contains("ralph-prompt.md") || command.contains("PROMPT.md"));
-        assert!(command.contains("ralph-beads.md"));
-        assert!(command.contains("--output-format=stream-json"));
-        assert!(command.contains("--print"));
+    #[test]
+    fn cli_status_format_parses() {
+        let cli = Cli::try_parse_from(["ralph", "status"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Status {
+                format: StatusFormat::Plain
+            })
+        ));
+        let cli = Cli::try_parse_from(["ralph", "status", "--format", "tmux"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Status {
+                format: StatusFormat::Tmux
+            })
+        ));
+        assert!(Cli::try_parse_from(["ralph", "status", "--format", "fish"]).is_err());
+    }
+
+    #[test]
+    fn cli_report_id_parses() {
+        let cli = Cli::try_parse_from(["ralph", "report"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Report { id: None })));
+        let cli = Cli::try_parse_from(["ralph", "report", "--id", "abc123"]).unwrap();
+        assert!(
+            matches!(cli.command, Some(Commands::Report { id: Some(ref id) }) if id == "abc123")
+        );
     }
 
     #[test]
@@ -0,0 +1,162 @@
+//! Prometheus metrics via node_exporter's textfile collector (`[metrics] textfile`).
+//!
+//! ralph rewrites the file whenever a session total or the run state
+//! changes, so each finished iteration shows up on the next scrape without
+//! ralph opening a socket. Counters are per session and restart at zero,
+//! which `rate()` and `increase()` already treat as a counter reset.
+
+use std::io;
+use std::path::Path;
+
+use tracing::{debug, warn};
+
+use crate::app::App;
+use crate::config::Config;
+use crate::run_status::{STATES, state_label};
+
+/// The values exported, as last written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsSample {
+    pub repo: String,
+    pub iterations: u32,
+    pub failed_iterations: u32,
+    pub tokens: u64,
+    pub cost_usd: f64,
+    pub state: &'static str,
+}
+
+impl MetricsSample {
+    pub fn capture(app: &App) -> Self {
+        Self {
+            repo: app.repo_path.clone(),
+            iterations: app.exchange_count,
+            failed_iterations: app.failed_iterations,
+            tokens: app.cumulative_tokens,
+            cost_usd: app.session_cost_usd,
+            state: state_label(app),
+        }
+    }
+}
+
+/// Quote a label value per the exposition format.
+fn label(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', r#"\""#)
+        .replace('\n', r"\n")
+}
+
+/// Render `sample` in the Prometheus text exposition format.
+pub fn render(sample: &MetricsSample) -> String {
+    let repo = label(&sample.repo);
+    let mut out = String::new();
+    let mut metric = |name: &str, kind: &str, help: &str, value: String| {
+        out.push_str(&format!(
+            "# HELP {name} {help}\n# TYPE {name} {kind}\n{name}{{repo=\"{repo}\"}} {value}\n"
+        ));
+    };
+    metric(
+        "ralph_iterations_total",
+        "counter",
+        "Iterations finished this session.",
+        sample.iterations.to_string(),
+    );
+    metric(
+        "ralph_failed_iterations_total",
+        "counter",
+        "Iterations this session that ended in an error or a failed command.",
+        sample.failed_iterations.to_string(),
+    );
+    metric(
+        "ralph_tokens_total",
+        "counter",
+        "Input and output tokens used this session.",
+        sample.tokens.to_string(),
+    );
+    metric(
+        "ralph_cost_usd_total",
+        "counter",
+        "Reported cost of this session's iterations in USD.",
+        sample.cost_usd.to_string(),
+    );
+    out.push_str("# HELP ralph_state Current run state (1 for the active state).\n");
+    out.push_str("# TYPE ralph_state gauge\n");
+    for state in STATES {
+        let value = u8::from(state == sample.state);
+        out.push_str(&format!(
+            "ralph_state{{repo=\"{repo}\",state=\"{state}\"}} {value}\n"
+        ));
+    }
+    out
+}
+
+/// Write `contents` via a temp file and rename, so the collector never
+/// scrapes a partial file (it ignores names not ending in `.prom`).
+fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    let tmp = path.with_extension("prom.tmp");
+    std::fs::write(&tmp, contents)?;
+    std::fs::rename(&tmp, path)
+}
+
+impl App {
+    /// Rewrite `[metrics] textfile` if anything it reports has changed.
+    pub fn publish_metrics(&mut self) {
+        let Some(textfile) = &self.config.metrics.textfile else {
+            return;
+        };
+        let written = (Config::expand_tilde(textfile), MetricsSample::capture(self));
+        if self.metrics_sample.as_ref() == Some(&written) {
+            return;
+        }
+        let (path, sample) = &written;
+        match write_atomic(path, &render(sample)) {
+            Ok(()) => debug!(path = %path.display(), "metrics_textfile_written"),
+            Err(e) => warn!(path = %path.display(), error = %e, "metrics_textfile_write_failed"),
+        }
+        // Remember it even on failure so a bad path isn't retried every tick
+        self.metrics_sample = Some(written);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> MetricsSample {
+        MetricsSample {
+            repo: "/code/my \"app\"".into(),
+            iterations: 4,
+            failed_iterations: 1,
+            tokens: 12_345,
+            cost_usd: 0.5,
+            state: "running",
+        }
+    }
+
+    #[test]
+    fn renders_counters_and_one_hot_state() {
+        let text = render(&sample());
+        assert!(text.contains("# TYPE ralph_iterations_total counter\n"));
+        assert!(text.contains(r#"ralph_iterations_total{repo="/code/my \"app\""} 4"#));
+        assert!(text.contains(r#"ralph_failed_iterations_total{repo="/code/my \"app\""} 1"#));
+        assert!(text.contains(r#"ralph_tokens_total{repo="/code/my \"app\""} 12345"#));
+        assert!(text.contains(r#"ralph_cost_usd_total{repo="/code/my \"app\""} 0.5"#));
+        assert!(text.contains(r#"ralph_state{repo="/code/my \"app\"",state="running"} 1"#));
+        assert!(text.contains(r#"ralph_state{repo="/code/my \"app\"",state="idle"} 0"#));
+        assert_eq!(
+            text.lines()
+                .filter(|l| l.starts_with("ralph_state{") && l.ends_with(" 1"))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn writes_atomically() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ralph.prom");
+        write_atomic(&path, "a 1\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a 1\n");
+        assert!(!path.with_extension("prom.tmp").exists());
+    }
+}
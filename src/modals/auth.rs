@@ -0,0 +1,52 @@
+//! Not-authenticated modal — shown when a run stopped because the Claude CLI
+//! has no usable credentials.
+
+use ratatui::Frame;
+use ratatui::layout::Alignment;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+use crate::app::App;
+use crate::ui::{centered_rect, truncate_to_width};
+
+/// Draw the not-authenticated modal.
+pub fn draw_auth_modal(f: &mut Frame, app: &App) {
+    let Some(message) = &app.auth_failure else {
+        return;
+    };
+
+    let modal_width: u16 = 64;
+    let modal_height: u16 = 9;
+    let modal_area = centered_rect(modal_width, modal_height, f.area());
+
+    f.render_widget(Clear, modal_area);
+
+    let key_style = Style::default().fg(Color::Cyan);
+    let dim = Style::default().fg(Color::DarkGray);
+    let detail = truncate_to_width(message, modal_width as usize - 6, "…");
+
+    let content: Vec<Line> = vec![
+        Line::from(""),
+        Line::from("  Claude CLI not authenticated \u{2014} run `claude login`"),
+        Line::from(Span::styled(format!("  {detail}"), dim)),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled("l", key_style),
+            Span::raw(" run claude login  "),
+            Span::styled("Esc", key_style),
+            Span::raw(" dismiss"),
+        ]),
+    ];
+
+    let modal = Paragraph::new(content).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Not Authenticated ")
+            .title_alignment(Alignment::Center)
+            .style(Style::default().fg(Color::Red)),
+    );
+
+    f.render_widget(modal, modal_area);
+}
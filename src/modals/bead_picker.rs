@@ -8,7 +8,8 @@ use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use serde::Deserialize;
 
 use crate::app::App;
-use crate::ui::centered_rect;
+use crate::bd::Bd;
+use crate::ui::{TextInput, centered_rect, truncate_to_width};
 
 /// Minimal bead data for the picker list.
 #[derive(Debug, Clone, Deserialize)]
@@ -30,9 +31,7 @@ pub struct BeadPickerState {
     /// Indices into `items` that match the current filter.
     pub filtered: Vec<usize>,
     /// Filter text input.
-    pub filter: String,
-    /// Cursor position (byte offset) within `filter`.
-    pub cursor_pos: usize,
+    pub filter: TextInput,
     /// Selected index within `filtered`.
     pub selected: usize,
     /// Scroll offset for the list.
@@ -49,8 +48,7 @@ impl BeadPickerState {
         Self {
             items: Vec::new(),
             filtered: Vec::new(),
-            filter: String::new(),
-            cursor_pos: 0,
+            filter: TextInput::default(),
             selected: 0,
             scroll_offset: 0,
             is_loading: true,
@@ -74,7 +72,7 @@ impl BeadPickerState {
 
     /// Recompute `filtered` indices based on current filter text.
     fn update_filter(&mut self) {
-        let query = self.filter.to_lowercase();
+        let query = self.filter.value().to_lowercase();
         self.filtered = self
             .items
             .iter()
@@ -98,46 +96,16 @@ impl BeadPickerState {
     }
 
     fn insert_char(&mut self, c: char) {
-        self.filter.insert(self.cursor_pos, c);
-        self.cursor_pos += c.len_utf8();
+        self.filter.insert_char(c);
         self.update_filter();
     }
 
     fn delete_char_before(&mut self) {
-        if self.cursor_pos > 0 {
-            let prev = self.filter[..self.cursor_pos]
-                .chars()
-                .last()
-                .map(|c| c.len_utf8())
-                .unwrap_or(0);
-            self.cursor_pos -= prev;
-            self.filter.remove(self.cursor_pos);
+        if self.filter.delete_char_before() {
             self.update_filter();
         }
     }
 
-    fn cursor_left(&mut self) {
-        if self.cursor_pos > 0 {
-            let prev = self.filter[..self.cursor_pos]
-                .chars()
-                .last()
-                .map(|c| c.len_utf8())
-                .unwrap_or(0);
-            self.cursor_pos -= prev;
-        }
-    }
-
-    fn cursor_right(&mut self) {
-        if self.cursor_pos < self.filter.len() {
-            let next = self.filter[self.cursor_pos..]
-                .chars()
-                .next()
-                .map(|c| c.len_utf8())
-                .unwrap_or(0);
-            self.cursor_pos += next;
-        }
-    }
-
     fn select_prev(&mut self) {
         if self.selected > 0 {
             self.selected -= 1;
@@ -164,9 +132,9 @@ impl BeadPickerState {
 }
 
 /// Fetch bead list data by running `bd list --json`.
-pub fn fetch_bead_picker_data(bd_path: &str) -> Result<Vec<BeadPickerItem>, String> {
+pub fn fetch_bead_picker_data(bd: &Bd) -> Result<Vec<BeadPickerItem>, String> {
     let output = crate::bd_lock::with_lock(|| {
-        std::process::Command::new(bd_path)
+        bd.command()
             .args(["list", "--json"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -222,16 +190,16 @@ pub fn handle_bead_picker_input(app: &mut App, key_code: KeyCode) {
             state.select_next();
         }
         KeyCode::Left => {
-            state.cursor_left();
+            state.filter.cursor_left();
         }
         KeyCode::Right => {
-            state.cursor_right();
+            state.filter.cursor_right();
         }
         KeyCode::Home => {
-            state.cursor_pos = 0;
+            state.filter.cursor_home();
         }
         KeyCode::End => {
-            state.cursor_pos = state.filter.len();
+            state.filter.cursor_end();
         }
         KeyCode::Backspace => {
             state.delete_char_before();
@@ -257,39 +225,16 @@ pub fn draw_bead_picker(f: &mut Frame, app: &mut App) {
     let mut content: Vec<Line> = Vec::new();
 
     // Filter input line with cursor
-    let filter_display = if state.filter.is_empty() && state.cursor_pos == 0 {
+    let filter_display = if state.filter.is_empty() {
         // Show placeholder when empty
         let cursor = Span::styled(" ", Style::default().fg(Color::Black).bg(Color::White));
         let placeholder = Span::styled(" type to filter...", Style::default().fg(Color::DarkGray));
         Line::from(vec![Span::raw("  > "), cursor, placeholder])
     } else {
-        // Build text with cursor
-        let char_indices: Vec<(usize, char)> = state.filter.char_indices().collect();
-        let visible_cursor = char_indices
-            .iter()
-            .position(|(byte_idx, _)| *byte_idx == state.cursor_pos)
-            .unwrap_or(char_indices.len());
-
-        let (before, cursor_char, after) = if visible_cursor < char_indices.len() {
-            let (idx, _) = char_indices[visible_cursor];
-            let before = &state.filter[..idx];
-            let c = state.filter[idx..].chars().next().unwrap_or(' ');
-            let rest_start = idx + c.len_utf8();
-            let after = &state.filter[rest_start..];
-            (before.to_string(), c.to_string(), after.to_string())
-        } else {
-            (state.filter.clone(), " ".to_string(), String::new())
-        };
-
-        Line::from(vec![
-            Span::raw("  > "),
-            Span::styled(before, Style::default().fg(Color::White)),
-            Span::styled(
-                cursor_char,
-                Style::default().fg(Color::Black).bg(Color::White),
-            ),
-            Span::styled(after, Style::default().fg(Color::White)),
-        ])
+        let width = modal_width.saturating_sub(6) as usize;
+        let mut spans = vec![Span::raw("  > ")];
+        spans.extend(state.filter.spans(width));
+        Line::from(spans)
     };
     content.push(filter_display);
     content.push(Line::from(""));
@@ -356,11 +301,7 @@ pub fn draw_bead_picker(f: &mut Frame, app: &mut App) {
             let inner_width = (modal_width as usize).saturating_sub(4); // borders + padding
             let prefix_width = 2 + 2 + id_width + 1 + 3 + 1; // "  " + status + " " + id + " " + priority + " "
             let title_max = inner_width.saturating_sub(prefix_width);
-            let title = if item.title.len() > title_max {
-                format!("{}…", &item.title[..title_max.saturating_sub(1)])
-            } else {
-                item.title.clone()
-            };
+            let title = truncate_to_width(&item.title, title_max, "…");
 
             // Pad to full width for highlight
             let line_text = format!(
@@ -369,7 +310,8 @@ pub fn draw_bead_picker(f: &mut Frame, app: &mut App) {
                 priority = priority_str,
             );
             let padded_len = inner_width;
-            let pad = padded_len.saturating_sub(line_text.chars().count());
+            let pad = padded_len
+                .saturating_sub(unicode_width::UnicodeWidthStr::width(line_text.as_str()));
 
             let padded = format!("{line_text}{}", " ".repeat(pad));
             content.push(Line::from(Span::styled(padded, line_style)));
@@ -0,0 +1,423 @@
+//! Bead search modal — find a spec by any of its text, not just its title.
+//!
+//! Every bead (closed ones too) is loaded once when the modal opens; each
+//! keystroke re-scans them for the query, case-insensitively. A match shows
+//! the line it was found on so similar titles can be told apart, and `Enter`
+//! moves the board cursor to the bead and focuses its preview.
+
+use crossterm::event::KeyCode;
+use ratatui::Frame;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use serde::Deserialize;
+
+use super::kanban::BoardFocus;
+use crate::app::App;
+use crate::bd::Bd;
+use crate::ui::{TextInput, centered_rect, truncate_to_width};
+
+/// Characters of context kept before the match in a snippet.
+const CONTEXT_BEFORE: usize = 24;
+
+/// The searchable text of one bead.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BeadSearchItem {
+    pub id: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub design: String,
+    #[serde(default)]
+    pub acceptance_criteria: String,
+    #[serde(default)]
+    pub notes: String,
+}
+
+impl BeadSearchItem {
+    /// Body fields in search order, with the label shown on a match.
+    fn fields(&self) -> [(&'static str, &str); 4] {
+        [
+            ("description", &self.description),
+            ("design", &self.design),
+            ("acceptance", &self.acceptance_criteria),
+            ("notes", &self.notes),
+        ]
+    }
+}
+
+/// A bead matching the query and where it matched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    /// Index into the loaded items.
+    pub item: usize,
+    /// Field the first match was in; `None` for the ID or title.
+    pub field: Option<&'static str>,
+    /// The matching line, trimmed to start shortly before the match.
+    pub snippet: String,
+}
+
+/// Beads whose ID, title or body contains `query` (any case), in load order.
+/// An empty query matches nothing.
+pub fn find_matches(items: &[BeadSearchItem], query: &str) -> Vec<SearchMatch> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    items
+        .iter()
+        .enumerate()
+        .filter_map(|(i, item)| {
+            if item.id.to_lowercase().contains(&query) || item.title.to_lowercase().contains(&query)
+            {
+                return Some(SearchMatch {
+                    item: i,
+                    field: None,
+                    snippet: String::new(),
+                });
+            }
+            item.fields().into_iter().find_map(|(field, text)| {
+                let snippet = text.lines().find_map(|line| snippet(line, &query))?;
+                Some(SearchMatch {
+                    item: i,
+                    field: Some(field),
+                    snippet,
+                })
+            })
+        })
+        .collect()
+}
+
+/// `line` from a little before its first match of lowercase `query`, or
+/// `None` without one.
+fn snippet(line: &str, query: &str) -> Option<String> {
+    let lower = line.to_lowercase();
+    let at = lower.find(query)?;
+    // Lowercasing can change byte lengths; count chars on the lowered line
+    let chars_before = lower[..at].chars().count();
+    let skip = chars_before.saturating_sub(CONTEXT_BEFORE);
+    let rest: String = line.chars().skip(skip).collect();
+    let rest = rest.split_whitespace().collect::<Vec<_>>().join(" ");
+    Some(if skip > 0 { format!("…{rest}") } else { rest })
+}
+
+/// State for the bead search modal.
+#[derive(Debug)]
+pub struct BeadSearchState {
+    /// Every bead, loaded when the modal opened.
+    pub items: Vec<BeadSearchItem>,
+    /// Matches for the current query.
+    pub matches: Vec<SearchMatch>,
+    /// Query text input.
+    pub query: TextInput,
+    /// Selected index within `matches`.
+    pub selected: usize,
+    /// Scroll offset for the list, in matches.
+    pub scroll_offset: usize,
+    /// Whether data is still loading.
+    pub is_loading: bool,
+    /// Error message if loading failed.
+    pub error: Option<String>,
+}
+
+impl BeadSearchState {
+    /// Create a new state in loading mode.
+    pub fn new_loading() -> Self {
+        Self {
+            items: Vec::new(),
+            matches: Vec::new(),
+            query: TextInput::default(),
+            selected: 0,
+            scroll_offset: 0,
+            is_loading: true,
+            error: None,
+        }
+    }
+
+    /// Populate with loaded data.
+    pub fn populate(&mut self, result: Result<Vec<BeadSearchItem>, String>) {
+        self.is_loading = false;
+        match result {
+            Ok(items) => {
+                self.items = items;
+                self.update_matches();
+            }
+            Err(e) => {
+                self.error = Some(e);
+            }
+        }
+    }
+
+    fn update_matches(&mut self) {
+        self.matches = find_matches(&self.items, self.query.value());
+        self.selected = 0;
+        self.scroll_offset = 0;
+    }
+
+    fn delete_char_before(&mut self) {
+        if self.query.delete_char_before() {
+            self.update_matches();
+        }
+    }
+
+    fn select_next(&mut self) {
+        if self.selected + 1 < self.matches.len() {
+            self.selected += 1;
+        }
+    }
+
+    fn selected_id(&self) -> Option<&str> {
+        let m = self.matches.get(self.selected)?;
+        Some(&self.items[m.item].id)
+    }
+
+    /// Ensure the selected match is visible given how many fit.
+    fn ensure_visible(&mut self, visible: usize) {
+        if visible == 0 {
+            return;
+        }
+        if self.selected < self.scroll_offset {
+            self.scroll_offset = self.selected;
+        } else if self.selected >= self.scroll_offset + visible {
+            self.scroll_offset = self.selected - visible + 1;
+        }
+    }
+}
+
+/// Fetch every bead's text by running `bd list --all --json`.
+pub fn fetch_bead_search_data(bd: &Bd) -> Result<Vec<BeadSearchItem>, String> {
+    let output = crate::bd_lock::with_lock(|| {
+        bd.command()
+            .args(["list", "--all", "--json", "--limit=0"])
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .output()
+    })
+    .map_err(|e| format!("Failed to run bd list: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("bd list failed: {stderr}"));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let trimmed = stdout.trim();
+    if trimmed.is_empty() || trimmed == "null" {
+        return Ok(Vec::new());
+    }
+
+    serde_json::from_str::<Vec<BeadSearchItem>>(trimmed)
+        .map_err(|e| format!("Failed to parse bd list output: {e}"))
+}
+
+/// Handle key input for the bead search modal.
+pub fn handle_bead_search_input(app: &mut App, key_code: KeyCode) {
+    let Some(state) = &mut app.bead_search_state else {
+        return;
+    };
+
+    match key_code {
+        KeyCode::Esc => {
+            app.bead_search_state = None;
+            app.bead_search_rx = None;
+        }
+        KeyCode::Enter => {
+            let Some(id) = state.selected_id().map(str::to_string) else {
+                return;
+            };
+            app.bead_search_state = None;
+            let board = &mut app.kanban_board_state;
+            if board.select_bead(&id) {
+                if board.preview_detail.is_some() {
+                    board.focus = BoardFocus::Preview;
+                }
+            } else {
+                board.set_status(format!("{id} isn't on the board"));
+            }
+        }
+        KeyCode::Up | KeyCode::BackTab => {
+            state.selected = state.selected.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Tab => state.select_next(),
+        KeyCode::Left => state.query.cursor_left(),
+        KeyCode::Right => state.query.cursor_right(),
+        KeyCode::Home => state.query.cursor_home(),
+        KeyCode::End => state.query.cursor_end(),
+        KeyCode::Backspace => state.delete_char_before(),
+        KeyCode::Char(c) => {
+            state.query.insert_char(c);
+            state.update_matches();
+        }
+        _ => {}
+    }
+}
+
+/// Draw the bead search modal.
+pub fn draw_bead_search(f: &mut Frame, app: &mut App) {
+    let Some(state) = &mut app.bead_search_state else {
+        return;
+    };
+
+    let modal_width: u16 = 80;
+    let modal_height: u16 = 24;
+    let modal_area = centered_rect(modal_width, modal_height, f.area());
+    f.render_widget(Clear, modal_area);
+
+    let inner_width = (modal_width as usize).saturating_sub(4);
+    let mut content: Vec<Line> = Vec::new();
+
+    let query_line = if state.query.is_empty() {
+        let cursor = Span::styled(" ", Style::default().fg(Color::Black).bg(Color::White));
+        let placeholder = Span::styled(
+            " search titles, descriptions, notes...",
+            Style::default().fg(Color::DarkGray),
+        );
+        Line::from(vec![Span::raw("  / "), cursor, placeholder])
+    } else {
+        let mut spans = vec![Span::raw("  / ")];
+        spans.extend(state.query.spans(inner_width.saturating_sub(4)));
+        Line::from(spans)
+    };
+    content.push(query_line);
+    content.push(Line::from(""));
+
+    let dim = Style::default().fg(Color::DarkGray);
+    if state.is_loading {
+        content.push(Line::from(Span::styled("  Loading...", dim)));
+    } else if let Some(error) = &state.error {
+        content.push(Line::from(Span::styled(
+            format!("  Error: {error}"),
+            Style::default().fg(Color::Yellow),
+        )));
+    } else if state.matches.is_empty() {
+        let msg = if state.query.is_empty() {
+            format!("  {} beads", state.items.len())
+        } else {
+            "  No matches".to_string()
+        };
+        content.push(Line::from(Span::styled(msg, dim)));
+    } else {
+        // Two lines per match; reserve the filter, blank, footer and borders
+        let visible = (modal_height as usize).saturating_sub(8) / 2;
+        state.ensure_visible(visible);
+
+        for (view_idx, m) in state
+            .matches
+            .iter()
+            .enumerate()
+            .skip(state.scroll_offset)
+            .take(visible)
+        {
+            let item = &state.items[m.item];
+            let line_style = if view_idx == state.selected {
+                Style::default().fg(Color::Black).bg(Color::White)
+            } else {
+                Style::default()
+            };
+            let status_char = match item.status.as_str() {
+                "open" => "○",
+                "in_progress" => "◐",
+                "blocked" => "●",
+                "closed" => "✓",
+                "deferred" => "❄",
+                _ => "·",
+            };
+            let head = format!("  {status_char} {} {}", item.id, item.title);
+            let head = truncate_to_width(&head, inner_width, "…");
+            let pad =
+                inner_width.saturating_sub(unicode_width::UnicodeWidthStr::width(head.as_str()));
+            content.push(Line::from(Span::styled(
+                format!("{head}{}", " ".repeat(pad)),
+                line_style,
+            )));
+
+            let context = match m.field {
+                Some(field) => format!("      {field}: {}", m.snippet),
+                None => String::new(),
+            };
+            content.push(Line::from(Span::styled(
+                truncate_to_width(&context, inner_width, "…"),
+                dim,
+            )));
+        }
+
+        let total = state.matches.len();
+        content.push(Line::from(""));
+        content.push(Line::from(Span::styled(
+            format!(
+                "  {total} match{}  ·  Enter to open",
+                if total == 1 { "" } else { "es" }
+            ),
+            dim,
+        )));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Search Beads ")
+        .title_alignment(ratatui::layout::Alignment::Center)
+        .style(Style::default().fg(Color::White));
+
+    let widget = Paragraph::new(content).block(block);
+    f.render_widget(widget, modal_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bead(id: &str, title: &str, description: &str, notes: &str) -> BeadSearchItem {
+        BeadSearchItem {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: description.to_string(),
+            notes: notes.to_string(),
+            ..BeadSearchItem::default()
+        }
+    }
+
+    #[test]
+    fn matches_id_title_and_body_in_any_case() {
+        let items = vec![
+            bead("ralph-a1", "Export CSV", "", ""),
+            bead(
+                "ralph-b2",
+                "Import",
+                "## Approach\nReuse the csv writer\n",
+                "",
+            ),
+            bead("ralph-c3", "Docs", "", "Mentioned CSV in passing"),
+            bead("ralph-d4", "Unrelated", "nothing here", ""),
+        ];
+        let matches = find_matches(&items, "CSV");
+        let found: Vec<_> = matches.iter().map(|m| (m.item, m.field)).collect();
+        assert_eq!(
+            found,
+            vec![(0, None), (1, Some("description")), (2, Some("notes"))]
+        );
+        assert_eq!(matches[1].snippet, "Reuse the csv writer");
+
+        assert_eq!(find_matches(&items, "B2").len(), 1);
+        assert!(find_matches(&items, "  ").is_empty());
+    }
+
+    #[test]
+    fn snippet_starts_shortly_before_the_match() {
+        let line = format!("{}needle and more", "x ".repeat(30));
+        let s = snippet(&line, "needle").unwrap();
+        assert_eq!(
+            s,
+            format!("…{}needle and more", "x ".repeat(CONTEXT_BEFORE / 2))
+        );
+        assert_eq!(
+            snippet("short needle", "needle").as_deref(),
+            Some("short needle")
+        );
+        assert_eq!(snippet("no match", "needle"), None);
+    }
+}
@@ -8,9 +8,14 @@ use tracing::debug;
 use std::sync::Arc;
 
 use crate::app::{App, AppStatus};
-use crate::config::save_partial_config;
+use crate::bd::Bd;
+use crate::config::{ConfigError, save_partial_config};
+use crate::modals::confirm::draw_confirm_prompt;
 use crate::startup::get_file_mtime;
-use crate::ui::centered_rect;
+use crate::ui::{
+    Button, FormTheme, button_row, centered_rect, choice_spans, render_text_field,
+    text_value_spans, toggle_label,
+};
 use crate::work_source::BeadsWorkSource;
 
 use super::ConfigModalField;
@@ -21,6 +26,18 @@ pub fn handle_config_modal_input(app: &mut App, key_code: KeyCode, modifiers: Ke
         return;
     };
 
+    // "Discard changes?" prompt takes all input while open
+    if state.confirm_discard {
+        match key_code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => close_discarding_preview(app),
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                state.confirm_discard = false;
+            }
+            _ => {}
+        }
+        return;
+    }
+
     // Clear any previous error when user takes action
     if state.error().is_some() && key_code != KeyCode::Esc {
         state.set_error(None);
@@ -41,50 +58,37 @@ pub fn handle_config_modal_input(app: &mut App, key_code: KeyCode, modifiers: Ke
 
         // Cancel / close
         KeyCode::Esc => {
-            app.show_config_modal = false;
-            app.config_modal_state = None;
+            cancel(app);
+        }
+
+        // Open the raw config file in $EDITOR
+        KeyCode::Char('e') if modifiers.contains(KeyModifiers::CONTROL) && app.read_only => {
+            app.deny_in_read_only("Editing the config");
+        }
+        KeyCode::Char('e') if modifiers.contains(KeyModifiers::CONTROL) => {
+            if state.is_dirty() {
+                app.set_hint("Save or discard your edits before opening the config file");
+            } else {
+                app.editor_request = state.project_config_path.clone();
+                close_discarding_preview(app);
+            }
         }
 
         // Enter - context-dependent
         KeyCode::Enter => match state.focus {
             ConfigModalField::SaveButton => {
-                if state.has_validation_errors() || app.status == AppStatus::Running {
-                    return;
+                if save_and_apply(app) {
+                    app.show_config_modal = false;
+                    app.config_modal_state = None;
                 }
-                let partial = state.to_partial_config();
-                let save_result = if let Some(ref path) = state.project_config_path {
-                    save_partial_config(&partial, path)
-                } else {
-                    Err("No project config path".to_string())
-                };
-                match save_result {
-                    Ok(()) => {
-                        let new_merged = state.to_config();
-                        let config_path = state.project_config_path.clone();
-                        if new_merged.behavior.bd_path != app.config.behavior.bd_path {
-                            app.work_source =
-                                Arc::new(BeadsWorkSource::new(new_merged.behavior.bd_path.clone()));
-                        }
-                        if new_merged.behavior.workers as usize != app.workers.len() {
-                            app.reshape_workers_to(new_merged.behavior.workers as usize);
-                        }
-                        app.config = new_merged;
-                        if let Some(ref path) = config_path {
-                            app.project_config_mtime = get_file_mtime(path);
-                            app.project_config_path = Some(path.clone());
-                        }
-                        app.show_config_modal = false;
-                        app.config_modal_state = None;
-                        debug!("Config saved successfully via modal");
-                    }
-                    Err(e) => {
-                        state.set_error(Some(e));
-                    }
+            }
+            ConfigModalField::ApplyButton => {
+                if save_and_apply(app) {
+                    app.set_hint("Config applied");
                 }
             }
             ConfigModalField::CancelButton => {
-                app.show_config_modal = false;
-                app.config_modal_state = None;
+                cancel(app);
             }
             _ => {
                 // Enter in text fields moves to next field
@@ -92,6 +96,14 @@ pub fn handle_config_modal_input(app: &mut App, key_code: KeyCode, modifiers: Ke
             }
         },
 
+        // Ctrl+R: clear the project override for the focused field
+        KeyCode::Char('r') if modifiers.contains(KeyModifiers::CONTROL) => {
+            let reset = state.reset_focused_field();
+            if reset {
+                app.set_hint("Reset to inherited default");
+            }
+        }
+
         // Text input handling
         KeyCode::Char(c) => {
             if matches!(
@@ -120,6 +132,8 @@ pub fn handle_config_modal_input(app: &mut App, key_code: KeyCode, modifiers: Ke
             ConfigModalField::StaleThreshold => state.stale_decrement(),
             ConfigModalField::KeepAwake => state.toggle_keep_awake(),
             ConfigModalField::Workers => state.workers_decrement(),
+            ConfigModalField::ResultPreviewLines => state.preview_lines_decrement(),
+            ConfigModalField::MouseCapture => state.toggle_mouse_capture(),
             _ => state.cursor_left(),
         },
 
@@ -130,6 +144,8 @@ pub fn handle_config_modal_input(app: &mut App, key_code: KeyCode, modifiers: Ke
             ConfigModalField::StaleThreshold => state.stale_increment(),
             ConfigModalField::KeepAwake => state.toggle_keep_awake(),
             ConfigModalField::Workers => state.workers_increment(),
+            ConfigModalField::ResultPreviewLines => state.preview_lines_increment(),
+            ConfigModalField::MouseCapture => state.toggle_mouse_capture(),
             _ => state.cursor_right(),
         },
 
@@ -149,7 +165,11 @@ pub fn handle_config_modal_input(app: &mut App, key_code: KeyCode, modifiers: Ke
             ConfigModalField::StaleThreshold => state.stale_increment(),
             ConfigModalField::KeepAwake => state.toggle_keep_awake(),
             ConfigModalField::Workers => state.workers_increment(),
-            ConfigModalField::SaveButton | ConfigModalField::CancelButton => state.focus_prev(),
+            ConfigModalField::ResultPreviewLines => state.preview_lines_increment(),
+            ConfigModalField::MouseCapture => state.toggle_mouse_capture(),
+            ConfigModalField::SaveButton
+            | ConfigModalField::ApplyButton
+            | ConfigModalField::CancelButton => state.focus_prev(),
             _ => {}
         },
 
@@ -160,18 +180,98 @@ pub fn handle_config_modal_input(app: &mut App, key_code: KeyCode, modifiers: Ke
             ConfigModalField::StaleThreshold => state.stale_decrement(),
             ConfigModalField::KeepAwake => state.toggle_keep_awake(),
             ConfigModalField::Workers => state.workers_decrement(),
-            ConfigModalField::SaveButton | ConfigModalField::CancelButton => state.focus_next(),
+            ConfigModalField::ResultPreviewLines => state.preview_lines_decrement(),
+            ConfigModalField::MouseCapture => state.toggle_mouse_capture(),
+            ConfigModalField::SaveButton
+            | ConfigModalField::ApplyButton
+            | ConfigModalField::CancelButton => state.focus_next(),
             _ => {}
         },
 
         _ => {}
     }
+
+    // Live preview: display settings take effect while the modal is open
+    if let Some(state) = &app.config_modal_state {
+        let lines = state.form.display.result_preview_lines;
+        if lines != app.config.display.result_preview_lines {
+            app.set_result_preview_lines(lines);
+        }
+    }
+}
+
+/// Save the form to the project config and apply it to the running app.
+///
+/// Returns `false` if nothing was saved (validation errors, a running loop, or
+/// a write error, which is shown on the form).
+fn save_and_apply(app: &mut App) -> bool {
+    let Some(state) = &mut app.config_modal_state else {
+        return false;
+    };
+    if state.has_validation_errors() || app.status == AppStatus::Running {
+        return false;
+    }
+    if app.read_only {
+        state.set_error(Some("Read-only mode: saving is disabled".to_string()));
+        return false;
+    }
+    let partial = state.to_partial_config();
+    let save_result = if let Some(ref path) = state.project_config_path {
+        save_partial_config(&partial, path)
+    } else {
+        Err(ConfigError::NoConfigDir)
+    };
+    if let Err(e) = save_result {
+        state.set_error(Some(e.to_string()));
+        return false;
+    }
+
+    let new_merged = state.to_config();
+    let config_path = state.project_config_path.clone();
+    state.mark_saved();
+    if new_merged.behavior.bd_path != app.config.behavior.bd_path {
+        app.work_source = Arc::new(BeadsWorkSource::new(Bd::new(
+            new_merged.behavior.bd_path.clone(),
+            app.project_dir.clone(),
+        )));
+    }
+    if new_merged.behavior.workers as usize != app.workers.len() {
+        app.reshape_workers_to(new_merged.behavior.workers as usize);
+    }
+    app.apply_display_config(new_merged.display.clone());
+    if new_merged.behavior.keep_awake != app.config.behavior.keep_awake {
+        app.wake_lock.set_enabled(new_merged.behavior.keep_awake);
+    }
+    app.config = new_merged;
+    if let Some(ref path) = config_path {
+        app.project_config_mtime = get_file_mtime(path);
+        app.config_base_mtimes = crate::app::config_base_mtimes(Some(path));
+        app.project_config_path = Some(path.clone());
+    }
+    debug!("Config saved successfully via modal");
+    true
+}
+
+/// Cancel: close straight away, or ask first when there are unsaved edits.
+fn cancel(app: &mut App) {
+    match &mut app.config_modal_state {
+        Some(state) if state.is_dirty() => state.confirm_discard = true,
+        _ => close_discarding_preview(app),
+    }
+}
+
+/// Close the modal without saving, undoing any live display preview.
+fn close_discarding_preview(app: &mut App) {
+    if let Some(state) = app.config_modal_state.take() {
+        app.apply_display_config(state.saved_display);
+    }
+    app.show_config_modal = false;
 }
 
 /// Draw the configuration modal.
 pub fn draw_config_modal(f: &mut Frame, app: &App) {
     let modal_width = 70;
-    let modal_height = 34;
+    let modal_height = 35;
     let modal_area = centered_rect(modal_width, modal_height, f.area());
 
     // Clear the area behind the modal
@@ -189,139 +289,40 @@ pub fn draw_config_modal(f: &mut Frame, app: &App) {
 
     let separator = "─".repeat(modal_width.saturating_sub(4) as usize);
     let field_width = 40;
-
-    // Determine which fields are explicitly set (vs inherited from defaults)
-    let explicit_fields = state.map(|s| &s.form.explicit_fields);
+    let theme = FormTheme::default();
+    let label_style = Style::default().fg(theme.muted);
 
     // Check if a field is inherited (not explicitly set in project config)
     let is_inherited = |field: ConfigModalField| -> bool {
-        if let Some(fields) = explicit_fields {
-            !fields.contains(&field)
-        } else {
-            false
-        }
+        state.is_some_and(|s| !s.form.explicit_fields.contains(&field))
     };
 
-    // Helper to render a text input field - returns owned Spans
-    let render_field =
-        |value: &str, focused: bool, cursor_pos: usize, inherited: bool| -> Vec<Span<'static>> {
-            let display_value: String = if value.len() > field_width {
-                let start = cursor_pos.saturating_sub(field_width / 2);
-                let end = (start + field_width).min(value.len());
-                let start = end.saturating_sub(field_width);
-                value[start..end].to_string()
-            } else {
-                value.to_string()
-            };
-
-            let visible_cursor = if value.len() > field_width {
-                let start = cursor_pos.saturating_sub(field_width / 2);
-                let end = (start + field_width).min(value.len());
-                let start = end.saturating_sub(field_width);
-                cursor_pos - start
-            } else {
-                cursor_pos
-            };
-
-            if focused {
-                let char_indices: Vec<_> = display_value.char_indices().collect();
-                let (before, cursor_char, rest) = if visible_cursor < char_indices.len() {
-                    let (idx, _) = char_indices[visible_cursor];
-                    let before = display_value[..idx].to_string();
-                    let cursor_char = display_value[idx..]
-                        .chars()
-                        .next()
-                        .unwrap_or(' ')
-                        .to_string();
-                    let rest_start = idx + cursor_char.len();
-                    let rest = if rest_start < display_value.len() {
-                        display_value[rest_start..].to_string()
-                    } else {
-                        String::new()
-                    };
-                    (before, cursor_char, rest)
-                } else {
-                    (display_value.clone(), " ".to_string(), String::new())
-                };
-
-                vec![
-                    Span::styled(before, Style::default().fg(Color::White)),
-                    Span::styled(
-                        cursor_char,
-                        Style::default().fg(Color::Black).bg(Color::White),
-                    ),
-                    Span::styled(rest, Style::default().fg(Color::White)),
-                ]
-            } else {
-                let fg = if inherited {
-                    Color::DarkGray
-                } else {
-                    Color::White
-                };
-                vec![Span::styled(display_value, Style::default().fg(fg))]
-            }
-        };
-
-    // Helper for label styling
-    let label_style = Style::default().fg(Color::DarkGray);
-    let focused_label_style = Style::default().fg(Color::Cyan);
-
-    // Get active form values
+    // Get active form values (or the live config when there's no form state)
     let form = state.map(|s| s.active_form());
-    let (
-        claude_path,
-        bd_path,
-        log_level,
-        iterations,
-        heartbeat_interval,
-        stale_threshold,
-        keep_awake,
-        cursor_pos,
-        focus,
-        has_errors,
-    ): (
-        &str,
-        &str,
-        &str,
-        i32,
-        u64,
-        u64,
-        bool,
-        usize,
-        Option<ConfigModalField>,
-        bool,
-    ) = if let Some(s) = state {
-        let f = s.active_form();
-        (
+    let focus = state.map(|s| s.focus);
+    let has_errors = state.is_some_and(|s| s.has_validation_errors());
+    let (claude_path, bd_path, log_level, cursor_pos) = match form {
+        Some(f) => (
             f.claude_path.as_str(),
             f.bd_path.as_str(),
             f.selected_log_level(),
-            f.iterations,
-            f.heartbeat_interval,
-            f.stale_threshold,
-            f.keep_awake,
             f.cursor_pos,
-            Some(s.focus),
-            s.has_validation_errors(),
-        )
-    } else {
-        (
+        ),
+        None => (
             app.config.claude.path.as_str(),
             app.config.behavior.bd_path.as_str(),
             app.config.logging.level.as_str(),
-            app.config.behavior.iterations,
-            app.config.behavior.heartbeat_interval,
-            app.config.behavior.stale_threshold,
-            app.config.behavior.keep_awake,
             0,
-            None,
-            false,
-        )
+        ),
     };
-
-    let workers = state
-        .map(|s| s.active_form().workers)
-        .unwrap_or(app.config.behavior.workers);
+    let behavior = form.map(|f| f.to_config().behavior);
+    let behavior = behavior.as_ref().unwrap_or(&app.config.behavior);
+    let result_preview_lines = form
+        .map(|f| f.display.result_preview_lines)
+        .unwrap_or(app.config.display.result_preview_lines);
+    let mouse_capture = form
+        .map(|f| f.mouse_capture)
+        .unwrap_or(app.config.keys.mouse_capture);
 
     // Helper to get validation error for a field
     let get_field_error = |field: ConfigModalField| -> Option<&str> {
@@ -349,240 +350,107 @@ pub fn draw_config_modal(f: &mut Frame, app: &App) {
     ]));
     content.push(Line::from(format!("  {separator}")));
 
-    // Claude CLI path field
-    let path_focused = focus == Some(ConfigModalField::ClaudePath);
-    let path_inherited = is_inherited(ConfigModalField::ClaudePath);
-    let path_label_style = if path_focused {
-        focused_label_style
-    } else {
-        label_style
-    };
-    let mut path_line = vec![Span::styled("  Claude CLI path: ", path_label_style)];
-    path_line.extend(render_field(
-        claude_path,
-        path_focused,
-        cursor_pos,
-        path_inherited,
-    ));
-    if path_inherited && !path_focused {
-        path_line.push(Span::styled(" (inherited)", label_style));
-    }
-    content.push(Line::from(path_line));
-    if let Some(error) = get_field_error(ConfigModalField::ClaudePath) {
-        content.push(Line::from(Span::styled(
-            format!("                     \u{26a0} {}", error),
-            error_style,
-        )));
-    }
-
-    // bd path field
-    let bd_focused = focus == Some(ConfigModalField::BdPath);
-    let bd_inherited = is_inherited(ConfigModalField::BdPath);
-    let bd_label_style = if bd_focused {
-        focused_label_style
-    } else {
-        label_style
-    };
-    let mut bd_line = vec![Span::styled("  bd path:          ", bd_label_style)];
-    bd_line.extend(render_field(bd_path, bd_focused, cursor_pos, bd_inherited));
-    if bd_inherited && !bd_focused {
-        bd_line.push(Span::styled(" (inherited)", label_style));
-    }
-    content.push(Line::from(bd_line));
-    if let Some(error) = get_field_error(ConfigModalField::BdPath) {
-        content.push(Line::from(Span::styled(
-            format!("                     \u{26a0} {}", error),
-            error_style,
-        )));
-    }
-
-    // Log level dropdown
-    let level_focused = focus == Some(ConfigModalField::LogLevel);
-    let level_inherited = is_inherited(ConfigModalField::LogLevel);
-    let level_label_style = if level_focused {
-        focused_label_style
-    } else {
-        label_style
-    };
-    let level_display = if level_focused {
-        format!("< {} >", log_level)
-    } else {
-        log_level.to_string()
-    };
-    let level_value_style = if level_focused {
-        Style::default().fg(Color::Cyan)
-    } else if level_inherited {
-        Style::default().fg(Color::DarkGray)
-    } else {
-        Style::default().fg(Color::White)
-    };
-    let mut level_line = vec![
-        Span::styled("  Log level:       ", level_label_style),
-        Span::styled(level_display, level_value_style),
-    ];
-    if level_inherited && !level_focused {
-        level_line.push(Span::styled(" (inherited)", label_style));
+    // Text fields, each followed by its validation error if any
+    for (field, label, value) in [
+        (
+            ConfigModalField::ClaudePath,
+            "  Claude CLI path: ",
+            claude_path,
+        ),
+        (ConfigModalField::BdPath, "  bd path:          ", bd_path),
+    ] {
+        let focused = focus == Some(field);
+        let mut line = vec![theme.label(label, focused)];
+        if focused {
+            line.extend(render_text_field(value, cursor_pos, field_width));
+        } else {
+            line.extend(text_value_spans(
+                value,
+                field_width,
+                is_inherited(field),
+                &theme,
+            ));
+        }
+        content.push(Line::from(line));
+        if let Some(error) = get_field_error(field) {
+            content.push(Line::from(Span::styled(
+                format!("                     \u{26a0} {}", error),
+                error_style,
+            )));
+        }
     }
-    content.push(Line::from(level_line));
 
-    // Iterations field
-    let iter_focused = focus == Some(ConfigModalField::Iterations);
-    let iter_inherited = is_inherited(ConfigModalField::Iterations);
-    let iter_label_style = if iter_focused {
-        focused_label_style
-    } else {
-        label_style
-    };
-    let iter_value = if iterations < 0 {
+    // Select / stepper / toggle fields, with an optional trailing note
+    let iterations = if behavior.iterations < 0 {
         "\u{221e}".to_string()
     } else {
-        iterations.to_string()
-    };
-    let iter_display = if iter_focused {
-        format!("< {} >", iter_value)
-    } else {
-        iter_value
-    };
-    let iter_value_style = if iter_focused {
-        Style::default().fg(Color::Cyan)
-    } else if iter_inherited {
-        Style::default().fg(Color::DarkGray)
-    } else {
-        Style::default().fg(Color::White)
-    };
-    let mut iter_line = vec![
-        Span::styled("  Iterations:      ", iter_label_style),
-        Span::styled(iter_display, iter_value_style),
-    ];
-    if iter_inherited && !iter_focused {
-        iter_line.push(Span::styled(" (inherited)", label_style));
-    }
-    content.push(Line::from(iter_line));
-
-    // Heartbeat interval field
-    let hb_focused = focus == Some(ConfigModalField::HeartbeatInterval);
-    let hb_inherited = is_inherited(ConfigModalField::HeartbeatInterval);
-    let hb_label_style = if hb_focused {
-        focused_label_style
-    } else {
-        label_style
-    };
-    let hb_display = if hb_focused {
-        format!("< {} >", heartbeat_interval)
-    } else {
-        heartbeat_interval.to_string()
-    };
-    let hb_value_style = if hb_focused {
-        Style::default().fg(Color::Cyan)
-    } else if hb_inherited {
-        Style::default().fg(Color::DarkGray)
-    } else {
-        Style::default().fg(Color::White)
-    };
-    let mut hb_line = vec![
-        Span::styled("  Heartbeat (s):   ", hb_label_style),
-        Span::styled(hb_display, hb_value_style),
-    ];
-    if hb_inherited && !hb_focused {
-        hb_line.push(Span::styled(" (inherited)", label_style));
-    }
-    hb_line.push(Span::styled(
-        " (applies at next relaunch)",
-        Style::default().fg(Color::DarkGray),
-    ));
-    content.push(Line::from(hb_line));
-
-    // Stale threshold field
-    let st_focused = focus == Some(ConfigModalField::StaleThreshold);
-    let st_inherited = is_inherited(ConfigModalField::StaleThreshold);
-    let st_label_style = if st_focused {
-        focused_label_style
-    } else {
-        label_style
-    };
-    let st_display = if st_focused {
-        format!("< {} >", stale_threshold)
-    } else {
-        stale_threshold.to_string()
-    };
-    let st_value_style = if st_focused {
-        Style::default().fg(Color::Cyan)
-    } else if st_inherited {
-        Style::default().fg(Color::DarkGray)
-    } else {
-        Style::default().fg(Color::White)
+        behavior.iterations.to_string()
     };
-    let mut st_line = vec![
-        Span::styled("  Stale (s):       ", st_label_style),
-        Span::styled(st_display, st_value_style),
-    ];
-    if st_inherited && !st_focused {
-        st_line.push(Span::styled(" (inherited)", label_style));
-    }
-    st_line.push(Span::styled(
-        " (applies at next loop start)",
-        Style::default().fg(Color::DarkGray),
-    ));
-    content.push(Line::from(st_line));
-
-    // Keep awake toggle
-    let keep_awake_focused = focus == Some(ConfigModalField::KeepAwake);
-    let keep_awake_inherited = is_inherited(ConfigModalField::KeepAwake);
-    let keep_awake_label_style = if keep_awake_focused {
-        focused_label_style
-    } else {
-        label_style
-    };
-    let keep_awake_value = if keep_awake { "ON" } else { "OFF" };
-    let keep_awake_display = if keep_awake_focused {
-        format!("< {} >", keep_awake_value)
-    } else {
-        keep_awake_value.to_string()
-    };
-    let keep_awake_value_style = if keep_awake_focused {
-        Style::default().fg(Color::Cyan)
-    } else if keep_awake_inherited {
-        Style::default().fg(Color::DarkGray)
-    } else {
-        Style::default().fg(Color::White)
-    };
-    let mut keep_awake_line = vec![
-        Span::styled("  Keep awake:        ", keep_awake_label_style),
-        Span::styled(keep_awake_display, keep_awake_value_style),
-    ];
-    if keep_awake_inherited && !keep_awake_focused {
-        keep_awake_line.push(Span::styled(" (inherited)", label_style));
-    }
-    content.push(Line::from(keep_awake_line));
-
-    // Workers field
-    let workers_focused = focus == Some(ConfigModalField::Workers);
-    let workers_inherited = is_inherited(ConfigModalField::Workers);
-    let workers_label_style = if workers_focused {
-        focused_label_style
-    } else {
-        label_style
-    };
-    let workers_display = if workers_focused {
-        format!("< {} >", workers)
-    } else {
-        workers.to_string()
-    };
-    let workers_value_style = if workers_focused {
-        Style::default().fg(Color::Cyan)
-    } else if workers_inherited {
-        Style::default().fg(Color::DarkGray)
-    } else {
-        Style::default().fg(Color::White)
-    };
-    let mut workers_line = vec![
-        Span::styled("  Workers:           ", workers_label_style),
-        Span::styled(workers_display, workers_value_style),
+    let choices = [
+        (
+            ConfigModalField::LogLevel,
+            "  Log level:       ",
+            log_level.to_string(),
+            None,
+        ),
+        (
+            ConfigModalField::Iterations,
+            "  Iterations:      ",
+            iterations,
+            None,
+        ),
+        (
+            ConfigModalField::HeartbeatInterval,
+            "  Heartbeat (s):   ",
+            behavior.heartbeat_interval.to_string(),
+            Some(" (applies at next relaunch)"),
+        ),
+        (
+            ConfigModalField::StaleThreshold,
+            "  Stale (s):       ",
+            behavior.stale_threshold.to_string(),
+            Some(" (applies at next loop start)"),
+        ),
+        (
+            ConfigModalField::KeepAwake,
+            "  Keep awake:        ",
+            toggle_label(behavior.keep_awake).to_string(),
+            None,
+        ),
+        (
+            ConfigModalField::Workers,
+            "  Workers:           ",
+            behavior.workers.to_string(),
+            None,
+        ),
+        (
+            ConfigModalField::ResultPreviewLines,
+            "  Result lines:      ",
+            result_preview_lines.to_string(),
+            Some(" (live preview)"),
+        ),
+        (
+            ConfigModalField::MouseCapture,
+            "  Mouse capture:     ",
+            toggle_label(mouse_capture).to_string(),
+            Some(" (off allows text selection)"),
+        ),
     ];
-    if workers_inherited && !workers_focused {
-        workers_line.push(Span::styled(" (inherited)", label_style));
+    for (field, label, value, note) in choices {
+        let focused = focus == Some(field);
+        let mut line = vec![theme.label(label, focused)];
+        line.extend(choice_spans(&value, focused, is_inherited(field), &theme));
+        if let Some(note) = note {
+            line.push(Span::styled(note, label_style));
+        }
+        content.push(Line::from(line));
+        if let Some(error) = get_field_error(field) {
+            content.push(Line::from(Span::styled(
+                format!("                     \u{26a0} {}", error),
+                error_style,
+            )));
+        }
     }
-    content.push(Line::from(workers_line));
 
     content.push(Line::from(""));
 
@@ -598,42 +466,31 @@ pub fn draw_config_modal(f: &mut Frame, app: &App) {
     }
 
     // Error message if any
-    if let Some(s) = state {
-        if let Some(error) = s.error() {
-            content.push(Line::from(Span::styled(
-                format!("  Error: {}", error),
-                Style::default().fg(Color::Red),
-            )));
-        } else {
-            content.push(Line::from(""));
-        }
-    } else {
-        content.push(Line::from(""));
+    match state.and_then(|s| s.error()) {
+        Some(error) => content.push(Line::from(Span::styled(
+            format!("  Error: {}", error),
+            Style::default().fg(Color::Red),
+        ))),
+        None => content.push(Line::from("")),
     }
 
-    // Buttons
-    let save_focused = focus == Some(ConfigModalField::SaveButton);
-    let cancel_focused = focus == Some(ConfigModalField::CancelButton);
-
-    let save_style = if has_errors || is_running {
-        Style::default().fg(Color::DarkGray)
-    } else if save_focused {
-        Style::default().fg(Color::Black).bg(Color::Cyan)
-    } else {
-        Style::default().fg(Color::Cyan)
-    };
-    let cancel_style = if cancel_focused {
-        Style::default().fg(Color::Black).bg(Color::White)
-    } else {
-        Style::default().fg(Color::White)
+    // Buttons (Save and Apply are disabled while invalid or running)
+    let can_save = !has_errors && !is_running;
+    let button = |label, field, primary: bool| Button {
+        label,
+        focused: focus == Some(field),
+        enabled: can_save || !primary,
+        primary,
     };
-
-    content.push(Line::from(vec![
-        Span::raw("                    "),
-        Span::styled(" Save ", save_style),
-        Span::raw("    "),
-        Span::styled(" Cancel ", cancel_style),
-    ]));
+    content.push(button_row(
+        &[
+            button("Save", ConfigModalField::SaveButton, true),
+            button("Apply", ConfigModalField::ApplyButton, true),
+            button("Cancel", ConfigModalField::CancelButton, false),
+        ],
+        15,
+        &theme,
+    ));
 
     content.push(Line::from(""));
 
@@ -648,6 +505,10 @@ pub fn draw_config_modal(f: &mut Frame, app: &App) {
     );
 
     f.render_widget(modal, modal_area);
+
+    if state.is_some_and(|s| s.confirm_discard) {
+        draw_confirm_prompt(f, "Unsaved changes", "Discard changes?");
+    }
 }
 
 #[cfg(test)]
@@ -672,8 +533,14 @@ mod tests {
         let field = field.next();
         assert_eq!(field, ConfigModalField::Workers);
         let field = field.next();
+        assert_eq!(field, ConfigModalField::ResultPreviewLines);
+        let field = field.next();
+        assert_eq!(field, ConfigModalField::MouseCapture);
+        let field = field.next();
         assert_eq!(field, ConfigModalField::SaveButton);
         let field = field.next();
+        assert_eq!(field, ConfigModalField::ApplyButton);
+        let field = field.next();
         assert_eq!(field, ConfigModalField::CancelButton);
         // Wraparound
         let field = field.next();
@@ -694,8 +561,14 @@ mod tests {
         let field = field.prev();
         assert_eq!(field, ConfigModalField::CancelButton);
         let field = field.prev();
+        assert_eq!(field, ConfigModalField::ApplyButton);
+        let field = field.prev();
         assert_eq!(field, ConfigModalField::SaveButton);
         let field = field.prev();
+        assert_eq!(field, ConfigModalField::MouseCapture);
+        let field = field.prev();
+        assert_eq!(field, ConfigModalField::ResultPreviewLines);
+        let field = field.prev();
         assert_eq!(field, ConfigModalField::Workers);
         let field = field.prev();
         assert_eq!(field, ConfigModalField::KeepAwake);
@@ -732,7 +605,10 @@ mod tests {
             ConfigModalField::StaleThreshold,
             ConfigModalField::KeepAwake,
             ConfigModalField::Workers,
+            ConfigModalField::ResultPreviewLines,
+            ConfigModalField::MouseCapture,
             ConfigModalField::SaveButton,
+            ConfigModalField::ApplyButton,
             ConfigModalField::CancelButton,
         ];
 
@@ -1,18 +1,32 @@
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
-use crate::config::{Config, PartialConfig};
-use crate::validators::validate_executable_path;
+use crate::config::{
+    BudgetConfig, Config, ControlConfig, DiskConfig, DisplayConfig, EncryptionConfig, HooksConfig,
+    KeysConfig, MAX_RESULT_PREVIEW_LINES, MetricsConfig, NetworkConfig, PartialBehaviorConfig,
+    PartialBudgetConfig, PartialClaudeConfig, PartialConfig, PartialControlConfig,
+    PartialDiskConfig, PartialDisplayConfig, PartialEncryptionConfig, PartialHooksConfig,
+    PartialKeysConfig, PartialLoggingConfig, PartialMetricsConfig, PartialNetworkConfig,
+    PartialScheduleConfig, PartialTrackerConfig, ScheduleConfig, TrackerConfig,
+};
+use crate::ui::{next_boundary, prev_boundary};
+use crate::validators::{
+    HEARTBEAT_MAX, HEARTBEAT_MIN, ITERATIONS_MAX, ITERATIONS_MIN, STALE_MAX, STALE_MIN,
+    WORKERS_MAX, WORKERS_MIN, validate_executable_path, validate_heartbeat_interval,
+    validate_iterations, validate_result_preview_lines, validate_stale_threshold, validate_workers,
+};
 
 /// Log level options for the dropdown.
 pub const LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error"];
 
-const HEARTBEAT_MIN: u64 = 1;
-const HEARTBEAT_MAX: u64 = 9999;
-const STALE_MIN: u64 = 1;
-const STALE_MAX: u64 = 9999;
-const WORKERS_MIN: u32 = 1;
-const WORKERS_MAX: u32 = 8;
+/// Stepper fields with bounds checked by [`ConfigModalState::validate_field`].
+const NUMERIC_FIELDS: [ConfigModalField; 5] = [
+    ConfigModalField::Iterations,
+    ConfigModalField::HeartbeatInterval,
+    ConfigModalField::StaleThreshold,
+    ConfigModalField::Workers,
+    ConfigModalField::ResultPreviewLines,
+];
 
 /// Per-tab form state storing field values and validation state.
 #[derive(Debug, Clone)]
@@ -25,11 +39,79 @@ pub struct TabFormState {
     pub stale_threshold: u64,
     pub keep_awake: bool,
     pub workers: u32,
+    pub mouse_capture: bool,
+    /// Cursor position (byte offset, always on a char boundary) within the focused text field.
     pub cursor_pos: usize,
     pub error: Option<String>,
     pub validation_errors: HashMap<ConfigModalField, String>,
     /// Fields explicitly set in this tab (only meaningful for project tab).
     pub explicit_fields: HashSet<ConfigModalField>,
+    /// Resolved `[display]` settings; only `result_preview_lines` is editable here.
+    pub display: DisplayConfig,
+    /// Project `[display]` overrides, written back on save.
+    pub display_overrides: PartialDisplayConfig,
+    /// Resolved `[schedule]` settings (not editable here).
+    pub schedule: ScheduleConfig,
+    /// Project `[schedule]` overrides, written back on save.
+    pub schedule_overrides: PartialScheduleConfig,
+    /// Resolved `[budget]` settings (not editable here).
+    pub budget: BudgetConfig,
+    /// Project `[budget]` overrides, written back on save.
+    pub budget_overrides: PartialBudgetConfig,
+    /// Resolved `[control]` settings (not editable here).
+    pub control: ControlConfig,
+    /// Project `[control]` overrides, written back on save.
+    pub control_overrides: PartialControlConfig,
+    /// Resolved `[network]` settings (not editable here).
+    pub network: NetworkConfig,
+    /// Project `[network]` overrides, written back on save.
+    pub network_overrides: PartialNetworkConfig,
+    /// Resolved `[disk]` settings (not editable here).
+    pub disk: DiskConfig,
+    /// Project `[disk]` overrides, written back on save.
+    pub disk_overrides: PartialDiskConfig,
+    /// Resolved `[metrics]` settings (not editable here).
+    pub metrics: MetricsConfig,
+    /// Project `[metrics]` overrides, written back on save.
+    pub metrics_overrides: PartialMetricsConfig,
+    /// Resolved `[tracker]` settings (not editable here).
+    pub tracker: TrackerConfig,
+    /// Project `[tracker]` overrides, written back on save.
+    pub tracker_overrides: PartialTrackerConfig,
+    /// Resolved `[encryption]` settings (not editable here).
+    pub encryption: EncryptionConfig,
+    /// Project `[encryption]` overrides, written back on save.
+    pub encryption_overrides: PartialEncryptionConfig,
+    /// Resolved `[hooks]` settings (not editable here).
+    pub hooks: HooksConfig,
+    /// Project `[hooks]` overrides, written back on save.
+    pub hooks_overrides: PartialHooksConfig,
+    /// Resolved `claude.pty` (not editable here).
+    pub claude_pty: bool,
+    /// Project `[claude]` overrides, for the fields not editable here.
+    pub claude_overrides: PartialClaudeConfig,
+    /// Resolved `logging.debug_on_error_minutes` (not editable here).
+    pub debug_on_error_minutes: u64,
+    /// Project `[logging]` overrides, for the fields not editable here.
+    pub logging_overrides: PartialLoggingConfig,
+    /// Resolved `behavior.resume_conversation` (not editable here).
+    pub resume_conversation: bool,
+    /// Resolved `behavior.backup_files` (not editable here).
+    pub backup_files: bool,
+    /// Resolved `behavior.confirm_dirty_start` (not editable here).
+    pub confirm_dirty_start: bool,
+    /// Resolved `behavior.stop_grace_secs` (not editable here).
+    pub stop_grace_secs: u64,
+    /// Resolved `behavior.iteration_timeout_secs` (not editable here).
+    pub iteration_timeout_secs: u64,
+    /// Resolved `behavior.continue_after_timeout` (not editable here).
+    pub continue_after_timeout: bool,
+    /// Resolved `behavior.pause_on_question` (not editable here).
+    pub pause_on_question: bool,
+    /// Project `[behavior]` overrides, for the fields not editable here.
+    pub behavior_overrides: PartialBehaviorConfig,
+    /// Project `extends` path, written back on save.
+    pub extends: Option<String>,
 }
 
 /// Which field is focused in the config modal.
@@ -43,7 +125,10 @@ pub enum ConfigModalField {
     StaleThreshold,
     KeepAwake,
     Workers,
+    ResultPreviewLines,
+    MouseCapture,
     SaveButton,
+    ApplyButton,
     CancelButton,
 }
 
@@ -57,8 +142,11 @@ impl ConfigModalField {
             Self::HeartbeatInterval => Self::StaleThreshold,
             Self::StaleThreshold => Self::KeepAwake,
             Self::KeepAwake => Self::Workers,
-            Self::Workers => Self::SaveButton,
-            Self::SaveButton => Self::CancelButton,
+            Self::Workers => Self::ResultPreviewLines,
+            Self::ResultPreviewLines => Self::MouseCapture,
+            Self::MouseCapture => Self::SaveButton,
+            Self::SaveButton => Self::ApplyButton,
+            Self::ApplyButton => Self::CancelButton,
             Self::CancelButton => Self::ClaudePath,
         }
     }
@@ -73,8 +161,11 @@ impl ConfigModalField {
             Self::StaleThreshold => Self::HeartbeatInterval,
             Self::KeepAwake => Self::StaleThreshold,
             Self::Workers => Self::KeepAwake,
-            Self::SaveButton => Self::Workers,
-            Self::CancelButton => Self::SaveButton,
+            Self::ResultPreviewLines => Self::Workers,
+            Self::MouseCapture => Self::ResultPreviewLines,
+            Self::SaveButton => Self::MouseCapture,
+            Self::ApplyButton => Self::SaveButton,
+            Self::CancelButton => Self::ApplyButton,
         }
     }
 }
@@ -88,6 +179,12 @@ pub struct ConfigModalState {
     pub form: TabFormState,
     /// Path to the per-project config file, if available.
     pub project_config_path: Option<PathBuf>,
+    /// `[display]` as last saved; restored on cancel after a live preview.
+    pub saved_display: DisplayConfig,
+    /// Form as loaded (or last applied), for detecting unsaved edits.
+    loaded: TabFormState,
+    /// "Discard changes?" prompt is showing.
+    pub confirm_discard: bool,
 }
 
 impl TabFormState {
@@ -120,6 +217,12 @@ impl TabFormState {
         if partial.behavior.workers.is_some() {
             explicit_fields.insert(ConfigModalField::Workers);
         }
+        if partial.display.result_preview_lines.is_some() {
+            explicit_fields.insert(ConfigModalField::ResultPreviewLines);
+        }
+        if partial.keys.mouse_capture.is_some() {
+            explicit_fields.insert(ConfigModalField::MouseCapture);
+        }
 
         // Display merged values (so inherited fields show their effective value)
         let log_level_index = LOG_LEVELS
@@ -136,10 +239,44 @@ impl TabFormState {
             stale_threshold: merged.behavior.stale_threshold,
             keep_awake: merged.behavior.keep_awake,
             workers: merged.behavior.workers,
+            mouse_capture: merged.keys.mouse_capture,
             cursor_pos: merged.claude.path.len(),
             error: None,
             validation_errors: HashMap::new(),
             explicit_fields,
+            display: merged.display.clone(),
+            display_overrides: partial.display.clone(),
+            schedule: merged.schedule.clone(),
+            schedule_overrides: partial.schedule.clone(),
+            budget: merged.budget.clone(),
+            budget_overrides: partial.budget.clone(),
+            control: merged.control.clone(),
+            control_overrides: partial.control.clone(),
+            network: merged.network.clone(),
+            network_overrides: partial.network.clone(),
+            disk: merged.disk.clone(),
+            disk_overrides: partial.disk.clone(),
+            metrics: merged.metrics.clone(),
+            metrics_overrides: partial.metrics.clone(),
+            tracker: merged.tracker.clone(),
+            tracker_overrides: partial.tracker.clone(),
+            encryption: merged.encryption.clone(),
+            encryption_overrides: partial.encryption.clone(),
+            hooks: merged.hooks.clone(),
+            hooks_overrides: partial.hooks.clone(),
+            claude_pty: merged.claude.pty,
+            claude_overrides: partial.claude.clone(),
+            debug_on_error_minutes: merged.logging.debug_on_error_minutes,
+            logging_overrides: partial.logging.clone(),
+            resume_conversation: merged.behavior.resume_conversation,
+            backup_files: merged.behavior.backup_files,
+            confirm_dirty_start: merged.behavior.confirm_dirty_start,
+            stop_grace_secs: merged.behavior.stop_grace_secs,
+            iteration_timeout_secs: merged.behavior.iteration_timeout_secs,
+            continue_after_timeout: merged.behavior.continue_after_timeout,
+            pause_on_question: merged.behavior.pause_on_question,
+            behavior_overrides: partial.behavior.clone(),
+            extends: partial.extends.clone(),
         }
     }
 
@@ -149,11 +286,26 @@ impl TabFormState {
             claude: crate::config::ClaudeConfig {
                 path: self.claude_path.clone(),
                 args: None,
+                pty: self.claude_pty,
             },
             logging: crate::config::LoggingConfig {
                 level: self.selected_log_level().to_string(),
+                debug_on_error_minutes: self.debug_on_error_minutes,
             },
             behavior: crate::config::BehaviorConfig::default(),
+            display: self.display.clone(),
+            schedule: self.schedule.clone(),
+            budget: self.budget.clone(),
+            control: self.control.clone(),
+            network: self.network.clone(),
+            disk: self.disk.clone(),
+            metrics: self.metrics.clone(),
+            tracker: self.tracker.clone(),
+            encryption: self.encryption.clone(),
+            hooks: self.hooks.clone(),
+            keys: KeysConfig {
+                mouse_capture: self.mouse_capture,
+            },
         };
         config.behavior.iterations = self.iterations;
         config.behavior.keep_awake = self.keep_awake;
@@ -161,18 +313,27 @@ impl TabFormState {
         config.behavior.heartbeat_interval = self.heartbeat_interval;
         config.behavior.stale_threshold = self.stale_threshold;
         config.behavior.workers = self.workers;
+        config.behavior.resume_conversation = self.resume_conversation;
+        config.behavior.backup_files = self.backup_files;
+        config.behavior.confirm_dirty_start = self.confirm_dirty_start;
+        config.behavior.stop_grace_secs = self.stop_grace_secs;
+        config.behavior.iteration_timeout_secs = self.iteration_timeout_secs;
+        config.behavior.continue_after_timeout = self.continue_after_timeout;
+        config.behavior.pause_on_question = self.pause_on_question;
         config
     }
 
     /// Build a PartialConfig from the current form values, including only explicit fields.
     pub(super) fn to_partial_config(&self) -> PartialConfig {
         PartialConfig {
+            extends: self.extends.clone(),
             claude: crate::config::PartialClaudeConfig {
                 path: if self.explicit_fields.contains(&ConfigModalField::ClaudePath) {
                     Some(self.claude_path.clone())
                 } else {
                     None
                 },
+                ..self.claude_overrides.clone()
             },
             logging: crate::config::PartialLoggingConfig {
                 level: if self.explicit_fields.contains(&ConfigModalField::LogLevel) {
@@ -180,6 +341,7 @@ impl TabFormState {
                 } else {
                     None
                 },
+                ..self.logging_overrides.clone()
             },
             behavior: crate::config::PartialBehaviorConfig {
                 iterations: if self.explicit_fields.contains(&ConfigModalField::Iterations) {
@@ -218,10 +380,55 @@ impl TabFormState {
                 } else {
                     None
                 },
+                ..self.behavior_overrides.clone()
+            },
+            display: PartialDisplayConfig {
+                result_preview_lines: if self
+                    .explicit_fields
+                    .contains(&ConfigModalField::ResultPreviewLines)
+                {
+                    Some(self.display.result_preview_lines)
+                } else {
+                    None
+                },
+                ..self.display_overrides.clone()
+            },
+            schedule: self.schedule_overrides.clone(),
+            budget: self.budget_overrides.clone(),
+            control: self.control_overrides.clone(),
+            network: self.network_overrides.clone(),
+            disk: self.disk_overrides.clone(),
+            metrics: self.metrics_overrides.clone(),
+            tracker: self.tracker_overrides.clone(),
+            encryption: self.encryption_overrides.clone(),
+            hooks: self.hooks_overrides.clone(),
+            keys: PartialKeysConfig {
+                mouse_capture: if self
+                    .explicit_fields
+                    .contains(&ConfigModalField::MouseCapture)
+                {
+                    Some(self.mouse_capture)
+                } else {
+                    None
+                },
             },
         }
     }
 
+    /// Whether every editable value matches `other` (ignores cursor and errors).
+    fn same_values(&self, other: &TabFormState) -> bool {
+        self.claude_path == other.claude_path
+            && self.bd_path == other.bd_path
+            && self.log_level_index == other.log_level_index
+            && self.iterations == other.iterations
+            && self.heartbeat_interval == other.heartbeat_interval
+            && self.stale_threshold == other.stale_threshold
+            && self.keep_awake == other.keep_awake
+            && self.workers == other.workers
+            && self.display.result_preview_lines == other.display.result_preview_lines
+            && self.mouse_capture == other.mouse_capture
+    }
+
     pub fn selected_log_level(&self) -> &'static str {
         LOG_LEVELS[self.log_level_index]
     }
@@ -239,11 +446,63 @@ impl ConfigModalState {
         merged_config: &Config,
         project_config_path: Option<PathBuf>,
     ) -> Self {
-        Self {
+        let form = TabFormState::from_partial_config(partial, merged_config);
+        let mut state = Self {
             focus: ConfigModalField::ClaudePath,
-            form: TabFormState::from_partial_config(partial, merged_config),
+            loaded: form.clone(),
+            form,
             project_config_path,
+            saved_display: merged_config.display.clone(),
+            confirm_discard: false,
+        };
+        // Flag out-of-range numbers from the project file right away
+        for field in NUMERIC_FIELDS {
+            state.validate_field(field);
         }
+        state
+    }
+
+    /// Whether any field differs from the loaded (or last applied) values.
+    pub fn is_dirty(&self) -> bool {
+        !self.form.same_values(&self.loaded)
+    }
+
+    /// Clear the focused field's project override so it goes back to inherited,
+    /// showing the compiled-in default. Returns `false` for buttons.
+    pub fn reset_focused_field(&mut self) -> bool {
+        let defaults =
+            TabFormState::from_partial_config(&PartialConfig::default(), &Config::default());
+        let field = self.focus;
+        let form = &mut self.form;
+        match field {
+            ConfigModalField::ClaudePath => form.claude_path = defaults.claude_path,
+            ConfigModalField::BdPath => form.bd_path = defaults.bd_path,
+            ConfigModalField::LogLevel => form.log_level_index = defaults.log_level_index,
+            ConfigModalField::Iterations => form.iterations = defaults.iterations,
+            ConfigModalField::HeartbeatInterval => {
+                form.heartbeat_interval = defaults.heartbeat_interval
+            }
+            ConfigModalField::StaleThreshold => form.stale_threshold = defaults.stale_threshold,
+            ConfigModalField::KeepAwake => form.keep_awake = defaults.keep_awake,
+            ConfigModalField::Workers => form.workers = defaults.workers,
+            ConfigModalField::ResultPreviewLines => {
+                form.display.result_preview_lines = defaults.display.result_preview_lines
+            }
+            ConfigModalField::MouseCapture => form.mouse_capture = defaults.mouse_capture,
+            ConfigModalField::SaveButton
+            | ConfigModalField::ApplyButton
+            | ConfigModalField::CancelButton => return false,
+        }
+        form.explicit_fields.remove(&field);
+        form.validation_errors.remove(&field);
+        self.update_cursor_for_new_focus();
+        true
+    }
+
+    /// Record the current form as saved, after Apply.
+    pub fn mark_saved(&mut self) {
+        self.loaded = self.form.clone();
+        self.saved_display = self.form.display.clone();
     }
 
     /// Get a reference to the form state.
@@ -300,6 +559,14 @@ impl ConfigModalState {
         self.form.explicit_fields.insert(self.focus);
     }
 
+    /// After a stepper changes the focused field: mark it explicit and
+    /// re-check its bounds, so an out-of-range value loaded from a file
+    /// shows its error until it is stepped back in range.
+    fn stepped(&mut self) {
+        self.mark_explicit();
+        self.validate_field(self.focus);
+    }
+
     /// Insert a character at the current cursor position.
     pub fn insert_char(&mut self, c: char) {
         let cursor = self.active_form().cursor_pos;
@@ -312,12 +579,9 @@ impl ConfigModalState {
                 } else {
                     &mut form.bd_path
                 };
-                if cursor >= field.len() {
-                    field.push(c);
-                } else {
-                    field.insert(cursor, c);
-                }
-                form.cursor_pos += 1;
+                let cursor = cursor.min(field.len());
+                field.insert(cursor, c);
+                form.cursor_pos = cursor + c.len_utf8();
                 true
             }
             _ => false,
@@ -343,8 +607,9 @@ impl ConfigModalState {
                 } else {
                     &mut form.bd_path
                 };
-                field.remove(cursor - 1);
-                form.cursor_pos -= 1;
+                let prev = prev_boundary(field, cursor);
+                field.remove(prev);
+                form.cursor_pos = prev;
                 true
             }
             _ => false,
@@ -384,20 +649,17 @@ impl ConfigModalState {
 
     /// Move cursor left within the current field.
     pub fn cursor_left(&mut self) {
-        let form = self.active_form_mut();
-        if form.cursor_pos > 0 {
-            form.cursor_pos -= 1;
+        if let Some(value) = self.current_field_value() {
+            let prev = prev_boundary(value, self.active_form().cursor_pos);
+            self.active_form_mut().cursor_pos = prev;
         }
     }
 
     /// Move cursor right within the current field.
     pub fn cursor_right(&mut self) {
         if let Some(value) = self.current_field_value() {
-            let len = value.len();
-            let form = self.active_form_mut();
-            if form.cursor_pos < len {
-                form.cursor_pos += 1;
-            }
+            let next = next_boundary(value, self.active_form().cursor_pos);
+            self.active_form_mut().cursor_pos = next;
         }
     }
 
@@ -439,19 +701,19 @@ impl ConfigModalState {
     /// Increment iterations value (towards positive/larger countdown).
     pub fn iterations_increment(&mut self) {
         let form = self.active_form_mut();
-        if form.iterations < 999 {
+        if form.iterations < ITERATIONS_MAX {
             form.iterations += 1;
         }
-        self.mark_explicit();
+        self.stepped();
     }
 
     /// Decrement iterations value (towards -1/infinite).
     pub fn iterations_decrement(&mut self) {
         let form = self.active_form_mut();
-        if form.iterations > -1 {
+        if form.iterations > ITERATIONS_MIN {
             form.iterations -= 1;
         }
-        self.mark_explicit();
+        self.stepped();
     }
 
     pub fn heartbeat_increment(&mut self) {
@@ -459,7 +721,7 @@ impl ConfigModalState {
         if form.heartbeat_interval < HEARTBEAT_MAX {
             form.heartbeat_interval += 1;
         }
-        self.mark_explicit();
+        self.stepped();
     }
 
     pub fn heartbeat_decrement(&mut self) {
@@ -467,7 +729,7 @@ impl ConfigModalState {
         if form.heartbeat_interval > HEARTBEAT_MIN {
             form.heartbeat_interval -= 1;
         }
-        self.mark_explicit();
+        self.stepped();
     }
 
     pub fn stale_increment(&mut self) {
@@ -475,7 +737,7 @@ impl ConfigModalState {
         if form.stale_threshold < STALE_MAX {
             form.stale_threshold += 1;
         }
-        self.mark_explicit();
+        self.stepped();
     }
 
     pub fn stale_decrement(&mut self) {
@@ -483,7 +745,7 @@ impl ConfigModalState {
         if form.stale_threshold > STALE_MIN {
             form.stale_threshold -= 1;
         }
-        self.mark_explicit();
+        self.stepped();
     }
 
     pub fn workers_increment(&mut self) {
@@ -491,7 +753,7 @@ impl ConfigModalState {
         if form.workers < WORKERS_MAX {
             form.workers += 1;
         }
-        self.mark_explicit();
+        self.stepped();
     }
 
     pub fn workers_decrement(&mut self) {
@@ -499,7 +761,21 @@ impl ConfigModalState {
         if form.workers > WORKERS_MIN {
             form.workers -= 1;
         }
-        self.mark_explicit();
+        self.stepped();
+    }
+
+    pub fn preview_lines_increment(&mut self) {
+        let form = self.active_form_mut();
+        if form.display.result_preview_lines < MAX_RESULT_PREVIEW_LINES {
+            form.display.result_preview_lines += 1;
+        }
+        self.stepped();
+    }
+
+    pub fn preview_lines_decrement(&mut self) {
+        let form = self.active_form_mut();
+        form.display.result_preview_lines = form.display.result_preview_lines.saturating_sub(1);
+        self.stepped();
     }
 
     /// Check if there are any validation errors.
@@ -524,6 +800,15 @@ impl ConfigModalState {
                     None
                 }
             }
+            ConfigModalField::Iterations => validate_iterations(form.iterations),
+            ConfigModalField::HeartbeatInterval => {
+                validate_heartbeat_interval(form.heartbeat_interval)
+            }
+            ConfigModalField::StaleThreshold => validate_stale_threshold(form.stale_threshold),
+            ConfigModalField::Workers => validate_workers(form.workers),
+            ConfigModalField::ResultPreviewLines => {
+                validate_result_preview_lines(form.display.result_preview_lines)
+            }
             _ => None,
         };
 
@@ -567,6 +852,13 @@ impl ConfigModalState {
         form.keep_awake = !form.keep_awake;
         self.mark_explicit();
     }
+
+    /// Toggle mouse capture.
+    pub fn toggle_mouse_capture(&mut self) {
+        let form = self.active_form_mut();
+        form.mouse_capture = !form.mouse_capture;
+        self.mark_explicit();
+    }
 }
 
 #[cfg(test)]
@@ -582,6 +874,135 @@ mod tests {
         make_state(&PartialConfig::default(), &Config::default())
     }
 
+    // -- Dirty tracking tests --
+
+    #[test]
+    fn fresh_state_is_clean() {
+        assert!(!default_state().is_dirty());
+    }
+
+    #[test]
+    fn edit_marks_dirty_and_reverting_cleans() {
+        let mut state = default_state();
+        state.focus = ConfigModalField::Workers;
+        state.workers_increment();
+        assert!(state.is_dirty());
+        state.workers_decrement();
+        assert!(!state.is_dirty());
+    }
+
+    #[test]
+    fn mark_saved_resets_dirty() {
+        let mut state = default_state();
+        state.toggle_keep_awake();
+        assert!(state.is_dirty());
+        state.mark_saved();
+        assert!(!state.is_dirty());
+    }
+
+    // -- Reset tests --
+
+    #[test]
+    fn reset_clears_override_and_restores_default() {
+        let mut partial = PartialConfig::default();
+        partial.behavior.workers = Some(4);
+        let mut merged = Config::default();
+        merged.behavior.workers = 4;
+        let mut state = make_state(&partial, &merged);
+        state.focus = ConfigModalField::Workers;
+
+        assert!(state.reset_focused_field());
+        assert_eq!(state.form.workers, Config::default().behavior.workers);
+        assert!(
+            !state
+                .form
+                .explicit_fields
+                .contains(&ConfigModalField::Workers)
+        );
+        assert_eq!(state.to_partial_config().behavior.workers, None);
+        assert!(state.is_dirty());
+    }
+
+    #[test]
+    fn reset_clears_validation_error() {
+        let mut state = default_state();
+        state.focus = ConfigModalField::BdPath;
+        state.cursor_end();
+        for _ in 0..10 {
+            state.delete_char_before();
+        }
+        state.focus_next();
+        state.focus_prev();
+        assert!(state.has_validation_errors());
+        state.reset_focused_field();
+        assert!(!state.has_validation_errors());
+        assert_eq!(state.form.bd_path, "bd");
+    }
+
+    #[test]
+    fn reset_ignores_buttons() {
+        let mut state = default_state();
+        state.focus = ConfigModalField::SaveButton;
+        assert!(!state.reset_focused_field());
+    }
+
+    // -- ResultPreviewLines tests --
+
+    #[test]
+    fn preview_lines_in_partial_when_explicit() {
+        let mut state = default_state();
+        state.focus = ConfigModalField::ResultPreviewLines;
+        state.preview_lines_increment();
+        let out = state.to_partial_config();
+        assert_eq!(out.display.result_preview_lines, Some(4));
+        assert_eq!(state.saved_display.result_preview_lines, 3);
+    }
+
+    #[test]
+    fn preview_lines_keeps_other_display_overrides() {
+        let mut partial = PartialConfig::default();
+        partial.display.tool_input_width = Some(30);
+        let state = make_state(&partial, &Config::default());
+        let out = state.to_partial_config();
+        assert_eq!(out.display.result_preview_lines, None);
+        assert_eq!(out.display.tool_input_width, Some(30));
+    }
+
+    #[test]
+    fn preview_lines_clamps_at_bounds() {
+        let mut state = default_state();
+        state.focus = ConfigModalField::ResultPreviewLines;
+        for _ in 0..5 {
+            state.preview_lines_decrement();
+        }
+        assert_eq!(state.form.display.result_preview_lines, 0);
+        state.form.display.result_preview_lines = MAX_RESULT_PREVIEW_LINES;
+        state.preview_lines_increment();
+        assert_eq!(
+            state.form.display.result_preview_lines,
+            MAX_RESULT_PREVIEW_LINES
+        );
+    }
+
+    // -- MouseCapture tests --
+
+    #[test]
+    fn mouse_capture_inherited_until_toggled() {
+        let mut merged = Config::default();
+        merged.keys.mouse_capture = false;
+        let mut state = make_state(&PartialConfig::default(), &merged);
+        assert!(!state.form.mouse_capture);
+        assert_eq!(state.to_partial_config().keys.mouse_capture, None);
+
+        state.focus = ConfigModalField::MouseCapture;
+        state.toggle_mouse_capture();
+        assert_eq!(state.to_partial_config().keys.mouse_capture, Some(true));
+        assert!(state.to_config().keys.mouse_capture);
+
+        assert!(state.reset_focused_field());
+        assert_eq!(state.to_partial_config().keys.mouse_capture, None);
+    }
+
     // -- BdPath round-trip tests --
 
     #[test]
@@ -707,9 +1128,9 @@ mod tests {
     fn stale_threshold_clamps_min() {
         let mut state = default_state();
         state.focus = ConfigModalField::StaleThreshold;
-        state.active_form_mut().stale_threshold = 1;
+        state.active_form_mut().stale_threshold = 10;
         state.stale_decrement();
-        assert_eq!(state.active_form().stale_threshold, 1);
+        assert_eq!(state.active_form().stale_threshold, 10);
     }
 
     #[test]
@@ -737,6 +1158,39 @@ mod tests {
         assert!(out.behavior.stale_threshold.is_some());
     }
 
+    #[test]
+    fn out_of_range_stale_threshold_from_file_is_flagged_until_stepped_in_range() {
+        let mut partial = PartialConfig::default();
+        partial.behavior.stale_threshold = Some(8);
+        let mut merged = Config::default();
+        merged.behavior.stale_threshold = 8;
+        let mut state = make_state(&partial, &merged);
+        assert!(
+            state
+                .active_form()
+                .validation_errors
+                .contains_key(&ConfigModalField::StaleThreshold)
+        );
+        assert!(state.has_validation_errors());
+
+        state.focus = ConfigModalField::StaleThreshold;
+        state.stale_increment();
+        assert!(state.has_validation_errors());
+        state.stale_increment();
+        assert_eq!(state.active_form().stale_threshold, 10);
+        assert!(!state.has_validation_errors());
+    }
+
+    #[test]
+    fn inherited_numbers_are_clamped_on_load_not_flagged() {
+        let mut merged = Config::default();
+        merged.behavior.workers = 20;
+        assert_eq!(crate::validators::clamp_numbers(&mut merged).len(), 1);
+        let state = make_state(&PartialConfig::default(), &merged);
+        assert_eq!(state.form.workers, crate::validators::WORKERS_MAX);
+        assert!(!state.has_validation_errors());
+    }
+
     // -- Workers tests --
 
     #[test]
@@ -784,4 +1238,42 @@ mod tests {
         state.workers_decrement();
         assert_eq!(state.active_form().workers, 1);
     }
+
+    #[test]
+    fn multibyte_insert_and_backspace_round_trip() {
+        let mut state = default_state();
+        state.focus = ConfigModalField::ClaudePath;
+        state.active_form_mut().claude_path = String::new();
+        state.active_form_mut().cursor_pos = 0;
+
+        for c in "日本🎉".chars() {
+            state.insert_char(c);
+        }
+        assert_eq!(state.active_form().claude_path, "日本🎉");
+        assert_eq!(state.active_form().cursor_pos, "日本🎉".len());
+
+        state.delete_char_before();
+        assert_eq!(state.active_form().claude_path, "日本");
+        assert_eq!(state.active_form().cursor_pos, "日本".len());
+    }
+
+    #[test]
+    fn cursor_moves_by_whole_chars_over_multibyte() {
+        let mut state = default_state();
+        state.focus = ConfigModalField::ClaudePath;
+        state.active_form_mut().claude_path = "a—b".to_string();
+        state.active_form_mut().cursor_pos = 0;
+
+        state.cursor_right();
+        state.cursor_right();
+        assert_eq!(state.active_form().cursor_pos, "a—".len());
+
+        state.insert_char('x');
+        assert_eq!(state.active_form().claude_path, "a—xb");
+
+        state.cursor_left();
+        state.cursor_left();
+        state.delete_char_at();
+        assert_eq!(state.active_form().claude_path, "axb");
+    }
 }
@@ -0,0 +1,46 @@
+//! Generic yes/no confirmation prompt, drawn over whatever is below it.
+
+use ratatui::Frame;
+use ratatui::layout::Alignment;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use unicode_width::UnicodeWidthStr;
+
+use crate::ui::centered_rect;
+
+/// Draw a small centered `question` box with `y yes  n no` choices.
+///
+/// Input is handled by the caller; by convention `y`/`Y` confirms and
+/// `n`/`N`/`Esc` cancels.
+pub fn draw_confirm_prompt(f: &mut Frame, title: &str, question: &str) {
+    let modal_width = (question.width() as u16 + 6).max(30);
+    let modal_height: u16 = 5;
+    let modal_area = centered_rect(modal_width, modal_height, f.area());
+
+    f.render_widget(Clear, modal_area);
+
+    let key_style = Style::default().fg(Color::Cyan);
+
+    let content: Vec<Line> = vec![
+        Line::from(""),
+        Line::from(format!("  {}", question)),
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled("y", key_style),
+            Span::raw(" yes  "),
+            Span::styled("n", key_style),
+            Span::raw(" no"),
+        ]),
+    ];
+
+    let modal = Paragraph::new(content).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", title))
+            .title_alignment(Alignment::Center)
+            .style(Style::default().fg(Color::White)),
+    );
+
+    f.render_widget(modal, modal_area);
+}
@@ -0,0 +1,216 @@
+//! Diagnostics modal — the most recent tracing events from the in-memory log ring.
+
+use crossterm::event::KeyCode;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use tracing::Level;
+
+use crate::app::App;
+use crate::log_ring::{self, LogEntry};
+use crate::ui::truncate_to_width;
+
+/// State for the diagnostics modal.
+pub struct DiagnosticsState {
+    entries: Vec<LogEntry>,
+    /// Lines scrolled back from the newest entry.
+    scroll_back: usize,
+    /// Show only WARN and ERROR events.
+    warnings_only: bool,
+}
+
+impl DiagnosticsState {
+    /// Snapshot the global ring. Opens filtered to warnings when there are any.
+    pub fn new() -> Self {
+        Self::from_entries(log_ring::global().snapshot())
+    }
+
+    fn from_entries(entries: Vec<LogEntry>) -> Self {
+        let warnings_only = entries.iter().any(LogEntry::is_warning);
+        Self {
+            entries,
+            scroll_back: 0,
+            warnings_only,
+        }
+    }
+
+    /// Entries passing the current filter, oldest first.
+    fn visible(&self) -> Vec<&LogEntry> {
+        self.entries
+            .iter()
+            .filter(|e| !self.warnings_only || e.is_warning())
+            .collect()
+    }
+
+    fn scroll_up(&mut self, amount: usize) {
+        let max = self.visible().len().saturating_sub(1);
+        self.scroll_back = self.scroll_back.saturating_add(amount).min(max);
+    }
+
+    fn scroll_down(&mut self, amount: usize) {
+        self.scroll_back = self.scroll_back.saturating_sub(amount);
+    }
+}
+
+/// Handle keyboard input for the diagnostics modal.
+pub fn handle_diagnostics_input(app: &mut App, key_code: KeyCode) {
+    let Some(state) = &mut app.diagnostics_state else {
+        return;
+    };
+    match key_code {
+        KeyCode::Esc | KeyCode::Char('q') => app.diagnostics_state = None,
+        KeyCode::Char('k') | KeyCode::Up => state.scroll_up(1),
+        KeyCode::Char('j') | KeyCode::Down => state.scroll_down(1),
+        KeyCode::PageUp => state.scroll_up(10),
+        KeyCode::PageDown => state.scroll_down(10),
+        KeyCode::Char('g') | KeyCode::Home => state.scroll_up(usize::MAX),
+        KeyCode::Char('G') | KeyCode::End => state.scroll_back = 0,
+        KeyCode::Char('W') => {
+            state.warnings_only = !state.warnings_only;
+            state.scroll_back = 0;
+        }
+        KeyCode::Char('r') => {
+            *state = DiagnosticsState {
+                warnings_only: state.warnings_only,
+                ..DiagnosticsState::new()
+            }
+        }
+        _ => {}
+    }
+}
+
+fn level_style(level: Level) -> Style {
+    match level {
+        Level::ERROR => Style::default().fg(Color::Red),
+        Level::WARN => Style::default().fg(Color::Yellow),
+        Level::INFO => Style::default().fg(Color::Green),
+        _ => Style::default().fg(Color::DarkGray),
+    }
+}
+
+fn entry_line(entry: &LogEntry, width: usize) -> Line<'static> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let prefix = format!("{} {:<5} ", entry.time_of_day(), entry.level);
+    let message = format!("{} {}", entry.target, entry.message_with_count());
+    Line::from(vec![
+        Span::styled(prefix[..9].to_string(), dim),
+        Span::styled(prefix[9..].to_string(), level_style(entry.level)),
+        Span::raw(truncate_to_width(
+            &message,
+            width.saturating_sub(prefix.len()),
+            "\u{2026}",
+        )),
+    ])
+}
+
+/// Draw the diagnostics modal.
+pub fn draw_diagnostics_modal(f: &mut Frame, app: &App) {
+    let Some(state) = &app.diagnostics_state else {
+        return;
+    };
+
+    let area = f.area();
+    let modal_area = Rect {
+        x: area.x + 2,
+        y: area.y + 1,
+        width: area.width.saturating_sub(4),
+        height: area.height.saturating_sub(2),
+    };
+    f.render_widget(Clear, modal_area);
+
+    let visible = state.visible();
+    let key_style = Style::default().fg(Color::Cyan);
+    let dim = Style::default().fg(Color::DarkGray);
+    let filter = if state.warnings_only {
+        "warnings"
+    } else {
+        "all"
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            " Diagnostics \u{b7} {} {} of {} events ",
+            visible.len(),
+            filter,
+            state.entries.len()
+        ))
+        .title_bottom(Line::from(vec![
+            Span::styled(" W", key_style),
+            Span::styled(" warnings/all  ", dim),
+            Span::styled("r", key_style),
+            Span::styled(" refresh  ", dim),
+            Span::styled("j/k", key_style),
+            Span::styled(" scroll  ", dim),
+            Span::styled("Esc", key_style),
+            Span::styled(" close ", dim),
+        ]))
+        .style(Style::default().fg(Color::White));
+    let inner = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    if visible.is_empty() {
+        f.render_widget(
+            Paragraph::new(Line::styled("  No events recorded yet", dim)),
+            inner,
+        );
+        return;
+    }
+
+    let height = inner.height as usize;
+    let width = inner.width as usize;
+    let end = visible.len().saturating_sub(state.scroll_back);
+    let start = end.saturating_sub(height);
+    let lines: Vec<Line> = visible[start..end]
+        .iter()
+        .map(|e| entry_line(e, width))
+        .collect();
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: Level, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: 0,
+            level,
+            target: "ralph".to_string(),
+            message: message.to_string(),
+            count: 1,
+        }
+    }
+
+    #[test]
+    fn opens_filtered_when_warnings_exist() {
+        let state =
+            DiagnosticsState::from_entries(vec![entry(Level::INFO, "a"), entry(Level::WARN, "b")]);
+        assert!(state.warnings_only);
+        assert_eq!(state.visible().len(), 1);
+
+        let state = DiagnosticsState::from_entries(vec![entry(Level::INFO, "a")]);
+        assert!(!state.warnings_only);
+        assert_eq!(state.visible().len(), 1);
+    }
+
+    #[test]
+    fn scroll_is_clamped_to_visible_entries() {
+        let mut state = DiagnosticsState::from_entries(
+            (0..5).map(|i| entry(Level::INFO, &i.to_string())).collect(),
+        );
+        state.scroll_up(100);
+        assert_eq!(state.scroll_back, 4);
+        state.scroll_down(100);
+        assert_eq!(state.scroll_back, 0);
+    }
+
+    #[test]
+    fn entry_line_shows_time_level_and_message() {
+        let line = entry_line(&entry(Level::WARN, "config_parse_failed"), 80);
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "00:00:00 WARN  ralph config_parse_failed");
+        assert_eq!(line.spans[1].style.fg, Some(Color::Yellow));
+    }
+}
@@ -0,0 +1,127 @@
+//! "Working tree is dirty" prompt, shown when starting a run with uncommitted
+//! changes (`behavior.confirm_dirty_start`).
+
+use crossterm::event::KeyCode;
+use ratatui::Frame;
+use ratatui::layout::Alignment;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use tracing::{info, warn};
+
+use crate::app::App;
+use crate::ui::{centered_rect, truncate_to_width};
+
+/// Changed paths listed before the rest are summarized.
+const MAX_LISTED: usize = 5;
+
+/// Handle keyboard input for the dirty tree prompt.
+pub fn handle_dirty_start_input(app: &mut App, key_code: KeyCode) {
+    match key_code {
+        KeyCode::Char('s') => {
+            let Some(changes) = app.dirty_start_prompt.take() else {
+                return;
+            };
+            match crate::dirty_tree::stash(&app.project_dir) {
+                Ok(()) => {
+                    info!(changes = changes.len(), "dirty_tree_stashed");
+                    app.add_text_line(format!(
+                        "[Stashed {} uncommitted change(s) — `git stash pop` restores them]",
+                        changes.len()
+                    ));
+                    app.start_workers();
+                }
+                Err(e) => {
+                    warn!(error = %e, "dirty_tree_stash_failed");
+                    app.set_hint(format!("Stash failed: {e}"));
+                }
+            }
+        }
+        KeyCode::Char('c') => {
+            let Some(changes) = app.dirty_start_prompt.take() else {
+                return;
+            };
+            info!(changes = changes.len(), "dirty_tree_continued");
+            app.warn_dirty_tree(changes.len());
+            app.start_workers();
+        }
+        KeyCode::Char('n') | KeyCode::Esc => {
+            app.dirty_start_prompt = None;
+        }
+        _ => {}
+    }
+}
+
+/// Draw the dirty tree prompt.
+pub fn draw_dirty_start_modal(f: &mut Frame, app: &App) {
+    let Some(changes) = &app.dirty_start_prompt else {
+        return;
+    };
+
+    let modal_width: u16 = 64;
+    let listed = changes.len().min(MAX_LISTED);
+    let more = changes.len() > MAX_LISTED;
+    let modal_height = listed as u16 + u16::from(more) + 7;
+    let modal_area = centered_rect(modal_width, modal_height, f.area());
+
+    f.render_widget(Clear, modal_area);
+
+    let key_style = Style::default().fg(Color::Cyan);
+    let dim = Style::default().fg(Color::DarkGray);
+    let path_width = modal_width.saturating_sub(8) as usize;
+
+    let mut content = vec![
+        Line::from(""),
+        Line::from("  The working tree has uncommitted changes. The agent may"),
+        Line::from("  commit on top of them:"),
+    ];
+    for path in &changes[..listed] {
+        content.push(Line::styled(
+            format!("    {}", truncate_to_width(path, path_width, "\u{2026}")),
+            dim,
+        ));
+    }
+    if more {
+        content.push(Line::styled(
+            format!("    \u{2026} and {} more", changes.len() - MAX_LISTED),
+            dim,
+        ));
+    }
+    content.push(Line::from(""));
+    content.push(Line::from(vec![
+        Span::raw("  "),
+        Span::styled("s", key_style),
+        Span::raw(" stash  "),
+        Span::styled("c", key_style),
+        Span::raw(" continue anyway  "),
+        Span::styled("Esc", key_style),
+        Span::raw(" cancel"),
+    ]));
+
+    let modal = Paragraph::new(content).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Working Tree Is Dirty ")
+            .title_alignment(Alignment::Center)
+            .style(Style::default().fg(Color::Yellow)),
+    );
+
+    f.render_widget(modal, modal_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LoadedConfig;
+
+    #[test]
+    fn cancel_dismisses_without_starting() {
+        let mut app = App::new("test".into(), None, LoadedConfig::default_for_test(), None);
+        app.dirty_start_prompt = Some(vec!["src/app.rs".into()]);
+        handle_dirty_start_input(&mut app, KeyCode::Char('x'));
+        assert!(app.dirty_start_prompt.is_some());
+        handle_dirty_start_input(&mut app, KeyCode::Esc);
+        assert!(app.dirty_start_prompt.is_none());
+        assert!(app.start_workers_rx.is_none());
+    }
+}
@@ -42,7 +42,15 @@ fn system_section() -> Vec<Line<'static>> {
     vec![
         header("System"),
         kv("S", "Start/Stop loop"),
+        kv("P", "Pause/resume the running iteration"),
         kv("q", "Quit"),
+        kv("K", "Toggle keep awake"),
+        kv("C", "Toggle fresh/continued conversation"),
+        kv("I", "Queue instructions for next iteration"),
+        kv("a", "Answer Claude's question (when paused for one)"),
+        kv("L", "Diagnostics (recent log)"),
+        kv("R", "Save diagnostics report"),
+        kv("F12", "Debug overlay"),
         kv("?", "This help"),
     ]
 }
@@ -53,6 +61,12 @@ fn navigate_section() -> Vec<Line<'static>> {
         kv("w", "Workers stream"),
         kv("c", "Configuration"),
         kv("i", "Initialize project"),
+        kv("N", "Shape a new spec with Claude"),
+        kv("e", "Edit PROMPT.md in $EDITOR"),
+        kv("Ctrl+t", "New tab"),
+        kv("Ctrl+w", "Close tab"),
+        kv("Ctrl+d", "Detach (run keeps going)"),
+        kv("1-9", "Switch tab"),
     ]
 }
 
@@ -67,7 +81,10 @@ pub fn content_for(ctx: HelpContext) -> Vec<Line<'static>> {
             lines.push(kv("k / \u{2191}", "Previous card"));
             lines.push(kv("j / \u{2193}", "Next card"));
             lines.push(kv("Enter", "Focus preview pane"));
+            lines.push(kv("Tab / Shift+Tab", "Cycle pane focus"));
+            lines.push(kv("PgUp / PgDn", "Move 10 cards"));
             lines.push(kv("r", "Refresh board"));
+            lines.push(kv("/", "Search all beads"));
             lines.push(kv("X", "Close bead"));
             lines.push(kv("d", "Defer bead"));
             lines.push(kv("b", "Add dependency"));
@@ -85,6 +102,8 @@ pub fn content_for(ctx: HelpContext) -> Vec<Line<'static>> {
             lines.push(header("This view"));
             lines.push(kv("j / \u{2193}", "Scroll down"));
             lines.push(kv("k / \u{2191}", "Scroll up"));
+            lines.push(kv("PgUp / PgDn", "Scroll 10 lines"));
+            lines.push(kv("Tab / Shift+Tab", "Cycle pane focus"));
             lines.push(kv("Esc / Enter", "Return to board"));
             lines.push(Line::from(""));
             lines.extend(navigate_section());
@@ -99,6 +118,17 @@ pub fn content_for(ctx: HelpContext) -> Vec<Line<'static>> {
             lines.push(kv("G", "Scroll to bottom (auto-follow)"));
             lines.push(kv("Ctrl+u", "Scroll up 10 lines"));
             lines.push(kv("Ctrl+d", "Scroll down 10 lines"));
+            lines.push(kv("p", "Expand/collapse prompt context"));
+            lines.push(kv("z", "Toggle line wrapping"));
+            lines.push(kv("h / l", "Scroll left/right (unwrapped)"));
+            lines.push(kv("+ / -", "More/fewer tool result lines"));
+            lines.push(kv("x", "Expand/collapse repeated tool calls"));
+            lines.push(kv("o / O", "Fold/unfold iteration at top / all"));
+            lines.push(kv("d", "Show/hide Edit diffs"));
+            lines.push(kv("V", "Compact/verbose output"));
+            lines.push(kv("y", "Copy last iteration's summary line"));
+            lines.push(kv("r", "Re-run a command from last failed iteration"));
+            lines.push(kv("b", "Restore a file backed up before an edit"));
             lines.push(kv("Esc", "Close modal"));
             lines.push(Line::from(""));
             lines.extend(system_section());
@@ -109,16 +139,25 @@ pub fn content_for(ctx: HelpContext) -> Vec<Line<'static>> {
             lines.push(kv("Shift+Tab", "Previous field"));
             lines.push(kv("\u{2190} / \u{2192}", "Adjust field or move cursor"));
             lines.push(kv("\u{2191} / \u{2193}", "Field nav or cycle options"));
-            lines.push(kv("Enter", "Save / Cancel / next field"));
+            lines.push(kv("Enter", "Save / Apply / Cancel / next field"));
             lines.push(kv("Home / End", "Cursor to start/end"));
-            lines.push(kv("Esc", "Close without saving"));
+            lines.push(kv("Ctrl+r", "Reset field to inherited default"));
+            lines.push(kv("Ctrl+e", "Open config file in $EDITOR"));
+            lines.push(kv("Esc", "Close without saving (confirms if edited)"));
             lines.push(Line::from(""));
             lines.extend(system_section());
         }
         HelpContext::Init => {
             lines.push(header("This view"));
-            lines.push(kv("Tab / \u{2190} / \u{2192}", "Switch buttons"));
-            lines.push(kv("Enter", "Confirm focused button"));
+            lines.push(kv("Tab / Shift+Tab", "Preset / files / buttons"));
+            lines.push(kv("j / k", "Move between files"));
+            lines.push(kv("Space", "Include / exclude file"));
+            lines.push(kv("d", "Show diff against template"));
+            lines.push(kv(
+                "\u{2190} / \u{2192}",
+                "Cycle project type or switch buttons",
+            ));
+            lines.push(kv("Enter", "Toggle file or confirm button"));
             lines.push(kv("Esc", "Close"));
             lines.push(Line::from(""));
             lines.extend(system_section());
@@ -172,7 +211,7 @@ mod tests {
     #[test]
     fn board_contains_all_keys() {
         for key in [
-            "h", "l", "k", "j", "Enter", "X", "d", "b", "+", "-", "H", "u", "Ctrl+r",
+            "h", "l", "k", "j", "Enter", "Tab", "X", "d", "b", "+", "-", "H", "u", "Ctrl+r",
         ] {
             assert!(
                 content_contains(HelpContext::Board, key),
@@ -183,7 +222,7 @@ mod tests {
 
     #[test]
     fn preview_contains_scroll_keys() {
-        for key in ["j", "k", "Esc", "Enter"] {
+        for key in ["j", "k", "PgUp", "Tab", "Esc", "Enter"] {
             assert!(
                 content_contains(HelpContext::Preview, key),
                 "Preview help missing key: {key}"
@@ -213,7 +252,7 @@ mod tests {
 
     #[test]
     fn init_contains_all_keys() {
-        for key in ["Tab", "Enter", "Esc"] {
+        for key in ["Tab", "Space", "d", "Enter", "Esc"] {
             assert!(
                 content_contains(HelpContext::Init, key),
                 "Init help missing key: {key}"
@@ -1,6 +1,7 @@
 //! Init modal — project scaffolding initialization.
 
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
 
 use crossterm::event::KeyCode;
 use ratatui::Frame;
@@ -9,12 +10,47 @@ use ratatui::style::{Color, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use similar::TextDiff;
-use tracing::debug;
+use thiserror::Error;
+use tracing::{debug, warn};
 
 use crate::app::App;
-use crate::config::Config;
-use crate::templates;
-use crate::ui::centered_rect;
+use crate::config::FileOp;
+use crate::file_stamp::{self, FileStamp};
+use crate::templates::{self, ProjectPreset};
+use crate::ui::{FormTheme, centered_rect, choice_spans};
+
+/// Why writing the scaffolding failed.
+#[derive(Debug, Error)]
+pub enum InitError {
+    #[error("Unknown template for: {0}")]
+    UnknownTemplate(String),
+    #[error("Failed to {op} {}: {source}", path.display())]
+    Io {
+        op: FileOp,
+        path: PathBuf,
+        source: io::Error,
+    },
+    #[error("Failed to parse {SETTINGS_PATH}: {0}")]
+    ParseSettings(serde_json::Error),
+    #[error("Failed to serialize {SETTINGS_PATH}: {0}")]
+    SerializeSettings(serde_json::Error),
+    /// The settings file isn't shaped the way Claude writes it.
+    #[error("{SETTINGS_PATH} {0}")]
+    SettingsShape(&'static str),
+    /// Another writer kept changing the settings file mid-update.
+    #[error("{SETTINGS_PATH} kept changing while ralph was updating it; left it unchanged")]
+    SettingsConflict,
+}
+
+impl InitError {
+    fn io(op: FileOp, path: &Path, source: io::Error) -> Self {
+        InitError::Io {
+            op,
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+}
 
 /// Status of a file for the init modal.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -23,7 +59,7 @@ pub enum InitFileStatus {
     WillCreate,
     /// File already exists and matches template (will be skipped).
     Exists,
-    /// File exists and differs from template — will be overwritten.
+    /// File exists and differs from template — overwritten only if selected.
     WillRegenerate,
 }
 
@@ -38,11 +74,18 @@ pub struct InitFileEntry {
     pub status: InitFileStatus,
     /// Unified diff lines (only for WillRegenerate).
     pub diff_lines: Vec<String>,
+    /// Whether init will write this file. Missing files start selected;
+    /// differing files must be opted in to be overwritten.
+    pub selected: bool,
+    /// Whether the diff is expanded in the modal.
+    pub show_diff: bool,
 }
 
 /// Which field is focused in the init modal.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InitModalField {
+    Preset,
+    Files,
     InitializeButton,
     CancelButton,
 }
@@ -50,23 +93,38 @@ pub enum InitModalField {
 impl InitModalField {
     pub fn next(self) -> Self {
         match self {
+            Self::Files => Self::InitializeButton,
             Self::InitializeButton => Self::CancelButton,
-            Self::CancelButton => Self::InitializeButton,
+            Self::CancelButton => Self::Preset,
+            Self::Preset => Self::Files,
         }
     }
 
     pub fn prev(self) -> Self {
-        self.next() // Only two options, so prev == next
+        match self {
+            Self::Preset => Self::CancelButton,
+            Self::Files => Self::Preset,
+            Self::InitializeButton => Self::Files,
+            Self::CancelButton => Self::InitializeButton,
+        }
     }
 }
 
 /// State for the init modal.
 #[derive(Debug, Clone)]
 pub struct InitModalState {
+    /// Project type whose PROMPT.md is scaffolded.
+    pub preset: ProjectPreset,
+    /// The project the files are written into.
+    pub project_dir: PathBuf,
     /// Files to be initialized with their status.
     pub files: Vec<InitFileEntry>,
     /// Current focused field.
     pub focus: InitModalField,
+    /// Highlighted row in the file list.
+    pub cursor: usize,
+    /// Opened to repair an already-scaffolded project (see [`Self::prepare_repair`]).
+    pub repair: bool,
     /// Error message to display.
     pub error: Option<String>,
     /// Success message to display (briefly before closing).
@@ -101,30 +159,61 @@ fn compute_diff(existing: &str, template: &str) -> Vec<String> {
     unified.lines().map(|l| l.to_string()).collect()
 }
 
-/// The list of files managed by init.
-fn managed_files() -> Vec<(String, PathBuf)> {
-    vec![
-        (
-            ".claude/skills/brain-dump/SKILL.md".to_string(),
-            PathBuf::from(".claude/skills/brain-dump/SKILL.md"),
-        ),
-        (
-            ".claude/skills/shape/SKILL.md".to_string(),
-            PathBuf::from(".claude/skills/shape/SKILL.md"),
-        ),
-        (
-            ".claude/skills/capture/SKILL.md".to_string(),
-            PathBuf::from(".claude/skills/capture/SKILL.md"),
-        ),
-        (
-            "scripts/bd-retry.sh".to_string(),
-            PathBuf::from("scripts/bd-retry.sh"),
-        ),
-        (
-            "scripts/intercept-bd.sh".to_string(),
-            PathBuf::from("scripts/intercept-bd.sh"),
-        ),
-    ]
+/// The list of files managed by init, relative to the project directory.
+const MANAGED_FILES: [&str; 5] = [
+    ".claude/skills/brain-dump/SKILL.md",
+    ".claude/skills/shape/SKILL.md",
+    ".claude/skills/capture/SKILL.md",
+    "scripts/bd-retry.sh",
+    "scripts/intercept-bd.sh",
+];
+
+/// The files managed by init in the project in `project_dir`.
+fn managed_files(project_dir: &Path) -> Vec<(String, PathBuf)> {
+    MANAGED_FILES
+        .iter()
+        .map(|path| (path.to_string(), project_dir.join(path)))
+        .collect()
+}
+
+/// Display path of the per-project PROMPT.md written for non-generic presets.
+const PROJECT_PROMPT_DISPLAY: &str = "PROMPT.md (project config)";
+
+/// Managed files for `preset`: the skill files, plus the per-project PROMPT.md
+/// when the preset differs from the compiled-in prompt.
+fn managed_files_for(preset: ProjectPreset, project_dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut files = managed_files(project_dir);
+    if preset != ProjectPreset::Generic
+        && let Some(prompt_path) = crate::config::compute_prompt_path(project_dir)
+    {
+        files.push((PROJECT_PROMPT_DISPLAY.to_string(), prompt_path));
+    }
+    files
+}
+
+/// Build an entry by comparing the file on disk with `template`.
+fn entry_for(display_path: String, full_path: PathBuf, template: &str) -> InitFileEntry {
+    let (status, diff_lines) = if full_path.exists() {
+        let existing = std::fs::read_to_string(&full_path).unwrap_or_default();
+        if existing == template {
+            (InitFileStatus::Exists, vec![])
+        } else {
+            (
+                InitFileStatus::WillRegenerate,
+                compute_diff(&existing, template),
+            )
+        }
+    } else {
+        (InitFileStatus::WillCreate, vec![])
+    };
+    InitFileEntry {
+        display_path,
+        full_path,
+        selected: status == InitFileStatus::WillCreate,
+        status,
+        diff_lines,
+        show_diff: false,
+    }
 }
 
 /// Path to the settings file that init merges the PreToolUse hook into.
@@ -133,48 +222,63 @@ const SETTINGS_PATH: &str = ".claude/settings.json";
 /// Command string used in the PreToolUse hook entry for `intercept-bd.sh`.
 const INTERCEPT_BD_HOOK_COMMAND: &str = "\"$CLAUDE_PROJECT_DIR\"/scripts/intercept-bd.sh";
 
-/// Merge the intercept-bd PreToolUse hook entry into `.claude/settings.json`.
+/// Merge the intercept-bd PreToolUse hook entry into the project's
+/// `.claude/settings.json`.
 ///
 /// Creates the file if missing. If an existing entry with matcher `"Bash"` is
 /// present, appends our hook alongside its current hooks (preserving any
 /// build-intercept entry). Otherwise a new matcher entry is added. No-op if
-/// our hook is already registered.
-fn ensure_intercept_bd_hook_registered() -> Result<(), String> {
-    let path = PathBuf::from(SETTINGS_PATH);
-
-    let mut json: serde_json::Value = if path.exists() {
-        let contents = std::fs::read_to_string(&path)
-            .map_err(|e| format!("Failed to read {SETTINGS_PATH}: {e}"))?;
-        serde_json::from_str(&contents)
-            .map_err(|e| format!("Failed to parse {SETTINGS_PATH}: {e}"))?
-    } else {
-        serde_json::json!({})
-    };
+/// our hook is already registered. If Claude edits the file mid-merge, the
+/// merge is redone on its version instead of overwriting it.
+fn ensure_intercept_bd_hook_registered(project_dir: &Path) -> Result<(), InitError> {
+    let path = project_dir.join(SETTINGS_PATH);
+
+    for attempt in 1..=file_stamp::MAX_ATTEMPTS {
+        let stamp = FileStamp::of(&path).map_err(|e| InitError::io(FileOp::Read, &path, e))?;
+        let mut json: serde_json::Value = if path.exists() {
+            let contents = std::fs::read_to_string(&path)
+                .map_err(|e| InitError::io(FileOp::Read, &path, e))?;
+            serde_json::from_str(&contents).map_err(InitError::ParseSettings)?
+        } else {
+            serde_json::json!({})
+        };
 
-    let updated = merge_intercept_bd_hook(&mut json)?;
-    if !updated {
-        return Ok(());
-    }
+        let updated = merge_intercept_bd_hook(&mut json)?;
+        if !updated {
+            return Ok(());
+        }
 
-    if let Some(parent) = path.parent()
-        && !parent.exists()
-    {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
-    }
+        if let Some(parent) = path.parent()
+            && !parent.exists()
+        {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| InitError::io(FileOp::CreateDir, parent, e))?;
+        }
 
-    let serialized = serde_json::to_string_pretty(&json)
-        .map_err(|e| format!("Failed to serialize {SETTINGS_PATH}: {e}"))?;
-    std::fs::write(&path, format!("{serialized}\n"))
-        .map_err(|e| format!("Failed to write {SETTINGS_PATH}: {e}"))?;
+        let serialized =
+            serde_json::to_string_pretty(&json).map_err(InitError::SerializeSettings)?;
+        if !stamp
+            .still_matches(&path)
+            .map_err(|e| InitError::io(FileOp::Read, &path, e))?
+        {
+            warn!(
+                path = SETTINGS_PATH,
+                attempt, "settings_changed_during_update"
+            );
+            continue;
+        }
+        std::fs::write(&path, format!("{serialized}\n"))
+            .map_err(|e| InitError::io(FileOp::Write, &path, e))?;
+        return Ok(());
+    }
 
-    Ok(())
+    Err(InitError::SettingsConflict)
 }
 
 /// Pure merge step: inserts the intercept-bd hook entry into the given JSON
 /// value. Returns `Ok(true)` if `json` was modified, `Ok(false)` if the hook
 /// was already registered. Fails on unexpected JSON shapes.
-fn merge_intercept_bd_hook(json: &mut serde_json::Value) -> Result<bool, String> {
+fn merge_intercept_bd_hook(json: &mut serde_json::Value) -> Result<bool, InitError> {
     let hook_entry = serde_json::json!({
         "type": "command",
         "command": INTERCEPT_BD_HOOK_COMMAND,
@@ -182,17 +286,19 @@ fn merge_intercept_bd_hook(json: &mut serde_json::Value) -> Result<bool, String>
 
     let root = json
         .as_object_mut()
-        .ok_or_else(|| format!("{SETTINGS_PATH} is not a JSON object"))?;
+        .ok_or(InitError::SettingsShape("is not a JSON object"))?;
     let hooks = root
         .entry("hooks")
         .or_insert_with(|| serde_json::json!({}))
         .as_object_mut()
-        .ok_or_else(|| format!("{SETTINGS_PATH} `hooks` is not a JSON object"))?;
+        .ok_or(InitError::SettingsShape("`hooks` is not a JSON object"))?;
     let pre_tool_use = hooks
         .entry("PreToolUse")
         .or_insert_with(|| serde_json::json!([]))
         .as_array_mut()
-        .ok_or_else(|| format!("{SETTINGS_PATH} `hooks.PreToolUse` is not a JSON array"))?;
+        .ok_or(InitError::SettingsShape(
+            "`hooks.PreToolUse` is not a JSON array",
+        ))?;
 
     for entry in pre_tool_use.iter() {
         if let Some(hook_list) = entry.get("hooks").and_then(|h| h.as_array())
@@ -221,46 +327,60 @@ fn merge_intercept_bd_hook(json: &mut serde_json::Value) -> Result<bool, String>
 }
 
 impl InitModalState {
-    /// Create a new init modal state by checking file existence and diffs.
+    /// Create a new init modal state for the generic preset in `project_dir`
+    /// by checking file existence and diffs.
     ///
-    /// Files that exist and match the template are skipped. Files that exist
-    /// but differ show a unified diff and will be overwritten.
-    pub fn new(_config: &Config) -> Self {
-        let files = managed_files()
+    /// Missing files are selected for creation. Files that exist but differ
+    /// show a unified diff and are only overwritten if selected.
+    pub fn new(project_dir: &Path) -> Self {
+        Self::with_preset(ProjectPreset::default(), project_dir)
+    }
+
+    /// Create a new init modal state for `preset` in `project_dir`.
+    pub fn with_preset(preset: ProjectPreset, project_dir: &Path) -> Self {
+        let prompt = preset.prompt();
+        let files = managed_files_for(preset, project_dir)
             .into_iter()
             .map(|(display, full)| {
-                if full.exists() {
-                    let existing = std::fs::read_to_string(&full).unwrap_or_default();
-                    let template = template_for_path(&display).unwrap_or("");
-                    if existing == template {
-                        InitFileEntry {
-                            display_path: display,
-                            full_path: full,
-                            status: InitFileStatus::Exists,
-                            diff_lines: vec![],
-                        }
-                    } else {
-                        InitFileEntry {
-                            diff_lines: compute_diff(&existing, template),
-                            display_path: display,
-                            full_path: full,
-                            status: InitFileStatus::WillRegenerate,
-                        }
-                    }
+                let template = if display == PROJECT_PROMPT_DISPLAY {
+                    prompt.as_str()
                 } else {
-                    InitFileEntry {
-                        display_path: display,
-                        full_path: full,
-                        status: InitFileStatus::WillCreate,
-                        diff_lines: vec![],
-                    }
-                }
+                    template_for_path(&display).unwrap_or("")
+                };
+                entry_for(display, full, template)
             })
             .collect();
+        Self {
+            preset,
+            ..Self::with_files(files, project_dir.to_path_buf())
+        }
+    }
+
+    /// Switch preset, recomputing the file list (and resetting selections).
+    pub fn set_preset(&mut self, preset: ProjectPreset) {
+        let focus = self.focus;
+        *self = Self::with_preset(preset, &self.project_dir);
+        self.focus = focus;
+    }
 
+    /// Build state for `files`. Focus starts on the file list when some file
+    /// differs from its template, since overwriting it needs an explicit choice.
+    fn with_files(files: Vec<InitFileEntry>, project_dir: PathBuf) -> Self {
+        let focus = if files
+            .iter()
+            .any(|f| f.status == InitFileStatus::WillRegenerate)
+        {
+            InitModalField::Files
+        } else {
+            InitModalField::InitializeButton
+        };
         Self {
+            preset: ProjectPreset::default(),
+            project_dir,
             files,
-            focus: InitModalField::InitializeButton,
+            focus,
+            cursor: 0,
+            repair: false,
             error: None,
             success: None,
         }
@@ -289,6 +409,90 @@ impl InitModalState {
             .count()
     }
 
+    /// Number of selected files that will be created.
+    pub fn selected_create_count(&self) -> usize {
+        self.selected_with(InitFileStatus::WillCreate)
+    }
+
+    /// Number of selected files that will be overwritten.
+    pub fn selected_regenerate_count(&self) -> usize {
+        self.selected_with(InitFileStatus::WillRegenerate)
+    }
+
+    /// Number of files init leaves untouched (up to date or deselected).
+    pub fn unselected_count(&self) -> usize {
+        self.files.iter().filter(|f| !f.selected).count()
+    }
+
+    fn selected_with(&self, status: InitFileStatus) -> usize {
+        self.files
+            .iter()
+            .filter(|f| f.selected && f.status == status)
+            .count()
+    }
+
+    /// True when no file is selected, so Initialize has nothing to do.
+    pub fn nothing_selected(&self) -> bool {
+        self.files.iter().all(|f| !f.selected)
+    }
+
+    /// True when some managed files are already installed but others are
+    /// missing or differ from their templates.
+    pub fn is_partially_initialized(&self) -> bool {
+        !self.all_up_to_date()
+            && self
+                .files
+                .iter()
+                .any(|f| f.status != InitFileStatus::WillCreate)
+    }
+
+    /// Offer to reinstall just the missing or outdated files: select them,
+    /// expand their diffs, and focus Initialize.
+    pub fn prepare_repair(&mut self) {
+        self.select_all();
+        for file in &mut self.files {
+            file.show_diff = !file.diff_lines.is_empty();
+        }
+        self.focus = InitModalField::InitializeButton;
+        self.repair = true;
+    }
+
+    /// Select every file that is missing or differs (used by `ralph init`).
+    pub fn select_all(&mut self) {
+        for file in &mut self.files {
+            file.selected = file.status != InitFileStatus::Exists;
+        }
+    }
+
+    /// Toggle whether the highlighted file is written. Up-to-date files stay
+    /// deselected.
+    pub fn toggle_selected(&mut self) {
+        if let Some(file) = self.files.get_mut(self.cursor)
+            && file.status != InitFileStatus::Exists
+        {
+            file.selected = !file.selected;
+        }
+    }
+
+    /// Expand or collapse the highlighted file's diff.
+    pub fn toggle_diff(&mut self) {
+        if let Some(file) = self.files.get_mut(self.cursor)
+            && !file.diff_lines.is_empty()
+        {
+            file.show_diff = !file.show_diff;
+        }
+    }
+
+    pub fn cursor_down(&mut self) {
+        if self.cursor + 1 < self.files.len() {
+            self.cursor += 1;
+        }
+    }
+
+    pub fn cursor_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
     /// Move focus to next field.
     pub fn focus_next(&mut self) {
         self.focus = self.focus.next();
@@ -317,40 +521,45 @@ impl InitModalState {
         }
     }
 
-    /// Create all files. Returns Ok(()) on success, Err(message) on failure.
-    pub fn create_files(&self) -> Result<(), String> {
+    /// Write the selected files.
+    pub fn create_files(&self) -> Result<(), InitError> {
         for file in &self.files {
-            if file.status == InitFileStatus::Exists {
+            if !file.selected || file.status == InitFileStatus::Exists {
                 continue;
             }
 
-            let content = template_for_path(&file.display_path)
-                .ok_or_else(|| format!("Unknown template for: {}", file.display_path))?;
+            let prompt;
+            let content = if file.display_path == PROJECT_PROMPT_DISPLAY {
+                prompt = self.preset.prompt();
+                prompt.as_str()
+            } else {
+                template_for_path(&file.display_path)
+                    .ok_or_else(|| InitError::UnknownTemplate(file.display_path.clone()))?
+            };
 
             if let Some(parent) = file.full_path.parent()
                 && !parent.exists()
             {
-                std::fs::create_dir_all(parent).map_err(|e| {
-                    format!("Failed to create directory {}: {}", parent.display(), e)
-                })?;
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| InitError::io(FileOp::CreateDir, parent, e))?;
             }
 
             std::fs::write(&file.full_path, content)
-                .map_err(|e| format!("Failed to write {}: {}", file.display_path, e))?;
+                .map_err(|e| InitError::io(FileOp::Write, &file.full_path, e))?;
 
             #[cfg(unix)]
             if file.display_path.ends_with(".sh") {
                 use std::os::unix::fs::PermissionsExt;
                 let mut perms = std::fs::metadata(&file.full_path)
-                    .map_err(|e| format!("Failed to read {}: {}", file.display_path, e))?
+                    .map_err(|e| InitError::io(FileOp::Read, &file.full_path, e))?
                     .permissions();
                 perms.set_mode(0o755);
                 std::fs::set_permissions(&file.full_path, perms)
-                    .map_err(|e| format!("Failed to chmod {}: {}", file.display_path, e))?;
+                    .map_err(|e| InitError::io(FileOp::Chmod, &file.full_path, e))?;
             }
         }
 
-        ensure_intercept_bd_hook_registered()?;
+        ensure_intercept_bd_hook_registered(&self.project_dir)?;
 
         Ok(())
     }
@@ -382,13 +591,45 @@ pub fn handle_init_modal_input(app: &mut App, key_code: KeyCode) {
     }
 
     match key_code {
-        // Navigation between buttons
-        KeyCode::Tab | KeyCode::Left | KeyCode::Right => {
+        KeyCode::Tab => {
             state.focus_next();
         }
         KeyCode::BackTab => {
             state.focus_prev();
         }
+        // Cycle project preset
+        KeyCode::Left | KeyCode::Char('h') if state.focus == InitModalField::Preset => {
+            state.set_preset(state.preset.prev());
+        }
+        KeyCode::Right | KeyCode::Char('l') if state.focus == InitModalField::Preset => {
+            state.set_preset(state.preset.next());
+        }
+        // Switch between buttons
+        KeyCode::Left | KeyCode::Right
+            if matches!(
+                state.focus,
+                InitModalField::InitializeButton | InitModalField::CancelButton
+            ) =>
+        {
+            state.focus = match state.focus {
+                InitModalField::InitializeButton => InitModalField::CancelButton,
+                _ => InitModalField::InitializeButton,
+            };
+        }
+
+        // File list
+        KeyCode::Char('j') | KeyCode::Down if state.focus == InitModalField::Files => {
+            state.cursor_down();
+        }
+        KeyCode::Char('k') | KeyCode::Up if state.focus == InitModalField::Files => {
+            state.cursor_up();
+        }
+        KeyCode::Char(' ') if state.focus == InitModalField::Files => {
+            state.toggle_selected();
+        }
+        KeyCode::Char('d') if state.focus == InitModalField::Files => {
+            state.toggle_diff();
+        }
 
         // Cancel / close
         KeyCode::Esc => {
@@ -398,12 +639,18 @@ pub fn handle_init_modal_input(app: &mut App, key_code: KeyCode) {
 
         // Enter - context-dependent
         KeyCode::Enter => match state.focus {
+            InitModalField::Preset => {
+                state.focus_next();
+            }
+            InitModalField::Files => {
+                state.toggle_selected();
+            }
             InitModalField::InitializeButton => {
-                // Disabled when all files already exist
-                if !state.all_up_to_date() {
-                    let created = state.create_count();
-                    let regenerated = state.regenerate_count();
-                    let skipped = state.skip_count();
+                // Disabled when nothing is selected
+                if !state.nothing_selected() {
+                    let created = state.selected_create_count();
+                    let regenerated = state.selected_regenerate_count();
+                    let skipped = state.unselected_count();
                     match state.create_files() {
                         Ok(()) => {
                             let mut parts = Vec::new();
@@ -425,7 +672,7 @@ pub fn handle_init_modal_input(app: &mut App, key_code: KeyCode) {
                             app.init_modal_state = None;
                         }
                         Err(e) => {
-                            state.error = Some(e);
+                            state.error = Some(e.to_string());
                         }
                     }
                 }
@@ -453,47 +700,83 @@ pub fn draw_init_modal(f: &mut Frame, app: &App) {
 
     let label_style = Style::default().fg(Color::DarkGray);
     let all_exist = state.all_up_to_date();
+    let nothing_selected = state.nothing_selected();
+    let list_focused = state.focus == InitModalField::Files;
+    let theme = FormTheme::default();
 
     let mut content: Vec<Line> = Vec::new();
 
+    // Project preset selector
+    content.push(Line::from(""));
+    let mut preset_spans = vec![
+        Span::raw("  "),
+        theme.label("Project type: ", state.focus == InitModalField::Preset),
+    ];
+    preset_spans.extend(choice_spans(
+        state.preset.label(),
+        state.focus == InitModalField::Preset,
+        false,
+        &theme,
+    ));
+    content.push(Line::from(preset_spans));
+
     // Title/description
     content.push(Line::from(""));
     content.push(Line::from(Span::styled(
-        "  Scaffold skill files:",
+        "  Scaffold project files:",
         label_style,
     )));
     content.push(Line::from(""));
 
-    // File list with status indicators
-    for file in &state.files {
-        let (icon, icon_style, label) = match file.status {
-            InitFileStatus::WillCreate => ("✓", Style::default().fg(Color::Green), None),
-            InitFileStatus::Exists => (
-                "—",
-                Style::default().fg(Color::DarkGray),
-                Some(" (up to date)"),
-            ),
-            InitFileStatus::WillRegenerate => (
-                "↻",
-                Style::default().fg(Color::Cyan),
-                Some(" (will update)"),
+    // File list with selection checkboxes and status
+    for (i, file) in state.files.iter().enumerate() {
+        let (checkbox, checkbox_style, label) = match (file.status, file.selected) {
+            (InitFileStatus::Exists, _) => {
+                (" — ", Style::default().fg(Color::DarkGray), " (up to date)")
+            }
+            (InitFileStatus::WillCreate, true) => {
+                ("[x]", Style::default().fg(Color::Green), " (create)")
+            }
+            (InitFileStatus::WillRegenerate, true) => {
+                ("[x]", Style::default().fg(Color::Cyan), " (overwrite)")
+            }
+            (InitFileStatus::WillCreate, false) => {
+                ("[ ]", Style::default().fg(Color::DarkGray), " (skip)")
+            }
+            (InitFileStatus::WillRegenerate, false) => (
+                "[ ]",
+                Style::default().fg(Color::Yellow),
+                " (differs \u{2014} keep yours)",
             ),
         };
 
+        let highlighted = list_focused && i == state.cursor;
+        let path_style = if highlighted {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::White)
+        };
         let mut spans = vec![
-            Span::raw("    "),
-            Span::styled(icon, icon_style),
+            Span::styled(
+                if highlighted { "  \u{203a} " } else { "    " },
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled(checkbox, checkbox_style),
             Span::raw(" "),
-            Span::styled(&file.display_path, Style::default().fg(Color::White)),
+            Span::styled(&file.display_path, path_style),
+            Span::styled(label, Style::default().fg(Color::DarkGray)),
         ];
-        if let Some(label) = label {
-            spans.push(Span::styled(label, Style::default().fg(Color::DarkGray)));
+        if highlighted && !file.diff_lines.is_empty() && !file.show_diff {
+            spans.push(Span::styled(
+                "  d: diff",
+                Style::default().fg(Color::DarkGray),
+            ));
         }
 
         content.push(Line::from(spans));
 
-        // Show diff for files that will be regenerated
-        if !file.diff_lines.is_empty() {
+        // Show the diff against the template when expanded
+        if file.show_diff {
             content.push(Line::from(""));
             let show_count = file.diff_lines.len().min(MAX_DIFF_LINES);
             for diff_line in &file.diff_lines[..show_count] {
@@ -528,7 +811,7 @@ pub fn draw_init_modal(f: &mut Frame, app: &App) {
     // Show status messages
     if all_exist {
         content.push(Line::from(Span::styled(
-            "  All skill files are up to date.",
+            "  All project files are up to date.",
             label_style,
         )));
     } else if let Some(error) = &state.error {
@@ -547,11 +830,19 @@ pub fn draw_init_modal(f: &mut Frame, app: &App) {
 
     content.push(Line::from(""));
 
-    // Buttons - Initialize is disabled when all files exist
+    if !all_exist {
+        content.push(Line::from(Span::styled(
+            "  Space toggles a file \u{b7} d shows its diff",
+            label_style,
+        )));
+        content.push(Line::from(""));
+    }
+
+    // Buttons - Initialize is disabled when nothing is selected
     let init_focused = state.focus == InitModalField::InitializeButton;
     let cancel_focused = state.focus == InitModalField::CancelButton;
 
-    let init_style = if all_exist {
+    let init_style = if nothing_selected {
         Style::default().fg(Color::DarkGray)
     } else if init_focused {
         Style::default().fg(Color::Black).bg(Color::Cyan)
@@ -583,7 +874,11 @@ pub fn draw_init_modal(f: &mut Frame, app: &App) {
     let modal = Paragraph::new(content).block(
         Block::default()
             .borders(Borders::ALL)
-            .title(" Initialize Project ")
+            .title(if state.repair {
+                " Repair Project Files "
+            } else {
+                " Initialize Project "
+            })
             .title_alignment(Alignment::Center)
             .style(Style::default().fg(Color::White)),
     );
@@ -597,8 +892,7 @@ mod tests {
 
     #[test]
     fn test_init_includes_expected_files() {
-        let config = Config::default();
-        let state = InitModalState::new(&config);
+        let state = InitModalState::new(&crate::config::startup_dir());
 
         let paths: Vec<&str> = state
             .files
@@ -613,10 +907,30 @@ mod tests {
         assert!(!paths.iter().any(|p| p.ends_with("PROMPT.md")));
     }
 
+    #[test]
+    fn non_generic_preset_adds_project_prompt() {
+        let dir = crate::config::startup_dir();
+        let state = InitModalState::with_preset(ProjectPreset::RustCrate, &dir);
+        if crate::config::compute_prompt_path(&dir).is_some() {
+            assert_eq!(state.files.len(), 6);
+            assert_eq!(state.files[5].display_path, PROJECT_PROMPT_DISPLAY);
+        }
+        assert_eq!(state.preset, ProjectPreset::RustCrate);
+    }
+
+    #[test]
+    fn set_preset_keeps_focus() {
+        let mut state = InitModalState::new(&crate::config::startup_dir());
+        state.focus = InitModalField::Preset;
+        state.set_preset(ProjectPreset::DocsOnly);
+        assert_eq!(state.focus, InitModalField::Preset);
+        state.set_preset(ProjectPreset::Generic);
+        assert_eq!(state.files.len(), 5);
+    }
+
     #[test]
     fn test_init_manages_five_files() {
-        let config = Config::default();
-        let state = InitModalState::new(&config);
+        let state = InitModalState::new(&crate::config::startup_dir());
         assert_eq!(state.files.len(), 5);
     }
 
@@ -722,6 +1036,17 @@ mod tests {
         assert!(json["hooks"]["PreToolUse"].is_array());
     }
 
+    #[test]
+    fn merge_rejects_unexpected_settings_shape() {
+        let mut json = serde_json::json!({ "hooks": [] });
+        let err = merge_intercept_bd_hook(&mut json).unwrap_err();
+        assert!(matches!(err, InitError::SettingsShape(_)));
+        assert_eq!(
+            err.to_string(),
+            format!("{SETTINGS_PATH} `hooks` is not a JSON object")
+        );
+    }
+
     fn make_state(statuses: &[InitFileStatus]) -> InitModalState {
         let files = statuses
             .iter()
@@ -730,15 +1055,97 @@ mod tests {
                 display_path: format!("file_{i}"),
                 full_path: PathBuf::from(format!("file_{i}")),
                 status,
-                diff_lines: vec![],
+                diff_lines: vec!["@@ -1 +1 @@".to_string()],
+                selected: status == InitFileStatus::WillCreate,
+                show_diff: false,
             })
             .collect();
-        InitModalState {
-            files,
-            focus: InitModalField::InitializeButton,
-            error: None,
-            success: None,
-        }
+        InitModalState::with_files(files, PathBuf::from("."))
+    }
+
+    #[test]
+    fn differing_files_need_explicit_opt_in() {
+        let mut state = make_state(&[
+            InitFileStatus::WillCreate,
+            InitFileStatus::WillRegenerate,
+            InitFileStatus::Exists,
+        ]);
+        assert_eq!(state.focus, InitModalField::Files);
+        assert_eq!(state.selected_create_count(), 1);
+        assert_eq!(state.selected_regenerate_count(), 0);
+        assert_eq!(state.unselected_count(), 2);
+
+        state.cursor_down();
+        state.toggle_selected();
+        assert_eq!(state.selected_regenerate_count(), 1);
+        assert_eq!(state.unselected_count(), 1);
+    }
+
+    #[test]
+    fn up_to_date_files_cannot_be_selected() {
+        let mut state = make_state(&[InitFileStatus::Exists]);
+        assert_eq!(state.focus, InitModalField::InitializeButton);
+        state.toggle_selected();
+        assert!(state.nothing_selected());
+        state.select_all();
+        assert!(state.nothing_selected());
+    }
+
+    #[test]
+    fn deselecting_everything_leaves_nothing_to_do() {
+        let mut state = make_state(&[InitFileStatus::WillCreate]);
+        assert!(!state.nothing_selected());
+        state.toggle_selected();
+        assert!(state.nothing_selected());
+        assert!(!state.all_up_to_date());
+    }
+
+    #[test]
+    fn select_all_includes_differing_files() {
+        let mut state = make_state(&[InitFileStatus::WillCreate, InitFileStatus::WillRegenerate]);
+        state.toggle_selected();
+        state.select_all();
+        assert_eq!(state.selected_create_count(), 1);
+        assert_eq!(state.selected_regenerate_count(), 1);
+    }
+
+    #[test]
+    fn partially_initialized_needs_an_installed_file() {
+        assert!(!make_state(&[InitFileStatus::WillCreate]).is_partially_initialized());
+        assert!(!make_state(&[InitFileStatus::Exists]).is_partially_initialized());
+        assert!(
+            make_state(&[InitFileStatus::WillCreate, InitFileStatus::Exists])
+                .is_partially_initialized()
+        );
+        assert!(make_state(&[InitFileStatus::WillRegenerate]).is_partially_initialized());
+    }
+
+    #[test]
+    fn prepare_repair_selects_outdated_files_with_diffs() {
+        let mut state = make_state(&[
+            InitFileStatus::WillRegenerate,
+            InitFileStatus::WillCreate,
+            InitFileStatus::Exists,
+        ]);
+        state.prepare_repair();
+        assert!(state.repair);
+        assert_eq!(state.focus, InitModalField::InitializeButton);
+        assert_eq!(state.selected_regenerate_count(), 1);
+        assert_eq!(state.selected_create_count(), 1);
+        assert!(state.files[0].show_diff);
+        assert!(!state.files[2].selected);
+    }
+
+    #[test]
+    fn cursor_and_diff_toggle_stay_in_bounds() {
+        let mut state = make_state(&[InitFileStatus::WillRegenerate, InitFileStatus::Exists]);
+        state.cursor_up();
+        assert_eq!(state.cursor, 0);
+        state.toggle_diff();
+        assert!(state.files[0].show_diff);
+        state.cursor_down();
+        state.cursor_down();
+        assert_eq!(state.cursor, 1);
     }
 
     #[test]
@@ -0,0 +1,150 @@
+//! Instruction queue modal — type notes for the next iteration's prompt.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::Alignment;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use tracing::info;
+
+use crate::app::App;
+use crate::ui::{TextInput, centered_rect, truncate_to_width};
+
+/// Queued instructions listed above the input, newest last.
+const MAX_LISTED: usize = 8;
+
+/// State for the instruction queue modal.
+#[derive(Debug, Clone, Default)]
+pub struct InstructionsState {
+    pub input: TextInput,
+}
+
+/// Handle keyboard input for the instruction queue modal.
+pub fn handle_instructions_input(app: &mut App, key_code: KeyCode, modifiers: KeyModifiers) {
+    let Some(state) = &mut app.instructions_state else {
+        return;
+    };
+    match key_code {
+        KeyCode::Esc => app.instructions_state = None,
+        KeyCode::Enter => {
+            if !app.instruction_queue.push(state.input.value()) {
+                return;
+            }
+            state.input = TextInput::default();
+            let pending = app.instruction_queue.pending().len();
+            info!(pending, "instruction_queued");
+        }
+        KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
+            if let Some(text) = app.instruction_queue.remove_last_pending() {
+                app.set_hint(format!("Removed: {text}"));
+            }
+        }
+        KeyCode::Char(c) => state.input.insert_char(c),
+        KeyCode::Backspace => {
+            state.input.delete_char_before();
+        }
+        KeyCode::Delete => {
+            state.input.delete_char_at();
+        }
+        KeyCode::Left => state.input.cursor_left(),
+        KeyCode::Right => state.input.cursor_right(),
+        KeyCode::Home => state.input.cursor_home(),
+        KeyCode::End => state.input.cursor_end(),
+        _ => {}
+    }
+}
+
+/// Draw the instruction queue modal.
+pub fn draw_instructions_modal(f: &mut Frame, app: &App) {
+    let Some(state) = &app.instructions_state else {
+        return;
+    };
+
+    let modal_width: u16 = 70;
+    let modal_height = MAX_LISTED as u16 + 8;
+    let modal_area = centered_rect(modal_width, modal_height, f.area());
+    f.render_widget(Clear, modal_area);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let key_style = Style::default().fg(Color::Cyan);
+    let field_width = modal_width.saturating_sub(6) as usize;
+
+    let items = app.instruction_queue.items();
+    let mut content = vec![Line::from("")];
+    if items.is_empty() {
+        content.push(Line::styled(
+            "  Nothing queued. Notes are added to the next iteration's prompt.",
+            dim,
+        ));
+    }
+    for item in &items[items.len().saturating_sub(MAX_LISTED)..] {
+        let (marker, style) = match item.delivered_in {
+            Some(loop_number) => (format!("\u{2713} #{loop_number} "), dim),
+            None => (
+                "\u{25cb} next ".to_string(),
+                Style::default().fg(Color::Yellow),
+            ),
+        };
+        let text = truncate_to_width(
+            &item.text,
+            field_width.saturating_sub(marker.chars().count()),
+            "\u{2026}",
+        );
+        content.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(marker, style),
+            Span::raw(text),
+        ]));
+    }
+    content.push(Line::from(""));
+    content.push(Line::from(
+        std::iter::once(Span::raw("  "))
+            .chain(state.input.spans(field_width))
+            .collect::<Vec<_>>(),
+    ));
+
+    let modal = Paragraph::new(content).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Instructions for the next iteration ")
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(vec![
+                Span::styled(" Enter", key_style),
+                Span::styled(" queue  ", dim),
+                Span::styled("Ctrl+u", key_style),
+                Span::styled(" remove last pending  ", dim),
+                Span::styled("Esc", key_style),
+                Span::styled(" close ", dim),
+            ]))
+            .style(Style::default().fg(Color::White)),
+    );
+    f.render_widget(modal, modal_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LoadedConfig;
+
+    #[test]
+    fn enter_queues_typed_text_and_clears_input() {
+        let mut app = App::new("test".into(), None, LoadedConfig::default_for_test(), None);
+        app.instructions_state = Some(InstructionsState::default());
+        for c in "skip bd-12".chars() {
+            handle_instructions_input(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        handle_instructions_input(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.instruction_queue.pending(), vec!["skip bd-12"]);
+        assert!(app.instructions_state.as_ref().unwrap().input.is_empty());
+
+        // Empty input queues nothing
+        handle_instructions_input(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.instruction_queue.items().len(), 1);
+
+        handle_instructions_input(&mut app, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert!(app.instruction_queue.pending().is_empty());
+        handle_instructions_input(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.instructions_state.is_none());
+    }
+}
@@ -3,9 +3,24 @@ use crossterm::event::{KeyCode, KeyModifiers};
 use super::overlays::{CloseConfirmState, DeferState, DepDirectionState};
 use super::state::{BoardAction, BoardFocus, DepDirection};
 use crate::app::App;
+use crate::ui::TextInput;
+
+/// Rows moved per `PgUp`/`PgDn` in the focused pane.
+const PAGE_ROWS: i16 = 10;
 
 /// Handle keyboard input for the kanban board (primary view).
 pub fn handle_kanban_input(app: &mut App, key_code: KeyCode, modifiers: KeyModifiers) {
+    // Any board key dismisses the startup summary
+    app.project_summary = None;
+    let mutating = matches!(
+        key_code,
+        KeyCode::Char('X' | '+' | '=' | '-' | 'H' | 'd' | 'b' | 'u')
+    ) || (key_code == KeyCode::Char('r')
+        && modifiers.contains(KeyModifiers::CONTROL));
+    if mutating && !app.kanban_board_state.has_overlay() && app.deny_in_read_only("Changing beads")
+    {
+        return;
+    }
     let state = &mut app.kanban_board_state;
 
     // If close confirmation is open, handle its input
@@ -16,7 +31,7 @@ pub fn handle_kanban_input(app: &mut App, key_code: KeyCode, modifiers: KeyModif
             }
             KeyCode::Enter => {
                 let bead_id = confirm.bead_id.clone();
-                let reason = confirm.reason.trim().to_string();
+                let reason = confirm.reason.value().trim().to_string();
                 let previous_status = state
                     .find_card(&bead_id)
                     .map(|c| c.status.clone())
@@ -34,16 +49,16 @@ pub fn handle_kanban_input(app: &mut App, key_code: KeyCode, modifiers: KeyModif
                 app.mutate_and_refresh_kanban(args);
             }
             KeyCode::Backspace => {
-                confirm.delete_char_before();
+                confirm.reason.delete_char_before();
             }
             KeyCode::Left => {
-                confirm.cursor_left();
+                confirm.reason.cursor_left();
             }
             KeyCode::Right => {
-                confirm.cursor_right();
+                confirm.reason.cursor_right();
             }
             KeyCode::Char(c) => {
-                confirm.insert_char(c);
+                confirm.reason.insert_char(c);
             }
             _ => {}
         }
@@ -85,7 +100,7 @@ pub fn handle_kanban_input(app: &mut App, key_code: KeyCode, modifiers: KeyModif
             }
             KeyCode::Enter => {
                 let bead_id = defer.bead_id.clone();
-                let until = defer.until.trim().to_string();
+                let until = defer.until.value().trim().to_string();
                 let previous_status = state
                     .find_card(&bead_id)
                     .map(|c| c.status.clone())
@@ -103,38 +118,49 @@ pub fn handle_kanban_input(app: &mut App, key_code: KeyCode, modifiers: KeyModif
                 app.mutate_and_refresh_kanban(args);
             }
             KeyCode::Backspace => {
-                defer.delete_char_before();
+                defer.until.delete_char_before();
             }
             KeyCode::Left => {
-                defer.cursor_left();
+                defer.until.cursor_left();
             }
             KeyCode::Right => {
-                defer.cursor_right();
+                defer.until.cursor_right();
             }
             KeyCode::Char(c) => {
-                defer.insert_char(c);
+                defer.until.insert_char(c);
             }
             _ => {}
         }
         return;
     }
 
+    // Focus cycling and scrolling apply to whichever pane has focus
+    let scroll = match key_code {
+        KeyCode::Tab => {
+            state.cycle_focus(true);
+            return;
+        }
+        KeyCode::BackTab => {
+            state.cycle_focus(false);
+            return;
+        }
+        KeyCode::Char('j') | KeyCode::Down => 1,
+        KeyCode::Char('k') | KeyCode::Up => -1,
+        KeyCode::PageDown => PAGE_ROWS,
+        KeyCode::PageUp => -PAGE_ROWS,
+        _ => 0,
+    };
+    if scroll != 0 {
+        state.scroll_focused(scroll);
+        return;
+    }
+
     // If preview pane has focus, handle preview input
     if state.focus == BoardFocus::Preview {
         match key_code {
             KeyCode::Esc | KeyCode::Enter => {
                 state.focus = BoardFocus::Board;
             }
-            KeyCode::Char('j') | KeyCode::Down => {
-                if let Some(ref mut detail) = state.preview_detail {
-                    detail.scroll_offset = detail.scroll_offset.saturating_add(1);
-                }
-            }
-            KeyCode::Char('k') | KeyCode::Up => {
-                if let Some(ref mut detail) = state.preview_detail {
-                    detail.scroll_offset = detail.scroll_offset.saturating_sub(1);
-                }
-            }
             KeyCode::Char('?') => {
                 app.help_context = Some(crate::modals::HelpContext::Preview);
             }
@@ -154,8 +180,7 @@ pub fn handle_kanban_input(app: &mut App, key_code: KeyCode, modifiers: KeyModif
             if let Some(card) = state.selected_card() {
                 state.close_confirm = Some(CloseConfirmState {
                     bead_id: card.id.clone(),
-                    reason: String::new(),
-                    cursor_pos: 0,
+                    reason: TextInput::default(),
                 });
             }
         }
@@ -210,8 +235,7 @@ pub fn handle_kanban_input(app: &mut App, key_code: KeyCode, modifiers: KeyModif
             if let Some(card) = state.selected_card() {
                 state.defer_input = Some(DeferState {
                     bead_id: card.id.clone(),
-                    until: String::new(),
-                    cursor_pos: 0,
+                    until: TextInput::default(),
                 });
             }
         }
@@ -242,6 +266,9 @@ pub fn handle_kanban_input(app: &mut App, key_code: KeyCode, modifiers: KeyModif
             // Manual refresh — re-fetch the board from bd.
             app.trigger_kanban_refresh();
         }
+        KeyCode::Char('/') => {
+            app.open_bead_search();
+        }
         KeyCode::Char('?') => {
             app.help_context = Some(crate::modals::HelpContext::Board);
         }
@@ -253,14 +280,6 @@ pub fn handle_kanban_input(app: &mut App, key_code: KeyCode, modifiers: KeyModif
             state.move_right();
             state.schedule_preview_fetch();
         }
-        KeyCode::Char('k') | KeyCode::Up => {
-            state.move_up();
-            state.schedule_preview_fetch();
-        }
-        KeyCode::Char('j') | KeyCode::Down => {
-            state.move_down();
-            state.schedule_preview_fetch();
-        }
         _ => {}
     }
 }
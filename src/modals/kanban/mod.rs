@@ -10,11 +10,12 @@ mod pipeline;
 mod preview;
 mod render;
 mod state;
+mod summary;
 
 pub use input::handle_kanban_input;
 pub use pipeline::stream_board_data;
 pub use render::draw_kanban_board;
 pub use state::{
-    BeadDetailState, BoardAction, BoardConfig, DepDirection, KanbanBoardState, KanbanFetchMsg,
-    load_board_config,
+    BeadDetailState, BoardAction, BoardConfig, BoardFocus, DepDirection, KanbanBoardState,
+    KanbanFetchMsg, load_board_config,
 };
@@ -1,5 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::bd::Bd;
+use crate::blocked_reason;
+
 use super::state::{
     ColumnDef, KanbanCard, KanbanColumnUpdate, KanbanFetchMsg, KanbanFinalized, short_id,
 };
@@ -48,16 +51,17 @@ fn parse_card(item: &serde_json::Value, emoji: &str) -> Option<KanbanCard> {
     })
 }
 
-fn run_shell_pipeline(command: &str, bd_path: &str) -> Result<Vec<serde_json::Value>, String> {
+fn run_shell_pipeline(command: &str, bd: &Bd) -> Result<Vec<serde_json::Value>, String> {
     let mut cmd = std::process::Command::new("sh");
     cmd.args(["-c", command])
+        .current_dir(bd.dir())
         .stdin(std::process::Stdio::null())
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped());
 
     // Ensure the directory containing bd is on PATH so pipeline commands
     // can find the bd binary even when bd_path is an absolute path.
-    let bd_abs = std::path::Path::new(bd_path);
+    let bd_abs = std::path::Path::new(bd.path());
     if let Some(parent) = bd_abs.parent().filter(|p| !p.as_os_str().is_empty())
         && let Ok(current_path) = std::env::var("PATH")
     {
@@ -90,7 +94,7 @@ fn run_shell_pipeline(command: &str, bd_path: &str) -> Result<Vec<serde_json::Va
 /// followed by exactly one `Finalized(..)`. The receiver may drop at any point; sends
 /// short-circuit the function so it exits cleanly.
 pub fn stream_board_data(
-    bd_path: &str,
+    bd: &Bd,
     column_defs: &[ColumnDef],
     tx: std::sync::mpsc::Sender<KanbanFetchMsg>,
 ) {
@@ -101,7 +105,7 @@ pub fn stream_board_data(
         let mut col_items: Vec<serde_json::Value> = Vec::new();
 
         for source in &col_def.sources {
-            match run_shell_pipeline(&source.command, bd_path) {
+            match run_shell_pipeline(&source.command, bd) {
                 Ok(items) => {
                     for item in &items {
                         if let Some(card) = parse_card(item, &source.emoji) {
@@ -153,7 +157,7 @@ pub fn stream_board_data(
 
     // Fetch stats serially — last so the earlier columns render first
     let stats_output = crate::bd_lock::with_lock(|| {
-        std::process::Command::new(bd_path)
+        bd.command()
             .args(["stats", "--json"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -240,11 +244,25 @@ pub fn stream_board_data(
         })
         .collect();
 
+    // Blocked beads whose notes or description say why
+    let blocked_reasons: HashMap<String, String> = all_items
+        .iter()
+        .filter(|item| item.get("status").and_then(|v| v.as_str()) == Some("blocked"))
+        .filter_map(|item| {
+            let id = item.get("id").and_then(|v| v.as_str())?;
+            let field = |name| item.get(name).and_then(|v| v.as_str()).unwrap_or("");
+            let reason =
+                blocked_reason::extract(&[field("notes"), field("description"), field("design")])?;
+            Some((id.to_string(), reason))
+        })
+        .collect();
+
     let _ = tx.send(KanbanFetchMsg::Finalized(KanbanFinalized {
         open_count,
         closed_count,
         dep_neighbors,
         manual_blocked_ids,
+        blocked_reasons,
         epic_ids,
     }));
 }
@@ -4,8 +4,24 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 
+use crate::blocked_reason;
+use crate::spec_lint::{self, Finding};
+
 use super::state::{BeadDetailState, BoardFocus, KanbanBoardState, short_id};
 
+/// `ralph spec lint` checks for the bead, with prerequisites it mentions
+/// checked against its recorded dependencies.
+fn lint_findings(detail: &BeadDetailState) -> Vec<Finding> {
+    spec_lint::lint(&detail.description, |id| {
+        (!detail.dependencies.iter().any(|d| d.id == id)).then(|| {
+            format!(
+                "Mentions {id} as a prerequisite but has no dependency on it (`bd dep add {} {id}`)",
+                detail.id
+            )
+        })
+    })
+}
+
 fn build_detail_content(detail: &BeadDetailState) -> Vec<Line<'_>> {
     let mut content: Vec<Line> = Vec::new();
 
@@ -80,6 +96,20 @@ fn build_detail_content(detail: &BeadDetailState) -> Vec<Line<'_>> {
     }
     content.push(Line::from(meta));
 
+    // Why it's blocked (or escalated to a human), when the bead says
+    if (detail.status == "blocked" || detail.labels.iter().any(|l| l == "human"))
+        && let Some(reason) =
+            blocked_reason::extract(&[&detail.notes, &detail.description, &detail.design])
+    {
+        content.push(Line::from(vec![
+            Span::styled(
+                "Blocked: ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(reason, Style::default().fg(Color::Red)),
+        ]));
+    }
+
     // Labels
     if !detail.labels.is_empty() {
         let mut label_spans: Vec<Span> = vec![Span::styled(
@@ -95,6 +125,26 @@ fn build_detail_content(detail: &BeadDetailState) -> Vec<Line<'_>> {
         content.push(Line::from(label_spans));
     }
 
+    // Lint (open slices only; epics are shaped differently)
+    if detail.issue_type != "epic" && detail.status != "closed" {
+        let findings = lint_findings(detail);
+        if !findings.is_empty() {
+            content.push(Line::from(""));
+            content.push(Line::from(Span::styled(
+                "Lint",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for finding in findings {
+                content.push(Line::from(Span::styled(
+                    format!("  \u{26a0} {}", finding.message),
+                    Style::default().fg(Color::Yellow),
+                )));
+            }
+        }
+    }
+
     // Dependencies
     if !detail.dependencies.is_empty() {
         content.push(Line::from(""));
@@ -9,8 +9,10 @@ use unicode_width::UnicodeWidthStr;
 
 use super::overlays::{CloseConfirmState, DeferState, DepDirectionState};
 use super::preview::draw_preview_pane;
-use super::state::short_id;
+use super::state::{BoardFocus, short_id};
+use super::summary::draw_project_summary;
 use crate::app::App;
+use crate::number_format::NumberFormat;
 use crate::ui::centered_rect;
 
 fn truncate_to_width(s: &str, max_width: usize) -> String {
@@ -48,9 +50,18 @@ pub fn draw_kanban_board(f: &mut Frame, app: &App, board_area: Rect) {
         (chunks[0], Some(chunks[1]))
     };
 
-    // Draw the preview pane in the bottom area
+    // Draw the preview pane (or the startup summary) in the bottom area
     if let Some(area) = preview_area {
-        draw_preview_pane(f, state, area);
+        match &app.project_summary {
+            Some(summary) => draw_project_summary(
+                f,
+                summary,
+                state,
+                &NumberFormat::from(&app.config.display),
+                area,
+            ),
+            None => draw_preview_pane(f, state, area),
+        }
     }
 
     let inner_height = columns_area.height.saturating_sub(2) as usize;
@@ -149,19 +160,30 @@ pub fn draw_kanban_board(f: &mut Frame, app: &App, board_area: Rect) {
         }
         content.push(Line::from(sep_spans));
 
-        // Warning banner for manual-blocked beads
+        // Warning banner for manual-blocked beads, or why the selected bead is blocked
         let manual_blocked_count = state.manual_blocked_ids.len();
-        let has_banner = manual_blocked_count > 0;
+        let selected_reason = state
+            .selected_card()
+            .and_then(|card| Some((card, state.blocked_reasons.get(&card.id)?)));
+        let has_banner = manual_blocked_count > 0 || selected_reason.is_some();
         if has_banner {
-            let noun = if manual_blocked_count == 1 {
-                "bead has"
+            let banner_text = if let Some((card, reason)) = selected_reason {
+                format!(" {} blocked: {reason}", short_id(&card.id))
             } else {
-                "beads have"
+                let noun = if manual_blocked_count == 1 {
+                    "bead has"
+                } else {
+                    "beads have"
+                };
+                format!(
+                    " {manual_blocked_count} {noun} 'blocked' status without dependencies \u{2014} Ralph won't pick these up"
+                )
             };
-            let banner_text = format!(
-                " {manual_blocked_count} {noun} 'blocked' status without dependencies \u{2014} Ralph won't pick these up"
+            let banner_padded = format!(
+                "{:<width$}",
+                truncate_to_width(&banner_text, inner_width),
+                width = inner_width
             );
-            let banner_padded = format!("{:<width$}", banner_text, width = inner_width);
             content.push(Line::from(Span::styled(
                 banner_padded,
                 Style::default().fg(Color::Yellow),
@@ -365,6 +387,11 @@ pub fn draw_kanban_board(f: &mut Frame, app: &App, board_area: Rect) {
             .title(" Work Board ")
             .title_alignment(Alignment::Center)
             .title_top(Line::from(stats_title).right_aligned())
+            .border_style(if state.focus == BoardFocus::Board {
+                Style::default().fg(Color::White)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            })
             .style(Style::default().fg(Color::White)),
     );
 
@@ -393,28 +420,11 @@ fn draw_close_confirm(f: &mut Frame, confirm: &CloseConfirmState) {
 
     let prompt = format!("Close {}? Reason (optional):", confirm.bead_id);
 
-    // Build the text input line with cursor
-    let before = &confirm.reason[..confirm.cursor_pos];
-    let at_end = confirm.cursor_pos >= confirm.reason.len();
-    let cursor_char = if at_end {
-        ' '
-    } else {
-        confirm.reason[confirm.cursor_pos..].chars().next().unwrap()
-    };
-    let after = if at_end {
-        ""
-    } else {
-        &confirm.reason[confirm.cursor_pos + cursor_char.len_utf8()..]
-    };
-
-    let input_line = Line::from(vec![
-        Span::styled(before, Style::default().fg(Color::White)),
-        Span::styled(
-            cursor_char.to_string(),
-            Style::default().fg(Color::Black).bg(Color::White),
-        ),
-        Span::styled(after, Style::default().fg(Color::White)),
-    ]);
+    let input_line = Line::from(
+        confirm
+            .reason
+            .spans(overlay.width.saturating_sub(2) as usize),
+    );
 
     let content = vec![
         Line::from(Span::styled(prompt, Style::default().fg(Color::Yellow))),
@@ -443,28 +453,7 @@ fn draw_defer_input(f: &mut Frame, defer: &DeferState) {
 
     let prompt = format!("Defer {}. Until (optional):", defer.bead_id);
 
-    // Build the text input line with cursor
-    let before = &defer.until[..defer.cursor_pos];
-    let at_end = defer.cursor_pos >= defer.until.len();
-    let cursor_char = if at_end {
-        ' '
-    } else {
-        defer.until[defer.cursor_pos..].chars().next().unwrap()
-    };
-    let after = if at_end {
-        ""
-    } else {
-        &defer.until[defer.cursor_pos + cursor_char.len_utf8()..]
-    };
-
-    let input_line = Line::from(vec![
-        Span::styled(before, Style::default().fg(Color::White)),
-        Span::styled(
-            cursor_char.to_string(),
-            Style::default().fg(Color::Black).bg(Color::White),
-        ),
-        Span::styled(after, Style::default().fg(Color::White)),
-    ]);
+    let input_line = Line::from(defer.until.spans(overlay.width.saturating_sub(2) as usize));
 
     let content = vec![
         Line::from(Span::styled(prompt, Style::default().fg(Color::Yellow))),
@@ -1,4 +1,5 @@
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::time::Instant;
 
 use serde::Deserialize;
@@ -30,8 +31,8 @@ pub struct SourceDef {
 /// Cascade: per-project `board_columns.toml` in the config dir → compiled-in default.
 /// If the external file exists but fails to parse, falls back to the compiled-in
 /// default and logs a warning.
-pub fn load_board_config() -> Result<BoardConfig, toml::de::Error> {
-    if let Some(path) = crate::config::resolve_board_columns_path() {
+pub fn load_board_config(project_dir: &Path) -> Result<BoardConfig, toml::de::Error> {
+    if let Some(path) = crate::config::resolve_board_columns_path(project_dir) {
         match std::fs::read_to_string(&path) {
             Ok(contents) => match toml::from_str::<BoardConfig>(&contents) {
                 Ok(config) => {
@@ -87,6 +88,8 @@ pub struct KanbanFinalized {
     pub closed_count: u64,
     pub dep_neighbors: HashMap<String, HashSet<String>>,
     pub manual_blocked_ids: HashSet<String>,
+    /// Why each blocked bead is blocked, where its text says.
+    pub blocked_reasons: HashMap<String, String>,
     /// IDs of beads that are parents of other beads (rendered with bold/epic style).
     pub epic_ids: HashSet<String>,
 }
@@ -294,8 +297,12 @@ pub struct KanbanBoardState {
     pub defer_input: Option<DeferState>,
     /// Bead IDs with status=blocked but no actual blocking dependencies.
     pub manual_blocked_ids: HashSet<String>,
+    /// Why each blocked bead is blocked, where its text says.
+    pub blocked_reasons: HashMap<String, String>,
     /// Dependency direction picker overlay state.
     pub dep_direction: Option<DepDirectionState>,
+    /// Bead to put the cursor on once the selected column loads (restored UI state).
+    pub pending_selection: Option<String>,
     /// Undo stack — push on every forward action, pop on undo.
     pub undo_stack: Vec<BoardAction>,
     /// Redo stack — push when undoing, clear on new forward action.
@@ -487,7 +494,9 @@ impl KanbanBoardState {
             close_confirm: None,
             defer_input: None,
             manual_blocked_ids: HashSet::new(),
+            blocked_reasons: HashMap::new(),
             dep_direction: None,
+            pending_selection: None,
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
             status_message: None,
@@ -499,6 +508,11 @@ impl KanbanBoardState {
         self.column_defs.len()
     }
 
+    /// Whether an inline overlay (close, defer, dep picker) is capturing keystrokes.
+    pub fn has_overlay(&self) -> bool {
+        self.close_confirm.is_some() || self.defer_input.is_some() || self.dep_direction.is_some()
+    }
+
     /// Returns the currently selected card, if any (skipping error cards).
     pub fn selected_card(&self) -> Option<&KanbanCard> {
         let col = self.selected_column;
@@ -546,6 +560,11 @@ impl KanbanBoardState {
 
         // If the selected card lives in this column, refresh the preview pane
         if col_idx == self.selected_column {
+            if let Some(id) = self.pending_selection.take()
+                && let Some(row) = self.columns[col_idx].iter().position(|c| c.id == id)
+            {
+                self.selected_row[col_idx] = row;
+            }
             self.schedule_preview_fetch();
         }
     }
@@ -556,6 +575,7 @@ impl KanbanBoardState {
         self.closed_count = finalized.closed_count;
         self.dep_neighbors = finalized.dep_neighbors;
         self.manual_blocked_ids = finalized.manual_blocked_ids;
+        self.blocked_reasons = finalized.blocked_reasons;
 
         // Patch epic flags based on accumulated parent references
         for column in self.columns.iter_mut() {
@@ -583,10 +603,27 @@ impl KanbanBoardState {
     }
 
     /// Find a card by bead ID across all columns.
-    pub(super) fn find_card(&self, bead_id: &str) -> Option<&KanbanCard> {
+    pub fn find_card(&self, bead_id: &str) -> Option<&KanbanCard> {
         self.columns.iter().flatten().find(|c| c.id == bead_id)
     }
 
+    /// Move the cursor to `bead_id`'s card and queue its preview. Returns
+    /// false (leaving the cursor alone) when no column shows it.
+    pub fn select_bead(&mut self, bead_id: &str) -> bool {
+        let found = self.columns.iter().enumerate().find_map(|(col, cards)| {
+            let row = cards.iter().position(|c| c.id == bead_id && !c.is_error)?;
+            Some((col, row))
+        });
+        let Some((col, row)) = found else {
+            return false;
+        };
+        self.selected_column = col;
+        self.selected_row[col] = row;
+        self.focus = BoardFocus::Board;
+        self.schedule_preview_fetch();
+        true
+    }
+
     /// Record a forward action: push to undo stack and clear redo stack.
     pub fn push_action(&mut self, action: BoardAction) {
         self.set_status(action.describe());
@@ -644,6 +681,49 @@ impl KanbanBoardState {
         }
     }
 
+    /// Panes that can take focus right now, in Tab order.
+    fn focusable_panes(&self) -> Vec<BoardFocus> {
+        let mut panes = vec![BoardFocus::Board];
+        if self.selected_card().is_some() && self.preview_detail.is_some() {
+            panes.push(BoardFocus::Preview);
+        }
+        panes
+    }
+
+    /// Move focus to the next (or previous) focusable pane, wrapping around.
+    pub fn cycle_focus(&mut self, forward: bool) {
+        let panes = self.focusable_panes();
+        let n = panes.len();
+        let i = panes.iter().position(|p| *p == self.focus).unwrap_or(0);
+        self.focus = panes[if forward {
+            (i + 1) % n
+        } else {
+            (i + n - 1) % n
+        }];
+    }
+
+    /// Scroll the focused pane by `delta` rows (negative scrolls up). On the
+    /// board this moves the card cursor.
+    pub fn scroll_focused(&mut self, delta: i16) {
+        match self.focus {
+            BoardFocus::Board => {
+                for _ in 0..delta.unsigned_abs() {
+                    if delta < 0 {
+                        self.move_up();
+                    } else {
+                        self.move_down();
+                    }
+                }
+                self.schedule_preview_fetch();
+            }
+            BoardFocus::Preview => {
+                if let Some(ref mut detail) = self.preview_detail {
+                    detail.scroll_offset = detail.scroll_offset.saturating_add_signed(delta);
+                }
+            }
+        }
+    }
+
     /// Schedule a debounced preview fetch for the currently selected card.
     pub fn schedule_preview_fetch(&mut self) {
         if let Some(card) = self.selected_card() {
@@ -674,7 +754,8 @@ mod tests {
     #[test]
     fn load_board_config_returns_compiled_default_when_no_external_file() {
         // With no external file, load_board_config should succeed with compiled-in default
-        let config = load_board_config().expect("compiled-in default should parse");
+        let config =
+            load_board_config(Path::new("relative")).expect("compiled-in default should parse");
         assert!(
             !config.columns.is_empty(),
             "default config should have columns"
@@ -789,4 +870,61 @@ emoji = "✓"
 
         assert_eq!(state.preview_detail.unwrap().scroll_offset, 5);
     }
+
+    #[test]
+    fn tab_cycles_through_visible_panes() {
+        let mut state = test_board_with_card("X");
+        // No preview loaded yet: the board is the only pane
+        state.cycle_focus(true);
+        assert_eq!(state.focus, BoardFocus::Board);
+
+        state.preview_detail = Some(BeadDetailState::new_loading("X".to_string()));
+        state.cycle_focus(true);
+        assert_eq!(state.focus, BoardFocus::Preview);
+        state.cycle_focus(true);
+        assert_eq!(state.focus, BoardFocus::Board);
+        state.cycle_focus(false);
+        assert_eq!(state.focus, BoardFocus::Preview);
+    }
+
+    #[test]
+    fn scroll_keys_go_to_the_focused_pane() {
+        let mut state = test_board_with_card("X");
+        state.preview_detail = Some(BeadDetailState::new_loading("X".to_string()));
+        state.focus = BoardFocus::Preview;
+        state.scroll_focused(10);
+        state.scroll_focused(-3);
+        assert_eq!(state.preview_detail.as_ref().unwrap().scroll_offset, 7);
+        state.scroll_focused(-20);
+        assert_eq!(state.preview_detail.as_ref().unwrap().scroll_offset, 0);
+        assert_eq!(state.selected_row[0], 0);
+    }
+
+    #[test]
+    fn select_bead_moves_the_cursor_to_its_card() {
+        let mut state = test_board_with_card("X");
+        let mut other = state.columns[0][0].clone();
+        other.id = "Y".to_string();
+        state.columns[0].push(other);
+
+        assert!(state.select_bead("Y"));
+        assert_eq!(state.selected_card().unwrap().id, "Y");
+        assert_eq!(state.preview_pending_id.as_deref(), Some("Y"));
+        assert!(!state.select_bead("missing"));
+        assert_eq!(state.selected_card().unwrap().id, "Y");
+    }
+
+    #[test]
+    fn pending_selection_is_applied_when_its_column_loads() {
+        let mut state = test_board_with_card("X");
+        let mut cards = state.columns[0].clone();
+        cards[0].id = "A".to_string();
+        cards.extend(state.columns[0].clone());
+        state.pending_selection = Some("X".to_string());
+
+        state.populate_column(0, KanbanColumnUpdate { cards });
+
+        assert_eq!(state.selected_card().unwrap().id, "X");
+        assert!(state.pending_selection.is_none());
+    }
 }
@@ -0,0 +1,209 @@
+use std::time::SystemTime;
+
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+use super::state::KanbanBoardState;
+use crate::number_format::NumberFormat;
+use crate::project_summary::{ProjectSummary, format_ago};
+
+fn row(label: &str, value: impl Into<String>, value_style: Style) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(
+            format!("  {label:<14}"),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(value.into(), value_style),
+    ])
+}
+
+fn build_summary_content(
+    summary: &ProjectSummary,
+    board: &KanbanBoardState,
+    now: SystemTime,
+    numbers: &NumberFormat,
+) -> Vec<Line<'static>> {
+    let plain = Style::default().fg(Color::White);
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut content = vec![Line::from("")];
+
+    let counts = board
+        .column_defs
+        .iter()
+        .zip(&board.columns)
+        .map(|(def, cards)| {
+            let n = cards.iter().filter(|c| !c.is_error).count();
+            format!("{} {n}", def.name)
+        })
+        .collect::<Vec<_>>()
+        .join(" \u{b7} ");
+    content.push(row("Board", counts, plain));
+    content.push(row(
+        "Beads",
+        format!(
+            "{} open \u{b7} {} closed",
+            board.open_count, board.closed_count
+        ),
+        plain,
+    ));
+    content.push(row(
+        "Branch",
+        summary.branch.as_deref().unwrap_or("(not a git repo)"),
+        plain,
+    ));
+    content.push(match summary.prompt_modified {
+        Some(modified) => row(
+            "PROMPT.md",
+            format!("project override, edited {}", format_ago(modified, now)),
+            plain,
+        ),
+        None => row("PROMPT.md", "compiled-in default", dim),
+    });
+    content.push(match &summary.last_session {
+        Some(last) => {
+            let ended = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(last.ended_at);
+            row(
+                "Last session",
+                format!(
+                    "{} \u{b7} {} iteration(s) \u{b7} {}",
+                    format_ago(ended, now),
+                    last.iterations,
+                    numbers.cost(last.cost_usd)
+                ),
+                plain,
+            )
+        }
+        None => row("Last session", "none recorded", dim),
+    });
+    if let Some(exit) = &summary.last_exit {
+        let ended = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(exit.ended_at);
+        let mut parts = vec![
+            format!("{} {}", exit.reason, format_ago(ended, now)),
+            format!("was {}", exit.state),
+            format!("{} iteration(s)", exit.iterations),
+            numbers.cost(exit.cost_usd),
+        ];
+        if !exit.beads.is_empty() {
+            parts.push(exit.beads.join(", "));
+        }
+        let style = if exit.clean {
+            plain
+        } else {
+            Style::default().fg(Color::Yellow)
+        };
+        content.push(row("Last exit", parts.join(" \u{b7} "), style));
+    }
+
+    if !summary.warnings.is_empty() {
+        content.push(Line::from(""));
+        content.push(Line::from(Span::styled(
+            "  Warnings",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for warning in &summary.warnings {
+            content.push(Line::from(Span::styled(
+                format!("  \u{2717} {warning}"),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+    }
+
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled(
+        "  Press S to start, or any board key to dismiss",
+        dim,
+    )));
+    content
+}
+
+/// Draw the startup project summary in place of the preview pane.
+pub(super) fn draw_project_summary(
+    f: &mut Frame,
+    summary: &ProjectSummary,
+    board: &KanbanBoardState,
+    numbers: &NumberFormat,
+    area: Rect,
+) {
+    let pane = Paragraph::new(build_summary_content(
+        summary,
+        board,
+        SystemTime::now(),
+        numbers,
+    ))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Project ")
+            .title_alignment(Alignment::Center)
+            .style(Style::default().fg(Color::DarkGray)),
+    )
+    .wrap(Wrap { trim: false });
+    f.render_widget(pane, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_summary::LastSession;
+    use crate::shutdown_summary::ShutdownSummary;
+
+    fn text(lines: &[Line]) -> String {
+        lines
+            .iter()
+            .map(|l| {
+                l.spans
+                    .iter()
+                    .map(|s| s.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn summary_lists_board_branch_last_session_and_warnings() {
+        let board = KanbanBoardState::new_loading(Vec::new());
+        let now = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(10 * 86400);
+        let summary = ProjectSummary {
+            branch: Some("main".into()),
+            prompt_modified: None,
+            last_session: Some(LastSession {
+                session_id: "abc".into(),
+                ended_at: 8 * 86400,
+                iterations: 3,
+                cost_usd: 0.5,
+            }),
+            last_exit: Some(ShutdownSummary {
+                session_id: "abc".into(),
+                pid: 7,
+                ended_at: 9 * 86400,
+                reason: "stopped by SIGHUP".into(),
+                clean: false,
+                state: "running".into(),
+                iterations: 2,
+                cost_usd: 0.25,
+                beads: vec!["ralph-x1".into()],
+                tabs: 1,
+            }),
+            warnings: vec!["bad config".into()],
+        };
+        let out = text(&build_summary_content(
+            &summary,
+            &board,
+            now,
+            &NumberFormat::default(),
+        ));
+        assert!(out.contains("main"));
+        assert!(out.contains("compiled-in default"));
+        assert!(out.contains("2d ago \u{b7} 3 iteration(s) \u{b7} $0.50"));
+        assert!(out.contains(
+            "stopped by SIGHUP 1d ago \u{b7} was running \u{b7} 2 iteration(s) \u{b7} $0.25 \u{b7} ralph-x1"
+        ));
+        assert!(out.contains("\u{2717} bad config"));
+    }
+}
@@ -0,0 +1,52 @@
+//! Lock conflict modal — shown when another ralph instance is running a loop
+//! in this project.
+
+use ratatui::Frame;
+use ratatui::layout::Alignment;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+use crate::app::App;
+use crate::ui::centered_rect;
+
+/// Draw the lock conflict modal.
+pub fn draw_lock_conflict_modal(f: &mut Frame, app: &App) {
+    let Some(info) = &app.lock_conflict else {
+        return;
+    };
+
+    let modal_width: u16 = 64;
+    let modal_height: u16 = 9;
+    let modal_area = centered_rect(modal_width, modal_height, f.area());
+
+    f.render_widget(Clear, modal_area);
+
+    let key_style = Style::default().fg(Color::Cyan);
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let content: Vec<Line> = vec![
+        Line::from(""),
+        Line::from("  Ralph is already running a loop in this project:"),
+        Line::from(Span::styled(format!("  {}", info.describe()), dim)),
+        Line::from(Span::styled(format!("  session {}", info.session_id), dim)),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled("a", key_style),
+            Span::raw(" attach read-only  "),
+            Span::styled("Esc", key_style),
+            Span::raw(" cancel"),
+        ]),
+    ];
+
+    let modal = Paragraph::new(content).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Already Running ")
+            .title_alignment(Alignment::Center)
+            .style(Style::default().fg(Color::Yellow)),
+    );
+
+    f.render_widget(modal, modal_area);
+}
@@ -0,0 +1,76 @@
+//! "Mark spec Done?" prompt, shown when a spec's acceptance criteria are all
+//! checked, or the agent reported it complete, but the bead is still open at
+//! the end of an iteration.
+
+use crossterm::event::KeyCode;
+use ratatui::Frame;
+
+use crate::app::App;
+
+use super::confirm::draw_confirm_prompt;
+
+/// Handle keyboard input for the mark-done prompt.
+pub fn handle_mark_done_input(app: &mut App, key_code: KeyCode) {
+    match key_code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => {
+            let Some(spec) = app.mark_done_prompt.take() else {
+                return;
+            };
+            let reason = if spec.progress.is_complete() {
+                "All acceptance criteria checked"
+            } else {
+                "Agent reported the spec complete"
+            };
+            app.mutate_and_refresh_kanban(vec![
+                "close".into(),
+                spec.bead_id.clone(),
+                "--reason".into(),
+                reason.into(),
+            ]);
+            app.set_hint(format!("Marked {} Done", spec.bead_id));
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+            app.mark_done_prompt = None;
+        }
+        _ => {}
+    }
+}
+
+/// Draw the mark-done prompt.
+pub fn draw_mark_done_modal(f: &mut Frame, app: &App) {
+    let Some(spec) = &app.mark_done_prompt else {
+        return;
+    };
+    draw_confirm_prompt(
+        f,
+        "Spec looks done",
+        &format!("{}. Mark it Done?", spec.reason()),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LoadedConfig;
+    use crate::spec_checklist::{ChecklistProgress, UnclosedSpec};
+
+    #[test]
+    fn declining_dismisses_the_prompt() {
+        let mut app = App::new("test".into(), None, LoadedConfig::default_for_test(), None);
+        app.mark_done_prompt = Some(UnclosedSpec {
+            worker: 0,
+            bead_id: "bd-7".into(),
+            title: "Add parser".into(),
+            status: "in_progress".into(),
+            progress: ChecklistProgress {
+                checked: 2,
+                total: 2,
+            },
+            reported_done: false,
+        });
+        handle_mark_done_input(&mut app, KeyCode::Char('x'));
+        assert!(app.mark_done_prompt.is_some());
+        handle_mark_done_input(&mut app, KeyCode::Esc);
+        assert!(app.mark_done_prompt.is_none());
+    }
+}
@@ -3,26 +3,54 @@
 //! Each modal lives in its own submodule with state, input handler,
 //! and draw function together.
 
+mod auth;
 mod bead_picker;
+mod bead_search;
 mod config;
+mod confirm;
+mod diagnostics;
+mod dirty_start;
 mod help;
 mod init;
+mod instructions;
 mod kanban;
+mod lock_conflict;
+mod mark_done;
+mod new_tab;
+mod question_reply;
 mod quit;
+mod rerun;
+mod restore;
 mod tool_allow;
 mod workers_stream;
 
+pub use auth::draw_auth_modal;
 pub use bead_picker::{
     BeadPickerItem, BeadPickerState, draw_bead_picker, fetch_bead_picker_data,
     handle_bead_picker_input,
 };
+pub use bead_search::{
+    BeadSearchItem, BeadSearchState, draw_bead_search, fetch_bead_search_data,
+    handle_bead_search_input,
+};
 pub use config::{ConfigModalState, draw_config_modal, handle_config_modal_input};
+pub use diagnostics::{DiagnosticsState, draw_diagnostics_modal, handle_diagnostics_input};
+pub use dirty_start::{draw_dirty_start_modal, handle_dirty_start_input};
 pub use help::{HelpContext, draw_help_modal};
 pub use init::{InitModalState, draw_init_modal, handle_init_modal_input};
+pub use instructions::{InstructionsState, draw_instructions_modal, handle_instructions_input};
 pub use kanban::{
-    BeadDetailState, BoardAction, BoardConfig, DepDirection, KanbanBoardState, KanbanFetchMsg,
-    draw_kanban_board, handle_kanban_input, load_board_config, stream_board_data,
+    BeadDetailState, BoardAction, BoardConfig, BoardFocus, DepDirection, KanbanBoardState,
+    KanbanFetchMsg, draw_kanban_board, handle_kanban_input, load_board_config, stream_board_data,
+};
+pub use lock_conflict::draw_lock_conflict_modal;
+pub use mark_done::{draw_mark_done_modal, handle_mark_done_input};
+pub use new_tab::{NewTabState, draw_new_tab_modal, handle_new_tab_input};
+pub use question_reply::{
+    QuestionReplyState, draw_question_reply_modal, handle_question_reply_input,
 };
 pub use quit::draw_quit_modal;
+pub use rerun::{RerunState, draw_rerun_modal, handle_rerun_input, open_rerun_modal};
+pub use restore::{RestoreState, draw_restore_modal, handle_restore_input, open_restore_modal};
 pub use tool_allow::{ToolAllowModalState, draw_tool_allow_modal, handle_tool_allow_modal_input};
 pub use workers_stream::{WorkersStreamState, draw_workers_stream, handle_workers_stream_input};
@@ -0,0 +1,185 @@
+//! New tab prompt (`Ctrl+t`): asks which project the tab opens on.
+//!
+//! Prefilled with the current tab's project. The tab resolves its config,
+//! work source, and session lock from the directory chosen here.
+
+use std::path::{Path, PathBuf};
+
+use crossterm::event::KeyCode;
+use ratatui::Frame;
+use ratatui::layout::Alignment;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph};
+use thiserror::Error;
+
+use crate::app::App;
+use crate::config::Config;
+use crate::ui::{TextInput, centered_rect};
+
+/// Why the typed directory can't be opened.
+#[derive(Debug, Error)]
+enum NewTabError {
+    #[error("Enter a directory")]
+    Empty,
+    #[error("Not a directory: {}", .0.display())]
+    NotADirectory(PathBuf),
+    #[error("{}: {source}", path.display())]
+    Resolve {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// State for the new tab prompt.
+#[derive(Debug, Clone, Default)]
+pub struct NewTabState {
+    pub input: TextInput,
+    /// Why the last Enter was refused.
+    pub error: Option<String>,
+}
+
+impl NewTabState {
+    pub fn new(project_dir: &Path) -> Self {
+        Self {
+            input: TextInput::new(project_dir.display().to_string()),
+            error: None,
+        }
+    }
+}
+
+/// The project directory typed into the prompt, made absolute. `~/` is
+/// expanded; relative paths are taken from `base`.
+fn resolve_dir(input: &str, base: &Path) -> Result<PathBuf, NewTabError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(NewTabError::Empty);
+    }
+    let dir = base.join(Config::expand_tilde(input));
+    if !dir.is_dir() {
+        return Err(NewTabError::NotADirectory(dir));
+    }
+    dir.canonicalize()
+        .map_err(|source| NewTabError::Resolve { path: dir, source })
+}
+
+/// Handle keyboard input for the new tab prompt. Returns the project
+/// directory once one is confirmed.
+pub fn handle_new_tab_input(app: &mut App, key_code: KeyCode) -> Option<PathBuf> {
+    let state = app.new_tab_state.as_mut()?;
+    match key_code {
+        KeyCode::Esc => app.new_tab_state = None,
+        KeyCode::Enter => match resolve_dir(state.input.value(), &app.project_dir) {
+            Ok(dir) => {
+                app.new_tab_state = None;
+                return Some(dir);
+            }
+            Err(e) => state.error = Some(e.to_string()),
+        },
+        KeyCode::Char(c) => state.input.insert_char(c),
+        KeyCode::Backspace => {
+            state.input.delete_char_before();
+        }
+        KeyCode::Delete => {
+            state.input.delete_char_at();
+        }
+        KeyCode::Left => state.input.cursor_left(),
+        KeyCode::Right => state.input.cursor_right(),
+        KeyCode::Home => state.input.cursor_home(),
+        KeyCode::End => state.input.cursor_end(),
+        _ => {}
+    }
+    None
+}
+
+/// Draw the new tab prompt.
+pub fn draw_new_tab_modal(f: &mut Frame, app: &App) {
+    let Some(state) = &app.new_tab_state else {
+        return;
+    };
+
+    let modal_width: u16 = 70;
+    let field_width = modal_width.saturating_sub(6) as usize;
+    let modal_area = centered_rect(modal_width, 7, f.area());
+    f.render_widget(Clear, modal_area);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let key_style = Style::default().fg(Color::Cyan);
+
+    let mut content = vec![
+        Line::styled("Project directory:", dim),
+        Line::from(state.input.spans(field_width)),
+        Line::from(""),
+    ];
+    if let Some(error) = &state.error {
+        content.push(Line::styled(error.clone(), Style::default().fg(Color::Red)));
+    }
+
+    let modal = Paragraph::new(content).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" New Tab ")
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(vec![
+                Span::styled(" Enter", key_style),
+                Span::styled(" open  ", dim),
+                Span::styled("Esc", key_style),
+                Span::styled(" cancel ", dim),
+            ]))
+            .padding(Padding::new(2, 2, 1, 0))
+            .style(Style::default().fg(Color::White)),
+    );
+    f.render_widget(modal, modal_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LoadedConfig;
+
+    fn type_path(app: &mut App, path: &str) {
+        app.new_tab_state = Some(NewTabState::default());
+        for c in path.chars() {
+            handle_new_tab_input(app, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn enter_opens_an_existing_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new("test".into(), None, LoadedConfig::default_for_test(), None);
+        type_path(&mut app, &dir.path().display().to_string());
+        assert_eq!(
+            handle_new_tab_input(&mut app, KeyCode::Enter),
+            Some(dir.path().canonicalize().unwrap())
+        );
+        assert!(app.new_tab_state.is_none());
+    }
+
+    #[test]
+    fn missing_directory_keeps_the_prompt_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new("test".into(), None, LoadedConfig::default_for_test(), None);
+        type_path(&mut app, &dir.path().join("gone").display().to_string());
+        assert_eq!(handle_new_tab_input(&mut app, KeyCode::Enter), None);
+        let state = app.new_tab_state.as_ref().unwrap();
+        assert!(state.error.as_ref().unwrap().starts_with("Not a directory"));
+
+        handle_new_tab_input(&mut app, KeyCode::Esc);
+        assert!(app.new_tab_state.is_none());
+    }
+
+    #[test]
+    fn relative_paths_are_taken_from_the_current_project() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("other")).unwrap();
+        assert_eq!(
+            resolve_dir(" other ", dir.path()).unwrap(),
+            dir.path().join("other").canonicalize().unwrap()
+        );
+        assert!(matches!(
+            resolve_dir("", dir.path()),
+            Err(NewTabError::Empty)
+        ));
+    }
+}
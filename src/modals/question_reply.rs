@@ -0,0 +1,207 @@
+//! Reply box for a question Claude ended its iteration with
+//! (`behavior.pause_on_question`, see [`crate::agent_question`]).
+//!
+//! Opens when the question holds auto-continue. The reply is queued for the
+//! next iteration's prompt like an `I` instruction, and the run carries on.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::Alignment;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap};
+use tracing::info;
+
+use crate::app::App;
+use crate::ui::{TextInput, centered_rect};
+
+/// Most lines of question text shown.
+const MAX_QUESTION_LINES: u16 = 10;
+
+/// State for the question reply box.
+#[derive(Debug, Clone, Default)]
+pub struct QuestionReplyState {
+    /// The worker whose question this answers.
+    pub worker: usize,
+    pub input: TextInput,
+}
+
+impl App {
+    /// Open the reply box for the first worker held by its question.
+    /// Returns false if none is.
+    pub fn open_question_reply(&mut self) -> bool {
+        let Some(worker) = self
+            .workers
+            .iter()
+            .position(|w| w.auto_continue_pending && w.question.is_some())
+        else {
+            return false;
+        };
+        self.question_reply_state = Some(QuestionReplyState {
+            worker,
+            input: TextInput::default(),
+        });
+        self.dirty = true;
+        true
+    }
+
+    /// Let worker `w` carry on past its question. With `reply`, the answer is
+    /// queued for the next iteration's prompt; without, the question is skipped.
+    pub fn answer_question(&mut self, w: usize, reply: Option<&str>) {
+        self.question_reply_state = None;
+        let Some(question) = self.workers[w].question.take() else {
+            return;
+        };
+        match reply {
+            Some(reply) => {
+                info!(worker = w, "question_answered");
+                self.instruction_queue
+                    .push(&format!("You asked: \"{question}\" Answer: {reply}"));
+                self.add_text_line(format!("[Answered: {reply}]"));
+            }
+            None => {
+                info!(worker = w, "question_skipped");
+                self.add_text_line("[Question skipped]".to_string());
+            }
+        }
+        self.dirty = true;
+        // Another worker may be waiting on its own question
+        self.open_question_reply();
+    }
+}
+
+/// Handle keyboard input for the question reply box.
+pub fn handle_question_reply_input(app: &mut App, key_code: KeyCode, modifiers: KeyModifiers) {
+    let Some(state) = &mut app.question_reply_state else {
+        return;
+    };
+    match key_code {
+        // Still held; `a` reopens the box
+        KeyCode::Esc => app.question_reply_state = None,
+        KeyCode::Enter => {
+            let reply = state.input.value().trim().to_string();
+            if !reply.is_empty() {
+                let w = state.worker;
+                app.answer_question(w, Some(&reply));
+            }
+        }
+        KeyCode::Char('s') if modifiers.contains(KeyModifiers::CONTROL) => {
+            let w = state.worker;
+            app.answer_question(w, None);
+        }
+        KeyCode::Char(c) => state.input.insert_char(c),
+        KeyCode::Backspace => {
+            state.input.delete_char_before();
+        }
+        KeyCode::Delete => {
+            state.input.delete_char_at();
+        }
+        KeyCode::Left => state.input.cursor_left(),
+        KeyCode::Right => state.input.cursor_right(),
+        KeyCode::Home => state.input.cursor_home(),
+        KeyCode::End => state.input.cursor_end(),
+        _ => {}
+    }
+}
+
+/// Draw the question reply box.
+pub fn draw_question_reply_modal(f: &mut Frame, app: &App) {
+    let Some(state) = &app.question_reply_state else {
+        return;
+    };
+    let Some(question) = app
+        .workers
+        .get(state.worker)
+        .and_then(|w| w.question.as_deref())
+    else {
+        return;
+    };
+
+    let modal_width: u16 = 70;
+    let field_width = modal_width.saturating_sub(6) as usize;
+    let question_lines =
+        (question.chars().count().div_ceil(field_width) as u16).clamp(1, MAX_QUESTION_LINES);
+    let modal_height = question_lines + 6;
+    let modal_area = centered_rect(modal_width, modal_height, f.area());
+    f.render_widget(Clear, modal_area);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let key_style = Style::default().fg(Color::Cyan);
+    let question_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+
+    let mut content = vec![Line::from("")];
+    content.push(Line::styled(question.to_string(), question_style));
+    content.push(Line::from(""));
+    content.push(Line::from(state.input.spans(field_width)));
+
+    let title = if app.workers.len() > 1 {
+        format!(" Claude asks (worker {}) ", state.worker + 1)
+    } else {
+        " Claude asks ".to_string()
+    };
+    let modal = Paragraph::new(content).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .title_bottom(Line::from(vec![
+                Span::styled(" Enter", key_style),
+                Span::styled(" answer  ", dim),
+                Span::styled("Ctrl+s", key_style),
+                Span::styled(" skip  ", dim),
+                Span::styled("Esc", key_style),
+                Span::styled(" later ", dim),
+            ]))
+            .padding(Padding::horizontal(2))
+            .style(Style::default().fg(Color::White)),
+    );
+    f.render_widget(modal, modal_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LoadedConfig;
+
+    fn asking_app() -> App {
+        let mut app = App::new("test".into(), None, LoadedConfig::default_for_test(), None);
+        app.workers[0].question = Some("Keep the v1 endpoint?".into());
+        app.workers[0].auto_continue_pending = true;
+        assert!(app.open_question_reply());
+        app
+    }
+
+    #[test]
+    fn answer_is_queued_for_the_next_iteration() {
+        let mut app = asking_app();
+        // A blank answer doesn't count
+        handle_question_reply_input(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.question_reply_state.is_some());
+        for c in "drop it".chars() {
+            handle_question_reply_input(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        handle_question_reply_input(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.question_reply_state.is_none());
+        assert!(app.workers[0].question.is_none());
+        assert_eq!(
+            app.instruction_queue.pending(),
+            vec!["You asked: \"Keep the v1 endpoint?\" Answer: drop it"]
+        );
+    }
+
+    #[test]
+    fn esc_leaves_the_question_waiting_and_ctrl_s_skips_it() {
+        let mut app = asking_app();
+        handle_question_reply_input(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.question_reply_state.is_none());
+        assert!(app.workers[0].question.is_some());
+
+        assert!(app.open_question_reply());
+        handle_question_reply_input(&mut app, KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert!(app.workers[0].question.is_none());
+        assert!(app.instruction_queue.pending().is_empty());
+        assert!(!app.open_question_reply());
+    }
+}
@@ -0,0 +1,421 @@
+//! Re-run modal — pick a Bash command from a worker's last failed iteration and
+//! run it locally (outside claude) to check whether the failure reproduces.
+
+use std::path::PathBuf;
+use std::process::Child;
+use std::sync::mpsc::Receiver;
+use std::time::Instant;
+
+use crossterm::event::KeyCode;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use tracing::{info, warn};
+
+use crate::app::{App, BashRun};
+use crate::shell_command;
+use crate::ui::{format_elapsed, truncate_to_width};
+
+/// Most output lines kept for one run (oldest are dropped).
+const MAX_OUTPUT_LINES: usize = 5000;
+
+/// A line of output from the re-run command.
+pub use crate::shell_command::OutputLine as RerunLine;
+
+/// Outcome of the current or last run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RerunStatus {
+    Idle,
+    Running(Instant),
+    Exited(Option<i32>),
+    Failed(String),
+}
+
+/// State for the re-run modal.
+#[derive(Debug)]
+pub struct RerunState {
+    /// Iteration the commands came from.
+    pub iteration: u32,
+    pub commands: Vec<BashRun>,
+    /// Selected index into `commands`.
+    pub selected: usize,
+    /// Directory commands run in (the worker's worktree, or the repo).
+    pub cwd: PathBuf,
+    pub status: RerunStatus,
+    pub output: Vec<RerunLine>,
+    /// Lines scrolled up from the bottom of the output.
+    pub scroll_back: usize,
+    child: Option<Child>,
+    receiver: Option<Receiver<RerunLine>>,
+}
+
+impl RerunState {
+    /// Open on `commands`, preselecting the last failed one.
+    pub fn new(iteration: u32, commands: Vec<BashRun>, cwd: PathBuf) -> Self {
+        let selected = commands.iter().rposition(|c| c.failed).unwrap_or(0);
+        Self {
+            iteration,
+            commands,
+            selected,
+            cwd,
+            status: RerunStatus::Idle,
+            output: Vec::new(),
+            scroll_back: 0,
+            child: None,
+            receiver: None,
+        }
+    }
+
+    fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    fn select_next(&mut self) {
+        if self.selected + 1 < self.commands.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(self.status, RerunStatus::Running(_))
+    }
+
+    /// Run the selected command with `sh -c`, streaming stdout and stderr.
+    fn start(&mut self) {
+        if self.is_running() {
+            return;
+        }
+        let Some(run) = self.commands.get(self.selected) else {
+            return;
+        };
+        self.output.clear();
+        self.scroll_back = 0;
+
+        // Cancelling must also stop what the shell started (cargo, test binaries)
+        let (child, rx) = match shell_command::spawn(&run.command, Some(&self.cwd)) {
+            Ok(spawned) => spawned,
+            Err(e) => {
+                warn!(error = %e, "rerun_spawn_failed");
+                self.status = RerunStatus::Failed(e.to_string());
+                return;
+            }
+        };
+        info!(command = %run.command, pid = child.id(), "rerun_started");
+        self.child = Some(child);
+        self.receiver = Some(rx);
+        self.status = RerunStatus::Running(Instant::now());
+    }
+
+    /// Kill the running command, if any.
+    fn cancel(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            crate::control::kill_process(&mut child);
+            info!(pid = child.id(), "rerun_cancelled");
+            self.status = RerunStatus::Failed("cancelled".to_string());
+        }
+    }
+
+    /// Drain new output and detect exit. Returns true if anything changed.
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        if let Some(rx) = &self.receiver {
+            for line in rx.try_iter() {
+                self.output.push(line);
+                changed = true;
+            }
+            if self.output.len() > MAX_OUTPUT_LINES {
+                self.output.drain(..self.output.len() - MAX_OUTPUT_LINES);
+            }
+        }
+        if let Some(child) = &mut self.child
+            && let Ok(Some(status)) = child.try_wait()
+        {
+            info!(code = ?status.code(), "rerun_exited");
+            self.status = RerunStatus::Exited(status.code());
+            self.child = None;
+            changed = true;
+        }
+        // Running commands update the elapsed timer every tick
+        changed || self.is_running()
+    }
+}
+
+impl Drop for RerunState {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Open the modal for the selected worker's last failed iteration.
+pub fn open_rerun_modal(app: &mut App, worker: usize) {
+    if app.attached_session.is_some() {
+        app.set_hint("Re-run is unavailable while attached");
+        return;
+    }
+    let Some(w) = app.workers.get(worker) else {
+        return;
+    };
+    let Some(record) = w.last_failed_iteration() else {
+        app.set_hint("No failed iteration with Bash commands yet");
+        return;
+    };
+    let cwd = w
+        .worktree_path
+        .clone()
+        .filter(|p| p.is_dir())
+        .unwrap_or_else(|| app.project_dir.clone());
+    app.rerun_state = Some(RerunState::new(
+        record.iteration,
+        record.commands.clone(),
+        cwd,
+    ));
+}
+
+/// Handle keyboard input for the re-run modal.
+pub fn handle_rerun_input(app: &mut App, key_code: KeyCode) {
+    let Some(state) = &mut app.rerun_state else {
+        return;
+    };
+    match key_code {
+        KeyCode::Esc => {
+            if state.is_running() {
+                state.cancel();
+            } else {
+                app.rerun_state = None;
+            }
+        }
+        KeyCode::Char('k') | KeyCode::Up => state.select_prev(),
+        KeyCode::Char('j') | KeyCode::Down => state.select_next(),
+        KeyCode::Enter => state.start(),
+        KeyCode::PageUp => {
+            state.scroll_back = (state.scroll_back + 10).min(state.output.len());
+        }
+        KeyCode::PageDown => {
+            state.scroll_back = state.scroll_back.saturating_sub(10);
+        }
+        _ => {}
+    }
+}
+
+/// Draw the re-run modal.
+pub fn draw_rerun_modal(f: &mut Frame, app: &App) {
+    let Some(state) = &app.rerun_state else {
+        return;
+    };
+
+    let area = f.area();
+    let modal_area = Rect {
+        x: area.x + 2,
+        y: area.y + 1,
+        width: area.width.saturating_sub(4),
+        height: area.height.saturating_sub(2),
+    };
+    f.render_widget(Clear, modal_area);
+
+    let key_style = Style::default().fg(Color::Cyan);
+    let dim = Style::default().fg(Color::DarkGray);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            " Re-run from iteration {} \u{b7} {} ",
+            state.iteration,
+            state.cwd.display()
+        ))
+        .title_bottom(Line::from(vec![
+            Span::styled(" Enter", key_style),
+            Span::styled(" run  ", dim),
+            Span::styled("j/k", key_style),
+            Span::styled(" select  ", dim),
+            Span::styled("PgUp/PgDn", key_style),
+            Span::styled(" scroll  ", dim),
+            Span::styled("Esc", key_style),
+            Span::styled(
+                if state.is_running() {
+                    " stop "
+                } else {
+                    " close "
+                },
+                dim,
+            ),
+        ]))
+        .style(Style::default().fg(Color::White));
+    let inner = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    let list_height = (state.commands.len() as u16).min(inner.height / 3).max(1);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(list_height),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    // Command list, keeping the selection in view
+    let visible = list_height as usize;
+    let first = state.selected.saturating_sub(visible.saturating_sub(1));
+    let width = chunks[0].width as usize;
+    let list: Vec<Line> = state
+        .commands
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible)
+        .map(|(i, run)| {
+            let marker = if i == state.selected { "> " } else { "  " };
+            let (icon, icon_style) = if run.failed {
+                ("\u{274c} ", Style::default().fg(Color::Red))
+            } else {
+                ("\u{2705} ", Style::default().fg(Color::Green))
+            };
+            let command = run.command.replace('\n', " ");
+            let text_style = if i == state.selected {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::raw(marker),
+                Span::styled(icon, icon_style),
+                Span::styled(
+                    truncate_to_width(&command, width.saturating_sub(5), "\u{2026}"),
+                    text_style,
+                ),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(list), chunks[0]);
+
+    f.render_widget(Paragraph::new(status_line(&state.status)), chunks[1]);
+
+    // Output tail, scrolled back by `scroll_back` lines
+    let height = chunks[2].height as usize;
+    let end = state.output.len().saturating_sub(state.scroll_back);
+    let start = end.saturating_sub(height);
+    let output: Vec<Line> = state.output[start..end]
+        .iter()
+        .map(|l| {
+            if l.stderr {
+                Line::styled(l.text.clone(), Style::default().fg(Color::LightRed))
+            } else {
+                Line::raw(l.text.clone())
+            }
+        })
+        .collect();
+    f.render_widget(Paragraph::new(output), chunks[2]);
+}
+
+/// One-line summary of the run status.
+fn status_line(status: &RerunStatus) -> Line<'static> {
+    let dim = Style::default().fg(Color::DarkGray);
+    match status {
+        RerunStatus::Idle => Line::styled("Press Enter to run the selected command", dim),
+        RerunStatus::Running(started) => Line::styled(
+            format!("Running\u{2026} {}", format_elapsed(started.elapsed())),
+            Style::default().fg(Color::Yellow),
+        ),
+        RerunStatus::Exited(Some(0)) => Line::styled("Exited 0", Style::default().fg(Color::Green)),
+        RerunStatus::Exited(code) => Line::styled(
+            match code {
+                Some(c) => format!("Exited {c}"),
+                None => "Killed by signal".to_string(),
+            },
+            Style::default().fg(Color::Red),
+        ),
+        RerunStatus::Failed(e) => {
+            Line::styled(format!("Failed: {e}"), Style::default().fg(Color::Red))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn run(command: &str, failed: bool) -> BashRun {
+        BashRun {
+            command: command.to_string(),
+            failed,
+        }
+    }
+
+    fn wait_for_exit(state: &mut RerunState) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while state.is_running() && Instant::now() < deadline {
+            state.poll();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        // Readers may deliver the tail just after exit
+        std::thread::sleep(Duration::from_millis(50));
+        state.poll();
+    }
+
+    #[test]
+    fn new_preselects_last_failed_command() {
+        let state = RerunState::new(
+            3,
+            vec![
+                run("a", true),
+                run("b", false),
+                run("c", true),
+                run("d", false),
+            ],
+            PathBuf::from("."),
+        );
+        assert_eq!(state.selected, 2);
+    }
+
+    #[test]
+    fn selection_clamps_to_commands() {
+        let mut state = RerunState::new(1, vec![run("a", false), run("b", false)], ".".into());
+        state.select_prev();
+        assert_eq!(state.selected, 0);
+        state.select_next();
+        state.select_next();
+        assert_eq!(state.selected, 1);
+    }
+
+    #[test]
+    fn start_captures_output_and_exit_code() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = RerunState::new(
+            1,
+            vec![run("echo out; echo err >&2; pwd; exit 3", true)],
+            dir.path().to_path_buf(),
+        );
+        state.start();
+        wait_for_exit(&mut state);
+
+        assert_eq!(state.status, RerunStatus::Exited(Some(3)));
+        assert!(state.output.contains(&RerunLine {
+            text: "out".into(),
+            stderr: false
+        }));
+        assert!(state.output.contains(&RerunLine {
+            text: "err".into(),
+            stderr: true
+        }));
+        let canonical = dir.path().canonicalize().unwrap();
+        assert!(
+            state
+                .output
+                .iter()
+                .any(|l| PathBuf::from(&l.text).canonicalize().ok() == Some(canonical.clone()))
+        );
+    }
+
+    #[test]
+    fn cancel_kills_running_command() {
+        let mut state = RerunState::new(1, vec![run("sleep 30", true)], ".".into());
+        state.start();
+        assert!(state.is_running());
+
+        state.cancel();
+        assert!(!state.is_running());
+        assert!(state.child.is_none());
+    }
+}
@@ -0,0 +1,157 @@
+//! Restore modal — pick a file backed up before Claude changed it
+//! (`behavior.backup_files`) and copy the saved contents back.
+
+use crossterm::event::KeyCode;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::App;
+use crate::file_backup;
+use crate::ui::truncate_to_width;
+
+/// State for the restore modal.
+#[derive(Debug, Default)]
+pub struct RestoreState {
+    /// Selected index into the backups, newest first.
+    pub selected: usize,
+}
+
+/// Open the restore modal, or explain why there is nothing to restore.
+pub fn open_restore_modal(app: &mut App) {
+    if app.file_backups.entries().is_empty() {
+        app.set_hint(if app.config.behavior.backup_files {
+            "No files backed up this session"
+        } else {
+            "File backups are off (behavior.backup_files)"
+        });
+        return;
+    }
+    app.restore_state = Some(RestoreState::default());
+}
+
+/// Handle keyboard input for the restore modal.
+pub fn handle_restore_input(app: &mut App, key_code: KeyCode) {
+    let Some(state) = &mut app.restore_state else {
+        return;
+    };
+    let count = app.file_backups.entries().len();
+    match key_code {
+        KeyCode::Esc | KeyCode::Char('q') => app.restore_state = None,
+        KeyCode::Char('k') | KeyCode::Up => state.selected = state.selected.saturating_sub(1),
+        KeyCode::Char('j') | KeyCode::Down => {
+            state.selected = (state.selected + 1).min(count.saturating_sub(1));
+        }
+        KeyCode::Enter => {
+            let Some(backup) = app.file_backups.entries().iter().rev().nth(state.selected) else {
+                return;
+            };
+            let message = match file_backup::restore(backup) {
+                Ok(()) => format!(
+                    "Restored {} (as of iteration {})",
+                    backup.original.display(),
+                    backup.iteration
+                ),
+                Err(e) => format!("Restore failed: {e}"),
+            };
+            app.restore_state = None;
+            app.set_hint(message);
+        }
+        _ => {}
+    }
+}
+
+/// Draw the restore modal.
+pub fn draw_restore_modal(f: &mut Frame, app: &App) {
+    let Some(state) = &app.restore_state else {
+        return;
+    };
+
+    let area = f.area();
+    let modal_area = Rect {
+        x: area.x + 2,
+        y: area.y + 1,
+        width: area.width.saturating_sub(4),
+        height: area.height.saturating_sub(2),
+    };
+    f.render_widget(Clear, modal_area);
+
+    let key_style = Style::default().fg(Color::Cyan);
+    let dim = Style::default().fg(Color::DarkGray);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Restore a file from before Claude changed it ")
+        .title_bottom(Line::from(vec![
+            Span::styled(" Enter", key_style),
+            Span::styled(" restore  ", dim),
+            Span::styled("j/k", key_style),
+            Span::styled(" select  ", dim),
+            Span::styled("Esc", key_style),
+            Span::styled(" close ", dim),
+        ]))
+        .style(Style::default().fg(Color::White));
+    let inner = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    // Newest first, keeping the selection in view
+    let visible = inner.height as usize;
+    let first = state.selected.saturating_sub(visible.saturating_sub(1));
+    let width = inner.width as usize;
+    let lines: Vec<Line> = app
+        .file_backups
+        .entries()
+        .iter()
+        .rev()
+        .enumerate()
+        .skip(first)
+        .take(visible)
+        .map(|(i, backup)| {
+            let marker = if i == state.selected { "> " } else { "  " };
+            let label = format!("W{} #{} ", backup.worker + 1, backup.iteration);
+            let path_style = if i == state.selected {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::raw(marker),
+                Span::styled(label.clone(), dim),
+                Span::styled(
+                    truncate_to_width(
+                        &backup.original.display().to_string(),
+                        width.saturating_sub(marker.len() + label.len()),
+                        "\u{2026}",
+                    ),
+                    path_style,
+                ),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LoadedConfig;
+
+    #[test]
+    fn open_without_backups_explains_why() {
+        let mut app = App::new("test".into(), None, LoadedConfig::default_for_test(), None);
+        open_restore_modal(&mut app);
+        assert!(app.restore_state.is_none());
+        assert_eq!(
+            app.hint.as_ref().map(|(h, _)| h.as_str()),
+            Some("File backups are off (behavior.backup_files)")
+        );
+
+        app.config.behavior.backup_files = true;
+        open_restore_modal(&mut app);
+        assert_eq!(
+            app.hint.as_ref().map(|(h, _)| h.as_str()),
+            Some("No files backed up this session")
+        );
+    }
+}
@@ -10,7 +10,7 @@ use tracing::debug;
 
 use crate::app::App;
 use crate::tool_settings;
-use crate::ui::centered_rect;
+use crate::ui::{TextInput, centered_rect, truncate_to_width};
 
 /// Which field is focused in the tool allow modal.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -44,9 +44,7 @@ pub struct ToolAllowModalState {
     /// The tool name (e.g., "Bash").
     pub tool_name: String,
     /// The editable pattern (e.g., "Bash(git status)").
-    pub pattern: String,
-    /// Cursor position within the pattern string.
-    pub cursor_pos: usize,
+    pub pattern: TextInput,
     /// Currently focused field.
     pub focus: ToolAllowField,
     /// Error message from a failed allow attempt.
@@ -62,62 +60,13 @@ impl ToolAllowModalState {
         } else {
             format!("{}({})", tool_name, summary)
         };
-        let cursor_pos = pattern.len();
         Self {
             tool_name: tool_name.to_string(),
-            pattern,
-            cursor_pos,
+            pattern: TextInput::new(pattern),
             focus: ToolAllowField::Pattern,
             error: None,
         }
     }
-
-    pub fn insert_char(&mut self, c: char) {
-        self.pattern.insert(self.cursor_pos, c);
-        self.cursor_pos += c.len_utf8();
-    }
-
-    pub fn delete_char_before(&mut self) {
-        if self.cursor_pos > 0 {
-            let prev = self.pattern[..self.cursor_pos]
-                .chars()
-                .last()
-                .map(|c| c.len_utf8())
-                .unwrap_or(0);
-            self.cursor_pos -= prev;
-            self.pattern.remove(self.cursor_pos);
-        }
-    }
-
-    pub fn cursor_left(&mut self) {
-        if self.cursor_pos > 0 {
-            let prev = self.pattern[..self.cursor_pos]
-                .chars()
-                .last()
-                .map(|c| c.len_utf8())
-                .unwrap_or(0);
-            self.cursor_pos -= prev;
-        }
-    }
-
-    pub fn cursor_right(&mut self) {
-        if self.cursor_pos < self.pattern.len() {
-            let next = self.pattern[self.cursor_pos..]
-                .chars()
-                .next()
-                .map(|c| c.len_utf8())
-                .unwrap_or(0);
-            self.cursor_pos += next;
-        }
-    }
-
-    pub fn cursor_home(&mut self) {
-        self.cursor_pos = 0;
-    }
-
-    pub fn cursor_end(&mut self) {
-        self.cursor_pos = self.pattern.len();
-    }
 }
 
 /// Handle input for the tool allow modal.
@@ -141,7 +90,7 @@ pub fn handle_tool_allow_modal_input(app: &mut App, key_code: KeyCode, _modifier
         }
         KeyCode::Enter => match state.focus {
             ToolAllowField::Pattern | ToolAllowField::AllowButton => {
-                let pattern = state.pattern.clone();
+                let pattern = state.pattern.value().to_string();
                 if pattern.is_empty() {
                     state.error = Some("Pattern cannot be empty".to_string());
                     return;
@@ -164,12 +113,17 @@ pub fn handle_tool_allow_modal_input(app: &mut App, key_code: KeyCode, _modifier
         },
         _ => match state.focus {
             ToolAllowField::Pattern => match key_code {
-                KeyCode::Char(c) => state.insert_char(c),
-                KeyCode::Backspace => state.delete_char_before(),
-                KeyCode::Left => state.cursor_left(),
-                KeyCode::Right => state.cursor_right(),
-                KeyCode::Home => state.cursor_home(),
-                KeyCode::End => state.cursor_end(),
+                KeyCode::Char(c) => state.pattern.insert_char(c),
+                KeyCode::Backspace => {
+                    state.pattern.delete_char_before();
+                }
+                KeyCode::Delete => {
+                    state.pattern.delete_char_at();
+                }
+                KeyCode::Left => state.pattern.cursor_left(),
+                KeyCode::Right => state.pattern.cursor_right(),
+                KeyCode::Home => state.pattern.cursor_home(),
+                KeyCode::End => state.pattern.cursor_end(),
                 _ => {}
             },
             ToolAllowField::AllowButton | ToolAllowField::CancelButton => match key_code {
@@ -187,56 +141,6 @@ pub fn handle_tool_allow_modal_input(app: &mut App, key_code: KeyCode, _modifier
     }
 }
 
-/// Render a text field with cursor.
-fn render_text_field(value: &str, cursor_pos: usize, field_width: usize) -> Vec<Span<'static>> {
-    let display_value: String = if value.len() > field_width {
-        let start = cursor_pos.saturating_sub(field_width / 2);
-        let end = (start + field_width).min(value.len());
-        let start = end.saturating_sub(field_width);
-        value[start..end].to_string()
-    } else {
-        value.to_string()
-    };
-
-    let visible_cursor = if value.len() > field_width {
-        let start = cursor_pos.saturating_sub(field_width / 2);
-        let end = (start + field_width).min(value.len());
-        let start = end.saturating_sub(field_width);
-        cursor_pos - start
-    } else {
-        cursor_pos
-    };
-
-    let char_indices: Vec<_> = display_value.char_indices().collect();
-    let (before, cursor_char, rest) = if visible_cursor < char_indices.len() {
-        let (idx, _) = char_indices[visible_cursor];
-        let before = display_value[..idx].to_string();
-        let cc = display_value[idx..]
-            .chars()
-            .next()
-            .unwrap_or(' ')
-            .to_string();
-        let rest_start = idx + cc.len();
-        let rest = if rest_start < display_value.len() {
-            display_value[rest_start..].to_string()
-        } else {
-            String::new()
-        };
-        (before, cc, rest)
-    } else {
-        (display_value.clone(), " ".to_string(), String::new())
-    };
-
-    vec![
-        Span::styled(before, Style::default().fg(Color::White)),
-        Span::styled(
-            cursor_char,
-            Style::default().fg(Color::Black).bg(Color::White),
-        ),
-        Span::styled(rest, Style::default().fg(Color::White)),
-    ]
-}
-
 /// Draw the tool allow modal.
 pub fn draw_tool_allow_modal(f: &mut Frame, app: &App) {
     let Some(state) = &app.tool_allow_modal_state else {
@@ -255,13 +159,9 @@ pub fn draw_tool_allow_modal(f: &mut Frame, app: &App) {
     // Pattern field with cursor
     let pattern_focused = state.focus == ToolAllowField::Pattern;
     let pattern_spans = if pattern_focused {
-        render_text_field(&state.pattern, state.cursor_pos, field_width)
+        state.pattern.spans(field_width)
     } else {
-        let display = if state.pattern.len() > field_width {
-            format!("{}…", &state.pattern[..field_width - 1])
-        } else {
-            state.pattern.clone()
-        };
+        let display = truncate_to_width(state.pattern.value(), field_width, "…");
         vec![Span::styled(display, Style::default().fg(Color::White))]
     };
 
@@ -5,9 +5,17 @@ use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+use unicode_width::UnicodeWidthStr;
 
 use crate::app::App;
+use crate::iteration_sections::{self, Row};
+use crate::number_format::NumberFormat;
+use crate::ui::truncate_to_width;
+
+/// Columns moved per `h`/`l` press when wrapping is off.
+const H_SCROLL_STEP: usize = 8;
 
 /// State for the workers stream modal.
 #[derive(Debug)]
@@ -20,6 +28,12 @@ pub struct WorkersStreamState {
     pub stream_scroll: usize,
     /// Whether to auto-scroll the output stream to the bottom.
     pub auto_scroll: bool,
+    /// Whether the right pane shows the prompt context instead of the output stream.
+    pub show_prompt_context: bool,
+    /// Whether long output lines soft-wrap (off enables horizontal scrolling).
+    pub wrap: bool,
+    /// Horizontal scroll offset in columns, used only when `wrap` is off.
+    pub h_scroll: usize,
 }
 
 impl WorkersStreamState {
@@ -30,6 +44,9 @@ impl WorkersStreamState {
             scroll_offset: 0,
             stream_scroll: 0,
             auto_scroll: true,
+            show_prompt_context: false,
+            wrap: true,
+            h_scroll: 0,
         }
     }
 
@@ -62,6 +79,16 @@ impl WorkersStreamState {
         self.auto_scroll = true;
     }
 
+    /// Expand or collapse the prompt context view, starting expanded views at the top.
+    pub fn toggle_prompt_context(&mut self) {
+        self.show_prompt_context = !self.show_prompt_context;
+        if self.show_prompt_context {
+            self.scroll_to_top();
+        } else {
+            self.reset_stream();
+        }
+    }
+
     /// Scroll the output stream up by `amount` lines, disabling auto-scroll.
     pub fn scroll_up(&mut self, amount: usize) {
         self.stream_scroll = self.stream_scroll.saturating_sub(amount);
@@ -80,6 +107,26 @@ impl WorkersStreamState {
         self.auto_scroll = false;
     }
 
+    /// Toggle soft-wrapping, returning to the left edge.
+    pub fn toggle_wrap(&mut self) {
+        self.wrap = !self.wrap;
+        self.h_scroll = 0;
+    }
+
+    /// Scroll left by `amount` columns (no-op while wrapping).
+    pub fn scroll_left(&mut self, amount: usize) {
+        if !self.wrap {
+            self.h_scroll = self.h_scroll.saturating_sub(amount);
+        }
+    }
+
+    /// Scroll right by `amount` columns, clamped to `max_h_scroll` (no-op while wrapping).
+    pub fn scroll_right(&mut self, amount: usize, max_h_scroll: usize) {
+        if !self.wrap {
+            self.h_scroll = (self.h_scroll + amount).min(max_h_scroll);
+        }
+    }
+
     /// Jump to the bottom of the output stream and re-enable auto-scroll.
     #[allow(dead_code)]
     pub fn scroll_to_bottom(&mut self, max_scroll: usize) {
@@ -90,6 +137,76 @@ impl WorkersStreamState {
 
 /// Handle keyboard input for the workers stream modal.
 pub fn handle_workers_stream_input(app: &mut App, key_code: KeyCode, modifiers: KeyModifiers) {
+    // Preview length is app-wide (re-renders every worker's results)
+    let current = app.config.display.result_preview_lines;
+    let adjusted = match key_code {
+        KeyCode::Char('+') | KeyCode::Char('=') => Some(current + 1),
+        KeyCode::Char('-') => Some(current.saturating_sub(1)),
+        _ => None,
+    };
+    if let Some(lines) = adjusted {
+        let lines = app.set_result_preview_lines(lines);
+        app.set_hint(format!("Result preview: {lines} lines"));
+        return;
+    }
+    if matches!(key_code, KeyCode::Char('r' | 'b'))
+        && app.deny_in_read_only("Running commands and restoring files")
+    {
+        return;
+    }
+    if key_code == KeyCode::Char('r') {
+        let worker = app.workers_stream_state.as_ref().map_or(0, |s| s.selected);
+        crate::modals::open_rerun_modal(app, worker);
+        return;
+    }
+    if key_code == KeyCode::Char('b') {
+        crate::modals::open_restore_modal(app);
+        return;
+    }
+    if key_code == KeyCode::Char('x') {
+        let expanded = app.toggle_repeated_tools_expanded();
+        app.set_hint(if expanded {
+            "Showing every repeated tool result"
+        } else {
+            "Showing latest repeated tool result"
+        });
+        return;
+    }
+    if key_code == KeyCode::Char('y') {
+        let worker = app.workers_stream_state.as_ref().map_or(0, |s| s.selected);
+        let hint = match app.workers.get(worker).and_then(|w| w.last_summary.clone()) {
+            None => "No finished iteration to copy yet".to_string(),
+            Some(summary) => match crate::iteration_summary::copy_to_clipboard(&summary) {
+                Ok(()) => format!("Copied: {summary}"),
+                Err(e) => format!("Copy failed: {e}"),
+            },
+        };
+        app.set_hint(hint);
+        return;
+    }
+    if matches!(key_code, KeyCode::Char('o' | 'O')) {
+        toggle_sections(app, key_code == KeyCode::Char('O'));
+        return;
+    }
+    if key_code == KeyCode::Char('V') {
+        let compact = app.toggle_compact_output();
+        app.set_hint(if compact {
+            "Compact output: tool calls and assistant text only"
+        } else {
+            "Verbose output: full previews, usage and stderr"
+        });
+        return;
+    }
+    if key_code == KeyCode::Char('d') && !modifiers.contains(KeyModifiers::CONTROL) {
+        let shown = app.toggle_edit_diffs();
+        app.set_hint(if shown {
+            "Showing Edit diffs"
+        } else {
+            "Hiding Edit diffs"
+        });
+        return;
+    }
+
     let worker_count = app.workers.len();
     let Some(state) = &mut app.workers_stream_state else {
         return;
@@ -122,6 +239,20 @@ pub fn handle_workers_stream_input(app: &mut App, key_code: KeyCode, modifiers:
             // Still disable auto-scroll for Ctrl+d
             state.auto_scroll = false;
         }
+        KeyCode::Char('p') => {
+            state.toggle_prompt_context();
+        }
+        KeyCode::Char('z') => {
+            state.toggle_wrap();
+            app.stream_wrap = state.wrap;
+        }
+        KeyCode::Char('h') | KeyCode::Left => {
+            state.scroll_left(H_SCROLL_STEP);
+        }
+        KeyCode::Char('l') | KeyCode::Right => {
+            // Render clamps to the widest visible line
+            state.scroll_right(H_SCROLL_STEP, usize::MAX);
+        }
         KeyCode::Char('?') => {
             app.help_context = Some(crate::modals::HelpContext::WorkersStream);
         }
@@ -129,6 +260,45 @@ pub fn handle_workers_stream_input(app: &mut App, key_code: KeyCode, modifiers:
     }
 }
 
+/// Fold or unfold the selected worker's iteration at the top of the view, or
+/// with `all`, every iteration at once.
+fn toggle_sections(app: &mut App, all: bool) {
+    let Some(state) = &mut app.workers_stream_state else {
+        return;
+    };
+    let Some(worker) = app.workers.get_mut(state.selected) else {
+        return;
+    };
+    if state.show_prompt_context || worker.sections.is_empty() {
+        app.set_hint("No iterations to fold yet");
+        return;
+    }
+    let total = worker.output_lines.len();
+    if all {
+        let expanded = iteration_sections::toggle_all(&mut worker.sections);
+        app.set_hint(if expanded {
+            "Expanded every iteration"
+        } else {
+            "Collapsed earlier iterations"
+        });
+        return;
+    }
+    // The section under the top of the view, or the latest one
+    let section = iteration_sections::rows(&worker.sections, total)
+        .get(state.stream_scroll)
+        .and_then(|row| row.section())
+        .unwrap_or(worker.sections.len() - 1);
+    worker.sections[section].collapsed = !worker.sections[section].collapsed;
+    // Keep the toggled header in view
+    if let Some(header) = iteration_sections::rows(&worker.sections, total)
+        .iter()
+        .position(|row| *row == Row::Header(section))
+    {
+        state.stream_scroll = header;
+        state.auto_scroll = false;
+    }
+}
+
 /// Draw the workers stream modal (full-screen overlay).
 pub fn draw_workers_stream(f: &mut Frame, app: &mut App) {
     let Some(state) = &mut app.workers_stream_state else {
@@ -149,9 +319,16 @@ pub fn draw_workers_stream(f: &mut Frame, app: &mut App) {
 
     f.render_widget(Clear, modal_area);
 
+    let mut title = match &app.attached_session {
+        Some(id) => format!(" Workers \u{b7} attached to {id} (read-only) "),
+        None => " Workers ".to_string(),
+    };
+    if let Some((hint, _)) = &app.hint {
+        title.push_str(&format!("\u{b7} {hint} "));
+    }
     let outer_block = Block::default()
         .borders(Borders::ALL)
-        .title(" Workers ")
+        .title(title)
         .style(Style::default().fg(Color::White));
     let inner_area = outer_block.inner(modal_area);
     f.render_widget(outer_block, modal_area);
@@ -201,11 +378,7 @@ fn draw_worker_list(f: &mut Frame, app: &App, area: Rect) {
         };
 
         let max_title_len = area.width.saturating_sub(6) as usize; // icon + space + index + padding
-        let truncated = if bead_title.len() > max_title_len {
-            &bead_title[..max_title_len]
-        } else {
-            bead_title
-        };
+        let truncated = truncate_to_width(bead_title, max_title_len, "");
 
         let is_selected = i == state.selected;
         let style = if is_selected {
@@ -224,17 +397,17 @@ fn draw_worker_list(f: &mut Frame, app: &App, area: Rect) {
             // For selected line, use inverted colors throughout
             Line::from(vec![
                 Span::styled(format!(" {status_icon} "), style),
-                Span::styled(truncated.to_string(), style),
+                Span::styled(truncated.clone(), style),
                 // Pad to full width for highlight effect
                 Span::styled(
-                    " ".repeat(max_title_len.saturating_sub(truncated.len())),
+                    " ".repeat(max_title_len.saturating_sub(truncated.width())),
                     style,
                 ),
             ])
         } else {
             Line::from(vec![
                 Span::styled(format!(" {status_icon} "), Style::default().fg(icon_color)),
-                Span::raw(truncated.to_string()),
+                Span::raw(truncated),
             ])
         };
 
@@ -269,10 +442,26 @@ fn draw_worker_output(f: &mut Frame, app: &mut App, area: Rect) {
     }
 
     let worker = &app.workers[state.selected];
-    let total_lines = worker.output_lines.len();
+    let numbers = NumberFormat::from(&app.config.display);
+    let shown_lines = match (&worker.prompt_context, state.show_prompt_context) {
+        (Some(context), true) => Some(prompt_context_lines(context, &numbers)),
+        _ if worker.sections.is_empty() => None,
+        _ => Some(iteration_sections::fold(
+            &worker.output_lines,
+            &worker.sections,
+            &numbers,
+        )),
+    };
+    let source = shown_lines.as_deref().unwrap_or(&worker.output_lines);
+    let total_lines = source.len();
 
-    // Compute max scroll
-    let max_scroll = total_lines.saturating_sub(visible_height);
+    // Compute max scroll (in logical lines). When wrapping, the bottom-most start
+    // line may wrap past the pane, so `overshoot` rows are skipped at the bottom.
+    let (max_scroll, overshoot) = if state.wrap {
+        wrapped_bottom_start(source, output_inner.width, visible_height)
+    } else {
+        (total_lines.saturating_sub(visible_height), 0)
+    };
 
     // Auto-scroll: pin to bottom
     if state.auto_scroll {
@@ -285,12 +474,22 @@ fn draw_worker_output(f: &mut Frame, app: &mut App, area: Rect) {
     let start = state.stream_scroll;
     let end = (start + visible_height).min(total_lines);
     let visible: Vec<Line> = if start < total_lines {
-        worker.output_lines[start..end].to_vec()
+        source[start..end].to_vec()
     } else {
         Vec::new()
     };
 
-    let output_widget = Paragraph::new(visible);
+    let output_widget = if state.wrap {
+        let skip = if start == max_scroll { overshoot } else { 0 };
+        Paragraph::new(visible)
+            .wrap(Wrap { trim: false })
+            .scroll((skip as u16, 0))
+    } else {
+        let widest = visible.iter().map(Line::width).max().unwrap_or(0);
+        let max_h_scroll = widest.saturating_sub(output_inner.width as usize);
+        state.h_scroll = state.h_scroll.min(max_h_scroll);
+        Paragraph::new(visible).scroll((0, state.h_scroll as u16))
+    };
     f.render_widget(output_widget, output_inner);
 
     // Scroll indicator when not at bottom
@@ -312,6 +511,40 @@ fn draw_worker_output(f: &mut Frame, app: &mut App, area: Rect) {
     }
 }
 
+/// Number of rows `line` occupies when soft-wrapped to `width` columns.
+fn wrapped_rows(line: &Line, width: u16) -> usize {
+    if width == 0 {
+        return 1;
+    }
+    Paragraph::new(line.clone())
+        .wrap(Wrap { trim: false })
+        .line_count(width)
+        .max(1)
+}
+
+/// First line to render so the wrapped tail of `lines` fills `height` rows.
+///
+/// Returns `(start, overshoot)` where `overshoot` is how many rows the first
+/// line's wrapped output extends above the pane. Only the tail is measured.
+fn wrapped_bottom_start(lines: &[Line], width: u16, height: usize) -> (usize, usize) {
+    let mut rows = 0;
+    let mut start = lines.len();
+    while start > 0 && rows < height {
+        start -= 1;
+        rows += wrapped_rows(&lines[start], width);
+    }
+    (start, rows.saturating_sub(height))
+}
+
+/// Build the expanded prompt context view: a header line followed by the raw prompt.
+fn prompt_context_lines(context: &str, numbers: &NumberFormat) -> Vec<Line<'static>> {
+    std::iter::once(crate::ui::format_prompt_context_styled(
+        context, true, numbers,
+    ))
+    .chain(context.lines().map(|l| Line::raw(l.to_string())))
+    .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -475,6 +708,77 @@ mod tests {
         assert!(state.auto_scroll);
     }
 
+    #[test]
+    fn toggle_prompt_context_expands_at_top_and_collapses_to_bottom() {
+        let mut state = WorkersStreamState::new(0);
+        state.stream_scroll = 30;
+
+        state.toggle_prompt_context();
+        assert!(state.show_prompt_context);
+        assert_eq!(state.stream_scroll, 0);
+        assert!(!state.auto_scroll);
+
+        state.toggle_prompt_context();
+        assert!(!state.show_prompt_context);
+        assert!(state.auto_scroll);
+    }
+
+    #[test]
+    fn prompt_context_lines_has_header_then_prompt_lines() {
+        let lines = prompt_context_lines("line one\nline two", &NumberFormat::default());
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1].to_string(), "line one");
+        assert_eq!(lines[2].to_string(), "line two");
+    }
+
+    #[test]
+    fn toggle_wrap_resets_horizontal_scroll() {
+        let mut state = WorkersStreamState::new(0);
+        assert!(state.wrap);
+
+        state.toggle_wrap();
+        state.scroll_right(8, 100);
+        assert_eq!(state.h_scroll, 8);
+
+        state.toggle_wrap();
+        assert!(state.wrap);
+        assert_eq!(state.h_scroll, 0);
+    }
+
+    #[test]
+    fn horizontal_scroll_ignored_while_wrapping() {
+        let mut state = WorkersStreamState::new(0);
+        state.scroll_right(8, 100);
+        assert_eq!(state.h_scroll, 0);
+    }
+
+    #[test]
+    fn horizontal_scroll_clamps_both_ends() {
+        let mut state = WorkersStreamState::new(0);
+        state.toggle_wrap();
+
+        state.scroll_right(8, 5);
+        assert_eq!(state.h_scroll, 5);
+
+        state.scroll_left(8);
+        assert_eq!(state.h_scroll, 0);
+    }
+
+    #[test]
+    fn wrapped_bottom_start_counts_wrapped_rows() {
+        let lines = vec![
+            Line::raw("short"),
+            Line::raw("a".repeat(25)), // 3 rows at width 10
+            Line::raw("end"),
+        ];
+        // 4 rows: "end" (1) + long line (3) exactly fill the pane
+        assert_eq!(wrapped_bottom_start(&lines, 10, 4), (1, 0));
+        // 3 rows: long line overshoots by one row
+        assert_eq!(wrapped_bottom_start(&lines, 10, 3), (1, 1));
+        // Everything fits
+        assert_eq!(wrapped_bottom_start(&lines, 10, 20), (0, 0));
+    }
+
     #[test]
     fn switching_worker_resets_scroll_and_enables_auto_scroll() {
         let mut state = WorkersStreamState::new(0);
@@ -0,0 +1,110 @@
+//! Token counts and costs formatted for the reader's locale.
+//!
+//! `7,371` reads as seven point three in much of Europe, so the digit
+//! grouping and decimal separators, the currency symbol and which side of
+//! the amount it goes on all come from `[display]`.
+
+use crate::config::{CurrencyPosition, DisplayConfig};
+
+/// Separators and currency placement for numbers shown to the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberFormat {
+    thousands_separator: String,
+    decimal_separator: String,
+    currency_symbol: String,
+    currency_position: CurrencyPosition,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::from(&DisplayConfig::default())
+    }
+}
+
+impl From<&DisplayConfig> for NumberFormat {
+    fn from(display: &DisplayConfig) -> Self {
+        Self {
+            thousands_separator: display.thousands_separator.clone(),
+            decimal_separator: display.decimal_separator.clone(),
+            currency_symbol: display.currency_symbol.clone(),
+            currency_position: display.currency_position,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Format a count with digit grouping (e.g. `1234` → `1,234`).
+    pub fn count(&self, n: u64) -> String {
+        let digits = n.to_string();
+        let mut out = String::with_capacity(digits.len() * 2);
+        for (i, ch) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                out.push_str(&self.thousands_separator);
+            }
+            out.push(ch);
+        }
+        out
+    }
+
+    /// Format `value` with `places` decimals, grouping the integer part.
+    pub fn decimal(&self, value: f64, places: usize) -> String {
+        let fixed = format!("{:.*}", places, value.abs());
+        let (whole, fraction) = fixed.split_once('.').unwrap_or((&fixed, ""));
+        let mut out = String::new();
+        if value < 0.0 && fixed.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            out.push('-');
+        }
+        out.push_str(&self.count(whole.parse().unwrap_or(0)));
+        if !fraction.is_empty() {
+            out.push_str(&self.decimal_separator);
+            out.push_str(fraction);
+        }
+        out
+    }
+
+    /// Format a USD amount to the cent (e.g. `$1,234.50` or `1.234,50 €`).
+    pub fn cost(&self, usd: f64) -> String {
+        let amount = self.decimal(usd, 2);
+        match self.currency_position {
+            CurrencyPosition::Before => format!("{}{amount}", self.currency_symbol),
+            CurrencyPosition::After => format!("{amount} {}", self.currency_symbol),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn european() -> NumberFormat {
+        NumberFormat::from(&DisplayConfig {
+            thousands_separator: ".".into(),
+            decimal_separator: ",".into(),
+            currency_symbol: "€".into(),
+            currency_position: CurrencyPosition::After,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn default_format_matches_en_us() {
+        let fmt = NumberFormat::default();
+        assert_eq!(fmt.count(0), "0");
+        assert_eq!(fmt.count(999), "999");
+        assert_eq!(fmt.count(1234), "1,234");
+        assert_eq!(fmt.count(1234567), "1,234,567");
+        assert_eq!(fmt.cost(0.5), "$0.50");
+        assert_eq!(fmt.cost(1234.567), "$1,234.57");
+        assert_eq!(fmt.decimal(2.26, 1), "2.3");
+    }
+
+    #[test]
+    fn european_format_swaps_separators_and_moves_symbol() {
+        let fmt = european();
+        assert_eq!(fmt.count(7371), "7.371");
+        assert_eq!(fmt.cost(1234.5), "1.234,50 €");
+        assert_eq!(fmt.decimal(0.76, 1), "0,8");
+        assert_eq!(fmt.decimal(-0.001, 2), "0,00");
+        assert_eq!(fmt.decimal(-12.4, 0), "-12");
+    }
+}
@@ -1,34 +1,42 @@
 //! Output processing pipeline — drains the mpsc channel and processes Claude NDJSON events.
 
-use crate::app::App;
+use crate::app::{App, BashRun, ToolCallLine};
+use crate::claude_cli::OutputFormat;
+use crate::control::{self, PermissionDecision};
 use crate::db;
-use crate::events::{
-    ClaudeEvent, ContentBlock, Delta, StreamInnerEvent, ToolResultContent, UserContent,
-};
-use crate::tool_panel::{ContentBlockState, PendingToolCall, ToolCallEntry, ToolCallStatus};
+use crate::hooks::HookEvent;
+use crate::iteration_summary;
+use crate::tool_panel::PendingToolCall;
 use crate::ui::{
-    ExchangeType, extract_text_from_task_result, extract_tool_summary,
-    format_assistant_header_styled, format_no_result_warning_styled, format_tool_result_styled,
-    format_tool_summary_styled, format_usage_summary,
+    ExchangeType, ToolResultPreview, extract_text_from_task_result, extract_tool_summary,
+    format_agent_question_styled, format_assistant_header_styled, format_edit_diff_styled,
+    format_no_result_warning_styled, format_self_report_styled, format_tool_errors_styled,
+    format_tool_input_preview_styled, format_tool_summary_styled, format_usage_summary,
 };
 
 use std::sync::mpsc::TryRecvError;
 
+use ralph_core::events::{
+    ClaudeEvent, ContentBlock, ControlRequest, Delta, StreamInnerEvent, ToolResultContent,
+    UserContent,
+};
+use ralph_core::output::{ContentBlockState, OutputMessage, ToolCallEntry, ToolCallStatus};
 use ratatui::text::{Line, Span};
 use tracing::{debug, info, trace, warn};
 
-/// Message types for output processing.
-pub enum OutputMessage {
-    Line(String),
-}
-
 /// Adds indentation to a styled Line by prepending "  " to the first span.
-fn indent_line(line: Line<'static>) -> Line<'static> {
+pub(crate) fn indent_line(line: Line<'static>) -> Line<'static> {
     let mut spans: Vec<Span<'static>> = vec![Span::raw("  ")];
     spans.extend(line.spans);
     Line::from(spans)
 }
 
+/// Extract the `command` argument from a Bash tool call's input JSON.
+fn bash_command(input_json: &str) -> Option<String> {
+    let input: serde_json::Value = serde_json::from_str(input_json).ok()?;
+    input.get("command")?.as_str().map(str::to_string)
+}
+
 /// Poll for output from all workers' child processes.
 pub fn poll_output(app: &mut App) {
     let display_worker = app.selected_worker;
@@ -59,12 +67,17 @@ fn poll_worker_output(app: &mut App, w: usize) {
         }
     }
 
+    app.debug_stats.set_queue_depth(w, messages.len());
+
     // Process collected messages
     if !messages.is_empty() {
         app.dirty = true;
     }
     for msg in messages {
         let OutputMessage::Line(line) = msg;
+        if let Some(transcript) = &mut app.transcript {
+            transcript.record(w, &line);
+        }
         process_line(app, &line);
     }
 
@@ -125,7 +138,7 @@ fn poll_worker_output(app: &mut App, w: usize) {
 }
 
 /// Parse and process a single NDJSON line.
-fn process_line(app: &mut App, line: &str) {
+pub(crate) fn process_line(app: &mut App, line: &str) {
     // Skip empty lines
     if line.trim().is_empty() {
         return;
@@ -137,8 +150,18 @@ fn process_line(app: &mut App, line: &str) {
     trace!(json = line, "raw_json_line");
 
     // Handle stderr lines (pass through as-is)
-    if line.starts_with("[stderr]") {
-        app.add_text_line(line.to_string());
+    if let Some(stderr) = line.strip_prefix("[stderr]") {
+        if crate::login::is_auth_error(stderr) {
+            let w = app.selected_worker;
+            app.workers[w].auth_error = Some(stderr.trim().to_string());
+        }
+        app.add_verbose_lines(vec![Line::raw(line.to_string())]);
+        return;
+    }
+
+    // A CLI without JSON output prints the final message as is
+    if app.workers[app.selected_worker].output_caps.format == OutputFormat::Text {
+        app.add_text_line(format!("  {line}"));
         return;
     }
 
@@ -152,9 +175,11 @@ fn process_line(app: &mut App, line: &str) {
                     warn!(event_type, "Unknown event type, skipping");
                 } else {
                     warn!(?e, "Failed to parse JSON line (no type field)");
+                    app.note_parse_failure();
                 }
             } else {
                 warn!(?e, "Malformed JSON line, skipping");
+                app.note_parse_failure();
             }
         }
     }
@@ -167,15 +192,77 @@ fn process_event(app: &mut App, event: ClaudeEvent) {
             // Silently ignore ping events
             debug!("Received ping");
         }
+        ClaudeEvent::PromptSnapshot(snapshot) => {
+            // Only seen when replaying a transcript (`ralph attach`)
+            debug!(iteration = snapshot.iteration, "Prompt snapshot");
+            let w = app.selected_worker;
+            app.add_line(crate::ui::format_prompt_context_styled(
+                &snapshot.prompt,
+                false,
+                &crate::number_format::NumberFormat::from(&app.config.display),
+            ));
+            app.workers[w].prompt_context = Some(snapshot.prompt);
+        }
         ClaudeEvent::RateLimit => {
             // Rate limit info is logged; the actual error message comes via
             // the subsequent Result event with is_error=true.
             debug!("Rate limit event received");
         }
+        ClaudeEvent::ControlRequest(req) => {
+            let w = app.selected_worker;
+            match req.request {
+                ControlRequest::CanUseTool { tool_name, input } => {
+                    // The tool is held until we answer, so this copy is never late
+                    if app.config.behavior.backup_files {
+                        backup_before_tool(app, w, &tool_name, &input);
+                    }
+                    let decision = control::decide(&tool_name, &app.config.control.auto_approve);
+                    info!(tool_name, ?decision, "permission_request");
+                    app.add_text_line(match &decision {
+                        PermissionDecision::Allow => format!("[Permission granted: {tool_name}]"),
+                        PermissionDecision::Deny(_) => format!(
+                            "[Permission denied: {tool_name} is not in control.auto_approve]"
+                        ),
+                    });
+                    if let Some(channel) = &mut app.workers[w].control {
+                        channel.answer_permission(&req.request_id, &decision, &input);
+                    }
+                }
+                ControlRequest::Unsupported => {
+                    warn!(request_id = %req.request_id, "Unsupported control request");
+                    if let Some(channel) = &mut app.workers[w].control {
+                        channel.reject_unsupported(&req.request_id);
+                    }
+                }
+            }
+        }
+        ClaudeEvent::ControlResponse(resp) => {
+            let resp = resp.response;
+            if let Some(error) = resp.error {
+                warn!(request_id = %resp.request_id, error, "control_request_failed");
+            } else {
+                debug!(request_id = %resp.request_id, subtype = %resp.subtype, "control_response");
+            }
+        }
         // SECURITY: DEBUG logs full event structures. Acceptable since DEBUG
         // is only enabled for local development, never in distributed logs.
         ClaudeEvent::System(sys) => {
             debug!(?sys, "System event");
+            if sys.subtype.as_deref() == Some("init")
+                && let Some(id) = sys.session_id.clone()
+            {
+                let w = app.selected_worker;
+                info!(
+                    model = sys.model.as_deref().unwrap_or("unknown"),
+                    tools = sys.tools.len(),
+                    mcp_servers = sys.mcp_servers.len(),
+                    permission_mode = sys.permission_mode.as_deref().unwrap_or("unknown"),
+                    "session_init"
+                );
+                app.add_line(crate::ui::format_session_init_styled(&sys));
+                app.add_line(crate::ui::format_claude_session_styled(&id));
+                app.workers[w].claude_session_id = Some(id);
+            }
         }
         ClaudeEvent::Assistant(asst) => {
             debug!(?asst, "Assistant event");
@@ -203,6 +290,11 @@ fn process_event(app: &mut App, event: ClaudeEvent) {
                                 });
 
                             let is_error = is_error.unwrap_or(false);
+                            let w = app.selected_worker;
+                            app.workers[w].iteration_tool_results += 1;
+                            if is_error {
+                                app.workers[w].iteration_tool_errors += 1;
+                            }
 
                             // Extract content string
                             let content_str = match content {
@@ -218,6 +310,10 @@ fn process_event(app: &mut App, event: ClaudeEvent) {
                                 content_str
                             };
 
+                            if is_error {
+                                app.record_tool_error(w, &tool_name, &content_str);
+                            }
+
                             // Update tool call record with result
                             if let Some(ref conn) = app.tool_history_db
                                 && !db::update_tool_result(
@@ -241,29 +337,47 @@ fn process_event(app: &mut App, event: ClaudeEvent) {
                             };
                             app.tool_panel.update_status(&tool_use_id, panel_status);
 
+                            app.clear_tool_preview();
                             // Check for pending tool call to correlate with
                             if let Some(pending) = app.tool_panel.pending_calls.remove(&tool_use_id)
                             {
-                                // Display tool call first
-                                app.add_line(pending.styled_line);
-                                // Display result indented under call
-                                let lines = format_tool_result_styled(
-                                    &pending.tool_name,
-                                    &content_str,
-                                    is_error,
-                                );
-                                for line in lines {
-                                    // Add indentation to styled line
-                                    let indented = indent_line(line);
-                                    app.add_line(indented);
+                                // Remember Bash commands so a failed iteration can be re-run
+                                if pending.tool_name == "Bash"
+                                    && let Some(command) = bash_command(&pending.input_json)
+                                {
+                                    let w = app.selected_worker;
+                                    app.workers[w].iteration_bash.push(BashRun {
+                                        command,
+                                        failed: is_error,
+                                    });
                                 }
+                                if !is_error {
+                                    let w = app.selected_worker;
+                                    app.workers[w].iteration_files.record_tool(
+                                        &pending.tool_name,
+                                        &pending.input_json,
+                                        &content_str,
+                                    );
+                                }
+                                // Display tool call with its result indented under it
+                                let call = ToolCallLine {
+                                    key: format!("{}\0{}", pending.tool_name, pending.input_json),
+                                    line: pending.styled_line,
+                                    diff: format_edit_diff_styled(
+                                        &pending.tool_name,
+                                        &pending.input_json,
+                                    ),
+                                };
+                                app.add_tool_result(
+                                    Some(call),
+                                    ToolResultPreview::new(&content_str, is_error),
+                                );
                             } else {
                                 // No pending call found - display result standalone
-                                let lines =
-                                    format_tool_result_styled(&tool_name, &content_str, is_error);
-                                for line in lines {
-                                    app.add_line(line);
-                                }
+                                app.add_tool_result(
+                                    None,
+                                    ToolResultPreview::new(&content_str, is_error),
+                                );
                             }
                         }
                     }
@@ -276,27 +390,112 @@ fn process_event(app: &mut App, event: ClaudeEvent) {
         }
         ClaudeEvent::Result(result) => {
             debug!(?result, "Result event");
+            let w = app.selected_worker;
+            // Claude waits for more stream-json input; closing stdin lets it exit
+            app.workers[w].control = None;
+            app.workers[w].finish_iteration(result.is_error.unwrap_or(false), &app.run_tags);
             // Store error message from result event (e.g. rate limit)
             if result.is_error.unwrap_or(false)
                 && let Some(ref msg) = result.result
             {
                 let w = app.selected_worker;
                 app.workers[w].last_result_error = Some(msg.clone());
+                if crate::login::is_auth_error(msg) {
+                    app.workers[w].auth_error = Some(msg.clone());
+                }
+            }
+            // The agent's own account of the iteration, if the prompt asked for one
+            let report = match result
+                .result
+                .as_deref()
+                .filter(|_| !result.is_error.unwrap_or(false))
+                .and_then(crate::self_report::parse)
+            {
+                Some(Ok(report)) => Some(report),
+                Some(Err(e)) => {
+                    warn!(error = %e, "self_report_invalid");
+                    app.add_text_line(format!("[ralph-report ignored: {e}]"));
+                    None
+                }
+                None => None,
+            };
+            if let Some(record) = app.workers[w].iteration_history.last_mut() {
+                record.report = report.clone();
+            }
+            // Without partial messages the final text never streamed in
+            if !app.workers[w].output_caps.streams_text()
+                && let Some(text) = result.result.as_deref()
+            {
+                for line in text.lines() {
+                    app.add_text_line(format!("  {line}"));
+                }
             }
             // Flush any pending tool calls that never received results
+            app.clear_tool_preview();
             let pending_calls: Vec<_> = app.tool_panel.pending_calls.drain().collect();
             for (_id, pending) in pending_calls {
                 app.add_line(pending.styled_line);
                 app.add_line(indent_line(format_no_result_warning_styled()));
             }
+            if app.config.disk.max_growth_mb > 0 {
+                app.disk_guard.measure();
+            }
+            if let Some(cost) = result.total_cost_usd {
+                app.spend.record(cost);
+                app.session_cost_usd += cost;
+            }
             // Increment exchange counter
             app.exchange_count += 1;
             // Accumulate tokens for session total
-            if let Some(usage) = &result.usage {
-                let input = usage.input_tokens.unwrap_or(0);
-                let output = usage.output_tokens.unwrap_or(0);
-                app.cumulative_tokens += input + output;
+            let tokens = result.usage.as_ref().map_or(0, |usage| {
+                usage.input_tokens.unwrap_or(0) + usage.output_tokens.unwrap_or(0)
+            });
+            app.cumulative_tokens += tokens;
+            app.check_spec_checklist(w, report.as_ref().is_some_and(|r| r.spec_complete));
+            let record = app.workers[w].iteration_history.last();
+            if record.is_some_and(|r| r.failed) {
+                app.failed_iterations += 1;
             }
+            // Store the iteration, attributed to the spec being worked on
+            if let Some(conn) = &app.tool_history_db {
+                crate::stats::record_iteration(
+                    conn,
+                    &crate::stats::IterationRow {
+                        session_id: &app.session_id,
+                        repo_path: &app.repo_path,
+                        worker: w,
+                        iteration: app.workers[w].current_iteration,
+                        bead_id: app.workers[w].hooked_bead_id.as_deref(),
+                        tokens,
+                        cost_usd: result.total_cost_usd.unwrap_or(0.0),
+                        duration_ms: result.duration_ms.unwrap_or(0),
+                        failed: record.is_some_and(|r| r.failed),
+                        tool_results: record.map_or(0, |r| r.tool_results),
+                        tool_errors: record.map_or(0, |r| r.tool_errors),
+                    },
+                );
+            }
+            app.fire_hook(
+                HookEvent::IterationEnd,
+                serde_json::json!({
+                    "iteration": app.workers[w].current_iteration,
+                    "bead_id": app.workers[w].hooked_bead_id,
+                    "failed": record.is_some_and(|r| r.failed),
+                    "tool_errors": record.map_or(0, |r| r.tool_errors),
+                    "tokens": tokens,
+                    "cost_usd": result.total_cost_usd,
+                    "duration_ms": result.duration_ms,
+                    "claude_session_id": app.workers[w].claude_session_id,
+                    "report": report,
+                }),
+            );
+            app.finish_iteration_section(
+                w,
+                result.total_cost_usd,
+                result.duration_ms,
+                record.is_some_and(|r| r.failed),
+            );
+            app.refresh_estimate_history();
             // Determine exchange type
             let exchange_type = if app.exchange_count == 1 {
                 ExchangeType::InitialPrompt
@@ -305,15 +504,107 @@ fn process_event(app: &mut App, event: ClaudeEvent) {
             } else {
                 ExchangeType::Continuation
             };
+            // List the iteration's file changes ahead of the usage summary
+            let touches = std::mem::take(&mut app.workers[w].iteration_files);
+            let root = Some(app.worker_dir(w));
+            if !touches.is_empty() {
+                for line in touches.summary_lines(root.as_deref()) {
+                    app.add_text_line(line);
+                }
+            }
+            let numbers = crate::number_format::NumberFormat::from(&app.config.display);
+            let record = app.workers[w].iteration_history.last();
+            let summary = iteration_summary::summary_line(
+                &iteration_summary::IterationOutcome {
+                    bead: app.workers[w].hooked_bead_id.as_deref(),
+                    iteration: app.workers[w].current_iteration,
+                    files: &touches.changed_paths(root.as_deref()),
+                    failed: record.is_some_and(|r| r.failed),
+                    tool_errors: record.map_or(0, |r| r.tool_errors),
+                    cost_usd: result.total_cost_usd,
+                },
+                &numbers,
+            );
+            // An observer mustn't overwrite the running session's file
+            if app.attached_session.is_none()
+                && let Err(e) = iteration_summary::write_last(&app.project_dir, &summary)
+            {
+                warn!(error = %e, "last_iteration_write_failed");
+            }
+            app.workers[w].last_summary = Some(summary);
             // Display usage summary with exchange info
-            let summary = format_usage_summary(&result, app.exchange_count, exchange_type);
-            for line in summary.lines() {
-                app.add_text_line(line.to_string());
+            let summary =
+                format_usage_summary(&result, app.exchange_count, exchange_type, &numbers);
+            app.add_verbose_lines(
+                summary
+                    .lines()
+                    .map(|line| Line::raw(line.to_string()))
+                    .collect(),
+            );
+            let (tags, continued, tool_results, tool_errors) = app.workers[w]
+                .iteration_history
+                .last()
+                .map(|r| {
+                    (
+                        r.tags.join(", "),
+                        r.continued,
+                        r.tool_results,
+                        r.tool_errors,
+                    )
+                })
+                .unwrap_or_default();
+            if !tags.is_empty() {
+                app.add_text_line(format!("Tags: {tags}"));
+            }
+            if let Some(report) = &report {
+                info!(
+                    spec_complete = report.spec_complete,
+                    blockers = report.blockers.len(),
+                    "self_report"
+                );
+                for line in format_self_report_styled(report) {
+                    app.add_line(line);
+                }
+            }
+            // A question for the user holds auto-continue until it's answered
+            if app.config.behavior.pause_on_question
+                && let Some(question) = result
+                    .result
+                    .as_deref()
+                    .filter(|_| !result.is_error.unwrap_or(false))
+                    .and_then(|text| crate::agent_question::detect(text, report.as_ref()))
+            {
+                info!("agent_question");
+                app.add_line(format_agent_question_styled(&question));
+                app.workers[w].question = Some(question);
+            }
+            if tool_errors > 0 {
+                let threshold = app.config.display.tool_error_threshold;
+                let flagged = threshold > 0 && tool_errors >= threshold;
+                info!(tool_errors, tool_results, flagged, "iteration_tool_errors");
+                app.add_line(format_tool_errors_styled(
+                    tool_errors,
+                    tool_results,
+                    flagged,
+                ));
+            }
+            info!(continued, "iteration_conversation_mode");
+            if continued {
+                app.add_text_line("Conversation: continued".to_string());
             }
         }
     }
 }
 
+/// Copy the file a Write/Edit call on worker `w` is about to change
+/// (`behavior.backup_files`).
+fn backup_before_tool(app: &mut App, w: usize, tool_name: &str, input: &serde_json::Value) {
+    let root = app.worker_dir(w);
+    let iteration = app.workers[w].current_iteration;
+    app.file_backups
+        .snapshot_for_tool(tool_name, input, &root, w, iteration);
+}
+
 /// Process inner streaming events (unwrapped from stream_event).
 fn process_stream_event(app: &mut App, event: StreamInnerEvent) {
     let w = app.selected_worker;
@@ -371,6 +662,14 @@ fn process_stream_event(app: &mut App, event: StreamInnerEvent) {
                 Delta::InputJsonDelta { partial_json } => {
                     let state = app.workers[w].content_blocks.entry(index).or_default();
                     state.input_json.push_str(&partial_json);
+                    if let Some(tool_name) = &state.tool_name {
+                        let line = format_tool_input_preview_styled(
+                            tool_name,
+                            &state.input_json,
+                            &app.config.display,
+                        );
+                        app.set_tool_preview(line);
+                    }
                 }
             }
         }
@@ -416,28 +715,49 @@ fn process_stream_event(app: &mut App, event: StreamInnerEvent) {
                         app.add_text_line("[Warning: failed to record tool call]".to_string());
                     }
                 }
+                if app.config.behavior.backup_files
+                    && app.attached_session.is_none()
+                    && let Ok(input) = serde_json::from_str(&input_json)
+                {
+                    backup_before_tool(app, w, &tool_name, &input);
+                }
+                app.fire_hook(
+                    HookEvent::ToolCall,
+                    serde_json::json!({
+                        "tool": tool_name,
+                        "tool_use_id": tool_use_id,
+                        "input": serde_json::from_str::<serde_json::Value>(&input_json)
+                            .unwrap_or(serde_json::Value::Null),
+                        "bead_id": app.workers[w].hooked_bead_id,
+                    }),
+                );
                 // Track the last tool used for exchange categorization
                 app.last_tool_used = Some(tool_name.clone());
                 // Add entry to tool panel
-                let summary = extract_tool_summary(&tool_name, &input_json);
+                let summary = extract_tool_summary(&tool_name, &input_json, &app.config.display);
                 app.tool_panel.add_entry(ToolCallEntry {
                     tool_name: tool_name.clone(),
                     summary,
                     status: ToolCallStatus::Pending,
                     tool_use_id: tool_use_id.clone(),
                 });
-                let styled_line = format_tool_summary_styled(&tool_name, &input_json);
-                // Buffer tool call if it has an ID (for correlation with result)
+                let styled_line =
+                    format_tool_summary_styled(&tool_name, &input_json, &app.config.display);
+                // Buffer tool call if it has an ID (for correlation with result),
+                // showing its summary in place of the input preview until then
                 if let Some(ref id) = tool_use_id {
+                    app.set_tool_preview(styled_line.clone());
                     app.tool_panel.pending_calls.insert(
                         id.clone(),
                         PendingToolCall {
                             tool_name: tool_name.clone(),
                             styled_line,
+                            input_json: input_json.clone(),
                         },
                     );
                 } else {
                     // No ID - display immediately
+                    app.clear_tool_preview();
                     app.add_line(styled_line);
                 }
             }
@@ -452,3 +772,196 @@ fn process_stream_event(app: &mut App, event: StreamInnerEvent) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    //! Randomized property tests for the NDJSON pipeline. Each seed builds a
+    //! stream of plausible events, then shuffles, truncates and strips fields
+    //! from it; the pipeline must never panic, and every buffered tool call
+    //! must be resolved by a result or a warning when the iteration ends.
+
+    use super::*;
+    use crate::config::LoadedConfig;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+    use serde_json::{Value, json};
+
+    const SEEDS: u64 = 256;
+
+    fn test_app() -> App {
+        App::new("test".into(), None, LoadedConfig::default_for_test(), None)
+    }
+
+    fn stream(event: Value) -> Value {
+        json!({ "type": "stream_event", "event": event })
+    }
+
+    /// One assistant turn: interleaved text and tool_use blocks, then the
+    /// results for some of the calls.
+    fn turn(rng: &mut StdRng, next_id: &mut u32) -> Vec<Value> {
+        let mut events = vec![stream(json!({ "type": "message_start", "message": {} }))];
+        let mut ids = Vec::new();
+        let blocks = rng.random_range(0..5);
+        for _ in 0..blocks {
+            let index = rng.random_range(0..4);
+            if rng.random_bool(0.5) {
+                *next_id += 1;
+                let id = format!("toolu_{next_id}");
+                let name = ["Bash", "Read", "Edit", "Write", "Task"][rng.random_range(0..5)];
+                let mut block = json!({ "type": "tool_use", "name": name, "input": {} });
+                if rng.random_bool(0.9) {
+                    block["id"] = json!(id);
+                    ids.push(id);
+                }
+                events.push(stream(json!({
+                    "type": "content_block_start", "index": index, "content_block": block,
+                })));
+                let input = r#"{"command":"cargo test","file_path":"src/main.rs"}"#;
+                let split = rng.random_range(0..=input.len());
+                for part in [&input[..split], &input[split..]] {
+                    events.push(stream(json!({
+                        "type": "content_block_delta", "index": index,
+                        "delta": { "type": "input_json_delta", "partial_json": part },
+                    })));
+                }
+            } else {
+                events.push(stream(json!({
+                    "type": "content_block_start", "index": index,
+                    "content_block": { "type": "text", "text": "" },
+                })));
+                events.push(stream(json!({
+                    "type": "content_block_delta", "index": index,
+                    "delta": { "type": "text_delta", "text": "thinking\nabout it" },
+                })));
+            }
+            events.push(stream(
+                json!({ "type": "content_block_stop", "index": index }),
+            ));
+        }
+        events.push(stream(json!({ "type": "message_stop" })));
+        for id in ids {
+            if rng.random_bool(0.6) {
+                events.push(json!({
+                    "type": "user",
+                    "message": { "content": [{
+                        "type": "tool_result", "tool_use_id": id,
+                        "content": "ok", "is_error": rng.random_bool(0.2),
+                    }]},
+                }));
+            }
+        }
+        events
+    }
+
+    /// A whole iteration's worth of well-formed events, ending in a result.
+    fn iteration(rng: &mut StdRng) -> Vec<Value> {
+        let mut next_id = 0;
+        let mut events =
+            vec![json!({ "type": "system", "subtype": "init", "session_id": "abc-1" })];
+        for _ in 0..rng.random_range(1..4) {
+            events.extend(turn(rng, &mut next_id));
+            if rng.random_bool(0.2) {
+                events.push(json!({ "type": "ping" }));
+            }
+        }
+        events.push(json!({
+            "type": "result", "total_cost_usd": 0.01, "duration_ms": 1000,
+            "usage": { "input_tokens": 10, "output_tokens": 5 },
+        }));
+        events
+    }
+
+    /// Remove a random key somewhere in `value`.
+    fn drop_field(rng: &mut StdRng, value: &mut Value) {
+        if let Value::Object(map) = value {
+            let keys: Vec<String> = map.keys().cloned().collect();
+            if keys.is_empty() {
+                return;
+            }
+            let key = &keys[rng.random_range(0..keys.len())];
+            if rng.random_bool(0.5) {
+                map.remove(key);
+            } else if let Some(inner) = map.get_mut(key) {
+                drop_field(rng, inner);
+            }
+        }
+    }
+
+    /// Serialize `events`, corrupting some lines along the way.
+    fn mangle(rng: &mut StdRng, events: Vec<Value>) -> Vec<String> {
+        events
+            .into_iter()
+            .map(|mut event| {
+                if rng.random_bool(0.1) {
+                    drop_field(rng, &mut event);
+                }
+                let line = event.to_string();
+                if rng.random_bool(0.05) {
+                    let mut cut = rng.random_range(0..line.len());
+                    while !line.is_char_boundary(cut) {
+                        cut -= 1;
+                    }
+                    return line[..cut].to_string();
+                }
+                line
+            })
+            .collect()
+    }
+
+    fn warning_count(app: &App) -> usize {
+        app.workers[0]
+            .output_lines
+            .iter()
+            .filter(|line| line.to_string().contains("no result received"))
+            .count()
+    }
+
+    fn is_result(line: &str) -> bool {
+        serde_json::from_str::<ClaudeEvent>(line).is_ok_and(|e| matches!(e, ClaudeEvent::Result(_)))
+    }
+
+    #[test]
+    fn ordered_streams_resolve_every_tool_call() {
+        for seed in 0..SEEDS {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut app = test_app();
+            for line in iteration(&mut rng) {
+                let line = line.to_string();
+                let pending = app.tool_panel.pending_calls.len();
+                let warnings = warning_count(&app);
+                process_line(&mut app, &line);
+                if is_result(&line) {
+                    assert!(app.tool_panel.pending_calls.is_empty(), "seed {seed}");
+                    assert_eq!(warning_count(&app), warnings + pending, "seed {seed}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shuffled_and_corrupted_streams_never_panic() {
+        for seed in 0..SEEDS {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut app = test_app();
+            let mut events = iteration(&mut rng);
+            events.extend(iteration(&mut rng));
+            // Swap a handful of neighbours, plus the odd long-range move
+            for _ in 0..rng.random_range(0..events.len()) {
+                let i = rng.random_range(0..events.len() - 1);
+                events.swap(i, i + 1);
+            }
+            if rng.random_bool(0.3) {
+                events.shuffle(&mut rng);
+            }
+            for line in mangle(&mut rng, events) {
+                process_line(&mut app, &line);
+                if is_result(&line) {
+                    assert!(app.tool_panel.pending_calls.is_empty(), "seed {seed}");
+                }
+            }
+            process_line(&mut app, "[stderr] something went sideways");
+            process_line(&mut app, r#"{"type":"unknown_future_event"}"#);
+        }
+    }
+}
@@ -0,0 +1,417 @@
+//! Lua scripts that transform output lines (the `scripting` cargo feature).
+//!
+//! Each `*.lua` file in the project's `scripts/` directory, next to its
+//! `config.toml`, returns a function. Every line added to the output panel
+//! is passed through those functions in file name order: one returns a
+//! string to show instead, `false` to hide the line, or `nil` to leave it.
+//! Scripts can also call `ralph.count(name, n)` to add to a counter shown
+//! in the command panel.
+//!
+//! A script that fails to load, or errors on a line, is reported once in the
+//! output and then skipped. So is one that runs past an instruction budget
+//! (a loop that never ends would otherwise freeze the TUI), with a hint as
+//! well. Built without the feature, scripts found in the directory are
+//! reported and not run.
+
+#[cfg(feature = "scripting")]
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use tracing::warn;
+
+use crate::config::compute_project_config_path;
+
+/// Directory under the per-project config dir that holds the scripts.
+const SCRIPTS_DIR: &str = "scripts";
+
+/// Lua instructions a script may run on one line, or while loading.
+#[cfg(feature = "scripting")]
+const INSTRUCTION_BUDGET: u32 = 1_000_000;
+
+/// How often, in instructions, the budget is checked.
+#[cfg(feature = "scripting")]
+const BUDGET_CHECK_EVERY: u32 = 10_000;
+
+#[cfg(feature = "scripting")]
+const MAX_CHECKS: u32 = INSTRUCTION_BUDGET / BUDGET_CHECK_EVERY;
+
+/// What the scripts made of a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+pub enum LineAction {
+    Keep,
+    Replace(String),
+    Hide,
+}
+
+/// A loaded script.
+#[cfg(feature = "scripting")]
+struct Script {
+    name: String,
+    function: mlua::RegistryKey,
+}
+
+/// The output scripts of one project.
+pub struct OutputScripts {
+    #[cfg(feature = "scripting")]
+    lua: mlua::Lua,
+    #[cfg(feature = "scripting")]
+    scripts: Vec<Script>,
+    /// Budget checks passed by the script running now.
+    #[cfg(feature = "scripting")]
+    checks: Rc<Cell<u32>>,
+    /// Totals from `ralph.count`, by name.
+    counters: Rc<RefCell<BTreeMap<String, i64>>>,
+    /// Problems not yet shown in the output.
+    errors: Vec<String>,
+    /// A script stopped for running too long, not yet shown as a hint.
+    hint: Option<String>,
+}
+
+impl OutputScripts {
+    /// The scripts of the project in `project_dir`.
+    pub fn load(project_dir: &Path) -> Self {
+        let dir = compute_project_config_path(project_dir)
+            .and_then(|p| p.parent().map(|d| d.join(SCRIPTS_DIR)));
+        match dir {
+            Some(dir) => Self::load_dir(&dir),
+            None => Self::empty(),
+        }
+    }
+
+    fn empty() -> Self {
+        Self {
+            #[cfg(feature = "scripting")]
+            lua: mlua::Lua::new(),
+            #[cfg(feature = "scripting")]
+            scripts: Vec::new(),
+            #[cfg(feature = "scripting")]
+            checks: Rc::default(),
+            counters: Rc::default(),
+            errors: Vec::new(),
+            hint: None,
+        }
+    }
+
+    #[cfg(not(feature = "scripting"))]
+    fn load_dir(dir: &Path) -> Self {
+        let mut scripts = Self::empty();
+        let found = script_files(dir).len();
+        if found > 0 {
+            warn!(dir = ?dir, found, "output_scripts_unsupported");
+            scripts.errors.push(format!(
+                "{found} script(s) in {} not run: ralph was built without the `scripting` feature",
+                dir.display()
+            ));
+        }
+        scripts
+    }
+
+    #[cfg(feature = "scripting")]
+    fn load_dir(dir: &Path) -> Self {
+        let mut scripts = Self::empty();
+        if let Err(e) = scripts.register_api() {
+            scripts.errors.push(format!("scripting setup: {e}"));
+            return scripts;
+        }
+        scripts.install_budget();
+        for path in script_files(dir) {
+            let name = path
+                .file_name()
+                .map_or_else(String::new, |n| n.to_string_lossy().into_owned());
+            match scripts.load_script(&path, &name) {
+                Ok(function) => scripts.scripts.push(Script { name, function }),
+                Err(e) => {
+                    warn!(script = %name, error = %e, "output_script_load_failed");
+                    if scripts.over_budget() {
+                        scripts.hint = Some(overrun_hint(&name));
+                    }
+                    scripts.errors.push(format!("{name}: {e}"));
+                }
+            }
+        }
+        scripts
+    }
+
+    /// Abort whatever script is running once it passes `INSTRUCTION_BUDGET`.
+    /// The count restarts before each load and each line.
+    #[cfg(feature = "scripting")]
+    fn install_budget(&self) {
+        let checks = Rc::clone(&self.checks);
+        let triggers = mlua::HookTriggers::new().every_nth_instruction(BUDGET_CHECK_EVERY);
+        self.lua.set_hook(triggers, move |_, _| {
+            checks.set(checks.get() + 1);
+            if checks.get() > MAX_CHECKS {
+                return Err(mlua::Error::runtime(format!(
+                    "stopped after {INSTRUCTION_BUDGET} instructions"
+                )));
+            }
+            Ok(())
+        });
+    }
+
+    /// Whether the script that just ran was stopped by the budget.
+    #[cfg(feature = "scripting")]
+    fn over_budget(&self) -> bool {
+        self.checks.get() > MAX_CHECKS
+    }
+
+    /// Expose `ralph.count(name, n)` to the scripts.
+    #[cfg(feature = "scripting")]
+    fn register_api(&self) -> mlua::Result<()> {
+        let counters = Rc::clone(&self.counters);
+        let count = self
+            .lua
+            .create_function(move |_, (name, n): (String, Option<i64>)| {
+                *counters.borrow_mut().entry(name).or_insert(0) += n.unwrap_or(1);
+                Ok(())
+            })?;
+        let api = self.lua.create_table()?;
+        api.set("count", count)?;
+        self.lua.globals().set("ralph", api)
+    }
+
+    /// Run the script at `path` and keep the function it returns.
+    #[cfg(feature = "scripting")]
+    fn load_script(&self, path: &Path, name: &str) -> Result<mlua::RegistryKey, String> {
+        let source = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        self.checks.set(0);
+        let value: mlua::Value = self
+            .lua
+            .load(source)
+            .set_name(name)
+            .eval()
+            .map_err(|e| e.to_string())?;
+        match value {
+            mlua::Value::Function(function) => self
+                .lua
+                .create_registry_value(function)
+                .map_err(|e| e.to_string()),
+            _ => Err("must return a function".to_string()),
+        }
+    }
+
+    /// No scripts to run and nothing to report.
+    pub fn is_empty(&self) -> bool {
+        #[cfg(feature = "scripting")]
+        let loaded = self.scripts.len();
+        #[cfg(not(feature = "scripting"))]
+        let loaded = 0;
+        loaded == 0 && self.errors.is_empty()
+    }
+
+    /// Pass `text` through each script in turn.
+    #[cfg(not(feature = "scripting"))]
+    pub fn apply(&mut self, _text: &str) -> LineAction {
+        LineAction::Keep
+    }
+
+    /// Pass `text` through each script in turn. A script that errors is
+    /// dropped and the line goes on unchanged.
+    #[cfg(feature = "scripting")]
+    pub fn apply(&mut self, text: &str) -> LineAction {
+        let mut current: Option<String> = None;
+        let mut hidden = false;
+        let mut failed = Vec::new();
+        for (i, script) in self.scripts.iter().enumerate() {
+            let line = current.as_deref().unwrap_or(text);
+            self.checks.set(0);
+            let result = self
+                .lua
+                .registry_value::<mlua::Function>(&script.function)
+                .and_then(|function| function.call::<_, mlua::Value>(line));
+            match result {
+                Ok(mlua::Value::Nil) => {}
+                Ok(mlua::Value::Boolean(false)) => {
+                    hidden = true;
+                    break;
+                }
+                Ok(mlua::Value::String(s)) => current = Some(s.to_string_lossy().into_owned()),
+                Ok(other) => failed.push((
+                    i,
+                    format!(
+                        "returned a {}, not a string, false or nil",
+                        other.type_name()
+                    ),
+                    false,
+                )),
+                Err(e) => failed.push((i, e.to_string(), self.over_budget())),
+            }
+        }
+        for (i, error, over_budget) in failed.into_iter().rev() {
+            let script = self.scripts.remove(i);
+            if over_budget {
+                self.hint = Some(overrun_hint(&script.name));
+            }
+            warn!(script = %script.name, error = %error, "output_script_disabled");
+            self.errors
+                .push(format!("{} disabled: {error}", script.name));
+        }
+        match current {
+            _ if hidden => LineAction::Hide,
+            Some(text) => LineAction::Replace(text),
+            None => LineAction::Keep,
+        }
+    }
+
+    /// Counter totals, by name.
+    pub fn counters(&self) -> Vec<(String, i64)> {
+        self.counters
+            .borrow()
+            .iter()
+            .map(|(name, n)| (name.clone(), *n))
+            .collect()
+    }
+
+    /// Problems to show in the output, each once.
+    pub fn take_errors(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// A hint to show about a script stopped for running too long, once.
+    pub fn take_hint(&mut self) -> Option<String> {
+        self.hint.take()
+    }
+}
+
+#[cfg(feature = "scripting")]
+fn overrun_hint(name: &str) -> String {
+    format!("Output script {name} ran too long and was disabled")
+}
+
+/// The `*.lua` files in `dir`, in name order.
+fn script_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "lua"))
+        .collect();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scripts_dir(scripts: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, source) in scripts {
+            std::fs::write(dir.path().join(name), source).unwrap();
+        }
+        dir
+    }
+
+    #[cfg(not(feature = "scripting"))]
+    #[test]
+    fn scripts_are_reported_when_built_without_scripting() {
+        let dir = scripts_dir(&[("paths.lua", "return function(line) end")]);
+        let mut scripts = OutputScripts::load_dir(dir.path());
+        assert_eq!(scripts.apply("hello"), LineAction::Keep);
+        let errors = scripts.take_errors();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("without the `scripting` feature"));
+
+        let empty = tempfile::tempdir().unwrap();
+        assert!(OutputScripts::load_dir(empty.path()).is_empty());
+    }
+
+    #[cfg(feature = "scripting")]
+    #[test]
+    fn scripts_rewrite_hide_and_count_in_name_order() {
+        let dir = scripts_dir(&[
+            (
+                "1-vendor.lua",
+                r#"return function(line)
+                    local short, n = line:gsub("/vendor/[%w/_%.-]+/", "/vendor/…/")
+                    if n > 0 then return short end
+                end"#,
+            ),
+            (
+                "2-noise.lua",
+                r#"return function(line)
+                    if line:find("^DEBUG") then return false end
+                    if line:find("test result: ok") then ralph.count("passes") end
+                end"#,
+            ),
+            ("notes.txt", "not a script"),
+        ]);
+        let mut scripts = OutputScripts::load_dir(dir.path());
+        assert!(scripts.take_errors().is_empty());
+        assert_eq!(
+            scripts.apply("Read /src/vendor/github.com/x/y/lib.go"),
+            LineAction::Replace("Read /src/vendor/…/lib.go".into())
+        );
+        assert_eq!(scripts.apply("DEBUG noisy"), LineAction::Hide);
+        assert_eq!(scripts.apply("plain"), LineAction::Keep);
+        scripts.apply("test result: ok. 3 passed");
+        scripts.apply("test result: ok. 4 passed");
+        assert_eq!(scripts.counters(), vec![("passes".to_string(), 2)]);
+    }
+
+    #[cfg(feature = "scripting")]
+    #[test]
+    fn broken_scripts_are_reported_once_and_skipped() {
+        let dir = scripts_dir(&[
+            ("a-syntax.lua", "return function(line"),
+            ("b-value.lua", "return 42"),
+            ("c-runtime.lua", "return function(line) error('boom') end"),
+            ("d-ok.lua", "return function(line) return line:upper() end"),
+        ]);
+        let mut scripts = OutputScripts::load_dir(dir.path());
+        let errors = scripts.take_errors();
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[1].contains("must return a function"));
+
+        assert_eq!(scripts.apply("hi"), LineAction::Replace("HI".into()));
+        let errors = scripts.take_errors();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0].starts_with("c-runtime.lua disabled"),
+            "{errors:?}"
+        );
+        assert_eq!(scripts.apply("hi"), LineAction::Replace("HI".into()));
+        assert!(scripts.take_errors().is_empty());
+        assert_eq!(scripts.take_hint(), None);
+    }
+
+    #[cfg(feature = "scripting")]
+    #[test]
+    fn looping_scripts_are_stopped_and_disabled() {
+        let dir = scripts_dir(&[
+            ("a-load.lua", "while true do end"),
+            (
+                "b-line.lua",
+                r#"return function(line)
+                    if line == "spin" then while true do end end
+                end"#,
+            ),
+            ("c-ok.lua", "return function(line) return line .. '!' end"),
+        ]);
+        let mut scripts = OutputScripts::load_dir(dir.path());
+        let errors = scripts.take_errors();
+        assert!(errors[0].starts_with("a-load.lua: "), "{errors:?}");
+        assert!(errors[0].contains("instructions"), "{errors:?}");
+        assert_eq!(
+            scripts.take_hint().as_deref(),
+            Some("Output script a-load.lua ran too long and was disabled")
+        );
+
+        assert_eq!(scripts.apply("fine"), LineAction::Replace("fine!".into()));
+        assert_eq!(scripts.apply("spin"), LineAction::Replace("spin!".into()));
+        let errors = scripts.take_errors();
+        assert!(errors[0].starts_with("b-line.lua disabled"), "{errors:?}");
+        assert_eq!(
+            scripts.take_hint().as_deref(),
+            Some("Output script b-line.lua ran too long and was disabled")
+        );
+        assert_eq!(scripts.apply("spin"), LineAction::Replace("spin!".into()));
+        assert!(scripts.take_errors().is_empty());
+    }
+}
@@ -0,0 +1,545 @@
+//! Moving a project's ralph state between machines.
+//!
+//! `ralph export` bundles the per-project config dir (config, PROMPT.md,
+//! board columns, UI state), this repo's history from the database
+//! (sessions, iterations, spec totals, tags, tool calls) and its sessions'
+//! `ralph report` archives into a tarball. Transcripts are left out unless
+//! `--transcripts` is passed. `ralph import` unpacks one into the current
+//! project: history rows are re-keyed to the local repo path and sessions
+//! already present are skipped, so importing twice is harmless.
+//!
+//! With `[encryption] age_recipients` set, reports and transcripts are
+//! encrypted as they're staged and the tarball is streamed through `age` to
+//! `.tar.gz.age`; it has to be decrypted with `age -d` before importing.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+use anyhow::{Context, Result, bail};
+use rusqlite::Connection;
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use serde_json::{Map, Value};
+use tempfile::TempDir;
+
+use crate::config::compute_project_config_path;
+use crate::{db, logging};
+
+/// Top-level directory inside the archive.
+const BUNDLE_DIR: &str = "ralph-state";
+const HISTORY_FILE: &str = "history.json";
+const MANIFEST_FILE: &str = "manifest.json";
+/// Per-project files that belong to one machine's running session.
+const SKIPPED_PROJECT_FILES: [&str; 6] = [
+    "session.lock",
+    "status.json",
+    "last-session.json",
+    "last-iteration.txt",
+    "daemon.pid",
+    "daemon.log",
+];
+
+/// History tables and the rows of them that belong to a repo (`?1`).
+const HISTORY_TABLES: [(&str, &str); 5] = [
+    ("sessions", "repo_path = ?1"),
+    (
+        "session_tags",
+        "session_id IN (SELECT session_id FROM sessions WHERE repo_path = ?1)",
+    ),
+    ("iterations", "repo_path = ?1"),
+    ("specs", "repo_path = ?1"),
+    ("tool_calls", "repo_path = ?1"),
+];
+
+/// This repo's rows from every history table, as `{table: [row, ...]}`.
+pub fn export_history(conn: &Connection, repo_path: &str) -> Result<Value> {
+    let mut tables = Map::new();
+    for (table, filter) in HISTORY_TABLES {
+        let mut stmt = conn.prepare(&format!("SELECT * FROM {table} WHERE {filter}"))?;
+        let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+        let rows = stmt.query_map([repo_path], |row| {
+            let mut object = Map::new();
+            for (i, column) in columns.iter().enumerate() {
+                let value = match row.get_ref(i)? {
+                    ValueRef::Null | ValueRef::Blob(_) => Value::Null,
+                    ValueRef::Integer(n) => Value::from(n),
+                    ValueRef::Real(x) => Value::from(x),
+                    ValueRef::Text(t) => Value::from(String::from_utf8_lossy(t)),
+                };
+                object.insert(column.clone(), value);
+            }
+            Ok(Value::Object(object))
+        })?;
+        let rows = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .with_context(|| format!("Failed to export {table}"))?;
+        tables.insert(table.to_string(), Value::Array(rows));
+    }
+    Ok(Value::Object(tables))
+}
+
+/// Insert exported history under `repo_path`, skipping sessions this
+/// database already has. Returns how many sessions were added.
+pub fn import_history(conn: &Connection, repo_path: &str, history: &Value) -> Result<usize> {
+    let mut known = HashSet::new();
+    {
+        let mut stmt = conn.prepare("SELECT session_id FROM sessions")?;
+        for id in stmt.query_map([], |row| row.get::<_, String>(0))? {
+            known.insert(id?);
+        }
+    }
+    let is_new = |row: &Map<String, Value>| {
+        row.get("session_id")
+            .and_then(Value::as_str)
+            .is_none_or(|id| !known.contains(id))
+    };
+
+    let mut added = 0;
+    for (table, _) in HISTORY_TABLES {
+        let Some(rows) = history.get(table).and_then(Value::as_array) else {
+            continue;
+        };
+        for row in rows
+            .iter()
+            .filter_map(Value::as_object)
+            .filter(|r| is_new(r))
+        {
+            insert_row(conn, table, row, repo_path)
+                .with_context(|| format!("Failed to import {table}"))?;
+            if table == "sessions" {
+                added += 1;
+            }
+        }
+    }
+    Ok(added)
+}
+
+/// Insert one exported row, dropping its local row ID and re-keying its repo.
+/// Column names come from the archive, so each must be a column of `table`.
+fn insert_row(
+    conn: &Connection,
+    table: &str,
+    row: &Map<String, Value>,
+    repo_path: &str,
+) -> Result<()> {
+    let known = table_columns(conn, table)?;
+    let mut columns = Vec::new();
+    let mut values = Vec::new();
+    for (column, value) in row.iter().filter(|(c, _)| *c != "id") {
+        if !known.contains(column) {
+            bail!("unknown column {column:?}");
+        }
+        columns.push(column.as_str());
+        values.push(if column == "repo_path" {
+            SqlValue::Text(repo_path.to_string())
+        } else {
+            match value {
+                Value::Bool(b) => SqlValue::Integer(i64::from(*b)),
+                Value::Number(n) => n
+                    .as_i64()
+                    .map(SqlValue::Integer)
+                    .or_else(|| n.as_f64().map(SqlValue::Real))
+                    .unwrap_or(SqlValue::Null),
+                Value::String(s) => SqlValue::Text(s.clone()),
+                _ => SqlValue::Null,
+            }
+        });
+    }
+    let placeholders = vec!["?"; columns.len()].join(", ");
+    conn.execute(
+        &format!(
+            "INSERT OR IGNORE INTO {table} ({}) VALUES ({placeholders})",
+            columns.join(", ")
+        ),
+        rusqlite::params_from_iter(values),
+    )?;
+    Ok(())
+}
+
+/// The column names of `table`.
+fn table_columns(conn: &Connection, table: &str) -> Result<HashSet<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>("name"))?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(columns)
+}
+
+/// Copy the regular files in `from` into `to`, skipping existing ones unless
+/// `overwrite`, and encrypting them on the way when `recipients` is set.
+/// Returns how many were copied.
+fn copy_files(
+    from: &Path,
+    to: &Path,
+    overwrite: bool,
+    recipients: &[String],
+    keep: impl Fn(&str) -> bool,
+) -> Result<usize> {
+    let Ok(entries) = std::fs::read_dir(from) else {
+        return Ok(0);
+    };
+    let mut copied = 0;
+    for entry in entries.filter_map(Result::ok) {
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else {
+            continue;
+        };
+        let target = to.join(name);
+        if !entry.file_type().is_ok_and(|t| t.is_file())
+            || !keep(name)
+            || (target.exists() && !overwrite)
+        {
+            continue;
+        }
+        std::fs::create_dir_all(to)
+            .with_context(|| format!("Failed to create {}", to.display()))?;
+        crate::encryption::copy_file(recipients, &entry.path(), &target)?;
+        copied += 1;
+    }
+    Ok(copied)
+}
+
+/// Session IDs in exported history.
+fn session_ids(history: &Value) -> HashSet<String> {
+    history
+        .get("sessions")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|s| s.get("session_id")?.as_str().map(str::to_string))
+        .collect()
+}
+
+/// Whether a report or transcript file name belongs to one of `sessions`.
+fn belongs_to(name: &str, sessions: &HashSet<String>) -> bool {
+    let stem = name
+        .strip_prefix("ralph-report-")
+        .unwrap_or(name)
+        .trim_end_matches(crate::encryption::EXTENSION)
+        .trim_end_matches(".tar.gz")
+        .trim_end_matches(".log");
+    sessions.contains(stem)
+}
+
+/// Where a project's state comes from or goes to.
+pub struct StateDirs {
+    /// Per-project config dir.
+    pub project: PathBuf,
+    /// Log dir, holding `reports/` and `transcripts/`.
+    pub logs: Option<PathBuf>,
+}
+
+/// Lay out the export bundle in `bundle`. With `recipients`, reports and
+/// transcripts are encrypted as they're copied in, so they are never staged
+/// in plaintext.
+pub fn stage_export(
+    bundle: &Path,
+    dirs: &StateDirs,
+    conn: &Connection,
+    repo_path: &str,
+    transcripts: bool,
+    recipients: &[String],
+) -> Result<()> {
+    std::fs::create_dir_all(bundle)
+        .with_context(|| format!("Failed to create {}", bundle.display()))?;
+    copy_files(&dirs.project, &bundle.join("project"), true, &[], |name| {
+        !SKIPPED_PROJECT_FILES.contains(&name)
+    })?;
+
+    let history = export_history(conn, repo_path)?;
+    let sessions = session_ids(&history);
+    std::fs::write(bundle.join(HISTORY_FILE), serde_json::to_string(&history)?)?;
+
+    if let Some(logs) = &dirs.logs {
+        let ours = |name: &str| belongs_to(name, &sessions);
+        copy_files(
+            &logs.join("reports"),
+            &bundle.join("reports"),
+            true,
+            recipients,
+            ours,
+        )?;
+        if transcripts {
+            copy_files(
+                &logs.join("transcripts"),
+                &bundle.join("transcripts"),
+                true,
+                recipients,
+                ours,
+            )?;
+        }
+    }
+
+    let exported_at = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let manifest = serde_json::json!({
+        "version": 1,
+        "ralph_version": env!("CARGO_PKG_VERSION"),
+        "repo_path": repo_path,
+        "exported_at": exported_at,
+        "sessions": sessions.len(),
+        "transcripts": transcripts,
+    });
+    std::fs::write(
+        bundle.join(MANIFEST_FILE),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+    Ok(())
+}
+
+/// Apply an unpacked bundle to this machine and describe what changed.
+pub fn apply_import(
+    bundle: &Path,
+    dirs: &StateDirs,
+    conn: &Connection,
+    repo_path: &str,
+    force: bool,
+) -> Result<Vec<String>> {
+    if !bundle.join(MANIFEST_FILE).exists() {
+        bail!("Not a ralph export (no {MANIFEST_FILE})");
+    }
+    let mut summary = Vec::new();
+
+    let copied = copy_files(&bundle.join("project"), &dirs.project, force, &[], |_| true)?;
+    summary.push(format!(
+        "{copied} project file(s) → {}",
+        dirs.project.display()
+    ));
+
+    let history: Value = serde_json::from_str(
+        &std::fs::read_to_string(bundle.join(HISTORY_FILE)).context("Failed to read history")?,
+    )
+    .context("Failed to parse history")?;
+    let added = import_history(conn, repo_path, &history)?;
+    summary.push(format!("{added} session(s) of history"));
+
+    if let Some(logs) = &dirs.logs {
+        for kind in ["reports", "transcripts"] {
+            let copied = copy_files(&bundle.join(kind), &logs.join(kind), false, &[], |_| true)?;
+            if copied > 0 {
+                summary.push(format!("{copied} {kind}"));
+            }
+        }
+    }
+    Ok(summary)
+}
+
+fn state_dirs() -> Result<StateDirs> {
+    let project = compute_project_config_path(&crate::config::startup_dir())
+        .and_then(|p| p.parent().map(Path::to_path_buf))
+        .context("Failed to determine the project config directory")?;
+    Ok(StateDirs {
+        project,
+        logs: logging::log_directory(),
+    })
+}
+
+/// A private scratch directory for staging, removed when dropped.
+fn scratch_dir(kind: &str) -> Result<TempDir> {
+    tempfile::Builder::new()
+        .prefix(&format!("ralph-{kind}-"))
+        .tempdir()
+        .context("Failed to create staging directory")
+}
+
+/// Run the export subcommand.
+pub fn run_export(output: Option<PathBuf>, transcripts: bool) -> Result<()> {
+    let repo_path = db::detect_repo_path(&crate::config::startup_dir());
+    let output = output.unwrap_or_else(|| {
+        let name = Path::new(&repo_path)
+            .file_name()
+            .map_or_else(|| "project".into(), |n| n.to_string_lossy());
+        PathBuf::from(format!("ralph-state-{name}.tar.gz"))
+    });
+    let conn = db::open()?;
+    let recipients = crate::config::load_config()
+        .config
+        .encryption
+        .age_recipients;
+    let scratch = scratch_dir("export")?;
+    let result = stage_export(
+        &scratch.path().join(BUNDLE_DIR),
+        &state_dirs()?,
+        &conn,
+        &repo_path,
+        transcripts,
+        &recipients,
+    )
+    .and_then(|()| {
+        // Streamed into age when encrypting, so the archive is never on disk in plaintext
+        let mut tar = Command::new("tar");
+        tar.arg("-czf")
+            .arg("-")
+            .arg("-C")
+            .arg(scratch.path())
+            .arg(BUNDLE_DIR);
+        crate::encryption::write_command_output(&recipients, tar, &output)
+    });
+    // Remove the staged files whether or not the archive was written
+    let _ = scratch.close();
+    println!("{}", result?.display());
+    Ok(())
+}
+
+/// Run the import subcommand.
+pub fn run_import(archive: &Path, force: bool) -> Result<()> {
+    if !archive.is_file() {
+        bail!("{} not found", archive.display());
+    }
+    if archive
+        .to_string_lossy()
+        .ends_with(crate::encryption::EXTENSION)
+    {
+        bail!(
+            "{} is encrypted; decrypt it first with `age -d -i <identity> -o state.tar.gz {}`",
+            archive.display(),
+            archive.display()
+        );
+    }
+    let conn = db::open()?;
+    let scratch = scratch_dir("import")?;
+    let status = Command::new("tar")
+        .arg("-xzf")
+        .arg(archive)
+        .arg("-C")
+        .arg(scratch.path())
+        .status()
+        .context("Failed to run tar")?;
+    if !status.success() {
+        bail!("tar exited with {status}");
+    }
+    let imported = apply_import(
+        &scratch.path().join(BUNDLE_DIR),
+        &state_dirs()?,
+        &conn,
+        &db::detect_repo_path(&crate::config::startup_dir()),
+        force,
+    )?;
+    for line in imported {
+        println!("Imported {line}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::open_memory;
+    use crate::stats::{self, IterationRow};
+
+    fn seed(conn: &Connection, repo: &str, session: &str) {
+        stats::start_session(conn, session, repo);
+        stats::end_session(conn, session, 1, 0.5);
+        db::insert_session_tags(conn, session, &["v2".to_string()]);
+        stats::record_iteration(
+            conn,
+            &IterationRow {
+                session_id: session,
+                repo_path: repo,
+                bead_id: Some("bd-1"),
+                tokens: 100,
+                cost_usd: 0.5,
+                ..Default::default()
+            },
+        );
+        stats::complete_spec(conn, repo, "bd-1", "Add login");
+    }
+
+    #[test]
+    fn history_moves_to_the_new_repo_path_once() {
+        let old = open_memory().unwrap();
+        seed(&old, "/old/repo", "sess-1");
+        seed(&old, "/elsewhere", "sess-other");
+        let history = export_history(&old, "/old/repo").unwrap();
+        assert_eq!(history["sessions"].as_array().unwrap().len(), 1);
+        assert_eq!(history["session_tags"].as_array().unwrap().len(), 1);
+
+        let new = open_memory().unwrap();
+        assert_eq!(import_history(&new, "/new/repo", &history).unwrap(), 1);
+        assert_eq!(import_history(&new, "/new/repo", &history).unwrap(), 0);
+
+        let specs = stats::spec_totals(&new, "/new/repo").unwrap();
+        assert_eq!(specs["bd-1"].iterations, 1);
+        assert!(specs["bd-1"].completed_at.is_some());
+        let last = stats::last_session(&new, "/new/repo").unwrap();
+        assert_eq!(last.session_id, "sess-1");
+    }
+
+    #[test]
+    fn unknown_history_columns_are_rejected() {
+        let conn = open_memory().unwrap();
+        let history = serde_json::json!({
+            "sessions": [{"session_id": "s1", "repo_path": "/x", "1); DROP TABLE sessions; --": 1}]
+        });
+        let err = import_history(&conn, "/repo", &history).unwrap_err();
+        assert!(format!("{err:#}").contains("unknown column"), "{err:#}");
+        assert!(stats::last_session(&conn, "/repo").is_none());
+    }
+
+    #[test]
+    fn bundle_round_trip_skips_lock_and_transcripts_by_default() {
+        let conn = open_memory().unwrap();
+        seed(&conn, "/repo", "sess-1");
+        let src = tempfile::tempdir().unwrap();
+        let project = src.path().join("project");
+        let logs = src.path().join("logs");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::write(project.join("config.toml"), "[behavior]\n").unwrap();
+        std::fs::write(project.join("session.lock"), "pid").unwrap();
+        std::fs::write(project.join("status.json"), "{}").unwrap();
+        std::fs::create_dir_all(logs.join("reports")).unwrap();
+        std::fs::create_dir_all(logs.join("transcripts")).unwrap();
+        std::fs::write(logs.join("reports/ralph-report-sess-1.tar.gz"), "r").unwrap();
+        std::fs::write(logs.join("reports/ralph-report-other.tar.gz"), "r").unwrap();
+        std::fs::write(logs.join("reports/ralph-report-sess-1.tar.gz.age"), "r").unwrap();
+        std::fs::write(logs.join("transcripts/sess-1.log"), "t").unwrap();
+        let dirs = StateDirs {
+            project,
+            logs: Some(logs),
+        };
+
+        let bundle = src.path().join(BUNDLE_DIR);
+        stage_export(&bundle, &dirs, &conn, "/repo", false, &[]).unwrap();
+        assert!(bundle.join("project/config.toml").exists());
+        assert!(!bundle.join("project/session.lock").exists());
+        assert!(!bundle.join("project/status.json").exists());
+        assert!(bundle.join("reports/ralph-report-sess-1.tar.gz").exists());
+        assert!(
+            bundle
+                .join("reports/ralph-report-sess-1.tar.gz.age")
+                .exists()
+        );
+        assert!(!bundle.join("reports/ralph-report-other.tar.gz").exists());
+        assert!(!bundle.join("transcripts").exists());
+
+        let dst = tempfile::tempdir().unwrap();
+        let target = StateDirs {
+            project: dst.path().join("project"),
+            logs: Some(dst.path().join("logs")),
+        };
+        std::fs::create_dir_all(&target.project).unwrap();
+        std::fs::write(target.project.join("config.toml"), "# local\n").unwrap();
+        let fresh = open_memory().unwrap();
+        let summary = apply_import(&bundle, &target, &fresh, "/repo", false).unwrap();
+        assert_eq!(summary[1], "1 session(s) of history");
+        assert_eq!(
+            std::fs::read_to_string(target.project.join("config.toml")).unwrap(),
+            "# local\n"
+        );
+        assert!(
+            dst.path()
+                .join("logs/reports/ralph-report-sess-1.tar.gz")
+                .exists()
+        );
+
+        apply_import(&bundle, &target, &fresh, "/repo", true).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(target.project.join("config.toml")).unwrap(),
+            "[behavior]\n"
+        );
+        assert!(apply_import(src.path(), &target, &fresh, "/repo", false).is_err());
+    }
+}
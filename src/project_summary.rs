@@ -0,0 +1,112 @@
+//! Project summary shown in the preview pane at startup, before the first run.
+//!
+//! Board counts come from the live board state at draw time; everything else
+//! is gathered once when the tab opens. The previous session's totals come
+//! from the stats database (see `stats`).
+
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::App;
+use crate::config::resolve_prompt_path;
+use crate::shutdown_summary::ShutdownSummary;
+
+/// Totals from the most recent session that ran at least one iteration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LastSession {
+    pub session_id: String,
+    /// Unix seconds when the session ended.
+    pub ended_at: u64,
+    pub iterations: u32,
+    pub cost_usd: f64,
+}
+
+/// Everything the summary shows besides the board counts.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectSummary {
+    /// Current git branch, if the project is a repo.
+    pub branch: Option<String>,
+    /// Per-project PROMPT.md modification time, or `None` for the compiled-in prompt.
+    pub prompt_modified: Option<SystemTime>,
+    pub last_session: Option<LastSession>,
+    /// How the previous session ended (`last-session.json`).
+    pub last_exit: Option<ShutdownSummary>,
+    /// Config problems and failed doctor checks.
+    pub warnings: Vec<String>,
+}
+
+impl ProjectSummary {
+    /// Gather the summary for the current project.
+    pub fn gather(app: &App) -> Self {
+        let mut warnings = Vec::new();
+        warnings.extend(app.project_config_error.clone());
+        warnings.extend(app.board_config_error.clone());
+        Self {
+            branch: current_branch(&app.project_dir),
+            prompt_modified: resolve_prompt_path(&app.project_dir)
+                .and_then(|p| crate::startup::get_file_mtime(&p)),
+            last_session: app
+                .tool_history_db
+                .as_ref()
+                .and_then(|conn| crate::stats::last_session(conn, &app.repo_path)),
+            last_exit: crate::shutdown_summary::load(&app.project_dir),
+            warnings,
+        }
+    }
+}
+
+fn current_branch(project_dir: &Path) -> Option<String> {
+    Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(project_dir)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8(o.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Describe how long ago `then` was, e.g. `3d ago`.
+pub fn format_ago(then: SystemTime, now: SystemTime) -> String {
+    let secs = now.duration_since(then).unwrap_or(Duration::ZERO).as_secs();
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+/// Record `app`'s totals as its session's end. Sessions that never ran an
+/// iteration don't count as the last session.
+pub fn save_last_session(app: &App) {
+    if let Some(conn) = &app.tool_history_db {
+        crate::stats::end_session(
+            conn,
+            &app.session_id,
+            app.exchange_count,
+            app.session_cost_usd,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_ago_picks_largest_unit() {
+        let now = SystemTime::now();
+        let ago = |secs| format_ago(now - Duration::from_secs(secs), now);
+        assert_eq!(ago(5), "just now");
+        assert_eq!(ago(125), "2m ago");
+        assert_eq!(ago(3 * 3600), "3h ago");
+        assert_eq!(ago(8 * 86400), "8d ago");
+        // Clock skew: a future time reads as now
+        assert_eq!(format_ago(now + Duration::from_secs(60), now), "just now");
+    }
+}
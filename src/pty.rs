@@ -0,0 +1,184 @@
+//! Pseudo-terminal for Claude's output (`claude.pty`).
+//!
+//! Some tools only color their output, draw progress or flush line by line
+//! when stdout is a terminal. With `claude.pty` Claude's stdout and stderr
+//! both go to the slave side of a raw-mode PTY and ralph reads the master,
+//! so the two streams arrive interleaved and stderr lines lose their
+//! `[stderr]` prefix. stdin stays a pipe for the prompt. [`clean_line`]
+//! strips what the terminal would have interpreted before lines reach the
+//! output parser.
+
+use std::fs::File;
+use std::io;
+
+/// Terminal size Claude sees: wide enough that tools don't wrap JSON.
+const COLUMNS: u16 = 200;
+const ROWS: u16 = 50;
+
+/// Both ends of a freshly opened PTY.
+pub struct Pty {
+    /// Read side for ralph.
+    pub master: File,
+    /// Handed to the child as stdout and stderr, then dropped here so the
+    /// master reads EOF (EIO) once the child is gone.
+    pub slave: File,
+}
+
+/// Open a raw-mode PTY of [`COLUMNS`]x[`ROWS`]. Both descriptors are
+/// close-on-exec; the child gets the slave through `dup2`.
+#[cfg(unix)]
+pub fn open() -> io::Result<Pty> {
+    use std::os::fd::FromRawFd;
+
+    let mut master = -1;
+    let mut slave = -1;
+    let size = libc::winsize {
+        ws_row: ROWS,
+        ws_col: COLUMNS,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    // SAFETY: openpty writes two descriptors we take ownership of right
+    // after; the name and termios pointers may be null.
+    let opened = unsafe {
+        libc::openpty(
+            &mut master,
+            &mut slave,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            &size as *const libc::winsize as *mut libc::winsize,
+        )
+    };
+    if opened != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: both descriptors are open and owned by nothing else.
+    let pty = unsafe {
+        Pty {
+            master: File::from_raw_fd(master),
+            slave: File::from_raw_fd(slave),
+        }
+    };
+    for fd in [master, slave] {
+        // SAFETY: fd is open for as long as `pty` lives.
+        if unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    // Raw mode: no echo and no `\n` → `\r\n`, so lines come through as written
+    // SAFETY: termios is plain data filled in by tcgetattr before use.
+    unsafe {
+        let mut termios: libc::termios = std::mem::zeroed();
+        if libc::tcgetattr(slave, &mut termios) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        libc::cfmakeraw(&mut termios);
+        if libc::tcsetattr(slave, libc::TCSANOW, &termios) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(pty)
+}
+
+#[cfg(not(unix))]
+pub fn open() -> io::Result<Pty> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "claude.pty needs a Unix pseudo-terminal",
+    ))
+}
+
+/// A line read from the PTY as it would end up on screen, without escape
+/// sequences: CSI (colors, cursor movement), OSC (titles, hyperlinks) and
+/// other `ESC` sequences are dropped. A carriage return starts the line
+/// over, so only the last redraw of a progress line is kept; backspace
+/// erases; other control characters except tab are dropped.
+pub fn clean_line(line: &str) -> String {
+    let line = line.strip_suffix('\r').unwrap_or(line);
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => match chars.next() {
+                // CSI: parameters and intermediates up to a final byte
+                Some('[') => {
+                    for c in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                // OSC: up to BEL or ST (`ESC \`)
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\x07' {
+                            break;
+                        }
+                        if c == '\x1b' {
+                            chars.next_if_eq(&'\\');
+                            break;
+                        }
+                    }
+                }
+                // Other escapes: intermediates, then one final character
+                Some(c) if ('\x20'..='\x2f').contains(&c) => {
+                    while chars.next_if(|c| ('\x20'..='\x2f').contains(c)).is_some() {}
+                    chars.next();
+                }
+                _ => {}
+            },
+            '\r' => out.clear(),
+            '\x08' => {
+                out.pop();
+            }
+            '\t' => out.push(c),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_sequences_are_stripped() {
+        assert_eq!(
+            clean_line("\x1b[1;31merror\x1b[0m: \x1b]8;;https://x.dev\x1b\\link\x1b]8;;\x07\r"),
+            "error: link"
+        );
+        assert_eq!(clean_line("\x1b(Bplain\x1b=text"), "plaintext");
+        assert_eq!(clean_line(r#"{"type":"result"}"#), r#"{"type":"result"}"#);
+    }
+
+    #[test]
+    fn carriage_returns_and_backspaces_redraw_the_line() {
+        assert_eq!(clean_line(" 10%\r 50%\r100% done"), "100% done");
+        assert_eq!(clean_line("abd\x08c"), "abc");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn child_sees_a_terminal_on_stdout() {
+        use std::io::Read;
+        use std::process::{Command, Stdio};
+
+        let Pty { mut master, slave } = open().unwrap();
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg("test -t 1 && echo tty")
+            .stdin(Stdio::null())
+            .stdout(slave)
+            .spawn()
+            .unwrap();
+        assert!(child.wait().unwrap().success());
+        let mut buf = [0; 16];
+        let n = master.read(&mut buf).unwrap();
+        assert_eq!(
+            clean_line(String::from_utf8_lossy(&buf[..n]).trim_end()),
+            "tty"
+        );
+    }
+}
@@ -0,0 +1,373 @@
+//! One-line run status for `ralph status`, e.g. in a tmux status bar or a
+//! starship prompt.
+//!
+//! While a tab owns the project session lock, it keeps `status.json` (next to
+//! `session.lock`) current with the run's state, iteration, spend and hooked
+//! bead, rewriting it only when something changes. `ralph status` prints
+//! nothing unless the lock's owner is alive, so a crashed run never leaves a
+//! stale status in the bar.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::app::{App, AppStatus};
+use crate::config::compute_project_config_path;
+use crate::number_format::NumberFormat;
+use crate::session_lock;
+use crate::ui::format_elapsed;
+
+const STATUS_FILE_NAME: &str = "status.json";
+
+/// What `ralph status` shows, as last published by the running session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunStatus {
+    /// Process that wrote it; must match the session lock's owner.
+    pub pid: u32,
+    pub session_id: String,
+    /// `running`, `starting`, `stopping`, `error`, `idle`, or why the run is held
+    /// (`quiet hours`, `throttled`, `disk`).
+    pub state: String,
+    /// Highest iteration any worker has reached this run.
+    pub iteration: u32,
+    /// Configured iterations (negative for infinite).
+    pub total_iterations: i32,
+    /// Worker count; 0 when the session hasn't published a status yet.
+    pub workers: usize,
+    /// Bead the first busy worker has claimed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bead: Option<String>,
+    pub cost_usd: f64,
+    /// Unix seconds the current run started.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_started_at: Option<u64>,
+}
+
+impl RunStatus {
+    /// Snapshot `app`'s run.
+    pub fn capture(app: &App) -> Self {
+        let busy = app.workers.iter().find(|w| w.hooked_bead_id.is_some());
+        let started = app.workers.iter().filter_map(|w| w.run_start_time).min();
+        Self {
+            pid: std::process::id(),
+            session_id: app.session_id.clone(),
+            state: state_label(app).to_string(),
+            iteration: app
+                .workers
+                .iter()
+                .map(|w| w.current_iteration)
+                .max()
+                .unwrap_or(0),
+            total_iterations: app.workers.first().map_or(0, |w| w.total_iterations),
+            workers: app.workers.len(),
+            bead: busy.and_then(|w| w.hooked_bead_id.clone()),
+            cost_usd: app.session_cost_usd,
+            run_started_at: started.map(|t| unix_now().saturating_sub(t.elapsed().as_secs())),
+        }
+    }
+
+    /// Whether this differs from `previous` in anything worth rewriting the file for.
+    ///
+    /// The run start is recomputed from an `Instant` each time, so it may
+    /// wobble by a second without the run having restarted.
+    fn differs_from(&self, previous: &RunStatus) -> bool {
+        let same_start = match (self.run_started_at, previous.run_started_at) {
+            (Some(a), Some(b)) => a.abs_diff(b) <= 1,
+            (a, b) => a == b,
+        };
+        !same_start
+            || RunStatus {
+                run_started_at: previous.run_started_at,
+                ..self.clone()
+            } != *previous
+    }
+}
+
+/// Every label [`state_label`] can return.
+pub const STATES: [&str; 9] = [
+    "idle",
+    "starting",
+    "running",
+    "stopping",
+    "error",
+    "quiet hours",
+    "throttled",
+    "disk",
+    "question",
+];
+
+/// Short lowercase name for what `app`'s run is doing.
+pub fn state_label(app: &App) -> &'static str {
+    match app.status {
+        AppStatus::Stopped if app.quiet_hours_paused => "quiet hours",
+        AppStatus::Stopped if app.question_paused => "question",
+        AppStatus::Stopped if app.budget_throttled_until.is_some() => "throttled",
+        AppStatus::Stopped if app.disk_paused.is_some() => "disk",
+        AppStatus::Stopped => "idle",
+        AppStatus::Starting => "starting",
+        AppStatus::Running if app.workers.iter().any(|w| w.stop_deadline.is_some()) => "stopping",
+        AppStatus::Running => "running",
+        AppStatus::Error => "error",
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Status file path for the project in `project_dir`.
+fn status_path(project_dir: &Path) -> Option<PathBuf> {
+    compute_project_config_path(project_dir).map(|p| p.with_file_name(STATUS_FILE_NAME))
+}
+
+/// Write `status` atomically so a reader never sees a half-written file.
+fn write_to(path: &Path, status: &RunStatus) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(status).map_err(io::Error::other)?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json)?;
+    std::fs::rename(&tmp, path)
+}
+
+fn read_from(path: &Path) -> Option<RunStatus> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+impl App {
+    /// Keep `status.json` current while this tab owns the session lock, and
+    /// remove it once the lock is released.
+    pub fn publish_run_status(&mut self) {
+        let owns_lock = self.session_lock.as_ref().is_some_and(|l| l.is_owner());
+        if !owns_lock {
+            if self.published_status.take().is_some()
+                && let Some(path) = status_path(&self.project_dir)
+            {
+                let _ = std::fs::remove_file(path);
+            }
+            return;
+        }
+        let status = RunStatus::capture(self);
+        if self
+            .published_status
+            .as_ref()
+            .is_some_and(|previous| !status.differs_from(previous))
+        {
+            return;
+        }
+        let Some(path) = status_path(&self.project_dir) else {
+            return;
+        };
+        match write_to(&path, &status) {
+            Ok(()) => debug!(state = %status.state, "run_status_published"),
+            Err(e) => warn!(error = %e, "run_status_write_failed"),
+        }
+        self.published_status = Some(status);
+    }
+}
+
+/// How `ralph status` prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusFormat {
+    Plain,
+    Tmux,
+    Json,
+}
+
+impl StatusFormat {
+    pub const ALL: [StatusFormat; 3] =
+        [StatusFormat::Plain, StatusFormat::Tmux, StatusFormat::Json];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            StatusFormat::Plain => "plain",
+            StatusFormat::Tmux => "tmux",
+            StatusFormat::Json => "json",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        Self::ALL
+            .into_iter()
+            .find(|f| f.name() == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|f| f.name()).collect();
+                format!(
+                    "unknown format '{name}' (expected one of: {})",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// Render `status` as one line, e.g. `ralph running 3/10 · bd-12 · 4:05 · $1.23`.
+pub fn format_status(
+    status: &RunStatus,
+    format: StatusFormat,
+    numbers: &NumberFormat,
+    now: u64,
+) -> Result<String> {
+    if format == StatusFormat::Json {
+        return Ok(serde_json::to_string(status)?);
+    }
+    let total = match status.total_iterations {
+        n if n < 0 => "\u{221e}".to_string(),
+        n => n.to_string(),
+    };
+    let mut parts = Vec::new();
+    // No workers means the session hasn't published anything yet
+    if status.workers > 0 {
+        parts.push(format!("{}/{total}", status.iteration));
+        parts.extend(status.bead.clone());
+        if let Some(started) = status.run_started_at {
+            parts.push(format_elapsed(Duration::from_secs(
+                now.saturating_sub(started),
+            )));
+        }
+        parts.push(numbers.cost(status.cost_usd));
+    }
+    let details = match parts.join(" \u{b7} ") {
+        joined if joined.is_empty() => joined,
+        joined => format!(" {joined}"),
+    };
+    Ok(match format {
+        StatusFormat::Tmux => {
+            let color = match status.state.as_str() {
+                "running" | "starting" => "green",
+                "error" => "red",
+                "idle" => "default",
+                _ => "yellow",
+            };
+            // `#` starts a tmux format sequence; double any in the text
+            format!(
+                "#[fg={color}]ralph {}#[default]{}",
+                status.state.replace('#', "##"),
+                details.replace('#', "##")
+            )
+        }
+        _ => format!("ralph {}{details}", status.state),
+    })
+}
+
+/// The live session's status, if one is running in this project.
+fn active_status() -> Option<RunStatus> {
+    let project_dir = crate::config::startup_dir();
+    let lock = session_lock::read(&session_lock::lock_path(&project_dir)?)?;
+    if !session_lock::is_pid_alive(lock.pid) {
+        return None;
+    }
+    // Running but not yet published (or written by an older ralph): show it's alive
+    let published = status_path(&project_dir).and_then(|p| read_from(&p));
+    Some(
+        published
+            .filter(|s| s.pid == lock.pid)
+            .unwrap_or(RunStatus {
+                pid: lock.pid,
+                session_id: lock.session_id,
+                state: "running".into(),
+                iteration: 0,
+                total_iterations: 0,
+                workers: 0,
+                bead: None,
+                cost_usd: 0.0,
+                run_started_at: None,
+            }),
+    )
+}
+
+/// Run the status subcommand. Prints nothing when no session is running.
+pub fn run(format: StatusFormat) -> Result<()> {
+    let Some(status) = active_status() else {
+        if format == StatusFormat::Json {
+            println!("null");
+        }
+        return Ok(());
+    };
+    let numbers = NumberFormat::from(&crate::config::load_config().config.display);
+    println!("{}", format_status(&status, format, &numbers, unix_now())?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status() -> RunStatus {
+        RunStatus {
+            pid: 42,
+            session_id: "s1".into(),
+            state: "running".into(),
+            iteration: 3,
+            total_iterations: 10,
+            workers: 1,
+            bead: Some("bd-12".into()),
+            cost_usd: 1.234,
+            run_started_at: Some(1_000),
+        }
+    }
+
+    #[test]
+    fn formats_plain_and_tmux_lines() {
+        let numbers = NumberFormat::default();
+        assert_eq!(
+            format_status(&status(), StatusFormat::Plain, &numbers, 1_245).unwrap(),
+            "ralph running 3/10 \u{b7} bd-12 \u{b7} 4:05 \u{b7} $1.23"
+        );
+        let mut idle = status();
+        idle.state = "idle".into();
+        idle.total_iterations = -1;
+        idle.bead = None;
+        idle.run_started_at = None;
+        idle.cost_usd = 0.0;
+        assert_eq!(
+            format_status(&idle, StatusFormat::Tmux, &numbers, 0).unwrap(),
+            "#[fg=default]ralph idle#[default] 3/\u{221e} \u{b7} $0.00"
+        );
+        let json = format_status(&status(), StatusFormat::Json, &numbers, 0).unwrap();
+        assert_eq!(serde_json::from_str::<RunStatus>(&json).unwrap(), status());
+
+        let mut unpublished = status();
+        unpublished.workers = 0;
+        assert_eq!(
+            format_status(&unpublished, StatusFormat::Plain, &numbers, 0).unwrap(),
+            "ralph running"
+        );
+    }
+
+    #[test]
+    fn start_time_wobble_is_not_a_change() {
+        let previous = status();
+        let mut next = status();
+        next.run_started_at = Some(1_001);
+        assert!(!next.differs_from(&previous));
+        next.run_started_at = Some(1_100);
+        assert!(next.differs_from(&previous));
+        let mut next = status();
+        next.iteration = 4;
+        assert!(next.differs_from(&previous));
+    }
+
+    #[test]
+    fn status_file_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(STATUS_FILE_NAME);
+        write_to(&path, &status()).unwrap();
+        assert_eq!(read_from(&path), Some(status()));
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+
+    #[test]
+    fn format_names_parse() {
+        assert_eq!(StatusFormat::from_name("tmux"), Ok(StatusFormat::Tmux));
+        assert!(StatusFormat::from_name("zsh").is_err());
+    }
+}
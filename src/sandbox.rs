@@ -0,0 +1,78 @@
+//! `--sandbox <dir>`: keep everything ralph writes under one directory.
+//!
+//! For hermetic end-to-end tests and demos on machines whose real config
+//! must not be touched. Enabled once at startup, before anything resolves a
+//! path. The config, data, log and hooks directories then come from the
+//! sandbox instead of the platform lookups; project state (session lock,
+//! run status, UI state, …) lives next to the project config, so it
+//! follows. A daemon started from a sandboxed ralph is sandboxed too.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+static SANDBOX: OnceLock<Sandbox> = OnceLock::new();
+
+/// Directory layout under a sandbox root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sandbox {
+    root: PathBuf,
+}
+
+impl Sandbox {
+    /// Create `dir` if needed. The root is made absolute so it still holds
+    /// after a `cd` and in child processes.
+    pub fn new(dir: &Path) -> io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        Ok(Self {
+            root: dir.canonicalize()?,
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Stands in for the platform config dir (`~/.config/ralph`).
+    pub fn config_dir(&self) -> PathBuf {
+        self.root.join("config")
+    }
+
+    /// Stands in for the platform data dir (`~/.local/share/ralph`).
+    pub fn data_dir(&self) -> PathBuf {
+        self.root.join("data")
+    }
+
+    /// Stands in for the platform log dir (`~/.local/state/ralph`).
+    pub fn log_dir(&self) -> PathBuf {
+        self.root.join("logs")
+    }
+}
+
+/// Sandbox this process under `dir`. Only the first call has any effect.
+pub fn enable(dir: &Path) -> io::Result<()> {
+    let sandbox = Sandbox::new(dir)?;
+    let _ = SANDBOX.set(sandbox);
+    Ok(())
+}
+
+/// The sandbox, if `--sandbox` was given.
+pub fn get() -> Option<&'static Sandbox> {
+    SANDBOX.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_directory_is_under_the_absolute_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let sandbox = Sandbox::new(&dir.path().join("demo")).unwrap();
+        assert!(sandbox.root().is_absolute());
+        assert!(sandbox.root().is_dir());
+        for path in [sandbox.config_dir(), sandbox.data_dir(), sandbox.log_dir()] {
+            assert!(path.starts_with(sandbox.root()));
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! Optional structured report the agent ends an iteration with.
+//!
+//! A prompt can ask Claude to finish its final message with a fenced block:
+//!
+//! ````text
+//! ```ralph-report
+//! {"slice_completed": "parser handles nested lists", "spec_complete": false,
+//!  "blockers": ["needs a fixture for CRLF input"], "confidence": 0.7}
+//! ```
+//! ````
+//!
+//! Every field is optional. The report is shown in the output, kept in the
+//! iteration history, sent to `on-iteration-end` hooks, and `spec_complete`
+//! offers to mark a still-open spec Done. A `question` holds auto-continue
+//! until it is answered (see [`crate::agent_question`]).
+
+use serde::{Deserialize, Serialize};
+
+/// Info string of the fenced block that holds the report.
+const FENCE_TAG: &str = "ralph-report";
+
+/// What the agent says about its iteration.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SelfReport {
+    /// The slice of the spec this iteration finished.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slice_completed: Option<String>,
+    /// The agent thinks every acceptance criterion is met.
+    pub spec_complete: bool,
+    /// What stopped or slowed the agent.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blockers: Vec<String>,
+    /// How sure the agent is of its work, from 0 to 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    /// Something the agent needs the user to answer before it goes on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub question: Option<String>,
+}
+
+impl SelfReport {
+    /// One line for the output, e.g. `parser done · spec complete · confidence 70%`.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        parts.extend(self.slice_completed.clone());
+        if self.spec_complete {
+            parts.push("spec complete".to_string());
+        }
+        if let Some(confidence) = self.confidence {
+            parts.push(format!("confidence {:.0}%", confidence * 100.0));
+        }
+        if parts.is_empty() {
+            parts.push("no details".to_string());
+        }
+        parts.join(" \u{b7} ")
+    }
+}
+
+/// The report in `text`, if the last fenced block is a `ralph-report`.
+///
+/// Returns `Some(Err(..))` when the block is there but isn't a valid report,
+/// so a prompt mistake is visible rather than silently ignored.
+pub fn parse(text: &str) -> Option<Result<SelfReport, String>> {
+    let start = text.rfind(&format!("```{FENCE_TAG}"))?;
+    let body = &text[start..];
+    let body = &body[body.find('\n')? + 1..];
+    let body = body.find("```").map_or(body, |end| &body[..end]);
+    Some(
+        serde_json::from_str::<SelfReport>(body)
+            .map_err(|e| e.to_string())
+            .map(|report| SelfReport {
+                confidence: report.confidence.map(|c| c.clamp(0.0, 1.0)),
+                ..report
+            }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_last_report_block() {
+        let text = "Done.\n\n```ralph-report\n{\"slice_completed\": \"old\"}\n```\nMore work.\n\
+                    ```ralph-report\n{\"slice_completed\": \"parser\", \"spec_complete\": true,\n \"blockers\": [\"CRLF fixture\"], \"confidence\": 1.5}\n```\n";
+        let report = parse(text).unwrap().unwrap();
+        assert_eq!(report.slice_completed.as_deref(), Some("parser"));
+        assert!(report.spec_complete);
+        assert_eq!(report.blockers, vec!["CRLF fixture"]);
+        assert_eq!(report.confidence, Some(1.0));
+        assert_eq!(
+            report.summary(),
+            "parser \u{b7} spec complete \u{b7} confidence 100%"
+        );
+    }
+
+    #[test]
+    fn missing_or_malformed_blocks() {
+        assert_eq!(parse("All done, no report."), None);
+        assert_eq!(parse("```json\n{}\n```"), None);
+        assert!(parse("```ralph-report\n{not json}\n```").unwrap().is_err());
+        // An unterminated block still parses
+        let report = parse("```ralph-report\n{}").unwrap().unwrap();
+        assert_eq!(report, SelfReport::default());
+        assert_eq!(report.summary(), "no details");
+    }
+}
@@ -0,0 +1,456 @@
+//! Per-project session lock preventing two ralph instances from running loops
+//! in the same repo at once.
+//!
+//! The lock is `session.lock` in the per-project config dir (next to
+//! `config.toml`), holding JSON describing the owner. It is created
+//! exclusively when a run starts, so two processes can't both take it, and
+//! removed once no tab in the process holds a `SessionLock` for it any more.
+//! A lock written on this host whose pid is no longer alive is treated as
+//! stale and taken over; one from another host is never judged stale, since
+//! its pid can't be checked from here. Takeovers hold an exclusive `flock` on
+//! the config dir and rename the new lock over the old one, so two processes
+//! can't both take over the same stale lock. Tabs within the same process
+//! share the lock rather than blocking each other.
+
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::config::compute_project_config_path;
+
+const LOCK_FILE_NAME: &str = "session.lock";
+/// A lock file too young to have been written yet belongs to a process
+/// that is still taking it, not to one that died.
+const WRITE_GRACE: Duration = Duration::from_secs(2);
+
+/// Lock files this process holds, shared by every tab that takes them.
+static HELD: Mutex<Vec<Weak<LockFile>>> = Mutex::new(Vec::new());
+
+/// Who holds the lock.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockInfo {
+    pub session_id: String,
+    pub pid: u32,
+    pub user: String,
+    pub host: String,
+    /// ISO 8601 UTC timestamp the run started.
+    pub started_at: String,
+}
+
+impl LockInfo {
+    /// Whether the lock was written on this machine, so its pid can be checked.
+    pub fn on_this_host(&self) -> bool {
+        self.host == hostname()
+    }
+
+    /// Describe the lock holder for display, e.g. `alice@laptop (pid 123) since …`.
+    pub fn describe(&self) -> String {
+        format!(
+            "{}@{} (pid {}) since {}",
+            self.user, self.host, self.pid, self.started_at
+        )
+    }
+}
+
+/// Result of trying to take the lock.
+#[derive(Debug)]
+pub enum AcquireError {
+    /// Another live process holds it.
+    Held(LockInfo),
+    /// The lock file couldn't be read or written.
+    Io(String),
+}
+
+/// A lock file this process wrote; removed when the last handle drops.
+#[derive(Debug)]
+struct LockFile {
+    path: PathBuf,
+    pid: u32,
+}
+
+impl Drop for LockFile {
+    fn drop(&mut self) {
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => info!(path = %self.path.display(), "session_lock_released"),
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => warn!(error = %e, "session_lock_release_failed"),
+        }
+    }
+}
+
+/// An acquired lock. The lock file stays until every tab's handle is dropped.
+#[derive(Debug)]
+pub struct SessionLock {
+    _file: Arc<LockFile>,
+    owned: bool,
+}
+
+impl SessionLock {
+    /// Whether this tab wrote the lock (rather than sharing another tab's).
+    pub fn is_owner(&self) -> bool {
+        self.owned
+    }
+}
+
+/// Lock file path for the project in `project_dir`.
+pub fn lock_path(project_dir: &Path) -> Option<PathBuf> {
+    compute_project_config_path(project_dir).map(|p| p.with_file_name(LOCK_FILE_NAME))
+}
+
+/// Read the current lock holder for the project, if any.
+pub fn read(path: &Path) -> Option<LockInfo> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Take the lock on the project in `project_dir` for `session_id`.
+pub fn acquire(project_dir: &Path, session_id: &str) -> Result<SessionLock, AcquireError> {
+    let path =
+        lock_path(project_dir).ok_or_else(|| AcquireError::Io("no project config dir".into()))?;
+    acquire_at(&path, session_id, std::process::id(), is_pid_alive)
+}
+
+/// Testable core of `acquire`: `pid` is ours, `alive` checks a foreign pid.
+fn acquire_at(
+    path: &Path,
+    session_id: &str,
+    pid: u32,
+    alive: impl Fn(u32) -> bool,
+) -> Result<SessionLock, AcquireError> {
+    let mut held = HELD.lock().unwrap_or_else(PoisonError::into_inner);
+    held.retain(|file| file.strong_count() > 0);
+    if let Some(file) = held
+        .iter()
+        .filter_map(Weak::upgrade)
+        .find(|file| file.path == path && file.pid == pid)
+    {
+        // Another tab in this process already holds it; share without owning.
+        return Ok(SessionLock {
+            _file: file,
+            owned: false,
+        });
+    }
+
+    let info = LockInfo {
+        session_id: session_id.to_string(),
+        pid,
+        user: std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".into()),
+        host: hostname(),
+        started_at: crate::agent::epoch_secs_to_iso8601(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        ),
+    };
+    let json = serde_json::to_string_pretty(&info).map_err(|e| AcquireError::Io(e.to_string()))?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| AcquireError::Io(e.to_string()))?;
+    }
+    let lock_file = match create_new(path) {
+        Ok(mut file) => {
+            // Created first so a failed write removes the empty lock again
+            let lock_file = Arc::new(LockFile {
+                path: path.to_path_buf(),
+                pid,
+            });
+            file.write_all(json.as_bytes())
+                .map_err(|e| AcquireError::Io(e.to_string()))?;
+            lock_file
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            take_over(path, &info, &json, &alive)?;
+            Arc::new(LockFile {
+                path: path.to_path_buf(),
+                pid,
+            })
+        }
+        Err(e) => return Err(AcquireError::Io(e.to_string())),
+    };
+
+    info!(path = %path.display(), "session_lock_acquired");
+    held.push(Arc::downgrade(&lock_file));
+    Ok(SessionLock {
+        _file: lock_file,
+        owned: true,
+    })
+}
+
+fn create_new(path: &Path) -> std::io::Result<std::fs::File> {
+    OpenOptions::new().write(true).create_new(true).open(path)
+}
+
+/// Replace the stale lock at `path` with `info`. Holding the config dir's
+/// `flock` while checking and replacing means no other taker can act on the
+/// same stale lock, and the rename means the lock never goes missing for a
+/// plain create to slip in. The owner is read back to confirm.
+fn take_over(
+    path: &Path,
+    info: &LockInfo,
+    json: &str,
+    alive: &impl Fn(u32) -> bool,
+) -> Result<(), AcquireError> {
+    let _guard = path
+        .parent()
+        .map(lock_dir)
+        .transpose()
+        .map_err(|e| AcquireError::Io(e.to_string()))?;
+    check_stale(path, info, alive)?;
+    let tmp = path.with_extension(format!("lock.{}.tmp", info.pid));
+    let replaced = std::fs::write(&tmp, json).and_then(|()| std::fs::rename(&tmp, path));
+    if let Err(e) = replaced {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(AcquireError::Io(e.to_string()));
+    }
+    match read(path) {
+        Some(owner) if owner == *info => Ok(()),
+        Some(owner) => Err(AcquireError::Held(owner)),
+        None => Err(AcquireError::Io(
+            "the lock changed while being taken".into(),
+        )),
+    }
+}
+
+/// Exclusive `flock` on `dir`, released when the returned handle closes.
+#[cfg(unix)]
+fn lock_dir(dir: &Path) -> std::io::Result<std::fs::File> {
+    use std::os::unix::io::AsRawFd;
+
+    let handle = std::fs::File::open(dir)?;
+    if unsafe { libc::flock(handle.as_raw_fd(), libc::LOCK_EX) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(handle)
+}
+
+#[cfg(not(unix))]
+fn lock_dir(_dir: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Fail unless the lock at `path` is stale: its owner is a dead process on
+/// this host, or it is unreadable and not still being written. A lock naming
+/// our own pid that no tab holds was left behind and is stale too.
+fn check_stale(
+    path: &Path,
+    info: &LockInfo,
+    alive: &impl Fn(u32) -> bool,
+) -> Result<(), AcquireError> {
+    match read(path) {
+        Some(existing) if existing.host != info.host => {
+            return Err(AcquireError::Held(existing));
+        }
+        Some(existing) if existing.pid != info.pid && alive(existing.pid) => {
+            return Err(AcquireError::Held(existing));
+        }
+        Some(existing) => {
+            warn!(stale_pid = existing.pid, session = %existing.session_id, "session_lock_stale");
+        }
+        None => {
+            let age = std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok());
+            if age.is_some_and(|age| age < WRITE_GRACE) {
+                return Err(AcquireError::Io("the lock is being taken".into()));
+            }
+            warn!(path = %path.display(), "session_lock_unreadable");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+pub fn is_pid_alive(pid: u32) -> bool {
+    // Signal 0 performs the permission/existence check without sending anything.
+    // EPERM means the process exists but belongs to someone else.
+    let rc = unsafe { libc::kill(pid as libc::pid_t, 0) };
+    rc == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+pub fn is_pid_alive(_pid: u32) -> bool {
+    // No cheap liveness check; assume alive so we never clobber a real run.
+    true
+}
+
+fn hostname() -> String {
+    #[cfg(unix)]
+    {
+        let mut buf = [0u8; 256];
+        let rc = unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) };
+        if rc == 0 {
+            let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+            if let Ok(name) = std::str::from_utf8(&buf[..end]) {
+                return name.to_string();
+            }
+        }
+    }
+    std::env::var("HOSTNAME").unwrap_or_else(|_| "unknown".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock_file() -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(LOCK_FILE_NAME);
+        (dir, path)
+    }
+
+    #[test]
+    fn acquire_writes_lock_and_drop_removes_it() {
+        let (_dir, path) = lock_file();
+        let lock = acquire_at(&path, "s1", 100, |_| true).unwrap();
+        let info = read(&path).unwrap();
+        assert_eq!(info.session_id, "s1");
+        assert_eq!(info.pid, 100);
+
+        drop(lock);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn live_foreign_lock_is_reported() {
+        let (_dir, path) = lock_file();
+        let _held = acquire_at(&path, "s1", 100, |_| true).unwrap();
+
+        match acquire_at(&path, "s2", 200, |_| true) {
+            Err(AcquireError::Held(info)) => assert_eq!(info.session_id, "s1"),
+            other => panic!("expected Held, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn stale_lock_is_taken_over() {
+        let (_dir, path) = lock_file();
+        let stale = acquire_at(&path, "s1", 100, |_| true).unwrap();
+        std::mem::forget(stale); // simulate a crashed process leaving the file
+
+        let _lock = acquire_at(&path, "s2", 200, |_| false).unwrap();
+        assert_eq!(read(&path).unwrap().session_id, "s2");
+    }
+
+    #[test]
+    fn same_process_shares_without_removing() {
+        let (_dir, path) = lock_file();
+        let owner = acquire_at(&path, "s1", 100, |_| true).unwrap();
+
+        let shared = acquire_at(&path, "s1-t2", 100, |_| true).unwrap();
+        drop(shared);
+        assert!(
+            path.exists(),
+            "shared handle must not remove the owner's lock"
+        );
+
+        drop(owner);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn lock_outlives_the_tab_that_wrote_it() {
+        let (_dir, path) = lock_file();
+        let owner = acquire_at(&path, "s1", 100, |_| true).unwrap();
+        let shared = acquire_at(&path, "s1-t2", 100, |_| true).unwrap();
+        assert!(owner.is_owner());
+        assert!(!shared.is_owner());
+
+        drop(owner);
+        assert_eq!(read(&path).unwrap().session_id, "s1");
+        drop(shared);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn lock_left_behind_by_this_pid_is_taken_over() {
+        let (_dir, path) = lock_file();
+        // Written by an earlier run of a process that had the same pid
+        let left = LockInfo {
+            session_id: "s1".into(),
+            pid: 100,
+            user: "alice".into(),
+            host: hostname(),
+            started_at: "2025-01-01T00:00:00Z".into(),
+        };
+        std::fs::write(&path, serde_json::to_string(&left).unwrap()).unwrap();
+
+        let lock = acquire_at(&path, "s2", 100, |_| true).unwrap();
+        assert!(lock.is_owner());
+        assert_eq!(read(&path).unwrap().session_id, "s2");
+    }
+
+    #[test]
+    fn lock_from_another_host_is_never_stale() {
+        let (_dir, path) = lock_file();
+        let remote = LockInfo {
+            session_id: "s1".into(),
+            pid: 100,
+            user: "alice".into(),
+            host: format!("not-{}", hostname()),
+            started_at: "2025-01-01T00:00:00Z".into(),
+        };
+        std::fs::write(&path, serde_json::to_string(&remote).unwrap()).unwrap();
+
+        match acquire_at(&path, "s2", 200, |_| false) {
+            Err(AcquireError::Held(info)) => assert_eq!(info, remote),
+            other => panic!("expected Held, got {other:?}"),
+        }
+        assert_eq!(read(&path), Some(remote));
+    }
+
+    #[test]
+    fn stale_lock_is_taken_over_once() {
+        let (_dir, path) = lock_file();
+        std::mem::forget(acquire_at(&path, "s0", 100, |_| true).unwrap());
+
+        // Both takers find 100 dead; the second must see the first's lock
+        let first = acquire_at(&path, "s1", 200, |pid| pid != 100).unwrap();
+        match acquire_at(&path, "s2", 300, |pid| pid != 100) {
+            Err(AcquireError::Held(info)) => assert_eq!(info.session_id, "s1"),
+            other => panic!("expected Held, got {other:?}"),
+        }
+        assert!(first.is_owner());
+        assert!(!path.with_extension("lock.200.tmp").exists());
+    }
+
+    #[test]
+    fn freshly_created_empty_lock_is_not_taken_over() {
+        let (_dir, path) = lock_file();
+        std::fs::write(&path, "").unwrap();
+        assert!(matches!(
+            acquire_at(&path, "s1", 100, |_| false),
+            Err(AcquireError::Io(_))
+        ));
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn describe_includes_owner_details() {
+        let info = LockInfo {
+            session_id: "s".into(),
+            pid: 42,
+            user: "alice".into(),
+            host: "box".into(),
+            started_at: "2025-01-01T00:00:00Z".into(),
+        };
+        assert_eq!(
+            info.describe(),
+            "alice@box (pid 42) since 2025-01-01T00:00:00Z"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn own_pid_is_alive() {
+        assert!(is_pid_alive(std::process::id()));
+    }
+}
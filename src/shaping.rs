@@ -0,0 +1,53 @@
+//! Interactive spec shaping — hands the terminal to `claude /shape` and back.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+
+use anyhow::Result;
+use ratatui::DefaultTerminal;
+use tracing::{info, warn};
+
+use crate::config::NetworkConfig;
+use crate::suspend;
+
+/// Slash command that starts the shaping interview.
+const SHAPE_COMMAND: &str = "/shape";
+
+/// Skill file that provides [`SHAPE_COMMAND`], relative to the project root.
+const SHAPE_SKILL_PATH: &str = ".claude/skills/shape/SKILL.md";
+
+/// True when the project has the shape skill installed.
+pub fn shape_skill_installed() -> bool {
+    PathBuf::from(SHAPE_SKILL_PATH).exists()
+}
+
+/// Suspend the TUI, run an interactive `claude /shape` session, then restore it.
+pub fn run_shaping_session(
+    terminal: &mut DefaultTerminal,
+    claude_path: &Path,
+    network: &NetworkConfig,
+) -> Result<()> {
+    info!(claude = %claude_path.display(), "shaping_session_start");
+    match suspend::suspended(terminal, || run_claude(claude_path, network))? {
+        Ok(status) => {
+            info!(code = ?status.code(), "shaping_session_end");
+            Ok(())
+        }
+        Err(e) => {
+            warn!(error = %e, "shaping_session_failed");
+            Err(e.into())
+        }
+    }
+}
+
+/// Spawned through `sh` like the worker command, with the path passed as `$0`
+/// so it needs no quoting.
+fn run_claude(claude_path: &Path, network: &NetworkConfig) -> io::Result<ExitStatus> {
+    Command::new("sh")
+        .arg("-c")
+        .arg(format!("exec \"$0\" {SHAPE_COMMAND}"))
+        .arg(claude_path)
+        .envs(network.child_env())
+        .status()
+}
@@ -0,0 +1,76 @@
+//! `sh -c` commands whose output is shown as it arrives: the re-run modal and
+//! the `[hooks]` iteration commands.
+//!
+//! The command runs in its own process group, so killing it with
+//! [`crate::control::kill_process`] also stops what the shell started.
+
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+/// A line of output from the command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputLine {
+    pub text: String,
+    pub stderr: bool,
+}
+
+/// Start `command` in `cwd` (the current directory if `None`). Its stdout and
+/// stderr lines arrive on the receiver, which disconnects once both close.
+pub fn spawn(command: &str, cwd: Option<&Path>) -> io::Result<(Child, Receiver<OutputLine>)> {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(cwd) = cwd {
+        cmd.current_dir(cwd);
+    }
+    crate::control::own_process_group(&mut cmd);
+    let mut child = cmd.spawn()?;
+
+    let (tx, rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        forward_lines(stdout, false, tx.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_lines(stderr, true, tx);
+    }
+    Ok((child, rx))
+}
+
+/// Forward each line from `source` to `tx` on a background thread.
+fn forward_lines(source: impl Read + Send + 'static, stderr: bool, tx: Sender<OutputLine>) {
+    thread::spawn(move || {
+        for text in BufReader::new(source).lines().map_while(Result::ok) {
+            if tx.send(OutputLine { text, stderr }).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streams_are_labelled_and_close_with_the_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut child, rx) = spawn("pwd; echo oops >&2", Some(dir.path())).unwrap();
+        child.wait().unwrap();
+        let lines: Vec<OutputLine> = rx.iter().collect();
+        let cwd = dir.path().canonicalize().unwrap();
+        assert!(lines.contains(&OutputLine {
+            text: cwd.display().to_string(),
+            stderr: false,
+        }));
+        assert!(lines.contains(&OutputLine {
+            text: "oops".into(),
+            stderr: true,
+        }));
+    }
+}
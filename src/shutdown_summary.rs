@@ -0,0 +1,303 @@
+//! How the previous session ended, for "what happened last night?".
+//!
+//! Every exit writes `last-session.json` next to the config of each project
+//! with a tab open: the reason (quit, the run ending after a detach, a
+//! signal, an error or a panic), the run state at the time, and the counters
+//! of that project's tabs. The next launch on the project shows it on the
+//! startup summary. A panic can't reach the `App`, so the event loop keeps a
+//! snapshot of the tabs for the panic hook to write.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::app::App;
+use crate::config::compute_project_config_path;
+use crate::run_status::state_label;
+
+const FILE_NAME: &str = "last-session.json";
+
+/// Signal caught by [`catch_signals`], or 0.
+static CAUGHT_SIGNAL: AtomicI32 = AtomicI32::new(0);
+
+/// The running tabs as of the last event loop pass, by project, for the
+/// panic hook.
+static LATEST: Mutex<Vec<(PathBuf, ShutdownSummary)>> = Mutex::new(Vec::new());
+
+/// Why the session ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExitReason {
+    /// The user quit.
+    Quit,
+    /// A detached or daemon run finished on its own.
+    RunEnded,
+    /// SIGTERM, SIGHUP or SIGINT (by number).
+    Signal(i32),
+    Error(String),
+    Panic(String),
+}
+
+impl ExitReason {
+    pub fn describe(&self) -> String {
+        match self {
+            ExitReason::Quit => "quit".to_string(),
+            ExitReason::RunEnded => "run ended".to_string(),
+            ExitReason::Signal(signal) => format!("stopped by {}", signal_name(*signal)),
+            ExitReason::Error(e) => format!("error: {e}"),
+            ExitReason::Panic(message) => format!("crashed: {message}"),
+        }
+    }
+
+    /// Whether the session ended the way it was meant to.
+    pub fn clean(&self) -> bool {
+        matches!(self, ExitReason::Quit | ExitReason::RunEnded)
+    }
+}
+
+fn signal_name(signal: i32) -> String {
+    #[cfg(unix)]
+    match signal {
+        libc::SIGTERM => return "SIGTERM".to_string(),
+        libc::SIGHUP => return "SIGHUP".to_string(),
+        libc::SIGINT => return "SIGINT".to_string(),
+        _ => {}
+    }
+    format!("signal {signal}")
+}
+
+/// What `last-session.json` holds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShutdownSummary {
+    pub session_id: String,
+    pub pid: u32,
+    /// Unix seconds when the session ended.
+    pub ended_at: u64,
+    /// [`ExitReason::describe`].
+    pub reason: String,
+    pub clean: bool,
+    /// The run state at exit (see `run_status::state_label`); the first tab
+    /// that wasn't idle decides.
+    pub state: String,
+    pub iterations: u32,
+    pub cost_usd: f64,
+    /// Beads claimed when the session ended.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub beads: Vec<String>,
+    pub tabs: usize,
+}
+
+impl ShutdownSummary {
+    /// One summary per project among `apps`, in tab order, each keyed by
+    /// the project directory.
+    pub fn capture_by_project(apps: &[App], reason: &ExitReason) -> Vec<(PathBuf, Self)> {
+        let mut dirs: Vec<&Path> = Vec::new();
+        for app in apps {
+            if !dirs.contains(&app.project_dir.as_path()) {
+                dirs.push(&app.project_dir);
+            }
+        }
+        dirs.into_iter()
+            .map(|dir| {
+                let tabs: Vec<&App> = apps.iter().filter(|a| a.project_dir == dir).collect();
+                (dir.to_path_buf(), Self::capture(&tabs, reason))
+            })
+            .collect()
+    }
+
+    /// Totals across `apps`, ending for `reason` now.
+    fn capture(apps: &[&App], reason: &ExitReason) -> Self {
+        let state = apps
+            .iter()
+            .map(|a| state_label(a))
+            .find(|s| *s != "idle")
+            .unwrap_or("idle");
+        Self {
+            session_id: apps
+                .first()
+                .map(|a| a.session_id.clone())
+                .unwrap_or_default(),
+            pid: std::process::id(),
+            ended_at: unix_now(),
+            reason: reason.describe(),
+            clean: reason.clean(),
+            state: state.to_string(),
+            iterations: apps.iter().map(|a| a.exchange_count).sum(),
+            cost_usd: apps.iter().map(|a| a.session_cost_usd).sum(),
+            beads: apps
+                .iter()
+                .flat_map(|a| &a.workers)
+                .filter_map(|w| w.hooked_bead_id.clone())
+                .collect(),
+            tabs: apps.len(),
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn summary_path(project_dir: &Path) -> Option<PathBuf> {
+    compute_project_config_path(project_dir).map(|p| p.with_file_name(FILE_NAME))
+}
+
+/// Remember `apps` as they are now, in case the process panics.
+pub fn record(apps: &[App]) {
+    if let Ok(mut latest) = LATEST.lock() {
+        *latest = ShutdownSummary::capture_by_project(apps, &ExitReason::Quit);
+    }
+}
+
+/// Write `summary` as the last session of the project in `project_dir`.
+pub fn write(project_dir: &Path, summary: &ShutdownSummary) {
+    let Some(path) = summary_path(project_dir) else {
+        return;
+    };
+    match write_to(&path, summary) {
+        Ok(()) => info!(reason = %summary.reason, "shutdown_summary_written"),
+        Err(e) => warn!(error = %e, "shutdown_summary_write_failed"),
+    }
+}
+
+fn write_to(path: &Path, summary: &ShutdownSummary) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(summary).map_err(std::io::Error::other)?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json)?;
+    std::fs::rename(&tmp, path)
+}
+
+fn read_from(path: &Path) -> Option<ShutdownSummary> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+/// How the previous session in the project in `project_dir` ended.
+pub fn load(project_dir: &Path) -> Option<ShutdownSummary> {
+    read_from(&summary_path(project_dir)?)
+}
+
+/// Write the last recorded snapshot as a crash before the default panic
+/// handler runs.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic| {
+        let message = panic
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic".to_string());
+        let message = match panic.location() {
+            Some(at) => format!("{message} at {}:{}", at.file(), at.line()),
+            None => message,
+        };
+        // A panic while the snapshot is locked must not deadlock here
+        if let Ok(mut latest) = LATEST.try_lock() {
+            let reason = ExitReason::Panic(message);
+            for (project_dir, mut summary) in std::mem::take(&mut *latest) {
+                summary.ended_at = unix_now();
+                summary.reason = reason.describe();
+                summary.clean = false;
+                write(&project_dir, &summary);
+            }
+        }
+        default_hook(panic);
+    }));
+}
+
+/// Record `signals` instead of dying from them; the loops check
+/// [`caught_signal`] and shut down normally.
+#[cfg(unix)]
+pub fn catch_signals(signals: &[libc::c_int]) {
+    extern "C" fn record_signal(signal: libc::c_int) {
+        CAUGHT_SIGNAL.store(signal, Ordering::Relaxed);
+    }
+    for &signal in signals {
+        // SAFETY: the handler only stores to an atomic, which is signal-safe.
+        unsafe {
+            libc::signal(signal, record_signal as *const () as libc::sighandler_t);
+        }
+    }
+}
+
+/// The signal [`catch_signals`] caught, if any.
+pub fn caught_signal() -> Option<i32> {
+    match CAUGHT_SIGNAL.load(Ordering::Relaxed) {
+        0 => None,
+        signal => Some(signal),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_quitting_and_finished_runs_are_clean() {
+        assert!(ExitReason::Quit.clean());
+        assert!(ExitReason::RunEnded.clean());
+        assert!(!ExitReason::Error("boom".into()).clean());
+        assert!(!ExitReason::Panic("boom".into()).clean());
+        #[cfg(unix)]
+        {
+            let signal = ExitReason::Signal(libc::SIGTERM);
+            assert!(!signal.clean());
+            assert_eq!(signal.describe(), "stopped by SIGTERM");
+        }
+    }
+
+    #[test]
+    fn summary_round_trips_through_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("project").join(FILE_NAME);
+        let summary = ShutdownSummary {
+            session_id: "abc".into(),
+            pid: 42,
+            ended_at: 1_700_000_000,
+            reason: ExitReason::Error("bd not found".into()).describe(),
+            clean: false,
+            state: "running".into(),
+            iterations: 7,
+            cost_usd: 1.25,
+            beads: vec!["ralph-x1".into()],
+            tabs: 1,
+        };
+        write_to(&path, &summary).unwrap();
+        assert_eq!(read_from(&path), Some(summary));
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+
+    #[test]
+    fn each_project_gets_the_totals_of_its_own_tabs() {
+        let app = |dir: &str, iterations| {
+            let mut app = App::new(
+                "abc".into(),
+                None,
+                crate::config::LoadedConfig::default_for_test(),
+                None,
+            );
+            app.project_dir = PathBuf::from(dir);
+            app.exchange_count = iterations;
+            app
+        };
+        let apps = [app("/a", 1), app("/b", 2), app("/a", 4)];
+        let summaries = ShutdownSummary::capture_by_project(&apps, &ExitReason::Quit);
+        let totals: Vec<(PathBuf, u32, usize)> = summaries
+            .into_iter()
+            .map(|(dir, s)| (dir, s.iterations, s.tabs))
+            .collect();
+        assert_eq!(
+            totals,
+            [(PathBuf::from("/a"), 5, 2), (PathBuf::from("/b"), 2, 1)]
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! Acceptance-criteria checkboxes on the spec a worker just iterated on.
+//!
+//! Agents often check off every criterion and then forget to close the bead,
+//! so the loop keeps handing the finished spec back. At the end of each
+//! iteration ralph reads the hooked bead's Markdown checkboxes (nested ones
+//! included) and, if they're all checked while the bead is still open,
+//! flags the mismatch and offers to mark it Done. The same offer is made when
+//! the agent's `ralph-report` says the spec is complete.
+
+use std::process::Stdio;
+use std::sync::mpsc::{Receiver, channel};
+use std::thread;
+
+use serde_json::Value;
+
+use crate::bd::Bd;
+
+/// Bead fields that hold acceptance criteria.
+const CHECKLIST_FIELDS: [&str; 2] = ["description", "acceptance_criteria"];
+
+/// How many of a spec's checkboxes are checked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChecklistProgress {
+    pub checked: usize,
+    pub total: usize,
+}
+
+impl ChecklistProgress {
+    /// Every checkbox is checked (and there is at least one).
+    pub fn is_complete(&self) -> bool {
+        self.total > 0 && self.checked == self.total
+    }
+}
+
+/// Count the `- [ ]` / `- [x]` task-list items in `text`, at any nesting depth.
+pub fn checklist_progress(text: &str) -> ChecklistProgress {
+    let mut progress = ChecklistProgress::default();
+    for line in text.lines() {
+        let item = line.trim_start();
+        let Some(rest) = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|bullet| item.strip_prefix(bullet))
+        else {
+            continue;
+        };
+        match rest.get(..3) {
+            Some("[ ]") => progress.total += 1,
+            Some("[x]" | "[X]") => {
+                progress.total += 1;
+                progress.checked += 1;
+            }
+            _ => {}
+        }
+    }
+    progress
+}
+
+/// A spec that looks done but is still open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnclosedSpec {
+    pub worker: usize,
+    pub bead_id: String,
+    pub title: String,
+    pub status: String,
+    pub progress: ChecklistProgress,
+    /// The agent's report said the spec is complete.
+    pub reported_done: bool,
+}
+
+impl UnclosedSpec {
+    /// Why the spec looks done, e.g. `All 3 acceptance criteria in bd-7 are checked`.
+    pub fn reason(&self) -> String {
+        if self.progress.is_complete() {
+            format!(
+                "All {} acceptance criteria in {} are checked",
+                self.progress.total, self.bead_id
+            )
+        } else {
+            format!("Claude reported {} complete", self.bead_id)
+        }
+    }
+}
+
+/// Check a `bd show --json` bead for an unclosed spec that has a complete
+/// checklist or that the agent reported done.
+pub fn unclosed_spec(worker: usize, bead: &Value, reported_done: bool) -> Option<UnclosedSpec> {
+    let field = |name: &str| bead.get(name).and_then(Value::as_str).unwrap_or("");
+    let status = field("status");
+    if status == "closed" {
+        return None;
+    }
+    let mut progress = ChecklistProgress::default();
+    for name in CHECKLIST_FIELDS {
+        let found = checklist_progress(field(name));
+        progress.checked += found.checked;
+        progress.total += found.total;
+    }
+    (progress.is_complete() || reported_done).then(|| UnclosedSpec {
+        worker,
+        bead_id: field("id").to_string(),
+        title: field("title").to_string(),
+        status: status.to_string(),
+        progress,
+        reported_done,
+    })
+}
+
+/// Fetch `bead_id` in the background and report it if it looks done but isn't closed.
+pub fn spawn_check(
+    bd: Bd,
+    bead_id: String,
+    worker: usize,
+    reported_done: bool,
+) -> Receiver<Option<UnclosedSpec>> {
+    let (tx, rx) = channel();
+    thread::spawn(move || {
+        let output = crate::bd_lock::with_lock(|| {
+            bd.command()
+                .args(["show", &bead_id, "--json"])
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .output()
+        });
+        let found = output
+            .ok()
+            .filter(|o| o.status.success())
+            .and_then(|o| serde_json::from_slice::<Value>(&o.stdout).ok())
+            .and_then(|val| {
+                let bead = val.as_array().and_then(|arr| arr.first()).unwrap_or(&val);
+                unclosed_spec(worker, bead, reported_done)
+            });
+        let _ = tx.send(found);
+    });
+    rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn counts_nested_checkboxes() {
+        let text = "## Acceptance\n- [x] parses input\n  - [X] nested edge case\n* [ ] docs\n- not a task\n-[x] no space\n";
+        assert_eq!(
+            checklist_progress(text),
+            ChecklistProgress {
+                checked: 2,
+                total: 3
+            }
+        );
+        assert!(!checklist_progress("no boxes").is_complete());
+    }
+
+    #[test]
+    fn only_complete_unclosed_specs_are_reported() {
+        let bead = json!({
+            "id": "bd-7",
+            "title": "Add parser",
+            "status": "in_progress",
+            "description": "- [x] one",
+            "acceptance_criteria": "- [x] two\n  - [x] three",
+        });
+        let spec = unclosed_spec(1, &bead, false).unwrap();
+        assert_eq!(spec.bead_id, "bd-7");
+        assert_eq!(spec.worker, 1);
+        assert_eq!(spec.progress.total, 3);
+        assert_eq!(
+            spec.reason(),
+            "All 3 acceptance criteria in bd-7 are checked"
+        );
+
+        let mut closed = bead.clone();
+        closed["status"] = json!("closed");
+        assert_eq!(unclosed_spec(1, &closed, true), None);
+
+        let mut pending = bead;
+        pending["acceptance_criteria"] = json!("- [ ] two");
+        assert_eq!(unclosed_spec(1, &pending, false), None);
+
+        // The agent's report is enough on its own
+        let reported = unclosed_spec(1, &pending, true).unwrap();
+        assert!(reported.reported_done);
+        assert_eq!(reported.reason(), "Claude reported bd-7 complete");
+    }
+}
@@ -0,0 +1,230 @@
+//! Agent-friendliness checks for spec text (`ralph spec lint`).
+//!
+//! An agent working from a spec can't ask clarifying questions, so a spec that
+//! doesn't say what done looks like, skips error handling, bundles too much
+//! work, or leans on other beads it never links wastes iterations. The checks
+//! follow the shape of a child bead in the shape skill: a description with
+//! `## Approach`, `## Edge Cases`, `## Acceptance` and `## Tests` sections.
+
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde_json::Value;
+
+use crate::bd::Bd;
+use crate::spec_checklist::checklist_progress;
+
+/// Acceptance items beyond which a spec is probably more than one slice.
+const MAX_ACCEPTANCE_ITEMS: usize = 8;
+/// Words beyond which a spec is probably more than one slice.
+const MAX_WORDS: usize = 700;
+
+/// Words that show a spec considers failure at all.
+const ERROR_WORDS: [&str; 6] = ["error", "fail", "invalid", "edge case", "reject", "timeout"];
+
+/// A bead ID after a phrase that makes it a prerequisite, e.g. `after bd-12`.
+static DEPENDENCY_REF: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(?:depends on|blocked by|requires|needs|waits for|after)\s+`?([a-z][a-z0-9]*-[a-z0-9]+(?:\.[0-9]+)*)",
+    )
+    .expect("dependency pattern is valid")
+});
+
+/// Which check a finding comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    NoAcceptance,
+    NoErrorCases,
+    TooLarge,
+    UncheckedDependency,
+}
+
+/// One problem with a spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub rule: Rule,
+    pub message: String,
+}
+
+/// Lint `text`. `check_dependency` is asked about every bead the text names as
+/// a prerequisite and returns a problem to report, if any.
+pub fn lint(text: &str, check_dependency: impl Fn(&str) -> Option<String>) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut push = |rule, message: String| findings.push(Finding { rule, message });
+
+    let acceptance = section_items(text, "acceptance");
+    let checkboxes = checklist_progress(text).total;
+    if acceptance.is_none_or(|n| n == 0) && checkboxes == 0 {
+        push(
+            Rule::NoAcceptance,
+            "No acceptance criteria: add an `## Acceptance` list of testable conditions".into(),
+        );
+    }
+
+    let lower = text.to_lowercase();
+    if !ERROR_WORDS.iter().any(|w| lower.contains(w)) {
+        push(
+            Rule::NoErrorCases,
+            "No error cases: say what happens on bad input or failure (`## Edge Cases`)".into(),
+        );
+    }
+
+    let items = acceptance.unwrap_or(0).max(checkboxes);
+    let words = text.split_whitespace().count();
+    if items > MAX_ACCEPTANCE_ITEMS {
+        push(
+            Rule::TooLarge,
+            format!(
+                "{items} acceptance criteria (over {MAX_ACCEPTANCE_ITEMS}): probably more than one slice"
+            ),
+        );
+    } else if words > MAX_WORDS {
+        push(
+            Rule::TooLarge,
+            format!("{words} words (over {MAX_WORDS}): probably more than one slice"),
+        );
+    }
+
+    for id in dependency_refs(text) {
+        if let Some(problem) = check_dependency(&id) {
+            push(Rule::UncheckedDependency, problem);
+        }
+    }
+    findings
+}
+
+/// Beads `text` names as prerequisites, in order of first mention.
+pub fn dependency_refs(text: &str) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for caps in DEPENDENCY_REF.captures_iter(text) {
+        let id = caps[1].to_lowercase();
+        // Bead hashes and numbers have digits; `needs real-time` isn't a reference
+        let suffix = id.split_once('-').map_or("", |(_, s)| s);
+        if suffix.contains(|c: char| c.is_ascii_digit()) && !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+/// List items under the first heading containing `name`, or `None` without
+/// such a heading.
+fn section_items(text: &str, name: &str) -> Option<usize> {
+    let mut lines = text.lines();
+    lines.find(|l| l.starts_with('#') && l.to_lowercase().contains(name))?;
+    Some(
+        lines
+            .take_while(|l| !l.starts_with('#'))
+            .filter(|l| {
+                let item = l.trim_start();
+                ["- ", "* ", "+ "].iter().any(|b| item.starts_with(b))
+                    || item
+                        .split_once(". ")
+                        .is_some_and(|(n, _)| n.parse::<u32>().is_ok())
+            })
+            .count(),
+    )
+}
+
+/// Run `ralph spec lint <file>`: print findings and exit 1 if there are any.
+pub fn run(file: &Path) -> Result<()> {
+    let text = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let bd = Bd::in_cwd(crate::config::load_config().config.behavior.bd_path);
+    let findings = lint(&text, |id| check_with_bd(&bd, id));
+
+    if findings.is_empty() {
+        println!("\u{2713} {}: no problems found", file.display());
+        return Ok(());
+    }
+    for finding in &findings {
+        println!("\u{2717} {}: {}", file.display(), finding.message);
+    }
+    std::process::exit(1);
+}
+
+/// A prerequisite in a spec file only holds if the bead exists, and an open one
+/// should be a real `bd dep` so the loop waits for it.
+fn check_with_bd(bd: &Bd, id: &str) -> Option<String> {
+    let output = crate::bd_lock::with_lock(|| {
+        bd.command()
+            .args(["show", id, "--json"])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+    })
+    .ok()?;
+    if !output.status.success() {
+        return Some(format!("References {id}, which bd doesn't know"));
+    }
+    let val: Value = serde_json::from_slice(&output.stdout).ok()?;
+    let bead = val.as_array().and_then(|arr| arr.first()).unwrap_or(&val);
+    let status = bead.get("status").and_then(Value::as_str).unwrap_or("");
+    (status != "closed").then(|| {
+        format!("Depends on {id}, which is still {status}: record it with `bd dep add` so the loop waits for it")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHAPED: &str = "Parse nested lists.\n\n## Approach\nExtend the parser.\n\n\
+                          ## Edge Cases\nInvalid indentation is an error.\n\n\
+                          ## Acceptance\n- Nested lists parse\n- Bad indent reports the line\n\n\
+                          ## Tests\nUnit tests in parser.rs.\n";
+
+    #[test]
+    fn shaped_spec_passes() {
+        assert_eq!(lint(SHAPED, |_| None), vec![]);
+    }
+
+    #[test]
+    fn flags_missing_sections_and_size() {
+        let rules = |text: &str| {
+            lint(text, |_| None)
+                .into_iter()
+                .map(|f| f.rule)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            rules("Make the parser better."),
+            vec![Rule::NoAcceptance, Rule::NoErrorCases]
+        );
+        // An empty Acceptance heading doesn't count; checkboxes elsewhere do
+        assert_eq!(
+            rules("## Acceptance\n\n## Edge Cases\nerrors"),
+            vec![Rule::NoAcceptance]
+        );
+        assert_eq!(rules("- [ ] parses\n\nfails cleanly"), vec![]);
+
+        let many = (0..9).map(|i| format!("- case {i}\n")).collect::<String>();
+        assert_eq!(
+            rules(&format!("## Acceptance\n{many}\nerror handling")),
+            vec![Rule::TooLarge]
+        );
+        let long = format!("{SHAPED}{}", "word ".repeat(MAX_WORDS));
+        assert_eq!(rules(&long), vec![Rule::TooLarge]);
+    }
+
+    #[test]
+    fn dependency_references_need_an_id_with_digits() {
+        let text = "Depends on bd-12. Do this after `ralph-a3f.2` lands; needs real-time \
+                    updates and blocked by BD-12 again.";
+        assert_eq!(dependency_refs(text), vec!["bd-12", "ralph-a3f.2"]);
+
+        let findings = lint(&format!("{SHAPED}\nRequires bd-7."), |id| {
+            Some(format!("{id} unchecked"))
+        });
+        assert_eq!(
+            findings,
+            vec![Finding {
+                rule: Rule::UncheckedDependency,
+                message: "bd-7 unchecked".into()
+            }]
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Per-spec prompt files.
+//!
+//! Some work needs a different workflow than PROMPT.md describes (a
+//! refactor, a docs pass). A spec can name its own prompt in front matter at
+//! the top of its bead description:
+//!
+//! ```text
+//! ---
+//! prompt: prompts/refactor.md
+//! ---
+//! ```
+//!
+//! Relative paths are resolved against the per-project config directory,
+//! next to PROMPT.md. Iterations that claim the bead send that file in place
+//! of PROMPT.md.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use serde_json::Value;
+use tracing::warn;
+
+use crate::bd::Bd;
+use crate::config::compute_project_config_path;
+
+/// The prompt a spec asks for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpecPrompt {
+    Found(PathBuf),
+    /// Named in the front matter but not on disk.
+    Missing(PathBuf),
+}
+
+/// The `prompt:` value in `description`'s leading `---` front matter.
+pub fn front_matter_prompt(description: &str) -> Option<&str> {
+    let mut lines = description.trim_start().lines();
+    if lines.next()?.trim_end() != "---" {
+        return None;
+    }
+    lines
+        .take_while(|l| l.trim_end() != "---")
+        .find_map(|l| l.strip_prefix("prompt:"))
+        .map(|v| v.trim().trim_matches(['"', '\'']))
+        .filter(|v| !v.is_empty())
+}
+
+/// `value` as a path, relative ones taken from `config_dir`.
+pub fn resolve(value: &str, config_dir: Option<&Path>) -> PathBuf {
+    let path = Path::new(value);
+    match config_dir {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Look up the prompt `bead_id` names, if any.
+pub fn for_bead(bd: &Bd, bead_id: &str) -> Option<SpecPrompt> {
+    let output = crate::bd_lock::with_lock(|| {
+        bd.command()
+            .args(["show", bead_id, "--json"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .output()
+    });
+    let bead = match output {
+        Ok(o) if o.status.success() => serde_json::from_slice::<Value>(&o.stdout).ok()?,
+        _ => {
+            warn!(bead_id, "spec_prompt_fetch_failed");
+            return None;
+        }
+    };
+    let bead = bead.as_array().and_then(|arr| arr.first()).unwrap_or(&bead);
+    let description = bead.get("description").and_then(Value::as_str)?;
+    let value = front_matter_prompt(description)?;
+    let config_dir =
+        compute_project_config_path(bd.dir()).and_then(|p| p.parent().map(PathBuf::from));
+    let path = resolve(value, config_dir.as_deref());
+    Some(if path.is_file() {
+        SpecPrompt::Found(path)
+    } else {
+        SpecPrompt::Missing(path)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_prompt_from_leading_front_matter() {
+        let description = "---\ntitle: x\nprompt: \"prompts/refactor.md\"\n---\n## Approach\n";
+        assert_eq!(
+            front_matter_prompt(description),
+            Some("prompts/refactor.md")
+        );
+    }
+
+    #[test]
+    fn ignores_prompt_outside_front_matter() {
+        assert_eq!(front_matter_prompt("prompt: a.md\n"), None);
+        assert_eq!(
+            front_matter_prompt("---\ntitle: x\n---\nprompt: a.md\n"),
+            None
+        );
+        assert_eq!(front_matter_prompt("---\nprompt:\n---\n"), None);
+    }
+
+    #[test]
+    fn relative_paths_resolve_against_the_config_dir() {
+        let dir = Path::new("/cfg/project");
+        assert_eq!(
+            resolve("prompts/a.md", Some(dir)),
+            PathBuf::from("/cfg/project/prompts/a.md")
+        );
+        assert_eq!(resolve("/abs/a.md", Some(dir)), PathBuf::from("/abs/a.md"));
+    }
+}
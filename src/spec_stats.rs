@@ -0,0 +1,171 @@
+//! Per-spec totals, reported by `ralph specs`.
+//!
+//! Each finished iteration's tokens and cost are attributed to the bead its
+//! worker had claimed. A spec can take several iterations (and sessions)
+//! before Claude closes it, so totals accumulate by bead ID until ralph sees
+//! the bead closed and stamps it complete. The data lives in the stats
+//! database (see `stats`).
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::number_format::NumberFormat;
+use crate::ui::format_elapsed;
+
+/// What one spec has cost so far.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpecStats {
+    /// Bead title, filled in when the spec completes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub iterations: u32,
+    pub tokens: u64,
+    pub cost_usd: f64,
+    /// Wall-clock time of the attributed iterations.
+    #[serde(default)]
+    pub duration_ms: u64,
+    /// Unix seconds when ralph saw the bead closed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<u64>,
+}
+
+/// Specs ordered by cost, most expensive first.
+fn by_cost(all: &BTreeMap<String, SpecStats>) -> Vec<(&String, &SpecStats)> {
+    let mut specs: Vec<_> = all.iter().collect();
+    specs.sort_by(|a, b| b.1.cost_usd.total_cmp(&a.1.cost_usd));
+    specs
+}
+
+/// Format per-spec totals as a human-readable table.
+pub fn format_table(all: &BTreeMap<String, SpecStats>, numbers: &NumberFormat) -> String {
+    if all.is_empty() {
+        return "No spec stats recorded yet.".to_string();
+    }
+
+    let row = |id: &str, iterations: &str, tokens: &str, cost: &str, time: &str, status: &str| {
+        format!("{id:<14} {iterations:>5} {tokens:>12} {cost:>9} {time:>9}  {status:<6}")
+    };
+    let mut lines = vec![
+        row("SPEC", "ITER", "TOKENS", "COST", "TIME", "STATUS") + " TITLE",
+        "─".repeat(75),
+    ];
+    let mut total = SpecStats::default();
+    for (id, stats) in by_cost(all) {
+        let status = if stats.completed_at.is_some() {
+            "done"
+        } else {
+            "open"
+        };
+        lines.push(
+            row(
+                id,
+                &stats.iterations.to_string(),
+                &numbers.count(stats.tokens),
+                &numbers.cost(stats.cost_usd),
+                &format_elapsed(Duration::from_millis(stats.duration_ms)),
+                status,
+            ) + " "
+                + stats.title.as_deref().unwrap_or(""),
+        );
+        total.iterations += stats.iterations;
+        total.tokens += stats.tokens;
+        total.cost_usd += stats.cost_usd;
+        total.duration_ms += stats.duration_ms;
+    }
+    lines.push("─".repeat(75));
+    lines.push(row(
+        &format!("{} spec(s)", all.len()),
+        &total.iterations.to_string(),
+        &numbers.count(total.tokens),
+        &numbers.cost(total.cost_usd),
+        &format_elapsed(Duration::from_millis(total.duration_ms)),
+        "",
+    ));
+    lines.join("\n")
+}
+
+/// Format per-spec totals as JSON, one object per spec with its `id`.
+pub fn format_json(all: &BTreeMap<String, SpecStats>) -> Result<String> {
+    let specs = by_cost(all)
+        .into_iter()
+        .map(|(id, stats)| {
+            let mut value = serde_json::to_value(stats)?;
+            value["id"] = serde_json::Value::from(id.as_str());
+            Ok(value)
+        })
+        .collect::<serde_json::Result<Vec<_>>>()
+        .context("Failed to serialize spec stats")?;
+    serde_json::to_string_pretty(&specs).context("Failed to serialize to JSON")
+}
+
+/// Run the specs subcommand.
+pub fn run(json: bool) -> Result<()> {
+    let conn = crate::db::open()?;
+    let all = crate::stats::spec_totals(
+        &conn,
+        &crate::db::detect_repo_path(&crate::config::startup_dir()),
+    )?;
+    if json {
+        println!("{}", format_json(&all)?);
+    } else {
+        let numbers = NumberFormat::from(&crate::config::load_config().config.display);
+        println!("{}", format_table(&all, &numbers));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_orders_by_cost_and_totals() {
+        let mut all = BTreeMap::new();
+        all.insert(
+            "bd-cheap".to_string(),
+            SpecStats {
+                iterations: 1,
+                tokens: 100,
+                cost_usd: 0.1,
+                duration_ms: 5_000,
+                ..Default::default()
+            },
+        );
+        all.insert(
+            "bd-big".to_string(),
+            SpecStats {
+                title: Some("Big one".into()),
+                iterations: 4,
+                tokens: 9_000,
+                cost_usd: 2.0,
+                duration_ms: 125_000,
+                completed_at: Some(1),
+            },
+        );
+
+        let table = format_table(&all, &NumberFormat::default());
+        let rows: Vec<&str> = table.lines().collect();
+        assert!(rows[2].starts_with("bd-big"));
+        assert!(rows[2].contains("$2.00") && rows[2].contains("2:05"));
+        assert!(rows[2].contains("done") && rows[2].ends_with("Big one"));
+        assert!(rows[3].starts_with("bd-cheap") && rows[3].contains("open"));
+        assert!(rows[5].starts_with("2 spec(s)") && rows[5].contains("$2.10"));
+
+        let json: Vec<serde_json::Value> =
+            serde_json::from_str(&format_json(&all).unwrap()).unwrap();
+        assert_eq!(json[0]["id"], "bd-big");
+        assert_eq!(json[0]["iterations"], 4);
+        assert_eq!(json[1]["duration_ms"], 5_000);
+    }
+
+    #[test]
+    fn empty_report() {
+        assert_eq!(
+            format_table(&BTreeMap::new(), &NumberFormat::default()),
+            "No spec stats recorded yet."
+        );
+    }
+}
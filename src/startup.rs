@@ -1,6 +1,6 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::Result;
 use ratatui::DefaultTerminal;
@@ -8,18 +8,25 @@ use tracing::warn;
 
 use crate::agent;
 use crate::app::{App, AppStatus};
-use crate::config::LoadedConfig;
+use crate::bd::Bd;
+use crate::config::{LoadedConfig, compute_project_config_path};
 use crate::db;
 use crate::doctor;
 use crate::event_loop::run_event_loop;
 use crate::logging::ReloadHandle;
 use crate::modals;
+use crate::output;
+use crate::project_summary::{self, ProjectSummary};
+use crate::shutdown_summary::{self, ExitReason, ShutdownSummary};
+use crate::stats;
+use crate::tabs::Tabs;
+use crate::ui_state::{self, UiState};
 
 /// Merge the current worktree branch to main, clean up, and create a fresh worktree.
 /// Epic-aware: within an active epic, skips merge and reuses the worktree.
 /// Returns false if the merge failed and the loop should stop.
 pub(crate) fn merge_and_refresh_worktree(app: &mut App) -> bool {
-    let bd_path = app.config.behavior.bd_path.clone();
+    let bd = app.bd();
     let w = app.selected_worker;
 
     let has_epic = app.workers[w].claimed_epic_id.is_some();
@@ -27,7 +34,7 @@ pub(crate) fn merge_and_refresh_worktree(app: &mut App) -> bool {
         && app.workers[w]
             .claimed_epic_id
             .as_ref()
-            .is_some_and(|eid| has_ready_children(&bd_path, eid));
+            .is_some_and(|eid| has_ready_children(&bd, eid));
 
     match agent::decide_iteration_action(has_epic, has_children) {
         agent::IterationAction::ContinueInEpic => return true,
@@ -36,11 +43,11 @@ pub(crate) fn merge_and_refresh_worktree(app: &mut App) -> bool {
             let agent_id = app.workers[w].agent_bead_id.clone().unwrap_or_default();
 
             app.add_text_line(format!("[Completing epic: {}]", epic_id));
-            agent::complete_epic(&bd_path, &epic_id);
+            agent::complete_epic(&bd, &epic_id);
             app.workers[w].claimed_epic_id = None;
 
             let _ = crate::bd_lock::with_lock(|| {
-                std::process::Command::new(&bd_path)
+                bd.command()
                     .args(["set-state", &agent_id, "epic=none"])
                     .stdin(std::process::Stdio::null())
                     .stdout(std::process::Stdio::null())
@@ -86,8 +93,8 @@ pub(crate) fn ensure_worktree(app: &mut App) -> bool {
         None => return true,
     };
 
-    let bd_path = app.config.behavior.bd_path.clone();
-    if let Some((new_name, new_path)) = agent::create_or_reuse_worktree(&bd_path, &worktree_name) {
+    let bd = app.bd();
+    if let Some((new_name, new_path)) = agent::create_or_reuse_worktree(&bd, &worktree_name) {
         app.workers[w].worktree_name = Some(new_name);
         app.workers[w].worktree_path = Some(new_path);
         true
@@ -100,9 +107,9 @@ pub(crate) fn ensure_worktree(app: &mut App) -> bool {
 }
 
 /// Check if an epic has ready children.
-pub(crate) fn has_ready_children(bd_path: &str, epic_id: &str) -> bool {
+pub(crate) fn has_ready_children(bd: &Bd, epic_id: &str) -> bool {
     let output = crate::bd_lock::with_lock(|| {
-        std::process::Command::new(bd_path)
+        bd.command()
             .args(["ready", "--parent", epic_id, "--json"])
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -132,13 +139,106 @@ pub(crate) fn run_app(
     log_directory: Option<PathBuf>,
     loaded_config: LoadedConfig,
     log_level_handle: Option<Arc<Mutex<ReloadHandle>>>,
-) -> Result<()> {
+    run_tags: Vec<String>,
+    read_only: bool,
+) -> Result<Option<String>> {
+    let first = init_app(
+        session_id.clone(),
+        log_directory.clone(),
+        loaded_config,
+        log_level_handle.clone(),
+        run_tags.clone(),
+        read_only,
+    );
+    let mut tabs = Tabs::new(first);
+
+    // New tabs load the config of the project they were opened on and get
+    // their own session id, so on the same project their agents, claims, and
+    // worktrees never collide with the first tab's.
+    let new_tab = |n: u32, project_dir: &Path| {
+        init_app(
+            format!("{session_id}-t{n}"),
+            log_directory.clone(),
+            crate::config::load_config_for(project_dir),
+            log_level_handle.clone(),
+            run_tags.clone(),
+            read_only,
+        )
+    };
+
+    let result = run_event_loop(&mut tabs, &mut terminal, new_tab);
+
+    // Each project keeps the UI state of its last tab, the focused one winning
+    let focused = tabs.active;
+    let order = (0..tabs.apps.len())
+        .filter(|&i| i != focused)
+        .chain([focused]);
+    for app in order.map(|i| &tabs.apps[i]) {
+        ui_state::save(&app.project_dir, &UiState::capture(app));
+    }
+
+    // Captured before shutdown stops whatever was still running
+    let reason = match &result {
+        Ok(reason) => reason.clone(),
+        Err(e) => ExitReason::Error(format!("{e:#}")),
+    };
+    let summaries = ShutdownSummary::capture_by_project(&tabs.apps, &reason);
+
+    // Always clean up resources, regardless of how we exited
+    for app in &mut tabs.apps {
+        project_summary::save_last_session(app);
+        shutdown_app(app);
+    }
+    for (project_dir, summary) in &summaries {
+        shutdown_summary::write(project_dir, summary);
+    }
+
+    // A lock conflict can end the session with a request to observe the other one
+    let attach_request = tabs.apps.iter_mut().find_map(|a| a.attach_request.take());
+    result.map(|_| attach_request)
+}
+
+/// Build a fully-initialised `App` for one tab: doctor checks, tool history DB,
+/// initial board fetch, and agent registration for every worker (skipped in
+/// read-only mode, which must not touch the bead database).
+pub(crate) fn init_app(
+    session_id: String,
+    log_directory: Option<PathBuf>,
+    loaded_config: LoadedConfig,
+    log_level_handle: Option<Arc<Mutex<ReloadHandle>>>,
+    run_tags: Vec<String>,
+    read_only: bool,
+) -> App {
     let loaded_for_doctor = loaded_config.clone();
     let mut app = App::new(session_id, log_directory, loaded_config, log_level_handle);
+    app.run_tags = run_tags;
+    app.read_only = read_only;
+    ui_state::load(&app.project_dir).restore(&mut app);
+
+    // Initialize the tool history and stats database (the summary reads the
+    // last session from it)
+    match db::open() {
+        Ok(conn) => {
+            if let Some(dir) = compute_project_config_path(&app.project_dir)
+                .and_then(|p| p.parent().map(PathBuf::from))
+            {
+                stats::import_legacy(&conn, &app.repo_path, &dir);
+            }
+            stats::start_session(&conn, &app.session_id, &app.repo_path);
+            db::insert_session_tags(&conn, &app.session_id, &app.run_tags);
+            app.tool_history_db = Some(conn);
+        }
+        Err(e) => {
+            warn!(error = %e, "tool_history_db_open_failed");
+            app.add_text_line(format!("[Tool history DB failed: {}]", e));
+        }
+    }
+
+    app.project_summary = Some(ProjectSummary::gather(&app));
     app.validate_board_config();
 
     // Hint when skill files are missing or drifted from compiled-in templates
-    let init_state = modals::InitModalState::new(&loaded_for_doctor.config);
+    let init_state = modals::InitModalState::new(&loaded_for_doctor.project_dir);
     if let Some(msg) = init_state.hint_message() {
         app.set_hint(msg);
     }
@@ -151,26 +251,20 @@ pub(crate) fn run_app(
             let mut checks = vec![
                 doctor::check_config(&loaded_for_doctor),
                 doctor::check_claude(cfg),
-                doctor::check_prompt(cfg),
+                doctor::check_prompt(&loaded_for_doctor),
             ];
             checks.push(doctor::check_bd(cfg));
-            checks.push(doctor::check_scaffolding_drift(cfg));
-            checks.push(doctor::check_work_items(cfg));
+            checks.push(doctor::check_scaffolding_drift(&loaded_for_doctor));
+            checks.push(doctor::check_work_items(&loaded_for_doctor));
             let _ = tx.send(checks);
         });
         app.doctor_rx = Some(rx);
     }
 
-    // Initialize tool history database
-    match db::open() {
-        Ok(conn) => {
-            app.tool_history_db = Some(conn);
-        }
-        Err(e) => {
-            warn!(error = %e, "tool_history_db_open_failed");
-            app.add_text_line(format!("[Tool history DB failed: {}]", e));
-        }
-    }
+    app.transcript = crate::transcript::TranscriptWriter::open(
+        &app.session_id,
+        &app.config.encryption.age_recipients,
+    );
 
     // Kick off the initial board fetch. After startup the board only
     // refreshes via the `r` keybinding or user-initiated mutations.
@@ -179,8 +273,8 @@ pub(crate) fn run_app(
     }
 
     // Register agent beads for all workers (worktrees created on first loop start)
-    {
-        let bd_path = app.config.behavior.bd_path.clone();
+    if !app.read_only {
+        let bd = app.bd();
         let heartbeat_interval = app.config.behavior.heartbeat_interval;
         for w in 0..app.workers.len() {
             let sid = if app.workers.len() > 1 {
@@ -188,9 +282,9 @@ pub(crate) fn run_app(
             } else {
                 app.session_id.clone()
             };
-            if let Some(setup) = agent::register(&bd_path, &sid) {
+            if let Some(setup) = agent::register(&bd, &sid) {
                 let stop = agent::start_heartbeat(
-                    bd_path.clone(),
+                    bd.clone(),
                     setup.agent_bead_id.clone(),
                     heartbeat_interval,
                 );
@@ -205,13 +299,32 @@ pub(crate) fn run_app(
         }
     }
 
-    let result = run_event_loop(&mut app, &mut terminal);
+    app
+}
 
-    // Always clean up resources, regardless of how we exited
+/// Stop every worker's child process and release the tab's agents and claims.
+///
+/// Children are interrupted first and get `behavior.stop_grace_secs` to write
+/// their final result (still recorded) and exit; any left are then killed.
+pub(crate) fn shutdown_app(app: &mut App) {
+    let grace = Duration::from_secs(app.config.behavior.stop_grace_secs);
+    let now = Instant::now();
+    let mut deadline = now;
+    for worker in &mut app.workers {
+        if worker.stop_deadline.is_none() && worker.child_running() {
+            worker.interrupt_child(now, grace);
+        }
+        deadline = deadline.max(worker.stop_deadline.unwrap_or(now));
+    }
+    while Instant::now() < deadline && app.workers.iter_mut().any(|w| w.child_running()) {
+        output::poll_output(app);
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    output::poll_output(app);
     for w in 0..app.workers.len() {
         app.workers[w].kill_child();
     }
     app.cleanup_agent();
-
-    result
+    app.session_lock = None;
+    app.publish_run_status();
 }
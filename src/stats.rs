@@ -0,0 +1,548 @@
+//! Run statistics kept in the SQLite database (`db`).
+//!
+//! Every finished iteration is stored with its usage and the spec (bead) it
+//! was attributed to, alongside a row per session. Per-spec totals, the
+//! startup summary's "last session" and `ralph stats` are all queries over
+//! these tables, scoped to the repo. Projects that predate the database have
+//! their `spec_stats.json` / `last_session.json` imported once.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::number_format::NumberFormat;
+use crate::project_summary::LastSession;
+use crate::spec_stats::SpecStats;
+
+const SECS_PER_DAY: u64 = 86_400;
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// One finished iteration.
+#[derive(Debug, Clone, Default)]
+pub struct IterationRow<'a> {
+    pub session_id: &'a str,
+    pub repo_path: &'a str,
+    pub worker: usize,
+    pub iteration: u32,
+    /// Spec the iteration is attributed to.
+    pub bead_id: Option<&'a str>,
+    pub tokens: u64,
+    pub cost_usd: f64,
+    pub duration_ms: u64,
+    pub failed: bool,
+    pub tool_results: u32,
+    pub tool_errors: u32,
+}
+
+/// Record the start of a session.
+/// Returns true on success, or logs a warning and returns false on failure.
+pub fn start_session(conn: &Connection, session_id: &str, repo_path: &str) -> bool {
+    match conn.execute(
+        "INSERT OR IGNORE INTO sessions (session_id, repo_path, started_at) VALUES (?1, ?2, ?3)",
+        params![session_id, repo_path, unix_now()],
+    ) {
+        Ok(_) => true,
+        Err(e) => {
+            warn!(error = %e, session_id, "Failed to record session start");
+            false
+        }
+    }
+}
+
+/// Record a session's end and its totals.
+/// Returns true on success, or logs a warning and returns false on failure.
+pub fn end_session(conn: &Connection, session_id: &str, iterations: u32, cost_usd: f64) -> bool {
+    match conn.execute(
+        "UPDATE sessions SET ended_at = ?1, iterations = ?2, cost_usd = ?3 WHERE session_id = ?4",
+        params![unix_now(), iterations, cost_usd, session_id],
+    ) {
+        Ok(_) => true,
+        Err(e) => {
+            warn!(error = %e, session_id, "Failed to record session end");
+            false
+        }
+    }
+}
+
+/// The most recent finished session in `repo_path` that ran at least one iteration.
+pub fn last_session(conn: &Connection, repo_path: &str) -> Option<LastSession> {
+    conn.query_row(
+        "SELECT session_id, ended_at, iterations, cost_usd FROM sessions
+         WHERE repo_path = ?1 AND ended_at IS NOT NULL AND iterations > 0
+         ORDER BY ended_at DESC LIMIT 1",
+        params![repo_path],
+        |row| {
+            Ok(LastSession {
+                session_id: row.get(0)?,
+                ended_at: row.get(1)?,
+                iterations: row.get(2)?,
+                cost_usd: row.get(3)?,
+            })
+        },
+    )
+    .optional()
+    .inspect_err(|e| warn!(error = %e, "Failed to load last session"))
+    .ok()
+    .flatten()
+}
+
+/// Record a finished iteration.
+/// Returns true on success, or logs a warning and returns false on failure.
+pub fn record_iteration(conn: &Connection, row: &IterationRow) -> bool {
+    let result = conn
+        .execute(
+            "INSERT INTO iterations (session_id, repo_path, worker, iteration, bead_id, tokens,
+                 cost_usd, duration_ms, failed, tool_results, tool_errors, ended_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                row.session_id,
+                row.repo_path,
+                row.worker,
+                row.iteration,
+                row.bead_id,
+                row.tokens,
+                row.cost_usd,
+                row.duration_ms,
+                row.failed,
+                row.tool_results,
+                row.tool_errors,
+                unix_now(),
+            ],
+        )
+        .and_then(|_| match row.bead_id {
+            Some(bead_id) => conn.execute(
+                "INSERT OR IGNORE INTO specs (repo_path, bead_id) VALUES (?1, ?2)",
+                params![row.repo_path, bead_id],
+            ),
+            None => Ok(0),
+        });
+    match result {
+        Ok(_) => true,
+        Err(e) => {
+            warn!(error = %e, iteration = row.iteration, "Failed to record iteration");
+            false
+        }
+    }
+}
+
+/// Mark `bead_id` complete and return its totals.
+pub fn complete_spec(conn: &Connection, repo_path: &str, bead_id: &str, title: &str) -> SpecStats {
+    let title = Some(title).filter(|t| !t.is_empty());
+    if let Err(e) = conn.execute(
+        "INSERT INTO specs (repo_path, bead_id, title, completed_at) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT (repo_path, bead_id) DO UPDATE SET title = ?3, completed_at = ?4",
+        params![repo_path, bead_id, title, unix_now()],
+    ) {
+        warn!(error = %e, bead_id, "Failed to record spec completion");
+    }
+    spec_totals(conn, repo_path)
+        .inspect_err(|e| warn!(error = %e, "Failed to load spec stats"))
+        .ok()
+        .and_then(|mut all| all.remove(bead_id))
+        .unwrap_or_default()
+}
+
+/// Totals for every spec in `repo_path`, keyed by bead ID.
+pub fn spec_totals(conn: &Connection, repo_path: &str) -> Result<BTreeMap<String, SpecStats>> {
+    let mut stmt = conn.prepare(
+        "SELECT s.bead_id, s.title, s.completed_at,
+                s.imported_iterations + COUNT(i.id),
+                s.imported_tokens + COALESCE(SUM(i.tokens), 0),
+                s.imported_cost_usd + COALESCE(SUM(i.cost_usd), 0),
+                s.imported_duration_ms + COALESCE(SUM(i.duration_ms), 0)
+         FROM specs s
+         LEFT JOIN iterations i ON i.repo_path = s.repo_path AND i.bead_id = s.bead_id
+         WHERE s.repo_path = ?1
+         GROUP BY s.bead_id",
+    )?;
+    let rows = stmt.query_map(params![repo_path], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            SpecStats {
+                title: row.get(1)?,
+                completed_at: row.get(2)?,
+                iterations: row.get(3)?,
+                tokens: row.get(4)?,
+                cost_usd: row.get(5)?,
+                duration_ms: row.get(6)?,
+            },
+        ))
+    })?;
+    rows.collect::<rusqlite::Result<_>>()
+        .context("Failed to read spec stats")
+}
+
+/// Import the JSON files projects kept before the database, renaming each
+/// to `*.imported` once its contents are in.
+pub fn import_legacy(conn: &Connection, repo_path: &str, config_dir: &Path) {
+    let specs_path = config_dir.join("spec_stats.json");
+    if let Ok(contents) = std::fs::read_to_string(&specs_path) {
+        let imported = serde_json::from_str::<BTreeMap<String, SpecStats>>(&contents)
+            .map_err(anyhow::Error::from)
+            .and_then(|all| import_specs(conn, repo_path, &all));
+        finish_import(&specs_path, imported);
+    }
+
+    let session_path = config_dir.join("last_session.json");
+    if let Ok(contents) = std::fs::read_to_string(&session_path) {
+        let imported = serde_json::from_str::<LastSession>(&contents)
+            .map_err(anyhow::Error::from)
+            .and_then(|s| {
+                conn.execute(
+                    "INSERT OR IGNORE INTO sessions
+                         (session_id, repo_path, started_at, ended_at, iterations, cost_usd)
+                     VALUES (?1, ?2, ?3, ?3, ?4, ?5)",
+                    params![
+                        s.session_id,
+                        repo_path,
+                        s.ended_at,
+                        s.iterations,
+                        s.cost_usd
+                    ],
+                )?;
+                Ok(())
+            });
+        finish_import(&session_path, imported);
+    }
+}
+
+fn import_specs(
+    conn: &Connection,
+    repo_path: &str,
+    all: &BTreeMap<String, SpecStats>,
+) -> Result<()> {
+    for (bead_id, stats) in all {
+        conn.execute(
+            "INSERT OR IGNORE INTO specs (repo_path, bead_id, title, completed_at,
+                 imported_iterations, imported_tokens, imported_cost_usd, imported_duration_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                repo_path,
+                bead_id,
+                stats.title,
+                stats.completed_at,
+                stats.iterations,
+                stats.tokens,
+                stats.cost_usd,
+                stats.duration_ms,
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+fn finish_import(path: &Path, imported: Result<()>) {
+    match imported {
+        Ok(()) => {
+            info!(path = %path.display(), "stats_json_imported");
+            let mut done = path.as_os_str().to_owned();
+            done.push(".imported");
+            if let Err(e) = std::fs::rename(path, done) {
+                warn!(path = %path.display(), error = %e, "stats_json_rename_failed");
+            }
+        }
+        Err(e) => warn!(path = %path.display(), error = %e, "stats_json_import_failed"),
+    }
+}
+
+/// One day's activity, by local date.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DayStats {
+    pub day: String,
+    pub cost_usd: f64,
+    pub iterations: u32,
+    pub specs_done: u32,
+}
+
+/// Activity over the last `days` days.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Summary {
+    pub days: u32,
+    /// `None` when summarizing every repo.
+    pub repo_path: Option<String>,
+    pub sessions: u32,
+    pub iterations: u32,
+    pub failed_iterations: u32,
+    pub tokens: u64,
+    pub cost_usd: f64,
+    pub specs_done: u32,
+    pub per_day: Vec<DayStats>,
+}
+
+/// Summarize activity since `since` (Unix seconds), in `repo_path` or everywhere.
+pub fn summarize(
+    conn: &Connection,
+    repo_path: Option<&str>,
+    since: u64,
+    days: u32,
+) -> Result<Summary> {
+    let mut summary = Summary {
+        days,
+        repo_path: repo_path.map(str::to_string),
+        ..Default::default()
+    };
+    let mut per_day: BTreeMap<String, DayStats> = BTreeMap::new();
+
+    let mut stmt = conn.prepare(
+        "SELECT date(ended_at, 'unixepoch', 'localtime'), COUNT(*), SUM(failed),
+                SUM(tokens), SUM(cost_usd)
+         FROM iterations
+         WHERE ended_at >= ?1 AND (?2 IS NULL OR repo_path = ?2)
+         GROUP BY 1",
+    )?;
+    let rows = stmt.query_map(params![since, repo_path], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, u32>(1)?,
+            row.get::<_, u32>(2)?,
+            row.get::<_, u64>(3)?,
+            row.get::<_, f64>(4)?,
+        ))
+    })?;
+    for row in rows {
+        let (day, iterations, failed, tokens, cost_usd) = row?;
+        summary.iterations += iterations;
+        summary.failed_iterations += failed;
+        summary.tokens += tokens;
+        summary.cost_usd += cost_usd;
+        let entry = per_day.entry(day.clone()).or_default();
+        entry.day = day;
+        entry.iterations = iterations;
+        entry.cost_usd = cost_usd;
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT date(completed_at, 'unixepoch', 'localtime'), COUNT(*)
+         FROM specs
+         WHERE completed_at >= ?1 AND (?2 IS NULL OR repo_path = ?2)
+         GROUP BY 1",
+    )?;
+    let rows = stmt.query_map(params![since, repo_path], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, u32>(1)?))
+    })?;
+    for row in rows {
+        let (day, done) = row?;
+        summary.specs_done += done;
+        let entry = per_day.entry(day.clone()).or_default();
+        entry.day = day;
+        entry.specs_done = done;
+    }
+
+    summary.sessions = conn.query_row(
+        "SELECT COUNT(*) FROM sessions
+         WHERE started_at >= ?1 AND iterations > 0 AND (?2 IS NULL OR repo_path = ?2)",
+        params![since, repo_path],
+        |row| row.get(0),
+    )?;
+    summary.per_day = per_day.into_values().collect();
+    Ok(summary)
+}
+
+/// Format a summary as a human-readable report.
+pub fn format_summary(summary: &Summary, numbers: &NumberFormat) -> String {
+    let scope = summary.repo_path.as_deref().unwrap_or("all projects");
+    let mut lines = vec![
+        format!("Last {} day(s) — {scope}", summary.days),
+        String::new(),
+        format!("Cost        {}", numbers.cost(summary.cost_usd)),
+        format!(
+            "Iterations  {} ({} failed)",
+            summary.iterations, summary.failed_iterations
+        ),
+        format!("Tokens      {}", numbers.count(summary.tokens)),
+        format!(
+            "Specs done  {} ({}/day)",
+            summary.specs_done,
+            numbers.decimal(
+                f64::from(summary.specs_done) / f64::from(summary.days.max(1)),
+                1
+            )
+        ),
+        format!("Sessions    {}", summary.sessions),
+    ];
+    if !summary.per_day.is_empty() {
+        lines.push(String::new());
+        lines.push(format!(
+            "{:<10} {:>9} {:>5} {:>5}",
+            "DAY", "COST", "ITER", "SPECS"
+        ));
+        for day in &summary.per_day {
+            lines.push(format!(
+                "{:<10} {:>9} {:>5} {:>5}",
+                day.day,
+                numbers.cost(day.cost_usd),
+                day.iterations,
+                day.specs_done
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Run the stats subcommand.
+pub fn run(days: u32, all: bool, json: bool) -> Result<()> {
+    let conn = crate::db::open()?;
+    let repo_path = crate::db::detect_repo_path(&crate::config::startup_dir());
+    let since = unix_now().saturating_sub(u64::from(days) * SECS_PER_DAY);
+    let summary = summarize(&conn, (!all).then_some(repo_path.as_str()), since, days)?;
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&summary).context("Failed to serialize to JSON")?
+        );
+    } else {
+        let numbers = NumberFormat::from(&crate::config::load_config().config.display);
+        println!("{}", format_summary(&summary, &numbers));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::open_memory;
+
+    fn iteration<'a>(bead_id: Option<&'a str>, tokens: u64, cost_usd: f64) -> IterationRow<'a> {
+        IterationRow {
+            session_id: "sess-1",
+            repo_path: "/repo",
+            bead_id,
+            tokens,
+            cost_usd,
+            duration_ms: 60_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn spec_totals_accumulate_until_completion() {
+        let conn = open_memory().unwrap();
+        assert!(record_iteration(
+            &conn,
+            &iteration(Some("bd-1"), 1_000, 0.5)
+        ));
+        assert!(record_iteration(&conn, &iteration(Some("bd-2"), 10, 0.01)));
+        assert!(record_iteration(&conn, &iteration(Some("bd-1"), 500, 0.25)));
+        assert!(record_iteration(&conn, &iteration(None, 5, 0.01)));
+        let stats = complete_spec(&conn, "/repo", "bd-1", "Add login");
+
+        assert_eq!(stats.iterations, 2);
+        assert_eq!(stats.tokens, 1_500);
+        assert_eq!(stats.duration_ms, 120_000);
+        assert!((stats.cost_usd - 0.75).abs() < 1e-9);
+        assert_eq!(stats.title.as_deref(), Some("Add login"));
+        assert!(stats.completed_at.is_some());
+
+        let all = spec_totals(&conn, "/repo").unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(all["bd-2"].completed_at.is_none());
+        assert!(spec_totals(&conn, "/elsewhere").unwrap().is_empty());
+    }
+
+    #[test]
+    fn last_session_skips_empty_and_unfinished_sessions() {
+        let conn = open_memory().unwrap();
+        assert!(start_session(&conn, "old", "/repo"));
+        assert!(end_session(&conn, "old", 4, 1.25));
+        assert!(start_session(&conn, "empty", "/repo"));
+        assert!(end_session(&conn, "empty", 0, 0.0));
+        assert!(start_session(&conn, "current", "/repo"));
+
+        let last = last_session(&conn, "/repo").unwrap();
+        assert_eq!(last.session_id, "old");
+        assert_eq!(last.iterations, 4);
+        assert!(last_session(&conn, "/elsewhere").is_none());
+    }
+
+    #[test]
+    fn legacy_json_is_imported_once() {
+        let conn = open_memory().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("spec_stats.json"),
+            r#"{"bd-1": {"iterations": 3, "tokens": 900, "cost_usd": 1.5, "duration_ms": 1000}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("last_session.json"),
+            r#"{"session_id": "abc", "ended_at": 1700000000, "iterations": 2, "cost_usd": 0.5}"#,
+        )
+        .unwrap();
+
+        import_legacy(&conn, "/repo", dir.path());
+        import_legacy(&conn, "/repo", dir.path());
+        assert!(dir.path().join("spec_stats.json.imported").exists());
+        assert!(!dir.path().join("last_session.json").exists());
+
+        record_iteration(&conn, &iteration(Some("bd-1"), 100, 0.5));
+        let all = spec_totals(&conn, "/repo").unwrap();
+        assert_eq!(all["bd-1"].iterations, 4);
+        assert_eq!(all["bd-1"].tokens, 1_000);
+        assert_eq!(last_session(&conn, "/repo").unwrap().session_id, "abc");
+    }
+
+    #[test]
+    fn summary_counts_recent_activity_per_repo() {
+        let conn = open_memory().unwrap();
+        start_session(&conn, "sess-1", "/repo");
+        end_session(&conn, "sess-1", 2, 0.75);
+        record_iteration(&conn, &iteration(Some("bd-1"), 1_000, 0.5));
+        let mut failed = iteration(None, 200, 0.25);
+        failed.failed = true;
+        record_iteration(&conn, &failed);
+        let mut other = iteration(None, 50, 9.0);
+        other.repo_path = "/other";
+        record_iteration(&conn, &other);
+        complete_spec(&conn, "/repo", "bd-1", "Add login");
+
+        let summary = summarize(&conn, Some("/repo"), 0, 7).unwrap();
+        assert_eq!(summary.iterations, 2);
+        assert_eq!(summary.failed_iterations, 1);
+        assert_eq!(summary.tokens, 1_200);
+        assert!((summary.cost_usd - 0.75).abs() < 1e-9);
+        assert_eq!(summary.specs_done, 1);
+        assert_eq!(summary.sessions, 1);
+        assert_eq!(summary.per_day.len(), 1);
+        assert_eq!(summary.per_day[0].specs_done, 1);
+
+        let everywhere = summarize(&conn, None, 0, 7).unwrap();
+        assert_eq!(everywhere.iterations, 3);
+
+        let future = summarize(&conn, Some("/repo"), unix_now() + 60, 7).unwrap();
+        assert_eq!(
+            future,
+            Summary {
+                days: 7,
+                repo_path: Some("/repo".into()),
+                ..Default::default()
+            }
+        );
+
+        let report = format_summary(&summary, &NumberFormat::default());
+        assert!(report.contains("Cost        $0.75"));
+        assert!(report.contains("Specs done  1 (0.1/day)"));
+
+        let european = NumberFormat::from(&crate::config::DisplayConfig {
+            thousands_separator: ".".into(),
+            decimal_separator: ",".into(),
+            currency_symbol: "€".into(),
+            currency_position: crate::config::CurrencyPosition::After,
+            ..Default::default()
+        });
+        let report = format_summary(&summary, &european);
+        assert!(report.contains("Cost        0,75 €"));
+        assert!(report.contains("Tokens      1.200"));
+        assert!(report.contains("Specs done  1 (0,1/day)"));
+    }
+}
@@ -0,0 +1,114 @@
+//! Hand the terminal to an interactive child process and take it back.
+
+use std::io;
+use std::path::Path;
+use std::process::{Command, ExitStatus};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
+use crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use ratatui::DefaultTerminal;
+use tracing::{info, warn};
+
+/// Editor used when neither `$VISUAL` nor `$EDITOR` is set.
+const FALLBACK_EDITOR: &str = "vi";
+
+/// Whether the TUI currently captures the mouse (`[keys] mouse_capture`).
+static MOUSE_CAPTURE: AtomicBool = AtomicBool::new(false);
+
+/// Turn mouse capture on or off, if that changes anything.
+pub fn set_mouse_capture(enabled: bool) -> io::Result<()> {
+    if MOUSE_CAPTURE.swap(enabled, Ordering::Relaxed) == enabled {
+        return Ok(());
+    }
+    if enabled {
+        execute!(io::stdout(), EnableMouseCapture)
+    } else {
+        execute!(io::stdout(), DisableMouseCapture)
+    }
+}
+
+/// Leave the alternate screen, run `f`, then restore the TUI and force a full redraw.
+///
+/// The terminal is restored even if `f` fails.
+pub fn suspended<T>(terminal: &mut DefaultTerminal, f: impl FnOnce() -> T) -> Result<T> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+
+    let result = f();
+
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    if MOUSE_CAPTURE.load(Ordering::Relaxed) {
+        execute!(io::stdout(), EnableMouseCapture)?;
+    }
+    terminal.clear()?;
+    Ok(result)
+}
+
+/// Editor command from `$VISUAL`, then `$EDITOR`, then [`FALLBACK_EDITOR`].
+pub fn editor_command() -> String {
+    editor_from(std::env::var("VISUAL").ok(), std::env::var("EDITOR").ok())
+}
+
+fn editor_from(visual: Option<String>, editor: Option<String>) -> String {
+    [visual, editor]
+        .into_iter()
+        .flatten()
+        .map(|e| e.trim().to_string())
+        .find(|e| !e.is_empty())
+        .unwrap_or_else(|| FALLBACK_EDITOR.to_string())
+}
+
+/// Open `path` in the user's editor with the TUI suspended.
+///
+/// Missing parent directories are created so the editor can write a new file.
+pub fn open_in_editor(terminal: &mut DefaultTerminal, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let editor = editor_command();
+    info!(editor = %editor, path = %path.display(), "editor_open");
+    let status = suspended(terminal, || run_editor(&editor, path))?;
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => {
+            warn!(editor = %editor, code = ?status.code(), "editor_exit_nonzero");
+            Err(anyhow::anyhow!("{editor} exited with {status}"))
+        }
+        Err(e) => {
+            warn!(editor = %editor, error = %e, "editor_spawn_failed");
+            Err(anyhow::anyhow!("could not run {editor}: {e}"))
+        }
+    }
+}
+
+/// `$EDITOR` may carry arguments (`code -w`), so it goes through the shell
+/// unquoted; the path is passed as `$1`.
+fn run_editor(editor: &str, path: &Path) -> io::Result<ExitStatus> {
+    Command::new("sh")
+        .arg("-c")
+        .arg(format!("{editor} \"$1\""))
+        .arg("sh")
+        .arg(path)
+        .status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn editor_prefers_visual_then_editor() {
+        assert_eq!(
+            editor_from(Some("code -w".into()), Some("nano".into())),
+            "code -w"
+        );
+        assert_eq!(editor_from(Some("  ".into()), Some("nano".into())), "nano");
+        assert_eq!(editor_from(None, None), FALLBACK_EDITOR);
+    }
+}
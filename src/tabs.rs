@@ -0,0 +1,201 @@
+//! Multiple independent sessions ("tabs") within one ralph instance.
+//!
+//! Each tab owns a complete `App` — its own workers, agents, board, and modals —
+//! and is rendered by the same UI code. Every tab is ticked each loop so background
+//! work keeps running, but only the active tab receives input and is drawn.
+//! Each tab works on its own project directory (chosen when it is opened), with
+//! that project's config, work source, and session lock.
+
+use crate::app::App;
+
+/// Maximum number of tabs (number keys 1-9 select them).
+pub const MAX_TABS: usize = 9;
+
+/// The set of open tabs and which one is focused.
+pub struct Tabs {
+    pub apps: Vec<App>,
+    pub active: usize,
+    /// Monotonic counter for tab session-id suffixes (never reused after close).
+    opened: u32,
+}
+
+/// Why a tab operation was refused.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TabError {
+    /// Already at `MAX_TABS`.
+    Full,
+    /// Closing the only tab; quit instead.
+    LastTab,
+    /// The tab still has a loop running or starting.
+    Busy,
+}
+
+impl TabError {
+    /// User-facing hint for the refusal.
+    pub fn hint(&self) -> &'static str {
+        match self {
+            TabError::Full => "tab limit reached (9)",
+            TabError::LastTab => "last tab — press q to quit",
+            TabError::Busy => "stop this tab's loop before closing it",
+        }
+    }
+}
+
+impl Tabs {
+    /// Start with a single tab.
+    pub fn new(first: App) -> Self {
+        Self {
+            apps: vec![first],
+            active: 0,
+            opened: 1,
+        }
+    }
+
+    /// The focused tab's app.
+    pub fn active_mut(&mut self) -> &mut App {
+        &mut self.apps[self.active]
+    }
+
+    /// Number for the next tab's session-id suffix, or `Full` if no room.
+    pub fn next_tab_number(&self) -> Result<u32, TabError> {
+        if self.apps.len() >= MAX_TABS {
+            Err(TabError::Full)
+        } else {
+            Ok(self.opened + 1)
+        }
+    }
+
+    /// Append a new tab and focus it.
+    pub fn open(&mut self, app: App) {
+        self.apps.push(app);
+        self.opened += 1;
+        self.switch_to(self.apps.len() - 1);
+    }
+
+    /// Focus the tab at `index`. Returns false if there is no such tab.
+    pub fn switch_to(&mut self, index: usize) -> bool {
+        if index >= self.apps.len() {
+            return false;
+        }
+        self.active = index;
+        self.apps[index].dirty = true;
+        true
+    }
+
+    /// Remove the focused tab and return it for cleanup.
+    ///
+    /// Refuses to close the last tab or a tab with a run under way, between
+    /// iterations included.
+    pub fn close_active(&mut self) -> Result<App, TabError> {
+        if self.apps.len() <= 1 {
+            return Err(TabError::LastTab);
+        }
+        if self.apps[self.active].run_in_progress() {
+            return Err(TabError::Busy);
+        }
+        let closed = self.apps.remove(self.active);
+        let next = self.active.min(self.apps.len() - 1);
+        self.switch_to(next);
+        Ok(closed)
+    }
+
+    /// True if any tab has a run under way, between iterations included.
+    pub fn any_active(&self) -> bool {
+        self.apps.iter().any(App::run_in_progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppStatus;
+    use crate::config::LoadedConfig;
+
+    fn app() -> App {
+        App::new("test".into(), None, LoadedConfig::default_for_test(), None)
+    }
+
+    #[test]
+    fn open_focuses_new_tab() {
+        let mut tabs = Tabs::new(app());
+        tabs.open(app());
+        assert_eq!(tabs.apps.len(), 2);
+        assert_eq!(tabs.active, 1);
+    }
+
+    #[test]
+    fn switch_to_out_of_range_is_noop() {
+        let mut tabs = Tabs::new(app());
+        assert!(!tabs.switch_to(3));
+        assert_eq!(tabs.active, 0);
+    }
+
+    #[test]
+    fn switch_to_marks_target_dirty() {
+        let mut tabs = Tabs::new(app());
+        tabs.open(app());
+        tabs.apps[0].dirty = false;
+        assert!(tabs.switch_to(0));
+        assert!(tabs.apps[0].dirty);
+    }
+
+    #[test]
+    fn close_last_tab_is_refused() {
+        let mut tabs = Tabs::new(app());
+        assert_eq!(tabs.close_active().err(), Some(TabError::LastTab));
+    }
+
+    #[test]
+    fn close_running_tab_is_refused() {
+        let mut tabs = Tabs::new(app());
+        tabs.open(app());
+        tabs.apps[1].status = AppStatus::Starting;
+        assert_eq!(tabs.close_active().err(), Some(TabError::Busy));
+        // Between iterations the status reads Stopped but the run goes on
+        tabs.apps[1].status = AppStatus::Stopped;
+        tabs.apps[1].workers[0].auto_continue_pending = true;
+        assert_eq!(tabs.close_active().err(), Some(TabError::Busy));
+        assert_eq!(tabs.apps.len(), 2);
+    }
+
+    #[test]
+    fn close_active_focuses_previous_when_last_removed() {
+        let mut tabs = Tabs::new(app());
+        tabs.open(app());
+        tabs.open(app());
+        assert!(tabs.close_active().is_ok());
+        assert_eq!(tabs.apps.len(), 2);
+        assert_eq!(tabs.active, 1);
+    }
+
+    #[test]
+    fn tab_numbers_are_not_reused_after_close() {
+        let mut tabs = Tabs::new(app());
+        assert_eq!(tabs.next_tab_number(), Ok(2));
+        tabs.open(app());
+        tabs.close_active().unwrap();
+        assert_eq!(tabs.next_tab_number(), Ok(3));
+    }
+
+    #[test]
+    fn next_tab_number_full_at_max() {
+        let mut tabs = Tabs::new(app());
+        for _ in 1..MAX_TABS {
+            tabs.open(app());
+        }
+        assert_eq!(tabs.next_tab_number(), Err(TabError::Full));
+    }
+
+    #[test]
+    fn any_active_checks_every_tab() {
+        let mut tabs = Tabs::new(app());
+        tabs.open(app());
+        assert!(!tabs.any_active());
+        tabs.apps[0].status = AppStatus::Starting;
+        assert!(tabs.any_active());
+        // Between iterations the status reads Stopped but the run goes on
+        tabs.apps[0].status = AppStatus::Stopped;
+        tabs.apps[1].workers[0].auto_continue_pending = true;
+        assert!(tabs.any_active());
+    }
+}
@@ -87,6 +87,142 @@ After committing ONE vertical slice, exit immediately. Do not start another task
 This workflow follows Shape Up methodology — appetite-driven, vertically-sliced, with clear boundaries. For deeper context, see https://www.ryansinger.co/posts/
 "#;
 
+/// Validation section of `PROMPT_MD`, replaced by [`ProjectPreset::prompt`].
+const GENERIC_VALIDATION: &str = r#"Before committing, run your project's validation:
+
+```bash
+# Run your tests
+# Run your linter
+# Run your type checker
+```
+"#;
+
+/// Project types `ralph init` can tailor PROMPT.md for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProjectPreset {
+    #[default]
+    Generic,
+    RustCrate,
+    WebApp,
+    Library,
+    DocsOnly,
+}
+
+impl ProjectPreset {
+    pub const ALL: [ProjectPreset; 5] = [
+        Self::Generic,
+        Self::RustCrate,
+        Self::WebApp,
+        Self::Library,
+        Self::DocsOnly,
+    ];
+
+    /// Name used by `ralph init --preset`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Generic => "generic",
+            Self::RustCrate => "rust",
+            Self::WebApp => "web",
+            Self::Library => "library",
+            Self::DocsOnly => "docs",
+        }
+    }
+
+    /// Display label for the init modal.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Generic => "Generic",
+            Self::RustCrate => "Rust crate",
+            Self::WebApp => "Web app",
+            Self::Library => "Library",
+            Self::DocsOnly => "Docs only",
+        }
+    }
+
+    /// Parse a `--preset` value.
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        Self::ALL
+            .into_iter()
+            .find(|p| p.name() == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|p| p.name()).collect();
+                format!(
+                    "unknown preset '{name}' (expected one of: {})",
+                    names.join(", ")
+                )
+            })
+    }
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    pub fn prev(self) -> Self {
+        let i = Self::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        Self::ALL[(i + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Body of the "Validate" section for this project type.
+    fn validation_section(self) -> &'static str {
+        match self {
+            Self::Generic => GENERIC_VALIDATION,
+            Self::RustCrate => {
+                r#"Before committing, run the full Rust validation:
+
+```bash
+cargo fmt --check
+cargo clippy --all-targets -- -D warnings
+cargo test
+```
+
+Never silence a clippy lint with `#[allow]` unless the bead asks for it.
+"#
+            }
+            Self::WebApp => {
+                r#"Before committing, run the project's checks from `package.json`:
+
+```bash
+npm run lint
+npm run typecheck
+npm test
+```
+
+For UI changes, also run the app and verify the behavior in a browser.
+"#
+            }
+            Self::Library => {
+                r#"Before committing, run your project's validation:
+
+```bash
+# Run your tests
+# Run your linter
+# Build the public API docs
+```
+
+Public API changes need doc comments and a changelog entry. Flag breaking changes for a human instead of shipping them.
+"#
+            }
+            Self::DocsOnly => {
+                r#"Before committing, check the docs render and link correctly:
+
+```bash
+# Build the docs site
+# Run the link checker
+```
+
+Keep edits scoped to the work item. Don't restructure navigation unless asked.
+"#
+            }
+        }
+    }
+
+    /// `PROMPT_MD` with this preset's validation section.
+    pub fn prompt(self) -> String {
+        PROMPT_MD.replacen(GENERIC_VALIDATION, self.validation_section(), 1)
+    }
+}
+
 /// Generate the beads workflow instructions appended after `PROMPT_MD`.
 /// Command reference is provided by `bd prime` — this only covers Ralph-specific workflow.
 pub fn beads_workflow(claimed_bead_id: Option<&str>) -> String {
@@ -191,6 +327,30 @@ pub const INTERCEPT_BD_SH: &str = include_str!("../scripts/intercept-bd.sh");
 mod tests {
     use super::*;
 
+    #[test]
+    fn generic_preset_prompt_is_the_default_prompt() {
+        assert!(PROMPT_MD.contains(GENERIC_VALIDATION));
+        assert_eq!(ProjectPreset::Generic.prompt(), PROMPT_MD);
+    }
+
+    #[test]
+    fn presets_swap_only_the_validation_section() {
+        let rust = ProjectPreset::RustCrate.prompt();
+        assert!(rust.contains("cargo clippy"));
+        assert!(!rust.contains("# Run your type checker"));
+        assert!(rust.contains("## 6. Commit"));
+    }
+
+    #[test]
+    fn preset_names_round_trip_and_cycle() {
+        for preset in ProjectPreset::ALL {
+            assert_eq!(ProjectPreset::from_name(preset.name()), Ok(preset));
+            assert_eq!(preset.next().prev(), preset);
+        }
+        assert!(ProjectPreset::from_name("cobol").is_err());
+        assert_eq!(ProjectPreset::DocsOnly.next(), ProjectPreset::Generic);
+    }
+
     #[test]
     fn beads_workflow_contains_hill_enforcement() {
         let content = beads_workflow(Some("test-123"));
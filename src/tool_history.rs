@@ -29,6 +29,8 @@ pub enum RepoFilter {
 pub enum QueryFilter {
     /// Show all tool calls from a specific session.
     Session(String),
+    /// Show all tool calls from sessions started with a `--tag`.
+    Tag(String),
     /// Show all calls to a specific tool (case-insensitive).
     Tool(String),
     /// Show tool calls in a time range.
@@ -219,6 +221,13 @@ fn build_query(
             conditions.push(format!("session_id = ?{}", params.len() + 1));
             params.push(session_id.clone());
         }
+        QueryFilter::Tag(tag) => {
+            conditions.push(format!(
+                "session_id IN (SELECT session_id FROM session_tags WHERE tag = ?{})",
+                params.len() + 1
+            ));
+            params.push(tag.clone());
+        }
         QueryFilter::Tool(tool_name) => {
             conditions.push(format!("tool_name = ?{} COLLATE NOCASE", params.len() + 1));
             params.push(tool_name.clone());
@@ -358,6 +367,7 @@ pub struct HistoryOptions {
     pub json: bool,
     pub show_db_path: bool,
     pub repo: Option<String>,
+    pub tag: Option<String>,
     pub all: bool,
 }
 
@@ -372,6 +382,8 @@ pub fn run(opts: HistoryOptions) -> Result<()> {
 
     let filter = if let Some(session_id) = opts.session {
         QueryFilter::Session(session_id)
+    } else if let Some(tag) = opts.tag {
+        QueryFilter::Tag(tag)
     } else if let Some(tool_name) = opts.tool {
         QueryFilter::Tool(tool_name)
     } else if let Some(since_spec) = opts.since {
@@ -390,7 +402,9 @@ pub fn run(opts: HistoryOptions) -> Result<()> {
     } else if let Some(value) = opts.repo {
         Some(RepoFilter::Smart(value))
     } else {
-        Some(RepoFilter::Exact(db::detect_repo_path()))
+        Some(RepoFilter::Exact(db::detect_repo_path(
+            &crate::config::startup_dir(),
+        )))
     };
 
     let conn = db::open().context("Failed to open tool history database")?;
@@ -473,6 +487,20 @@ mod tests {
         assert!(records.iter().all(|r| r.session_id == "sess-aaa111"));
     }
 
+    #[test]
+    fn query_by_tag() {
+        let conn = setup_test_db();
+        db::insert_session_tags(&conn, "sess-bbb222", &["prompt-v3".to_string()]);
+        let records =
+            query_tool_calls(&conn, &QueryFilter::Tag("prompt-v3".into()), false, None).unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|r| r.session_id == "sess-bbb222"));
+
+        let none =
+            query_tool_calls(&conn, &QueryFilter::Tag("prompt-v2".into()), false, None).unwrap();
+        assert!(none.is_empty());
+    }
+
     #[test]
     fn query_by_tool_case_insensitive() {
         let conn = setup_test_db();
@@ -1,22 +1,8 @@
 use std::collections::HashMap;
 
+use ralph_core::output::{ToolCallEntry, ToolCallStatus};
 use ratatui::text::Line;
 
-/// Tracks accumulated state for a content block being streamed.
-#[derive(Debug, Default)]
-pub struct ContentBlockState {
-    /// For text blocks: accumulated text content.
-    pub text: String,
-    /// For tool_use blocks: the tool name.
-    pub tool_name: Option<String>,
-    /// For tool_use blocks: the tool use ID (for correlating with results).
-    pub tool_use_id: Option<String>,
-    /// For tool_use blocks: accumulated JSON input string.
-    pub input_json: String,
-    /// Whether we've shown the assistant header for this text block.
-    pub header_shown: bool,
-}
-
 /// A pending tool call waiting for its result.
 #[derive(Debug, Clone)]
 pub struct PendingToolCall {
@@ -24,30 +10,8 @@ pub struct PendingToolCall {
     pub tool_name: String,
     /// The styled line to display.
     pub styled_line: Line<'static>,
-}
-
-/// Status of a tool call in the panel display.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ToolCallStatus {
-    /// Tool call sent, waiting for result.
-    Pending,
-    /// Tool call completed successfully.
-    Success,
-    /// Tool call returned an error.
-    Error,
-}
-
-/// A tool call entry for the panel display.
-#[derive(Debug, Clone)]
-pub struct ToolCallEntry {
-    /// The tool name (e.g., "Read", "Bash").
-    pub tool_name: String,
-    /// Summary of the key argument (e.g., "git status", "/path/to/file.rs").
-    pub summary: String,
-    /// Current status.
-    pub status: ToolCallStatus,
-    /// Tool use ID for correlating with results.
-    pub tool_use_id: Option<String>,
+    /// Raw tool input JSON (identifies repeats of the same call).
+    pub input_json: String,
 }
 
 /// Which panel is currently focused for scrolling.
@@ -2,8 +2,11 @@
 
 use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::file_stamp::{self, FileStamp};
 
 /// The permissions block inside a Claude settings file.
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -102,6 +105,33 @@ fn write_settings(path: &Path, settings: &SettingsFile) -> Result<()> {
     Ok(())
 }
 
+/// Apply `change` to the settings file at `path`. If the file changes while
+/// ralph is updating it (e.g. Claude editing it mid-run), re-read it and
+/// re-apply the change rather than overwriting the other edit. Returns
+/// whether `change` modified anything.
+fn update_settings(path: &Path, mut change: impl FnMut(&mut SettingsFile) -> bool) -> Result<bool> {
+    for attempt in 1..=file_stamp::MAX_ATTEMPTS {
+        let stamp =
+            FileStamp::of(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut settings = read_settings(path)?.unwrap_or_default();
+        if !change(&mut settings) {
+            return Ok(false);
+        }
+        if stamp
+            .still_matches(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+        {
+            write_settings(path, &settings)?;
+            return Ok(true);
+        }
+        warn!(path = %path.display(), attempt, "settings_changed_during_update");
+    }
+    bail!(
+        "{} kept changing while ralph was updating it; left it unchanged",
+        path.display()
+    )
+}
+
 /// Add a pattern to the allow list in the given settings file.
 pub fn allow_pattern(pattern: &str, project: bool) -> Result<()> {
     let level = if project {
@@ -110,18 +140,21 @@ pub fn allow_pattern(pattern: &str, project: bool) -> Result<()> {
         SettingsLevel::Local
     };
     let path = settings_path(level)?;
-    let mut settings = read_settings(&path)?.unwrap_or_default();
-    let perms = settings
-        .permissions
-        .get_or_insert_with(Permissions::default);
+    let added = update_settings(&path, |settings| {
+        let perms = settings
+            .permissions
+            .get_or_insert_with(Permissions::default);
+        if perms.allow.iter().any(|p| p == pattern) {
+            return false;
+        }
+        perms.allow.push(pattern.to_string());
+        true
+    })?;
 
-    if perms.allow.iter().any(|p| p == pattern) {
+    if !added {
         println!("{pattern} is already in {level} allow list");
         return Ok(());
     }
-
-    perms.allow.push(pattern.to_string());
-    write_settings(&path, &settings)?;
     println!("Added {pattern} to {level} allow list");
     Ok(())
 }
@@ -134,18 +167,21 @@ pub fn deny_pattern(pattern: &str, project: bool) -> Result<()> {
         SettingsLevel::Local
     };
     let path = settings_path(level)?;
-    let mut settings = read_settings(&path)?.unwrap_or_default();
-    let perms = settings
-        .permissions
-        .get_or_insert_with(Permissions::default);
+    let added = update_settings(&path, |settings| {
+        let perms = settings
+            .permissions
+            .get_or_insert_with(Permissions::default);
+        if perms.deny.iter().any(|p| p == pattern) {
+            return false;
+        }
+        perms.deny.push(pattern.to_string());
+        true
+    })?;
 
-    if perms.deny.iter().any(|p| p == pattern) {
+    if !added {
         println!("{pattern} is already in {level} deny list");
         return Ok(());
     }
-
-    perms.deny.push(pattern.to_string());
-    write_settings(&path, &settings)?;
     println!("Added {pattern} to {level} deny list");
     Ok(())
 }
@@ -306,4 +342,53 @@ mod tests {
         let settings = read_settings(&path).unwrap().unwrap();
         assert!(settings.permissions.is_none());
     }
+
+    #[test]
+    fn update_settings_reapplies_over_a_concurrent_edit() {
+        let dir = setup_temp_dir();
+        let path = dir.path().join("settings.json");
+        fs::write(&path, r#"{"permissions": {"allow": ["Read"]}}"#).unwrap();
+
+        // The first pass races with another writer adding its own rule
+        let mut calls = 0;
+        let added = update_settings(&path, |settings| {
+            calls += 1;
+            if calls == 1 {
+                fs::write(&path, r#"{"permissions": {"allow": ["Read", "Edit"]}}"#).unwrap();
+            }
+            settings
+                .permissions
+                .get_or_insert_with(Permissions::default)
+                .allow
+                .push("Bash(cargo test)".into());
+            true
+        })
+        .unwrap();
+
+        assert!(added);
+        assert_eq!(calls, 2);
+        let allow = read_settings(&path)
+            .unwrap()
+            .unwrap()
+            .permissions
+            .unwrap()
+            .allow;
+        assert_eq!(allow, vec!["Read", "Edit", "Bash(cargo test)"]);
+    }
+
+    #[test]
+    fn update_settings_gives_up_when_the_file_keeps_changing() {
+        let dir = setup_temp_dir();
+        let path = dir.path().join("settings.json");
+        let mut n = 0;
+        let err = update_settings(&path, |_| {
+            n += 1;
+            fs::write(&path, format!(r#"{{"n": {n}}}"#)).unwrap();
+            true
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("kept changing"));
+        assert_eq!(n, file_stamp::MAX_ATTEMPTS);
+        assert_eq!(fs::read_to_string(&path).unwrap(), r#"{"n": 3}"#);
+    }
 }
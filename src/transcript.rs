@@ -0,0 +1,398 @@
+//! Per-session transcript of raw Claude stream lines.
+//!
+//! Every line a worker's child process emits (stdout NDJSON and `[stderr]` lines)
+//! is appended to `<log dir>/transcripts/<session_id>.log` as `<worker>\t<line>`,
+//! along with a `ralph_prompt` line holding the exact prompt of each iteration.
+//! `ralph attach` tails this file to render another session read-only.
+//!
+//! With `[encryption] age_recipients` set, the lines are piped through `age`
+//! into `<session_id>.log.age` instead, which can't be attached to.
+//!
+//! Transcripts are pruned like the logs: after
+//! [`logging::RETENTION_DAYS`] days, and oldest first while they take more
+//! than [`MAX_TOTAL_BYTES`].
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime};
+
+use tracing::{debug, warn};
+
+use crate::encryption;
+use crate::logging;
+
+/// How often the tail thread checks for new lines after reaching EOF.
+const TAIL_POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// How much disk all transcripts together may take.
+pub const MAX_TOTAL_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Directory holding all session transcripts.
+pub fn transcript_dir() -> Option<PathBuf> {
+    logging::log_directory().map(|d| d.join("transcripts"))
+}
+
+/// Transcript file path for a session.
+pub fn transcript_path(session_id: &str) -> Option<PathBuf> {
+    transcript_dir().map(|d| d.join(format!("{session_id}.log")))
+}
+
+/// Appends a session's worker output lines to its transcript file.
+pub struct TranscriptWriter {
+    file: Box<dyn Write>,
+    path: PathBuf,
+    /// `age` encrypting the lines on their way to `path`.
+    age: Option<Child>,
+}
+
+impl TranscriptWriter {
+    /// Open (creating if needed) the transcript for `session_id`, encrypted
+    /// to `recipients` when there are any.
+    ///
+    /// Returns `None` and logs a warning if the file can't be opened — a missing
+    /// transcript only disables `ralph attach`, never the run itself.
+    pub fn open(session_id: &str, recipients: &[String]) -> Option<Self> {
+        let mut path = transcript_path(session_id)?;
+        if let Some(parent) = path.parent()
+            && let Err(e) = std::fs::create_dir_all(parent)
+        {
+            warn!(path = %path.display(), error = %e, "transcript_open_failed");
+            return None;
+        }
+        let result = if recipients.is_empty() {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map(|file| (Box::new(file) as Box<dyn Write>, None))
+        } else {
+            path = encryption::encrypted_path(&path);
+            encryption::spawn_writer(recipients, &path).and_then(|mut child| {
+                let stdin = child.stdin.take().ok_or(std::io::ErrorKind::BrokenPipe)?;
+                Ok((Box::new(stdin) as Box<dyn Write>, Some(child)))
+            })
+        };
+        match result {
+            Ok((file, age)) => Some(Self { file, path, age }),
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "transcript_open_failed");
+                None
+            }
+        }
+    }
+
+    /// Where the transcript is written.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record the prompt worker `w` started `iteration` with, so later edits
+    /// to the prompt file don't change what the transcript says was sent.
+    pub fn record_prompt(&mut self, w: usize, iteration: u32, prompt: &str) {
+        let snapshot = serde_json::json!({
+            "type": "ralph_prompt",
+            "iteration": iteration,
+            "prompt": prompt,
+        });
+        self.record(w, &snapshot.to_string());
+    }
+
+    /// Record one output line from worker `w`.
+    pub fn record(&mut self, w: usize, line: &str) {
+        if let Err(e) = writeln!(self.file, "{}", encode_line(w, line)) {
+            warn!(error = %e, "transcript_write_failed");
+        }
+    }
+}
+
+impl Drop for TranscriptWriter {
+    /// Close `age`'s input so it finishes the encrypted file.
+    fn drop(&mut self) {
+        if let Some(mut age) = self.age.take() {
+            self.file = Box::new(std::io::sink());
+            let _ = age.wait();
+        }
+    }
+}
+
+/// Encode a worker line as `<worker>\t<line>`.
+fn encode_line(w: usize, line: &str) -> String {
+    format!("{w}\t{line}")
+}
+
+/// Decode a transcript line back into `(worker, line)`.
+pub fn decode_line(raw: &str) -> Option<(usize, String)> {
+    let (w, line) = raw.split_once('\t')?;
+    Some((w.parse().ok()?, line.to_string()))
+}
+
+/// A transcript being followed; the reader thread ends when this is dropped.
+pub struct Tail {
+    rx: Receiver<(usize, String)>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Tail {
+    /// Decoded `(worker, line)` pairs, in transcript order.
+    pub fn lines(&self) -> &Receiver<(usize, String)> {
+        &self.rx
+    }
+}
+
+impl Drop for Tail {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Follow a transcript from the beginning, sending each decoded line.
+///
+/// The background thread replays existing content, then polls for appended
+/// lines until the [`Tail`] is dropped. Partial lines (no trailing newline yet)
+/// are held until the writer finishes them.
+pub fn tail(path: PathBuf) -> std::io::Result<Tail> {
+    let file = File::open(&path)?;
+    let (tx, rx) = mpsc::channel();
+    let stop = Arc::new(AtomicBool::new(false));
+    let stopped = Arc::clone(&stop);
+    let thread = std::thread::spawn(move || {
+        let mut reader = BufReader::new(file);
+        let mut pending = String::new();
+        while !stopped.load(Ordering::Relaxed) {
+            match reader.read_line(&mut pending) {
+                Ok(0) => std::thread::sleep(TAIL_POLL_INTERVAL),
+                Ok(_) if pending.ends_with('\n') => {
+                    let raw = pending.trim_end_matches(['\n', '\r']);
+                    if let Some(decoded) = decode_line(raw)
+                        && tx.send(decoded).is_err()
+                    {
+                        return;
+                    }
+                    pending.clear();
+                }
+                Ok(_) => std::thread::sleep(TAIL_POLL_INTERVAL),
+                Err(e) => {
+                    warn!(error = %e, "transcript_tail_failed");
+                    return;
+                }
+            }
+        }
+    });
+    Ok(Tail {
+        rx,
+        stop,
+        thread: Some(thread),
+    })
+}
+
+/// Delete transcripts older than the log retention, then the oldest ones
+/// while all of them together exceed [`MAX_TOTAL_BYTES`]. `keep` (the
+/// current session's) is never deleted.
+pub fn prune(keep: &str) {
+    if let Some(dir) = transcript_dir() {
+        prune_dir(&dir, keep, SystemTime::now(), MAX_TOTAL_BYTES);
+    }
+}
+
+fn prune_dir(dir: &Path, keep: &str, now: SystemTime, max_total_bytes: u64) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let retention = Duration::from_secs(logging::RETENTION_DAYS * 24 * 60 * 60);
+    let mut files: Vec<(SystemTime, u64, PathBuf)> = entries
+        .filter_map(Result::ok)
+        .filter(|e| {
+            let name = e.file_name();
+            !name
+                .to_string_lossy()
+                .strip_prefix(keep)
+                .is_some_and(|rest| rest.starts_with(".log"))
+        })
+        .filter_map(|e| {
+            let metadata = e.metadata().ok().filter(|m| m.is_file())?;
+            Some((metadata.modified().ok()?, metadata.len(), e.path()))
+        })
+        .collect();
+    // Newest first, so the running total reaches the cap at the oldest
+    files.sort_by_key(|f| std::cmp::Reverse(f.0));
+    let mut total = 0;
+    for (modified, len, path) in files {
+        total += len;
+        let expired = now
+            .duration_since(modified)
+            .is_ok_and(|age| age > retention);
+        if !expired && total <= max_total_bytes {
+            continue;
+        }
+        match std::fs::remove_file(&path) {
+            Ok(()) => debug!(path = %path.display(), "transcript_pruned"),
+            Err(e) => warn!(path = %path.display(), error = %e, "transcript_prune_failed"),
+        }
+    }
+}
+
+/// Session IDs that have transcripts, most recently modified first.
+pub fn list_sessions() -> Vec<String> {
+    transcript_dir().map_or_else(Vec::new, |dir| sessions_in(&dir, true))
+}
+
+/// Session IDs with a plaintext transcript `ralph attach` can follow, most
+/// recently modified first.
+pub fn list_attachable_sessions() -> Vec<String> {
+    transcript_dir().map_or_else(Vec::new, |dir| sessions_in(&dir, false))
+}
+
+fn sessions_in(dir: &Path, include_encrypted: bool) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut sessions: Vec<(std::time::SystemTime, String)> = entries
+        .filter_map(Result::ok)
+        .filter_map(|e| {
+            let file_name = e.file_name();
+            let name = file_name.to_str()?;
+            let name = match name.strip_suffix(encryption::EXTENSION) {
+                Some(_) if !include_encrypted => return None,
+                Some(plain) => plain,
+                None => name,
+            };
+            let name = name.strip_suffix(".log")?.to_string();
+            let modified = e.metadata().and_then(|m| m.modified()).ok()?;
+            Some((modified, name))
+        })
+        .collect();
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.0));
+    sessions.into_iter().map(|(_, name)| name).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_round_trip() {
+        let line = r#"{"type":"system","subtype":"init"}"#;
+        assert_eq!(
+            decode_line(&encode_line(3, line)),
+            Some((3, line.to_string()))
+        );
+    }
+
+    #[test]
+    fn decode_keeps_tabs_inside_line() {
+        assert_eq!(
+            decode_line("0\t[stderr] a\tb"),
+            Some((0, "[stderr] a\tb".to_string()))
+        );
+    }
+
+    #[test]
+    fn prompt_snapshot_replays_as_event() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s.log");
+        let mut writer = TranscriptWriter {
+            file: Box::new(File::create(&path).unwrap()),
+            path: path.clone(),
+            age: None,
+        };
+        writer.record_prompt(1, 4, "line one\n\tline two");
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        let (w, line) = decode_line(raw.trim_end()).unwrap();
+        assert_eq!(w, 1);
+        let Ok(ralph_core::events::ClaudeEvent::PromptSnapshot(snapshot)) =
+            serde_json::from_str(&line)
+        else {
+            panic!("expected prompt snapshot: {line}");
+        };
+        assert_eq!(snapshot.iteration, 4);
+        assert_eq!(snapshot.prompt, "line one\n\tline two");
+    }
+
+    #[test]
+    fn decode_rejects_malformed() {
+        assert_eq!(decode_line("no tab here"), None);
+        assert_eq!(decode_line("x\tline"), None);
+    }
+
+    #[test]
+    fn tail_replays_and_follows_appended_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s.log");
+        std::fs::write(&path, "0\tfirst\n").unwrap();
+
+        let tail = tail(path.clone()).unwrap();
+        let rx = tail.lines();
+        let timeout = Duration::from_secs(2);
+        assert_eq!(rx.recv_timeout(timeout).unwrap(), (0, "first".to_string()));
+
+        let mut f = OpenOptions::new().append(true).open(&path).unwrap();
+        write!(f, "1\tsec").unwrap();
+        f.flush().unwrap();
+        writeln!(f, "ond").unwrap();
+        assert_eq!(rx.recv_timeout(timeout).unwrap(), (1, "second".to_string()));
+
+        // The reader stops once the tail is dropped, even while idle at EOF
+        let started = std::time::Instant::now();
+        drop(tail);
+        assert!(started.elapsed() < timeout);
+    }
+
+    #[test]
+    fn prune_drops_expired_then_oldest_over_the_cap() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = SystemTime::now();
+        let day = Duration::from_secs(24 * 60 * 60);
+        for (name, age_days) in [
+            ("cur.log", 30),
+            ("gone.log", 8),
+            ("old.log", 3),
+            ("mid.log", 2),
+            ("new.log", 1),
+        ] {
+            let path = dir.path().join(name);
+            std::fs::write(&path, "0\tx\n").unwrap();
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - day * age_days)
+                .unwrap();
+        }
+
+        prune_dir(dir.path(), "cur", now, 8);
+        let mut left: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        left.sort();
+        assert_eq!(left, ["cur.log", "mid.log", "new.log"]);
+    }
+
+    #[test]
+    fn encrypted_sessions_are_listed_but_not_attachable() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = SystemTime::now();
+        for (name, age_secs) in [("plain.log", 2), ("sealed.log.age", 1), ("notes.txt", 0)] {
+            let path = dir.path().join(name);
+            std::fs::write(&path, "").unwrap();
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - Duration::from_secs(age_secs))
+                .unwrap();
+        }
+        assert_eq!(sessions_in(dir.path(), true), ["sealed", "plain"]);
+        assert_eq!(sessions_in(dir.path(), false), ["plain"]);
+    }
+}
@@ -1,3 +1,6 @@
+use std::time::SystemTime;
+
+use ralph_core::run_loop::iteration_timeout;
 use ratatui::Frame;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Style};
@@ -5,12 +8,22 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
 use crate::app::{App, AppStatus};
+use crate::debug_overlay::draw_debug_overlay;
 use crate::modals::{
-    draw_bead_picker, draw_config_modal, draw_help_modal, draw_init_modal, draw_kanban_board,
-    draw_quit_modal, draw_tool_allow_modal, draw_workers_stream,
+    draw_auth_modal, draw_bead_picker, draw_bead_search, draw_config_modal, draw_diagnostics_modal,
+    draw_dirty_start_modal, draw_help_modal, draw_init_modal, draw_instructions_modal,
+    draw_kanban_board, draw_lock_conflict_modal, draw_mark_done_modal, draw_new_tab_modal,
+    draw_question_reply_modal, draw_quit_modal, draw_rerun_modal, draw_restore_modal,
+    draw_tool_allow_modal, draw_workers_stream,
 };
 
+use unicode_width::UnicodeWidthStr;
+
+use super::shortcuts::{self, Shortcut};
+use super::text::truncate_to_width;
+use super::theme::status_marker;
 use super::tool_display::format_elapsed;
+use crate::wake_lock::WakeLockStatus;
 
 /// Calculate a centered rectangle within the given area.
 pub fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
@@ -19,92 +32,293 @@ pub fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     Rect::new(x, y, width.min(area.width), height.min(area.height))
 }
 
-/// Draw the main UI.
-pub fn draw_ui(f: &mut Frame, app: &mut App) {
-    use ratatui::layout::{Constraint, Direction, Layout};
+/// Lay out the command panel: shortcuts on the left, hint centred, status on the right.
+///
+/// All widths are display columns. `shortcuts` are shown with labels and
+/// followed by "? Help". When they don't all fit, the primary ones stay and
+/// the rest are split into pages ending in "? more", showing `page` (modulo
+/// the page count). While a hint is up only the primary shortcuts are shown,
+/// falling back to bare keys ("S q ?") and, on extremely narrow terminals,
+/// to just the status. The hint is truncated to whatever space is left
+/// rather than pushing the status off-screen. Status icons (conversation
+/// mode, wake lock) sit just left of the status marker, and are dropped
+/// together when they don't fit.
+///
+/// Returns the spans and the number of pages.
+#[allow(clippy::too_many_arguments)]
+fn command_bar_spans(
+    inner_width: usize,
+    shortcuts: &[Shortcut],
+    page: usize,
+    hint: Option<&str>,
+    icons: Vec<Span<'static>>,
+    status_text: &str,
+    status_color: Color,
+    status_marker: &str,
+) -> (Vec<Span<'static>>, usize) {
+    let key_style = Style::default().fg(Color::Cyan);
+    let label_style = Style::default().fg(Color::DarkGray);
+    let labelled = |shortcuts: &[&Shortcut], help: &str| -> Vec<Span<'static>> {
+        let mut spans = Vec::new();
+        for s in shortcuts {
+            spans.push(Span::styled(s.key, key_style));
+            spans.push(Span::styled(format!(" {}  ", s.label), label_style));
+        }
+        spans.push(Span::styled("?", key_style));
+        spans.push(Span::styled(format!(" {help}"), label_style));
+        spans
+    };
+    let shortcut_width = |s: &Shortcut| s.key.width() + s.label.width() + 3;
 
-    let command_height = 3u16; // Fixed: border + 1 content + border
+    let icons_width: usize = icons.iter().map(|icon| icon.width() + 1).sum();
+    let (icons, icons_width) =
+        if icons_width + status_marker.width() + status_text.width() <= inner_width {
+            (icons, icons_width)
+        } else {
+            (Vec::new(), 0)
+        };
+    let status_width = icons_width + status_marker.width() + status_text.width();
+    let spans_width = |spans: &[Span]| -> usize { spans.iter().map(|s| s.width()).sum() };
 
-    // Two-level layout: content area (flexible) + command bar (fixed)
-    let outer = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Min(5),                 // Content area
-            Constraint::Length(command_height), // Command panel
-        ])
-        .split(f.area());
+    // Keep at least one column between the shortcuts and the status.
+    let budget = inner_width.saturating_sub(status_width + 1);
+    let primary: Vec<&Shortcut> = shortcuts.iter().filter(|s| s.primary).collect();
+    let primary_width: usize = primary.iter().map(|s| shortcut_width(s)).sum();
+    let all: Vec<&Shortcut> = shortcuts.iter().collect();
+    let pages = if hint.is_some() {
+        Vec::new()
+    } else if spans_width(&labelled(&all, "Help")) <= budget {
+        vec![all]
+    } else {
+        // Every page keeps the primary keys and ends with "? more"
+        let room = budget.saturating_sub(primary_width + "? more".width());
+        let mut pages: Vec<Vec<&Shortcut>> = Vec::new();
+        let mut used = room;
+        for s in shortcuts.iter().filter(|s| !s.primary) {
+            let width = shortcut_width(s);
+            if width > room {
+                continue;
+            }
+            if used + width > room {
+                pages.push(primary.clone());
+                used = 0;
+            }
+            pages.last_mut().expect("a page was just pushed").push(s);
+            used += width;
+        }
+        pages
+    };
+    let page_count = pages.len().max(1);
+    let command_spans = match pages.len() {
+        0 => {
+            let full = labelled(&primary, "Help");
+            let mut compact = Vec::new();
+            for s in &primary {
+                compact.push(Span::styled(s.key, key_style));
+                compact.push(Span::raw(" "));
+            }
+            compact.push(Span::styled("?", key_style));
+            if spans_width(&full) <= budget {
+                full
+            } else if spans_width(&compact) <= budget {
+                compact
+            } else {
+                Vec::new()
+            }
+        }
+        1 if pages[0].len() == shortcuts.len() => labelled(&pages[0], "Help"),
+        n => labelled(&pages[page % n], "more"),
+    };
+    let commands_width = spans_width(&command_spans);
 
-    let content_area = outer[0];
-    let command_area = outer[1];
+    // The hint gets a column of breathing room on each side, or nothing at all.
+    let hint_room = inner_width
+        .saturating_sub(commands_width + status_width)
+        .saturating_sub(2);
+    let hint_text = hint
+        .filter(|_| hint_room > 0)
+        .map(|h| truncate_to_width(h, hint_room, "…"))
+        .unwrap_or_default();
 
-    // === Board (primary content area) ===
-    draw_kanban_board(f, app, content_area);
+    let remaining = inner_width.saturating_sub(commands_width + hint_text.width() + status_width);
+    let left_pad = remaining / 2;
+    let right_pad = remaining.saturating_sub(left_pad);
 
-    // === Command Panel ===
-    let w = app.selected_worker;
-    let key_style = Style::default().fg(Color::Cyan);
-    let label_style = Style::default().fg(Color::DarkGray);
+    let mut line_spans = command_spans;
+    line_spans.push(Span::raw(" ".repeat(left_pad)));
+    if !hint_text.is_empty() {
+        line_spans.push(Span::styled(hint_text, Style::default().fg(Color::Yellow)));
+    }
+    line_spans.push(Span::raw(" ".repeat(right_pad)));
+    for icon in icons {
+        line_spans.push(icon);
+        line_spans.push(Span::raw(" "));
+    }
+    line_spans.push(Span::styled(
+        status_marker.to_string(),
+        Style::default().fg(status_color),
+    ));
+    line_spans.push(Span::styled(
+        status_text.to_string(),
+        Style::default().fg(status_color),
+    ));
+    (line_spans, page_count)
+}
 
-    let start_stop_label = match app.status {
-        AppStatus::Running => "Stop",
-        _ => "Start",
-    };
+/// Icon for the conversation mode: loop arrow when iterations continue the
+/// previous Claude conversation, a plain dot when each starts fresh.
+fn conversation_mode_icon(resume: bool) -> Span<'static> {
+    if resume {
+        Span::styled("↻", Style::default().fg(Color::Cyan))
+    } else {
+        Span::styled("○", Style::default().fg(Color::DarkGray))
+    }
+}
+
+/// Icon for the wake lock state: sun when held, moon when not, red when acquisition failed.
+fn wake_lock_icon(status: &WakeLockStatus) -> Span<'static> {
+    match status {
+        WakeLockStatus::Held => Span::styled("☀", Style::default().fg(Color::Yellow)),
+        WakeLockStatus::NotHeld => Span::styled("☾", Style::default().fg(Color::DarkGray)),
+        WakeLockStatus::Unsupported => Span::styled("⊘", Style::default().fg(Color::DarkGray)),
+        WakeLockStatus::Error(_) => Span::styled("☀", Style::default().fg(Color::Red)),
+    }
+}
+
+/// Draw the tab strip over the top-left of the board border.
+///
+/// Only called when more than one tab is open. Each label is `N status`,
+/// coloured by that tab's status; the active tab is shown inverted.
+pub fn draw_tab_strip(f: &mut Frame, labels: &[(&str, Color)], active: usize) {
+    let area = f.area();
+    let spans = tab_strip_spans(labels, active);
+    let width =
+        (spans.iter().map(|s| s.width()).sum::<usize>() as u16).min(area.width.saturating_sub(2));
+    if width == 0 || area.height == 0 {
+        return;
+    }
+    let strip = Rect::new(area.x + 1, area.y, width, 1);
+    f.render_widget(Paragraph::new(Line::from(spans)), strip);
+}
+
+fn tab_strip_spans(labels: &[(&str, Color)], active: usize) -> Vec<Span<'static>> {
+    labels
+        .iter()
+        .enumerate()
+        .map(|(i, (status, color))| {
+            let style = if i == active {
+                Style::default().fg(Color::Black).bg(*color)
+            } else {
+                Style::default().fg(*color)
+            };
+            Span::styled(format!(" {} {} ", i + 1, status), style)
+        })
+        .collect()
+}
 
-    let status_dot = "● ";
-    let status_text = match app.status {
+/// Status shown at the right of the command panel: elapsed time while running.
+pub fn status_text(app: &App) -> String {
+    let w = app.selected_worker;
+    match app.status {
+        AppStatus::Stopped if app.quiet_hours_paused => "PAUSED: QUIET HOURS".to_string(),
+        AppStatus::Stopped if app.question_paused => "PAUSED: QUESTION".to_string(),
+        AppStatus::Stopped if app.budget_throttled_until.is_some() => {
+            "THROTTLED: BUDGET RATE".to_string()
+        }
+        AppStatus::Stopped if app.disk_paused.is_some() => "PAUSED: DISK".to_string(),
+        AppStatus::Stopped if app.read_only => "READ-ONLY".to_string(),
         AppStatus::Stopped => "IDLE".to_string(),
         AppStatus::Starting => "STARTING".to_string(),
+        AppStatus::Running if app.workers.iter().any(|w| w.stop_deadline.is_some()) => {
+            "STOPPING".to_string()
+        }
+        AppStatus::Running if app.workers[w].paused_at.is_some() => {
+            // The timer stands still while paused
+            match (app.workers[w].run_start_time, app.workers[w].paused_at) {
+                (Some(start), Some(paused_at)) => format!(
+                    "PAUSED {}",
+                    format_elapsed(paused_at.saturating_duration_since(start))
+                ),
+                _ => "PAUSED".to_string(),
+            }
+        }
         AppStatus::Running => {
             if let Some(start_time) = app.workers[w].run_start_time {
-                format_elapsed(start_time.elapsed())
+                let elapsed = app.clock.elapsed(start_time);
+                match iteration_timeout(app.config.behavior.iteration_timeout_secs) {
+                    Some(limit) => format!(
+                        "{} ({} left)",
+                        format_elapsed(elapsed),
+                        format_elapsed(limit.saturating_sub(elapsed))
+                    ),
+                    None => format_elapsed(elapsed),
+                }
             } else {
                 "RUNNING".to_string()
             }
         }
         AppStatus::Error => {
             if let Some(start_time) = app.workers[w].run_start_time {
-                format_elapsed(start_time.elapsed())
+                format_elapsed(app.clock.elapsed(start_time))
             } else {
                 "ERROR".to_string()
             }
         }
-    };
-    let status_color = app.status.status_color();
+    }
+}
 
-    // Build command spans: "S Start  q Quit  ? Help"
-    let command_spans = vec![
-        Span::styled("S", key_style),
-        Span::styled(format!(" {}  ", start_stop_label), label_style),
-        Span::styled("q", key_style),
-        Span::styled(" Quit  ", label_style),
-        Span::styled("?", key_style),
-        Span::styled(" Help", label_style),
-    ];
-
-    let commands_len: usize = command_spans.iter().map(|s| s.content.len()).sum();
-    let inner_width = command_area.width.saturating_sub(2) as usize;
-    let status_len = status_dot.len() + status_text.len();
+/// `status` followed by the output scripts' counters, if they kept any.
+fn with_script_counters(status: String, counters: &[(String, i64)]) -> String {
+    counters.iter().fold(status, |text, (name, n)| {
+        format!("{text} \u{b7} {name} {n}")
+    })
+}
 
-    let hint_span = app
-        .hint
-        .as_ref()
-        .map(|(msg, _)| Span::styled(msg.as_str(), Style::default().fg(Color::Yellow)));
-    let hint_len = hint_span.as_ref().map_or(0, |s| s.content.len());
+/// Draw the main UI.
+pub fn draw_ui(f: &mut Frame, app: &mut App) {
+    use ratatui::layout::{Constraint, Direction, Layout};
 
-    let total_fixed = commands_len + hint_len + status_len;
-    let remaining = inner_width.saturating_sub(total_fixed);
-    let left_pad = remaining / 2;
-    let right_pad = remaining.saturating_sub(left_pad);
+    let command_height = 3u16; // Fixed: border + 1 content + border
 
-    let mut line_spans = command_spans;
-    line_spans.push(Span::raw(" ".repeat(left_pad)));
-    if let Some(span) = hint_span {
-        line_spans.push(span);
-    }
-    line_spans.push(Span::raw(" ".repeat(right_pad)));
-    line_spans.push(Span::styled(status_dot, Style::default().fg(status_color)));
-    line_spans.push(Span::styled(status_text, Style::default().fg(status_color)));
+    // Two-level layout: content area (flexible) + command bar (fixed)
+    let outer = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(5),                 // Content area
+            Constraint::Length(command_height), // Command panel
+        ])
+        .split(f.area());
+
+    let content_area = outer[0];
+    let command_area = outer[1];
 
+    // === Board (primary content area) ===
+    draw_kanban_board(f, app, content_area);
+
+    // === Command Panel ===
+    let status_text = with_script_counters(status_text(app), &app.output_scripts.counters());
+    let status_color = app.status.status_color();
+
+    let hint = app.hint.as_ref().map(|(msg, _)| msg.as_str());
+    let inner_width = command_area.width.saturating_sub(2) as usize;
+    let shortcuts = shortcuts::for_app(app);
+    let page = shortcuts::shortcut_page_at(SystemTime::now());
+    let (line_spans, pages) = command_bar_spans(
+        inner_width,
+        &shortcuts,
+        page,
+        hint,
+        vec![
+            conversation_mode_icon(app.resume_conversation),
+            wake_lock_icon(&app.wake_lock.status()),
+        ],
+        &status_text,
+        status_color,
+        status_marker(app.status, app.config.display.theme),
+    );
+
+    // A page that cycles needs a redraw when it's due, see `tick`
+    app.command_bar_page = (pages > 1).then_some(page);
     let command_line = Line::from(line_spans);
 
     // Build config error warning for bottom title
@@ -119,12 +333,27 @@ pub fn draw_ui(f: &mut Frame, app: &mut App) {
         let warning_style = Style::default().fg(Color::Yellow);
         // Truncate error to fit in bottom border
         let max_len = command_area.width.saturating_sub(4) as usize;
-        let truncated = if error.len() > max_len {
-            format!("{}…", &error[..max_len.saturating_sub(1)])
-        } else {
-            error.to_string()
-        };
+        let truncated = truncate_to_width(error, max_len, "…");
         block = block.title_bottom(Line::styled(truncated, warning_style));
+    } else {
+        if app.prompt_changed {
+            block = block.title_bottom(Line::styled(
+                " PROMPT.md changed \u{2014} will apply next iteration ",
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+        if app.status == AppStatus::Running
+            && let Some(estimate) = app.remaining_estimate()
+        {
+            let numbers = crate::number_format::NumberFormat::from(&app.config.display);
+            block = block.title_bottom(
+                Line::styled(
+                    format!(" {} ", crate::estimate::format(&estimate, &numbers)),
+                    Style::default().fg(Color::DarkGray),
+                )
+                .right_aligned(),
+            );
+        }
     }
 
     let command_panel = Paragraph::new(command_line).block(block);
@@ -166,11 +395,51 @@ pub fn draw_ui(f: &mut Frame, app: &mut App) {
         draw_bead_picker(f, app);
     }
 
+    // Bead search modal
+    if app.bead_search_state.is_some() {
+        draw_bead_search(f, app);
+    }
+
     // Workers stream modal
     if app.show_workers_stream {
         draw_workers_stream(f, app);
     }
 
+    // Re-run modal (on top of the workers stream it was opened from)
+    if app.rerun_state.is_some() {
+        draw_rerun_modal(f, app);
+    }
+
+    // Restore modal (also opened from the workers stream)
+    if app.restore_state.is_some() {
+        draw_restore_modal(f, app);
+    }
+
+    // Diagnostics modal
+    if app.diagnostics_state.is_some() {
+        draw_diagnostics_modal(f, app);
+    }
+
+    // Instruction queue modal
+    if app.instructions_state.is_some() {
+        draw_instructions_modal(f, app);
+    }
+
+    // Claude's question holds the run
+    if app.question_reply_state.is_some() {
+        draw_question_reply_modal(f, app);
+    }
+
+    // A spec looks done but is still open
+    if app.mark_done_prompt.is_some() {
+        draw_mark_done_modal(f, app);
+    }
+
+    // Project directory for a new tab
+    if app.new_tab_state.is_some() {
+        draw_new_tab_modal(f, app);
+    }
+
     // Help modal (renders on top of all other modals except quit)
     if let Some(ctx) = app.help_context {
         draw_help_modal(f, ctx);
@@ -180,4 +449,284 @@ pub fn draw_ui(f: &mut Frame, app: &mut App) {
     if app.show_quit_modal {
         draw_quit_modal(f, app);
     }
+
+    // Another instance holds the project session lock
+    if app.lock_conflict.is_some() {
+        draw_lock_conflict_modal(f, app);
+    }
+
+    // Uncommitted changes when starting a run
+    if app.dirty_start_prompt.is_some() {
+        draw_dirty_start_modal(f, app);
+    }
+
+    // A run stopped because Claude isn't logged in
+    if app.auth_failure.is_some() {
+        draw_auth_modal(f, app);
+    }
+
+    // Developer internals, above everything
+    if app.show_debug_overlay {
+        draw_debug_overlay(f, app);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_of(spans: &[Span]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    fn basic(start_stop: &'static str) -> Vec<Shortcut> {
+        vec![
+            Shortcut {
+                key: "S",
+                label: start_stop,
+                primary: true,
+            },
+            Shortcut {
+                key: "q",
+                label: "Quit",
+                primary: true,
+            },
+        ]
+    }
+
+    fn with_extras(extras: &[(&'static str, &'static str)]) -> Vec<Shortcut> {
+        let mut shortcuts = basic("Start");
+        shortcuts.extend(extras.iter().map(|&(key, label)| Shortcut {
+            key,
+            label,
+            primary: false,
+        }));
+        shortcuts
+    }
+
+    #[test]
+    fn command_bar_shows_every_shortcut_that_fits() {
+        let shortcuts = with_extras(&[("w", "Workers"), ("c", "Config")]);
+        let (spans, pages) = command_bar_spans(
+            60,
+            &shortcuts,
+            3,
+            None,
+            Vec::new(),
+            "IDLE",
+            Color::Gray,
+            "● ",
+        );
+        assert_eq!(pages, 1);
+        assert!(text_of(&spans).starts_with("S Start  q Quit  w Workers  c Config  ? Help"));
+    }
+
+    #[test]
+    fn command_bar_cycles_overflowing_shortcuts_in_pages() {
+        let shortcuts = with_extras(&[
+            ("Enter", "Preview"),
+            ("/", "Search"),
+            ("w", "Workers"),
+            ("c", "Config"),
+        ]);
+        let page = |page| {
+            let (spans, pages) = command_bar_spans(
+                55,
+                &shortcuts,
+                page,
+                None,
+                Vec::new(),
+                "IDLE",
+                Color::Gray,
+                "● ",
+            );
+            (text_of(&spans), pages)
+        };
+        let (first, pages) = page(0);
+        assert_eq!(pages, 2);
+        assert_eq!(first.width(), 55);
+        assert!(first.starts_with("S Start  q Quit  Enter Preview  / Search  ? more"));
+        let (second, _) = page(1);
+        assert!(second.starts_with("S Start  q Quit  w Workers  c Config  ? more"));
+        assert_eq!(page(2).0, first);
+    }
+
+    #[test]
+    fn command_bar_keeps_only_primary_shortcuts_beside_a_hint() {
+        let shortcuts = with_extras(&[("w", "Workers")]);
+        let (spans, pages) = command_bar_spans(
+            60,
+            &shortcuts,
+            0,
+            Some("Saved"),
+            Vec::new(),
+            "IDLE",
+            Color::Gray,
+            "● ",
+        );
+        assert_eq!(pages, 1);
+        let text = text_of(&spans);
+        assert!(text.starts_with("S Start  q Quit  ? Help"));
+        assert!(!text.contains("Workers"));
+    }
+
+    #[test]
+    fn tab_strip_numbers_tabs_from_one() {
+        let labels = [("idle", Color::Cyan), ("running", Color::Green)];
+        let spans = tab_strip_spans(&labels, 1);
+        assert_eq!(text_of(&spans), " 1 idle  2 running ");
+        assert_eq!(spans[1].style.bg, Some(Color::Green));
+        assert_eq!(spans[0].style.bg, None);
+    }
+
+    #[test]
+    fn command_bar_fills_exact_width_and_right_aligns_status() {
+        let spans = command_bar_spans(
+            60,
+            &basic("Start"),
+            0,
+            Some("Saved"),
+            Vec::new(),
+            "IDLE",
+            Color::Gray,
+            "● ",
+        )
+        .0;
+        let text = text_of(&spans);
+        assert_eq!(text.width(), 60);
+        assert!(text.starts_with("S Start  q Quit  ? Help"));
+        assert!(text.ends_with("● IDLE"));
+        assert!(text.contains("Saved"));
+    }
+
+    #[test]
+    fn command_bar_multibyte_hint_keeps_status_aligned() {
+        // "—" and "日本" are multi-byte; byte lengths would misplace the status.
+        let spans = command_bar_spans(
+            60,
+            &basic("Stop"),
+            0,
+            Some("Merged — 日本"),
+            Vec::new(),
+            "1:23",
+            Color::Green,
+            "● ",
+        )
+        .0;
+        let text = text_of(&spans);
+        assert_eq!(text.width(), 60);
+        assert!(text.ends_with("● 1:23"));
+    }
+
+    #[test]
+    fn command_bar_narrow_uses_compact_shortcuts() {
+        let spans = command_bar_spans(
+            20,
+            &basic("Start"),
+            0,
+            None,
+            Vec::new(),
+            "STARTING",
+            Color::Yellow,
+            "● ",
+        )
+        .0;
+        let text = text_of(&spans);
+        assert_eq!(text.width(), 20);
+        assert!(text.starts_with("S q ?"));
+        assert!(text.ends_with("● STARTING"));
+    }
+
+    #[test]
+    fn command_bar_tiny_shows_only_status() {
+        let spans = command_bar_spans(
+            8,
+            &basic("Start"),
+            0,
+            Some("hint"),
+            Vec::new(),
+            "IDLE",
+            Color::Gray,
+            "● ",
+        )
+        .0;
+        let text = text_of(&spans);
+        assert_eq!(text.trim_start(), "● IDLE");
+    }
+
+    #[test]
+    fn command_bar_shows_wake_icon_before_status() {
+        let icon = wake_lock_icon(&WakeLockStatus::Held);
+        let spans = command_bar_spans(
+            60,
+            &basic("Stop"),
+            0,
+            None,
+            vec![icon],
+            "1:23",
+            Color::Green,
+            "● ",
+        )
+        .0;
+        let text = text_of(&spans);
+        assert_eq!(text.width(), 60);
+        assert!(text.ends_with("☀ ● 1:23"));
+    }
+
+    #[test]
+    fn command_bar_shows_conversation_mode_before_wake_icon() {
+        let icons = vec![
+            conversation_mode_icon(true),
+            wake_lock_icon(&WakeLockStatus::Held),
+        ];
+        let spans = command_bar_spans(
+            60,
+            &basic("Stop"),
+            0,
+            None,
+            icons,
+            "1:23",
+            Color::Green,
+            "● ",
+        )
+        .0;
+        assert!(text_of(&spans).ends_with("↻ ☀ ● 1:23"));
+    }
+
+    #[test]
+    fn command_bar_drops_wake_icon_when_too_narrow() {
+        let icon = wake_lock_icon(&WakeLockStatus::NotHeld);
+        let spans = command_bar_spans(
+            7,
+            &basic("Start"),
+            0,
+            None,
+            vec![icon],
+            "IDLE",
+            Color::Gray,
+            "● ",
+        )
+        .0;
+        assert_eq!(text_of(&spans).trim_start(), "● IDLE");
+    }
+
+    #[test]
+    fn command_bar_truncates_hint_instead_of_overflowing() {
+        let long_hint = "x".repeat(200);
+        let spans = command_bar_spans(
+            50,
+            &basic("Start"),
+            0,
+            Some(&long_hint),
+            Vec::new(),
+            "IDLE",
+            Color::Gray,
+            "● ",
+        )
+        .0;
+        let text = text_of(&spans);
+        assert_eq!(text.width(), 50);
+        assert!(text.contains('…'));
+        assert!(text.ends_with("● IDLE"));
+    }
 }
@@ -0,0 +1,288 @@
+//! Reusable form widgets for modals: text inputs, choice fields, and button rows.
+//!
+//! Select, toggle, and number-stepper fields all render the same way (`< value >`
+//! while focused), so they share [`choice_spans`]; their values stay plain fields
+//! on each modal's own state.
+
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+
+use super::text::{next_boundary, prev_boundary, render_text_field, truncate_to_width};
+
+/// Colors shared by form widgets.
+#[derive(Debug, Clone, Copy)]
+pub struct FormTheme {
+    /// Focused labels, values, and primary buttons.
+    pub accent: Color,
+    /// Explicitly set values and secondary buttons.
+    pub text: Color,
+    /// Unfocused labels, inherited values, and disabled buttons.
+    pub muted: Color,
+}
+
+impl Default for FormTheme {
+    fn default() -> Self {
+        Self {
+            accent: Color::Cyan,
+            text: Color::White,
+            muted: Color::DarkGray,
+        }
+    }
+}
+
+impl FormTheme {
+    /// Field label, highlighted while its field is focused.
+    pub fn label(&self, text: &str, focused: bool) -> Span<'static> {
+        let color = if focused { self.accent } else { self.muted };
+        Span::styled(text.to_string(), Style::default().fg(color))
+    }
+}
+
+/// Single-line text input with a char-boundary-safe byte cursor.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextInput {
+    value: String,
+    cursor: usize,
+}
+
+impl TextInput {
+    /// Input holding `value`, with the cursor at the end.
+    pub fn new(value: impl Into<String>) -> Self {
+        let value = value.into();
+        let cursor = value.len();
+        Self { value, cursor }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.value.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    /// Backspace. Returns `true` if a char was removed.
+    pub fn delete_char_before(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        self.cursor = prev_boundary(&self.value, self.cursor);
+        self.value.remove(self.cursor);
+        true
+    }
+
+    /// Delete. Returns `true` if a char was removed.
+    pub fn delete_char_at(&mut self) -> bool {
+        if self.cursor >= self.value.len() {
+            return false;
+        }
+        self.value.remove(self.cursor);
+        true
+    }
+
+    pub fn cursor_left(&mut self) {
+        self.cursor = prev_boundary(&self.value, self.cursor);
+    }
+
+    pub fn cursor_right(&mut self) {
+        self.cursor = next_boundary(&self.value, self.cursor);
+    }
+
+    pub fn cursor_home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn cursor_end(&mut self) {
+        self.cursor = self.value.len();
+    }
+
+    /// Focused rendering with a block cursor, scrolled to fit `width` columns.
+    pub fn spans(&self, width: usize) -> Vec<Span<'static>> {
+        render_text_field(&self.value, self.cursor, width)
+    }
+}
+
+/// Value of a select, toggle, or number stepper: `< value >` in the accent
+/// color while focused, muted with an `(inherited)` note when not explicitly set.
+pub fn choice_spans(
+    value: &str,
+    focused: bool,
+    inherited: bool,
+    theme: &FormTheme,
+) -> Vec<Span<'static>> {
+    if focused {
+        return vec![Span::styled(
+            format!("< {} >", value),
+            Style::default().fg(theme.accent),
+        )];
+    }
+    if inherited {
+        vec![
+            Span::styled(value.to_string(), Style::default().fg(theme.muted)),
+            Span::styled(" (inherited)", Style::default().fg(theme.muted)),
+        ]
+    } else {
+        vec![Span::styled(
+            value.to_string(),
+            Style::default().fg(theme.text),
+        )]
+    }
+}
+
+/// Unfocused text field value, truncated to `width` and styled like [`choice_spans`].
+pub fn text_value_spans(
+    value: &str,
+    width: usize,
+    inherited: bool,
+    theme: &FormTheme,
+) -> Vec<Span<'static>> {
+    let color = if inherited { theme.muted } else { theme.text };
+    let mut spans = vec![Span::styled(
+        truncate_to_width(value, width, "\u{2026}"),
+        Style::default().fg(color),
+    )];
+    if inherited {
+        spans.push(Span::styled(
+            " (inherited)",
+            Style::default().fg(theme.muted),
+        ));
+    }
+    spans
+}
+
+/// Display label for a toggle value.
+pub fn toggle_label(on: bool) -> &'static str {
+    if on { "ON" } else { "OFF" }
+}
+
+/// One button in a [`button_row`].
+#[derive(Debug, Clone, Copy)]
+pub struct Button<'a> {
+    pub label: &'a str,
+    pub focused: bool,
+    /// Disabled buttons render muted and never show focus.
+    pub enabled: bool,
+    /// Primary buttons use the accent color; others use the text color.
+    pub primary: bool,
+}
+
+/// A row of ` Label ` buttons separated by four spaces, after `indent` columns.
+/// The focused button is drawn inverted.
+pub fn button_row(buttons: &[Button], indent: usize, theme: &FormTheme) -> Line<'static> {
+    let mut spans = vec![Span::raw(" ".repeat(indent))];
+    for (i, button) in buttons.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw("    "));
+        }
+        let color = if button.primary {
+            theme.accent
+        } else {
+            theme.text
+        };
+        let style = if !button.enabled {
+            Style::default().fg(theme.muted)
+        } else if button.focused {
+            Style::default().fg(Color::Black).bg(color)
+        } else {
+            Style::default().fg(color)
+        };
+        spans.push(Span::styled(format!(" {} ", button.label), style));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(spans: &[Span]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn text_input_edits_around_multibyte_chars() {
+        let mut input = TextInput::new("día");
+        input.cursor_left();
+        input.cursor_left();
+        assert_eq!(input.cursor, 1);
+        input.insert_char('é');
+        assert_eq!(input.value(), "déía");
+        assert!(input.delete_char_before());
+        assert!(input.delete_char_at());
+        assert_eq!(input.value(), "da");
+    }
+
+    #[test]
+    fn text_input_bounds_are_noops() {
+        let mut input = TextInput::default();
+        assert!(!input.delete_char_before());
+        assert!(!input.delete_char_at());
+        input.cursor_left();
+        input.cursor_right();
+        assert_eq!(input.cursor, 0);
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn text_input_home_end() {
+        let mut input = TextInput::new("abc");
+        input.cursor_home();
+        assert_eq!(input.cursor, 0);
+        input.cursor_end();
+        assert_eq!(input.cursor, 3);
+        assert_eq!(text(&input.spans(10)), "abc ");
+    }
+
+    #[test]
+    fn choice_spans_focused_inherited_and_explicit() {
+        let theme = FormTheme::default();
+        let focused = choice_spans("info", true, true, &theme);
+        assert_eq!(text(&focused), "< info >");
+        assert_eq!(focused[0].style.fg, Some(Color::Cyan));
+
+        let inherited = choice_spans("info", false, true, &theme);
+        assert_eq!(text(&inherited), "info (inherited)");
+        assert_eq!(inherited[0].style.fg, Some(Color::DarkGray));
+
+        let explicit = choice_spans("info", false, false, &theme);
+        assert_eq!(text(&explicit), "info");
+        assert_eq!(explicit[0].style.fg, Some(Color::White));
+    }
+
+    #[test]
+    fn text_value_spans_truncates() {
+        let spans = text_value_spans("abcdefgh", 5, false, &FormTheme::default());
+        assert_eq!(text(&spans), "abcd\u{2026}");
+    }
+
+    #[test]
+    fn button_row_styles_focus_and_disabled() {
+        let theme = FormTheme::default();
+        let row = button_row(
+            &[
+                Button {
+                    label: "Save",
+                    focused: true,
+                    enabled: false,
+                    primary: true,
+                },
+                Button {
+                    label: "Cancel",
+                    focused: true,
+                    enabled: true,
+                    primary: false,
+                },
+            ],
+            2,
+            &theme,
+        );
+        assert_eq!(text(&row.spans), "   Save      Cancel ");
+        assert_eq!(row.spans[1].style.bg, None);
+        assert_eq!(row.spans[3].style.bg, Some(Color::White));
+    }
+}
@@ -0,0 +1,162 @@
+//! Regex highlight rules for tool result previews (`[display.highlights]`).
+
+use std::collections::BTreeMap;
+
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+use regex::Regex;
+use tracing::warn;
+
+/// Compiled highlight rules, applied in sorted pattern order (earlier rules win overlaps).
+#[derive(Debug, Clone, Default)]
+pub struct Highlights {
+    rules: Vec<(Regex, Style)>,
+}
+
+impl Highlights {
+    /// Compile `pattern → style` rules, skipping (and logging) invalid entries.
+    pub fn compile(rules: &BTreeMap<String, String>) -> Self {
+        let rules = rules
+            .iter()
+            .filter_map(|(pattern, style)| {
+                let Some(parsed) = parse_style(style) else {
+                    warn!(pattern, style, "highlight_style_invalid");
+                    return None;
+                };
+                match Regex::new(pattern) {
+                    Ok(re) => Some((re, parsed)),
+                    Err(e) => {
+                        warn!(pattern, error = %e, "highlight_pattern_invalid");
+                        None
+                    }
+                }
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Split `text` into spans, styling substrings matched by any rule.
+    pub fn spans(&self, text: &str) -> Vec<Span<'static>> {
+        // Accept matches in rule order, dropping any that overlap an earlier one
+        let mut matches: Vec<(usize, usize, Style)> = Vec::new();
+        for (re, style) in &self.rules {
+            for m in re.find_iter(text) {
+                if m.is_empty() {
+                    continue;
+                }
+                let overlaps = matches
+                    .iter()
+                    .any(|&(s, e, _)| m.start() < e && s < m.end());
+                if !overlaps {
+                    matches.push((m.start(), m.end(), *style));
+                }
+            }
+        }
+        matches.sort_by_key(|&(start, _, _)| start);
+
+        let mut spans = Vec::new();
+        let mut cursor = 0;
+        for (start, end, style) in matches {
+            if start > cursor {
+                spans.push(Span::raw(text[cursor..start].to_string()));
+            }
+            spans.push(Span::styled(text[start..end].to_string(), style));
+            cursor = end;
+        }
+        if cursor < text.len() || spans.is_empty() {
+            spans.push(Span::raw(text[cursor..].to_string()));
+        }
+        spans
+    }
+}
+
+/// Parse a style like `"red"`, `"red bold"` or `"bold light_yellow"`.
+///
+/// Words are a color (name such as `light_red`, or `#rrggbb`) and modifiers (`bold`, `dim`,
+/// `italic`, `underlined`, `reversed`). Returns `None` for unknown words.
+pub fn parse_style(spec: &str) -> Option<Style> {
+    let mut style = Style::default();
+    let mut words = spec.split_whitespace().peekable();
+    words.peek()?;
+    for word in words {
+        let word = word.to_ascii_lowercase();
+        style = match word.as_str() {
+            "bold" => style.add_modifier(Modifier::BOLD),
+            "dim" => style.add_modifier(Modifier::DIM),
+            "italic" => style.add_modifier(Modifier::ITALIC),
+            "underlined" | "underline" => style.add_modifier(Modifier::UNDERLINED),
+            "reversed" => style.add_modifier(Modifier::REVERSED),
+            color => style.fg(color.parse::<Color>().ok()?),
+        };
+    }
+    Some(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(pairs: &[(&str, &str)]) -> Highlights {
+        Highlights::compile(
+            &pairs
+                .iter()
+                .map(|(p, s)| (p.to_string(), s.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn parse_style_colors_and_modifiers() {
+        assert_eq!(parse_style("red"), Some(Style::default().fg(Color::Red)));
+        assert_eq!(
+            parse_style("bold red"),
+            Some(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+        );
+        assert_eq!(
+            parse_style("light_yellow"),
+            Some(Style::default().fg(Color::LightYellow))
+        );
+        assert_eq!(parse_style("mauve"), None);
+        assert_eq!(parse_style("  "), None);
+    }
+
+    #[test]
+    fn spans_style_matches_and_keep_text() {
+        let h = rules(&[(r"error\[E\d+\]", "red")]);
+        let spans = h.spans("x error[E0308]: mismatched");
+        let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "x error[E0308]: mismatched");
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[1].content, "error[E0308]");
+        assert_eq!(spans[1].style.fg, Some(Color::Red));
+    }
+
+    #[test]
+    fn spans_without_matches_is_single_raw_span() {
+        let h = rules(&[("warning:", "yellow")]);
+        let spans = h.spans("all good");
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].style, Style::default());
+    }
+
+    #[test]
+    fn earlier_rule_wins_overlap() {
+        // Rules apply in (sorted) pattern order: "a+" before "ab"
+        let h = rules(&[("ab", "blue"), ("a+", "red")]);
+        let spans = h.spans("aab");
+        assert_eq!(spans[0].content, "aa");
+        assert_eq!(spans[0].style.fg, Some(Color::Red));
+        assert_eq!(spans[1].content, "b");
+    }
+
+    #[test]
+    fn invalid_rules_are_skipped() {
+        let h = rules(&[("(unclosed", "red"), ("ok", "nocolor"), ("fine", "green")]);
+        assert_eq!(h.rules.len(), 1);
+    }
+
+    #[test]
+    fn empty_text_yields_one_span() {
+        assert_eq!(Highlights::default().spans("").len(), 1);
+    }
+}
@@ -0,0 +1,168 @@
+//! Idle screen dimming / blanking (`display.idle_minutes`).
+
+use std::time::{Duration, Instant};
+
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+use unicode_width::UnicodeWidthStr;
+
+/// How often the blank-screen status line moves.
+const DRIFT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Tracks keyboard inactivity across all tabs.
+#[derive(Debug)]
+pub struct IdleTracker {
+    last_input: Instant,
+    idle: bool,
+    drift_step: u64,
+}
+
+impl Default for IdleTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdleTracker {
+    pub fn new() -> Self {
+        Self {
+            last_input: Instant::now(),
+            idle: false,
+            drift_step: 0,
+        }
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.idle
+    }
+
+    /// Record a keypress. Returns `true` if it woke the screen, in which case
+    /// the key should be swallowed rather than acted on.
+    pub fn wake(&mut self) -> bool {
+        self.last_input = Instant::now();
+        std::mem::replace(&mut self.idle, false)
+    }
+
+    /// Update idle state for `timeout` (`None` = disabled).
+    ///
+    /// Returns `true` when the screen needs a redraw: entering or leaving idle,
+    /// or the blank-screen status line moving to its next position.
+    pub fn check(&mut self, timeout: Option<Duration>) -> bool {
+        self.check_at(timeout, Instant::now())
+    }
+
+    fn check_at(&mut self, timeout: Option<Duration>, now: Instant) -> bool {
+        let Some(timeout) = timeout else {
+            return std::mem::replace(&mut self.idle, false);
+        };
+        let elapsed = now.saturating_duration_since(self.last_input);
+        if elapsed < timeout {
+            return std::mem::replace(&mut self.idle, false);
+        }
+        let step = (elapsed - timeout).as_secs() / DRIFT_INTERVAL.as_secs();
+        let changed = !self.idle || step != self.drift_step;
+        self.idle = true;
+        self.drift_step = step;
+        changed
+    }
+}
+
+/// Convert `display.idle_minutes` into a timeout (`0` = disabled).
+pub fn idle_timeout(minutes: u64) -> Option<Duration> {
+    (minutes > 0).then(|| Duration::from_secs(minutes * 60))
+}
+
+/// Dim everything already drawn this frame.
+pub fn dim_frame(f: &mut Frame) {
+    let area = f.area();
+    f.buffer_mut().set_style(
+        area,
+        Style::default()
+            .fg(Color::DarkGray)
+            .bg(Color::Reset)
+            .add_modifier(Modifier::DIM),
+    );
+}
+
+/// Clear the screen, leaving one dim status line at a position that drifts
+/// every minute so no pixel stays lit all night.
+pub fn draw_blank_screen(f: &mut Frame, tracker: &IdleTracker, status: &str) {
+    let area = f.area();
+    f.render_widget(Clear, area);
+    let text = format!("ralph · {} · any key to wake", status);
+    let width = (text.width() as u16).min(area.width);
+    if width == 0 || area.height == 0 {
+        return;
+    }
+    let (x, y) = drift_position(tracker.drift_step, area.width - width, area.height - 1);
+    f.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            text,
+            Style::default().fg(Color::DarkGray),
+        ))),
+        Rect::new(area.x + x, area.y + y, width, 1),
+    );
+}
+
+/// Position for drift step `step` within `0..=max_x` × `0..=max_y`.
+fn drift_position(step: u64, max_x: u16, max_y: u16) -> (u16, u16) {
+    let x = (step * 17) % (max_x as u64 + 1);
+    let y = (step * 7) % (max_y as u64 + 1);
+    (x as u16, y as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker_idle_for(elapsed: Duration) -> (IdleTracker, Instant) {
+        let tracker = IdleTracker::new();
+        let now = tracker.last_input + elapsed;
+        (tracker, now)
+    }
+
+    #[test]
+    fn disabled_timeout_never_idles() {
+        let (mut t, now) = tracker_idle_for(Duration::from_secs(3600));
+        assert!(!t.check_at(None, now));
+        assert!(!t.is_idle());
+    }
+
+    #[test]
+    fn goes_idle_after_timeout_and_redraws_once() {
+        let timeout = Some(Duration::from_secs(60));
+        let (mut t, now) = tracker_idle_for(Duration::from_secs(61));
+        assert!(t.check_at(timeout, now));
+        assert!(t.is_idle());
+        assert!(!t.check_at(timeout, now + Duration::from_secs(1)));
+        // Next drift step triggers another redraw
+        assert!(t.check_at(timeout, now + DRIFT_INTERVAL));
+    }
+
+    #[test]
+    fn wake_reports_whether_screen_was_idle() {
+        let (mut t, now) = tracker_idle_for(Duration::from_secs(120));
+        assert!(!t.wake());
+        t.check_at(Some(Duration::from_secs(60)), now);
+        assert!(t.wake());
+        assert!(!t.is_idle());
+    }
+
+    #[test]
+    fn idle_timeout_zero_is_disabled() {
+        assert_eq!(idle_timeout(0), None);
+        assert_eq!(idle_timeout(2), Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn drift_position_stays_in_bounds() {
+        for step in 0..200 {
+            let (x, y) = drift_position(step, 40, 10);
+            assert!(x <= 40 && y <= 10);
+        }
+        assert_eq!(drift_position(5, 0, 0), (0, 0));
+    }
+}
@@ -1,11 +1,30 @@
 //! UI rendering functions.
 
 mod draw;
+mod form;
+mod highlight;
+mod idle;
+mod palette;
+mod shortcuts;
+mod text;
+mod theme;
 mod tool_display;
 
-pub use draw::{centered_rect, draw_ui};
+pub use draw::{centered_rect, draw_tab_strip, draw_ui, status_text};
+pub use form::{
+    Button, FormTheme, TextInput, button_row, choice_spans, text_value_spans, toggle_label,
+};
+pub use highlight::Highlights;
+pub use idle::{IdleTracker, dim_frame, draw_blank_screen, idle_timeout};
+pub use palette::{detect_color_depth, fit_frame_colors};
+pub use shortcuts::shortcut_page_at;
+pub use text::{next_boundary, prev_boundary, render_text_field, truncate_to_width};
+pub use theme::apply_theme;
 pub use tool_display::{
-    ExchangeType, extract_text_from_task_result, extract_tool_summary,
-    format_assistant_header_styled, format_no_result_warning_styled, format_tool_result_styled,
-    format_tool_summary_styled, format_usage_summary,
+    ExchangeType, ToolResultPreview, extract_text_from_task_result, extract_tool_summary,
+    format_agent_question_styled, format_assistant_header_styled, format_claude_session_styled,
+    format_edit_diff_styled, format_elapsed, format_no_result_warning_styled,
+    format_prompt_context_styled, format_self_report_styled, format_session_init_styled,
+    format_spec_done_styled, format_tool_errors_styled, format_tool_input_preview_styled,
+    format_tool_summary_styled, format_unclosed_spec_styled, format_usage_summary,
 };
@@ -0,0 +1,187 @@
+//! Fit drawn colors to what the terminal can show.
+//!
+//! crossterm writes `Color::Rgb` as a 24-bit escape whether or not the
+//! terminal understands it, and 16-color terminals (common over SSH) tend to
+//! render those as black. Each frame is drawn in full color and then mapped
+//! down to the 256- or 16-color palette when the terminal is more limited.
+
+use ratatui::Frame;
+use ratatui::style::Color;
+
+use crate::config::ColorDepth;
+
+/// Guess the terminal's color depth from `COLORTERM` and `TERM`.
+pub fn detect_color_depth() -> ColorDepth {
+    depth_from_env(
+        std::env::var("COLORTERM").ok().as_deref(),
+        std::env::var("TERM").ok().as_deref(),
+    )
+}
+
+fn depth_from_env(colorterm: Option<&str>, term: Option<&str>) -> ColorDepth {
+    if matches!(colorterm, Some("truecolor" | "24bit")) {
+        return ColorDepth::TrueColor;
+    }
+    match term {
+        Some(t) if t.contains("direct") => ColorDepth::TrueColor,
+        Some(t) if t.contains("256color") => ColorDepth::Ansi256,
+        _ => ColorDepth::Ansi16,
+    }
+}
+
+/// Map every cell drawn this frame down to `depth`.
+pub fn fit_frame_colors(f: &mut Frame, depth: ColorDepth) {
+    if matches!(depth, ColorDepth::Auto | ColorDepth::TrueColor) {
+        return;
+    }
+    for cell in &mut f.buffer_mut().content {
+        cell.fg = fit_color(cell.fg, depth);
+        cell.bg = fit_color(cell.bg, depth);
+    }
+}
+
+/// The closest color `depth` can show (named colors are left alone).
+pub fn fit_color(color: Color, depth: ColorDepth) -> Color {
+    match (depth, color) {
+        (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_indexed((r, g, b))),
+        (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_ansi((r, g, b)),
+        (ColorDepth::Ansi16, Color::Indexed(i)) => nearest_ansi(indexed_rgb(i)),
+        _ => color,
+    }
+}
+
+type Rgb = (u8, u8, u8);
+
+/// Channel levels of the 6×6×6 cube in the 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The 16 ANSI colors with their usual (VGA) values.
+const ANSI: [(Color, Rgb); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (128, 0, 0)),
+    (Color::Green, (0, 128, 0)),
+    (Color::Yellow, (128, 128, 0)),
+    (Color::Blue, (0, 0, 128)),
+    (Color::Magenta, (128, 0, 128)),
+    (Color::Cyan, (0, 128, 128)),
+    (Color::Gray, (192, 192, 192)),
+    (Color::DarkGray, (128, 128, 128)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (0, 0, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channels at or below this count as black; anything brighter never maps to
+/// `Black`, so a dark but colored state doesn't vanish into the background.
+const NEAR_BLACK: u8 = 16;
+
+fn distance(a: Rgb, b: Rgb) -> u32 {
+    let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).unsigned_abs().pow(2);
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+fn nearest_ansi(rgb: Rgb) -> Color {
+    let near_black = rgb.0.max(rgb.1).max(rgb.2) <= NEAR_BLACK;
+    ANSI.iter()
+        .filter(|(color, _)| near_black || *color != Color::Black)
+        .min_by_key(|(_, value)| distance(rgb, *value))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+fn nearest_cube_level(channel: u8) -> usize {
+    (0..CUBE_LEVELS.len())
+        .min_by_key(|&i| CUBE_LEVELS[i].abs_diff(channel))
+        .unwrap_or(0)
+}
+
+fn nearest_indexed(rgb: Rgb) -> u8 {
+    let (r, g, b) = (
+        nearest_cube_level(rgb.0),
+        nearest_cube_level(rgb.1),
+        nearest_cube_level(rgb.2),
+    );
+    let cube = (16 + 36 * r + 6 * g + b) as u8;
+    let average = (u32::from(rgb.0) + u32::from(rgb.1) + u32::from(rgb.2)) / 3;
+    let gray = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+    if distance(rgb, indexed_rgb(gray)) < distance(rgb, indexed_rgb(cube)) {
+        gray
+    } else {
+        cube
+    }
+}
+
+/// The RGB value a 256-color palette index stands for.
+fn indexed_rgb(index: u8) -> Rgb {
+    match index {
+        0..16 => ANSI[usize::from(index)].1,
+        16..232 => {
+            let i = usize::from(index - 16);
+            (
+                CUBE_LEVELS[i / 36],
+                CUBE_LEVELS[i / 6 % 6],
+                CUBE_LEVELS[i % 6],
+            )
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+            (level, level, level)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_depth_from_env() {
+        assert_eq!(
+            depth_from_env(Some("truecolor"), Some("xterm")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            depth_from_env(None, Some("xterm-256color")),
+            ColorDepth::Ansi256
+        );
+        assert_eq!(
+            depth_from_env(None, Some("screen.xterm-direct")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(depth_from_env(None, Some("xterm")), ColorDepth::Ansi16);
+        assert_eq!(depth_from_env(None, None), ColorDepth::Ansi16);
+    }
+
+    #[test]
+    fn dark_colors_stay_visible_on_16_colors() {
+        let fit = |c| fit_color(c, ColorDepth::Ansi16);
+        assert_eq!(fit(Color::Rgb(128, 0, 0)), Color::Red);
+        assert_eq!(fit(Color::Rgb(25, 35, 60)), Color::Blue);
+        assert_eq!(fit(Color::Rgb(4, 4, 4)), Color::Black);
+        assert_eq!(fit(Color::Indexed(196)), Color::LightRed);
+        assert_eq!(fit(Color::Yellow), Color::Yellow);
+    }
+
+    #[test]
+    fn rgb_maps_to_cube_or_gray_ramp_on_256_colors() {
+        let fit = |c| fit_color(c, ColorDepth::Ansi256);
+        assert_eq!(fit(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+        assert_eq!(fit(Color::Rgb(128, 0, 0)), Color::Indexed(88));
+        assert_eq!(fit(Color::Rgb(128, 128, 128)), Color::Indexed(244));
+        assert_eq!(fit(Color::Cyan), Color::Cyan);
+        assert_eq!(
+            fit_color(Color::Rgb(1, 2, 3), ColorDepth::TrueColor),
+            Color::Rgb(1, 2, 3)
+        );
+    }
+
+    #[test]
+    fn indexed_rgb_round_trips_the_cube() {
+        for index in 16..=255u8 {
+            assert_eq!(nearest_indexed(indexed_rgb(index)), index);
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Keys offered in the command panel for whatever has focus.
+//!
+//! Each context lists its keys most useful first. The command bar shows as
+//! many as fit and cycles through the rest a page at a time; `?` opens the
+//! full list in the help modal.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::app::{App, AppStatus};
+use crate::modals::BoardFocus;
+
+/// How long each page of shortcuts stays up before the next one.
+pub const PAGE_INTERVAL: Duration = Duration::from_secs(4);
+
+/// One key and its command bar label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shortcut {
+    pub key: &'static str,
+    pub label: &'static str,
+    /// Kept on every page and when a hint is showing.
+    pub primary: bool,
+}
+
+const fn key(key: &'static str, label: &'static str) -> Shortcut {
+    Shortcut {
+        key,
+        label,
+        primary: false,
+    }
+}
+
+const fn primary(key: &'static str, label: &'static str) -> Shortcut {
+    Shortcut {
+        key,
+        label,
+        primary: true,
+    }
+}
+
+const BOARD: [Shortcut; 9] = [
+    key("Enter", "Preview"),
+    key("/", "Search"),
+    key("w", "Workers"),
+    key("I", "Instruct"),
+    key("X", "Close"),
+    key("b", "Depend"),
+    key("N", "New spec"),
+    key("c", "Config"),
+    key("L", "Log"),
+];
+
+const PREVIEW: [Shortcut; 2] = [key("j/k", "Scroll"), key("Esc", "Board")];
+
+const WORKERS_STREAM: [Shortcut; 7] = [
+    primary("Esc", "Close"),
+    key("j/k", "Worker"),
+    key("G", "Follow"),
+    key("z", "Wrap"),
+    key("V", "Verbose"),
+    key("p", "Prompt"),
+    key("y", "Copy"),
+];
+
+/// The keys that work right now, most useful first (without `?`, which the
+/// command bar always adds).
+pub fn for_app(app: &App) -> Vec<Shortcut> {
+    if app.show_workers_stream {
+        return WORKERS_STREAM.to_vec();
+    }
+    let mut shortcuts = vec![primary(
+        "S",
+        if app.status == AppStatus::Running {
+            "Stop"
+        } else {
+            "Start"
+        },
+    )];
+    if app.question_paused {
+        shortcuts.push(primary("a", "Answer"));
+    }
+    if app.status == AppStatus::Running {
+        let paused = app.workers.iter().any(|w| w.paused_at.is_some());
+        shortcuts.push(key("P", if paused { "Resume" } else { "Pause" }));
+    }
+    shortcuts.push(primary("q", "Quit"));
+    match app.kanban_board_state.focus {
+        BoardFocus::Board => shortcuts.extend(BOARD),
+        BoardFocus::Preview => shortcuts.extend(PREVIEW),
+    }
+    shortcuts
+}
+
+/// Which page of an overflowing shortcut list to show at `now`.
+pub fn shortcut_page_at(now: SystemTime) -> usize {
+    let secs = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    (secs / PAGE_INTERVAL.as_secs()) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pages_advance_every_interval() {
+        let at = |secs| shortcut_page_at(UNIX_EPOCH + Duration::from_secs(secs));
+        assert_eq!(at(0), at(PAGE_INTERVAL.as_secs() - 1));
+        assert_eq!(at(PAGE_INTERVAL.as_secs()), at(0) + 1);
+    }
+}
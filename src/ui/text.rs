@@ -0,0 +1,185 @@
+//! Display-width-aware text helpers.
+//!
+//! Everything here measures in terminal columns via `unicode-width` and only
+//! ever cuts on char boundaries, so CJK, emoji, and other multi-byte content
+//! can't panic a slice or overflow a fixed-width cell.
+
+use ratatui::style::{Color, Style};
+use ratatui::text::Span;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Truncates `s` to at most `max_width` columns, ending with `ellipsis` if cut.
+///
+/// The ellipsis counts toward the width. If `max_width` is narrower than the
+/// ellipsis itself, the ellipsis alone is returned.
+pub fn truncate_to_width(s: &str, max_width: usize, ellipsis: &str) -> String {
+    if s.width() <= max_width {
+        return s.to_string();
+    }
+    let budget = max_width.saturating_sub(ellipsis.width());
+    let mut out = take_width(s.chars(), budget);
+    out.push_str(ellipsis);
+    out
+}
+
+/// Collects chars from `chars` until adding the next one would exceed `budget` columns.
+fn take_width(chars: impl Iterator<Item = char>, budget: usize) -> String {
+    let mut used = 0;
+    let mut out = String::new();
+    for ch in chars {
+        let w = ch.width().unwrap_or(0);
+        if used + w > budget {
+            break;
+        }
+        used += w;
+        out.push(ch);
+    }
+    out
+}
+
+/// Splits `value` into the visible `(before, cursor, after)` pieces of a
+/// `width`-column text field, scrolling so the cursor stays in view.
+///
+/// `cursor` is a byte offset on a char boundary. At the end of the value the
+/// cursor piece is a single space. When the value overflows, the cursor sits
+/// roughly centred, shifting right only as far as the remaining text allows.
+pub fn window_at_cursor(value: &str, cursor: usize, width: usize) -> (String, String, String) {
+    let cursor = cursor.min(value.len());
+    let (pre, rest) = value.split_at(cursor);
+    let mut rest_chars = rest.chars();
+    let cursor_char = rest_chars.next().unwrap_or(' ');
+    let post = rest_chars.as_str();
+
+    let avail = width.saturating_sub(cursor_char.width().unwrap_or(1));
+    let left_budget = (avail / 2)
+        .max(avail.saturating_sub(post.width()))
+        .min(pre.width());
+    let right_budget = avail - left_budget;
+
+    let mut before: Vec<char> = Vec::new();
+    let mut used = 0;
+    for ch in pre.chars().rev() {
+        let w = ch.width().unwrap_or(0);
+        if used + w > left_budget {
+            break;
+        }
+        used += w;
+        before.push(ch);
+    }
+    let before: String = before.into_iter().rev().collect();
+    let after = take_width(post.chars(), right_budget);
+
+    (before, cursor_char.to_string(), after)
+}
+
+/// Renders a focused single-line text field with an inverted block cursor.
+pub fn render_text_field(value: &str, cursor: usize, width: usize) -> Vec<Span<'static>> {
+    let (before, cursor_char, after) = window_at_cursor(value, cursor, width);
+    vec![
+        Span::styled(before, Style::default().fg(Color::White)),
+        Span::styled(
+            cursor_char,
+            Style::default().fg(Color::Black).bg(Color::White),
+        ),
+        Span::styled(after, Style::default().fg(Color::White)),
+    ]
+}
+
+/// Byte offset of the char boundary before `cursor` (or 0).
+///
+/// A `cursor` past the end is treated as `s.len()`.
+pub fn prev_boundary(s: &str, cursor: usize) -> usize {
+    let cursor = cursor.min(s.len());
+    s[..cursor]
+        .chars()
+        .last()
+        .map_or(0, |c| cursor - c.len_utf8())
+}
+
+/// Byte offset of the char boundary after `cursor` (or `s.len()`).
+pub fn next_boundary(s: &str, cursor: usize) -> usize {
+    let cursor = cursor.min(s.len());
+    s[cursor..]
+        .chars()
+        .next()
+        .map_or(s.len(), |c| cursor + c.len_utf8())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_to_width_leaves_short_strings_alone() {
+        assert_eq!(truncate_to_width("hello", 5, "…"), "hello");
+    }
+
+    #[test]
+    fn truncate_to_width_counts_ellipsis() {
+        assert_eq!(truncate_to_width("hello world", 6, "…"), "hello…");
+        assert_eq!(truncate_to_width("hello world", 8, "..."), "hello...");
+    }
+
+    #[test]
+    fn truncate_to_width_cjk_uses_two_columns_per_char() {
+        // Each CJK char is 2 columns: 7-col budget fits 3 chars (6 cols) + "…".
+        let result = truncate_to_width("日本語テキスト", 7, "…");
+        assert_eq!(result, "日本語…");
+        assert!(result.width() <= 7);
+    }
+
+    #[test]
+    fn truncate_to_width_never_splits_wide_char() {
+        // 4-col budget minus 1 for ellipsis = 3: "a" + "🎉"(2) fits exactly.
+        assert_eq!(truncate_to_width("a🎉b🎉", 4, "…"), "a🎉…");
+        // 3-col budget: "a" fits, "🎉" would overflow → dropped, not split.
+        assert_eq!(truncate_to_width("a🎉b🎉", 3, "…"), "a…");
+    }
+
+    #[test]
+    fn truncate_to_width_narrower_than_ellipsis() {
+        assert_eq!(truncate_to_width("hello", 2, "..."), "...");
+    }
+
+    #[test]
+    fn window_fits_entirely() {
+        let (b, c, a) = window_at_cursor("abc", 1, 10);
+        assert_eq!((b.as_str(), c.as_str(), a.as_str()), ("a", "b", "c"));
+    }
+
+    #[test]
+    fn window_cursor_at_end_is_space() {
+        let (b, c, a) = window_at_cursor("abc", 3, 10);
+        assert_eq!((b.as_str(), c.as_str(), a.as_str()), ("abc", " ", ""));
+    }
+
+    #[test]
+    fn window_overflow_keeps_cursor_visible_at_end() {
+        let (b, c, a) = window_at_cursor("abcdefghij", 10, 5);
+        assert_eq!((b.as_str(), c.as_str(), a.as_str()), ("ghij", " ", ""));
+    }
+
+    #[test]
+    fn window_overflow_centres_cursor() {
+        let (b, c, a) = window_at_cursor("abcdefghij", 5, 5);
+        assert_eq!((b.as_str(), c.as_str(), a.as_str()), ("de", "f", "gh"));
+    }
+
+    #[test]
+    fn window_with_multibyte_stays_within_width() {
+        let value = "/Users/日本語/🎉/claude";
+        for (cursor, _) in value.char_indices().chain([(value.len(), ' ')]) {
+            let (b, c, a) = window_at_cursor(value, cursor, 8);
+            assert!(b.width() + c.width() + a.width() <= 8, "cursor {cursor}");
+        }
+    }
+
+    #[test]
+    fn boundaries_step_over_multibyte_chars() {
+        let s = "a—b";
+        assert_eq!(next_boundary(s, 1), 4);
+        assert_eq!(prev_boundary(s, 4), 1);
+        assert_eq!(prev_boundary(s, 0), 0);
+        assert_eq!(next_boundary(s, s.len()), s.len());
+    }
+}
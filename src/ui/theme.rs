@@ -0,0 +1,109 @@
+//! Accessible color schemes (`display.theme`).
+//!
+//! The UI is drawn with the default colors and each frame is then recolored,
+//! the same way `palette` fits colors to the terminal. Red against green is
+//! the only thing telling a running loop from a failed one in the default
+//! colors, so the accessible themes also mark the status with a symbol.
+
+use ratatui::Frame;
+use ratatui::style::Color;
+
+use crate::app::AppStatus;
+use crate::config::Theme;
+
+/// Okabe-Ito colors, distinguishable with the common forms of color blindness.
+const ORANGE: Color = Color::Rgb(230, 159, 0);
+const SKY_BLUE: Color = Color::Rgb(86, 180, 233);
+const BLUISH_GREEN: Color = Color::Rgb(0, 158, 115);
+const YELLOW: Color = Color::Rgb(240, 228, 66);
+const BLUE: Color = Color::Rgb(0, 114, 178);
+const VERMILLION: Color = Color::Rgb(213, 94, 0);
+const REDDISH_PURPLE: Color = Color::Rgb(204, 121, 167);
+
+/// Recolor every cell drawn this frame for `theme`.
+pub fn apply_theme(f: &mut Frame, theme: Theme) {
+    if theme == Theme::Default {
+        return;
+    }
+    for cell in &mut f.buffer_mut().content {
+        cell.fg = theme_color(cell.fg, theme);
+        cell.bg = theme_color(cell.bg, theme);
+    }
+}
+
+/// The color `theme` draws in place of `color`. RGB colors (diff backgrounds,
+/// result highlights) are left alone.
+pub fn theme_color(color: Color, theme: Theme) -> Color {
+    match theme {
+        Theme::Default => color,
+        Theme::Colorblind => match color {
+            Color::Green | Color::LightGreen => BLUISH_GREEN,
+            Color::Blue | Color::LightBlue => BLUE,
+            Color::Red => VERMILLION,
+            Color::LightRed => ORANGE,
+            Color::Cyan | Color::LightCyan => SKY_BLUE,
+            Color::Yellow | Color::LightYellow => YELLOW,
+            Color::Magenta | Color::LightMagenta => REDDISH_PURPLE,
+            other => other,
+        },
+        Theme::HighContrast => match color {
+            Color::Green => Color::LightGreen,
+            Color::Red => Color::LightRed,
+            Color::Cyan => Color::LightCyan,
+            Color::Yellow => Color::LightYellow,
+            Color::Blue => Color::LightBlue,
+            Color::Magenta => Color::LightMagenta,
+            Color::DarkGray | Color::Gray => Color::White,
+            other => other,
+        },
+    }
+}
+
+/// The mark before the status text: a dot in the default colors, a symbol
+/// that differs per status otherwise.
+pub fn status_marker(status: AppStatus, theme: Theme) -> &'static str {
+    if theme == Theme::Default {
+        return "● ";
+    }
+    match status {
+        AppStatus::Stopped => "■ ",
+        AppStatus::Starting => "◌ ",
+        AppStatus::Running => "▶ ",
+        AppStatus::Error => "✗ ",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn running_and_error_differ_without_red_and_green() {
+        for theme in [Theme::Colorblind, Theme::HighContrast] {
+            let running = theme_color(AppStatus::Running.status_color(), theme);
+            let error = theme_color(AppStatus::Error.status_color(), theme);
+            assert_ne!(running, error);
+            assert_ne!(
+                status_marker(AppStatus::Running, theme),
+                status_marker(AppStatus::Error, theme)
+            );
+        }
+        assert_eq!(theme_color(Color::Red, Theme::Colorblind), VERMILLION);
+        assert_eq!(theme_color(Color::Green, Theme::Colorblind), BLUISH_GREEN);
+    }
+
+    #[test]
+    fn default_theme_changes_nothing() {
+        assert_eq!(theme_color(Color::Green, Theme::Default), Color::Green);
+        assert_eq!(status_marker(AppStatus::Error, Theme::Default), "● ");
+        // Dim text is raised, RGB colors kept
+        assert_eq!(
+            theme_color(Color::DarkGray, Theme::HighContrast),
+            Color::White
+        );
+        assert_eq!(
+            theme_color(Color::Rgb(1, 2, 3), Theme::Colorblind),
+            Color::Rgb(1, 2, 3)
+        );
+    }
+}
@@ -1,13 +1,14 @@
-use std::time::Duration;
+use std::path::Path;
 
+pub use ralph_core::clock::format_elapsed;
+use ralph_core::events::SystemEvent;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
+use similar::{ChangeTag, TextDiff};
 
-/// Maximum length for truncated tool input display.
-pub const TOOL_INPUT_MAX_LEN: usize = 60;
-
-/// Maximum length for Bash command display (spec says 50 chars).
-const BASH_COMMAND_MAX_LEN: usize = 50;
+use super::highlight::Highlights;
+use crate::config::{DisplayConfig, MAX_RESULT_PREVIEW_LINES};
+use crate::number_format::NumberFormat;
 
 /// Icon for tool calls.
 const TOOL_ICON: &str = "⏺";
@@ -21,34 +22,20 @@ const ERROR_ICON: &str = "❌";
 /// Icon for warnings (no result received).
 const WARNING_ICON: &str = "⚠";
 
-/// Formats a duration as M:SS (under 1 hour) or H:MM:SS (1+ hours).
-pub fn format_elapsed(duration: Duration) -> String {
-    let total_secs = duration.as_secs();
-    let hours = total_secs / 3600;
-    let minutes = (total_secs % 3600) / 60;
-    let seconds = total_secs % 60;
+/// Unchanged lines kept around each change in an Edit diff.
+const DIFF_CONTEXT_LINES: usize = 2;
 
-    if hours > 0 {
-        format!("{}:{:02}:{:02}", hours, minutes, seconds)
-    } else {
-        format!("{}:{:02}", minutes, seconds)
-    }
-}
+/// Most diff lines shown for one Edit call.
+const MAX_DIFF_LINES: usize = 20;
 
-/// Truncates a string to the given maximum length, appending "..." if truncated.
+/// Truncates a string to the given display width, appending "..." if truncated.
+///
+/// Width is measured in terminal columns, so wide CJK/emoji chars count double
+/// and are never split.
 pub fn truncate_str(s: &str, max_len: usize) -> String {
     // Replace newlines with spaces for single-line display
     let single_line: String = s.chars().map(|c| if c == '\n' { ' ' } else { c }).collect();
-
-    if single_line.len() <= max_len {
-        single_line
-    } else {
-        let mut end = max_len.saturating_sub(3);
-        while end > 0 && !single_line.is_char_boundary(end) {
-            end -= 1;
-        }
-        format!("{}...", &single_line[..end])
-    }
+    super::text::truncate_to_width(&single_line, max_len, "...")
 }
 
 /// Formats a tool invocation for display (plain text version for tests).
@@ -63,54 +50,153 @@ pub fn format_tool_summary(tool_name: &str, input_json: &str) -> String {
         Err(_) => return format!("{} {}", TOOL_ICON, tool_name),
     };
 
-    // Extract key argument based on tool type
-    let key_arg = match tool_name {
-        "Bash" => extract_bash_arg(&input),
-        "Read" => extract_file_path(&input),
-        "Edit" => extract_file_path(&input),
-        "Write" => extract_file_path(&input),
-        "Grep" => extract_pattern(&input),
-        "Glob" => extract_pattern(&input),
-        _ => None,
-    };
-
-    match key_arg {
+    let summary = match extract_key_arg(tool_name, &input, &DisplayConfig::default()) {
         Some(arg) => format!("{} {}({})", TOOL_ICON, tool_name, arg),
         None => format!("{} {}", TOOL_ICON, tool_name),
+    };
+    match change_size(tool_name, &input) {
+        Some(size) => format!("{} ({})", summary, size),
+        None => summary,
     }
 }
 
-/// Extract command argument for Bash tool (truncated to 50 chars).
-fn extract_bash_arg(input: &serde_json::Value) -> Option<String> {
-    input
-        .get("command")
-        .and_then(|v| v.as_str())
-        .map(|cmd| truncate_str(cmd, BASH_COMMAND_MAX_LEN))
+/// The input field shown in parentheses for known tools, and its truncation width.
+fn key_arg_field(tool_name: &str, display: &DisplayConfig) -> Option<(&'static str, usize)> {
+    match tool_name {
+        "Bash" => Some(("command", display.bash_command_width)),
+        "Read" | "Edit" | "Write" => Some(("file_path", display.tool_input_width)),
+        "Grep" | "Glob" => Some(("pattern", display.tool_input_width)),
+        _ => None,
+    }
 }
 
-/// Extract file_path argument.
-fn extract_file_path(input: &serde_json::Value) -> Option<String> {
+/// Extract the key argument shown in parentheses for known tools.
+fn extract_key_arg(
+    tool_name: &str,
+    input: &serde_json::Value,
+    display: &DisplayConfig,
+) -> Option<String> {
+    let (field, max_width) = key_arg_field(tool_name, display)?;
     input
-        .get("file_path")
+        .get(field)
         .and_then(|v| v.as_str())
-        .map(|p| truncate_str(p, TOOL_INPUT_MAX_LEN))
+        .map(|s| truncate_str(s, max_width))
 }
 
-/// Extract pattern argument.
-fn extract_pattern(input: &serde_json::Value) -> Option<String> {
-    input
-        .get("pattern")
-        .and_then(|v| v.as_str())
-        .map(|p| truncate_str(p, TOOL_INPUT_MAX_LEN))
+fn count_lines(n: usize) -> String {
+    if n == 1 {
+        "1 line".to_string()
+    } else {
+        format!("{n} lines")
+    }
 }
 
-/// Maximum number of preview lines to show for tool results.
-const RESULT_PREVIEW_LINES: usize = 3;
+/// Size of the change a Write or Edit makes: `182 lines` for a Write, and
+/// `3→5 lines` for an Edit (`12→18 chars` when both sides are one line).
+fn change_size(tool_name: &str, input: &serde_json::Value) -> Option<String> {
+    let field = |name: &str| input.get(name).and_then(|v| v.as_str());
+    match tool_name {
+        "Write" => Some(count_lines(field("content")?.lines().count())),
+        "Edit" => {
+            let (old, new) = (field("old_string")?, field("new_string")?);
+            let (old_lines, new_lines) = (old.lines().count(), new.lines().count());
+            Some(if old_lines <= 1 && new_lines <= 1 {
+                format!(
+                    "{}\u{2192}{} chars",
+                    old.chars().count(),
+                    new.chars().count()
+                )
+            } else {
+                format!("{old_lines}\u{2192}{}", count_lines(new_lines))
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Decode the (possibly unterminated) string value of `field` from JSON that
+/// is still streaming in. Stops at the closing quote or at the end of input,
+/// dropping a trailing escape sequence that hasn't fully arrived.
+fn partial_string_field(partial_json: &str, field: &str) -> Option<String> {
+    let key = format!("\"{field}\"");
+    let rest = &partial_json[partial_json.find(&key)? + key.len()..];
+    let rest = rest.trim_start().strip_prefix(':')?.trim_start();
+    let mut chars = rest.strip_prefix('"')?.chars();
+    let mut value = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some('r') => {}
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    if hex.len() < 4 {
+                        break;
+                    }
+                    value.push(
+                        u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .unwrap_or(char::REPLACEMENT_CHARACTER),
+                    );
+                }
+                Some(other) => value.push(other),
+                None => break,
+            },
+            _ => value.push(c),
+        }
+    }
+    Some(value)
+}
+
+/// Formats a byte count as `512 B`, `4.1 KB` or `2.3 MB`.
+fn format_size(bytes: usize) -> String {
+    match bytes {
+        0..1024 => format!("{bytes} B"),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+/// Formats a tool call whose input is still streaming, e.g.
+/// `⏺ Bash(cargo te… · 42 B`. Shows as much of the key argument as has
+/// arrived plus the size of the input so far, dimmed until the call completes.
+pub fn format_tool_input_preview_styled(
+    tool_name: &str,
+    partial_json: &str,
+    display: &DisplayConfig,
+) -> Line<'static> {
+    let cyan = Style::default().fg(Color::Cyan);
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut spans = vec![
+        Span::styled(format!("{} ", TOOL_ICON), cyan),
+        Span::styled(tool_name.to_string(), cyan.add_modifier(Modifier::DIM)),
+    ];
+    if let Some((field, max_width)) = key_arg_field(tool_name, display)
+        && let Some(arg) = partial_string_field(partial_json, field)
+    {
+        spans.push(Span::styled(
+            format!("({}\u{2026}", truncate_str(&arg, max_width)),
+            dim,
+        ));
+    }
+    spans.push(Span::styled(
+        format!(" \u{b7} {}", format_size(partial_json.len())),
+        dim,
+    ));
+    Line::from(spans)
+}
 
 /// Formats a tool invocation as a styled line.
 ///
 /// Returns a styled `Line` with cyan icon and bold cyan tool name.
-pub fn format_tool_summary_styled(tool_name: &str, input_json: &str) -> Line<'static> {
+pub fn format_tool_summary_styled(
+    tool_name: &str,
+    input_json: &str,
+    display: &DisplayConfig,
+) -> Line<'static> {
     let cyan = Style::default().fg(Color::Cyan);
     let cyan_bold = Style::default()
         .fg(Color::Cyan)
@@ -127,81 +213,138 @@ pub fn format_tool_summary_styled(tool_name: &str, input_json: &str) -> Line<'st
         }
     };
 
-    // Extract key argument based on tool type
-    let key_arg = match tool_name {
-        "Bash" => extract_bash_arg(&input),
-        "Read" => extract_file_path(&input),
-        "Edit" => extract_file_path(&input),
-        "Write" => extract_file_path(&input),
-        "Grep" => extract_pattern(&input),
-        "Glob" => extract_pattern(&input),
-        _ => None,
+    let mut spans = vec![
+        Span::styled(format!("{} ", TOOL_ICON), cyan),
+        Span::styled(tool_name.to_string(), cyan_bold),
+    ];
+    if let Some(arg) = extract_key_arg(tool_name, &input, display) {
+        spans.push(Span::raw(format!("({})", arg)));
+    }
+    if let Some(size) = change_size(tool_name, &input) {
+        spans.push(Span::styled(
+            format!(" ({})", size),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Renders an Edit call's `old_string` → `new_string` change as a compact
+/// colored unified diff. Returns no lines for other tools or unparseable input.
+pub fn format_edit_diff_styled(tool_name: &str, input_json: &str) -> Vec<Line<'static>> {
+    if tool_name != "Edit" {
+        return Vec::new();
+    }
+    let Ok(input) = serde_json::from_str::<serde_json::Value>(input_json) else {
+        return Vec::new();
+    };
+    let field = |name: &str| input.get(name).and_then(|v| v.as_str());
+    let (Some(old), Some(new)) = (field("old_string"), field("new_string")) else {
+        return Vec::new();
     };
 
-    match key_arg {
-        Some(arg) => Line::from(vec![
-            Span::styled(format!("{} ", TOOL_ICON), cyan),
-            Span::styled(tool_name.to_string(), cyan_bold),
-            Span::raw(format!("({})", arg)),
-        ]),
-        None => Line::from(vec![
-            Span::styled(format!("{} ", TOOL_ICON), cyan),
-            Span::styled(tool_name.to_string(), cyan_bold),
-        ]),
+    let dim = Style::default().fg(Color::DarkGray);
+    let diff = TextDiff::from_lines(old, new);
+    let mut lines = Vec::new();
+    for (i, group) in diff.grouped_ops(DIFF_CONTEXT_LINES).iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::from(Span::styled("\u{22ef}".to_string(), dim)));
+        }
+        for change in group.iter().flat_map(|op| diff.iter_changes(op)) {
+            let (sign, style) = match change.tag() {
+                ChangeTag::Delete => ("-", Style::default().fg(Color::Red)),
+                ChangeTag::Insert => ("+", Style::default().fg(Color::Green)),
+                ChangeTag::Equal => (" ", dim),
+            };
+            let text = change.value().trim_end_matches(['\n', '\r']);
+            lines.push(Line::from(Span::styled(format!("{sign} {text}"), style)));
+        }
+    }
+
+    if lines.len() > MAX_DIFF_LINES {
+        let hidden = lines.len() - MAX_DIFF_LINES;
+        lines.truncate(MAX_DIFF_LINES);
+        lines.push(Line::from(Span::styled(
+            format!("({hidden} more diff lines)"),
+            dim,
+        )));
     }
+    lines
 }
 
-/// Formats a tool result as styled lines.
+/// The parts of a tool result needed to render its preview at any length.
 ///
-/// Returns a vector of styled `Line`s with colored icons and dim metadata.
-pub fn format_tool_result_styled(
-    _tool_name: &str,
-    content: &str,
-    is_error: bool,
-) -> Vec<Line<'static>> {
-    let mut lines = Vec::new();
+/// Keeps at most `MAX_RESULT_PREVIEW_LINES` lines of the content so results can
+/// be re-rendered when the preview length changes without retaining full output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolResultPreview {
+    pub head: Vec<String>,
+    pub line_count: usize,
+    pub char_count: usize,
+    pub is_error: bool,
+}
 
-    let (icon, icon_style) = if is_error {
-        (ERROR_ICON, Style::default().fg(Color::Red))
-    } else {
-        (SUCCESS_ICON, Style::default().fg(Color::Green))
-    };
-    let dim = Style::default().fg(Color::DarkGray);
+impl ToolResultPreview {
+    pub fn new(content: &str, is_error: bool) -> Self {
+        Self {
+            head: content
+                .lines()
+                .take(MAX_RESULT_PREVIEW_LINES)
+                .map(str::to_string)
+                .collect(),
+            line_count: content.lines().count(),
+            char_count: content.chars().count(),
+            is_error,
+        }
+    }
 
-    if content.is_empty() {
+    /// Render as styled lines showing up to `preview_lines` lines of content,
+    /// coloring substrings matched by `highlights`.
+    pub fn styled(&self, preview_lines: usize, highlights: &Highlights) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+
+        let (icon, icon_style) = if self.is_error {
+            (ERROR_ICON, Style::default().fg(Color::Red))
+        } else {
+            (SUCCESS_ICON, Style::default().fg(Color::Green))
+        };
+        let dim = Style::default().fg(Color::DarkGray);
+
+        if self.char_count == 0 {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} ", icon), icon_style),
+                Span::styled("(empty)".to_string(), dim),
+            ]));
+            return lines;
+        }
+
+        // Build summary line with icon
         lines.push(Line::from(vec![
             Span::styled(format!("{} ", icon), icon_style),
-            Span::styled("(empty)".to_string(), dim),
+            Span::styled(
+                format!("({} lines, {} chars)", self.line_count, self.char_count),
+                dim,
+            ),
         ]));
-        return lines;
-    }
-
-    let content_lines: Vec<&str> = content.lines().collect();
-    let line_count = content_lines.len();
-    let char_count = content.len();
 
-    // Build summary line with icon
-    lines.push(Line::from(vec![
-        Span::styled(format!("{} ", icon), icon_style),
-        Span::styled(format!("({} lines, {} chars)", line_count, char_count), dim),
-    ]));
+        // Add preview lines (indented) in default color, plus highlight matches
+        for line in self.head.iter().take(preview_lines) {
+            let mut spans = vec![Span::raw("  ")];
+            spans.extend(highlights.spans(line));
+            lines.push(Line::from(spans));
+        }
 
-    // Add preview lines (indented) in default color
-    let preview_count = line_count.min(RESULT_PREVIEW_LINES);
-    for line in content_lines.iter().take(preview_count) {
-        lines.push(Line::raw(format!("  {}", line)));
-    }
+        // Add truncation indicator if needed
+        let remaining = self.line_count.saturating_sub(preview_lines);
+        if remaining > 0 {
+            lines.push(Line::from(Span::styled(
+                format!("  ({} more lines)", remaining),
+                dim,
+            )));
+        }
 
-    // Add truncation indicator if needed
-    let remaining = line_count.saturating_sub(RESULT_PREVIEW_LINES);
-    if remaining > 0 {
-        lines.push(Line::from(Span::styled(
-            format!("  ({} more lines)", remaining),
-            dim,
-        )));
+        lines
     }
-
-    lines
 }
 
 /// Returns a styled warning line for tool calls with no result.
@@ -225,6 +368,191 @@ pub fn format_assistant_header_styled() -> Line<'static> {
     ])
 }
 
+/// Estimates the token count of prompt text (~4 chars per token, rounded up).
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Returns the dim entry naming the Claude CLI session an iteration runs in.
+pub fn format_claude_session_styled(session_id: &str) -> Line<'static> {
+    Line::from(Span::styled(
+        format!("Claude session {session_id}"),
+        Style::default().fg(Color::DarkGray),
+    ))
+}
+
+/// Returns the compact header for a Claude session's `init` event, e.g.
+/// `Session: model=claude-sonnet-4-5, 14 tools, cwd=~/proj`.
+pub fn format_session_init_styled(sys: &SystemEvent) -> Line<'static> {
+    let mut parts = vec![format!(
+        "model={}",
+        sys.model.as_deref().unwrap_or("unknown")
+    )];
+    parts.push(match sys.tools.len() {
+        1 => "1 tool".to_string(),
+        n => format!("{n} tools"),
+    });
+    if let Some(cwd) = &sys.cwd {
+        parts.push(format!("cwd={}", tilde_path(cwd)));
+    }
+    if let Some(mode) = sys.permission_mode.as_deref().filter(|m| *m != "default") {
+        parts.push(format!("permissions={mode}"));
+    }
+    if !sys.mcp_servers.is_empty() {
+        let servers: Vec<String> = sys
+            .mcp_servers
+            .iter()
+            .map(|s| match s.status.as_deref() {
+                Some("connected") | None => s.name.clone(),
+                Some(status) => format!("{} ({status})", s.name),
+            })
+            .collect();
+        parts.push(format!("mcp={}", servers.join(" ")));
+    }
+    Line::from(Span::styled(
+        format!("Session: {}", parts.join(", ")),
+        Style::default().fg(Color::DarkGray),
+    ))
+}
+
+/// `path` with the home directory shown as `~`.
+fn tilde_path(path: &str) -> String {
+    dirs::home_dir()
+        .and_then(|home| {
+            let rest = Path::new(path).strip_prefix(&home).ok()?;
+            Some(if rest.as_os_str().is_empty() {
+                "~".to_string()
+            } else {
+                format!("~/{}", rest.display())
+            })
+        })
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// Returns the usage-summary line counting an iteration's failed tool results,
+/// highlighted when `flagged` (enough failures to suspect the environment).
+pub fn format_tool_errors_styled(errors: u32, results: u32, flagged: bool) -> Line<'static> {
+    let percent = u64::from(errors) * 100 / u64::from(results.max(1));
+    let text = format!("Tool errors: {errors}/{results} ({percent}%)");
+    if flagged {
+        Line::from(vec![
+            Span::styled(
+                format!("{WARNING_ICON} {text}"),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                " — environment may be broken",
+                Style::default().fg(Color::Yellow),
+            ),
+        ])
+    } else {
+        Line::from(Span::styled(text, Style::default().fg(Color::DarkGray)))
+    }
+}
+
+/// Returns the agent's `ralph-report`: a summary line, then one warning per blocker.
+pub fn format_self_report_styled(report: &crate::self_report::SelfReport) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(Span::raw(format!(
+        "Report: {}",
+        report.summary()
+    )))];
+    lines.extend(report.blockers.iter().map(|blocker| {
+        Line::from(Span::styled(
+            format!("{WARNING_ICON} Blocker: {blocker}"),
+            Style::default().fg(Color::Yellow),
+        ))
+    }));
+    lines
+}
+
+/// Returns the entry for a question Claude ended its iteration with.
+pub fn format_agent_question_styled(question: &str) -> Line<'static> {
+    Line::from(Span::styled(
+        format!("? Claude asks: {question}"),
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    ))
+}
+
+/// Icon for a spec that was closed during the run.
+const SPEC_DONE_ICON: &str = "🎉";
+
+/// Returns the entry announcing that a spec was closed, with what it cost.
+pub fn format_spec_done_styled(
+    bead_id: &str,
+    title: &str,
+    stats: &crate::spec_stats::SpecStats,
+    numbers: &NumberFormat,
+) -> Line<'static> {
+    let green_bold = Style::default()
+        .fg(Color::Green)
+        .add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+    let name = if title.is_empty() {
+        bead_id.to_string()
+    } else {
+        format!("{bead_id} {title}")
+    };
+    let iterations = match stats.iterations {
+        1 => "1 iteration".to_string(),
+        n => format!("{n} iterations"),
+    };
+    Line::from(vec![
+        Span::styled(format!("{SPEC_DONE_ICON} Spec done: {name}"), green_bold),
+        Span::styled(
+            format!(
+                "  {iterations} · {} tokens · {}",
+                numbers.count(stats.tokens),
+                numbers.cost(stats.cost_usd)
+            ),
+            dim,
+        ),
+    ])
+}
+
+/// Returns the warning for a spec that looks done but which Claude left open.
+pub fn format_unclosed_spec_styled(spec: &crate::spec_checklist::UnclosedSpec) -> Line<'static> {
+    Line::from(Span::styled(
+        format!(
+            "{WARNING_ICON} {} but it is still {}",
+            spec.reason(),
+            spec.status
+        ),
+        Style::default().fg(Color::Yellow),
+    ))
+}
+
+/// Returns the "Prompt context" entry shown at the top of each iteration.
+///
+/// Collapsed in the output stream; the workers view shows the expanded form
+/// as a header above the full prompt text.
+pub fn format_prompt_context_styled(
+    context: &str,
+    expanded: bool,
+    numbers: &NumberFormat,
+) -> Line<'static> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let (marker, hint) = if expanded {
+        ("▾ ", "  p to collapse")
+    } else {
+        ("▸ ", "  p to expand in workers view")
+    };
+    Line::from(vec![
+        Span::styled(marker.to_string(), dim),
+        Span::styled(
+            format!(
+                "Prompt context ({} tokens)",
+                numbers.count(estimate_tokens(context) as u64)
+            ),
+            dim.add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(hint.to_string(), dim),
+    ])
+}
+
 /// Formats a tool result for display (plain text version for tests).
 ///
 /// Returns a vector of lines:
@@ -246,7 +574,7 @@ pub fn format_tool_result(_tool_name: &str, content: &str, is_error: bool) -> Ve
 
     let content_lines: Vec<&str> = content.lines().collect();
     let line_count = content_lines.len();
-    let char_count = content.len();
+    let char_count = content.chars().count();
 
     // Build summary line with icon
     let icon = if is_error { ERROR_ICON } else { SUCCESS_ICON };
@@ -254,13 +582,14 @@ pub fn format_tool_result(_tool_name: &str, content: &str, is_error: bool) -> Ve
     lines.push(summary);
 
     // Add preview lines (indented)
-    let preview_count = line_count.min(RESULT_PREVIEW_LINES);
+    let preview_lines = DisplayConfig::default().result_preview_lines;
+    let preview_count = line_count.min(preview_lines);
     for line in content_lines.iter().take(preview_count) {
         lines.push(format!("  {}", line));
     }
 
     // Add truncation indicator if needed
-    let remaining = line_count.saturating_sub(RESULT_PREVIEW_LINES);
+    let remaining = line_count.saturating_sub(preview_lines);
     if remaining > 0 {
         lines.push(format!("  ({} more lines)", remaining));
     }
@@ -274,12 +603,8 @@ pub fn format_malformed_result(_tool_name: &str, raw_content: &str) -> Vec<Strin
     let mut lines = Vec::new();
     lines.push(format!("{} error parsing result", ERROR_ICON));
 
-    // Show first 100 chars of raw content
-    let truncated = if raw_content.len() > 100 {
-        format!("{}...", &raw_content[..100])
-    } else {
-        raw_content.to_string()
-    };
+    // Show first 100 columns of raw content
+    let truncated = super::text::truncate_to_width(raw_content, 103, "...");
     lines.push(format!("  {}", truncated));
 
     lines
@@ -339,9 +664,10 @@ impl std::fmt::Display for ExchangeType {
 /// ───────────────────────────────────
 /// ```
 pub fn format_usage_summary(
-    result: &crate::events::ResultEvent,
+    result: &ralph_core::events::ResultEvent,
     exchange_num: u32,
     exchange_type: ExchangeType,
+    numbers: &NumberFormat,
 ) -> String {
     let separator = "─".repeat(35);
 
@@ -349,11 +675,11 @@ pub fn format_usage_summary(
     let tokens_str = if let Some(usage) = &result.usage {
         let input = usage
             .input_tokens
-            .map(|n| n.to_string())
+            .map(|n| numbers.count(n))
             .unwrap_or_else(|| "—".to_string());
         let output = usage
             .output_tokens
-            .map(|n| n.to_string())
+            .map(|n| numbers.count(n))
             .unwrap_or_else(|| "—".to_string());
         format!("{} in / {} out", input, output)
     } else {
@@ -369,7 +695,7 @@ pub fn format_usage_summary(
     let mut parts = Vec::new();
 
     if let Some(cost) = result.total_cost_usd {
-        parts.push(format!("Cost: ${:.2}", cost));
+        parts.push(format!("Cost: {}", numbers.cost(cost)));
     }
 
     if let Some(duration_ms) = result.duration_ms {
@@ -390,57 +716,86 @@ pub fn format_usage_summary(
 }
 
 /// Extract the key argument summary from tool arguments JSON.
-pub fn extract_tool_summary(tool_name: &str, input_json: &str) -> String {
+pub fn extract_tool_summary(tool_name: &str, input_json: &str, display: &DisplayConfig) -> String {
     let input: serde_json::Value = match serde_json::from_str(input_json) {
         Ok(v) => v,
         Err(_) => return String::new(),
     };
 
-    let key_arg = match tool_name {
-        "Bash" => extract_bash_arg(&input),
-        "Read" => extract_file_path(&input),
-        "Edit" => extract_file_path(&input),
-        "Write" => extract_file_path(&input),
-        "Grep" => extract_pattern(&input),
-        "Glob" => extract_pattern(&input),
-        _ => None,
-    };
-
-    key_arg.unwrap_or_default()
+    extract_key_arg(tool_name, &input, display).unwrap_or_default()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    // format_elapsed tests
+    fn init_event(json: &str) -> SystemEvent {
+        match serde_json::from_str(json).unwrap() {
+            ralph_core::events::ClaudeEvent::System(sys) => sys,
+            other => panic!("expected system event, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn session_init_header_summarizes_the_init_event() {
+        let sys = init_event(
+            r#"{"type":"system","subtype":"init","session_id":"s1","model":"claude-sonnet-4-5",
+                "cwd":"/srv/proj","tools":["Bash","Read","Edit"],"permissionMode":"acceptEdits",
+                "mcp_servers":[{"name":"github","status":"connected"},{"name":"db","status":"failed"}]}"#,
+        );
+        assert_eq!(
+            line_text(&format_session_init_styled(&sys)),
+            "Session: model=claude-sonnet-4-5, 3 tools, cwd=/srv/proj, \
+             permissions=acceptEdits, mcp=github db (failed)"
+        );
+    }
 
     #[test]
-    fn test_format_elapsed_zero() {
-        assert_eq!(format_elapsed(Duration::from_secs(0)), "0:00");
+    fn session_init_header_tolerates_missing_fields() {
+        let sys = init_event(r#"{"type":"system","subtype":"init","session_id":"s1"}"#);
+        assert_eq!(
+            line_text(&format_session_init_styled(&sys)),
+            "Session: model=unknown, 0 tools"
+        );
     }
 
     #[test]
-    fn test_format_elapsed_seconds_only() {
-        assert_eq!(format_elapsed(Duration::from_secs(5)), "0:05");
-        assert_eq!(format_elapsed(Duration::from_secs(45)), "0:45");
-        assert_eq!(format_elapsed(Duration::from_secs(59)), "0:59");
+    fn tool_errors_line_shows_rate_and_flag() {
+        assert_eq!(
+            line_text(&format_tool_errors_styled(3, 12, false)),
+            "Tool errors: 3/12 (25%)"
+        );
+        let flagged = format_tool_errors_styled(6, 8, true);
+        assert_eq!(
+            line_text(&flagged),
+            "⚠ Tool errors: 6/8 (75%) — environment may be broken"
+        );
+        assert_eq!(flagged.spans[0].style.fg, Some(Color::Yellow));
     }
 
     #[test]
-    fn test_format_elapsed_minutes_and_seconds() {
-        assert_eq!(format_elapsed(Duration::from_secs(60)), "1:00");
-        assert_eq!(format_elapsed(Duration::from_secs(65)), "1:05");
-        assert_eq!(format_elapsed(Duration::from_secs(125)), "2:05");
-        assert_eq!(format_elapsed(Duration::from_secs(3599)), "59:59");
+    fn self_report_lists_blockers_as_warnings() {
+        let report = crate::self_report::SelfReport {
+            slice_completed: Some("parser".into()),
+            blockers: vec!["CRLF fixture".into()],
+            confidence: Some(0.7),
+            ..Default::default()
+        };
+        let lines = format_self_report_styled(&report);
+        assert_eq!(line_text(&lines[0]), "Report: parser · confidence 70%");
+        assert_eq!(line_text(&lines[1]), "⚠ Blocker: CRLF fixture");
+        assert_eq!(lines[1].spans[0].style.fg, Some(Color::Yellow));
     }
 
     #[test]
-    fn test_format_elapsed_hours() {
-        assert_eq!(format_elapsed(Duration::from_secs(3600)), "1:00:00");
-        assert_eq!(format_elapsed(Duration::from_secs(3661)), "1:01:01");
-        assert_eq!(format_elapsed(Duration::from_secs(7325)), "2:02:05");
-        assert_eq!(format_elapsed(Duration::from_secs(36000)), "10:00:00");
+    fn tilde_path_shortens_home() {
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(
+            tilde_path(&home.join("proj").display().to_string()),
+            "~/proj"
+        );
+        assert_eq!(tilde_path(&home.display().to_string()), "~");
+        assert_eq!(tilde_path("/elsewhere"), "/elsewhere");
     }
 
     // truncate_str tests
@@ -514,16 +869,141 @@ mod tests {
     }
 
     #[test]
-    fn test_truncate_str_multibyte_on_char_boundary() {
-        // max_len-3 lands exactly on a char boundary — no walk-back needed.
-        // "abc—" is 6 bytes (3 ASCII + 3-byte em dash). max_len=9 → end=6, on boundary.
+    fn test_truncate_str_multibyte_counts_columns_not_bytes() {
+        // The em dash is 3 bytes but 1 column, so 6 columns of text fit before "...".
         let input = "abc—defghij";
         let result = truncate_str(input, 9);
-        assert_eq!(result, "abc—...");
+        assert_eq!(result, "abc—de...");
+    }
+
+    #[test]
+    fn test_truncate_str_cjk_fits_display_width() {
+        // CJK chars are 2 columns wide; result must fit the column budget.
+        let result = truncate_str("abc日本語テキスト", 10);
+        assert_eq!(result, "abc日本...");
+        assert!(unicode_width::UnicodeWidthStr::width(result.as_str()) <= 10);
+    }
+
+    #[test]
+    fn test_format_malformed_result_multibyte_does_not_panic() {
+        let raw = "—".repeat(150);
+        let lines = format_malformed_result("Bash", &raw);
+        assert!(lines[1].ends_with("..."));
+    }
+
+    // format_tool_input_preview_styled tests
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_format_spec_done_shows_name_and_totals() {
+        let stats = crate::spec_stats::SpecStats {
+            iterations: 3,
+            tokens: 12_345,
+            cost_usd: 1.5,
+            ..Default::default()
+        };
+        assert_eq!(
+            line_text(&format_spec_done_styled(
+                "bd-7",
+                "Add login",
+                &stats,
+                &NumberFormat::default()
+            )),
+            "🎉 Spec done: bd-7 Add login  3 iterations · 12,345 tokens · $1.50"
+        );
+        assert!(
+            line_text(&format_spec_done_styled(
+                "bd-7",
+                "",
+                &stats,
+                &NumberFormat::default()
+            ))
+            .contains("done: bd-7  ")
+        );
+    }
+
+    #[test]
+    fn test_partial_string_field_unterminated_value() {
+        let json = r#"{"command": "cargo test \"a b\"\nls"#;
+        assert_eq!(
+            partial_string_field(json, "command").as_deref(),
+            Some("cargo test \"a b\"\nls")
+        );
+        assert_eq!(partial_string_field(r#"{"comm"#, "command"), None);
+        assert_eq!(partial_string_field(r#"{"command":"#, "command"), None);
+        assert_eq!(
+            partial_string_field(r#"{"command":""#, "command").as_deref(),
+            Some("")
+        );
+    }
+
+    #[test]
+    fn test_partial_string_field_drops_incomplete_escapes() {
+        assert_eq!(
+            partial_string_field(r#"{"file_path":"a\"#, "file_path").as_deref(),
+            Some("a")
+        );
+        assert_eq!(
+            partial_string_field(r#"{"file_path":"a\u00"#, "file_path").as_deref(),
+            Some("a")
+        );
+        assert_eq!(
+            partial_string_field(r#"{"file_path":"a\u00e9b"}"#, "file_path").as_deref(),
+            Some("a\u{e9}b")
+        );
+    }
+
+    #[test]
+    fn test_format_tool_input_preview_styled() {
+        let display = DisplayConfig::default();
+        let json = r#"{"file_path":"/tmp/out.rs","content":"fn main() {"#;
+        let text = line_text(&format_tool_input_preview_styled("Write", json, &display));
+        assert_eq!(
+            text,
+            format!("⏺ Write(/tmp/out.rs\u{2026} \u{b7} {} B", json.len())
+        );
+
+        let text = line_text(&format_tool_input_preview_styled(
+            "Task", "{\"desc", &display,
+        ));
+        assert_eq!(text, "⏺ Task \u{b7} 6 B");
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(4198), "4.1 KB");
+        assert_eq!(format_size(3 * 1_048_576), "3.0 MB");
     }
 
     // format_tool_result tests
 
+    #[test]
+    fn test_estimate_tokens_rounds_up() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+
+    #[test]
+    fn test_estimate_tokens_counts_chars_not_bytes() {
+        assert_eq!(estimate_tokens("————"), 1);
+    }
+
+    #[test]
+    fn test_format_prompt_context_styled() {
+        let line = format_prompt_context_styled(&"x".repeat(4936), false, &NumberFormat::default());
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(text.starts_with("▸ Prompt context (1,234 tokens)"));
+
+        let line = format_prompt_context_styled("abc", true, &NumberFormat::default());
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(text.starts_with("▾ Prompt context (1 tokens)"));
+    }
+
     #[test]
     fn test_format_tool_result_empty() {
         let result = format_tool_result("Read", "", false);
@@ -571,6 +1051,48 @@ mod tests {
         assert_eq!(result[1], "  error: could not compile");
     }
 
+    #[test]
+    fn test_tool_result_preview_respects_preview_lines() {
+        let preview = ToolResultPreview::new("a\nb\nc\nd\ne", false);
+
+        let short = preview.styled(1, &Highlights::default());
+        assert_eq!(short.len(), 3);
+        assert_eq!(short[2].to_string(), "  (4 more lines)");
+
+        let long = preview.styled(10, &Highlights::default());
+        assert_eq!(long.len(), 6);
+        assert_eq!(long[5].to_string(), "  e");
+
+        let summary_only = preview.styled(0, &Highlights::default());
+        assert_eq!(summary_only.len(), 2);
+        assert_eq!(summary_only[1].to_string(), "  (5 more lines)");
+    }
+
+    #[test]
+    fn test_tool_result_preview_caps_retained_lines() {
+        let content = (0..MAX_RESULT_PREVIEW_LINES + 50)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let preview = ToolResultPreview::new(&content, false);
+        assert_eq!(preview.head.len(), MAX_RESULT_PREVIEW_LINES);
+        assert_eq!(preview.line_count, MAX_RESULT_PREVIEW_LINES + 50);
+    }
+
+    #[test]
+    fn test_format_tool_summary_styled_uses_configured_width() {
+        let display = DisplayConfig {
+            bash_command_width: 10,
+            ..Default::default()
+        };
+        let line = format_tool_summary_styled(
+            "Bash",
+            r#"{"command": "cargo test --workspace"}"#,
+            &display,
+        );
+        assert_eq!(line.to_string(), "⏺ Bash(cargo t...)");
+    }
+
     // format_tool_summary tests
 
     #[test]
@@ -591,6 +1113,66 @@ mod tests {
         assert_eq!(result, "⏺ Grep(fn main)");
     }
 
+    #[test]
+    fn test_format_tool_summary_write_counts_lines() {
+        let result = format_tool_summary(
+            "Write",
+            r#"{"file_path": "src/foo.rs", "content": "a\nb\nc\n"}"#,
+        );
+        assert_eq!(result, "⏺ Write(src/foo.rs) (3 lines)");
+    }
+
+    #[test]
+    fn test_format_tool_summary_edit_shows_old_to_new() {
+        let result = format_tool_summary(
+            "Edit",
+            r#"{"file_path": "a.rs", "old_string": "x\ny", "new_string": "x\ny\nz"}"#,
+        );
+        assert_eq!(result, "⏺ Edit(a.rs) (2\u{2192}3 lines)");
+
+        let result = format_tool_summary(
+            "Edit",
+            r#"{"file_path": "a.rs", "old_string": "foo", "new_string": "foobar"}"#,
+        );
+        assert_eq!(result, "⏺ Edit(a.rs) (3\u{2192}6 chars)");
+    }
+
+    #[test]
+    fn test_format_tool_summary_styled_dims_change_size() {
+        let line = format_tool_summary_styled(
+            "Write",
+            r#"{"file_path": "x.rs", "content": "fn main() {}"}"#,
+            &DisplayConfig::default(),
+        );
+        assert_eq!(line.to_string(), "⏺ Write(x.rs) (1 line)");
+        assert_eq!(line.spans[3].style.fg, Some(Color::DarkGray));
+    }
+
+    #[test]
+    fn test_format_edit_diff_styled_trims_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\n";
+        let new = "a\nb\nc\nD\ne\nf\ng\n";
+        let input = serde_json::json!({"file_path": "x", "old_string": old, "new_string": new});
+        let lines: Vec<String> = format_edit_diff_styled("Edit", &input.to_string())
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert_eq!(lines, vec!["  b", "  c", "- d", "+ D", "  e", "  f"]);
+    }
+
+    #[test]
+    fn test_format_edit_diff_styled_colors_and_caps() {
+        let new: String = (0..30).map(|i| format!("line {i}\n")).collect();
+        let input = serde_json::json!({"old_string": "", "new_string": new});
+        let lines = format_edit_diff_styled("Edit", &input.to_string());
+        assert_eq!(lines.len(), MAX_DIFF_LINES + 1);
+        assert_eq!(lines[0].spans[0].style.fg, Some(Color::Green));
+        assert_eq!(lines[MAX_DIFF_LINES].to_string(), "(10 more diff lines)");
+
+        assert!(format_edit_diff_styled("Write", &input.to_string()).is_empty());
+        assert!(format_edit_diff_styled("Edit", "{not json").is_empty());
+    }
+
     #[test]
     fn test_format_tool_summary_unknown_tool() {
         let result = format_tool_summary("CustomTool", r#"{"custom": "arg"}"#);
@@ -0,0 +1,166 @@
+//! View preferences remembered between launches.
+//!
+//! Stored as `ui_state.json` in the per-project config dir (next to
+//! `config.toml`). Saved from the active tab on exit and restored into every
+//! tab at startup. A missing or unreadable file just means the defaults.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::app::App;
+use crate::config::compute_project_config_path;
+
+const STATE_FILE_NAME: &str = "ui_state.json";
+
+/// Persisted view preferences.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    /// Name of the selected board column.
+    pub board_column: Option<String>,
+    /// Bead the board cursor was on.
+    pub board_bead: Option<String>,
+    /// Show every result of repeated tool calls (`x` in the workers view).
+    pub expand_repeated_tools: bool,
+    /// Soft-wrap long lines in the workers view (`z`).
+    pub stream_wrap: bool,
+    /// Show the diff under Edit tool calls (`d` in the workers view).
+    pub show_edit_diffs: bool,
+    /// Show only tool calls and assistant text (`V` in the workers view).
+    pub compact_output: bool,
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            board_column: None,
+            board_bead: None,
+            expand_repeated_tools: false,
+            stream_wrap: true,
+            show_edit_diffs: true,
+            compact_output: false,
+        }
+    }
+}
+
+impl UiState {
+    /// Snapshot the parts of `app` worth keeping.
+    pub fn capture(app: &App) -> Self {
+        let board = &app.kanban_board_state;
+        Self {
+            board_column: board
+                .column_defs
+                .get(board.selected_column)
+                .map(|c| c.name.clone()),
+            board_bead: board.selected_card().map(|c| c.id.clone()),
+            expand_repeated_tools: app.expand_repeated_tools,
+            stream_wrap: app.stream_wrap,
+            show_edit_diffs: app.show_edit_diffs,
+            compact_output: app.compact_output,
+        }
+    }
+
+    /// Apply to a freshly built `app`. The bead is selected once its column loads.
+    pub fn restore(self, app: &mut App) {
+        let board = &mut app.kanban_board_state;
+        if let Some(col) = self
+            .board_column
+            .and_then(|name| board.column_defs.iter().position(|c| c.name == name))
+        {
+            board.selected_column = col;
+        }
+        board.pending_selection = self.board_bead;
+        app.expand_repeated_tools = self.expand_repeated_tools;
+        app.stream_wrap = self.stream_wrap;
+        app.show_edit_diffs = self.show_edit_diffs;
+        app.compact_output = self.compact_output;
+    }
+}
+
+/// State file path for the project in `project_dir`.
+fn state_path(project_dir: &Path) -> Option<PathBuf> {
+    compute_project_config_path(project_dir).map(|p| p.with_file_name(STATE_FILE_NAME))
+}
+
+/// Load the saved UI state of the project in `project_dir`, or the defaults.
+pub fn load(project_dir: &Path) -> UiState {
+    state_path(project_dir)
+        .map(|p| load_from(&p))
+        .unwrap_or_default()
+}
+
+fn load_from(path: &Path) -> UiState {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return UiState::default();
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        warn!(path = %path.display(), error = %e, "ui_state_parse_failed");
+        UiState::default()
+    })
+}
+
+/// Save `state` for the project in `project_dir`, logging (not failing) on
+/// error.
+pub fn save(project_dir: &Path, state: &UiState) {
+    if let Some(path) = state_path(project_dir) {
+        save_to(&path, state);
+    }
+}
+
+fn save_to(path: &Path, state: &UiState) {
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| {
+            let json = serde_json::to_string_pretty(state).map_err(std::io::Error::other)?;
+            std::fs::write(path, json)
+        });
+    match result {
+        Ok(()) => debug!(path = %path.display(), "ui_state_saved"),
+        Err(e) => warn!(path = %path.display(), error = %e, "ui_state_save_failed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(STATE_FILE_NAME);
+        let state = UiState {
+            board_column: Some("In Progress".into()),
+            board_bead: Some("ralph-abc".into()),
+            expand_repeated_tools: true,
+            stream_wrap: false,
+            show_edit_diffs: false,
+            compact_output: true,
+        };
+        save_to(&path, &state);
+        assert_eq!(load_from(&path), state);
+    }
+
+    #[test]
+    fn missing_or_corrupt_file_gives_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATE_FILE_NAME);
+        assert_eq!(load_from(&path), UiState::default());
+        std::fs::write(&path, "{not json").unwrap();
+        assert_eq!(load_from(&path), UiState::default());
+    }
+
+    #[test]
+    fn unknown_and_missing_fields_are_tolerated() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATE_FILE_NAME);
+        std::fs::write(&path, r#"{"expand_repeated_tools": true, "panel": 3}"#).unwrap();
+        let state = load_from(&path);
+        assert!(state.expand_repeated_tools);
+        assert!(state.stream_wrap);
+        assert!(state.show_edit_diffs);
+        assert!(!state.compact_output);
+    }
+}
@@ -1,6 +1,33 @@
-//! Path validation functions for configuration fields.
+//! Validation functions for configuration fields: executable paths and
+//! numeric bounds.
+//!
+//! The config modal's steppers stay within the bounds below, but values
+//! loaded from a config file can be anything. Loading pulls them back into
+//! range with a warning ([`clamp_numbers`]), and the modal flags a project
+//! value that is out of range inline before Save or Apply.
 
-use crate::config::Config;
+use crate::config::{Config, MAX_RESULT_PREVIEW_LINES};
+
+/// `behavior.iterations`: -1 runs until stopped.
+pub const ITERATIONS_MIN: i32 = -1;
+pub const ITERATIONS_MAX: i32 = 999;
+/// `behavior.heartbeat_interval`, in seconds.
+pub const HEARTBEAT_MIN: u64 = 1;
+pub const HEARTBEAT_MAX: u64 = 9999;
+/// `behavior.stale_threshold`, in seconds. Shorter than a heartbeat or two
+/// and live agents would be reaped as stale.
+pub const STALE_MIN: u64 = 10;
+pub const STALE_MAX: u64 = 9999;
+/// `behavior.workers`.
+pub const WORKERS_MIN: u32 = 1;
+pub const WORKERS_MAX: u32 = 8;
+/// `behavior.stop_grace_secs`: 0 kills at once.
+pub const STOP_GRACE_MAX: u64 = 600;
+/// `behavior.iteration_timeout_secs` and `hooks.timeout_secs`, in seconds
+/// (a day). An iteration timeout of 0 means no limit.
+pub const TIMEOUT_MAX: u64 = 86_400;
+/// `disk.min_free_mb` and `disk.max_growth_mb` (1 TiB); 0 disables either.
+pub const DISK_MB_MAX: u64 = 1_048_576;
 
 /// Check if metadata indicates a valid executable file (pure function).
 /// Returns an error message if validation fails, None if valid.
@@ -58,10 +85,203 @@ pub fn validate_executable_path(path: &str) -> Option<String> {
     }
 }
 
+/// Error message for a `value` outside `min..=max`, naming the unit if any.
+fn check_range<T: PartialOrd + std::fmt::Display>(
+    value: T,
+    min: T,
+    max: T,
+    unit: &str,
+) -> Option<String> {
+    if value < min || value > max {
+        Some(format!("Must be {min}\u{2013}{max}{unit}"))
+    } else {
+        None
+    }
+}
+
+/// Validate an iteration count: -1 (infinite), 0 (stopped) or a countdown.
+pub fn validate_iterations(value: i32) -> Option<String> {
+    if (ITERATIONS_MIN..=ITERATIONS_MAX).contains(&value) {
+        None
+    } else {
+        Some(format!(
+            "Must be {ITERATIONS_MIN} (infinite) to {ITERATIONS_MAX}"
+        ))
+    }
+}
+
+/// Validate the agent heartbeat interval in seconds.
+pub fn validate_heartbeat_interval(secs: u64) -> Option<String> {
+    check_range(secs, HEARTBEAT_MIN, HEARTBEAT_MAX, " s")
+}
+
+/// Validate the stale agent threshold in seconds.
+pub fn validate_stale_threshold(secs: u64) -> Option<String> {
+    check_range(secs, STALE_MIN, STALE_MAX, " s")
+}
+
+/// Validate the worker count.
+pub fn validate_workers(workers: u32) -> Option<String> {
+    check_range(workers, WORKERS_MIN, WORKERS_MAX, "")
+}
+
+/// Validate the number of result lines previewed.
+pub fn validate_result_preview_lines(lines: usize) -> Option<String> {
+    check_range(lines, 0, MAX_RESULT_PREVIEW_LINES, " lines")
+}
+
+/// Validate the hourly budget in USD.
+pub fn validate_budget(usd: f64) -> Option<String> {
+    if usd.is_finite() && usd >= 0.0 {
+        None
+    } else {
+        Some("Must be 0 or more USD".to_string())
+    }
+}
+
+/// Pull out-of-range numbers in a loaded `config` back into range. Returns
+/// a message for each value changed; an invalid budget is dropped.
+pub fn clamp_numbers(config: &mut Config) -> Vec<String> {
+    let mut adjusted = Vec::new();
+    let b = &mut config.behavior;
+    clamp_into(
+        "behavior.iterations",
+        &mut b.iterations,
+        ITERATIONS_MIN,
+        ITERATIONS_MAX,
+        &mut adjusted,
+    );
+    clamp_into(
+        "behavior.heartbeat_interval",
+        &mut b.heartbeat_interval,
+        HEARTBEAT_MIN,
+        HEARTBEAT_MAX,
+        &mut adjusted,
+    );
+    clamp_into(
+        "behavior.stale_threshold",
+        &mut b.stale_threshold,
+        STALE_MIN,
+        STALE_MAX,
+        &mut adjusted,
+    );
+    clamp_into(
+        "behavior.workers",
+        &mut b.workers,
+        WORKERS_MIN,
+        WORKERS_MAX,
+        &mut adjusted,
+    );
+    clamp_into(
+        "behavior.stop_grace_secs",
+        &mut b.stop_grace_secs,
+        0,
+        STOP_GRACE_MAX,
+        &mut adjusted,
+    );
+    clamp_into(
+        "behavior.iteration_timeout_secs",
+        &mut b.iteration_timeout_secs,
+        0,
+        TIMEOUT_MAX,
+        &mut adjusted,
+    );
+    clamp_into(
+        "display.result_preview_lines",
+        &mut config.display.result_preview_lines,
+        0,
+        MAX_RESULT_PREVIEW_LINES,
+        &mut adjusted,
+    );
+    clamp_into(
+        "hooks.timeout_secs",
+        &mut config.hooks.timeout_secs,
+        1,
+        TIMEOUT_MAX,
+        &mut adjusted,
+    );
+    clamp_into(
+        "disk.min_free_mb",
+        &mut config.disk.min_free_mb,
+        0,
+        DISK_MB_MAX,
+        &mut adjusted,
+    );
+    clamp_into(
+        "disk.max_growth_mb",
+        &mut config.disk.max_growth_mb,
+        0,
+        DISK_MB_MAX,
+        &mut adjusted,
+    );
+    if let Some(usd) = config.budget.max_usd_per_hour
+        && let Some(error) = validate_budget(usd)
+    {
+        adjusted.push(format!(
+            "budget.max_usd_per_hour = {usd}: {error}; ignoring it"
+        ));
+        config.budget.max_usd_per_hour = None;
+    }
+    adjusted
+}
+
+/// Set `value` to the nearest of `min..=max`, noting the change in `adjusted`.
+fn clamp_into<T: PartialOrd + Copy + std::fmt::Display>(
+    name: &str,
+    value: &mut T,
+    min: T,
+    max: T,
+    adjusted: &mut Vec<String>,
+) {
+    let clamped = if *value < min {
+        min
+    } else if *value > max {
+        max
+    } else {
+        return;
+    };
+    adjusted.push(format!(
+        "{name} = {value} is out of range ({min}\u{2013}{max}); using {clamped}"
+    ));
+    *value = clamped;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn loaded_numbers_are_clamped_with_a_message() {
+        let mut config = Config::default();
+        config.behavior.workers = 20;
+        config.behavior.stale_threshold = 1;
+        config.hooks.timeout_secs = 0;
+        config.budget.max_usd_per_hour = Some(-5.0);
+        let adjusted = clamp_numbers(&mut config);
+        assert_eq!(config.behavior.workers, WORKERS_MAX);
+        assert_eq!(config.behavior.stale_threshold, STALE_MIN);
+        assert_eq!(config.hooks.timeout_secs, 1);
+        assert_eq!(config.budget.max_usd_per_hour, None);
+        assert_eq!(adjusted.len(), 4);
+        assert_eq!(
+            adjusted[0],
+            "behavior.stale_threshold = 1 is out of range (10\u{2013}9999); using 10"
+        );
+
+        let mut config = Config::default();
+        config.budget.max_usd_per_hour = Some(2.5);
+        assert!(clamp_numbers(&mut config).is_empty());
+        assert_eq!(config.budget.max_usd_per_hour, Some(2.5));
+    }
+
+    #[test]
+    fn budget_must_be_a_non_negative_number() {
+        assert_eq!(validate_budget(0.0), None);
+        assert_eq!(validate_budget(12.5), None);
+        assert!(validate_budget(-0.01).is_some());
+        assert!(validate_budget(f64::NAN).is_some());
+    }
+
     // Tests for check_executable_metadata (pure function)
 
     #[test]
@@ -118,7 +338,42 @@ mod tests {
 
     #[test]
     fn test_file_error_message_other_error() {
-        let error = std::io::Error::new(std::io::ErrorKind::Other, "other");
+        let error = std::io::Error::other("other");
         assert_eq!(file_error_message(&error), "Invalid path");
     }
+
+    // Tests for numeric bounds
+
+    #[test]
+    fn test_numeric_bounds_are_inclusive() {
+        assert_eq!(validate_iterations(ITERATIONS_MIN), None);
+        assert_eq!(validate_iterations(0), None);
+        assert_eq!(validate_iterations(ITERATIONS_MAX), None);
+        assert_eq!(validate_heartbeat_interval(HEARTBEAT_MIN), None);
+        assert_eq!(validate_stale_threshold(STALE_MIN), None);
+        assert_eq!(validate_stale_threshold(STALE_MAX), None);
+        assert_eq!(validate_workers(WORKERS_MAX), None);
+        assert_eq!(validate_result_preview_lines(0), None);
+    }
+
+    #[test]
+    fn test_numeric_out_of_range_names_bounds_and_unit() {
+        assert_eq!(
+            validate_iterations(-5).as_deref(),
+            Some("Must be -1 (infinite) to 999")
+        );
+        assert_eq!(
+            validate_heartbeat_interval(0).as_deref(),
+            Some("Must be 1\u{2013}9999 s")
+        );
+        assert_eq!(
+            validate_stale_threshold(5).as_deref(),
+            Some("Must be 10\u{2013}9999 s")
+        );
+        assert_eq!(validate_workers(0).as_deref(), Some("Must be 1\u{2013}8"));
+        assert_eq!(
+            validate_result_preview_lines(MAX_RESULT_PREVIEW_LINES + 1).as_deref(),
+            Some("Must be 0\u{2013}100 lines")
+        );
+    }
 }
@@ -51,16 +51,113 @@ impl Drop for WakeLock {
     }
 }
 
-/// Attempts to acquire a wake lock, logging any failures.
+/// Whether the current platform has a wake lock backend.
+const SUPPORTED: bool = cfg!(any(
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "windows"
+));
+
+/// Observable wake lock state, shown as an icon in the command panel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WakeLockStatus {
+    Held,
+    NotHeld,
+    Unsupported,
+    Error(String),
+}
+
+/// Owns the wake lock and whether keep-awake is currently wanted.
 ///
-/// Returns `Some(WakeLock)` on success, or `None` if acquisition fails.
-/// Failures are logged at WARN level.
-pub fn acquire() -> Option<WakeLock> {
-    match WakeLock::new() {
-        Ok(lock) => Some(lock),
-        Err(e) => {
-            warn!(error = %e, "wake_lock_failed");
-            None
+/// `enabled` starts from `behavior.keep_awake` and can be flipped mid-run.
+pub struct WakeLockState {
+    lock: Option<WakeLock>,
+    enabled: bool,
+    error: Option<String>,
+}
+
+impl WakeLockState {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            lock: None,
+            enabled,
+            error: None,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Acquire the lock if keep-awake is enabled and it isn't already held.
+    ///
+    /// Returns `false` only when acquisition was attempted and failed.
+    pub fn acquire_if_enabled(&mut self) -> bool {
+        self.acquire_with(WakeLock::new)
+    }
+
+    fn acquire_with(&mut self, create: impl FnOnce() -> Result<WakeLock, WakeLockError>) -> bool {
+        if !self.enabled || self.lock.is_some() || !SUPPORTED {
+            return true;
+        }
+        match create() {
+            Ok(lock) => {
+                self.lock = Some(lock);
+                self.error = None;
+                true
+            }
+            Err(e) => {
+                warn!(error = %e, "wake_lock_failed");
+                self.error = Some(e.message);
+                false
+            }
+        }
+    }
+
+    /// Follow a changed `keep_awake` setting; takes effect from the next start.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.release();
+            self.error = None;
+        }
+    }
+
+    /// Release the lock (if held) without changing whether keep-awake is enabled.
+    pub fn release(&mut self) {
+        self.lock = None;
+    }
+
+    /// Flip keep-awake; acquires immediately when `running`, releases when disabled.
+    pub fn toggle(&mut self, running: bool) -> WakeLockStatus {
+        self.toggle_with(running, WakeLock::new)
+    }
+
+    fn toggle_with(
+        &mut self,
+        running: bool,
+        create: impl FnOnce() -> Result<WakeLock, WakeLockError>,
+    ) -> WakeLockStatus {
+        self.enabled = !self.enabled;
+        self.error = None;
+        if self.enabled && running {
+            self.acquire_with(create);
+        } else if !self.enabled {
+            self.release();
+        }
+        info!(enabled = self.enabled, "wake_lock_toggled");
+        self.status()
+    }
+
+    pub fn status(&self) -> WakeLockStatus {
+        if self.lock.is_some() {
+            WakeLockStatus::Held
+        } else if !SUPPORTED {
+            WakeLockStatus::Unsupported
+        } else if let Some(e) = &self.error {
+            WakeLockStatus::Error(e.clone())
+        } else {
+            WakeLockStatus::NotHeld
         }
     }
 }
@@ -69,6 +166,52 @@ pub fn acquire() -> Option<WakeLock> {
 mod tests {
     use super::*;
 
+    fn failing() -> Result<WakeLock, WakeLockError> {
+        Err(WakeLockError {
+            message: "no backend".to_string(),
+        })
+    }
+
+    #[test]
+    fn disabled_state_never_attempts_acquire() {
+        let mut state = WakeLockState::new(false);
+        assert!(state.acquire_with(|| panic!("should not be called")));
+        assert_eq!(state.status(), WakeLockStatus::NotHeld);
+    }
+
+    #[test]
+    fn failed_acquire_reports_error() {
+        let mut state = WakeLockState::new(true);
+        assert!(!state.acquire_with(failing));
+        assert_eq!(
+            state.status(),
+            WakeLockStatus::Error("no backend".to_string())
+        );
+    }
+
+    #[test]
+    fn toggle_off_clears_error_and_disables() {
+        let mut state = WakeLockState::new(true);
+        state.acquire_with(failing);
+        assert_eq!(state.toggle_with(true, failing), WakeLockStatus::NotHeld);
+        assert!(!state.enabled());
+    }
+
+    #[test]
+    fn toggle_on_while_stopped_defers_acquire() {
+        let mut state = WakeLockState::new(false);
+        let status = state.toggle_with(false, || panic!("should not be called"));
+        assert!(state.enabled());
+        assert_eq!(status, WakeLockStatus::NotHeld);
+    }
+
+    #[test]
+    fn toggle_on_while_running_attempts_acquire() {
+        let mut state = WakeLockState::new(false);
+        let status = state.toggle_with(true, failing);
+        assert!(matches!(status, WakeLockStatus::Error(_)));
+    }
+
     #[test]
     fn wake_lock_new_succeeds_on_current_platform() {
         let lock = WakeLock::new();
@@ -2,12 +2,22 @@
 
 use std::sync::Arc;
 use std::sync::mpsc::{self, TryRecvError};
-use std::time::Instant;
+use std::time::Duration;
 
+use ralph_core::run_loop::{
+    ExitAction, RemainingAction, RunExit, WorkRemaining, decide_exit, decide_remaining,
+    iteration_timeout,
+};
 use tracing::{info, warn};
 
 use crate::app::{App, AppStatus};
-use crate::work_source::WorkRemaining;
+use crate::disk_guard;
+use crate::execution;
+use crate::iteration_hook::{self, HookPhase, IterationHook, IterationHookError};
+use crate::ui::format_elapsed;
+
+/// How often the project directory is re-measured while held for its growth.
+const DISK_RECHECK_INTERVAL: Duration = Duration::from_secs(30);
 
 impl App {
     /// Handle a worker's channel disconnecting (process exited).
@@ -15,16 +25,65 @@ impl App {
     pub fn handle_channel_disconnected(&mut self, worker_idx: usize, exit_code: Option<i32>) {
         self.dirty = true;
         self.workers[worker_idx].output_receiver = None;
+        self.workers[worker_idx].control = None;
         self.current_bead = None;
         self.workers[worker_idx].run_start_time = None;
         // Release wake lock when no workers are active
         if !self.any_worker_active() {
-            self.wake_lock = None;
+            self.wake_lock.release();
+        }
+
+        let stopped = self.workers[worker_idx].stop_deadline.take().is_some();
+        let auth_error = self.workers[worker_idx].auth_error.take();
+        let result_error = self.workers[worker_idx].last_result_error.take();
+        let action = decide_exit(RunExit {
+            exit_code,
+            stopped,
+            auth_failed: auth_error.is_some(),
+            iterations_left: self.workers[worker_idx].should_auto_continue(),
+            result_error: result_error.as_deref(),
+            timed_out: self.workers[worker_idx].timed_out.take(),
+            continue_after_timeout: self.config.behavior.continue_after_timeout,
+        });
+        // Started first so the worker still counts as active while it runs
+        if !stopped
+            && action != ExitAction::StopForLogin
+            && let Some(command) = self.config.hooks.after_iteration.clone()
+        {
+            self.start_iteration_hook(worker_idx, HookPhase::After, &command);
         }
+        self.apply_exit_action(worker_idx, action, auth_error);
+    }
 
-        // Determine next state based on exit code and iteration control
-        match exit_code {
-            Some(0) if self.workers[worker_idx].should_auto_continue() => {
+    /// Carry out [`decide_exit`]'s decision for `worker_idx`.
+    fn apply_exit_action(
+        &mut self,
+        worker_idx: usize,
+        action: ExitAction,
+        auth_error: Option<String>,
+    ) {
+        match action {
+            ExitAction::Stop => {
+                self.workers[worker_idx].reset_iteration_state();
+            }
+            ExitAction::StopForLogin => {
+                self.workers[worker_idx].reset_iteration_state();
+                self.add_text_line(
+                    "[Claude CLI not authenticated \u{2014} run `claude login`]".to_string(),
+                );
+                for w in 0..self.workers.len() {
+                    if w != worker_idx && self.workers[w].child_process.is_some() {
+                        self.workers[w].kill_child();
+                        self.workers[w].reset_iteration_state();
+                        self.release_worker_hooked_bead(w);
+                    }
+                }
+                if !self.any_worker_active() {
+                    self.wake_lock.release();
+                }
+                self.auth_failure = auth_error;
+            }
+            ExitAction::CheckRemaining => {
                 // Kick off background check_remaining (non-blocking)
                 let complete_msg = self.work_source.complete_message();
                 let (tx, rx) = mpsc::channel();
@@ -34,19 +93,9 @@ impl App {
                 });
                 self.workers[worker_idx].pending_work_check = Some(rx);
             }
-            Some(0) => {
-                // Countdown exhausted or iterations = 0, just stop
-                self.workers[worker_idx].reset_iteration_state();
-            }
-            Some(code) => {
-                // Non-zero exit code → Error state — stop all workers
+            ExitAction::Fail { message } => {
                 self.workers[worker_idx].reset_iteration_state();
-                if let Some(ref msg) = self.workers[worker_idx].last_result_error {
-                    self.add_text_line(format!("[Error: {}]", msg));
-                } else {
-                    self.add_text_line(format!("[Error: process exited with code {}]", code));
-                }
-                self.workers[worker_idx].last_result_error = None;
+                self.add_text_line(message);
                 for w in 0..self.workers.len() {
                     if w != worker_idx && self.workers[w].child_process.is_some() {
                         self.workers[w].kill_child();
@@ -54,15 +103,153 @@ impl App {
                     }
                 }
                 self.status = AppStatus::Error;
-                self.error_at = Some(Instant::now());
+                self.error_at = Some(self.clock.now());
                 return;
             }
-            None => {
-                // Killed by signal (manual stop)
-                self.workers[worker_idx].reset_iteration_state();
+        }
+
+        self.update_derived_status();
+    }
+
+    /// Kill interrupted runs that haven't exited within the grace period.
+    pub fn poll_stop_deadlines(&mut self) {
+        let now = self.clock.now();
+        let mut killed = false;
+        for worker in &mut self.workers {
+            if worker.stop_deadline.is_some_and(|d| now >= d) {
+                warn!(worker = worker.id, "stop_grace_expired");
+                worker.kill_child();
+                worker.reset_iteration_state();
+                killed = true;
+            }
+        }
+        if killed {
+            self.dirty = true;
+            if !self.any_worker_active() {
+                self.wake_lock.release();
+            }
+            self.update_derived_status();
+        }
+    }
+
+    /// Kill runs that have gone past `behavior.iteration_timeout_secs`, then
+    /// carry on as if they had exited. Paused and stopping runs are left alone.
+    pub fn poll_iteration_timeouts(&mut self) {
+        let Some(limit) = iteration_timeout(self.config.behavior.iteration_timeout_secs) else {
+            return;
+        };
+        for w in 0..self.workers.len() {
+            let worker = &self.workers[w];
+            let expired = worker.child_process.is_some()
+                && worker.paused_at.is_none()
+                && worker.stop_deadline.is_none()
+                && worker
+                    .run_start_time
+                    .is_some_and(|start| self.clock.elapsed(start) >= limit);
+            if !expired {
+                continue;
+            }
+            warn!(
+                worker = w,
+                limit_secs = limit.as_secs(),
+                "iteration_timed_out"
+            );
+            if self.config.behavior.continue_after_timeout {
+                self.add_worker_line(
+                    w,
+                    ratatui::text::Line::from(format!(
+                        "[Iteration timed out after {}]",
+                        format_elapsed(limit)
+                    )),
+                );
+            }
+            self.workers[w].kill_child();
+            self.workers[w].timed_out = Some(limit);
+            self.handle_channel_disconnected(w, None);
+        }
+    }
+
+    /// Start a `[hooks]` command for worker `w` in its worktree (or the
+    /// project), announcing it in the worker's output.
+    pub fn start_iteration_hook(&mut self, w: usize, phase: HookPhase, command: &str) {
+        self.add_worker_line(
+            w,
+            ratatui::text::Line::from(iteration_hook::header(phase, command)),
+        );
+        let cwd = self.worker_dir(w);
+        let timeout = Duration::from_secs(self.config.hooks.timeout_secs);
+        match IterationHook::start(phase, command, Some(&cwd), timeout) {
+            Ok(hook) => self.workers[w].iteration_hook = Some(hook),
+            Err(e) => {
+                warn!(hook = phase.name(), error = %e, "iteration_hook_spawn_failed");
+                self.finish_iteration_hook(w, phase, Err(IterationHookError::Spawn(e)));
             }
         }
+    }
+
+    /// Start worker `w`'s `before_iteration` hook, or Claude straight away if
+    /// there is none.
+    pub fn begin_iteration(&mut self, w: usize) {
+        match self.config.hooks.before_iteration.clone() {
+            Some(command) => self.start_iteration_hook(w, HookPhase::Before, &command),
+            None => self.finish_iteration_hook(w, HookPhase::Before, Ok(())),
+        }
+    }
 
+    /// Show new output from running `[hooks]` commands and carry on once
+    /// they finish: Claude starts after a passing `before_iteration`, and
+    /// held auto-continues go ahead after `after_iteration`.
+    pub fn poll_iteration_hooks(&mut self) {
+        for w in 0..self.workers.len() {
+            let Some(hook) = &mut self.workers[w].iteration_hook else {
+                continue;
+            };
+            let phase = hook.phase;
+            let mut lines = Vec::new();
+            let outcome = hook.poll(&mut lines);
+            for line in lines {
+                self.dirty = true;
+                self.add_worker_line(
+                    w,
+                    ratatui::text::Line::from(iteration_hook::output_line(&line)),
+                );
+            }
+            if let Some(outcome) = outcome {
+                self.dirty = true;
+                self.workers[w].iteration_hook = None;
+                self.finish_iteration_hook(w, phase, outcome);
+            }
+        }
+    }
+
+    /// Carry on after worker `w`'s hook for `phase` finished with `outcome`.
+    fn finish_iteration_hook(
+        &mut self,
+        w: usize,
+        phase: HookPhase,
+        outcome: Result<(), IterationHookError>,
+    ) {
+        let prev = self.selected_worker;
+        self.selected_worker = w;
+        match (phase, outcome) {
+            (HookPhase::Before, Ok(())) => {
+                if let Err(e) = execution::launch_claude(self) {
+                    let message = format!("Error starting command: {e}");
+                    self.apply_exit_action(w, ExitAction::Fail { message }, None);
+                }
+            }
+            (HookPhase::Before, Err(e)) => {
+                warn!(worker = w, error = %e, "before_iteration_hook_failed");
+                let message = iteration_hook::failure_line(phase, &e);
+                self.apply_exit_action(w, ExitAction::Fail { message }, None);
+            }
+            (HookPhase::After, Ok(())) => {}
+            (HookPhase::After, Err(e)) => {
+                warn!(worker = w, error = %e, "after_iteration_hook_failed");
+                self.add_text_line(iteration_hook::failure_line(phase, &e));
+            }
+        }
+        self.selected_worker = prev;
         self.update_derived_status();
     }
 
@@ -105,47 +292,252 @@ impl App {
     /// Process a check_remaining result for auto-continue decisions.
     fn handle_work_remaining(&mut self, result: WorkRemaining, complete_msg: &str) {
         let w = self.selected_worker;
-        match result {
-            WorkRemaining::Yes => {
+        if let WorkRemaining::ReadError(e) = &result {
+            warn!(error = %e, "work_source_read_error");
+        }
+        match decide_remaining(result, complete_msg, &self.config.display.banners) {
+            RemainingAction::Continue => {
                 info!(
                     current = self.workers[w].current_iteration,
                     total = self.workers[w].total_iterations,
                     "auto_continue"
                 );
-                self.add_text_line(
-                    "══════════════════ AUTO-CONTINUING ══════════════════".to_string(),
-                );
+                let banners = &self.config.display.banners;
+                if !banners.auto_continue.is_empty() {
+                    self.add_text_line(banners.banner(&banners.auto_continue));
+                }
                 self.workers[w].auto_continue_pending = true;
             }
-            WorkRemaining::No => {
+            RemainingAction::Complete { banner } => {
                 info!("all_work_complete");
-                self.add_text_line(format!(
-                    "══════════════════ {} ══════════════════",
-                    complete_msg
-                ));
+                self.add_text_line(banner);
                 self.workers[w].reset_iteration_state();
                 self.update_derived_status();
             }
-            WorkRemaining::HumanOnly(count) => {
+            RemainingAction::HumanOnly { count, banner } => {
                 info!(count, "all_ready_beads_human_only");
-                self.add_text_line(format!(
-                    "══════════════════ no work for Ralph — {} {} available for humans ══════════════════",
-                    count,
-                    if count == 1 { "bead" } else { "beads" }
-                ));
+                self.fire_hook(
+                    crate::hooks::HookEvent::Blocked,
+                    serde_json::json!({ "human_beads": count }),
+                );
+                self.add_text_line(banner);
                 self.workers[w].reset_iteration_state();
                 self.update_derived_status();
             }
-            WorkRemaining::ReadError(e) => {
-                warn!(error = %e, "work_source_read_error");
-                self.add_text_line(format!("[Error reading work source: {}]", e));
+            RemainingAction::Fail { message } => {
+                self.add_text_line(message);
                 self.workers[w].reset_iteration_state();
                 self.status = AppStatus::Error;
-                self.error_at = Some(Instant::now());
+                self.error_at = Some(self.clock.now());
             }
         }
     }
 
+    /// Hold pending auto-continues while `schedule.quiet_hours` is active.
+    /// Returns `true` while held, in which case no new iteration should start;
+    /// the held iterations start on the first tick after the window ends.
+    pub fn hold_for_quiet_hours(&mut self) -> bool {
+        let in_quiet_hours = self
+            .config
+            .schedule
+            .in_quiet_hours_at(self.clock.minute_of_day());
+        self.set_quiet_hours_hold(in_quiet_hours)
+    }
+
+    pub(crate) fn set_quiet_hours_hold(&mut self, in_quiet_hours: bool) -> bool {
+        let paused = in_quiet_hours && self.workers.iter().any(|w| w.auto_continue_pending);
+        if paused != self.quiet_hours_paused {
+            self.quiet_hours_paused = paused;
+            self.dirty = true;
+            if paused {
+                info!("quiet_hours_pause");
+                self.add_text_line(self.config.display.banners.banner("PAUSED: QUIET HOURS"));
+            } else {
+                info!("quiet_hours_resume");
+            }
+        }
+        paused
+    }
+
+    /// Hold the auto-continue of any worker whose iteration ended with a
+    /// question until it is answered or skipped, opening the reply box when
+    /// the hold begins. Returns `true` while some worker is held; workers
+    /// without a question carry on.
+    pub fn hold_for_question(&mut self) -> bool {
+        let paused = self
+            .workers
+            .iter()
+            .any(|w| w.auto_continue_pending && w.question.is_some());
+        if paused != self.question_paused {
+            self.question_paused = paused;
+            self.dirty = true;
+            if paused {
+                info!("question_pause");
+                self.add_text_line(
+                    self.config
+                        .display
+                        .banners
+                        .banner("PAUSED: CLAUDE ASKED A QUESTION"),
+                );
+                self.open_question_reply();
+            } else {
+                info!("question_resume");
+                self.question_reply_state = None;
+            }
+        }
+        paused
+    }
+
+    /// Hold pending auto-continues while the last hour's spend exceeds
+    /// `budget.max_usd_per_hour`. Returns `true` while held; the held
+    /// iterations start once enough spend has aged out of the window.
+    pub fn hold_for_budget(&mut self) -> bool {
+        let pending = self.workers.iter().any(|w| w.auto_continue_pending);
+        let now = self.clock.now();
+        let delay = match self.config.budget.max_usd_per_hour {
+            Some(max) if pending => self.spend.throttle_delay(max, now),
+            _ => None,
+        };
+        let until = delay.map(|d| now + d);
+        if until.is_some() != self.budget_throttled_until.is_some() {
+            self.dirty = true;
+            if let Some(delay) = delay {
+                info!(delay_secs = delay.as_secs(), "budget_throttle");
+                self.add_text_line(self.config.display.banners.banner(&format!(
+                    "THROTTLED: BUDGET RATE — resuming in {}",
+                    format_elapsed(delay)
+                )));
+            } else {
+                info!("budget_throttle_end");
+            }
+        }
+        self.budget_throttled_until = until;
+        until.is_some()
+    }
+
+    /// Hold pending auto-continues while free disk space or the project
+    /// directory's growth this run is past a `[disk]` limit. Returns `true`
+    /// while held; the held iterations start once space is freed.
+    pub fn hold_for_disk_space(&mut self) -> bool {
+        if !self.workers.iter().any(|w| w.auto_continue_pending) {
+            return self.set_disk_hold(None);
+        }
+        let growth_limited = self.config.disk.max_growth_mb > 0;
+        if growth_limited && self.disk_guard.measuring() {
+            // The iteration's size measurement hasn't finished yet
+            return true;
+        }
+        let free = disk_guard::free_bytes(self.disk_guard.root());
+        let growth = self.disk_guard.growth().filter(|_| growth_limited);
+        let reason = disk_guard::exceeded(&self.config.disk, free, growth);
+        let now = self.clock.now();
+        if reason.is_some() && growth.is_some() && self.disk_recheck_at.is_none_or(|at| now >= at) {
+            self.disk_guard.measure();
+            self.disk_recheck_at = Some(now + DISK_RECHECK_INTERVAL);
+        }
+        self.set_disk_hold(reason)
+    }
+
+    pub(crate) fn set_disk_hold(&mut self, reason: Option<String>) -> bool {
+        let paused = reason.is_some();
+        if reason != self.disk_paused {
+            self.dirty = true;
+            match &reason {
+                Some(reason) if self.disk_paused.is_none() => {
+                    warn!(reason, "disk_pause");
+                    self.add_text_line(
+                        self.config
+                            .display
+                            .banners
+                            .banner(&format!("PAUSED: {reason}")),
+                    );
+                }
+                Some(_) => {}
+                None => {
+                    info!("disk_resume");
+                    self.disk_recheck_at = None;
+                }
+            }
+            self.disk_paused = reason;
+        }
+        paused
+    }
+
+    /// Drop iterations held for quiet hours, the budget rate or disk limits
+    /// (`S` while held). Returns `false` if nothing was held.
+    pub fn cancel_held_iterations(&mut self) -> bool {
+        if !self.quiet_hours_paused
+            && !self.question_paused
+            && self.budget_throttled_until.is_none()
+            && self.disk_paused.is_none()
+        {
+            return false;
+        }
+        for worker in &mut self.workers {
+            if worker.auto_continue_pending {
+                worker.auto_continue_pending = false;
+                worker.question = None;
+                worker.reset_iteration_state();
+            }
+        }
+        self.quiet_hours_paused = false;
+        self.question_paused = false;
+        self.question_reply_state = None;
+        self.budget_throttled_until = None;
+        self.disk_paused = None;
+        self.disk_recheck_at = None;
+        self.dirty = true;
+        info!("held_iterations_cancelled");
+        true
+    }
+
+    /// Stop the run at whatever stage it is in: interrupt running workers,
+    /// drop held iterations and forget pending checks for more work. Used
+    /// where nobody is at the keyboard to press `S` again.
+    pub fn stop_run(&mut self) {
+        self.stop_command();
+        self.cancel_held_iterations();
+        for worker in &mut self.workers {
+            if worker.pending_work_check.take().is_some() || worker.auto_continue_pending {
+                worker.auto_continue_pending = false;
+                worker.reset_iteration_state();
+            }
+        }
+        self.dirty = true;
+    }
+
+    /// Pause every running worker's Claude process (`P`), or resume them if
+    /// any is paused.
+    pub fn toggle_pause(&mut self) {
+        if self.status != AppStatus::Running
+            || self.workers.iter().any(|w| w.stop_deadline.is_some())
+        {
+            self.set_hint("Nothing running to pause");
+            return;
+        }
+        let now = self.clock.now();
+        let message = if self.workers.iter().any(|w| w.paused_at.is_some()) {
+            for worker in &mut self.workers {
+                worker.resume(now);
+            }
+            "Resumed"
+        } else {
+            let mut paused = false;
+            for worker in &mut self.workers {
+                paused |= worker.pause(now);
+            }
+            if paused {
+                "Paused \u{2014} press P to resume"
+            } else if cfg!(unix) {
+                "Nothing running to pause"
+            } else {
+                "Pausing is only supported on Unix"
+            }
+        };
+        self.dirty = true;
+        self.set_hint(message);
+    }
+
     /// Start a new iteration run, reading config and setting up iteration tracking for all workers.
     /// Returns false if iterations = 0 (stopped mode).
     pub fn start_iteration_run(&mut self) -> bool {
@@ -160,6 +552,69 @@ impl App {
             worker.total_iterations = iterations;
             worker.current_iteration = 1;
         }
+        if self.config.disk.max_growth_mb > 0 {
+            self.disk_guard.start_run();
+        }
+        self.refresh_estimate_history();
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_hooks(app: &mut App) {
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        while app.workers[0].iteration_hook.is_some() {
+            assert!(std::time::Instant::now() < deadline, "hook never finished");
+            app.poll_iteration_hooks();
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    fn output_text(app: &App) -> Vec<String> {
+        app.workers[0]
+            .output_lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn failing_before_hook_aborts_the_iteration() {
+        let mut app = App::new(
+            "test".into(),
+            None,
+            crate::config::LoadedConfig::default_for_test(),
+            None,
+        );
+        app.start_iteration_hook(0, HookPhase::Before, "echo pulling; exit 1");
+        assert!(app.any_worker_active());
+        run_hooks(&mut app);
+        assert_eq!(app.status, AppStatus::Error);
+        assert!(app.workers[0].child_process.is_none());
+        let text = output_text(&app);
+        assert!(text.contains(&"  pulling".to_string()), "{text:?}");
+        assert!(text.contains(
+            &"[before_iteration failed (exit code 1) \u{2014} iteration aborted]".to_string()
+        ));
+    }
+
+    #[test]
+    fn after_hook_holds_auto_continue_and_failure_is_reported() {
+        let mut app = App::new(
+            "test".into(),
+            None,
+            crate::config::LoadedConfig::default_for_test(),
+            None,
+        );
+        app.status = AppStatus::Running;
+        app.start_iteration_hook(0, HookPhase::After, "exit 2");
+        app.update_derived_status();
+        assert_eq!(app.status, AppStatus::Running);
+        run_hooks(&mut app);
+        assert_eq!(app.status, AppStatus::Stopped);
+        assert!(output_text(&app).contains(&"[after_iteration failed (exit code 2)]".to_string()));
+    }
+}
@@ -1,20 +1,10 @@
 //! Beads-based work source for ralph's core loop.
 
-use std::process::Command;
 use std::time::{Duration, SystemTime};
 
-/// Result of checking if there's remaining work.
-#[derive(Debug, PartialEq)]
-pub enum WorkRemaining {
-    /// There are work items with active status.
-    Yes,
-    /// All work items are done or blocked.
-    No,
-    /// All ready beads are for humans only (human label).
-    HumanOnly(usize),
-    /// Error reading the work source.
-    ReadError(String),
-}
+use crate::bd::Bd;
+
+pub use ralph_core::run_loop::WorkRemaining;
 
 /// Status of a work item.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -40,12 +30,12 @@ pub struct WorkItem {
 
 /// Work source backed by the `bd` CLI for bead-based workflows.
 pub struct BeadsWorkSource {
-    bd_path: String,
+    bd: Bd,
 }
 
 impl BeadsWorkSource {
-    pub fn new(bd_path: String) -> Self {
-        Self { bd_path }
+    pub fn new(bd: Bd) -> Self {
+        Self { bd }
     }
 
     /// Timeout for bd commands.
@@ -58,7 +48,9 @@ impl BeadsWorkSource {
         // against every other ralph-initiated bd call. The guard drops when this
         // function returns (success, timeout, or error).
         let _bd_guard = crate::bd_lock::acquire();
-        let mut child = match Command::new(&self.bd_path)
+        let mut child = match self
+            .bd
+            .command()
             .args(args)
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
@@ -67,7 +59,7 @@ impl BeadsWorkSource {
         {
             Ok(child) => child,
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                return Err(format!("{}: command not found", self.bd_path));
+                return Err(format!("{}: command not found", self.bd.path()));
             }
             Err(e) => return Err(format!("failed to run bd: {}", e)),
         };
@@ -1,22 +1,23 @@
-use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
-use std::process::{Child, Command, Stdio};
+use std::process::{Child, Stdio};
 use std::sync::mpsc::{self, Receiver, TryRecvError};
-use std::time::Instant;
 
-use tracing::{debug, info};
+use ralph_core::output::OutputMessage;
+use tracing::{debug, info, warn};
 
 use crate::agent;
 use crate::app::{App, AppStatus};
+use crate::bd::Bd;
+use crate::claude_cli::Capabilities;
 use crate::config::Config;
+use crate::control::{self, ControlChannel};
 use crate::execution;
-use crate::output::OutputMessage;
+use crate::session_lock::{self, AcquireError};
 use crate::startup::has_ready_children;
-use crate::wake_lock;
 
 struct WorkerStartSnapshot {
     worker_index: usize,
-    bd_path: String,
+    bd: Bd,
     stale_threshold: u64,
     agent_bead_id: Option<String>,
     claimed_epic_id: Option<String>,
@@ -24,6 +25,8 @@ struct WorkerStartSnapshot {
     worktree_name: Option<String>,
     worktree_path: Option<PathBuf>,
     has_output: bool,
+    /// Queued operator instructions for this iteration's prompt.
+    instructions: Vec<String>,
     config: Config,
 }
 
@@ -36,7 +39,15 @@ pub(crate) struct WorkerStartResult {
     pub child_process: Option<Child>,
     pub output_receiver: Option<Receiver<OutputMessage>>,
     pub output_lines: Vec<String>,
+    pub prompt_context: Option<String>,
+    pub output_caps: Capabilities,
+    pub control: Option<ControlChannel>,
+    /// ID and title of the previously hooked bead, if Claude closed it.
+    pub completed_spec: Option<(String, String)>,
     pub error: Option<String>,
+    /// Claude waits for the `before_iteration` hook, which runs on the main
+    /// thread so its output streams and `S` can stop it.
+    pub awaiting_before_hook: bool,
 }
 
 fn run_worker_startups(snapshots: Vec<WorkerStartSnapshot>) -> Vec<WorkerStartResult> {
@@ -46,21 +57,34 @@ fn run_worker_startups(snapshots: Vec<WorkerStartSnapshot>) -> Vec<WorkerStartRe
         .collect()
 }
 
+impl WorkerStartResult {
+    /// Nothing done yet for the worker in `snapshot`.
+    fn new(snapshot: &WorkerStartSnapshot) -> Self {
+        Self {
+            worker_index: snapshot.worker_index,
+            claimed_epic_id: snapshot.claimed_epic_id.clone(),
+            hooked_bead_id: None,
+            worktree_name: snapshot.worktree_name.clone(),
+            worktree_path: snapshot.worktree_path.clone(),
+            child_process: None,
+            output_receiver: None,
+            output_lines: Vec::new(),
+            prompt_context: None,
+            output_caps: Capabilities::default(),
+            control: None,
+            completed_spec: None,
+            error: None,
+            awaiting_before_hook: false,
+        }
+    }
+}
+
 fn run_single_worker_startup(snapshot: WorkerStartSnapshot) -> WorkerStartResult {
-    let mut result = WorkerStartResult {
-        worker_index: snapshot.worker_index,
-        claimed_epic_id: snapshot.claimed_epic_id.clone(),
-        hooked_bead_id: None,
-        worktree_name: snapshot.worktree_name.clone(),
-        worktree_path: snapshot.worktree_path.clone(),
-        child_process: None,
-        output_receiver: None,
-        output_lines: Vec::new(),
-        error: None,
-    };
+    let mut result = WorkerStartResult::new(&snapshot);
 
     if let (Some(agent_id), Some(bead_id)) = (&snapshot.agent_bead_id, &snapshot.hooked_bead_id) {
-        agent::release_bead(&snapshot.bd_path, agent_id, bead_id);
+        result.completed_spec = agent::release_bead(&snapshot.bd, agent_id, bead_id)
+            .map(|title| (bead_id.clone(), title));
     }
 
     if !merge_and_refresh_bg(&snapshot, &mut result) {
@@ -79,14 +103,14 @@ fn run_single_worker_startup(snapshot: WorkerStartSnapshot) -> WorkerStartResult
 }
 
 fn merge_and_refresh_bg(snapshot: &WorkerStartSnapshot, result: &mut WorkerStartResult) -> bool {
-    let bd_path = &snapshot.bd_path;
+    let bd = &snapshot.bd;
 
     let has_epic = result.claimed_epic_id.is_some();
     let has_children = has_epic
         && result
             .claimed_epic_id
             .as_ref()
-            .is_some_and(|eid| has_ready_children(bd_path, eid));
+            .is_some_and(|eid| has_ready_children(bd, eid));
 
     match agent::decide_iteration_action(has_epic, has_children) {
         agent::IterationAction::ContinueInEpic => return true,
@@ -96,10 +120,10 @@ fn merge_and_refresh_bg(snapshot: &WorkerStartSnapshot, result: &mut WorkerStart
             result
                 .output_lines
                 .push(format!("[Completing epic: {}]", epic_id));
-            agent::complete_epic(bd_path, &epic_id);
+            agent::complete_epic(bd, &epic_id);
             result.claimed_epic_id = None;
             let _ = crate::bd_lock::with_lock(|| {
-                Command::new(bd_path)
+                bd.command()
                     .args(["set-state", &agent_id, "epic=none"])
                     .stdin(Stdio::null())
                     .stdout(Stdio::null())
@@ -111,19 +135,19 @@ fn merge_and_refresh_bg(snapshot: &WorkerStartSnapshot, result: &mut WorkerStart
     }
 
     if let Some(wt_name) = result.worktree_name.clone() {
-        if agent::merge_worktree_to_main(&wt_name) {
-            agent::remove_merged_worktree(bd_path, &wt_name);
+        if agent::merge_worktree_to_main(bd.dir(), &wt_name) {
+            agent::remove_merged_worktree(bd, &wt_name);
             result.worktree_name = None;
             result.worktree_path = None;
-        } else if let Some(existing_bead_id) = agent::find_merge_conflict_bead(bd_path, &wt_name) {
-            agent::escalate_merge_conflict(bd_path, &wt_name, &existing_bead_id);
+        } else if let Some(existing_bead_id) = agent::find_merge_conflict_bead(bd, &wt_name) {
+            agent::escalate_merge_conflict(bd, &wt_name, &existing_bead_id);
             result.output_lines.push(
                 "[Merge conflict persists after Claude attempt — filed human bead, stopping]"
                     .to_string(),
             );
             result.error = Some("Merge conflict".to_string());
             return false;
-        } else if let Some(bead_id) = agent::file_merge_conflict_bead(bd_path, &wt_name) {
+        } else if let Some(bead_id) = agent::file_merge_conflict_bead(bd, &wt_name) {
             result.output_lines.push(format!(
                 "[Merge conflict — filed {}, Claude will resolve next iteration]",
                 bead_id
@@ -153,12 +177,12 @@ fn claim_before_start_bg(snapshot: &WorkerStartSnapshot, result: &mut WorkerStar
         None => return,
     };
 
-    let bd_path = &snapshot.bd_path;
+    let bd = &snapshot.bd;
 
-    let stale_agents = agent::find_stale_agents(bd_path, snapshot.stale_threshold, Some(&agent_id));
+    let stale_agents = agent::find_stale_agents(bd, snapshot.stale_threshold, Some(&agent_id));
     if !stale_agents.is_empty() {
         let first = &stale_agents[0];
-        match agent::resume_stale_bead(bd_path, &agent_id, first) {
+        match agent::resume_stale_bead(bd, &agent_id, first) {
             agent::ResumeResult::Resumed => {
                 result.output_lines.push(format!(
                     "[Auto-reclaimed: {} \"{}\"]",
@@ -166,7 +190,7 @@ fn claim_before_start_bg(snapshot: &WorkerStartSnapshot, result: &mut WorkerStar
                 ));
                 result.hooked_bead_id = Some(first.hooked_bead_id.clone());
                 for stale in stale_agents.iter().skip(1) {
-                    agent::release_stale_bead(bd_path, stale);
+                    agent::release_stale_bead(bd, stale);
                     result.output_lines.push(format!(
                         "[Released stale: {} \"{}\"]",
                         stale.hooked_bead_id, stale.hooked_bead_title
@@ -180,7 +204,7 @@ fn claim_before_start_bg(snapshot: &WorkerStartSnapshot, result: &mut WorkerStar
                     first.hooked_bead_id, first.hooked_bead_title
                 ));
                 for stale in stale_agents.iter().skip(1) {
-                    agent::release_stale_bead(bd_path, stale);
+                    agent::release_stale_bead(bd, stale);
                     result.output_lines.push(format!(
                         "[Released stale: {} \"{}\"]",
                         stale.hooked_bead_id, stale.hooked_bead_title
@@ -193,7 +217,7 @@ fn claim_before_start_bg(snapshot: &WorkerStartSnapshot, result: &mut WorkerStar
 
     if let Some(ref epic_id) = result.claimed_epic_id {
         let epic_id = epic_id.clone();
-        match agent::claim_next_child(bd_path, &agent_id, &epic_id) {
+        match agent::claim_next_child(bd, &agent_id, &epic_id) {
             Some((child_id, child_title)) => {
                 result.output_lines.push(format!(
                     "[Claimed child: {} {} (epic: {})]",
@@ -212,7 +236,7 @@ fn claim_before_start_bg(snapshot: &WorkerStartSnapshot, result: &mut WorkerStar
         }
     }
 
-    match agent::select_and_claim_work(bd_path, &agent_id) {
+    match agent::select_and_claim_work(bd, &agent_id) {
         Some(claim) => {
             match &claim.epic_id {
                 Some(epic_id) => result.output_lines.push(format!(
@@ -249,8 +273,8 @@ fn ensure_worktree_bg(snapshot: &WorkerStartSnapshot, result: &mut WorkerStartRe
         None => return true,
     };
 
-    let bd_path = &snapshot.bd_path;
-    if let Some((new_name, new_path)) = agent::create_or_reuse_worktree(bd_path, &worktree_name) {
+    let bd = &snapshot.bd;
+    if let Some((new_name, new_path)) = agent::create_or_reuse_worktree(bd, &worktree_name) {
         result.worktree_name = Some(new_name);
         result.worktree_path = Some(new_path);
         true
@@ -264,8 +288,15 @@ fn ensure_worktree_bg(snapshot: &WorkerStartSnapshot, result: &mut WorkerStartRe
 }
 
 fn start_command_bg(snapshot: &WorkerStartSnapshot, result: &mut WorkerStartResult) {
-    if snapshot.has_output {
-        result.output_lines.push("─".repeat(40));
+    let divider = &snapshot.config.display.banners.divider;
+    if snapshot.has_output && !divider.is_empty() {
+        result.output_lines.push(divider.clone());
+    }
+
+    // Claude starts once the hook passes (see poll_worker_start)
+    if snapshot.config.hooks.before_iteration.is_some() {
+        result.awaiting_before_hook = true;
+        return;
     }
 
     let dirty_context = result
@@ -274,63 +305,39 @@ fn start_command_bg(snapshot: &WorkerStartSnapshot, result: &mut WorkerStartResu
         .and_then(agent::check_worktree_dirty)
         .map(|(status, diff)| agent::build_dirty_worktree_context(&status, &diff));
 
-    let command = match execution::assemble_prompt(
+    // A new run always starts a fresh conversation
+    let assembled = match execution::assemble_prompt(
         &snapshot.config,
+        &snapshot.bd,
         result.hooked_bead_id.as_deref(),
         dirty_context,
+        &snapshot.instructions,
+        None,
     ) {
-        Ok(cmd) => cmd,
+        Ok(assembled) => assembled,
         Err(e) => {
             result.error = Some(format!("Error assembling prompt: {}", e));
             return;
         }
     };
-
-    let mut cmd = Command::new("sh");
-    cmd.arg("-c")
-        .arg(&command)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
-
-    if let Some(ref wt_path) = result.worktree_path {
-        cmd.current_dir(wt_path);
+    if let Some(note) = &assembled.prompt_note {
+        result.output_lines.push(note.clone());
     }
 
-    match cmd.spawn() {
-        Ok(mut child) => {
+    match assembled.spawn(
+        snapshot.config.network.child_env(),
+        Some(result.worktree_path.as_deref().unwrap_or(snapshot.bd.dir())),
+    ) {
+        Ok((mut child, rx)) => {
             debug!(pid = child.id(), "command_spawned");
 
-            let (tx, rx) = mpsc::channel();
-
-            if let Some(stdout) = child.stdout.take() {
-                let tx_stdout = tx.clone();
-                std::thread::spawn(move || {
-                    let reader = BufReader::new(stdout);
-                    for line in reader.lines().map_while(Result::ok) {
-                        if tx_stdout.send(OutputMessage::Line(line)).is_err() {
-                            break;
-                        }
-                    }
-                });
-            }
-
-            if let Some(stderr) = child.stderr.take() {
-                let tx_stderr = tx.clone();
-                std::thread::spawn(move || {
-                    let reader = BufReader::new(stderr);
-                    for line in reader.lines().map_while(Result::ok) {
-                        if tx_stderr
-                            .send(OutputMessage::Line(format!("[stderr] {}", line)))
-                            .is_err()
-                        {
-                            break;
-                        }
-                    }
-                });
+            if assembled.control {
+                result.control = control::open(&mut child, &assembled.context);
             }
-
             result.child_process = Some(child);
             result.output_receiver = Some(rx);
+            result.prompt_context = Some(assembled.context);
+            result.output_caps = assembled.caps;
         }
         Err(e) => {
             result.error = Some(format!("Error starting command: {}", e));
@@ -344,14 +351,44 @@ impl App {
             return;
         }
 
+        if self.session_lock.is_none() {
+            match session_lock::acquire(&self.project_dir, &self.session_id) {
+                Ok(lock) => self.session_lock = Some(lock),
+                Err(AcquireError::Held(info)) => {
+                    self.lock_conflict = Some(info);
+                    return;
+                }
+                Err(AcquireError::Io(e)) => {
+                    warn!(error = %e, "session_lock_unavailable");
+                }
+            }
+        }
+
+        let changes = crate::dirty_tree::uncommitted_changes(&self.project_dir);
+        if !changes.is_empty() {
+            if self.config.behavior.confirm_dirty_start {
+                info!(changes = changes.len(), "dirty_tree_prompt");
+                self.dirty_start_prompt = Some(changes);
+                return;
+            }
+            self.warn_dirty_tree(changes.len());
+        }
+        self.start_workers();
+    }
+
+    /// Start a run once the session lock is held and the tree has been checked.
+    pub fn start_workers(&mut self) {
         if !self.start_iteration_run() {
             return;
         }
+        self.project_summary = None;
 
+        let instructions = self.instruction_queue.pending();
+        self.starting_instructions = instructions.len();
         let snapshots: Vec<_> = (0..self.workers.len())
             .map(|w| WorkerStartSnapshot {
                 worker_index: w,
-                bd_path: self.config.behavior.bd_path.clone(),
+                bd: self.bd(),
                 stale_threshold: self.config.behavior.stale_threshold,
                 agent_bead_id: self.workers[w].agent_bead_id.clone(),
                 claimed_epic_id: self.workers[w].claimed_epic_id.clone(),
@@ -359,6 +396,7 @@ impl App {
                 worktree_name: self.workers[w].worktree_name.clone(),
                 worktree_path: self.workers[w].worktree_path.clone(),
                 has_output: !self.workers[w].output_lines.is_empty(),
+                instructions: instructions.clone(),
                 config: self.config.clone(),
             })
             .collect();
@@ -373,6 +411,14 @@ impl App {
         self.status = AppStatus::Starting;
     }
 
+    /// Note in the output that a run is starting over uncommitted changes.
+    pub fn warn_dirty_tree(&mut self, changes: usize) {
+        warn!(changes, "dirty_tree_start");
+        self.add_text_line(format!(
+            "⚠ Starting with {changes} uncommitted change(s) — the agent may commit on top of them"
+        ));
+    }
+
     pub fn poll_worker_start(&mut self) {
         let rx = match self.start_workers_rx.take() {
             Some(rx) => rx,
@@ -383,11 +429,15 @@ impl App {
             Ok(results) => {
                 self.dirty = true;
                 let mut any_started = false;
+                let mut awaiting_hook = Vec::new();
 
                 for result in results {
                     let w = result.worker_index;
                     self.selected_worker = w;
 
+                    if let Some((bead_id, title)) = &result.completed_spec {
+                        self.announce_spec_done(w, bead_id, title);
+                    }
                     for line in result.output_lines {
                         self.add_text_line(line);
                     }
@@ -399,12 +449,39 @@ impl App {
 
                     if result.error.is_some() {
                         self.workers[w].reset_iteration_state();
+                    } else if result.awaiting_before_hook {
+                        self.begin_iteration_section(w);
+                        self.workers[w].content_blocks.clear();
+                        self.workers[w].current_line.clear();
+                        self.workers[w].tool_preview_line = None;
+                        self.workers[w].iteration_files = Default::default();
+                        self.workers[w].claude_session_id = None;
+                        self.workers[w].start_fresh = true;
+                        self.loop_count += 1;
+                        info!(loop_number = self.loop_count, "loop_start");
+                        awaiting_hook.push(w);
                     } else if result.child_process.is_some() {
+                        self.begin_iteration_section(w);
                         self.workers[w].content_blocks.clear();
                         self.workers[w].current_line.clear();
+                        self.workers[w].tool_preview_line = None;
+                        self.workers[w].iteration_files = Default::default();
+                        self.workers[w].claude_session_id = None;
+                        self.workers[w].continued_conversation = false;
+                        if let Some(context) = result.prompt_context {
+                            self.mark_prompt_used(w, &context);
+                            self.add_line(crate::ui::format_prompt_context_styled(
+                                &context,
+                                false,
+                                &crate::number_format::NumberFormat::from(&self.config.display),
+                            ));
+                            self.workers[w].prompt_context = Some(context);
+                        }
+                        self.set_output_capabilities(w, result.output_caps);
                         self.workers[w].child_process = result.child_process;
+                        self.workers[w].control = result.control;
                         self.workers[w].output_receiver = result.output_receiver;
-                        self.workers[w].run_start_time = Some(Instant::now());
+                        self.workers[w].run_start_time = Some(self.clock.now());
                         self.loop_count += 1;
                         info!(loop_number = self.loop_count, "loop_start");
                         any_started = true;
@@ -415,18 +492,23 @@ impl App {
                 self.selected_worker = 0;
 
                 if any_started {
-                    if self.config.behavior.keep_awake {
-                        self.wake_lock = wake_lock::acquire();
-                        if self.wake_lock.is_none() {
-                            self.add_text_line(
-                                "⚠ Warning: Could not acquire wake lock - system may sleep during execution"
-                                    .to_string(),
-                            );
-                        }
+                    let delivered = std::mem::take(&mut self.starting_instructions);
+                    self.deliver_instructions(delivered);
+                    if !self.wake_lock.acquire_if_enabled() {
+                        self.add_text_line(
+                            "⚠ Warning: Could not acquire wake lock - system may sleep during execution"
+                                .to_string(),
+                        );
                     }
                     self.status = AppStatus::Running;
-                } else {
+                } else if awaiting_hook.is_empty() {
                     self.status = AppStatus::Stopped;
+                } else {
+                    self.status = AppStatus::Running;
+                }
+                // Instructions go out with the prompt once Claude launches
+                for w in awaiting_hook {
+                    self.begin_iteration(w);
                 }
             }
             Err(TryRecvError::Empty) => {
@@ -441,7 +523,7 @@ impl App {
                     worker.reset_iteration_state();
                 }
                 self.status = AppStatus::Error;
-                self.error_at = Some(Instant::now());
+                self.error_at = Some(self.clock.now());
             }
         }
     }
@@ -450,6 +532,7 @@ impl App {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::process::Command;
 
     fn app_with_workers(n: u32) -> App {
         use crate::config::LoadedConfig;
@@ -474,7 +557,12 @@ mod tests {
             child_process: Some(child),
             output_receiver: Some(output_rx),
             output_lines: vec!["[Claimed epic: epic-1]".to_string()],
+            prompt_context: Some("You are Ralph.".to_string()),
+            output_caps: Capabilities::default(),
+            control: None,
+            completed_spec: None,
             error: None,
+            awaiting_before_hook: false,
         };
 
         let (tx, rx) = mpsc::channel();
@@ -490,10 +578,89 @@ mod tests {
         assert_eq!(app.workers[0].claimed_epic_id.as_deref(), Some("epic-1"));
         assert_eq!(app.workers[0].hooked_bead_id.as_deref(), Some("bead-1"));
         assert!(app.workers[0].child_process.is_some());
+        assert_eq!(
+            app.workers[0].prompt_context.as_deref(),
+            Some("You are Ralph.")
+        );
 
         app.workers[0].kill_child();
     }
 
+    #[test]
+    fn before_hook_of_a_new_run_streams_on_the_main_thread() {
+        let mut app = app_with_workers(1);
+        app.config.hooks.before_iteration = Some("echo pulling; exit 1".to_string());
+        app.workers[0].total_iterations = 5;
+        app.workers[0].current_iteration = 1;
+
+        let result = WorkerStartResult {
+            worker_index: 0,
+            claimed_epic_id: None,
+            hooked_bead_id: None,
+            worktree_name: None,
+            worktree_path: None,
+            child_process: None,
+            output_receiver: None,
+            output_lines: Vec::new(),
+            prompt_context: None,
+            output_caps: Capabilities::default(),
+            control: None,
+            completed_spec: None,
+            error: None,
+            awaiting_before_hook: true,
+        };
+        let (tx, rx) = mpsc::channel();
+        tx.send(vec![result]).unwrap();
+        app.status = AppStatus::Starting;
+        app.start_workers_rx = Some(rx);
+
+        app.poll_worker_start();
+        assert_eq!(app.status, AppStatus::Running);
+        assert!(app.workers[0].iteration_hook.is_some());
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while app.workers[0].iteration_hook.is_some() && std::time::Instant::now() < deadline {
+            app.poll_iteration_hooks();
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        assert!(app.workers[0].iteration_hook.is_none());
+        assert_eq!(app.status, AppStatus::Error);
+        assert!(app.workers[0].child_process.is_none());
+    }
+
+    #[test]
+    fn background_start_uses_the_configured_divider() {
+        let mut app = app_with_workers(1);
+        // Stops start_command_bg before it spawns Claude
+        app.config.hooks.before_iteration = Some("true".to_string());
+        let snapshot = |config: &Config, has_output| WorkerStartSnapshot {
+            worker_index: 0,
+            bd: app.bd(),
+            stale_threshold: 0,
+            agent_bead_id: None,
+            claimed_epic_id: None,
+            hooked_bead_id: None,
+            worktree_name: None,
+            worktree_path: None,
+            has_output,
+            instructions: Vec::new(),
+            config: config.clone(),
+        };
+        let lines = |snapshot: WorkerStartSnapshot| {
+            let mut result = WorkerStartResult::new(&snapshot);
+            start_command_bg(&snapshot, &mut result);
+            assert!(result.awaiting_before_hook);
+            result.output_lines
+        };
+
+        let mut config = app.config.clone();
+        config.display.banners.divider = "~~~~".to_string();
+        assert_eq!(lines(snapshot(&config, true)), ["~~~~"]);
+        assert!(lines(snapshot(&config, false)).is_empty());
+        config.display.banners.divider = String::new();
+        assert!(lines(snapshot(&config, true)).is_empty());
+    }
+
     #[test]
     fn starting_to_stopped_on_error() {
         let mut app = app_with_workers(1);
@@ -509,7 +676,12 @@ mod tests {
             child_process: None,
             output_receiver: None,
             output_lines: vec!["[Merge conflict]".to_string()],
+            prompt_context: None,
+            output_caps: Capabilities::default(),
+            control: None,
+            completed_spec: None,
             error: Some("Merge conflict".to_string()),
+            awaiting_before_hook: false,
         };
 
         let (tx, rx) = mpsc::channel();